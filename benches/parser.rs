@@ -0,0 +1,198 @@
+//! Throughput of the lexer in tokens per second and of the parser in statements per second,
+//! and how long parsing a large file again after an edit takes, compared to parsing it
+//! from scratch.
+//!
+//! Real-world files are read from the checkouts made by `tests/third_party_tests.rs` under
+//! `target/third-party`, and are skipped if those tests have not been run. The fixtures
+//! that parse are always measured, together as one input.
+//!
+//! ```sh
+//! cargo test --release --test third_party_tests -- symfony laravel wordpress
+//! cargo bench --bench parser
+//! ```
+
+use std::fs;
+use std::path::PathBuf;
+
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+use php_parser_rs::construct;
+use php_parser_rs::lexer::token::Token;
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parse;
+use php_parser_rs::parse_bench;
+use php_parser_rs::parser::incremental::reparse;
+use php_parser_rs::parser::incremental::TextEdit;
+use php_parser_rs::ParserConfig;
+
+const REAL_WORLD: &[(&str, &str)] = &[
+    (
+        "symfony",
+        "symfony-framework/src/Symfony/Component/HttpFoundation/Request.php",
+    ),
+    (
+        "laravel",
+        "laravel-framework/src/Illuminate/Database/Query/Builder.php",
+    ),
+    ("wordpress", "wordpress/wp-includes/class-wp-query.php"),
+];
+
+struct Input {
+    name: String,
+    files: Vec<Vec<u8>>,
+    tokens: u64,
+    statements: u64,
+    bytes: u64,
+}
+
+impl Input {
+    fn new(name: &str, files: Vec<Vec<u8>>) -> Self {
+        let mut input = Self {
+            name: name.to_string(),
+            files: vec![],
+            tokens: 0,
+            statements: 0,
+            bytes: 0,
+        };
+
+        for file in files {
+            // files that don't parse would only measure how fast errors are reported.
+            if let Ok(result) = parse_bench(&file) {
+                input.tokens += result.tokens as u64;
+                input.statements += result.statements as u64;
+                input.bytes += result.bytes as u64;
+                input.files.push(file);
+            }
+        }
+
+        input
+    }
+}
+
+fn inputs() -> Vec<Input> {
+    let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(manifest.join("tests/fixtures"))
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.path().join("code.php"))
+        .collect();
+    fixtures.sort();
+
+    let mut inputs = vec![Input::new(
+        "fixtures",
+        fixtures.iter().flat_map(fs::read).collect(),
+    )];
+
+    for (name, path) in REAL_WORLD {
+        let path = manifest.join("target/third-party").join(path);
+        match fs::read(&path) {
+            Ok(code) => inputs.push(Input::new(name, vec![code])),
+            Err(_) => eprintln!("skipping {}: {} does not exist", name, path.display()),
+        }
+    }
+
+    inputs.retain(|input| !input.files.is_empty());
+    inputs
+}
+
+fn benchmarks(c: &mut Criterion) {
+    let inputs = inputs();
+    let lexer = Lexer::new();
+
+    let mut group = c.benchmark_group("lex");
+    for input in &inputs {
+        group.throughput(Throughput::Elements(input.tokens));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(&input.name),
+            input,
+            |b, input| {
+                b.iter(|| {
+                    for file in &input.files {
+                        black_box(lexer.tokenize(file).unwrap());
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("parse");
+    for input in &inputs {
+        let tokens: Vec<Vec<Token>> = input
+            .files
+            .iter()
+            .map(|file| lexer.tokenize(file).unwrap())
+            .collect();
+
+        group.throughput(Throughput::Elements(input.statements));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(&input.name),
+            &tokens,
+            |b, tokens| {
+                b.iter(|| {
+                    for tokens in tokens {
+                        black_box(construct(tokens).unwrap());
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("lex and parse");
+    for input in &inputs {
+        group.throughput(Throughput::Bytes(input.bytes));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(&input.name),
+            input,
+            |b, input| {
+                b.iter(|| {
+                    for file in &input.files {
+                        black_box(parse(file).unwrap());
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+// A file of 4,000 lines in a namespace, which is edited in one of its functions.
+fn incremental(c: &mut Criterion) {
+    let mut code = String::from("<?php\n\nnamespace App;\n\n");
+    for i in 0..1_000 {
+        code.push_str(&format!(
+            "function f{}(int $a): int {{\n    return $a + {};\n}}\n\n",
+            i, i
+        ));
+    }
+
+    let previous = parse(&code).unwrap();
+
+    let mut group = c.benchmark_group("reparse");
+    group.bench_function("from scratch", |b| {
+        b.iter(|| black_box(parse(&code).unwrap()))
+    });
+
+    for (name, function) in [("start", 0), ("middle", 500), ("end", 999)] {
+        let offset = code.find(&format!("+ {};", function)).unwrap();
+        let edit = TextEdit::new(offset..offset + 1, "-");
+
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                black_box(
+                    reparse(&previous, code.as_bytes(), &edit, ParserConfig::default()).unwrap(),
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, benchmarks, incremental);
+criterion_main!(benches);
@@ -0,0 +1,52 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+// Hash the lexer and parser sources, so that `VERSION_INFO` changes whenever the
+// grammar or the shape of the AST may have changed.
+fn main() {
+    let root = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+
+    let mut files = vec![];
+    for directory in ["src/lexer", "src/parser"] {
+        println!("cargo:rerun-if-changed={}", directory);
+        collect(&root, &root.join(directory), &mut files);
+    }
+
+    // the same on every platform and in every checkout.
+    files.sort();
+
+    // FNV-1a, over both the paths and the contents.
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for (name, path) in files {
+        let contents = fs::read(&path).unwrap();
+        for byte in name.bytes().chain(contents) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    println!("cargo:rustc-env=PHP_PARSER_GRAMMAR_HASH={:016x}", hash);
+}
+
+// Collect the `.rs` files of the directory, along with their path relative to the
+// root, separated by `/`.
+fn collect(root: &Path, directory: &Path, files: &mut Vec<(String, PathBuf)>) {
+    for entry in fs::read_dir(directory).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect(root, &path, files);
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            let name = path
+                .strip_prefix(root)
+                .unwrap()
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            files.push((name, path));
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::downcast::downcast;
+use crate::node::Node;
+use crate::parser::ast::attributes::Attribute;
+use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::classes::AnonymousClassExpression;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::enums::BackedEnumCase;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::UnitEnumCase;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::functions::AbstractConstructor;
+use crate::parser::ast::functions::AbstractMethod;
+use crate::parser::ast::functions::ArrowFunctionExpression;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::ConstructorParameter;
+use crate::parser::ast::functions::FunctionParameter;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::interfaces::InterfaceStatement;
+use crate::parser::ast::properties::Property;
+use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::traits::TraitStatement;
+
+/// An attribute that is built into PHP and changes the semantics of its target.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum WellKnownAttribute {
+    // `#[\AllowDynamicProperties]`, PHP 8.2
+    AllowDynamicProperties,
+    // `#[\ReturnTypeWillChange]`, PHP 8.1
+    ReturnTypeWillChange,
+    // `#[\Override]`, PHP 8.3
+    Override,
+}
+
+impl WellKnownAttribute {
+    /// Interpret the given attribute as a well-known attribute.
+    ///
+    /// Attribute names are case-insensitive, and may be written with or without a leading
+    /// backslash. Since name resolution is not performed here, an unqualified name is assumed
+    /// to refer to the global attribute.
+    pub fn from_attribute(attribute: &Attribute) -> Option<Self> {
+        let name = attribute.name.value.as_slice();
+        let name = name.strip_prefix(b"\\").unwrap_or(name);
+
+        match name.to_ascii_lowercase().as_slice() {
+            b"allowdynamicproperties" => Some(Self::AllowDynamicProperties),
+            b"returntypewillchange" => Some(Self::ReturnTypeWillChange),
+            b"override" => Some(Self::Override),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::AllowDynamicProperties => "AllowDynamicProperties",
+            Self::ReturnTypeWillChange => "ReturnTypeWillChange",
+            Self::Override => "Override",
+        }
+    }
+}
+
+/// The well-known attributes applied to a declaration.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct AttributeFlags {
+    pub allow_dynamic_properties: bool,
+    pub return_type_will_change: bool,
+    pub r#override: bool,
+}
+
+impl AttributeFlags {
+    pub fn from_groups(groups: &[AttributeGroup]) -> Self {
+        let mut flags = Self::default();
+
+        for attribute in groups.iter().flat_map(|group| group.iter()) {
+            match WellKnownAttribute::from_attribute(attribute) {
+                Some(WellKnownAttribute::AllowDynamicProperties) => {
+                    flags.allow_dynamic_properties = true
+                }
+                Some(WellKnownAttribute::ReturnTypeWillChange) => {
+                    flags.return_type_will_change = true
+                }
+                Some(WellKnownAttribute::Override) => flags.r#override = true,
+                None => {}
+            }
+        }
+
+        flags
+    }
+
+    pub fn has(&self, attribute: WellKnownAttribute) -> bool {
+        match attribute {
+            WellKnownAttribute::AllowDynamicProperties => self.allow_dynamic_properties,
+            WellKnownAttribute::ReturnTypeWillChange => self.return_type_will_change,
+            WellKnownAttribute::Override => self.r#override,
+        }
+    }
+}
+
+/// The attributes of a declaration, which are not part of its children.
+pub fn declaration_attributes(node: &dyn Node) -> Option<&[AttributeGroup]> {
+    macro_rules! attributes {
+        ($($type:ty),*) => {
+            $(
+                if let Some(node) = downcast::<$type>(node) {
+                    return Some(&node.attributes);
+                }
+            )*
+        };
+    }
+
+    attributes!(
+        ClassStatement,
+        AnonymousClassExpression,
+        InterfaceStatement,
+        TraitStatement,
+        UnitEnumStatement,
+        BackedEnumStatement,
+        UnitEnumCase,
+        BackedEnumCase,
+        ClassishConstant,
+        Property,
+        VariableProperty,
+        FunctionStatement,
+        ClosureExpression,
+        ArrowFunctionExpression,
+        FunctionParameter,
+        ConstructorParameter,
+        AbstractConstructor,
+        ConcreteConstructor,
+        AbstractMethod,
+        ConcreteMethod
+    );
+
+    None
+}
@@ -0,0 +1,112 @@
+use crate::downcast::downcast;
+use crate::downcast::downcast_mut;
+use crate::lexer::byte_string::ByteString;
+use crate::node::Node;
+use crate::parser::ast::classes::AnonymousClassExpression;
+use crate::parser::ast::functions::ArrowFunctionExpression;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::ast::functions::FunctionParameter;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::variables::BracedVariableVariable;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::VariableVariable;
+
+/// The variables of the enclosing scope that a closure or arrow function body refers to.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Captures {
+    // The variables, including the `$`, in the order they first appear, excluding
+    // parameters and `$this`.
+    pub variables: Vec<ByteString>,
+    // Whether the body refers to variables by a computed name, such as `$$name`, in
+    // which case the captured variables can not be known.
+    pub dynamic: bool,
+}
+
+impl Captures {
+    /// The variables the body of the given closure or arrow function refers to, or `None`
+    /// if it is neither.
+    ///
+    /// For an arrow function, these are the variables it captures implicitly. For a
+    /// closure, these are the variables it would need to import with `use` to behave
+    /// like an arrow function.
+    pub fn of(node: &mut dyn Node) -> Option<Self> {
+        let mut captures = Self::default();
+
+        if let Some(closure) = downcast_mut::<ClosureExpression>(node) {
+            let parameters = parameter_names(closure.parameters.parameters.iter());
+            for statement in &mut closure.body.statements {
+                captures.collect(statement, &parameters);
+            }
+        } else if let Some(function) = downcast_mut::<ArrowFunctionExpression>(node) {
+            let parameters = parameter_names(function.parameters.parameters.iter());
+            captures.collect(function.body.as_mut(), &parameters);
+        } else {
+            return None;
+        }
+
+        Some(captures)
+    }
+
+    fn collect(&mut self, node: &mut dyn Node, parameters: &[ByteString]) {
+        if let Some(variable) = downcast::<SimpleVariable>(node) {
+            self.add(&variable.name, parameters);
+
+            return;
+        }
+
+        if downcast::<VariableVariable>(node).is_some()
+            || downcast::<BracedVariableVariable>(node).is_some()
+        {
+            self.dynamic = true;
+        }
+
+        // a nested closure only refers to the variables it imports, while a nested arrow
+        // function captures from this body in turn.
+        if let Some(closure) = downcast::<ClosureExpression>(node) {
+            if let Some(uses) = &closure.uses {
+                for r#use in uses.variables.iter() {
+                    self.add(&r#use.variable.name, parameters);
+                }
+            }
+
+            return;
+        }
+
+        if let Some(function) = downcast_mut::<ArrowFunctionExpression>(node) {
+            if let Some(nested) = Self::of(function) {
+                self.dynamic |= nested.dynamic;
+                for variable in &nested.variables {
+                    self.add(variable, parameters);
+                }
+            }
+
+            return;
+        }
+
+        if downcast::<FunctionStatement>(node).is_some()
+            || downcast::<AnonymousClassExpression>(node).is_some()
+        {
+            return;
+        }
+
+        for child in node.children() {
+            self.collect(child, parameters);
+        }
+    }
+
+    fn add(&mut self, name: &ByteString, parameters: &[ByteString]) {
+        if name == b"$this" || parameters.contains(name) {
+            return;
+        }
+
+        if !self.variables.contains(name) {
+            self.variables.push(name.clone());
+        }
+    }
+}
+
+fn parameter_names<'a>(parameters: impl Iterator<Item = &'a FunctionParameter>) -> Vec<ByteString> {
+    parameters
+        .map(|parameter| parameter.name.name.clone())
+        .collect()
+}
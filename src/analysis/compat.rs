@@ -0,0 +1,270 @@
+use crate::index::ClassLikeEntry;
+use crate::index::Index;
+use crate::index::MethodEntry;
+use crate::index::ParameterEntry;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::modifiers::Visibility;
+
+/// A kind of change that breaks code depending on the changed declaration.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum CompatKind {
+    ClassRemoved,
+    ClassMadeFinal,
+    FunctionRemoved,
+    MethodRemoved,
+    PropertyRemoved,
+    ConstantRemoved,
+    VisibilityNarrowed,
+    RequiredParameterAdded,
+    ParameterMadeRequired,
+    ReturnTypeChanged,
+    ConstantValueChanged,
+}
+
+impl CompatKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ClassRemoved => "class-removed",
+            Self::ClassMadeFinal => "class-made-final",
+            Self::FunctionRemoved => "function-removed",
+            Self::MethodRemoved => "method-removed",
+            Self::PropertyRemoved => "property-removed",
+            Self::ConstantRemoved => "constant-removed",
+            Self::VisibilityNarrowed => "visibility-narrowed",
+            Self::RequiredParameterAdded => "required-parameter-added",
+            Self::ParameterMadeRequired => "parameter-made-required",
+            Self::ReturnTypeChanged => "return-type-changed",
+            Self::ConstantValueChanged => "constant-value-changed",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CompatFinding {
+    pub kind: CompatKind,
+    // The changed declaration, such as `Foo\Bar`, `Foo\Bar::baz()`, `Foo\Bar::$baz`
+    // or `Foo\Bar::BAZ`.
+    pub symbol: String,
+    pub message: String,
+}
+
+/// Compare the declarations of two versions of a codebase, reporting the changes that
+/// break code using the old version, ordered by symbol.
+///
+/// Only the public API is compared: private members are ignored, while protected
+/// members are compared as they can be used by subclasses. Types are compared as
+/// written, without resolving names or accounting for variance, so a return type
+/// that was narrowed is reported as well.
+pub fn compare(old: &Index, new: &Index) -> Vec<CompatFinding> {
+    let mut findings = vec![];
+
+    for class in old.classes() {
+        match new.class(&class.name) {
+            Some(changed) => compare_class(new, class, changed, &mut findings),
+            None => findings.push(CompatFinding {
+                kind: CompatKind::ClassRemoved,
+                symbol: class.name.to_string(),
+                message: format!("`{}` was removed", class.name),
+            }),
+        }
+    }
+
+    for function in old.functions() {
+        let symbol = format!("{}()", function.name);
+        match new.function(&function.name) {
+            Some(changed) => {
+                compare_parameters(
+                    &symbol,
+                    &function.parameters,
+                    &changed.parameters,
+                    &mut findings,
+                );
+                compare_return_type(
+                    &symbol,
+                    &function.return_type,
+                    &changed.return_type,
+                    &mut findings,
+                );
+            }
+            None => findings.push(CompatFinding {
+                kind: CompatKind::FunctionRemoved,
+                message: format!("`{}` was removed", symbol),
+                symbol,
+            }),
+        }
+    }
+
+    findings.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    findings
+}
+
+fn compare_class(
+    new: &Index,
+    old: &ClassLikeEntry,
+    changed: &ClassLikeEntry,
+    findings: &mut Vec<CompatFinding>,
+) {
+    if !old.is_final && changed.is_final {
+        findings.push(CompatFinding {
+            kind: CompatKind::ClassMadeFinal,
+            symbol: old.name.to_string(),
+            message: format!("`{}` was made final", old.name),
+        });
+    }
+
+    for constant in &old.constants {
+        if constant.visibility == Visibility::Private {
+            continue;
+        }
+
+        let symbol = format!("{}::{}", old.name, constant.name);
+        let Some(changed) = changed.constant(&constant.name) else {
+            removed(CompatKind::ConstantRemoved, symbol, findings);
+            continue;
+        };
+
+        compare_visibility(&symbol, &constant.visibility, &changed.visibility, findings);
+        if let (Some(value), Some(changed)) = (&constant.value, &changed.value) {
+            if value != changed {
+                findings.push(CompatFinding {
+                    kind: CompatKind::ConstantValueChanged,
+                    message: format!("the value of `{}` was changed", symbol),
+                    symbol,
+                });
+            }
+        }
+    }
+
+    for property in &old.properties {
+        if property.visibility == Visibility::Private {
+            continue;
+        }
+
+        let symbol = format!("{}::${}", old.name, property.name);
+        match changed.property(&property.name) {
+            Some(changed) => {
+                compare_visibility(&symbol, &property.visibility, &changed.visibility, findings)
+            }
+            None => removed(CompatKind::PropertyRemoved, symbol, findings),
+        }
+    }
+
+    for method in &old.methods {
+        if method.visibility == Visibility::Private {
+            continue;
+        }
+
+        let symbol = format!("{}::{}()", old.name, method.name);
+        // a method moved to a parent class or trait is still available.
+        let Some(changed) = changed
+            .method(&method.name)
+            .or_else(|| new.find_method(&changed.name, &method.name))
+        else {
+            removed(CompatKind::MethodRemoved, symbol, findings);
+            continue;
+        };
+
+        compare_method(&symbol, method, changed, findings);
+    }
+}
+
+fn compare_method(
+    symbol: &str,
+    old: &MethodEntry,
+    changed: &MethodEntry,
+    findings: &mut Vec<CompatFinding>,
+) {
+    compare_visibility(symbol, &old.visibility, &changed.visibility, findings);
+    compare_parameters(symbol, &old.parameters, &changed.parameters, findings);
+    compare_return_type(symbol, &old.return_type, &changed.return_type, findings);
+}
+
+fn removed(kind: CompatKind, symbol: String, findings: &mut Vec<CompatFinding>) {
+    findings.push(CompatFinding {
+        kind,
+        message: format!("`{}` was removed", symbol),
+        symbol,
+    });
+}
+
+fn compare_visibility(
+    symbol: &str,
+    old: &Visibility,
+    changed: &Visibility,
+    findings: &mut Vec<CompatFinding>,
+) {
+    let rank = |visibility: &Visibility| match visibility {
+        Visibility::Public => 0,
+        Visibility::Protected => 1,
+        Visibility::Private => 2,
+    };
+
+    if rank(changed) > rank(old) {
+        findings.push(CompatFinding {
+            kind: CompatKind::VisibilityNarrowed,
+            symbol: symbol.to_owned(),
+            message: format!(
+                "`{}` was made {}",
+                symbol,
+                format!("{:?}", changed).to_lowercase()
+            ),
+        });
+    }
+}
+
+fn compare_parameters(
+    symbol: &str,
+    old: &[ParameterEntry],
+    changed: &[ParameterEntry],
+    findings: &mut Vec<CompatFinding>,
+) {
+    for (position, parameter) in changed.iter().enumerate() {
+        if parameter.is_optional {
+            continue;
+        }
+
+        match old.get(position) {
+            Some(old) if !old.is_optional => {}
+            Some(_) => findings.push(CompatFinding {
+                kind: CompatKind::ParameterMadeRequired,
+                symbol: symbol.to_owned(),
+                message: format!(
+                    "parameter `${}` of `{}` is no longer optional",
+                    parameter.name, symbol
+                ),
+            }),
+            None => findings.push(CompatFinding {
+                kind: CompatKind::RequiredParameterAdded,
+                symbol: symbol.to_owned(),
+                message: format!(
+                    "required parameter `${}` was added to `{}`",
+                    parameter.name, symbol
+                ),
+            }),
+        }
+    }
+}
+
+fn compare_return_type(
+    symbol: &str,
+    old: &Option<Type>,
+    changed: &Option<Type>,
+    findings: &mut Vec<CompatFinding>,
+) {
+    let render = |r#type: &Option<Type>| match r#type {
+        Some(r#type) => format!("`{}`", r#type),
+        None => "none".to_owned(),
+    };
+
+    let (old, changed) = (render(old), render(changed));
+    if !old.eq_ignore_ascii_case(&changed) {
+        findings.push(CompatFinding {
+            kind: CompatKind::ReturnTypeChanged,
+            symbol: symbol.to_owned(),
+            message: format!(
+                "the return type of `{}` was changed from {} to {}",
+                symbol, old, changed
+            ),
+        });
+    }
+}
@@ -0,0 +1,59 @@
+use crate::parser::ast::declares::DeclareEntry;
+use crate::parser::ast::literals::IntegerValue;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+
+/// Provides [`Declares::declares`] and [`Declares::strict_types`] on a [`Program`].
+pub trait Declares {
+    /// The entries of the `declare` statements at the top level of the program, or of
+    /// its namespaces, in the order they appear in.
+    fn declares(&self) -> Vec<&DeclareEntry>;
+
+    /// Whether the program is declared with `declare(strict_types=1)`.
+    fn strict_types(&self) -> bool;
+}
+
+impl Declares for Program {
+    fn declares(&self) -> Vec<&DeclareEntry> {
+        let mut entries = Vec::new();
+        collect(self, &mut entries);
+
+        entries
+    }
+
+    fn strict_types(&self) -> bool {
+        // `strict_types` is only allowed before any other statement, so it can't be
+        // inside of a namespace.
+        self.iter()
+            .filter_map(|statement| match statement {
+                Statement::Declare(declare) => Some(&declare.entries.entries),
+                _ => None,
+            })
+            .flatten()
+            .rev()
+            .find(|entry| entry.key.value.eq_ignore_ascii_case(b"strict_types"))
+            .is_some_and(|entry| {
+                matches!(
+                    &entry.value,
+                    Literal::Integer(integer) if integer.to_value() == IntegerValue::Integer(1)
+                )
+            })
+    }
+}
+
+fn collect<'a>(statements: &'a [Statement], entries: &mut Vec<&'a DeclareEntry>) {
+    for statement in statements {
+        match statement {
+            Statement::Declare(declare) => entries.extend(declare.entries.entries.iter()),
+            Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                collect(&namespace.statements, entries)
+            }
+            Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                collect(&namespace.body.statements, entries)
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use crate::analysis::views::MethodView;
+use crate::evaluator::const_expr_with;
+use crate::evaluator::PhpConstValue;
+use crate::lexer::byte_string::ByteString;
+use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::constant::ConstantEntry;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::BackedEnumType;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::Expression;
+use crate::parser::ast::Statement;
+
+// How deeply constants may reference other constants, which guards against cycles.
+const MAX_CONSTANT_DEPTH: usize = 32;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EnumBackingType {
+    Int,
+    String,
+}
+
+/// The value of a case of a backed enum.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum EnumBackingValue {
+    Int(i64),
+    String(ByteString),
+}
+
+/// A typed view over a parsed enum, similar to what `cases()` and reflection
+/// return at runtime.
+#[derive(Debug, Clone)]
+pub struct EnumShape<'a> {
+    pub name: &'a SimpleIdentifier,
+    pub attributes: &'a [AttributeGroup],
+    // `None` for unit enums.
+    pub backing_type: Option<EnumBackingType>,
+    pub cases: Vec<EnumCaseShape<'a>>,
+    pub interfaces: Vec<&'a SimpleIdentifier>,
+    pub methods: Vec<MethodView<'a>>,
+    // The cases by their evaluated backing value, which is what `from()` and `tryFrom()`
+    // look up. Empty for unit enums. Cases whose value can not be evaluated are missing,
+    // and when several cases share a value, which PHP rejects, the first one is kept.
+    pub lookup_table: HashMap<EnumBackingValue, &'a SimpleIdentifier>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnumCaseShape<'a> {
+    pub name: &'a SimpleIdentifier,
+    pub attributes: &'a [AttributeGroup],
+    // The evaluated backing value, `None` for unit enums, or if the value is not a
+    // constant expression that can be evaluated.
+    pub value: Option<PhpConstValue>,
+}
+
+impl<'a> EnumShape<'a> {
+    /// Create the shape of the given statement, if it is an enum declaration.
+    pub fn from_statement(statement: &'a Statement) -> Option<Self> {
+        match statement {
+            Statement::UnitEnum(r#enum) => Some(Self::from_unit_enum(r#enum)),
+            Statement::BackedEnum(r#enum) => Some(Self::from_backed_enum(r#enum)),
+            _ => None,
+        }
+    }
+
+    pub fn from_unit_enum(r#enum: &'a UnitEnumStatement) -> Self {
+        let mut shape = Self::new(&r#enum.name, &r#enum.attributes, &r#enum.implements);
+
+        for member in &r#enum.body.members {
+            match member {
+                UnitEnumMember::Case(case) => shape.cases.push(EnumCaseShape {
+                    name: &case.name,
+                    attributes: &case.attributes,
+                    value: None,
+                }),
+                UnitEnumMember::Method(method) => {
+                    shape.methods.push(MethodView::from_concrete_method(method))
+                }
+                UnitEnumMember::Constant(_) | UnitEnumMember::TraitUsage(_) => {}
+            }
+        }
+
+        shape
+    }
+
+    pub fn from_backed_enum(r#enum: &'a BackedEnumStatement) -> Self {
+        let mut shape = Self::new(&r#enum.name, &r#enum.attributes, &r#enum.implements);
+        shape.backing_type = Some(match r#enum.backed_type {
+            BackedEnumType::Int(..) => EnumBackingType::Int,
+            BackedEnumType::String(..) => EnumBackingType::String,
+        });
+
+        let constants: Vec<&ConstantEntry> = r#enum
+            .body
+            .members
+            .iter()
+            .filter_map(|member| match member {
+                BackedEnumMember::Constant(constant) => Some(constant.entries.iter()),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+
+        for member in &r#enum.body.members {
+            match member {
+                BackedEnumMember::Case(case) => shape.cases.push(EnumCaseShape {
+                    name: &case.name,
+                    attributes: &case.attributes,
+                    value: evaluate(&r#enum.name, &constants, &case.value, 0),
+                }),
+                BackedEnumMember::Method(method) => {
+                    shape.methods.push(MethodView::from_concrete_method(method))
+                }
+                BackedEnumMember::Constant(_) | BackedEnumMember::TraitUsage(_) => {}
+            }
+        }
+
+        for case in &shape.cases {
+            let value = match &case.value {
+                Some(PhpConstValue::Int(value)) => EnumBackingValue::Int(*value),
+                Some(PhpConstValue::String(value)) => EnumBackingValue::String(value.clone()),
+                _ => continue,
+            };
+
+            shape.lookup_table.entry(value).or_insert(case.name);
+        }
+
+        shape
+    }
+
+    /// Find a case by name. Unlike methods, case names are case-sensitive.
+    pub fn case(&self, name: &[u8]) -> Option<&EnumCaseShape<'a>> {
+        self.cases.iter().find(|case| case.name.value[..] == *name)
+    }
+
+    /// Find the case `tryFrom()` would return for the given value, or `None` if it would
+    /// return `null` or throw a `TypeError`.
+    ///
+    /// Values of other types are coerced the way PHP does outside of strict mode: an
+    /// integer-backed enum accepts numeric strings, integral floats and booleans, and a
+    /// string-backed enum accepts integers and booleans.
+    pub fn try_from(&self, value: &PhpConstValue) -> Option<&EnumCaseShape<'a>> {
+        let key = match (self.backing_type?, value) {
+            (EnumBackingType::Int, PhpConstValue::Int(value)) => EnumBackingValue::Int(*value),
+            (EnumBackingType::Int, PhpConstValue::Bool(value)) => {
+                EnumBackingValue::Int(*value as i64)
+            }
+            (EnumBackingType::Int, PhpConstValue::Float(value)) => {
+                if value.fract() != 0.0 || value.abs() >= i64::MAX as f64 {
+                    return None;
+                }
+
+                EnumBackingValue::Int(*value as i64)
+            }
+            (EnumBackingType::Int, PhpConstValue::String(value)) => {
+                let value = std::str::from_utf8(value).ok()?.trim();
+                match value.parse::<i64>() {
+                    Ok(value) => EnumBackingValue::Int(value),
+                    Err(_) => {
+                        return self.try_from(&PhpConstValue::Float(value.parse::<f64>().ok()?))
+                    }
+                }
+            }
+            (EnumBackingType::String, PhpConstValue::String(value)) => {
+                EnumBackingValue::String(value.clone())
+            }
+            (EnumBackingType::String, PhpConstValue::Int(value)) => {
+                EnumBackingValue::String(value.to_string().into())
+            }
+            (EnumBackingType::String, PhpConstValue::Bool(value)) => {
+                EnumBackingValue::String(if *value { "1" } else { "" }.into())
+            }
+            _ => return None,
+        };
+
+        let name = self.lookup_table.get(&key)?;
+
+        self.case(&name.value)
+    }
+
+    fn new(
+        name: &'a SimpleIdentifier,
+        attributes: &'a [AttributeGroup],
+        implements: &'a [SimpleIdentifier],
+    ) -> Self {
+        Self {
+            name,
+            attributes,
+            backing_type: None,
+            cases: vec![],
+            interfaces: implements.iter().collect(),
+            methods: vec![],
+            lookup_table: HashMap::new(),
+        }
+    }
+}
+
+// Evaluate an expression in the enum, resolving references to its own constants.
+fn evaluate(
+    name: &SimpleIdentifier,
+    constants: &[&ConstantEntry],
+    expression: &Expression,
+    depth: usize,
+) -> Option<PhpConstValue> {
+    if depth > MAX_CONSTANT_DEPTH {
+        return None;
+    }
+
+    const_expr_with(expression, &mut |reference| {
+        let fetch = match reference {
+            Expression::ConstantFetch(fetch) => fetch,
+            _ => return None,
+        };
+
+        let own = match fetch.target.as_ref() {
+            Expression::Self_(_) | Expression::Static(_) => true,
+            Expression::Identifier(Identifier::SimpleIdentifier(target)) => {
+                target.value.eq_ignore_ascii_case(&name.value)
+            }
+            _ => false,
+        };
+
+        let constant = match &fetch.constant {
+            Identifier::SimpleIdentifier(constant) if own => constant,
+            _ => return None,
+        };
+
+        let entry = constants
+            .iter()
+            .find(|entry| entry.name.value == constant.value)?;
+
+        evaluate(name, constants, &entry.value, depth + 1)
+    })
+}
@@ -0,0 +1,232 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::analysis::attributes::declaration_attributes;
+use crate::downcast::downcast;
+use crate::lexer::keywords::PhpVersion;
+use crate::node::Node;
+use crate::parser::ast::arguments::NamedArgument;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::functions::ArrowFunctionExpression;
+use crate::parser::ast::functions::ConstructorParameter;
+use crate::parser::ast::properties::Property;
+use crate::parser::ast::MatchExpression;
+use crate::parser::ast::NullsafeMethodCallExpression;
+use crate::parser::ast::NullsafePropertyFetchExpression;
+use crate::parser::ast::Program;
+
+/// A language feature whose usage is counted by [`FeatureStats`].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Feature {
+    // `fn($x) => $x`
+    ArrowFunction,
+    // `match ($x) { ... }`
+    Match,
+    // `#[Foo]`, counted per attribute.
+    Attribute,
+    // `$a?->b`, `$a?->b()`
+    Nullsafe,
+    // `foo(name: $x)`
+    NamedArgument,
+    // `enum Foo { ... }`
+    Enum,
+    // `public readonly int $x`, including promoted properties.
+    ReadonlyProperty,
+    // `readonly class Foo { ... }`
+    ReadonlyClass,
+}
+
+impl Feature {
+    pub const ALL: [Self; 8] = [
+        Self::ArrowFunction,
+        Self::Match,
+        Self::Attribute,
+        Self::Nullsafe,
+        Self::NamedArgument,
+        Self::Enum,
+        Self::ReadonlyProperty,
+        Self::ReadonlyClass,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ArrowFunction => "arrow-function",
+            Self::Match => "match",
+            Self::Attribute => "attribute",
+            Self::Nullsafe => "nullsafe",
+            Self::NamedArgument => "named-argument",
+            Self::Enum => "enum",
+            Self::ReadonlyProperty => "readonly-property",
+            Self::ReadonlyClass => "readonly-class",
+        }
+    }
+
+    /// The PHP version the feature was introduced in.
+    pub fn introduced_in(&self) -> PhpVersion {
+        match self {
+            Self::ArrowFunction => PhpVersion::Php74,
+            Self::Match | Self::Attribute | Self::Nullsafe | Self::NamedArgument => {
+                PhpVersion::Php80
+            }
+            Self::Enum | Self::ReadonlyProperty => PhpVersion::Php81,
+            Self::ReadonlyClass => PhpVersion::Php82,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct FeatureUsage {
+    // The number of times the feature is used.
+    pub count: usize,
+    // The number of files using the feature at least once.
+    pub files: usize,
+}
+
+/// Counts how often language features are used across the programs of a codebase,
+/// such as to find the PHP version it requires, or what an upgrade would affect.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct FeatureStats {
+    pub files: usize,
+    usage: BTreeMap<Feature, FeatureUsage>,
+}
+
+impl FeatureStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_program(&mut self, program: &mut Program) {
+        let mut counts = BTreeMap::new();
+        for statement in program.iter_mut() {
+            walk(statement, &mut counts);
+        }
+
+        self.files += 1;
+        for (feature, count) in counts {
+            let usage = self.usage.entry(feature).or_default();
+            usage.count += count;
+            usage.files += 1;
+        }
+    }
+
+    pub fn usage(&self, feature: Feature) -> FeatureUsage {
+        self.usage.get(&feature).copied().unwrap_or_default()
+    }
+
+    /// The oldest PHP version that supports every feature used, as far as the counted
+    /// features tell.
+    pub fn minimum_version(&self) -> PhpVersion {
+        self.usage
+            .keys()
+            .map(|feature| feature.introduced_in())
+            .max()
+            .unwrap_or(PhpVersion::OLDEST)
+    }
+
+    /// Render the stats as a table of all features, followed by the minimum PHP version.
+    pub fn report(&self) -> String {
+        let mut output = format!(
+            "{:<20}{:>8}{:>8}  {}\n",
+            "feature", "count", "files", "since"
+        );
+
+        for feature in Feature::ALL {
+            let usage = self.usage(feature);
+            writeln!(
+                output,
+                "{:<20}{:>8}{:>8}  PHP {}",
+                feature.name(),
+                usage.count,
+                usage.files,
+                feature.introduced_in().as_str()
+            )
+            .unwrap();
+        }
+
+        writeln!(
+            output,
+            "\n{} files, requiring at least PHP {}",
+            self.files,
+            self.minimum_version().as_str()
+        )
+        .unwrap();
+
+        output
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let features: serde_json::Map<String, serde_json::Value> = Feature::ALL
+            .iter()
+            .map(|feature| {
+                let usage = self.usage(*feature);
+
+                (
+                    feature.name().to_owned(),
+                    serde_json::json!({
+                        "count": usage.count,
+                        "files": usage.files,
+                        "since": feature.introduced_in().as_str(),
+                    }),
+                )
+            })
+            .collect();
+
+        serde_json::json!({
+            "files": self.files,
+            "minimum_version": self.minimum_version().as_str(),
+            "features": features,
+        })
+    }
+}
+
+fn walk(node: &mut dyn Node, counts: &mut BTreeMap<Feature, usize>) {
+    let mut count = |feature: Feature, amount: usize| {
+        if amount > 0 {
+            *counts.entry(feature).or_default() += amount;
+        }
+    };
+
+    if let Some(groups) = declaration_attributes(node) {
+        count(
+            Feature::Attribute,
+            groups.iter().map(|group| group.members.len()).sum(),
+        );
+    }
+
+    if downcast::<ArrowFunctionExpression>(node).is_some() {
+        count(Feature::ArrowFunction, 1);
+    } else if downcast::<MatchExpression>(node).is_some() {
+        count(Feature::Match, 1);
+    } else if downcast::<NullsafePropertyFetchExpression>(node).is_some()
+        || downcast::<NullsafeMethodCallExpression>(node).is_some()
+    {
+        count(Feature::Nullsafe, 1);
+    } else if downcast::<NamedArgument>(node).is_some() {
+        count(Feature::NamedArgument, 1);
+    } else if downcast::<UnitEnumStatement>(node).is_some()
+        || downcast::<BackedEnumStatement>(node).is_some()
+    {
+        count(Feature::Enum, 1);
+    } else if let Some(class) = downcast::<ClassStatement>(node) {
+        count(
+            Feature::ReadonlyClass,
+            class.modifiers.has_readonly() as usize,
+        );
+    } else if let Some(property) = downcast::<Property>(node) {
+        count(
+            Feature::ReadonlyProperty,
+            property.modifiers.has_readonly() as usize,
+        );
+    } else if let Some(parameter) = downcast::<ConstructorParameter>(node) {
+        count(
+            Feature::ReadonlyProperty,
+            parameter.modifiers.has_readonly() as usize,
+        );
+    }
+
+    for child in node.children() {
+        walk(child, counts);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::downcast::downcast;
+use crate::node::Node;
+use crate::parser::ast::classes::AnonymousClassExpression;
+use crate::parser::ast::functions::AbstractConstructor;
+use crate::parser::ast::functions::AbstractMethod;
+use crate::parser::ast::functions::ArrowFunctionExpression;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::YieldExpression;
+use crate::parser::ast::YieldFromExpression;
+
+/// Facts about a function, closure, arrow function, method or constructor that follow
+/// from its declaration and body alone, without resolving any names.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FunctionFlags {
+    // Whether the body contains `yield` or `yield from`, making the function return
+    // a `Generator` when called. `None` for abstract methods, which have no body.
+    pub is_generator: Option<bool>,
+    // `function &foo()`
+    pub returns_by_reference: bool,
+    // `static function () {}`, `static fn () => 1`, `public static function foo()`
+    pub is_static: bool,
+}
+
+impl FunctionFlags {
+    /// The flags of the given node, or `None` if it is not a function-like declaration.
+    pub fn of(node: &mut dyn Node) -> Option<Self> {
+        let (returns_by_reference, is_static, has_body) =
+            if let Some(function) = downcast::<FunctionStatement>(node) {
+                (function.ampersand.is_some(), false, true)
+            } else if let Some(closure) = downcast::<ClosureExpression>(node) {
+                (
+                    closure.ampersand.is_some(),
+                    closure.r#static.is_some(),
+                    true,
+                )
+            } else if let Some(function) = downcast::<ArrowFunctionExpression>(node) {
+                (
+                    function.ampersand.is_some(),
+                    function.r#static.is_some(),
+                    true,
+                )
+            } else if let Some(method) = downcast::<ConcreteMethod>(node) {
+                (
+                    method.ampersand.is_some(),
+                    method.modifiers.has_static(),
+                    true,
+                )
+            } else if let Some(constructor) = downcast::<ConcreteConstructor>(node) {
+                (constructor.ampersand.is_some(), false, true)
+            } else if let Some(method) = downcast::<AbstractMethod>(node) {
+                (
+                    method.ampersand.is_some(),
+                    method.modifiers.has_static(),
+                    false,
+                )
+            } else if let Some(constructor) = downcast::<AbstractConstructor>(node) {
+                (constructor.ampersand.is_some(), false, false)
+            } else {
+                return None;
+            };
+
+        Some(Self {
+            is_generator: has_body.then(|| node.children().into_iter().any(contains_yield)),
+            returns_by_reference,
+            is_static,
+        })
+    }
+}
+
+// Whether the node contains a `yield` that belongs to the enclosing function, skipping
+// nested functions and classes, whose bodies belong to themselves.
+fn contains_yield(node: &mut dyn Node) -> bool {
+    if downcast::<YieldExpression>(node).is_some()
+        || downcast::<YieldFromExpression>(node).is_some()
+    {
+        return true;
+    }
+
+    if downcast::<ClosureExpression>(node).is_some()
+        || downcast::<ArrowFunctionExpression>(node).is_some()
+        || downcast::<FunctionStatement>(node).is_some()
+        || downcast::<AnonymousClassExpression>(node).is_some()
+    {
+        return false;
+    }
+
+    node.children().into_iter().any(contains_yield)
+}
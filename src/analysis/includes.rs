@@ -0,0 +1,126 @@
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::path::MAIN_SEPARATOR;
+
+use crate::parser::ast::arguments::Argument;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::Expression;
+use crate::parser::ast::MagicConstantExpression;
+
+/// Resolve the file included by an `include`, `include_once`, `require` or
+/// `require_once` expression in the given file.
+///
+/// See [`resolve_path`] for the paths that can be resolved.
+pub fn resolve_include(file: &Path, expression: &Expression) -> Option<PathBuf> {
+    let path = match expression {
+        Expression::Include(include) => &include.path,
+        Expression::IncludeOnce(include) => &include.path,
+        Expression::Require(require) => &require.path,
+        Expression::RequireOnce(require) => &require.path,
+        _ => return None,
+    };
+
+    resolve_path(file, path)
+}
+
+/// Resolve a path expression in the given file to a normalized absolute path, if it
+/// only consists of string literals, `__DIR__`, `__FILE__`, `DIRECTORY_SEPARATOR`,
+/// `dirname()` calls, and concatenations of them.
+///
+/// Relative paths are not resolved, as PHP looks them up using the `include_path`
+/// setting and the working directory. `..` and `.` components are resolved lexically,
+/// without following symbolic links, so the file does not need to exist.
+pub fn resolve_path(file: &Path, path: &Expression) -> Option<PathBuf> {
+    let path = PathBuf::from(fold(file, path)?);
+    if !path.is_absolute() {
+        return None;
+    }
+
+    Some(normalize(&path))
+}
+
+fn fold(file: &Path, expression: &Expression) -> Option<String> {
+    match expression {
+        Expression::Literal(Literal::String(string)) => {
+            String::from_utf8(string.value.to_vec()).ok()
+        }
+        Expression::MagicConstant(MagicConstantExpression::Directory(_)) => {
+            Some(file.parent()?.to_str()?.to_owned())
+        }
+        Expression::MagicConstant(MagicConstantExpression::File(_)) => {
+            Some(file.to_str()?.to_owned())
+        }
+        Expression::Identifier(Identifier::SimpleIdentifier(identifier))
+            if is_global(&identifier.value, b"DIRECTORY_SEPARATOR") =>
+        {
+            Some(MAIN_SEPARATOR.to_string())
+        }
+        Expression::Concat(concat) => Some(fold(file, &concat.left)? + &fold(file, &concat.right)?),
+        Expression::Parenthesized(parenthesized) => fold(file, &parenthesized.expr),
+        Expression::FunctionCall(call) => {
+            match call.target.as_ref() {
+                Expression::Identifier(Identifier::SimpleIdentifier(identifier))
+                    if is_global(&identifier.value.to_ascii_lowercase(), b"dirname") => {}
+                _ => return None,
+            }
+
+            let mut arguments = call.arguments.iter().map(|argument| match argument {
+                Argument::Positional(argument) if argument.ellipsis.is_none() => {
+                    Some(&argument.value)
+                }
+                _ => None,
+            });
+
+            let path = fold(file, arguments.next()??)?;
+            let levels = match arguments.next() {
+                Some(Some(Expression::Literal(Literal::Integer(integer)))) => {
+                    std::str::from_utf8(&integer.value).ok()?.parse().ok()?
+                }
+                Some(_) => return None,
+                None => 1,
+            };
+
+            if arguments.next().is_some() {
+                return None;
+            }
+
+            Some(dirname(&path, levels))
+        }
+        _ => None,
+    }
+}
+
+// Whether a name refers to the given global function or constant.
+fn is_global(name: &[u8], global: &[u8]) -> bool {
+    name.strip_prefix(b"\\").unwrap_or(name) == global
+}
+
+fn dirname(path: &str, levels: usize) -> String {
+    let mut path = Path::new(path);
+    for _ in 0..levels {
+        path = match path.parent() {
+            Some(parent) if parent.as_os_str().is_empty() => return ".".to_owned(),
+            Some(parent) => parent,
+            None => break,
+        };
+    }
+
+    path.to_string_lossy().into_owned()
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
@@ -0,0 +1,12 @@
+pub mod attributes;
+pub mod captures;
+pub mod compat;
+pub mod declares;
+pub mod enums;
+pub mod features;
+pub mod functions;
+pub mod includes;
+pub mod scopes;
+pub mod stats;
+pub mod summary;
+pub mod views;
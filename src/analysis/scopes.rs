@@ -0,0 +1,607 @@
+use crate::downcast::downcast;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::arguments::Argument;
+use crate::parser::ast::arguments::ArgumentList;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::loops::ForeachStatementIterator;
+use crate::parser::ast::operators::AssignmentOperationExpression;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::try_block::CatchBlock;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::Expression;
+use crate::parser::ast::GlobalStatement;
+use crate::parser::ast::ListEntry;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StaticPropertyFetchExpression;
+use crate::parser::ast::StaticVar;
+
+// Variables defined in every scope by PHP itself.
+const SUPERGLOBALS: [&[u8]; 9] = [
+    b"$GLOBALS",
+    b"$_SERVER",
+    b"$_GET",
+    b"$_POST",
+    b"$_FILES",
+    b"$_COOKIE",
+    b"$_SESSION",
+    b"$_REQUEST",
+    b"$_ENV",
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ScopeKind {
+    File,
+    Function,
+    Method,
+    Closure,
+    ArrowFunction,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DefinitionKind {
+    // `function foo($a)`
+    Parameter,
+    // `$a = 1`, `[$a, $b] = $c`, `$a[] = 1`, `$a .= 'b'`
+    Assignment,
+    // `$b = &$a`, `function () use (&$a)`, defining `$a` in the enclosing scope
+    Reference,
+    // `foreach ($a as $key => $value)`
+    Foreach,
+    // `catch (Exception $e)`
+    Catch,
+    // `function () use ($a)`
+    Use,
+    // `global $a;`
+    Global,
+    // `static $a;`
+    Static,
+    // `preg_match($pattern, $subject, $matches)`, see [`Scope::undefined`]
+    Argument,
+}
+
+/// A variable being given a value.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Definition {
+    // The name of the variable, including the `$`.
+    pub name: ByteString,
+    pub span: Span,
+    pub kind: DefinitionKind,
+}
+
+/// The variables defined and read in the top level of a file, or in the body of a
+/// function, method, closure or arrow function.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    // The `function` or `fn` keyword starting the scope, `None` for the file.
+    pub span: Option<Span>,
+    // The definitions in the order they appear. An arrow function also has access to
+    // the variables of the enclosing scope defined before it, which are not repeated.
+    pub definitions: Vec<Definition>,
+    // The variables read, excluding `$this`, in the order they appear.
+    pub reads: Vec<SimpleVariable>,
+    // The reads of variables that may not be defined, as no definition of them comes
+    // before the read within the scope. Reads in `isset()`, `empty()`, `unset()` and on
+    // the left of `??` are not included, as they don't require the variable to exist,
+    // and the variables they check are not considered undefined afterwards either.
+    //
+    // Variables passed to a call before being defined are assumed to be taken by
+    // reference, which defines them, as the called function is not known.
+    pub undefined: Vec<SimpleVariable>,
+    // Whether the scope uses variables by a computed name, such as `$$name`, or may
+    // define variables that can not be known, with `extract()`, `parse_str()` without
+    // a result argument, `eval()` or an included file. No reads are considered
+    // undefined in such a scope.
+    pub dynamic: bool,
+}
+
+impl Scope {
+    fn new(kind: ScopeKind, span: Option<Span>) -> Self {
+        Self {
+            kind,
+            span,
+            definitions: vec![],
+            reads: vec![],
+            undefined: vec![],
+            dynamic: false,
+        }
+    }
+
+    /// Whether the variable, including the `$`, is defined anywhere in the scope.
+    pub fn defines(&self, name: &[u8]) -> bool {
+        self.definitions
+            .iter()
+            .any(|definition| definition.name[..] == *name)
+    }
+}
+
+/// The scopes of the program: the file first, followed by every function, method,
+/// closure and arrow function in the order they appear.
+///
+/// ```
+/// use php_parser_rs::analysis::scopes::scopes;
+/// use php_parser_rs::parser;
+///
+/// let program = parser::parse("<?php function foo($a) { return $a + $b; }").unwrap();
+/// let scopes = scopes(&program);
+///
+/// assert_eq!(scopes.len(), 2);
+/// assert_eq!(scopes[1].undefined[0].name, b"$b");
+/// ```
+pub fn scopes(program: &[Statement]) -> Vec<Scope> {
+    let mut scopes = vec![];
+
+    let mut file = Collector::new(&mut scopes, ScopeKind::File, None, vec![]);
+    // defined by the CLI for the script being run.
+    file.known.push(b"$argc".into());
+    file.known.push(b"$argv".into());
+    for statement in program {
+        file.visit(statement);
+    }
+    file.finish();
+
+    scopes
+}
+
+struct Collector<'a> {
+    scopes: &'a mut Vec<Scope>,
+    // The index of the scope being collected in `scopes`.
+    index: usize,
+    // The variables defined or checked so far, including those inherited by an arrow
+    // function.
+    known: Vec<ByteString>,
+    // Whether reads don't require the variable to be defined, as in `isset()`.
+    quiet: bool,
+}
+
+impl<'a> Collector<'a> {
+    fn new(
+        scopes: &'a mut Vec<Scope>,
+        kind: ScopeKind,
+        span: Option<Span>,
+        known: Vec<ByteString>,
+    ) -> Self {
+        scopes.push(Scope::new(kind, span));
+
+        Self {
+            index: scopes.len() - 1,
+            scopes,
+            known,
+            quiet: false,
+        }
+    }
+
+    fn scope(&mut self) -> &mut Scope {
+        &mut self.scopes[self.index]
+    }
+
+    fn finish(mut self) {
+        let scope = self.scope();
+        if scope.dynamic {
+            scope.undefined.clear();
+        }
+    }
+
+    // Collect a nested scope, with the given parameters and known variables.
+    fn nested<'n>(
+        &mut self,
+        kind: ScopeKind,
+        span: Span,
+        parameters: impl Iterator<Item = &'n SimpleVariable>,
+        known: Vec<ByteString>,
+        body: &[&dyn Node],
+    ) -> Collector<'_> {
+        let mut nested = Collector::new(self.scopes, kind, Some(span), known);
+        for parameter in parameters {
+            nested.define(parameter, DefinitionKind::Parameter);
+        }
+
+        for node in body {
+            nested.visit(*node);
+        }
+
+        nested
+    }
+
+    fn visit(&mut self, node: &dyn Node) {
+        if let Some(variable) = downcast::<SimpleVariable>(node) {
+            return self.read(variable);
+        }
+
+        if let Some(variable) = downcast::<Variable>(node) {
+            if !matches!(variable, Variable::SimpleVariable(_)) {
+                self.scope().dynamic = true;
+            }
+        }
+
+        if let Some(expression) = downcast::<Expression>(node) {
+            if self.expression(expression) {
+                return;
+            }
+        }
+
+        if let Some(arguments) = downcast::<ArgumentList>(node) {
+            return self.arguments(arguments);
+        }
+
+        if let Some(function) = downcast::<FunctionStatement>(node) {
+            let statements = statements(&function.body.statements);
+
+            return self
+                .nested(
+                    ScopeKind::Function,
+                    function.function,
+                    parameter_names(&function.parameters),
+                    vec![],
+                    &statements,
+                )
+                .finish();
+        }
+
+        if let Some(method) = downcast::<ConcreteMethod>(node) {
+            let statements = statements(&method.body.statements);
+
+            return self
+                .nested(
+                    ScopeKind::Method,
+                    method.function,
+                    parameter_names(&method.parameters),
+                    vec![],
+                    &statements,
+                )
+                .finish();
+        }
+
+        if let Some(constructor) = downcast::<ConcreteConstructor>(node) {
+            let statements = statements(&constructor.body.statements);
+            let parameters = constructor
+                .parameters
+                .parameters
+                .iter()
+                .map(|parameter| &parameter.name);
+
+            return self
+                .nested(
+                    ScopeKind::Method,
+                    constructor.function,
+                    parameters,
+                    vec![],
+                    &statements,
+                )
+                .finish();
+        }
+
+        if let Some(iterator) = downcast::<ForeachStatementIterator>(node) {
+            return match iterator {
+                ForeachStatementIterator::Value {
+                    expression, value, ..
+                } => {
+                    self.visit(expression);
+                    self.assign(value, DefinitionKind::Foreach);
+                }
+                ForeachStatementIterator::KeyAndValue {
+                    expression,
+                    key,
+                    value,
+                    ..
+                } => {
+                    self.visit(expression);
+                    self.assign(key, DefinitionKind::Foreach);
+                    self.assign(value, DefinitionKind::Foreach);
+                }
+            };
+        }
+
+        if let Some(catch) = downcast::<CatchBlock>(node) {
+            if let Some(variable) = &catch.var {
+                self.define(variable, DefinitionKind::Catch);
+            }
+
+            for statement in &catch.body {
+                self.visit(statement);
+            }
+
+            return;
+        }
+
+        if let Some(global) = downcast::<GlobalStatement>(node) {
+            for variable in &global.variables {
+                match variable {
+                    Variable::SimpleVariable(variable) => {
+                        self.define(variable, DefinitionKind::Global)
+                    }
+                    variable => self.visit(variable),
+                }
+            }
+
+            return;
+        }
+
+        if let Some(r#static) = downcast::<StaticVar>(node) {
+            if let Some(default) = &r#static.default {
+                self.visit(default);
+            }
+
+            match &r#static.var {
+                Variable::SimpleVariable(variable) => self.define(variable, DefinitionKind::Static),
+                variable => self.visit(variable),
+            }
+
+            return;
+        }
+
+        // `Foo::$bar` is a property, not a variable.
+        if let Some(fetch) = downcast::<StaticPropertyFetchExpression>(node) {
+            self.visit(fetch.target.as_ref());
+            if !matches!(fetch.property, Variable::SimpleVariable(_)) {
+                self.visit(&fetch.property);
+            }
+
+            return;
+        }
+
+        // neither are the properties of a class.
+        if downcast::<PropertyEntry>(node).is_some() {
+            return;
+        }
+
+        for child in node.children_ref() {
+            self.visit(child);
+        }
+    }
+
+    // Visit the expressions that define variables, or change how the variables within
+    // them are read, returning whether the expression was visited.
+    fn expression(&mut self, expression: &Expression) -> bool {
+        match expression {
+            Expression::AssignmentOperation(AssignmentOperationExpression::Assign {
+                left,
+                right,
+                ..
+            }) => {
+                self.visit(right.as_ref());
+                self.assign(left, DefinitionKind::Assignment);
+            }
+            Expression::AssignmentOperation(AssignmentOperationExpression::Coalesce {
+                left,
+                right,
+                ..
+            }) => {
+                self.quietly(left.as_ref());
+                self.visit(right.as_ref());
+                self.assign(left, DefinitionKind::Assignment);
+            }
+            Expression::AssignmentOperation(operation) => {
+                self.visit(operation.left());
+                self.visit(operation.right());
+                self.assign(operation.left(), DefinitionKind::Assignment);
+            }
+            Expression::Reference(reference) => {
+                self.assign(&reference.right, DefinitionKind::Reference);
+            }
+            Expression::Isset(isset) => self.quietly(isset),
+            Expression::Empty(empty) => self.quietly(empty),
+            Expression::Unset(unset) => self.quietly(unset),
+            Expression::Coalesce(coalesce) => {
+                self.quietly(coalesce.lhs.as_ref());
+                self.visit(coalesce.rhs.as_ref());
+            }
+            Expression::Eval(_)
+            | Expression::Include(_)
+            | Expression::IncludeOnce(_)
+            | Expression::Require(_)
+            | Expression::RequireOnce(_) => {
+                self.scope().dynamic = true;
+
+                return false;
+            }
+            Expression::FunctionCall(call) => {
+                if let Expression::Identifier(Identifier::SimpleIdentifier(identifier)) =
+                    call.target.as_ref()
+                {
+                    let name = identifier
+                        .value
+                        .strip_prefix(b"\\")
+                        .unwrap_or(&identifier.value);
+                    let arguments = call.arguments.arguments.len();
+
+                    if name.eq_ignore_ascii_case(b"extract")
+                        || (name.eq_ignore_ascii_case(b"parse_str") && arguments < 2)
+                    {
+                        self.scope().dynamic = true;
+                    }
+                }
+
+                return false;
+            }
+            Expression::Closure(closure) => self.closure(closure),
+            Expression::ArrowFunction(function) => {
+                let body: [&dyn Node; 1] = [function.body.as_ref()];
+
+                self.nested(
+                    ScopeKind::ArrowFunction,
+                    function.r#fn,
+                    parameter_names(&function.parameters),
+                    self.known.clone(),
+                    &body,
+                )
+                .finish();
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn closure(&mut self, closure: &ClosureExpression) {
+        let mut uses = vec![];
+        if let Some(r#use) = &closure.uses {
+            for variable in r#use.variables.iter() {
+                if variable.ampersand.is_some() {
+                    self.define(&variable.variable, DefinitionKind::Reference);
+                } else {
+                    self.read(&variable.variable);
+                }
+
+                uses.push(&variable.variable);
+            }
+        }
+
+        let statements = statements(&closure.body.statements);
+        let mut nested = self.nested(
+            ScopeKind::Closure,
+            closure.function,
+            parameter_names(&closure.parameters),
+            vec![],
+            &[],
+        );
+
+        for variable in uses {
+            nested.define(variable, DefinitionKind::Use);
+        }
+
+        for statement in statements {
+            nested.visit(statement);
+        }
+
+        nested.finish();
+    }
+
+    fn arguments(&mut self, arguments: &ArgumentList) {
+        for argument in arguments.arguments.iter() {
+            let (ellipsis, value) = match argument {
+                Argument::Positional(argument) => (argument.ellipsis, &argument.value),
+                Argument::Named(argument) => (argument.ellipsis, &argument.value),
+            };
+
+            match value {
+                Expression::Variable(Variable::SimpleVariable(variable))
+                    if ellipsis.is_none() && !self.quiet && !self.is_known(&variable.name) =>
+                {
+                    self.define(variable, DefinitionKind::Argument)
+                }
+                value => self.visit(value),
+            }
+        }
+    }
+
+    // Define the variables written to by assigning to the expression.
+    fn assign(&mut self, target: &Expression, kind: DefinitionKind) {
+        match target {
+            Expression::Variable(Variable::SimpleVariable(variable)) => self.define(variable, kind),
+            // `$a[] = 1` creates the array if `$a` is not defined.
+            Expression::ArrayIndex(index) => {
+                if let Some(index) = &index.index {
+                    self.visit(index.as_ref());
+                }
+
+                self.assign(&index.array, kind);
+            }
+            Expression::List(list) => {
+                for entry in &list.items {
+                    match entry {
+                        ListEntry::Skipped => {}
+                        ListEntry::Value { value } | ListEntry::ReferencedValue { value, .. } => {
+                            self.assign(value, kind)
+                        }
+                        ListEntry::KeyValue { key, value, .. }
+                        | ListEntry::ReferencedKeyValue { key, value, .. } => {
+                            self.visit(key);
+                            self.assign(value, kind);
+                        }
+                    }
+                }
+            }
+            Expression::ShortArray(_) | Expression::Array(_) => {
+                let items = match target {
+                    Expression::ShortArray(array) => &array.items,
+                    Expression::Array(array) => &array.items,
+                    _ => unreachable!(),
+                };
+
+                for item in items.iter() {
+                    match item {
+                        ArrayItem::Skipped => {}
+                        ArrayItem::Value { value } | ArrayItem::ReferencedValue { value, .. } => {
+                            self.assign(value, kind)
+                        }
+                        ArrayItem::KeyValue { key, value, .. }
+                        | ArrayItem::ReferencedKeyValue { key, value, .. } => {
+                            self.visit(key);
+                            self.assign(value, kind);
+                        }
+                        item => self.visit(item),
+                    }
+                }
+            }
+            Expression::Reference(reference) => {
+                self.assign(&reference.right, DefinitionKind::Reference)
+            }
+            target => self.visit(target),
+        }
+    }
+
+    fn define(&mut self, variable: &SimpleVariable, kind: DefinitionKind) {
+        self.scope().definitions.push(Definition {
+            name: variable.name.clone(),
+            span: variable.span,
+            kind,
+        });
+
+        if !self.is_known(&variable.name) {
+            self.known.push(variable.name.clone());
+        }
+    }
+
+    fn read(&mut self, variable: &SimpleVariable) {
+        if variable.name == b"$this" {
+            return;
+        }
+
+        let known = self.is_known(&variable.name);
+        if self.quiet && !known {
+            self.known.push(variable.name.clone());
+        }
+
+        let undefined = !known && !self.quiet && !SUPERGLOBALS.contains(&&variable.name[..]);
+
+        let scope = self.scope();
+        scope.reads.push(variable.clone());
+        if undefined {
+            scope.undefined.push(variable.clone());
+        }
+    }
+
+    fn quietly(&mut self, node: &dyn Node) {
+        let quiet = std::mem::replace(&mut self.quiet, true);
+        self.visit(node);
+        self.quiet = quiet;
+    }
+
+    fn is_known(&self, name: &ByteString) -> bool {
+        self.known.contains(name)
+    }
+}
+
+fn parameter_names(parameters: &FunctionParameterList) -> impl Iterator<Item = &SimpleVariable> {
+    parameters
+        .parameters
+        .iter()
+        .map(|parameter| &parameter.name)
+}
+
+fn statements(statements: &[Statement]) -> Vec<&dyn Node> {
+    statements
+        .iter()
+        .map(|statement| statement as &dyn Node)
+        .collect()
+}
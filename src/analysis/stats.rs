@@ -0,0 +1,61 @@
+use std::collections::HashSet;
+
+use crate::parser::ast::literals::LiteralString;
+use crate::parser::ast::Program;
+use crate::traverser::walk;
+use crate::traverser::Visitor;
+
+/// How much memory the string literals of a program take up, and how much of it is
+/// saved by interning them.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct ProgramStats {
+    pub string_literals: usize,
+    // The total length of the string literals, as if each had its own storage.
+    pub string_literal_bytes: usize,
+    // The number of distinct allocations backing the string literals.
+    pub string_literal_allocations: usize,
+    // The bytes not allocated because a literal shares the storage of another.
+    pub shared_bytes: usize,
+}
+
+/// Provides [`Stats::stats`] on a [`Program`].
+pub trait Stats {
+    fn stats(&self) -> ProgramStats;
+}
+
+impl Stats for Program {
+    /// Count the string literals of the program, and how many share their storage
+    /// because of [`ParserConfig::intern_literals`](crate::ParserConfig::intern_literals).
+    fn stats(&self) -> ProgramStats {
+        let mut counter = Counter::default();
+        for statement in self {
+            walk(&mut counter, statement);
+        }
+
+        counter.stats
+    }
+}
+
+#[derive(Default)]
+struct Counter {
+    stats: ProgramStats,
+    allocations: HashSet<*const u8>,
+}
+
+impl Visitor for Counter {
+    fn enter_literal_string(&mut self, literal: &LiteralString) {
+        self.stats.string_literals += 1;
+        self.stats.string_literal_bytes += literal.value.len();
+
+        // empty strings don't allocate.
+        if literal.value.is_empty() {
+            return;
+        }
+
+        if self.allocations.insert(literal.value.as_ptr()) {
+            self.stats.string_literal_allocations += 1;
+        } else {
+            self.stats.shared_bytes += literal.value.len();
+        }
+    }
+}
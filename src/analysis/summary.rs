@@ -0,0 +1,581 @@
+use crate::docblock::docblock;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::comments::CommentGroup;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::BackedEnumType;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::functions::AbstractConstructor;
+use crate::parser::ast::functions::AbstractMethod;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::ConstructorParameterList;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::functions::ReturnType;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::modifiers::MethodModifierGroup;
+use crate::parser::ast::modifiers::Visibility;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::properties::Property;
+use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::Statement;
+
+/// How important a line of the outline is. When the outline does not fit in the
+/// byte budget, the least important lines are dropped first.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Detail {
+    // Namespaces, class-likes and functions.
+    Declaration,
+    // Methods, properties, constants and enum cases.
+    Member,
+    // Docblock summaries and statement counts.
+    Annotation,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OutlineLine {
+    pub depth: usize,
+    pub detail: Detail,
+    pub text: String,
+}
+
+/// A structural outline of a program.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Outline {
+    pub lines: Vec<OutlineLine>,
+}
+
+impl Outline {
+    pub fn new(program: &[Statement]) -> Self {
+        let mut outline = Self::default();
+        outline.statements(0, program);
+        outline
+    }
+
+    /// Render the outline, dropping the least important lines until it fits within
+    /// `budget` bytes. If even the declarations don't fit, the output is cut at a line
+    /// boundary and ends with a marker stating how many lines were omitted.
+    pub fn render(&self, budget: usize) -> String {
+        for detail in [Detail::Annotation, Detail::Member, Detail::Declaration] {
+            let rendered = self.render_detail(detail);
+            if rendered.len() <= budget {
+                return rendered;
+            }
+        }
+
+        let lines = self
+            .lines
+            .iter()
+            .filter(|line| line.detail == Detail::Declaration)
+            .collect::<Vec<&OutlineLine>>();
+
+        let mut output = String::new();
+        for (position, line) in lines.iter().enumerate() {
+            let rendered = render_line(line);
+            let marker = format!("... ({} more)\n", lines.len() - position);
+            if output.len() + rendered.len() + marker.len() > budget {
+                if output.len() + marker.len() <= budget {
+                    output.push_str(&marker);
+                }
+
+                break;
+            }
+
+            output.push_str(&rendered);
+        }
+
+        output
+    }
+
+    fn render_detail(&self, detail: Detail) -> String {
+        self.lines
+            .iter()
+            .filter(|line| line.detail <= detail)
+            .map(render_line)
+            .collect()
+    }
+
+    fn push(&mut self, depth: usize, detail: Detail, text: String) {
+        self.lines.push(OutlineLine {
+            depth,
+            detail,
+            text,
+        });
+    }
+
+    fn statements(&mut self, depth: usize, statements: &[Statement]) {
+        for statement in statements {
+            match statement {
+                Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                    self.push(
+                        depth,
+                        Detail::Declaration,
+                        format!("namespace {}", namespace.name),
+                    );
+                    self.statements(depth + 1, &namespace.statements);
+                }
+                Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                    let name = match &namespace.name {
+                        Some(name) => format!("namespace {}", name),
+                        None => "namespace".to_owned(),
+                    };
+
+                    self.push(depth, Detail::Declaration, name);
+                    self.statements(depth + 1, &namespace.body.statements);
+                }
+                Statement::Function(function) => {
+                    self.docblock(depth, &function.comments);
+                    self.push(
+                        depth,
+                        Detail::Declaration,
+                        format!(
+                            "function {}{}",
+                            function.name,
+                            signature(&function.parameters, &function.return_type)
+                        ),
+                    );
+                    self.statement_count(depth + 1, function.body.statements.len());
+                }
+                Statement::Constant(constant) => {
+                    self.docblock(depth, &constant.comments);
+                    for entry in &constant.entries {
+                        self.push(depth, Detail::Declaration, format!("const {}", entry.name));
+                    }
+                }
+                Statement::Class(class) => self.class(depth, class),
+                Statement::Interface(interface) => {
+                    let mut text = format!("interface {}", interface.name);
+                    if let Some(extends) = &interface.extends {
+                        text.push_str(&format!(" extends {}", names(&extends.parents.inner)));
+                    }
+
+                    self.push(depth, Detail::Declaration, text);
+                    for member in &interface.body.members {
+                        match member {
+                            InterfaceMember::Constant(constant) => {
+                                self.classish_constant(depth + 1, constant)
+                            }
+                            InterfaceMember::Method(method) => {
+                                self.abstract_method(depth + 1, method)
+                            }
+                            InterfaceMember::Constructor(constructor) => {
+                                self.abstract_constructor(depth + 1, constructor)
+                            }
+                        }
+                    }
+                }
+                Statement::Trait(r#trait) => {
+                    self.push(
+                        depth,
+                        Detail::Declaration,
+                        format!("trait {}", r#trait.name),
+                    );
+                    for member in &r#trait.body.members {
+                        match member {
+                            TraitMember::Constant(constant) => {
+                                self.classish_constant(depth + 1, constant)
+                            }
+                            TraitMember::TraitUsage(usage) => self.trait_usage(depth + 1, usage),
+                            TraitMember::Property(property) => self.property(depth + 1, property),
+                            TraitMember::VariableProperty(property) => {
+                                self.variable_property(depth + 1, property)
+                            }
+                            TraitMember::AbstractMethod(method) => {
+                                self.abstract_method(depth + 1, method)
+                            }
+                            TraitMember::AbstractConstructor(constructor) => {
+                                self.abstract_constructor(depth + 1, constructor)
+                            }
+                            TraitMember::ConcreteMethod(method) => {
+                                self.concrete_method(depth + 1, method)
+                            }
+                            TraitMember::ConcreteConstructor(constructor) => {
+                                self.concrete_constructor(depth + 1, constructor)
+                            }
+                        }
+                    }
+                }
+                Statement::UnitEnum(r#enum) => {
+                    let mut text = format!("enum {}", r#enum.name);
+                    if !r#enum.implements.is_empty() {
+                        text.push_str(&format!(" implements {}", names(&r#enum.implements)));
+                    }
+
+                    self.push(depth, Detail::Declaration, text);
+                    for member in &r#enum.body.members {
+                        match member {
+                            UnitEnumMember::Case(case) => {
+                                self.push(depth + 1, Detail::Member, format!("case {}", case.name))
+                            }
+                            UnitEnumMember::Method(method) => {
+                                self.concrete_method(depth + 1, method)
+                            }
+                            UnitEnumMember::Constant(constant) => {
+                                self.classish_constant(depth + 1, constant)
+                            }
+                            UnitEnumMember::TraitUsage(usage) => self.trait_usage(depth + 1, usage),
+                        }
+                    }
+                }
+                Statement::BackedEnum(r#enum) => {
+                    let backed_type = match r#enum.backed_type {
+                        BackedEnumType::String(..) => "string",
+                        BackedEnumType::Int(..) => "int",
+                    };
+                    let mut text = format!("enum {}: {}", r#enum.name, backed_type);
+                    if !r#enum.implements.is_empty() {
+                        text.push_str(&format!(" implements {}", names(&r#enum.implements)));
+                    }
+
+                    self.push(depth, Detail::Declaration, text);
+                    for member in &r#enum.body.members {
+                        match member {
+                            BackedEnumMember::Case(case) => {
+                                self.push(depth + 1, Detail::Member, format!("case {}", case.name))
+                            }
+                            BackedEnumMember::Method(method) => {
+                                self.concrete_method(depth + 1, method)
+                            }
+                            BackedEnumMember::Constant(constant) => {
+                                self.classish_constant(depth + 1, constant)
+                            }
+                            BackedEnumMember::TraitUsage(usage) => {
+                                self.trait_usage(depth + 1, usage)
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn class(&mut self, depth: usize, class: &ClassStatement) {
+        let mut text = String::new();
+        if class.modifiers.has_abstract() {
+            text.push_str("abstract ");
+        }
+        if class.modifiers.has_final() {
+            text.push_str("final ");
+        }
+        if class.modifiers.has_readonly() {
+            text.push_str("readonly ");
+        }
+
+        text.push_str(&format!("class {}", class.name));
+        if let Some(extends) = &class.extends {
+            text.push_str(&format!(" extends {}", extends.parent));
+        }
+        if let Some(implements) = &class.implements {
+            text.push_str(&format!(
+                " implements {}",
+                names(&implements.interfaces.inner)
+            ));
+        }
+
+        self.push(depth, Detail::Declaration, text);
+        for member in &class.body.members {
+            self.class_member(depth + 1, member);
+        }
+    }
+
+    fn class_member(&mut self, depth: usize, member: &ClassMember) {
+        match member {
+            ClassMember::Constant(constant) => self.classish_constant(depth, constant),
+            ClassMember::TraitUsage(usage) => self.trait_usage(depth, usage),
+            ClassMember::Property(property) => self.property(depth, property),
+            ClassMember::VariableProperty(property) => self.variable_property(depth, property),
+            ClassMember::AbstractMethod(method) => self.abstract_method(depth, method),
+            ClassMember::AbstractConstructor(constructor) => {
+                self.abstract_constructor(depth, constructor)
+            }
+            ClassMember::ConcreteMethod(method) => self.concrete_method(depth, method),
+            ClassMember::ConcreteConstructor(constructor) => {
+                self.concrete_constructor(depth, constructor)
+            }
+        }
+    }
+
+    fn trait_usage(&mut self, depth: usize, usage: &TraitUsage) {
+        self.push(
+            depth,
+            Detail::Member,
+            format!("use {}", names(&usage.traits)),
+        )
+    }
+
+    fn abstract_method(&mut self, depth: usize, method: &AbstractMethod) {
+        self.method(
+            depth,
+            &method.comments,
+            &method.modifiers,
+            &method.name.to_string(),
+            signature(&method.parameters, &method.return_type),
+            None,
+        )
+    }
+
+    fn abstract_constructor(&mut self, depth: usize, constructor: &AbstractConstructor) {
+        self.method(
+            depth,
+            &constructor.comments,
+            &constructor.modifiers,
+            &constructor.name.to_string(),
+            signature(&constructor.parameters, &None),
+            None,
+        )
+    }
+
+    fn concrete_method(&mut self, depth: usize, method: &ConcreteMethod) {
+        self.method(
+            depth,
+            &method.comments,
+            &method.modifiers,
+            &method.name.to_string(),
+            signature(&method.parameters, &method.return_type),
+            Some(method.body.statements.len()),
+        )
+    }
+
+    fn concrete_constructor(&mut self, depth: usize, constructor: &ConcreteConstructor) {
+        self.method(
+            depth,
+            &constructor.comments,
+            &constructor.modifiers,
+            &constructor.name.to_string(),
+            constructor_signature(&constructor.parameters),
+            Some(constructor.body.statements.len()),
+        )
+    }
+
+    fn method(
+        &mut self,
+        depth: usize,
+        comments: &CommentGroup,
+        modifiers: &MethodModifierGroup,
+        name: &str,
+        signature: String,
+        statements: Option<usize>,
+    ) {
+        self.docblock(depth, comments);
+
+        let mut text = visibility(&modifiers.visibility()).to_owned();
+        if modifiers.has_abstract() {
+            text.push_str(" abstract");
+        }
+        if modifiers.has_final() {
+            text.push_str(" final");
+        }
+        if modifiers.has_static() {
+            text.push_str(" static");
+        }
+
+        self.push(
+            depth,
+            Detail::Member,
+            format!("{} function {}{}", text, name, signature),
+        );
+
+        if let Some(statements) = statements {
+            self.statement_count(depth + 1, statements);
+        }
+    }
+
+    fn property(&mut self, depth: usize, property: &Property) {
+        let mut text = visibility(&property.modifiers.visibility()).to_owned();
+        if property.modifiers.has_static() {
+            text.push_str(" static");
+        }
+        if property.modifiers.has_readonly() {
+            text.push_str(" readonly");
+        }
+
+        for entry in &property.entries {
+            self.push(
+                depth,
+                Detail::Member,
+                format!(
+                    "{} {}{}",
+                    text,
+                    optional_type(&property.r#type),
+                    entry.variable()
+                ),
+            );
+        }
+    }
+
+    fn variable_property(&mut self, depth: usize, property: &VariableProperty) {
+        for entry in &property.entries {
+            self.push(
+                depth,
+                Detail::Member,
+                format!(
+                    "var {}{}",
+                    optional_type(&property.r#type),
+                    entry.variable()
+                ),
+            );
+        }
+    }
+
+    fn classish_constant(&mut self, depth: usize, constant: &ClassishConstant) {
+        self.docblock(depth, &constant.comments);
+        for entry in &constant.entries {
+            self.push(
+                depth,
+                Detail::Member,
+                format!(
+                    "{} const {}",
+                    visibility(&constant.modifiers.visibility()),
+                    entry.name
+                ),
+            );
+        }
+    }
+
+    fn docblock(&mut self, depth: usize, comments: &CommentGroup) {
+        let summary = docblock(comments).and_then(|content| docblock_summary(content));
+
+        if let Some(summary) = summary {
+            self.push(depth, Detail::Annotation, format!("// {}", summary));
+        }
+    }
+
+    fn statement_count(&mut self, depth: usize, statements: usize) {
+        self.push(
+            depth,
+            Detail::Annotation,
+            format!(
+                "// {} statement{}",
+                statements,
+                if statements == 1 { "" } else { "s" }
+            ),
+        );
+    }
+}
+
+/// Produce a structural outline of the given program that is at most `budget` bytes long.
+pub fn summarize(program: &[Statement], budget: usize) -> String {
+    Outline::new(program).render(budget)
+}
+
+fn render_line(line: &OutlineLine) -> String {
+    format!("{}{}\n", "  ".repeat(line.depth), line.text)
+}
+
+/// The first line of a docblock that is not empty and not a tag.
+fn docblock_summary(content: &[u8]) -> Option<String> {
+    let content = String::from_utf8_lossy(content);
+    let content = content.trim_start_matches("/**").trim_end_matches("*/");
+
+    content
+        .lines()
+        .map(|line| line.trim().trim_start_matches('*').trim())
+        .find(|line| !line.is_empty())
+        .filter(|line| !line.starts_with('@'))
+        .map(|line| line.to_owned())
+}
+
+fn names<T: std::fmt::Display>(names: &[T]) -> String {
+    names
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn visibility(visibility: &Visibility) -> &'static str {
+    match visibility {
+        Visibility::Public => "public",
+        Visibility::Protected => "protected",
+        Visibility::Private => "private",
+    }
+}
+
+fn optional_type(r#type: &Option<Type>) -> String {
+    match r#type {
+        Some(r#type) => format!("{} ", r#type),
+        None => String::new(),
+    }
+}
+
+fn signature(parameters: &FunctionParameterList, return_type: &Option<ReturnType>) -> String {
+    let parameters = parameters
+        .parameters
+        .inner
+        .iter()
+        .map(|parameter| {
+            format!(
+                "{}{}{}{}{}",
+                optional_type(&parameter.data_type),
+                if parameter.ampersand.is_some() {
+                    "&"
+                } else {
+                    ""
+                },
+                if parameter.ellipsis.is_some() {
+                    "..."
+                } else {
+                    ""
+                },
+                parameter.name,
+                if parameter.default.is_some() {
+                    " = ..."
+                } else {
+                    ""
+                },
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    match return_type {
+        Some(return_type) => format!("({}): {}", parameters, return_type.data_type),
+        None => format!("({})", parameters),
+    }
+}
+
+fn constructor_signature(parameters: &ConstructorParameterList) -> String {
+    let parameters = parameters
+        .parameters
+        .inner
+        .iter()
+        .map(|parameter| {
+            let modifiers = parameter
+                .modifiers
+                .modifiers
+                .iter()
+                .map(|modifier| format!("{} ", modifier))
+                .collect::<String>();
+
+            format!(
+                "{}{}{}{}{}{}",
+                modifiers,
+                optional_type(&parameter.data_type),
+                if parameter.ampersand.is_some() {
+                    "&"
+                } else {
+                    ""
+                },
+                if parameter.ellipsis.is_some() {
+                    "..."
+                } else {
+                    ""
+                },
+                parameter.name,
+                if parameter.default.is_some() {
+                    " = ..."
+                } else {
+                    ""
+                },
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    format!("({})", parameters)
+}
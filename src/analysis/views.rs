@@ -0,0 +1,491 @@
+use serde::Serialize;
+
+use crate::analysis::attributes::AttributeFlags;
+use crate::lexer::token::Span;
+use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::comments::CommentGroup;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::functions::AbstractConstructor;
+use crate::parser::ast::functions::AbstractMethod;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::ConstructorParameter;
+use crate::parser::ast::functions::FunctionParameter;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::interfaces::InterfaceStatement;
+use crate::parser::ast::modifiers::MethodModifierGroup;
+use crate::parser::ast::modifiers::Visibility;
+use crate::parser::ast::properties::Property;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitStatement;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::Expression;
+use crate::parser::ast::Statement;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum ClassLikeKind {
+    Class,
+    Interface,
+    Trait,
+    Enum,
+}
+
+/// A read-only view over a class-like declaration, with the information
+/// most analyses need already extracted from the AST.
+#[derive(Debug, Clone)]
+pub struct ClassLikeView<'a> {
+    pub kind: ClassLikeKind,
+    pub comments: &'a CommentGroup,
+    pub name: &'a SimpleIdentifier,
+    pub attributes: &'a [AttributeGroup],
+    pub flags: AttributeFlags,
+    // `extends Foo`, only ever set for classes.
+    pub parent: Option<&'a SimpleIdentifier>,
+    // `implements Foo` for classes and enums, `extends Foo` for interfaces.
+    pub interfaces: Vec<&'a SimpleIdentifier>,
+    // `use Foo;`
+    pub traits: Vec<&'a SimpleIdentifier>,
+    // `final class Foo`, only ever set for classes.
+    pub is_final: bool,
+    pub constants: Vec<&'a ClassishConstant>,
+    // Properties, including promoted constructor properties.
+    pub properties: Vec<PropertyView<'a>>,
+    pub methods: Vec<MethodView<'a>>,
+}
+
+impl<'a> ClassLikeView<'a> {
+    /// Create a view for the given statement, if it is a class-like declaration.
+    pub fn from_statement(statement: &'a Statement) -> Option<Self> {
+        match statement {
+            Statement::Class(class) => Some(Self::from_class(class)),
+            Statement::Interface(interface) => Some(Self::from_interface(interface)),
+            Statement::Trait(r#trait) => Some(Self::from_trait(r#trait)),
+            Statement::UnitEnum(r#enum) => Some(Self::from_unit_enum(r#enum)),
+            Statement::BackedEnum(r#enum) => Some(Self::from_backed_enum(r#enum)),
+            _ => None,
+        }
+    }
+
+    pub fn from_class(class: &'a ClassStatement) -> Self {
+        let mut view = Self::new(
+            ClassLikeKind::Class,
+            &class.comments,
+            &class.name,
+            &class.attributes,
+        );
+        view.parent = class.extends.as_ref().map(|extends| &extends.parent);
+        view.is_final = class.modifiers.has_final();
+        if let Some(implements) = &class.implements {
+            view.interfaces = implements.interfaces.inner.iter().collect();
+        }
+
+        for member in &class.body.members {
+            match member {
+                ClassMember::TraitUsage(usage) => view.traits.extend(usage.traits.iter()),
+                ClassMember::Constant(constant) => view.constants.push(constant),
+                ClassMember::Property(property) => view
+                    .properties
+                    .extend(PropertyView::from_property(property)),
+                ClassMember::VariableProperty(property) => view
+                    .properties
+                    .extend(PropertyView::from_variable_property(property)),
+                ClassMember::AbstractMethod(method) => {
+                    view.methods.push(MethodView::from_abstract_method(method))
+                }
+                ClassMember::AbstractConstructor(constructor) => view
+                    .methods
+                    .push(MethodView::from_abstract_constructor(constructor)),
+                ClassMember::ConcreteMethod(method) => {
+                    view.methods.push(MethodView::from_concrete_method(method))
+                }
+                ClassMember::ConcreteConstructor(constructor) => {
+                    view.properties
+                        .extend(PropertyView::from_promoted_parameters(constructor));
+                    view.methods
+                        .push(MethodView::from_concrete_constructor(constructor))
+                }
+            }
+        }
+
+        view
+    }
+
+    pub fn from_interface(interface: &'a InterfaceStatement) -> Self {
+        let mut view = Self::new(
+            ClassLikeKind::Interface,
+            &interface.comments,
+            &interface.name,
+            &interface.attributes,
+        );
+        if let Some(extends) = &interface.extends {
+            view.interfaces = extends.parents.inner.iter().collect();
+        }
+
+        for member in &interface.body.members {
+            match member {
+                InterfaceMember::Method(method) => {
+                    view.methods.push(MethodView::from_abstract_method(method))
+                }
+                InterfaceMember::Constructor(constructor) => view
+                    .methods
+                    .push(MethodView::from_abstract_constructor(constructor)),
+                InterfaceMember::Constant(constant) => view.constants.push(constant),
+            }
+        }
+
+        view
+    }
+
+    pub fn from_trait(r#trait: &'a TraitStatement) -> Self {
+        let mut view = Self::new(
+            ClassLikeKind::Trait,
+            &r#trait.comments,
+            &r#trait.name,
+            &r#trait.attributes,
+        );
+
+        for member in &r#trait.body.members {
+            match member {
+                TraitMember::TraitUsage(usage) => view.traits.extend(usage.traits.iter()),
+                TraitMember::Constant(constant) => view.constants.push(constant),
+                TraitMember::Property(property) => view
+                    .properties
+                    .extend(PropertyView::from_property(property)),
+                TraitMember::VariableProperty(property) => view
+                    .properties
+                    .extend(PropertyView::from_variable_property(property)),
+                TraitMember::AbstractMethod(method) => {
+                    view.methods.push(MethodView::from_abstract_method(method))
+                }
+                TraitMember::AbstractConstructor(constructor) => view
+                    .methods
+                    .push(MethodView::from_abstract_constructor(constructor)),
+                TraitMember::ConcreteMethod(method) => {
+                    view.methods.push(MethodView::from_concrete_method(method))
+                }
+                TraitMember::ConcreteConstructor(constructor) => {
+                    view.properties
+                        .extend(PropertyView::from_promoted_parameters(constructor));
+                    view.methods
+                        .push(MethodView::from_concrete_constructor(constructor))
+                }
+            }
+        }
+
+        view
+    }
+
+    pub fn from_unit_enum(r#enum: &'a UnitEnumStatement) -> Self {
+        let mut view = Self::new(
+            ClassLikeKind::Enum,
+            &r#enum.comments,
+            &r#enum.name,
+            &r#enum.attributes,
+        );
+        view.interfaces = r#enum.implements.iter().collect();
+
+        for member in &r#enum.body.members {
+            match member {
+                UnitEnumMember::TraitUsage(usage) => view.traits.extend(usage.traits.iter()),
+                UnitEnumMember::Constant(constant) => view.constants.push(constant),
+                UnitEnumMember::Method(method) => {
+                    view.methods.push(MethodView::from_concrete_method(method))
+                }
+                _ => {}
+            }
+        }
+
+        view
+    }
+
+    pub fn from_backed_enum(r#enum: &'a BackedEnumStatement) -> Self {
+        let mut view = Self::new(
+            ClassLikeKind::Enum,
+            &r#enum.comments,
+            &r#enum.name,
+            &r#enum.attributes,
+        );
+        view.interfaces = r#enum.implements.iter().collect();
+
+        for member in &r#enum.body.members {
+            match member {
+                BackedEnumMember::TraitUsage(usage) => view.traits.extend(usage.traits.iter()),
+                BackedEnumMember::Constant(constant) => view.constants.push(constant),
+                BackedEnumMember::Method(method) => {
+                    view.methods.push(MethodView::from_concrete_method(method))
+                }
+                _ => {}
+            }
+        }
+
+        view
+    }
+
+    /// Find a method by name, using PHP's case-insensitive comparison.
+    pub fn method(&self, name: &[u8]) -> Option<&MethodView<'a>> {
+        self.methods
+            .iter()
+            .find(|method| method.name.value.eq_ignore_ascii_case(name))
+    }
+
+    fn new(
+        kind: ClassLikeKind,
+        comments: &'a CommentGroup,
+        name: &'a SimpleIdentifier,
+        attributes: &'a [AttributeGroup],
+    ) -> Self {
+        Self {
+            kind,
+            comments,
+            name,
+            attributes,
+            flags: AttributeFlags::from_groups(attributes),
+            parent: None,
+            interfaces: vec![],
+            traits: vec![],
+            is_final: false,
+            constants: vec![],
+            properties: vec![],
+            methods: vec![],
+        }
+    }
+}
+
+/// A read-only view over a method or constructor declaration.
+#[derive(Debug, Clone)]
+pub struct MethodView<'a> {
+    pub comments: &'a CommentGroup,
+    pub name: &'a SimpleIdentifier,
+    pub attributes: &'a [AttributeGroup],
+    pub flags: AttributeFlags,
+    pub modifiers: &'a MethodModifierGroup,
+    pub function: Span, // `function`
+    pub parameters: Vec<ParameterView<'a>>,
+    // `: int`, never set for constructors.
+    pub return_type: Option<&'a Type>,
+    pub is_abstract: bool,
+}
+
+/// A read-only view over a function, method or constructor parameter.
+#[derive(Debug, Clone)]
+pub struct ParameterView<'a> {
+    pub name: &'a SimpleVariable,
+    pub data_type: Option<&'a Type>,
+    pub default: Option<&'a Expression>,
+    pub is_variadic: bool,
+    pub is_by_reference: bool,
+    // Whether this is a promoted constructor property.
+    pub is_promoted: bool,
+}
+
+impl<'a> ParameterView<'a> {
+    pub fn from_parameter(parameter: &'a FunctionParameter) -> Self {
+        Self {
+            name: &parameter.name,
+            data_type: parameter.data_type.as_ref(),
+            default: parameter.default.as_ref(),
+            is_variadic: parameter.ellipsis.is_some(),
+            is_by_reference: parameter.ampersand.is_some(),
+            is_promoted: false,
+        }
+    }
+
+    pub fn from_constructor_parameter(parameter: &'a ConstructorParameter) -> Self {
+        Self {
+            name: &parameter.name,
+            data_type: parameter.data_type.as_ref(),
+            default: parameter.default.as_ref(),
+            is_variadic: parameter.ellipsis.is_some(),
+            is_by_reference: parameter.ampersand.is_some(),
+            is_promoted: !parameter.modifiers.is_empty(),
+        }
+    }
+
+    pub fn from_list(parameters: &'a FunctionParameterList) -> Vec<Self> {
+        parameters
+            .parameters
+            .inner
+            .iter()
+            .map(Self::from_parameter)
+            .collect()
+    }
+}
+
+impl<'a> MethodView<'a> {
+    pub fn from_abstract_method(method: &'a AbstractMethod) -> Self {
+        Self {
+            return_type: method
+                .return_type
+                .as_ref()
+                .map(|return_type| &return_type.data_type),
+            ..Self::new(
+                &method.comments,
+                &method.name,
+                &method.attributes,
+                &method.modifiers,
+                method.function,
+                ParameterView::from_list(&method.parameters),
+                true,
+            )
+        }
+    }
+
+    pub fn from_abstract_constructor(constructor: &'a AbstractConstructor) -> Self {
+        Self::new(
+            &constructor.comments,
+            &constructor.name,
+            &constructor.attributes,
+            &constructor.modifiers,
+            constructor.function,
+            ParameterView::from_list(&constructor.parameters),
+            true,
+        )
+    }
+
+    pub fn from_concrete_method(method: &'a ConcreteMethod) -> Self {
+        Self {
+            return_type: method
+                .return_type
+                .as_ref()
+                .map(|return_type| &return_type.data_type),
+            ..Self::new(
+                &method.comments,
+                &method.name,
+                &method.attributes,
+                &method.modifiers,
+                method.function,
+                ParameterView::from_list(&method.parameters),
+                false,
+            )
+        }
+    }
+
+    pub fn from_concrete_constructor(constructor: &'a ConcreteConstructor) -> Self {
+        Self::new(
+            &constructor.comments,
+            &constructor.name,
+            &constructor.attributes,
+            &constructor.modifiers,
+            constructor.function,
+            constructor
+                .parameters
+                .parameters
+                .inner
+                .iter()
+                .map(ParameterView::from_constructor_parameter)
+                .collect(),
+            false,
+        )
+    }
+
+    /// The span at which the declaration starts, ignoring any attributes.
+    pub fn start(&self) -> Span {
+        self.modifiers
+            .modifiers
+            .first()
+            .map(|modifier| modifier.span())
+            .unwrap_or(self.function)
+    }
+
+    fn new(
+        comments: &'a CommentGroup,
+        name: &'a SimpleIdentifier,
+        attributes: &'a [AttributeGroup],
+        modifiers: &'a MethodModifierGroup,
+        function: Span,
+        parameters: Vec<ParameterView<'a>>,
+        is_abstract: bool,
+    ) -> Self {
+        Self {
+            comments,
+            name,
+            attributes,
+            flags: AttributeFlags::from_groups(attributes),
+            modifiers,
+            function,
+            parameters,
+            return_type: None,
+            is_abstract,
+        }
+    }
+}
+
+/// A read-only view over a property declaration, or a promoted constructor property.
+#[derive(Debug, Clone)]
+pub struct PropertyView<'a> {
+    pub comments: &'a CommentGroup,
+    pub name: &'a SimpleVariable,
+    pub data_type: Option<&'a Type>,
+    pub visibility: Visibility,
+    pub is_static: bool,
+    pub is_readonly: bool,
+}
+
+impl<'a> PropertyView<'a> {
+    pub fn from_property(property: &'a Property) -> Vec<Self> {
+        property
+            .entries
+            .iter()
+            .map(|entry| Self {
+                comments: &property.comments,
+                name: entry_variable(entry),
+                data_type: property.r#type.as_ref(),
+                visibility: property.modifiers.visibility(),
+                is_static: property.modifiers.has_static(),
+                is_readonly: property.modifiers.has_readonly(),
+            })
+            .collect()
+    }
+
+    pub fn from_variable_property(property: &'a VariableProperty) -> Vec<Self> {
+        property
+            .entries
+            .iter()
+            .map(|entry| Self {
+                comments: &property.comments,
+                name: entry_variable(entry),
+                data_type: property.r#type.as_ref(),
+                visibility: Visibility::Public,
+                is_static: false,
+                is_readonly: false,
+            })
+            .collect()
+    }
+
+    pub fn from_promoted_parameters(constructor: &'a ConcreteConstructor) -> Vec<Self> {
+        constructor
+            .parameters
+            .parameters
+            .inner
+            .iter()
+            .filter(|parameter| !parameter.modifiers.is_empty())
+            .map(|parameter| Self {
+                comments: &parameter.comments,
+                name: &parameter.name,
+                data_type: parameter.data_type.as_ref(),
+                visibility: parameter.modifiers.visibility(),
+                is_static: false,
+                is_readonly: parameter.modifiers.has_readonly(),
+            })
+            .collect()
+    }
+}
+
+fn entry_variable(entry: &PropertyEntry) -> &SimpleVariable {
+    match entry {
+        PropertyEntry::Uninitialized { variable } | PropertyEntry::Initialized { variable, .. } => {
+            variable
+        }
+    }
+}
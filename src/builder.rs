@@ -0,0 +1,285 @@
+//! Functions building AST nodes, for tools generating code or rewriting it with a
+//! [`Transformer`](crate::traverser::Transformer).
+//!
+//! Built nodes don't come from a source, so all their spans are empty and point at its
+//! start, and they have no comments.
+//!
+//! ```
+//! use php_parser_rs::builder::*;
+//!
+//! // `$result = $client->send(new Request('GET'));`
+//! let statement = statement(assign(
+//!     variable("result"),
+//!     method_call(
+//!         variable("client"),
+//!         "send",
+//!         vec![new(name("Request"), vec![string("GET")])],
+//!     ),
+//! ));
+//! ```
+
+use crate::lexer::token::Span;
+use crate::parser::ast::arguments::Argument;
+use crate::parser::ast::arguments::ArgumentList;
+use crate::parser::ast::arguments::PositionalArgument;
+use crate::parser::ast::comments::CommentGroup;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::literals::LiteralFloat;
+use crate::parser::ast::literals::LiteralInteger;
+use crate::parser::ast::literals::LiteralString;
+use crate::parser::ast::literals::LiteralStringKind;
+use crate::parser::ast::operators::AssignmentOperationExpression;
+use crate::parser::ast::utils::CommaSeparated;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::ArrayIndexExpression;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::BlockStatement;
+use crate::parser::ast::BoolExpression;
+use crate::parser::ast::ConcatExpression;
+use crate::parser::ast::ConstantFetchExpression;
+use crate::parser::ast::EchoStatement;
+use crate::parser::ast::Ending;
+use crate::parser::ast::Expression;
+use crate::parser::ast::ExpressionStatement;
+use crate::parser::ast::FunctionCallExpression;
+use crate::parser::ast::MethodCallExpression;
+use crate::parser::ast::NewExpression;
+use crate::parser::ast::PropertyFetchExpression;
+use crate::parser::ast::ReturnStatement;
+use crate::parser::ast::ShortArrayExpression;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StaticMethodCallExpression;
+
+fn span() -> Span {
+    Span::new(1, 1, 0)
+}
+
+fn comments() -> CommentGroup {
+    CommentGroup { comments: vec![] }
+}
+
+/// An identifier, such as the name of a function, class or method.
+pub fn identifier(name: &str) -> SimpleIdentifier {
+    SimpleIdentifier {
+        span: span(),
+        value: name.into(),
+    }
+}
+
+/// A name used as a value, e.g. `Foo` in `Foo::bar()`, or `foo` in `foo()`.
+pub fn name(name: &str) -> Expression {
+    Expression::Identifier(Identifier::SimpleIdentifier(identifier(name)))
+}
+
+/// A variable, named with or without its `$`.
+pub fn variable(name: &str) -> Expression {
+    let name = match name.strip_prefix('$') {
+        Some(_) => name.to_string(),
+        None => format!("${}", name),
+    };
+
+    Expression::Variable(Variable::SimpleVariable(SimpleVariable {
+        span: span(),
+        name: name.into(),
+    }))
+}
+
+/// An integer literal.
+pub fn int(value: i64) -> Expression {
+    Expression::Literal(Literal::Integer(LiteralInteger {
+        value: value.to_string().into(),
+        span: span(),
+    }))
+}
+
+/// A float literal, always written with a fraction or exponent so that it stays a float.
+pub fn float(value: f64) -> Expression {
+    let mut written = value.to_string();
+    if !written.contains(['.', 'e', 'E']) && value.is_finite() {
+        written.push_str(".0");
+    }
+
+    Expression::Literal(Literal::Float(LiteralFloat {
+        value: written.into(),
+        span: span(),
+    }))
+}
+
+/// A single-quoted string literal, with the given value.
+pub fn string(value: &str) -> Expression {
+    Expression::Literal(Literal::String(LiteralString {
+        value: value.into(),
+        span: span(),
+        kind: LiteralStringKind::SingleQuoted,
+    }))
+}
+
+/// `true` or `false`.
+pub fn bool(value: bool) -> Expression {
+    Expression::Bool(BoolExpression {
+        span: span(),
+        value,
+    })
+}
+
+/// `null`.
+pub fn null() -> Expression {
+    Expression::Null(span())
+}
+
+/// The arguments of a call, all positional.
+pub fn arguments(arguments: Vec<Expression>) -> ArgumentList {
+    ArgumentList {
+        comments: comments(),
+        left_parenthesis: span(),
+        arguments: arguments
+            .into_iter()
+            .map(|value| {
+                Argument::Positional(PositionalArgument {
+                    comments: comments(),
+                    ellipsis: None,
+                    value,
+                })
+            })
+            .collect(),
+        right_parenthesis: span(),
+    }
+}
+
+/// `target(...arguments)`, e.g. a function call when the target is a [`name`].
+pub fn call(target: Expression, arguments: Vec<Expression>) -> Expression {
+    Expression::FunctionCall(FunctionCallExpression {
+        target: Box::new(target),
+        arguments: self::arguments(arguments),
+    })
+}
+
+/// `target->method(...arguments)`.
+pub fn method_call(target: Expression, method: &str, arguments: Vec<Expression>) -> Expression {
+    Expression::MethodCall(MethodCallExpression {
+        target: Box::new(target),
+        arrow: span(),
+        method: Box::new(name(method)),
+        arguments: self::arguments(arguments),
+    })
+}
+
+/// `class::method(...arguments)`.
+pub fn static_call(class: Expression, method: &str, arguments: Vec<Expression>) -> Expression {
+    Expression::StaticMethodCall(StaticMethodCallExpression {
+        target: Box::new(class),
+        double_colon: span(),
+        method: Identifier::SimpleIdentifier(identifier(method)),
+        arguments: self::arguments(arguments),
+    })
+}
+
+/// `new class(...arguments)`.
+pub fn new(class: Expression, arguments: Vec<Expression>) -> Expression {
+    Expression::New(NewExpression {
+        new: span(),
+        target: Box::new(class),
+        arguments: Some(self::arguments(arguments)),
+    })
+}
+
+/// `target->property`.
+pub fn property(target: Expression, property: &str) -> Expression {
+    Expression::PropertyFetch(PropertyFetchExpression {
+        target: Box::new(target),
+        arrow: span(),
+        property: Box::new(name(property)),
+    })
+}
+
+/// `class::CONSTANT`.
+pub fn class_constant(class: Expression, constant: &str) -> Expression {
+    Expression::ConstantFetch(ConstantFetchExpression {
+        target: Box::new(class),
+        double_colon: span(),
+        constant: Identifier::SimpleIdentifier(identifier(constant)),
+    })
+}
+
+/// `array[index]`.
+pub fn index(array: Expression, index: Expression) -> Expression {
+    Expression::ArrayIndex(ArrayIndexExpression {
+        array: Box::new(array),
+        left_bracket: span(),
+        index: Some(Box::new(index)),
+        right_bracket: span(),
+    })
+}
+
+/// `[...values]`, a list of values without keys.
+pub fn array(values: Vec<Expression>) -> Expression {
+    let commas = vec![span(); values.len().saturating_sub(1)];
+
+    Expression::ShortArray(ShortArrayExpression {
+        start: span(),
+        items: CommaSeparated {
+            inner: values
+                .into_iter()
+                .map(|value| ArrayItem::Value { value })
+                .collect(),
+            commas,
+        },
+        end: span(),
+    })
+}
+
+/// `left = right`.
+pub fn assign(left: Expression, right: Expression) -> Expression {
+    Expression::AssignmentOperation(AssignmentOperationExpression::Assign {
+        left: Box::new(left),
+        equals: span(),
+        right: Box::new(right),
+    })
+}
+
+/// `left . right`.
+pub fn concat(left: Expression, right: Expression) -> Expression {
+    Expression::Concat(ConcatExpression {
+        left: Box::new(left),
+        dot: span(),
+        right: Box::new(right),
+    })
+}
+
+/// An expression used as a statement, ending with `;`.
+pub fn statement(expression: Expression) -> Statement {
+    Statement::Expression(ExpressionStatement {
+        expression,
+        ending: Ending::Semicolon(span()),
+    })
+}
+
+/// `echo ...values;`.
+pub fn echo(values: Vec<Expression>) -> Statement {
+    Statement::Echo(EchoStatement {
+        echo: span(),
+        values,
+        ending: Ending::Semicolon(span()),
+    })
+}
+
+/// `return value;`, or `return;` without a value.
+pub fn r#return(value: Option<Expression>) -> Statement {
+    Statement::Return(ReturnStatement {
+        r#return: span(),
+        value,
+        ending: Ending::Semicolon(span()),
+    })
+}
+
+/// `{ ...statements }`.
+pub fn block(statements: Vec<Statement>) -> Statement {
+    Statement::Block(BlockStatement {
+        left_brace: span(),
+        statements,
+        right_brace: span(),
+    })
+}
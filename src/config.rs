@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Deserializer;
+
+use crate::lexer::keywords::PhpVersion;
+use crate::lexer::LexerConfig;
+use crate::lint::config::LintConfig;
+use crate::parser::ParserConfig;
+
+/// The name of the configuration files discovered by [`ConfigResolver`].
+pub const CONFIG_FILE_NAME: &str = ".phpparser.toml";
+
+/// The configuration of a directory and the files in it:
+///
+/// ```toml
+/// php-version = "8.1"
+/// short-tags = false
+///
+/// [lint.rules]
+/// nested-ternary = "error"
+/// ```
+///
+/// Every setting is optional, so that a config file only has to list what differs
+/// from the config files of its parent directories.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    // Stop looking for config files in the parent directories.
+    #[serde(default)]
+    pub root: bool,
+    // The PHP version the code targets, `8.3` by default.
+    #[serde(default, deserialize_with = "php_version")]
+    pub php_version: Option<PhpVersion>,
+    // Whether `<?` opens PHP code, `true` by default.
+    pub short_tags: Option<bool>,
+    #[serde(default)]
+    pub lint: LintConfig,
+}
+
+fn php_version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<PhpVersion>, D::Error> {
+    let version = String::deserialize(deserializer)?;
+
+    version.parse().map(Some).map_err(serde::de::Error::custom)
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, std::io::Error),
+    Toml(PathBuf, toml::de::Error),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(path, error) => {
+                write!(f, "failed to read {}: {}", path.display(), error)
+            }
+            ConfigError::Toml(path, error) => write!(f, "invalid {}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    pub fn from_toml(source: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(source)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let source =
+            std::fs::read_to_string(path).map_err(|error| ConfigError::Io(path.into(), error))?;
+        let mut config =
+            Self::from_toml(&source).map_err(|error| ConfigError::Toml(path.into(), error))?;
+
+        if let (Some(baseline), Some(directory)) = (&config.lint.baseline, path.parent()) {
+            config.lint.baseline = Some(directory.join(baseline));
+        }
+
+        Ok(config)
+    }
+
+    /// Apply the settings of a more specific config, such as the one of a
+    /// subdirectory, on top of this one.
+    pub fn merge(&mut self, other: Config) {
+        self.root |= other.root;
+        self.php_version = other.php_version.or(self.php_version);
+        self.short_tags = other.short_tags.or(self.short_tags);
+        self.lint.merge(other.lint);
+    }
+
+    pub fn php_version(&self) -> PhpVersion {
+        self.php_version.unwrap_or_default()
+    }
+
+    pub fn parser_config(&self) -> ParserConfig {
+        ParserConfig {
+            lexer: LexerConfig {
+                short_tags: self.short_tags.unwrap_or(true),
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// Finds the configuration of each file by merging the [`CONFIG_FILE_NAME`] files
+/// of its directory and all parent directories, with the nearest file taking
+/// precedence. The search stops at a config file that sets `root = true`.
+///
+/// The configuration of each directory is cached, so resolving the configuration of
+/// many files in the same project only reads each config file once.
+#[derive(Debug, Default)]
+pub struct ConfigResolver {
+    directories: HashMap<PathBuf, Config>,
+}
+
+impl ConfigResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The configuration of the given file.
+    pub fn resolve(&mut self, file: &Path) -> Result<Config, ConfigError> {
+        match file.parent() {
+            Some(directory) if directory.as_os_str().is_empty() => self.directory(Path::new(".")),
+            Some(directory) => self.directory(directory),
+            None => Ok(Config::default()),
+        }
+    }
+
+    fn directory(&mut self, directory: &Path) -> Result<Config, ConfigError> {
+        let directory = directory
+            .canonicalize()
+            .unwrap_or_else(|_| directory.to_path_buf());
+        if let Some(config) = self.directories.get(&directory) {
+            return Ok(config.clone());
+        }
+
+        let path = directory.join(CONFIG_FILE_NAME);
+        let local = match path.is_file() {
+            true => Some(Config::load(&path)?),
+            false => None,
+        };
+
+        let config = match (local, directory.parent()) {
+            (Some(local), _) if local.root => local,
+            (local, Some(parent)) => {
+                let mut config = self.directory(parent)?;
+                // `root` only stops the search from the directory it is set in.
+                config.root = false;
+                if let Some(local) = local {
+                    config.merge(local);
+                }
+
+                config
+            }
+            (local, None) => local.unwrap_or_default(),
+        };
+
+        self.directories.insert(directory, config.clone());
+
+        Ok(config)
+    }
+}
@@ -0,0 +1,348 @@
+use ariadne::{CharSet, Color, Config, Report, ReportKind, Source};
+
+use crate::lexer::error::SyntaxError;
+use crate::lexer::token::Span;
+use crate::lint::Edit;
+use crate::lint::Fix;
+use crate::lint::Severity;
+use crate::parser::error::ParseError;
+use crate::parser::error::ParseErrorAnnotationType;
+use crate::parser::error::ParseErrorStack;
+use crate::snippet::ColumnUnit;
+use crate::source_map::SourceMap;
+
+/// An error found in a source, either while tokenizing or parsing it, with everything
+/// needed to show it to a user: labels pointing at the source, notes, and fixes.
+///
+/// ```rust
+/// use php_parser_rs::diagnostic::Diagnostic;
+///
+/// let code = "<?php if ($a): echo 1; }";
+/// let error = php_parser_rs::parse(code).unwrap_err();
+/// let diagnostic = Diagnostic::from(&error.errors[0]);
+///
+/// assert_eq!(diagnostic.suggestions[0].description, "did you mean `endif;`?");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Diagnostic {
+    pub code: String,
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+    pub suggestions: Vec<Fix>,
+}
+
+/// A range of the source, with a message about it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Label {
+    pub style: LabelStyle,
+    pub message: String,
+    pub position: usize,
+    pub length: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LabelStyle {
+    /// The code that causes the error.
+    Primary,
+    /// Code related to the error, such as a previous declaration.
+    Secondary,
+}
+
+impl Diagnostic {
+    /// Render the diagnostic with an excerpt of the source, the labels underlining it,
+    /// and the notes and suggestions below it.
+    pub fn report<'a>(
+        &self,
+        source: &'a str,
+        origin: Option<&'a str>,
+        colored: bool,
+        ascii: bool,
+    ) -> std::io::Result<String> {
+        let origin = origin.unwrap_or("input");
+
+        let kind = match self.severity {
+            Severity::Info => ReportKind::Advice,
+            Severity::Warning => ReportKind::Warning,
+            Severity::Error => ReportKind::Error,
+        };
+
+        let mut report = Report::build(kind, origin, char_offset(source, self.span.position))
+            .with_code(&self.code)
+            .with_message(&self.message)
+            .with_config(
+                Config::default()
+                    .with_color(colored)
+                    .with_char_set(if ascii {
+                        CharSet::Ascii
+                    } else {
+                        CharSet::Unicode
+                    }),
+            );
+
+        for (order, label) in self.labels.iter().enumerate() {
+            let mut report_label = ariadne::Label::new((
+                origin,
+                char_offset(source, label.position)
+                    ..char_offset(source, label.position + label.length),
+            ))
+            .with_order(order.try_into().unwrap());
+
+            if !label.message.is_empty() {
+                report_label = report_label.with_message(&label.message);
+            }
+
+            if colored {
+                report_label = match label.style {
+                    LabelStyle::Secondary => report_label.with_color(Color::Cyan),
+                    LabelStyle::Primary => report_label.with_color(Color::Red),
+                };
+            }
+
+            report = report.with_label(report_label);
+        }
+
+        if !self.notes.is_empty() {
+            report = report.with_note(self.notes.join("\n"));
+        }
+
+        if !self.suggestions.is_empty() {
+            let suggestions: Vec<&str> = self
+                .suggestions
+                .iter()
+                .map(|fix| fix.description.as_str())
+                .collect();
+
+            report = report.with_help(suggestions.join("\n"));
+        }
+
+        let code = (origin, Source::from(source));
+
+        let mut bytes = Vec::new();
+
+        report.finish().write(code, &mut bytes)?;
+
+        let string = unsafe {
+            // SAFETY: We know that the bytes are valid UTF-8
+            String::from_utf8_unchecked(bytes)
+        };
+
+        Ok(string)
+    }
+}
+
+impl From<&ParseError> for Diagnostic {
+    fn from(error: &ParseError) -> Self {
+        Self {
+            code: error.id.clone(),
+            severity: Severity::Error,
+            message: error.message.clone(),
+            span: error.span,
+            labels: error
+                .annotations
+                .iter()
+                .map(|annotation| Label {
+                    style: match annotation.r#type {
+                        ParseErrorAnnotationType::Error => LabelStyle::Primary,
+                        ParseErrorAnnotationType::Hint => LabelStyle::Secondary,
+                    },
+                    message: annotation.message.clone(),
+                    position: annotation.position,
+                    length: annotation.length,
+                })
+                .collect(),
+            notes: error.note.iter().cloned().collect(),
+            suggestions: error
+                .suggestions
+                .iter()
+                .map(|suggestion| Fix {
+                    description: suggestion.message.clone(),
+                    edits: vec![Edit {
+                        position: suggestion.position,
+                        length: suggestion.length,
+                        replacement: suggestion.replacement.clone().into_bytes(),
+                    }],
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&SyntaxError> for Diagnostic {
+    fn from(error: &SyntaxError) -> Self {
+        Self::from(&ParseError::from(error.clone()))
+    }
+}
+
+impl ParseErrorStack {
+    /// The diagnostics of every error, in the order they were found.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.errors.iter().map(Diagnostic::from).collect()
+    }
+}
+
+/// Serialize the diagnostics of a file to JSON, for consumers such as editors.
+///
+/// Lines and columns are 1-based, with columns counted in the given unit, while
+/// positions and lengths are byte offsets into the source.
+pub fn diagnostics_to_json(
+    path: &str,
+    source: &[u8],
+    diagnostics: &[Diagnostic],
+    column_unit: ColumnUnit,
+) -> serde_json::Value {
+    let map = SourceMap::new(source);
+    let diagnostics = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let (start, end) = map.span_range(&diagnostic.span, column_unit);
+
+            serde_json::json!({
+                "file": path,
+                "code": diagnostic.code,
+                "severity": match diagnostic.severity {
+                    Severity::Info => "info",
+                    Severity::Warning => "warning",
+                    Severity::Error => "error",
+                },
+                "message": diagnostic.message,
+                "line": diagnostic.span.line,
+                "column": start.column,
+                "end_line": end.line,
+                "end_column": end.column,
+                "position": diagnostic.span.position,
+                "end_position": diagnostic.span.end_position.max(diagnostic.span.position),
+                "labels": diagnostic.labels.iter().map(|label| serde_json::json!({
+                    "style": match label.style {
+                        LabelStyle::Primary => "primary",
+                        LabelStyle::Secondary => "secondary",
+                    },
+                    "message": label.message,
+                    "position": label.position,
+                    "length": label.length,
+                })).collect::<Vec<_>>(),
+                "notes": diagnostic.notes,
+                "suggestions": diagnostic.suggestions.iter().map(|fix| serde_json::json!({
+                    "description": fix.description,
+                    "edits": fix.edits.iter().map(|edit| serde_json::json!({
+                        "position": edit.position,
+                        "length": edit.length,
+                        "replacement": String::from_utf8_lossy(&edit.replacement),
+                    })).collect::<Vec<_>>(),
+                })).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+
+    serde_json::Value::Array(diagnostics)
+}
+
+/// Serialize the diagnostics of a file to a SARIF 2.1.0 log, the format read by code
+/// scanning tools of CI systems.
+///
+/// Columns are counted in UTF-16 code units, as SARIF expects by default, and the
+/// suggested fixes replace regions given in bytes.
+pub fn diagnostics_to_sarif(
+    path: &str,
+    source: &[u8],
+    diagnostics: &[Diagnostic],
+) -> serde_json::Value {
+    let uri = path.replace('\\', "/");
+    let map = SourceMap::new(source);
+
+    let region = |position: usize, length: usize| {
+        let start = map.line_column(position, ColumnUnit::Utf16CodeUnits);
+        let end = map.line_column(position + length, ColumnUnit::Utf16CodeUnits);
+
+        serde_json::json!({
+            "startLine": start.line,
+            "startColumn": start.column,
+            "endLine": end.line,
+            "endColumn": end.column,
+            "byteOffset": position,
+            "byteLength": length,
+        })
+    };
+
+    let mut rules: Vec<&str> = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.code.as_str())
+        .collect();
+    rules.sort_unstable();
+    rules.dedup();
+
+    let results: Vec<serde_json::Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let span = diagnostic.span;
+
+            serde_json::json!({
+                "ruleId": diagnostic.code,
+                "level": match diagnostic.severity {
+                    Severity::Info => "note",
+                    Severity::Warning => "warning",
+                    Severity::Error => "error",
+                },
+                "message": { "text": diagnostic.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": uri },
+                        "region": region(span.position, span.length()),
+                    },
+                }],
+                "relatedLocations": diagnostic.labels.iter()
+                    .filter(|label| label.style == LabelStyle::Secondary)
+                    .map(|label| serde_json::json!({
+                        "message": { "text": label.message },
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": uri },
+                            "region": region(label.position, label.length),
+                        },
+                    }))
+                    .collect::<Vec<_>>(),
+                "fixes": diagnostic.suggestions.iter().map(|fix| serde_json::json!({
+                    "description": { "text": fix.description },
+                    "artifactChanges": [{
+                        "artifactLocation": { "uri": uri },
+                        "replacements": fix.edits.iter().map(|edit| serde_json::json!({
+                            "deletedRegion": {
+                                "byteOffset": edit.position,
+                                "byteLength": edit.length,
+                            },
+                            "insertedContent": {
+                                "text": String::from_utf8_lossy(&edit.replacement),
+                            },
+                        })).collect::<Vec<_>>(),
+                    }],
+                })).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules.iter().map(|id| serde_json::json!({ "id": id })).collect::<Vec<_>>(),
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+// The 1-based line of the byte at `position`.
+// Reports count offsets in characters, while spans count them in bytes.
+fn char_offset(source: &str, position: usize) -> usize {
+    source
+        .char_indices()
+        .take_while(|(index, _)| *index < position)
+        .count()
+}
@@ -0,0 +1,172 @@
+use crate::docblock::docblock_lines;
+use crate::index::ClassLikeEntry;
+use crate::index::Index;
+use crate::lexer::byte_string::ByteString;
+
+const INLINE_INHERIT_DOC: &[u8] = b"{@inheritdoc}";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MemberKind {
+    Constant,
+    Property,
+    Method,
+}
+
+/// The documentation that applies to a class member, once `@inheritDoc` is resolved.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Documentation {
+    // The fully qualified name of the class-like the docblock is written in.
+    pub declared_in: ByteString,
+    // The docblock, with any inline `{@inheritDoc}` replaced by the inherited description.
+    pub docblock: ByteString,
+}
+
+/// The effective documentation of a member declared by a class-like.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MemberDocumentation {
+    pub kind: MemberKind,
+    // The name of the member, without the leading `$` for properties.
+    pub name: ByteString,
+    pub documentation: Option<Documentation>,
+}
+
+/// The effective documentation of every constant, property and method declared by the
+/// given class-like, in declaration order.
+pub fn class_documentation(index: &Index, class: &[u8]) -> Vec<MemberDocumentation> {
+    let Some(entry) = index.class(class) else {
+        return vec![];
+    };
+
+    let constants = entry
+        .constants
+        .iter()
+        .map(|constant| (MemberKind::Constant, &constant.name));
+    let properties = entry
+        .properties
+        .iter()
+        .map(|property| (MemberKind::Property, &property.name));
+    let methods = entry
+        .methods
+        .iter()
+        .map(|method| (MemberKind::Method, &method.name));
+
+    constants
+        .chain(properties)
+        .chain(methods)
+        .map(|(kind, name)| MemberDocumentation {
+            kind,
+            name: name.clone(),
+            documentation: resolve(index, entry, kind, name, &mut vec![]),
+        })
+        .collect()
+}
+
+/// The effective documentation of a member of the given class-like, which may be declared
+/// by the class-like itself or inherited from one of its ancestors.
+///
+/// A member without a docblock, with an empty one, or with one that only contains
+/// `@inheritDoc` takes the documentation of the member it overrides, looking at used
+/// traits first, then the parent class, then interfaces. An inline `{@inheritDoc}` is
+/// replaced by the description of the overridden member.
+pub fn member_documentation(
+    index: &Index,
+    class: &[u8],
+    kind: MemberKind,
+    name: &[u8],
+) -> Option<Documentation> {
+    resolve(index, index.class(class)?, kind, name, &mut vec![])
+}
+
+fn resolve<'a>(
+    index: &'a Index,
+    entry: &'a ClassLikeEntry,
+    kind: MemberKind,
+    name: &[u8],
+    seen: &mut Vec<&'a ByteString>,
+) -> Option<Documentation> {
+    if seen
+        .iter()
+        .any(|seen| seen.eq_ignore_ascii_case(&entry.name))
+    {
+        return None;
+    }
+    seen.push(&entry.name);
+
+    let docblock = match member_docblock(entry, kind, name) {
+        Some(docblock) => docblock,
+        None => return inherited(index, entry, kind, name, seen),
+    };
+
+    let lines = docblock_lines(docblock);
+    let text = lines.join(" ");
+    let text = text.trim();
+    if text.is_empty()
+        || text.eq_ignore_ascii_case("@inheritdoc")
+        || text.eq_ignore_ascii_case("{@inheritdoc}")
+    {
+        return inherited(index, entry, kind, name, seen);
+    }
+
+    let mut documentation = Documentation {
+        declared_in: entry.name.clone(),
+        docblock: docblock.clone(),
+    };
+
+    if let Some(position) = find_inline_inherit_doc(docblock) {
+        let description = inherited(index, entry, kind, name, seen)
+            .map(|inherited| description(&inherited.docblock))
+            .unwrap_or_default();
+
+        let mut replaced = docblock[..position].to_vec();
+        replaced.extend(description.as_bytes());
+        replaced.extend(&docblock[position + INLINE_INHERIT_DOC.len()..]);
+        documentation.docblock = replaced.into();
+    }
+
+    Some(documentation)
+}
+
+fn inherited<'a>(
+    index: &'a Index,
+    entry: &'a ClassLikeEntry,
+    kind: MemberKind,
+    name: &[u8],
+    seen: &mut Vec<&'a ByteString>,
+) -> Option<Documentation> {
+    entry
+        .traits
+        .iter()
+        .chain(entry.parent.iter())
+        .chain(entry.interfaces.iter())
+        .filter_map(|ancestor| index.class(ancestor))
+        .find_map(|ancestor| resolve(index, ancestor, kind, name, seen))
+}
+
+fn member_docblock<'a>(
+    entry: &'a ClassLikeEntry,
+    kind: MemberKind,
+    name: &[u8],
+) -> Option<&'a ByteString> {
+    match kind {
+        MemberKind::Constant => entry.constant(name)?.docblock.as_ref(),
+        MemberKind::Property => entry.property(name)?.docblock.as_ref(),
+        MemberKind::Method => entry.method(name)?.docblock.as_ref(),
+    }
+}
+
+fn find_inline_inherit_doc(docblock: &[u8]) -> Option<usize> {
+    docblock
+        .windows(INLINE_INHERIT_DOC.len())
+        .position(|window| window.eq_ignore_ascii_case(INLINE_INHERIT_DOC))
+}
+
+// The lines of a docblock before its first tag, joined by spaces.
+fn description(docblock: &[u8]) -> String {
+    docblock_lines(docblock)
+        .iter()
+        .take_while(|line| !line.starts_with('@'))
+        .filter(|line| !line.is_empty())
+        .map(|line| line.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
@@ -0,0 +1,44 @@
+use crate::lexer::byte_string::ByteString;
+use crate::parser::ast::comments::Comment;
+use crate::parser::ast::comments::CommentFormat;
+use crate::parser::ast::comments::CommentGroup;
+
+pub mod inheritance;
+pub mod tags;
+pub mod types;
+
+pub use tags::parse_docblock;
+pub use tags::Docblock;
+pub use tags::Tag;
+pub use tags::TagKind;
+pub use tags::TemplateVariance;
+pub use types::parse_type;
+pub use types::parse_type_prefix;
+pub use types::ArrayShapeEntry;
+pub use types::ArrayShapeKey;
+pub use types::ArrayShapeKind;
+pub use types::DocblockType;
+
+/// The docblock of a declaration: the last `/** ... */` comment preceding it.
+pub fn docblock(comments: &CommentGroup) -> Option<&ByteString> {
+    docblock_comment(comments).map(|comment| &comment.content)
+}
+
+/// The comment holding the docblock of a declaration, to parse it with [`parse_docblock`].
+pub fn docblock_comment(comments: &CommentGroup) -> Option<&Comment> {
+    comments
+        .iter()
+        .rev()
+        .find(|comment| comment.format == CommentFormat::Document)
+}
+
+/// The lines of a docblock, without the comment delimiters and the leading `*` of each line.
+pub fn docblock_lines(content: &[u8]) -> Vec<String> {
+    let content = String::from_utf8_lossy(content);
+    let content = content.trim_start_matches("/**").trim_end_matches("*/");
+
+    content
+        .lines()
+        .map(|line| line.trim().trim_start_matches('*').trim().to_owned())
+        .collect()
+}
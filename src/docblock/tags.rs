@@ -0,0 +1,344 @@
+use crate::docblock::parse_type_prefix;
+use crate::docblock::DocblockType;
+use crate::lexer::token::Span;
+use crate::parser::ast::comments::Comment;
+
+/// A `/** ... */` comment, split into its description and tags.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Docblock {
+    pub span: Span,
+    // The lines before the first tag, without the comment delimiters and leading `*`.
+    pub description: String,
+    pub tags: Vec<Tag>,
+}
+
+/// A tag of a docblock, such as `@param int $count The number of items.`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Tag {
+    // From the `@` to the end of the last line of the tag.
+    pub span: Span,
+    // The name as written, without the `@`, e.g. `param` or `psalm-param`.
+    pub name: String,
+    pub kind: TagKind,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TagKind {
+    // `@param int $count`, `@param string &...$names`
+    Param {
+        r#type: Option<DocblockType>,
+        // The name of the parameter, including the leading `$`.
+        variable: String,
+        by_reference: bool,
+        variadic: bool,
+        description: String,
+    },
+    // `@var int`, `@var int $count`
+    Var {
+        r#type: DocblockType,
+        variable: Option<String>,
+        description: String,
+    },
+    // `@return int`
+    Return {
+        r#type: DocblockType,
+        description: String,
+    },
+    // `@throws Exception`
+    Throws {
+        r#type: DocblockType,
+        description: String,
+    },
+    // `@template T`, `@template-covariant T of Foo`
+    Template {
+        name: String,
+        variance: TemplateVariance,
+        // `of Foo` or `as Foo`
+        bound: Option<DocblockType>,
+        description: String,
+    },
+    // Any other tag, or one of the above whose type or variable can't be parsed.
+    Other {
+        body: String,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TemplateVariance {
+    Invariant,
+    Covariant,
+    Contravariant,
+}
+
+impl Docblock {
+    /// The tags whose name matches the given one, ignoring case and the `psalm-`,
+    /// `phpstan-` and `phan-` prefixes.
+    pub fn tags_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Tag> + 'a {
+        self.tags
+            .iter()
+            .filter(move |tag| unprefixed(&tag.name).eq_ignore_ascii_case(name))
+    }
+
+    /// The `@param` tag describing the given variable, which includes the leading `$`.
+    pub fn param(&self, variable: &str) -> Option<&Tag> {
+        self.tags.iter().find(
+            |tag| matches!(&tag.kind, TagKind::Param { variable: name, .. } if name == variable),
+        )
+    }
+}
+
+/// Parse a `/** ... */` comment.
+///
+/// Every tag starts on a line of its own, and continues up to the next tag or the end
+/// of the comment, so multi-line types and descriptions are part of the tag. Spans are
+/// relative to the file the comment is in.
+pub fn parse_docblock(comment: &Comment) -> Docblock {
+    let lines = lines(comment);
+
+    let mut description: Vec<&Line> = vec![];
+    let mut tags: Vec<Vec<&Line>> = vec![];
+    for line in &lines {
+        if is_tag(line.text) {
+            tags.push(vec![line]);
+        } else if let Some(tag) = tags.last_mut() {
+            tag.push(line);
+        } else {
+            description.push(line);
+        }
+    }
+
+    Docblock {
+        span: comment.span,
+        description: join(&description),
+        tags: tags.iter().map(|lines| tag(comment, lines)).collect(),
+    }
+}
+
+// A line of a docblock, without the comment delimiters, leading `*` and surrounding
+// whitespace, along with the byte offsets of the text within the comment.
+struct Line<'a> {
+    text: &'a str,
+    start: usize,
+    end: usize,
+}
+
+fn lines(comment: &Comment) -> Vec<Line<'_>> {
+    let content: &[u8] = &comment.content;
+    let start = if content.starts_with(b"/**") { 3 } else { 0 };
+    let end = if content.len() >= start + 2 && content.ends_with(b"*/") {
+        content.len() - 2
+    } else {
+        content.len()
+    };
+
+    let mut lines = vec![];
+    let mut offset = start;
+    for line in content[start..end].split(|b| *b == b'\n') {
+        let line_start = offset;
+        offset += line.len() + 1;
+
+        let mut from = line
+            .iter()
+            .position(|b| !b.is_ascii_whitespace())
+            .unwrap_or(line.len());
+        if line.get(from) == Some(&b'*') {
+            from += 1;
+            from += line[from..]
+                .iter()
+                .take_while(|b| b.is_ascii_whitespace())
+                .count();
+        }
+
+        let until = line.len()
+            - line[from..]
+                .iter()
+                .rev()
+                .take_while(|b| b.is_ascii_whitespace())
+                .count();
+
+        if let Ok(text) = std::str::from_utf8(&line[from..until]) {
+            lines.push(Line {
+                text,
+                start: line_start + from,
+                end: line_start + until,
+            });
+        }
+    }
+
+    lines
+}
+
+fn is_tag(text: &str) -> bool {
+    text.strip_prefix('@')
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '\\')
+}
+
+// The lines joined by newlines, without leading and trailing empty lines.
+fn join(lines: &[&Line]) -> String {
+    lines
+        .iter()
+        .map(|line| line.text)
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .trim()
+        .to_owned()
+}
+
+fn tag(comment: &Comment, lines: &[&Line]) -> Tag {
+    let first = lines[0];
+    let last = lines
+        .iter()
+        .rev()
+        .find(|line| !line.text.is_empty())
+        .unwrap_or(&first);
+
+    let text = join(lines);
+    let name_length = text[1..]
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '\\')))
+        .unwrap_or(text.len() - 1);
+    let name = text[1..=name_length].to_owned();
+    let body = text[1 + name_length..].trim();
+
+    let kind = match unprefixed(&name).to_ascii_lowercase().as_str() {
+        "param" => param(body),
+        "var" => var(body),
+        "return" => typed(body).map(|(r#type, description)| TagKind::Return {
+            r#type,
+            description,
+        }),
+        "throws" => typed(body).map(|(r#type, description)| TagKind::Throws {
+            r#type,
+            description,
+        }),
+        "template" => template(body, TemplateVariance::Invariant),
+        "template-covariant" => template(body, TemplateVariance::Covariant),
+        "template-contravariant" => template(body, TemplateVariance::Contravariant),
+        _ => None,
+    };
+
+    Tag {
+        span: span(comment, first.start, last.end),
+        name,
+        kind: kind.unwrap_or_else(|| TagKind::Other {
+            body: body.to_owned(),
+        }),
+    }
+}
+
+fn unprefixed(name: &str) -> &str {
+    ["psalm-", "phpstan-", "phan-"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .unwrap_or(name)
+}
+
+fn typed(body: &str) -> Option<(DocblockType, String)> {
+    let (r#type, rest) = parse_type_prefix(body)?;
+
+    Some((r#type, rest.trim().to_owned()))
+}
+
+fn param(body: &str) -> Option<TagKind> {
+    // the type is optional, e.g. `@param $foo`.
+    let (r#type, rest) = if body.starts_with(['$', '&', '.']) {
+        (None, body)
+    } else {
+        let (r#type, rest) = parse_type_prefix(body)?;
+        (Some(r#type), rest)
+    };
+
+    let rest = rest.trim_start();
+    let (by_reference, rest) = match rest.strip_prefix('&') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, rest),
+    };
+    let (variadic, rest) = match rest.strip_prefix("...") {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    let (variable, rest) = variable(rest)?;
+
+    Some(TagKind::Param {
+        r#type,
+        variable,
+        by_reference,
+        variadic,
+        description: rest.trim().to_owned(),
+    })
+}
+
+fn var(body: &str) -> Option<TagKind> {
+    let (r#type, rest) = parse_type_prefix(body)?;
+    let (variable, rest) = match variable(rest) {
+        Some((variable, rest)) => (Some(variable), rest),
+        None => (None, rest),
+    };
+
+    Some(TagKind::Var {
+        r#type,
+        variable,
+        description: rest.trim().to_owned(),
+    })
+}
+
+fn template(body: &str, variance: TemplateVariance) -> Option<TagKind> {
+    let length = body
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(body.len());
+    if length == 0 {
+        return None;
+    }
+
+    let (name, rest) = body.split_at(length);
+    let rest = rest.trim_start();
+    let (bound, rest) = match rest
+        .strip_prefix("of ")
+        .or_else(|| rest.strip_prefix("as "))
+    {
+        Some(rest) => {
+            let (bound, rest) = parse_type_prefix(rest)?;
+            (Some(bound), rest)
+        }
+        None => (None, rest),
+    };
+
+    Some(TagKind::Template {
+        name: name.to_owned(),
+        variance,
+        bound,
+        description: rest.trim().to_owned(),
+    })
+}
+
+// A `$name` at the start of the input, and the rest of the input after it.
+fn variable(input: &str) -> Option<(String, &str)> {
+    let name = input.strip_prefix('$')?;
+    let length = name
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(name.len());
+    if length == 0 {
+        return None;
+    }
+
+    Some((input[..=length].to_owned(), &input[1 + length..]))
+}
+
+// The span between two byte offsets within the comment.
+fn span(comment: &Comment, from: usize, until: usize) -> Span {
+    let before = &comment.content[..from];
+    let (line, column) = match before.iter().rposition(|b| *b == b'\n') {
+        Some(newline) => (
+            comment.span.line + before.iter().filter(|b| **b == b'\n').count(),
+            from - newline,
+        ),
+        None => (comment.span.line, comment.span.column + from),
+    };
+
+    Span {
+        line,
+        column,
+        position: comment.span.position + from,
+        end_position: comment.span.position + until,
+    }
+}
@@ -0,0 +1,351 @@
+use crate::lexer::source::Source;
+
+/// A type written in a docblock, such as `list<int>` or `array{id: int, name?: string}`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum DocblockType {
+    // `int`, `non-empty-string`, `Foo\Bar`, `$this`
+    Named(String),
+    // `array<int, string>`, `list<Foo>`, `Collection<Foo>`
+    Generic {
+        name: String,
+        arguments: Vec<DocblockType>,
+    },
+    // `Foo[]`
+    Array(Box<DocblockType>),
+    // `array{id: int, name?: string}`, `list{int, string}`
+    ArrayShape {
+        kind: ArrayShapeKind,
+        entries: Vec<ArrayShapeEntry>,
+        // `false` if the shape ends with `...`, allowing other entries.
+        sealed: bool,
+    },
+    // `class-string`, `class-string<Foo>`
+    ClassString(Option<Box<DocblockType>>),
+    // `key-of<Foo::BAR>`
+    KeyOf(Box<DocblockType>),
+    // `value-of<Foo::BAR>`
+    ValueOf(Box<DocblockType>),
+    // `Foo::BAR`, `Foo::BAR_*`
+    ClassConstant {
+        class: String,
+        constant: String,
+    },
+    // `'foo'`, `"foo"`
+    String(String),
+    // `1`, `-1`
+    Integer(i64),
+    // `1.5`
+    Float(f64),
+    // `?Foo`
+    Nullable(Box<DocblockType>),
+    Union(Vec<DocblockType>),
+    Intersection(Vec<DocblockType>),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ArrayShapeKind {
+    Array,
+    List,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArrayShapeEntry {
+    // `None` for entries without a key, which are numbered from 0.
+    pub key: Option<ArrayShapeKey>,
+    // `name?: string`
+    pub optional: bool,
+    pub value: DocblockType,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ArrayShapeKey {
+    String(String),
+    Integer(i64),
+}
+
+/// Parse a docblock type, which must make up the whole input apart from surrounding
+/// whitespace.
+pub fn parse_type(input: &str) -> Option<DocblockType> {
+    match parse_type_prefix(input)? {
+        (r#type, rest) if rest.trim().is_empty() => Some(r#type),
+        _ => None,
+    }
+}
+
+/// Parse the docblock type at the start of the input, returning it along with the
+/// rest of the input, such as the variable and description of a `@param` tag, without
+/// leading whitespace.
+pub fn parse_type_prefix(input: &str) -> Option<(DocblockType, &str)> {
+    let mut parser = Parser {
+        source: Source::new(input.as_bytes()),
+        depth: 0,
+    };
+    let r#type = parser.union()?;
+
+    Some((r#type, input[parser.source.position()..].trim_start()))
+}
+
+// The deepest nesting of types within each other, such as `array<array<int>>`, that is
+// parsed before giving up, so that malicious docblocks can not overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+struct Parser<'a> {
+    source: Source<'a>,
+    // the number of types the current one is nested within.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        self.source.skip_while(|c| c.is_ascii_whitespace());
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.source.current().copied()
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        self.skip_whitespace();
+        self.source.consume(&[c])
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        self.skip_whitespace();
+        self.source.consume(s.as_bytes())
+    }
+
+    fn union(&mut self) -> Option<DocblockType> {
+        let mut types = vec![self.intersection()?];
+        while self.eat(b'|') {
+            types.push(self.intersection()?);
+        }
+
+        Some(match types.len() {
+            1 => types.pop()?,
+            _ => DocblockType::Union(types),
+        })
+    }
+
+    fn intersection(&mut self) -> Option<DocblockType> {
+        let mut types = vec![self.postfix()?];
+        // `&...$name` and `& $name` are by-reference parameters, not intersections.
+        while self.peek() == Some(b'&')
+            && self
+                .source
+                .peek_byte(1)
+                .is_some_and(|c| !c.is_ascii_whitespace() && !matches!(c, b'$' | b'.'))
+        {
+            self.source.next();
+            types.push(self.postfix()?);
+        }
+
+        Some(match types.len() {
+            1 => types.pop()?,
+            _ => DocblockType::Intersection(types),
+        })
+    }
+
+    fn postfix(&mut self) -> Option<DocblockType> {
+        let mut r#type = self.primary()?;
+        while self.source.consume(b"[]") {
+            r#type = DocblockType::Array(Box::new(r#type));
+        }
+
+        Some(r#type)
+    }
+
+    fn primary(&mut self) -> Option<DocblockType> {
+        if self.depth >= MAX_DEPTH {
+            return None;
+        }
+
+        self.depth += 1;
+        let r#type = self.nested();
+        self.depth -= 1;
+
+        r#type
+    }
+
+    fn nested(&mut self) -> Option<DocblockType> {
+        match self.peek()? {
+            b'?' => {
+                self.source.next();
+                Some(DocblockType::Nullable(Box::new(self.postfix()?)))
+            }
+            b'(' => {
+                self.source.next();
+                let r#type = self.union()?;
+                self.eat(b')').then_some(r#type)
+            }
+            b'\'' | b'"' => Some(DocblockType::String(self.string()?)),
+            b'-' | b'0'..=b'9' => self.number(),
+            _ => {
+                let name = self.name()?;
+                self.named(name)
+            }
+        }
+    }
+
+    fn named(&mut self, name: String) -> Option<DocblockType> {
+        if self.source.consume(b"::") {
+            let constant = self
+                .source
+                .read_while(|c| c.is_ascii_alphanumeric() || matches!(c, b'_' | b'*'));
+            let constant = std::str::from_utf8(constant).ok()?;
+            if constant.is_empty() {
+                return None;
+            }
+
+            return Some(DocblockType::ClassConstant {
+                class: name,
+                constant: constant.to_owned(),
+            });
+        }
+
+        let lowercase = name.to_ascii_lowercase();
+        if self.source.consume(b"{") {
+            let kind = match lowercase.as_str() {
+                "array" | "non-empty-array" => ArrayShapeKind::Array,
+                "list" | "non-empty-list" => ArrayShapeKind::List,
+                _ => return None,
+            };
+
+            return self.shape(kind);
+        }
+
+        if !self.source.consume(b"<") {
+            return Some(match lowercase.as_str() {
+                "class-string" => DocblockType::ClassString(None),
+                _ => DocblockType::Named(name),
+            });
+        }
+
+        let mut arguments = vec![self.union()?];
+        while self.eat(b',') {
+            arguments.push(self.union()?);
+        }
+
+        if !self.eat(b'>') {
+            return None;
+        }
+
+        let argument = |mut arguments: Vec<DocblockType>| match arguments.len() {
+            1 => arguments.pop().map(Box::new),
+            _ => None,
+        };
+
+        Some(match lowercase.as_str() {
+            "class-string" => DocblockType::ClassString(Some(argument(arguments)?)),
+            "key-of" => DocblockType::KeyOf(argument(arguments)?),
+            "value-of" => DocblockType::ValueOf(argument(arguments)?),
+            _ => DocblockType::Generic { name, arguments },
+        })
+    }
+
+    fn shape(&mut self, kind: ArrayShapeKind) -> Option<DocblockType> {
+        let mut entries = vec![];
+        let mut sealed = true;
+
+        while !self.eat(b'}') {
+            if self.eat_str("...") {
+                sealed = false;
+                self.eat(b',');
+                if !self.eat(b'}') {
+                    return None;
+                }
+
+                break;
+            }
+
+            entries.push(self.shape_entry()?);
+            if !self.eat(b',') {
+                if !self.eat(b'}') {
+                    return None;
+                }
+
+                break;
+            }
+        }
+
+        Some(DocblockType::ArrayShape {
+            kind,
+            entries,
+            sealed,
+        })
+    }
+
+    fn shape_entry(&mut self) -> Option<ArrayShapeEntry> {
+        // the key is only known to be one once the `:` or `?:` after it is found.
+        let start = self.source.span();
+        let key = match self.peek()? {
+            b'\'' | b'"' => self.string().map(ArrayShapeKey::String),
+            b'0'..=b'9' | b'-' => match self.number() {
+                Some(DocblockType::Integer(integer)) => Some(ArrayShapeKey::Integer(integer)),
+                _ => None,
+            },
+            _ => self.name().map(ArrayShapeKey::String),
+        };
+
+        if let Some(key) = key {
+            let optional = self.eat(b'?');
+            if self.eat(b':') {
+                return Some(ArrayShapeEntry {
+                    key: Some(key),
+                    optional,
+                    value: self.union()?,
+                });
+            }
+        }
+
+        self.source.reset(start);
+
+        Some(ArrayShapeEntry {
+            key: None,
+            optional: false,
+            value: self.union()?,
+        })
+    }
+
+    fn name(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let name = self.source.read_while(|c| {
+            c.is_ascii_alphanumeric() || matches!(c, b'_' | b'-' | b'\\' | b'$') || c >= 0x80
+        });
+
+        match std::str::from_utf8(name).ok()? {
+            "" => None,
+            name => Some(name.to_owned()),
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let quote = self.peek()?;
+        self.source.next();
+        let string = self.source.read_while(|c| c != quote);
+        if !self.source.consume(&[quote]) {
+            return None;
+        }
+
+        Some(std::str::from_utf8(string).ok()?.to_owned())
+    }
+
+    fn number(&mut self) -> Option<DocblockType> {
+        self.skip_whitespace();
+        let start = self.source.span();
+        self.source.consume(b"-");
+        self.source
+            .skip_while(|c| c.is_ascii_digit() || matches!(c, b'.' | b'_'));
+
+        let number: String = std::str::from_utf8(self.source.slice(self.source.span_from(start)))
+            .ok()?
+            .chars()
+            .filter(|c| *c != '_')
+            .collect();
+
+        match number.parse() {
+            Ok(integer) => Some(DocblockType::Integer(integer)),
+            Err(_) => Some(DocblockType::Float(number.parse().ok()?)),
+        }
+    }
+}
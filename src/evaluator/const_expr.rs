@@ -0,0 +1,314 @@
+use serde::Serialize;
+
+use crate::lexer::byte_string::ByteString;
+use crate::parser::ast::literals::IntegerValue;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::operators::ArithmeticOperationExpression;
+use crate::parser::ast::operators::BitwiseOperationExpression;
+use crate::parser::ast::utils::CommaSeparated;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::Expression;
+use crate::parser::ast::MagicConstantExpression;
+
+/// The value of a constant expression.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(tag = "type", content = "value")]
+pub enum PhpConstValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(ByteString),
+    // The entries of an array, in order, with unique keys.
+    Array(Vec<(PhpArrayKey, PhpConstValue)>),
+}
+
+/// The key of an array entry, which PHP restricts to integers and strings.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+#[serde(tag = "type", content = "value")]
+pub enum PhpArrayKey {
+    Int(i64),
+    String(ByteString),
+}
+
+/// Fold a constant expression into its value, if it only consists of literals and
+/// operators on them.
+pub fn const_expr(expression: &Expression) -> Option<PhpConstValue> {
+    const_expr_with(expression, &mut |_| None)
+}
+
+/// Fold a constant expression into its value, calling `resolve` for any constant it
+/// references, such as `FOO` or `self::FOO`, and for magic constants other than
+/// `__LINE__`, such as `__CLASS__`, whose value depends on where the expression is.
+///
+/// Operations PHP would reject or warn about, such as division by zero, overflowing
+/// integer arithmetic, or arithmetic on non-numeric strings, are not folded.
+pub fn const_expr_with(
+    expression: &Expression,
+    resolve: &mut dyn FnMut(&Expression) -> Option<PhpConstValue>,
+) -> Option<PhpConstValue> {
+    let value = match expression {
+        Expression::Null(_) => PhpConstValue::Null,
+        Expression::Bool(bool) => PhpConstValue::Bool(bool.value),
+        Expression::Literal(Literal::Integer(integer)) => match integer.to_value() {
+            IntegerValue::Integer(value) => PhpConstValue::Int(value),
+            IntegerValue::Overflowed(value) => PhpConstValue::Float(value),
+        },
+        Expression::Literal(Literal::Float(float)) => PhpConstValue::Float(float.to_value()),
+        Expression::Literal(Literal::String(string)) => PhpConstValue::String(string.value.clone()),
+        Expression::Parenthesized(parenthesized) => {
+            return const_expr_with(&parenthesized.expr, resolve)
+        }
+        Expression::Concat(concat) => {
+            let mut left = to_string(const_expr_with(&concat.left, resolve)?)?;
+            let right = to_string(const_expr_with(&concat.right, resolve)?)?;
+            left.extend(right.iter());
+
+            PhpConstValue::String(left)
+        }
+        Expression::ArithmeticOperation(operation) => arithmetic(operation, resolve)?,
+        Expression::BitwiseOperation(operation) => bitwise(operation, resolve)?,
+        Expression::Array(array) => self::array(&array.items, resolve)?,
+        Expression::ShortArray(array) => self::array(&array.items, resolve)?,
+        Expression::ArrayIndex(index) => {
+            let PhpConstValue::Array(entries) = const_expr_with(&index.array, resolve)? else {
+                return None;
+            };
+            let key = to_key(const_expr_with(index.index.as_ref()?, resolve)?)?;
+
+            return entries
+                .into_iter()
+                .find_map(|(k, value)| (k == key).then_some(value));
+        }
+        Expression::MagicConstant(MagicConstantExpression::Line(span)) => {
+            PhpConstValue::Int(span.line as i64)
+        }
+        Expression::Identifier(_) | Expression::ConstantFetch(_) | Expression::MagicConstant(_) => {
+            resolve(expression)?
+        }
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+fn arithmetic(
+    operation: &ArithmeticOperationExpression,
+    resolve: &mut dyn FnMut(&Expression) -> Option<PhpConstValue>,
+) -> Option<PhpConstValue> {
+    let (left, right) = match operation {
+        ArithmeticOperationExpression::Negative { right, .. } => {
+            return match to_number(const_expr_with(right, resolve)?)? {
+                PhpConstValue::Int(value) => Some(PhpConstValue::Int(value.checked_neg()?)),
+                PhpConstValue::Float(value) => Some(PhpConstValue::Float(-value)),
+                _ => None,
+            };
+        }
+        ArithmeticOperationExpression::Positive { right, .. } => {
+            return to_number(const_expr_with(right, resolve)?);
+        }
+        ArithmeticOperationExpression::Addition { left, right, .. }
+        | ArithmeticOperationExpression::Subtraction { left, right, .. }
+        | ArithmeticOperationExpression::Multiplication { left, right, .. }
+        | ArithmeticOperationExpression::Division { left, right, .. }
+        | ArithmeticOperationExpression::Modulo { left, right, .. }
+        | ArithmeticOperationExpression::Exponentiation { left, right, .. } => (
+            to_number(const_expr_with(left, resolve)?)?,
+            to_number(const_expr_with(right, resolve)?)?,
+        ),
+        _ => return None,
+    };
+
+    if let (PhpConstValue::Int(left), PhpConstValue::Int(right)) = (&left, &right) {
+        let (left, right) = (*left, *right);
+        let value = match operation {
+            ArithmeticOperationExpression::Addition { .. } => left.checked_add(right),
+            ArithmeticOperationExpression::Subtraction { .. } => left.checked_sub(right),
+            ArithmeticOperationExpression::Multiplication { .. } => left.checked_mul(right),
+            ArithmeticOperationExpression::Division { .. }
+                if left.checked_rem(right) == Some(0) =>
+            {
+                left.checked_div(right)
+            }
+            ArithmeticOperationExpression::Division { .. } if right != 0 => {
+                return Some(PhpConstValue::Float(left as f64 / right as f64));
+            }
+            ArithmeticOperationExpression::Modulo { .. } => left.checked_rem(right),
+            ArithmeticOperationExpression::Exponentiation { .. } => {
+                left.checked_pow(u32::try_from(right).ok()?)
+            }
+            _ => None,
+        };
+
+        return value.map(PhpConstValue::Int);
+    }
+
+    let (left, right) = (to_float(&left)?, to_float(&right)?);
+    let value = match operation {
+        ArithmeticOperationExpression::Addition { .. } => left + right,
+        ArithmeticOperationExpression::Subtraction { .. } => left - right,
+        ArithmeticOperationExpression::Multiplication { .. } => left * right,
+        ArithmeticOperationExpression::Division { .. } if right != 0.0 => left / right,
+        ArithmeticOperationExpression::Exponentiation { .. } => left.powf(right),
+        // `%` always operates on integers.
+        _ => return None,
+    };
+
+    Some(PhpConstValue::Float(value))
+}
+
+fn bitwise(
+    operation: &BitwiseOperationExpression,
+    resolve: &mut dyn FnMut(&Expression) -> Option<PhpConstValue>,
+) -> Option<PhpConstValue> {
+    let mut integer = |expression: &Expression| match const_expr_with(expression, resolve)? {
+        PhpConstValue::Int(value) => Some(value),
+        _ => None,
+    };
+
+    let value = match operation {
+        BitwiseOperationExpression::And { left, right, .. } => integer(left)? & integer(right)?,
+        BitwiseOperationExpression::Or { left, right, .. } => integer(left)? | integer(right)?,
+        BitwiseOperationExpression::Xor { left, right, .. } => integer(left)? ^ integer(right)?,
+        BitwiseOperationExpression::LeftShift { left, right, .. } => {
+            integer(left)?.checked_shl(u32::try_from(integer(right)?).ok()?)?
+        }
+        BitwiseOperationExpression::RightShift { left, right, .. } => {
+            integer(left)?.checked_shr(u32::try_from(integer(right)?).ok()?)?
+        }
+        BitwiseOperationExpression::Not { right, .. } => !integer(right)?,
+    };
+
+    Some(PhpConstValue::Int(value))
+}
+
+// Build an array like PHP does: entries without a key, and unpacked entries with an
+// integer key, are numbered from the largest integer key so far, and later entries
+// overwrite earlier ones with the same key.
+fn array(
+    items: &CommaSeparated<ArrayItem>,
+    resolve: &mut dyn FnMut(&Expression) -> Option<PhpConstValue>,
+) -> Option<PhpConstValue> {
+    let mut entries: Vec<(PhpArrayKey, PhpConstValue)> = vec![];
+    // one more than the largest integer key so far, which can be past `i64::MAX`.
+    let mut next: Option<i128> = None;
+
+    let mut insert = |entries: &mut Vec<(PhpArrayKey, PhpConstValue)>,
+                      key: Option<PhpArrayKey>,
+                      value: PhpConstValue| {
+        let key = match key {
+            Some(key) => key,
+            None => PhpArrayKey::Int(i64::try_from(next.unwrap_or(0)).ok()?),
+        };
+
+        if let PhpArrayKey::Int(key) = key {
+            let key = key as i128 + 1;
+            if next.is_none_or(|next| key > next) {
+                next = Some(key);
+            }
+        }
+
+        match entries.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = value,
+            None => entries.push((key, value)),
+        }
+
+        Some(())
+    };
+
+    for item in items.iter() {
+        match item {
+            ArrayItem::Value { value } => {
+                let value = const_expr_with(value, resolve)?;
+                insert(&mut entries, None, value)?;
+            }
+            ArrayItem::KeyValue { key, value, .. } => {
+                let key = to_key(const_expr_with(key, resolve)?)?;
+                let value = const_expr_with(value, resolve)?;
+                insert(&mut entries, Some(key), value)?;
+            }
+            ArrayItem::SpreadValue { value, .. } => {
+                let PhpConstValue::Array(unpacked) = const_expr_with(value, resolve)? else {
+                    return None;
+                };
+
+                for (key, value) in unpacked {
+                    let key = match key {
+                        PhpArrayKey::Int(_) => None,
+                        key => Some(key),
+                    };
+                    insert(&mut entries, key, value)?;
+                }
+            }
+            // references aren't allowed in constant expressions, and skipped items are
+            // only allowed in `list()`.
+            _ => return None,
+        }
+    }
+
+    Some(PhpConstValue::Array(entries))
+}
+
+// Convert a value used as an array key, like PHP does for numeric strings, booleans,
+// `null` and floats.
+fn to_key(value: PhpConstValue) -> Option<PhpArrayKey> {
+    let key = match value {
+        PhpConstValue::Null => PhpArrayKey::String(ByteString::default()),
+        PhpConstValue::Bool(value) => PhpArrayKey::Int(value as i64),
+        PhpConstValue::Int(value) => PhpArrayKey::Int(value),
+        PhpConstValue::Float(value) if value.is_finite() => PhpArrayKey::Int(value as i64),
+        // only decimal integers written the way PHP would print them are converted, e.g.
+        // "8" but not "08", "+8" or "8.0".
+        PhpConstValue::String(string) => match std::str::from_utf8(&string)
+            .ok()
+            .and_then(|s| s.parse::<i64>().ok())
+            .filter(|integer| integer.to_string().as_bytes() == &string[..])
+        {
+            Some(integer) => PhpArrayKey::Int(integer),
+            None => PhpArrayKey::String(string),
+        },
+        _ => return None,
+    };
+
+    Some(key)
+}
+
+// Convert a value used in arithmetic to a number, like PHP does for `null`, booleans
+// and numeric strings.
+fn to_number(value: PhpConstValue) -> Option<PhpConstValue> {
+    match value {
+        PhpConstValue::Null => Some(PhpConstValue::Int(0)),
+        PhpConstValue::Bool(value) => Some(PhpConstValue::Int(value as i64)),
+        PhpConstValue::Int(_) | PhpConstValue::Float(_) => Some(value),
+        PhpConstValue::String(string) => {
+            let string = std::str::from_utf8(&string).ok()?.trim();
+            match string.parse::<i64>() {
+                Ok(value) => Some(PhpConstValue::Int(value)),
+                Err(_) => Some(PhpConstValue::Float(string.parse().ok()?)),
+            }
+        }
+        PhpConstValue::Array(_) => None,
+    }
+}
+
+fn to_float(value: &PhpConstValue) -> Option<f64> {
+    match value {
+        PhpConstValue::Int(value) => Some(*value as f64),
+        PhpConstValue::Float(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn to_string(value: PhpConstValue) -> Option<ByteString> {
+    let string = match value {
+        PhpConstValue::Null | PhpConstValue::Bool(false) => ByteString::default(),
+        PhpConstValue::Bool(true) => "1".into(),
+        PhpConstValue::Int(value) => value.to_string().into(),
+        // PHP's float formatting differs from Rust's, so floats are not converted.
+        PhpConstValue::Float(_) => return None,
+        PhpConstValue::String(string) => string,
+        PhpConstValue::Array(_) => return None,
+    };
+
+    Some(string)
+}
@@ -0,0 +1,373 @@
+pub mod const_expr;
+
+pub use const_expr::const_expr;
+pub use const_expr::const_expr_with;
+pub use const_expr::PhpArrayKey;
+pub use const_expr::PhpConstValue;
+
+use crate::parser::ast::control_flow::IfStatementBody;
+use crate::parser::ast::declares::DeclareBody;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::loops::ForStatementBody;
+use crate::parser::ast::loops::ForeachStatementBody;
+use crate::parser::ast::loops::ForeachStatementIterator;
+use crate::parser::ast::loops::Level;
+use crate::parser::ast::loops::WhileStatementBody;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::Expression;
+use crate::parser::ast::FunctionCallExpression;
+use crate::parser::ast::Statement;
+
+/// How execution of a statement completed.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Flow<V> {
+    Normal,
+    Return(Option<V>),
+    Break(usize),
+    Continue(usize),
+}
+
+/// The kind of an `include` or `require` expression.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum IncludeKind {
+    Include,
+    IncludeOnce,
+    Require,
+    RequireOnce,
+}
+
+/// The surface an external runtime implements to evaluate a program.
+///
+/// Control flow statements are driven by [`execute`], which delegates every
+/// expression to [`Evaluator::eval_expr`]. By default, `eval_expr` routes includes
+/// and function calls to their own hooks, and everything else to
+/// [`Evaluator::eval_other`], so an engine only has to match on the expressions it
+/// has semantics for.
+///
+/// The driver does not model exceptions: `throw` expressions and `try` statements
+/// are left to the engine.
+pub trait Evaluator {
+    type Value;
+
+    fn eval_expr(&mut self, expression: &Expression) -> Self::Value {
+        match expression {
+            Expression::Include(include) => self.include(IncludeKind::Include, &include.path),
+            Expression::IncludeOnce(include) => {
+                self.include(IncludeKind::IncludeOnce, &include.path)
+            }
+            Expression::Require(require) => self.include(IncludeKind::Require, &require.path),
+            Expression::RequireOnce(require) => {
+                self.include(IncludeKind::RequireOnce, &require.path)
+            }
+            Expression::FunctionCall(call) => self.call_function(call),
+            expression => self.eval_other(expression),
+        }
+    }
+
+    /// Evaluate any expression that does not have a dedicated hook.
+    fn eval_other(&mut self, expression: &Expression) -> Self::Value;
+
+    fn call_function(&mut self, call: &FunctionCallExpression) -> Self::Value;
+
+    /// Evaluate an `include` or `require` of the given path expression.
+    ///
+    /// Engines are expected to resolve and parse the file themselves, and may run it
+    /// through [`execute`].
+    fn include(&mut self, kind: IncludeKind, path: &Expression) -> Self::Value;
+
+    fn is_truthy(&mut self, value: &Self::Value) -> bool;
+
+    /// Compare two values using `==`, as `switch` does.
+    fn loose_equals(&mut self, left: &Self::Value, right: &Self::Value) -> bool;
+
+    fn echo(&mut self, value: Self::Value);
+
+    /// Assign a value to a `foreach` key or value target.
+    fn assign(&mut self, target: &Expression, value: Self::Value);
+
+    /// The key and value pairs to iterate over in a `foreach` loop.
+    fn iterate(&mut self, value: Self::Value) -> Vec<(Self::Value, Self::Value)>;
+
+    /// Called when entering the body of a namespace declaration.
+    fn enter_namespace(&mut self, _name: Option<&SimpleIdentifier>) {}
+
+    /// Execute any statement the driver does not handle itself, such as declarations,
+    /// `try`, `global`, `static`, and inline HTML.
+    fn exec_other(&mut self, statement: &Statement) -> Flow<Self::Value>;
+}
+
+/// Execute the statements in order, until one of them completes abnormally.
+pub fn execute<E: Evaluator + ?Sized>(
+    evaluator: &mut E,
+    statements: &[Statement],
+) -> Flow<E::Value> {
+    for statement in statements {
+        match execute_statement(evaluator, statement) {
+            Flow::Normal => {}
+            flow => return flow,
+        }
+    }
+
+    Flow::Normal
+}
+
+pub fn execute_statement<E: Evaluator + ?Sized>(
+    evaluator: &mut E,
+    statement: &Statement,
+) -> Flow<E::Value> {
+    match statement {
+        Statement::Expression(statement) => {
+            evaluator.eval_expr(&statement.expression);
+        }
+        Statement::Echo(echo) => {
+            for value in &echo.values {
+                let value = evaluator.eval_expr(value);
+                evaluator.echo(value);
+            }
+        }
+        Statement::Return(statement) => {
+            return Flow::Return(
+                statement
+                    .value
+                    .as_ref()
+                    .map(|value| evaluator.eval_expr(value)),
+            );
+        }
+        Statement::Break(statement) => return Flow::Break(level(statement.level.as_ref())),
+        Statement::Continue(statement) => return Flow::Continue(level(statement.level.as_ref())),
+        Statement::Block(block) => return execute(evaluator, &block.statements),
+        Statement::If(statement) => {
+            let condition = evaluator.eval_expr(&statement.condition);
+            let condition = evaluator.is_truthy(&condition);
+
+            match &statement.body {
+                IfStatementBody::Statement {
+                    statement,
+                    elseifs,
+                    r#else,
+                } => {
+                    if condition {
+                        return execute_statement(evaluator, statement);
+                    }
+
+                    for elseif in elseifs {
+                        let condition = evaluator.eval_expr(&elseif.condition);
+                        if evaluator.is_truthy(&condition) {
+                            return execute_statement(evaluator, &elseif.statement);
+                        }
+                    }
+
+                    if let Some(r#else) = r#else {
+                        return execute_statement(evaluator, &r#else.statement);
+                    }
+                }
+                IfStatementBody::Block {
+                    statements,
+                    elseifs,
+                    r#else,
+                    ..
+                } => {
+                    if condition {
+                        return execute(evaluator, statements);
+                    }
+
+                    for elseif in elseifs {
+                        let condition = evaluator.eval_expr(&elseif.condition);
+                        if evaluator.is_truthy(&condition) {
+                            return execute(evaluator, &elseif.statements);
+                        }
+                    }
+
+                    if let Some(r#else) = r#else {
+                        return execute(evaluator, &r#else.statements);
+                    }
+                }
+            }
+        }
+        Statement::Switch(statement) => {
+            let subject = evaluator.eval_expr(&statement.condition);
+
+            let mut matched = None;
+            for (position, case) in statement.body.cases().iter().enumerate() {
+                if let Some(condition) = &case.condition {
+                    let condition = evaluator.eval_expr(condition);
+                    if evaluator.loose_equals(&subject, &condition) {
+                        matched = Some(position);
+                        break;
+                    }
+                }
+            }
+
+            let matched = matched.or_else(|| {
+                statement
+                    .body
+                    .cases()
+                    .iter()
+                    .position(|case| case.condition.is_none())
+            });
+
+            if let Some(matched) = matched {
+                // Execution falls through the following cases until a `break`.
+                for case in &statement.body.cases()[matched..] {
+                    match execute(evaluator, &case.body) {
+                        Flow::Normal => {}
+                        flow => return exit_loop(flow).unwrap_or(Flow::Normal),
+                    }
+                }
+            }
+        }
+        Statement::While(statement) => loop {
+            let condition = evaluator.eval_expr(&statement.condition);
+            if !evaluator.is_truthy(&condition) {
+                break;
+            }
+
+            let flow = match &statement.body {
+                WhileStatementBody::Statement { statement } => {
+                    execute_statement(evaluator, statement)
+                }
+                WhileStatementBody::Block { statements, .. } => execute(evaluator, statements),
+            };
+
+            if let Some(flow) = exit_loop(flow) {
+                return flow;
+            }
+        },
+        Statement::DoWhile(statement) => loop {
+            if let Some(flow) = exit_loop(execute_statement(evaluator, &statement.body)) {
+                return flow;
+            }
+
+            let condition = evaluator.eval_expr(&statement.condition);
+            if !evaluator.is_truthy(&condition) {
+                break;
+            }
+        },
+        Statement::For(statement) => {
+            let iterator = &statement.iterator;
+            for initialization in &iterator.initializations.inner {
+                evaluator.eval_expr(initialization);
+            }
+
+            loop {
+                // All conditions are evaluated, but only the last one decides whether
+                // the loop continues.
+                let mut proceed = true;
+                for condition in &iterator.conditions.inner {
+                    let condition = evaluator.eval_expr(condition);
+                    proceed = evaluator.is_truthy(&condition);
+                }
+
+                if !proceed {
+                    break;
+                }
+
+                let flow = match &statement.body {
+                    ForStatementBody::Statement { statement } => {
+                        execute_statement(evaluator, statement)
+                    }
+                    ForStatementBody::Block { statements, .. } => execute(evaluator, statements),
+                };
+
+                if let Some(flow) = exit_loop(flow) {
+                    return flow;
+                }
+
+                for expression in &iterator.r#loop.inner {
+                    evaluator.eval_expr(expression);
+                }
+            }
+        }
+        Statement::Foreach(statement) => {
+            let (expression, key, value) = match &statement.iterator {
+                ForeachStatementIterator::Value {
+                    expression, value, ..
+                } => (expression, None, value),
+                ForeachStatementIterator::KeyAndValue {
+                    expression,
+                    key,
+                    value,
+                    ..
+                } => (expression, Some(key), value),
+            };
+
+            let iterable = evaluator.eval_expr(expression);
+            for (current_key, current_value) in evaluator.iterate(iterable) {
+                if let Some(key) = key {
+                    evaluator.assign(key, current_key);
+                }
+                evaluator.assign(value, current_value);
+
+                let flow = match &statement.body {
+                    ForeachStatementBody::Statement { statement } => {
+                        execute_statement(evaluator, statement)
+                    }
+                    ForeachStatementBody::Block { statements, .. } => {
+                        execute(evaluator, statements)
+                    }
+                };
+
+                if let Some(flow) = exit_loop(flow) {
+                    return flow;
+                }
+            }
+        }
+        Statement::Declare(statement) => match &statement.body {
+            DeclareBody::Braced { statements, .. } | DeclareBody::Block { statements, .. } => {
+                return execute(evaluator, statements);
+            }
+            DeclareBody::Expression { expression, .. } => {
+                evaluator.eval_expr(expression);
+            }
+            DeclareBody::Noop { .. } => {}
+        },
+        Statement::Namespace(namespace) => {
+            let (name, statements) = match namespace {
+                NamespaceStatement::Unbraced(namespace) => {
+                    (Some(&namespace.name), &namespace.statements)
+                }
+                NamespaceStatement::Braced(namespace) => {
+                    (namespace.name.as_ref(), &namespace.body.statements)
+                }
+            };
+
+            evaluator.enter_namespace(name);
+
+            return execute(evaluator, statements);
+        }
+        Statement::Shebang(_)
+        | Statement::FullOpeningTag(_)
+        | Statement::ShortOpeningTag(_)
+        | Statement::EchoOpeningTag(_)
+        | Statement::ClosingTag(_)
+        | Statement::Comment(_)
+        | Statement::Noop(_) => {}
+        statement => return evaluator.exec_other(statement),
+    }
+
+    Flow::Normal
+}
+
+// Consume a `break` or `continue` targeting the innermost loop, returning the flow
+// that should leave the loop, if any.
+fn exit_loop<V>(flow: Flow<V>) -> Option<Flow<V>> {
+    match flow {
+        Flow::Normal | Flow::Continue(1) => None,
+        Flow::Break(1) => Some(Flow::Normal),
+        Flow::Break(level) => Some(Flow::Break(level - 1)),
+        Flow::Continue(level) => Some(Flow::Continue(level - 1)),
+        flow => Some(flow),
+    }
+}
+
+fn level(level: Option<&Level>) -> usize {
+    match level {
+        Some(Level::Literal(literal)) => std::str::from_utf8(&literal.value)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(1)
+            .max(1),
+        Some(Level::Parenthesized { level, .. }) => self::level(Some(level)),
+        None => 1,
+    }
+}
@@ -0,0 +1,103 @@
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow::array::ArrayRef;
+use arrow::array::BinaryArray;
+use arrow::array::StringArray;
+use arrow::array::UInt64Array;
+use arrow::datatypes::DataType;
+use arrow::datatypes::Field;
+use arrow::datatypes::Schema;
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use parquet::errors::ParquetError;
+
+use crate::export::NodeRow;
+use crate::export::TokenRow;
+
+pub fn token_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("index", DataType::UInt64, false),
+        Field::new("kind", DataType::Utf8, false),
+        Field::new("line", DataType::UInt64, false),
+        Field::new("column", DataType::UInt64, false),
+        Field::new("position", DataType::UInt64, false),
+        Field::new("value", DataType::Binary, false),
+    ])
+}
+
+pub fn node_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("id", DataType::UInt64, false),
+        Field::new("parent", DataType::UInt64, true),
+        Field::new("kind", DataType::Utf8, false),
+        Field::new("line", DataType::UInt64, true),
+        Field::new("column", DataType::UInt64, true),
+        Field::new("position", DataType::UInt64, true),
+        Field::new("name", DataType::Binary, true),
+    ])
+}
+
+pub fn tokens_to_record_batch(rows: &[TokenRow]) -> Result<RecordBatch, ArrowError> {
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt64Array::from_iter_values(
+            rows.iter().map(|row| row.index),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|row| row.kind.as_str()),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            rows.iter().map(|row| row.line),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            rows.iter().map(|row| row.column),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            rows.iter().map(|row| row.position),
+        )),
+        Arc::new(BinaryArray::from_iter_values(
+            rows.iter().map(|row| row.value.as_slice()),
+        )),
+    ];
+
+    RecordBatch::try_new(Arc::new(token_schema()), columns)
+}
+
+pub fn nodes_to_record_batch(rows: &[NodeRow]) -> Result<RecordBatch, ArrowError> {
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt64Array::from_iter_values(rows.iter().map(|row| row.id))),
+        Arc::new(UInt64Array::from_iter(rows.iter().map(|row| row.parent))),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|row| row.kind),
+        )),
+        Arc::new(UInt64Array::from_iter(
+            rows.iter().map(|row| row.span.map(|span| span.line as u64)),
+        )),
+        Arc::new(UInt64Array::from_iter(
+            rows.iter()
+                .map(|row| row.span.map(|span| span.column as u64)),
+        )),
+        Arc::new(UInt64Array::from_iter(
+            rows.iter()
+                .map(|row| row.span.map(|span| span.position as u64)),
+        )),
+        Arc::new(BinaryArray::from_iter(
+            rows.iter()
+                .map(|row| row.name.as_ref().map(|name| name.as_slice())),
+        )),
+    ];
+
+    RecordBatch::try_new(Arc::new(node_schema()), columns)
+}
+
+/// Write the given record batch to a Parquet file.
+pub fn write_parquet(path: impl AsRef<Path>, batch: &RecordBatch) -> Result<(), ParquetError> {
+    let file = File::create(path)?;
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), None)?;
+    writer.write(batch)?;
+    writer.close()?;
+
+    Ok(())
+}
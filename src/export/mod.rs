@@ -0,0 +1,139 @@
+use crate::downcast::downcast;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::lexer::token::Token;
+use crate::node::Node;
+use crate::parser::ast::comments::Comment;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::literals::LiteralFloat;
+use crate::parser::ast::literals::LiteralInteger;
+use crate::parser::ast::literals::LiteralString;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::Statement;
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod typescript;
+
+/// A single token, flattened into a row.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TokenRow {
+    pub index: u64,
+    pub kind: String,
+    pub line: u64,
+    pub column: u64,
+    pub position: u64,
+    pub value: ByteString,
+}
+
+/// A single AST node, flattened into a row.
+///
+/// The span is the start of the first identifier, variable, literal or comment found
+/// within the node, and is missing for nodes that contain none of them.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NodeRow {
+    pub id: u64,
+    pub parent: Option<u64>,
+    pub kind: &'static str,
+    pub span: Option<Span>,
+    pub name: Option<ByteString>,
+}
+
+pub fn token_rows(tokens: &[Token]) -> Vec<TokenRow> {
+    tokens
+        .iter()
+        .enumerate()
+        .map(|(index, token)| TokenRow {
+            index: index as u64,
+            kind: format!("{:?}", token.kind),
+            line: token.span.line as u64,
+            column: token.span.column as u64,
+            position: token.span.position as u64,
+            value: token.value.clone(),
+        })
+        .collect()
+}
+
+/// Flatten a program into rows, in pre-order.
+///
+/// The program itself is represented by a row with the kind `Program` and the id `0`.
+pub fn node_rows(program: &mut [Statement]) -> Vec<NodeRow> {
+    let mut rows = vec![NodeRow {
+        id: 0,
+        parent: None,
+        kind: "Program",
+        span: None,
+        name: None,
+    }];
+
+    let mut span = None;
+    for statement in program.iter_mut() {
+        span = earliest(span, flatten(statement, 0, &mut rows));
+    }
+
+    rows[0].span = span;
+    rows
+}
+
+// Push the node and its descendants, returning the earliest span found among them.
+fn flatten(node: &mut dyn Node, parent: u64, rows: &mut Vec<NodeRow>) -> Option<Span> {
+    let id = rows.len();
+    let (mut span, name) = leaf(node);
+
+    rows.push(NodeRow {
+        id: id as u64,
+        parent: Some(parent),
+        kind: node.kind(),
+        span,
+        name,
+    });
+
+    for child in node.children() {
+        if rows[id].name.is_none() {
+            if let Some(identifier) = downcast::<SimpleIdentifier>(child) {
+                rows[id].name = Some(identifier.value.clone());
+            }
+        }
+
+        span = earliest(span, flatten(child, id as u64, rows));
+    }
+
+    rows[id].span = span;
+    span
+}
+
+/// Find the earliest span within the given node, using the same rules as [`NodeRow::span`].
+pub(crate) fn first_span(node: &mut dyn Node) -> Option<Span> {
+    let (mut span, _) = leaf(node);
+    for child in node.children() {
+        span = earliest(span, first_span(child));
+    }
+
+    span
+}
+
+fn leaf(node: &dyn Node) -> (Option<Span>, Option<ByteString>) {
+    if let Some(identifier) = downcast::<SimpleIdentifier>(node) {
+        (Some(identifier.span), Some(identifier.value.clone()))
+    } else if let Some(variable) = downcast::<SimpleVariable>(node) {
+        (Some(variable.span), Some(variable.name.clone()))
+    } else if let Some(literal) = downcast::<LiteralString>(node) {
+        (Some(literal.span), None)
+    } else if let Some(literal) = downcast::<LiteralInteger>(node) {
+        (Some(literal.span), None)
+    } else if let Some(literal) = downcast::<LiteralFloat>(node) {
+        (Some(literal.span), None)
+    } else if let Some(comment) = downcast::<Comment>(node) {
+        (Some(comment.span), None)
+    } else {
+        (None, None)
+    }
+}
+
+fn earliest(a: Option<Span>, b: Option<Span>) -> Option<Span> {
+    match (a, b) {
+        (Some(a), Some(b)) if b.position < a.position => Some(b),
+        (Some(a), _) => Some(a),
+        (None, b) => b,
+    }
+}
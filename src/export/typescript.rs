@@ -0,0 +1,377 @@
+use std::fmt::Write;
+
+use crate::analysis::enums::EnumShape;
+use crate::docblock::docblock_comment;
+use crate::docblock::parse_docblock;
+use crate::docblock::ArrayShapeKey;
+use crate::docblock::ArrayShapeKind;
+use crate::docblock::DocblockType;
+use crate::docblock::TagKind;
+use crate::evaluator::PhpConstValue;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::comments::CommentGroup;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::modifiers::Visibility;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::Statement;
+
+/// Emit TypeScript definitions for the classes and enums in a program.
+///
+/// Classes become interfaces with the public, non-static properties that
+/// `json_encode()` would output, including promoted constructor properties. A
+/// `@var` docblock tag (or a `@param` tag of the constructor, for promoted
+/// properties) takes precedence over the declared type, as it is usually more
+/// precise. Backed enums become enums with their evaluated case values, and unit
+/// enums become enums with each case's name as its value.
+pub fn typescript_definitions(program: &[Statement]) -> String {
+    let mut definitions = vec![];
+    collect(program, &mut definitions);
+
+    definitions.join("\n")
+}
+
+fn collect(statements: &[Statement], definitions: &mut Vec<String>) {
+    for statement in statements {
+        match statement {
+            Statement::Class(class) => definitions.push(interface(class)),
+            Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                collect(&namespace.statements, definitions)
+            }
+            Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                collect(&namespace.body.statements, definitions)
+            }
+            Statement::Block(block) => collect(&block.statements, definitions),
+            statement => {
+                if let Some(shape) = EnumShape::from_statement(statement) {
+                    definitions.push(r#enum(&shape));
+                }
+            }
+        }
+    }
+}
+
+fn interface(class: &ClassStatement) -> String {
+    let name = class.name.value.to_string();
+
+    let mut output = format!("export interface {}", name);
+    if let Some(extends) = &class.extends {
+        write!(
+            output,
+            " extends {}",
+            short_name(&extends.parent.value.to_string())
+        )
+        .unwrap();
+    }
+    output.push_str(" {\n");
+
+    let mut field = |readonly: bool, property: &[u8], r#type: String| {
+        let readonly = if readonly { "readonly " } else { "" };
+        let property = String::from_utf8_lossy(property.strip_prefix(b"$").unwrap_or(property));
+        writeln!(output, "    {}{}: {};", readonly, property, r#type).unwrap();
+    };
+
+    for member in &class.body.members {
+        let (comments, r#type, entries, readonly) = match member {
+            ClassMember::Property(property)
+                if property.modifiers.visibility() == Visibility::Public
+                    && !property.modifiers.has_static() =>
+            {
+                (
+                    &property.comments,
+                    &property.r#type,
+                    &property.entries,
+                    property.modifiers.has_readonly(),
+                )
+            }
+            ClassMember::VariableProperty(property) => (
+                &property.comments,
+                &property.r#type,
+                &property.entries,
+                false,
+            ),
+            ClassMember::ConcreteConstructor(constructor) => {
+                for parameter in constructor.parameters.parameters.iter() {
+                    if parameter.modifiers.is_empty()
+                        || parameter.modifiers.visibility() != Visibility::Public
+                    {
+                        continue;
+                    }
+
+                    let variable = &parameter.name.name;
+                    let r#type = docblock_tag(&parameter.comments, "var", None, &name)
+                        .or_else(|| {
+                            docblock_tag(&constructor.comments, "param", Some(variable), &name)
+                        })
+                        .or_else(|| parameter.data_type.as_ref().map(|ty| native(ty, &name)))
+                        .unwrap_or_else(|| "unknown".to_owned());
+
+                    field(parameter.modifiers.has_readonly(), variable, r#type);
+                }
+
+                continue;
+            }
+            _ => continue,
+        };
+
+        for entry in entries {
+            let variable = match entry {
+                PropertyEntry::Uninitialized { variable }
+                | PropertyEntry::Initialized { variable, .. } => &variable.name,
+            };
+
+            let r#type = docblock_tag(comments, "var", None, &name)
+                .or_else(|| r#type.as_ref().map(|ty| native(ty, &name)))
+                .unwrap_or_else(|| "unknown".to_owned());
+
+            field(readonly, variable, r#type);
+        }
+    }
+
+    output.push_str("}\n");
+    output
+}
+
+fn r#enum(shape: &EnumShape) -> String {
+    let mut output = format!("export enum {} {{\n", shape.name.value);
+
+    for case in &shape.cases {
+        let name = case.name.value.to_string();
+        let value = match &case.value {
+            Some(PhpConstValue::Int(value)) => value.to_string(),
+            Some(PhpConstValue::String(value)) => string(&value.to_string()),
+            // a backed case whose value could not be evaluated can't be represented.
+            Some(_) => continue,
+            None if shape.backing_type.is_some() => continue,
+            None => string(&name),
+        };
+
+        writeln!(output, "    {} = {},", name, value).unwrap();
+    }
+
+    output.push_str("}\n");
+    output
+}
+
+fn string(value: &str) -> String {
+    serde_json::to_string(value).unwrap()
+}
+
+fn short_name(name: &str) -> String {
+    name.rsplit('\\').next().unwrap_or(name).to_owned()
+}
+
+fn native(r#type: &Type, class: &str) -> String {
+    match r#type {
+        Type::Named(_, name) => short_name(&name.to_string()),
+        Type::Nullable(_, inner) => format!("{} | null", native(inner, class)),
+        Type::Union(types) => join(types, " | ", class),
+        Type::Intersection(types) => join(types, " & ", class),
+        Type::Null(_) => "null".to_owned(),
+        Type::True(_) => "true".to_owned(),
+        Type::False(_) => "false".to_owned(),
+        Type::Float(_) | Type::Integer(_) => "number".to_owned(),
+        Type::Boolean(_) => "boolean".to_owned(),
+        Type::String(_) => "string".to_owned(),
+        Type::Array(_) | Type::Iterable(_) => "unknown[]".to_owned(),
+        Type::Object(_) => "object".to_owned(),
+        Type::StaticReference(_) | Type::SelfReference(_) => class.to_owned(),
+        Type::Mixed(_)
+        | Type::Callable(_)
+        | Type::Void(_)
+        | Type::Never(_)
+        | Type::ParentReference(_) => "unknown".to_owned(),
+    }
+}
+
+fn join(types: &[Type], separator: &str, class: &str) -> String {
+    types
+        .iter()
+        .map(|ty| match ty {
+            Type::Union(_) | Type::Intersection(_) => format!("({})", native(ty, class)),
+            ty => native(ty, class),
+        })
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+// Find the type of the given docblock tag, and convert it to TypeScript. For `@param`
+// tags, the variable the tag must describe is given.
+fn docblock_tag(
+    comments: &CommentGroup,
+    tag: &str,
+    variable: Option<&[u8]>,
+    class: &str,
+) -> Option<String> {
+    let docblock = parse_docblock(docblock_comment(comments)?);
+
+    let r#type = docblock.tags_named(tag).find_map(|tag| {
+        let r#type = match (&tag.kind, variable) {
+            (TagKind::Var { r#type, .. }, None) => r#type,
+            (
+                TagKind::Param {
+                    r#type: Some(r#type),
+                    variable: name,
+                    ..
+                },
+                Some(variable),
+            ) if name.as_bytes() == variable => r#type,
+            _ => return None,
+        };
+
+        from_docblock(r#type, class)
+    });
+
+    r#type
+}
+
+// The TypeScript type of a scalar docblock type, given in lowercase, with or without its
+// generic arguments.
+fn scalar(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "int" | "integer" | "positive-int" | "negative-int" | "non-negative-int"
+        | "non-positive-int" | "non-zero-int" | "int-mask" | "int-mask-of" | "float" | "double" => {
+            "number"
+        }
+        "string"
+        | "non-empty-string"
+        | "numeric-string"
+        | "literal-string"
+        | "non-empty-literal-string"
+        | "lowercase-string"
+        | "non-falsy-string"
+        | "truthy-string"
+        | "class-string"
+        | "interface-string"
+        | "trait-string"
+        | "enum-string"
+        | "callable-string" => "string",
+        "bool" | "boolean" => "boolean",
+        _ => return None,
+    })
+}
+
+fn from_docblock(r#type: &DocblockType, class: &str) -> Option<String> {
+    let r#type = match r#type {
+        DocblockType::Named(name) => match name.to_ascii_lowercase().as_str() {
+            lowercase if scalar(lowercase).is_some() => scalar(lowercase)?.to_owned(),
+            "true" | "false" | "null" => name.to_ascii_lowercase(),
+            "array-key" => "string | number".to_owned(),
+            "scalar" => "string | number | boolean".to_owned(),
+            "object" => "object".to_owned(),
+            "mixed" | "callable" | "resource" | "closure" => "unknown".to_owned(),
+            "array" | "list" | "iterable" | "non-empty-array" | "non-empty-list" => {
+                "unknown[]".to_owned()
+            }
+            "self" | "static" | "$this" => class.to_owned(),
+            "void" | "never" => return None,
+            _ => short_name(name),
+        },
+        DocblockType::Generic { name, arguments } => match name.to_ascii_lowercase().as_str() {
+            "array" | "list" | "iterable" | "non-empty-array" | "non-empty-list" => {
+                match arguments.as_slice() {
+                    [value] => array(value, class)?,
+                    [key, value] => {
+                        format!(
+                            "Record<{}, {}>",
+                            from_docblock(key, class)?,
+                            from_docblock(value, class)?
+                        )
+                    }
+                    _ => return None,
+                }
+            }
+            // int ranges and masks, and class strings such as `class-string<Foo>`.
+            lowercase if scalar(lowercase).is_some() => scalar(lowercase)?.to_owned(),
+            // generic classes are emitted as plain interfaces.
+            _ => short_name(name),
+        },
+        DocblockType::Array(inner) => array(inner, class)?,
+        DocblockType::ArrayShape { kind, entries, .. } => {
+            if *kind == ArrayShapeKind::List || entries.iter().all(|entry| entry.key.is_none()) {
+                let items = entries
+                    .iter()
+                    .map(|entry| from_docblock(&entry.value, class))
+                    .collect::<Option<Vec<String>>>()?;
+
+                format!("[{}]", items.join(", "))
+            } else {
+                let mut position = 0;
+                let items = entries
+                    .iter()
+                    .map(|entry| {
+                        let key = match &entry.key {
+                            Some(ArrayShapeKey::String(key))
+                                if key.chars().all(|c| c == '_' || c.is_alphanumeric())
+                                    && !key.starts_with(|c: char| c.is_ascii_digit()) =>
+                            {
+                                key.clone()
+                            }
+                            Some(ArrayShapeKey::String(key)) => string(key),
+                            Some(ArrayShapeKey::Integer(key)) => key.to_string(),
+                            None => {
+                                position += 1;
+                                (position - 1).to_string()
+                            }
+                        };
+                        let optional = if entry.optional { "?" } else { "" };
+
+                        Some(format!(
+                            "{}{}: {}",
+                            key,
+                            optional,
+                            from_docblock(&entry.value, class)?
+                        ))
+                    })
+                    .collect::<Option<Vec<String>>>()?;
+
+                format!("{{ {} }}", items.join("; "))
+            }
+        }
+        DocblockType::ClassString(_) => "string".to_owned(),
+        DocblockType::KeyOf(_) => "string | number".to_owned(),
+        DocblockType::ValueOf(_) | DocblockType::ClassConstant { .. } => "unknown".to_owned(),
+        DocblockType::String(value) => string(value),
+        DocblockType::Integer(value) => value.to_string(),
+        DocblockType::Float(value) => value.to_string(),
+        DocblockType::Nullable(inner) => format!("{} | null", from_docblock(inner, class)?),
+        DocblockType::Union(types) => {
+            let mut union: Vec<String> = vec![];
+            for r#type in types {
+                // different PHP types, such as `int` and `float`, may map to the same type.
+                let r#type = from_docblock(r#type, class)?;
+                if !union.contains(&r#type) {
+                    union.push(r#type);
+                }
+            }
+
+            union.join(" | ")
+        }
+        DocblockType::Intersection(types) => types
+            .iter()
+            .map(|r#type| {
+                from_docblock(r#type, class).map(|r#type| match r#type.contains(" | ") {
+                    true => format!("({})", r#type),
+                    false => r#type,
+                })
+            })
+            .collect::<Option<Vec<String>>>()?
+            .join(" & "),
+    };
+
+    Some(r#type)
+}
+
+fn array(value: &DocblockType, class: &str) -> Option<String> {
+    let r#type = from_docblock(value, class)?;
+    let array = match value {
+        DocblockType::Union(_) | DocblockType::Intersection(_) | DocblockType::Nullable(_) => {
+            format!("({})[]", r#type)
+        }
+        DocblockType::Named(_) if r#type.contains(' ') => format!("({})[]", r#type),
+        _ => format!("{}[]", r#type),
+    };
+
+    Some(array)
+}
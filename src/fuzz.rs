@@ -0,0 +1,84 @@
+//! Entry points for fuzzing the lexer and parser, enabled by the `fuzz` feature.
+//!
+//! None of them may panic or overflow the stack, whatever the input: invalid code must
+//! be reported as an error. A fuzzer can call them directly, for example from a
+//! `cargo fuzz` target:
+//!
+//! ```rust,ignore
+//! libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+//!     php_parser_rs::fuzz::exercise(data);
+//! });
+//! ```
+
+use crate::lexer::error::SyntaxResult;
+use crate::lexer::token::Token;
+use crate::lexer::Lexer;
+use crate::lexer::LineState;
+use crate::parser;
+use crate::parser::ast::Program;
+use crate::parser::error::ParseErrorStack;
+use crate::parser::ParserConfig;
+
+/// The deepest nesting accepted by the entry points.
+///
+/// This is lower than the parser's default: deeper nesting exercises no code that
+/// shallower nesting doesn't, and only slows every run down. Chains of operators, calls,
+/// and fetches don't count as nesting, and are accepted at any length.
+pub const MAX_DEPTH: usize = 256;
+
+fn config() -> ParserConfig {
+    ParserConfig {
+        max_depth: Some(MAX_DEPTH),
+        ..ParserConfig::default()
+    }
+}
+
+/// Parse arbitrary bytes, which need not be valid UTF-8, let alone valid PHP.
+pub fn parse_unchecked(input: &[u8]) -> Result<Program, ParseErrorStack> {
+    parser::parse_with_config(input, config())
+}
+
+/// Run the input through every way of lexing and parsing it, checking that they agree.
+///
+/// Panics only if one of the entry points does, or if the results are inconsistent: the
+/// tokens read line by line differ from those of the whole input, or the lossless AST
+/// does not print back to the input.
+pub fn exercise(input: &[u8]) {
+    let lexer = Lexer::new();
+    let tokens = lexer.tokenize(input);
+
+    assert_eq!(
+        tokenize_lines(&lexer, input),
+        tokens,
+        "tokenizing line by line changed the tokens"
+    );
+
+    let _ = parse_unchecked(input);
+    let _ = parser::parse_with_recovery_and_config(input, config());
+
+    if let Ok(program) = parser::parse_lossless_with_config(input, config()) {
+        assert_eq!(
+            program.print(),
+            input,
+            "the lossless AST did not round-trip"
+        );
+    }
+}
+
+fn tokenize_lines(lexer: &Lexer, input: &[u8]) -> SyntaxResult<Vec<Token>> {
+    // the last line has no line ending, even if it is empty.
+    let last: &[u8] = b"";
+    let lines = input
+        .split_inclusive(|b| *b == b'\n')
+        .chain(std::iter::once(last).filter(|_| input.is_empty() || input.ends_with(b"\n")));
+
+    let mut state = LineState::new();
+    let mut tokens = Vec::new();
+    for line in lines {
+        let (line_tokens, next) = lexer.tokenize_line(&state, line)?;
+        tokens.extend(line_tokens);
+        state = next;
+    }
+
+    Ok(tokens)
+}
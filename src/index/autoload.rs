@@ -0,0 +1,449 @@
+//! Resolves class names to the files declaring them, following the autoload
+//! configuration of a Composer project, as Composer's own class loader does.
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! use php_parser_rs::index::autoload::Autoloader;
+//! use php_parser_rs::index::Index;
+//!
+//! let autoloader = Autoloader::from_project(Path::new("/path/to/project")).unwrap();
+//! let file = autoloader.find_file(b"App\\Http\\Controller");
+//!
+//! let mut index = Index::new();
+//! index.autoload(&autoloader, b"App\\Http\\Controller");
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::index::walk_class_likes;
+use crate::lexer::byte_string::ByteString;
+use crate::parser;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::Expression;
+use crate::parser::ast::ReturnStatement;
+use crate::parser::ast::Statement;
+use crate::parser::ast::UseKind;
+
+// Prefixes with the directories they map to.
+type Prefixes = Vec<(Vec<u8>, Vec<PathBuf>)>;
+
+/// Maps class names to files, using a class map and PSR-4 and PSR-0 prefixes.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Autoloader {
+    classes: HashMap<Vec<u8>, PathBuf>,
+    // The prefixes and their directories, with the longest prefixes first. The empty
+    // prefix holds the fallback directories.
+    psr4: Prefixes,
+    psr0: Prefixes,
+}
+
+#[derive(Debug)]
+pub enum AutoloadError {
+    Io(PathBuf, std::io::Error),
+    Json(PathBuf, serde_json::Error),
+    // A generated `vendor/composer/autoload_*.php` file that is not a map of paths.
+    InvalidMap(PathBuf),
+}
+
+impl Display for AutoloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            AutoloadError::Io(path, error) => {
+                write!(f, "failed to read {}: {}", path.display(), error)
+            }
+            AutoloadError::Json(path, error) => write!(f, "invalid {}: {}", path.display(), error),
+            AutoloadError::InvalidMap(path) => {
+                write!(f, "invalid autoload map {}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for AutoloadError {}
+
+impl Autoloader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the autoload configuration of the project in the given directory.
+    ///
+    /// When `composer install` or `composer dump-autoload` generated the maps in
+    /// `vendor/composer`, they are used, covering the project and its dependencies.
+    /// Otherwise, only the `autoload` and `autoload-dev` sections of `composer.json` are.
+    pub fn from_project(root: &Path) -> Result<Self, AutoloadError> {
+        let path = root.join("composer.json");
+        let composer = read_json(&path)?;
+
+        let vendor = composer["config"]["vendor-dir"]
+            .as_str()
+            .unwrap_or("vendor");
+        let generated = root.join(vendor).join("composer");
+
+        if generated.join("autoload_psr4.php").is_file() {
+            return Self::from_generated(&generated, root);
+        }
+
+        let mut autoloader = Self::new();
+        autoloader.add_composer_json(root, &composer["autoload"]);
+        autoloader.add_composer_json(root, &composer["autoload-dev"]);
+
+        Ok(autoloader)
+    }
+
+    /// Load the maps Composer generates in `vendor/composer`, where `$vendorDir` is its
+    /// parent directory and `$baseDir` the root of the project.
+    pub fn from_generated(directory: &Path, root: &Path) -> Result<Self, AutoloadError> {
+        let vendor = directory.parent().unwrap_or(directory);
+        let mut autoloader = Self::new();
+
+        for (name, psr4) in [
+            ("autoload_psr4.php", true),
+            ("autoload_namespaces.php", false),
+        ] {
+            let path = directory.join(name);
+            if !path.is_file() {
+                continue;
+            }
+
+            for (prefix, directories) in read_map(&path, vendor, root)? {
+                for directory in directories {
+                    match psr4 {
+                        true => autoloader.add_psr4(&prefix, directory),
+                        false => autoloader.add_psr0(&prefix, directory),
+                    }
+                }
+            }
+        }
+
+        let path = directory.join("autoload_classmap.php");
+        if path.is_file() {
+            for (class, files) in read_map(&path, vendor, root)? {
+                for file in files {
+                    autoloader.add_class(&class, file);
+                }
+            }
+        }
+
+        Ok(autoloader)
+    }
+
+    /// Add the `psr-4`, `psr-0` and `classmap` entries of an `autoload` section of a
+    /// `composer.json` file, with paths relative to the given directory.
+    ///
+    /// The directories of the class map are scanned for the class-likes they declare.
+    pub fn add_composer_json(&mut self, root: &Path, autoload: &serde_json::Value) {
+        let directories = |value: &serde_json::Value| -> Vec<PathBuf> {
+            match value {
+                serde_json::Value::String(path) => vec![root.join(path)],
+                serde_json::Value::Array(paths) => paths
+                    .iter()
+                    .filter_map(|path| path.as_str())
+                    .map(|path| root.join(path))
+                    .collect(),
+                _ => vec![],
+            }
+        };
+
+        for (key, psr4) in [("psr-4", true), ("psr-0", false)] {
+            if let Some(prefixes) = autoload[key].as_object() {
+                for (prefix, value) in prefixes {
+                    for directory in directories(value) {
+                        match psr4 {
+                            true => self.add_psr4(prefix.as_bytes(), directory),
+                            false => self.add_psr0(prefix.as_bytes(), directory),
+                        }
+                    }
+                }
+            }
+        }
+
+        for path in directories(&autoload["classmap"]) {
+            self.scan(&path);
+        }
+    }
+
+    /// Map classes starting with the given namespace prefix, such as `App\`, to the
+    /// directory, where `App\Http\Controller` is found in `Http/Controller.php`.
+    ///
+    /// Classes matching no prefix are looked up in the directories of the empty prefix.
+    pub fn add_psr4(&mut self, prefix: &[u8], directory: impl Into<PathBuf>) {
+        add_prefix(&mut self.psr4, prefix, directory.into());
+    }
+
+    /// Map classes starting with the given prefix to the directory, where both
+    /// `Vendor\Package\Foo` and `Vendor_Package_Foo` are found in `Vendor/Package/Foo.php`.
+    pub fn add_psr0(&mut self, prefix: &[u8], directory: impl Into<PathBuf>) {
+        add_prefix(&mut self.psr0, prefix, directory.into());
+    }
+
+    /// Map a class to the file declaring it, taking precedence over any prefix.
+    pub fn add_class(&mut self, class: &[u8], file: impl Into<PathBuf>) {
+        let class = class.strip_prefix(b"\\").unwrap_or(class);
+
+        self.classes.insert(class.to_vec(), file.into());
+    }
+
+    /// The files the class may be declared in, in the order they are tried.
+    pub fn candidates(&self, class: &[u8]) -> Vec<PathBuf> {
+        let class = class.strip_prefix(b"\\").unwrap_or(class);
+        let mut candidates = vec![];
+
+        if let Some(file) = self.classes.get(class) {
+            candidates.push(file.clone());
+        }
+
+        for (prefix, directories) in &self.psr4 {
+            if let Some(rest) = class.strip_prefix(&prefix[..]) {
+                let path = logical_path(rest, false);
+                candidates.extend(directories.iter().map(|directory| directory.join(&path)));
+            }
+        }
+
+        let path = logical_path(class, true);
+        for (prefix, directories) in &self.psr0 {
+            if class.starts_with(prefix) {
+                candidates.extend(directories.iter().map(|directory| directory.join(&path)));
+            }
+        }
+
+        candidates
+    }
+
+    /// The file declaring the class: the first of its [`candidates`](Self::candidates)
+    /// that exists.
+    pub fn find_file(&self, class: &[u8]) -> Option<PathBuf> {
+        self.candidates(class)
+            .into_iter()
+            .find(|candidate| candidate.is_file())
+    }
+
+    /// The classes imported by the `use` statements of the program, with the files
+    /// declaring them.
+    pub fn resolve_imports(&self, program: &[Statement]) -> Vec<(ByteString, Option<PathBuf>)> {
+        let mut imports = vec![];
+        collect_imports(program, &mut |name| {
+            let file = self.find_file(&name);
+
+            imports.push((name, file));
+        });
+
+        imports
+    }
+
+    // Add the class-likes declared in the file, or in the `.php` and `.inc` files of the
+    // directory, to the class map.
+    fn scan(&mut self, path: &Path) {
+        if let Ok(entries) = std::fs::read_dir(path) {
+            let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+            paths.sort();
+
+            for path in paths {
+                let is_php = matches!(
+                    path.extension().and_then(|extension| extension.to_str()),
+                    Some("php" | "inc")
+                );
+
+                if path.is_dir() || is_php {
+                    self.scan(&path);
+                }
+            }
+
+            return;
+        }
+
+        let Ok(source) = std::fs::read(path) else {
+            return;
+        };
+
+        let (program, _) = parser::parse_with_recovery(&source);
+        walk_class_likes(&program, &mut |context, view| {
+            let name = context.declare(&view.name.value);
+
+            self.classes.insert(name.to_vec(), path.to_path_buf());
+        });
+    }
+}
+
+fn add_prefix(prefixes: &mut Prefixes, prefix: &[u8], directory: PathBuf) {
+    match prefixes.iter_mut().find(|(existing, _)| existing == prefix) {
+        Some((_, directories)) => directories.push(directory),
+        None => {
+            prefixes.push((prefix.to_vec(), vec![directory]));
+            prefixes.sort_by_key(|(prefix, _)| Reverse(prefix.len()));
+        }
+    }
+}
+
+// The path of a class relative to the directory of its prefix, with `_` in the class
+// name, but not the namespace, separating directories for PSR-0.
+fn logical_path(class: &[u8], psr0: bool) -> PathBuf {
+    let (namespace, name) = match class.iter().rposition(|byte| *byte == b'\\') {
+        Some(separator) => (&class[..=separator], &class[separator + 1..]),
+        None => (&class[..0], class),
+    };
+
+    let mut path: Vec<u8> = namespace
+        .iter()
+        .map(|byte| if *byte == b'\\' { b'/' } else { *byte })
+        .collect();
+    path.extend(name.iter().map(|byte| match byte {
+        b'_' if psr0 => b'/',
+        byte => *byte,
+    }));
+    path.extend_from_slice(b".php");
+
+    PathBuf::from(String::from_utf8_lossy(&path).into_owned())
+}
+
+fn read_json(path: &Path) -> Result<serde_json::Value, AutoloadError> {
+    let source =
+        std::fs::read_to_string(path).map_err(|error| AutoloadError::Io(path.into(), error))?;
+
+    serde_json::from_str(&source).map_err(|error| AutoloadError::Json(path.into(), error))
+}
+
+// Read a map generated by Composer, of prefixes or classes to paths, such as:
+//
+// ```php
+// $vendorDir = dirname(__DIR__);
+// $baseDir = dirname($vendorDir);
+//
+// return array(
+//     'App\\' => array($baseDir . '/src'),
+// );
+// ```
+fn read_map(path: &Path, vendor: &Path, root: &Path) -> Result<Prefixes, AutoloadError> {
+    let source = std::fs::read(path).map_err(|error| AutoloadError::Io(path.into(), error))?;
+    let invalid = || AutoloadError::InvalidMap(path.into());
+
+    let program = parser::parse(&source).map_err(|_| invalid())?;
+    let items = program
+        .iter()
+        .find_map(|statement| match statement {
+            Statement::Return(ReturnStatement {
+                value: Some(Expression::Array(array)),
+                ..
+            }) => Some(&array.items),
+            Statement::Return(ReturnStatement {
+                value: Some(Expression::ShortArray(array)),
+                ..
+            }) => Some(&array.items),
+            _ => None,
+        })
+        .ok_or_else(invalid)?;
+
+    let resolve = |expression: &Expression| -> Option<PathBuf> {
+        match expression {
+            Expression::Literal(Literal::String(path)) => Some(PathBuf::from(
+                String::from_utf8_lossy(&path.value).into_owned(),
+            )),
+            Expression::Concat(concat) => {
+                let directory = match concat.left.as_ref() {
+                    Expression::Variable(Variable::SimpleVariable(variable))
+                        if variable.name == b"$vendorDir" =>
+                    {
+                        vendor
+                    }
+                    Expression::Variable(Variable::SimpleVariable(variable))
+                        if variable.name == b"$baseDir" =>
+                    {
+                        root
+                    }
+                    _ => return None,
+                };
+
+                let Expression::Literal(Literal::String(path)) = concat.right.as_ref() else {
+                    return None;
+                };
+
+                let path = String::from_utf8_lossy(&path.value).into_owned();
+
+                Some(directory.join(path.trim_start_matches('/')))
+            }
+            _ => None,
+        }
+    };
+
+    let mut map = vec![];
+    for item in items.iter() {
+        let ArrayItem::KeyValue {
+            key: Expression::Literal(Literal::String(key)),
+            value,
+            ..
+        } = item
+        else {
+            return Err(invalid());
+        };
+
+        let paths = match value {
+            Expression::Array(array) => array.items.iter().collect::<Vec<_>>(),
+            Expression::ShortArray(array) => array.items.iter().collect(),
+            value => {
+                map.push((
+                    key.value.to_vec(),
+                    vec![resolve(value).ok_or_else(invalid)?],
+                ));
+
+                continue;
+            }
+        };
+
+        let paths = paths
+            .into_iter()
+            .map(|item| match item {
+                ArrayItem::Value { value } => resolve(value),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+
+        map.push((key.value.to_vec(), paths));
+    }
+
+    Ok(map)
+}
+
+fn collect_imports(statements: &[Statement], callback: &mut impl FnMut(ByteString)) {
+    for statement in statements {
+        match statement {
+            Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                collect_imports(&namespace.statements, callback)
+            }
+            Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                collect_imports(&namespace.body.statements, callback)
+            }
+            Statement::Use(statement) => {
+                for r#use in &statement.uses {
+                    if let UseKind::Normal = r#use.kind.as_ref().unwrap_or(&statement.kind) {
+                        let name = &r#use.name.value;
+
+                        callback(name.strip_prefix(b"\\").unwrap_or(name).into());
+                    }
+                }
+            }
+            Statement::GroupUse(statement) => {
+                let prefix = &statement.prefix.value;
+                let prefix = prefix.strip_prefix(b"\\").unwrap_or(prefix);
+
+                for r#use in &statement.uses {
+                    if let UseKind::Normal = r#use.kind.as_ref().unwrap_or(&statement.kind) {
+                        let mut name = prefix.to_vec();
+                        name.extend(r#use.name.value.iter());
+
+                        callback(name.into());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,555 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+
+use crate::analysis::views::ClassLikeKind;
+use crate::analysis::views::ClassLikeView;
+use crate::analysis::views::MethodView;
+use crate::analysis::views::ParameterView;
+use crate::analysis::views::PropertyView;
+use crate::docblock::docblock;
+use crate::evaluator::const_expr;
+use crate::evaluator::PhpConstValue;
+use crate::index::autoload::Autoloader;
+use crate::index::names::NameContext;
+use crate::lexer::byte_string::ByteString;
+use crate::parser;
+use crate::parser::ast::arguments::Argument;
+use crate::parser::ast::constant::ConstantStatement;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::modifiers::Visibility;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::Expression;
+use crate::parser::ast::FunctionCallExpression;
+use crate::parser::ast::Statement;
+
+pub mod autoload;
+pub mod names;
+
+/// An index of the class-like, function and global constant declarations found across
+/// one or more programs.
+///
+/// It serializes to an object listing the `classes`, `functions` and `constants`, each
+/// sorted by name.
+///
+/// ```
+/// use php_parser_rs::index::Index;
+/// use php_parser_rs::parser;
+///
+/// let program = parser::parse("<?php namespace App; const VERSION = '1.0'; class Foo {}").unwrap();
+/// let index = Index::from_program(&program);
+///
+/// let json = serde_json::to_value(&index).unwrap();
+/// assert_eq!(json["classes"][0]["name"], "App\\Foo");
+/// assert_eq!(json["constants"][0]["name"], "App\\VERSION");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Index {
+    classes: HashMap<Vec<u8>, ClassLikeEntry>,
+    functions: HashMap<Vec<u8>, FunctionEntry>,
+    constants: HashMap<Vec<u8>, GlobalConstantEntry>,
+}
+
+/// A declaration found while walking a program.
+#[derive(Debug, Clone)]
+pub enum Declaration<'a> {
+    ClassLike(ClassLikeView<'a>),
+    Function(&'a FunctionStatement),
+    // `const FOO = 1, BAR = 2;`
+    Constant(&'a ConstantStatement),
+    // `define('FOO', 1);`, with a string literal as the name.
+    Define(&'a FunctionCallExpression),
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct ClassLikeEntry {
+    pub kind: ClassLikeKind,
+    // The fully qualified name, without a leading backslash.
+    pub name: ByteString,
+    pub parent: Option<ByteString>,
+    pub interfaces: Vec<ByteString>,
+    pub traits: Vec<ByteString>,
+    pub is_final: bool,
+    pub constants: Vec<ConstantEntry>,
+    pub properties: Vec<PropertyEntry>,
+    pub methods: Vec<MethodEntry>,
+    // The `/** ... */` comment preceding the declaration.
+    pub docblock: Option<ByteString>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct ConstantEntry {
+    pub name: ByteString,
+    pub visibility: Visibility,
+    // The value, if it is a constant expression that does not reference other constants.
+    pub value: Option<PhpConstValue>,
+    // The `/** ... */` comment preceding the declaration.
+    pub docblock: Option<ByteString>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct PropertyEntry {
+    // The name, without the leading `$`.
+    pub name: ByteString,
+    pub visibility: Visibility,
+    pub is_static: bool,
+    pub data_type: Option<Type>,
+    // The `/** ... */` comment preceding the declaration.
+    pub docblock: Option<ByteString>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct MethodEntry {
+    pub name: ByteString,
+    pub visibility: Visibility,
+    pub is_static: bool,
+    pub is_abstract: bool,
+    pub parameters: Vec<ParameterEntry>,
+    pub return_type: Option<Type>,
+    // The `/** ... */` comment preceding the declaration.
+    pub docblock: Option<ByteString>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct FunctionEntry {
+    // The fully qualified name, without a leading backslash.
+    pub name: ByteString,
+    pub parameters: Vec<ParameterEntry>,
+    pub return_type: Option<Type>,
+    // The `/** ... */` comment preceding the declaration.
+    pub docblock: Option<ByteString>,
+}
+
+/// A constant declared outside of a class-like, with `const` or `define()`.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct GlobalConstantEntry {
+    // The fully qualified name, without a leading backslash.
+    pub name: ByteString,
+    // The value, if it is a constant expression that does not reference other constants.
+    pub value: Option<PhpConstValue>,
+    // The `/** ... */` comment preceding a `const` declaration.
+    pub docblock: Option<ByteString>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct ParameterEntry {
+    // The name, without the leading `$`.
+    pub name: ByteString,
+    pub data_type: Option<Type>,
+    pub is_variadic: bool,
+    pub is_by_reference: bool,
+    // Whether the parameter has a default value, or is variadic.
+    pub is_optional: bool,
+    // Whether the default value is `null`, which makes the parameter implicitly nullable.
+    pub has_null_default: bool,
+}
+
+impl Index {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build an index from a single program.
+    pub fn from_program(program: &[Statement]) -> Self {
+        let mut index = Self::new();
+        index.add_program(program);
+        index
+    }
+
+    /// Add all class-like, function and global constant declarations of the given program
+    /// to the index.
+    pub fn add_program(&mut self, program: &[Statement]) {
+        walk_declarations(program, &mut |context, declaration| match declaration {
+            Declaration::ClassLike(view) => {
+                let entry = ClassLikeEntry::new(context, &view);
+
+                self.classes.insert(entry.name.to_ascii_lowercase(), entry);
+            }
+            Declaration::Function(function) => {
+                let entry = FunctionEntry::new(context, function);
+
+                self.functions
+                    .insert(entry.name.to_ascii_lowercase(), entry);
+            }
+            Declaration::Constant(constant) => {
+                for entry in &constant.entries {
+                    let entry = GlobalConstantEntry {
+                        name: context.declare(&entry.name.value),
+                        value: const_expr(&entry.value),
+                        docblock: docblock(&constant.comments).cloned(),
+                    };
+
+                    self.constants.insert(constant_key(&entry.name), entry);
+                }
+            }
+            Declaration::Define(call) => {
+                if let Some(entry) = GlobalConstantEntry::define(call) {
+                    self.constants.insert(constant_key(&entry.name), entry);
+                }
+            }
+        });
+    }
+
+    /// Add the declarations of the file the autoloader maps the class to, unless the class
+    /// is already in the index, returning the file that was added.
+    ///
+    /// The file is parsed with error recovery, so that the declarations of a file with
+    /// syntax errors are still added.
+    pub fn autoload(&mut self, autoloader: &Autoloader, class: &[u8]) -> Option<PathBuf> {
+        if self.class(class).is_some() {
+            return None;
+        }
+
+        let file = autoloader.find_file(class)?;
+        let source = std::fs::read(&file).ok()?;
+
+        let (program, _) = parser::parse_with_recovery(&source);
+        self.add_program(&program);
+
+        Some(file)
+    }
+
+    pub fn classes(&self) -> impl Iterator<Item = &ClassLikeEntry> {
+        self.classes.values()
+    }
+
+    /// Find a class-like by its fully qualified name, using PHP's case-insensitive comparison.
+    pub fn class(&self, name: &[u8]) -> Option<&ClassLikeEntry> {
+        let name = name.strip_prefix(b"\\").unwrap_or(name);
+
+        self.classes.get(&name.to_ascii_lowercase())
+    }
+
+    pub fn functions(&self) -> impl Iterator<Item = &FunctionEntry> {
+        self.functions.values()
+    }
+
+    /// Find a function by its fully qualified name, using PHP's case-insensitive comparison.
+    pub fn function(&self, name: &[u8]) -> Option<&FunctionEntry> {
+        let name = name.strip_prefix(b"\\").unwrap_or(name);
+
+        self.functions.get(&name.to_ascii_lowercase())
+    }
+
+    pub fn constants(&self) -> impl Iterator<Item = &GlobalConstantEntry> {
+        self.constants.values()
+    }
+
+    /// Find a global constant by its fully qualified name. The namespace is compared
+    /// case-insensitively, while the name of the constant itself is case-sensitive.
+    pub fn constant(&self, name: &[u8]) -> Option<&GlobalConstantEntry> {
+        let name = name.strip_prefix(b"\\").unwrap_or(name);
+
+        self.constants.get(&constant_key(name))
+    }
+
+    /// Find a method declared by the given class-like, or inherited from one of its ancestors.
+    ///
+    /// Own methods take precedence over those of used traits, which take precedence over
+    /// those of the parent class and interfaces.
+    pub fn find_method(&self, class: &[u8], name: &[u8]) -> Option<&MethodEntry> {
+        let mut seen: Vec<&ByteString> = vec![];
+        let mut queue = VecDeque::from([self.class(class)?]);
+
+        while let Some(entry) = queue.pop_front() {
+            if seen
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(&entry.name))
+            {
+                continue;
+            }
+
+            if let Some(method) = entry.method(name) {
+                return Some(method);
+            }
+
+            seen.push(&entry.name);
+            for ancestor in entry
+                .traits
+                .iter()
+                .chain(entry.parent.iter())
+                .chain(entry.interfaces.iter())
+            {
+                queue.push_back(self.class(ancestor)?);
+            }
+        }
+
+        None
+    }
+
+    /// Collect all known ancestors of the given class-like: parent classes, interfaces and traits.
+    ///
+    /// Returns `None` if any ancestor is not present in the index, in which case the
+    /// inheritance chain can not be fully resolved.
+    pub fn ancestors(&self, entry: &ClassLikeEntry) -> Option<Vec<&ClassLikeEntry>> {
+        let mut ancestors: Vec<&ClassLikeEntry> = vec![];
+        let mut queue: Vec<&ByteString> = vec![];
+        queue.extend(entry.parent.iter());
+        queue.extend(entry.interfaces.iter());
+        queue.extend(entry.traits.iter());
+
+        while let Some(name) = queue.pop() {
+            let ancestor = self.class(name)?;
+            if ancestors
+                .iter()
+                .any(|existing| existing.name.eq_ignore_ascii_case(&ancestor.name))
+            {
+                continue;
+            }
+
+            queue.extend(ancestor.parent.iter());
+            queue.extend(ancestor.interfaces.iter());
+            queue.extend(ancestor.traits.iter());
+            ancestors.push(ancestor);
+        }
+
+        Some(ancestors)
+    }
+}
+
+impl Serialize for Index {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        fn sorted<T>(entries: &HashMap<Vec<u8>, T>) -> Vec<&T> {
+            let mut keys: Vec<&Vec<u8>> = entries.keys().collect();
+            keys.sort();
+
+            keys.into_iter().map(|key| &entries[key]).collect()
+        }
+
+        let mut index = serializer.serialize_struct("Index", 3)?;
+        index.serialize_field("classes", &sorted(&self.classes))?;
+        index.serialize_field("functions", &sorted(&self.functions))?;
+        index.serialize_field("constants", &sorted(&self.constants))?;
+        index.end()
+    }
+}
+
+// Namespaces are case-insensitive, but constant names are not.
+fn constant_key(name: &[u8]) -> Vec<u8> {
+    match name.iter().rposition(|byte| *byte == b'\\') {
+        Some(separator) => {
+            let mut key = name[..separator].to_ascii_lowercase();
+            key.extend_from_slice(&name[separator..]);
+            key
+        }
+        None => name.to_vec(),
+    }
+}
+
+/// Walk all class-like declarations of the given statements, tracking the namespace
+/// and imports they are declared with.
+pub fn walk_class_likes<'a>(
+    statements: &'a [Statement],
+    callback: &mut impl FnMut(&NameContext, ClassLikeView<'a>),
+) {
+    walk_declarations(statements, &mut |context, declaration| {
+        if let Declaration::ClassLike(view) = declaration {
+            callback(context, view);
+        }
+    })
+}
+
+/// Walk all top-level class-like, function and constant declarations of the given
+/// statements, tracking the namespace and imports they are declared with.
+pub fn walk_declarations<'a>(
+    statements: &'a [Statement],
+    callback: &mut impl FnMut(&NameContext, Declaration<'a>),
+) {
+    walk_statements(&mut NameContext::default(), statements, callback)
+}
+
+fn walk_statements<'a>(
+    context: &mut NameContext,
+    statements: &'a [Statement],
+    callback: &mut impl FnMut(&NameContext, Declaration<'a>),
+) {
+    for statement in statements {
+        match statement {
+            Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                context.enter_namespace(Some(&namespace.name.value));
+                walk_statements(context, &namespace.statements, callback);
+            }
+            Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                context.enter_namespace(namespace.name.as_ref().map(|name| &name.value));
+                walk_statements(context, &namespace.body.statements, callback);
+                context.enter_namespace(None);
+            }
+            Statement::Use(r#use) => context.add_use(r#use),
+            Statement::GroupUse(r#use) => context.add_group_use(r#use),
+            Statement::Function(function) => callback(context, Declaration::Function(function)),
+            Statement::Constant(constant) => callback(context, Declaration::Constant(constant)),
+            Statement::Expression(statement) => {
+                if let Expression::FunctionCall(call) = &statement.expression {
+                    if is_define(call) {
+                        callback(context, Declaration::Define(call));
+                    }
+                }
+            }
+            statement => {
+                if let Some(view) = ClassLikeView::from_statement(statement) {
+                    callback(context, Declaration::ClassLike(view));
+                }
+            }
+        }
+    }
+}
+
+impl ClassLikeEntry {
+    fn new(context: &NameContext, view: &ClassLikeView) -> Self {
+        Self {
+            kind: view.kind,
+            name: context.declare(&view.name.value),
+            parent: view
+                .parent
+                .map(|parent| context.resolve_class(&parent.value)),
+            interfaces: view
+                .interfaces
+                .iter()
+                .map(|interface| context.resolve_class(&interface.value))
+                .collect(),
+            traits: view
+                .traits
+                .iter()
+                .map(|r#trait| context.resolve_class(&r#trait.value))
+                .collect(),
+            is_final: view.is_final,
+            constants: view
+                .constants
+                .iter()
+                .flat_map(|constant| {
+                    constant.entries.iter().map(|entry| ConstantEntry {
+                        name: entry.name.value.clone(),
+                        visibility: constant.modifiers.visibility(),
+                        value: const_expr(&entry.value),
+                        docblock: docblock(&constant.comments).cloned(),
+                    })
+                })
+                .collect(),
+            properties: view.properties.iter().map(PropertyEntry::new).collect(),
+            methods: view.methods.iter().map(MethodEntry::new).collect(),
+            docblock: docblock(view.comments).cloned(),
+        }
+    }
+
+    /// Find a method by name, using PHP's case-insensitive comparison.
+    pub fn method(&self, name: &[u8]) -> Option<&MethodEntry> {
+        self.methods
+            .iter()
+            .find(|method| method.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Find a constant by its case-sensitive name.
+    pub fn constant(&self, name: &[u8]) -> Option<&ConstantEntry> {
+        self.constants
+            .iter()
+            .find(|constant| constant.name.as_slice() == name)
+    }
+
+    /// Find a property by its case-sensitive name, without the leading `$`.
+    pub fn property(&self, name: &[u8]) -> Option<&PropertyEntry> {
+        self.properties
+            .iter()
+            .find(|property| property.name.as_slice() == name)
+    }
+}
+
+impl PropertyEntry {
+    fn new(view: &PropertyView) -> Self {
+        let name = &view.name.name;
+
+        Self {
+            name: name.strip_prefix(b"$").unwrap_or(name).into(),
+            visibility: view.visibility.clone(),
+            is_static: view.is_static,
+            data_type: view.data_type.cloned(),
+            docblock: docblock(view.comments).cloned(),
+        }
+    }
+}
+
+impl MethodEntry {
+    fn new(view: &MethodView) -> Self {
+        Self {
+            name: view.name.value.clone(),
+            visibility: view.modifiers.visibility(),
+            is_static: view.modifiers.has_static(),
+            is_abstract: view.is_abstract,
+            parameters: view.parameters.iter().map(ParameterEntry::new).collect(),
+            return_type: view.return_type.cloned(),
+            docblock: docblock(view.comments).cloned(),
+        }
+    }
+}
+
+impl FunctionEntry {
+    fn new(context: &NameContext, function: &FunctionStatement) -> Self {
+        Self {
+            name: context.declare(&function.name.value),
+            parameters: ParameterView::from_list(&function.parameters)
+                .iter()
+                .map(ParameterEntry::new)
+                .collect(),
+            return_type: function
+                .return_type
+                .as_ref()
+                .map(|return_type| return_type.data_type.clone()),
+            docblock: docblock(&function.comments).cloned(),
+        }
+    }
+}
+
+impl GlobalConstantEntry {
+    fn define(call: &FunctionCallExpression) -> Option<Self> {
+        let mut arguments = call
+            .arguments
+            .arguments
+            .iter()
+            .map(|argument| match argument {
+                Argument::Positional(argument) => &argument.value,
+                Argument::Named(argument) => &argument.value,
+            });
+
+        let Some(Expression::Literal(Literal::String(name))) = arguments.next() else {
+            return None;
+        };
+
+        Some(Self {
+            name: name.value.strip_prefix(b"\\").unwrap_or(&name.value).into(),
+            value: arguments.next().and_then(const_expr),
+            docblock: None,
+        })
+    }
+}
+
+// `define()` or `\define()`.
+fn is_define(call: &FunctionCallExpression) -> bool {
+    match call.target.as_ref() {
+        Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => identifier
+            .value
+            .strip_prefix(b"\\")
+            .unwrap_or(&identifier.value)
+            .eq_ignore_ascii_case(b"define"),
+        _ => false,
+    }
+}
+
+impl ParameterEntry {
+    fn new(view: &ParameterView) -> Self {
+        let name = &view.name.name;
+
+        Self {
+            name: name.strip_prefix(b"$").unwrap_or(name).into(),
+            data_type: view.data_type.cloned(),
+            is_variadic: view.is_variadic,
+            is_by_reference: view.is_by_reference,
+            is_optional: view.default.is_some() || view.is_variadic,
+            has_null_default: matches!(view.default, Some(Expression::Null(_))),
+        }
+    }
+}
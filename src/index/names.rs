@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use crate::lexer::byte_string::ByteString;
+use crate::parser::ast::GroupUseStatement;
+use crate::parser::ast::UseKind;
+use crate::parser::ast::UseStatement;
+
+/// Tracks the current namespace and class imports while walking a file, so
+/// that class references can be turned into fully qualified names.
+#[derive(Debug, Clone, Default)]
+pub struct NameContext {
+    namespace: Option<ByteString>,
+    imports: HashMap<Vec<u8>, ByteString>,
+    function_imports: HashMap<Vec<u8>, ByteString>,
+    // Constant aliases are case-sensitive, unlike class and function aliases.
+    constant_imports: HashMap<Vec<u8>, ByteString>,
+}
+
+impl NameContext {
+    /// Enter a new namespace, forgetting all imports of the previous one.
+    pub fn enter_namespace(&mut self, namespace: Option<&ByteString>) {
+        self.namespace = namespace.map(strip_leading_backslash);
+        self.imports.clear();
+        self.function_imports.clear();
+        self.constant_imports.clear();
+    }
+
+    pub fn namespace(&self) -> Option<&ByteString> {
+        self.namespace.as_ref()
+    }
+
+    pub fn add_use(&mut self, statement: &UseStatement) {
+        for r#use in &statement.uses {
+            self.import(
+                r#use.kind.as_ref().unwrap_or(&statement.kind),
+                strip_leading_backslash(&r#use.name.value),
+                r#use.alias.as_ref().map(|alias| &alias.value),
+            );
+        }
+    }
+
+    pub fn add_group_use(&mut self, statement: &GroupUseStatement) {
+        // the prefix includes the trailing backslash.
+        let prefix = strip_leading_backslash(&statement.prefix.value);
+
+        for r#use in &statement.uses {
+            let mut name = prefix.clone();
+            name.extend(r#use.name.value.iter());
+
+            self.import(
+                r#use.kind.as_ref().unwrap_or(&statement.kind),
+                name,
+                r#use.alias.as_ref().map(|alias| &alias.value),
+            );
+        }
+    }
+
+    /// Qualify a name declared in the current namespace.
+    pub fn declare(&self, name: &ByteString) -> ByteString {
+        self.prefix(name)
+    }
+
+    /// Resolve a class name reference to its fully qualified form, without a leading backslash.
+    pub fn resolve_class(&self, name: &ByteString) -> ByteString {
+        if let Some(name) = name.strip_prefix(b"\\") {
+            return name.into();
+        }
+
+        if let Some(name) = strip_namespace_keyword(name) {
+            return self.prefix(&name);
+        }
+
+        let lowercase = name.to_ascii_lowercase();
+        if matches!(lowercase.as_slice(), b"self" | b"static" | b"parent") {
+            return name.clone();
+        }
+
+        let (first, rest) = match name.iter().position(|b| *b == b'\\') {
+            Some(position) => (&name[..position], Some(&name[position..])),
+            None => (&name[..], None),
+        };
+
+        if let Some(import) = self.imports.get(&first.to_ascii_lowercase()) {
+            let mut resolved = import.clone();
+            if let Some(rest) = rest {
+                resolved.extend(rest);
+            }
+
+            return resolved;
+        }
+
+        self.prefix(name)
+    }
+
+    /// Resolve a function name reference to the fully qualified names it may refer to, in
+    /// the order PHP tries them: unqualified names fall back to the global function.
+    pub fn resolve_function(&self, name: &ByteString) -> Vec<ByteString> {
+        if let Some(name) = name.strip_prefix(b"\\") {
+            return vec![name.into()];
+        }
+
+        if let Some(name) = strip_namespace_keyword(name) {
+            return vec![self.prefix(&name)];
+        }
+
+        if name.contains(&b'\\') {
+            return vec![self.resolve_class(name)];
+        }
+
+        if let Some(import) = self.function_imports.get(&name.to_ascii_lowercase()) {
+            return vec![import.clone()];
+        }
+
+        match &self.namespace {
+            Some(_) => vec![self.prefix(name), name.clone()],
+            None => vec![name.clone()],
+        }
+    }
+
+    /// Resolve a constant name reference to the fully qualified names it may refer to, in
+    /// the order PHP tries them: unqualified names fall back to the global constant.
+    pub fn resolve_constant(&self, name: &ByteString) -> Vec<ByteString> {
+        if let Some(name) = name.strip_prefix(b"\\") {
+            return vec![name.into()];
+        }
+
+        if let Some(name) = strip_namespace_keyword(name) {
+            return vec![self.prefix(&name)];
+        }
+
+        if name.contains(&b'\\') {
+            return vec![self.resolve_class(name)];
+        }
+
+        if let Some(import) = self.constant_imports.get(&name[..]) {
+            return vec![import.clone()];
+        }
+
+        match &self.namespace {
+            Some(_) => vec![self.prefix(name), name.clone()],
+            None => vec![name.clone()],
+        }
+    }
+
+    fn import(&mut self, kind: &UseKind, name: ByteString, alias: Option<&ByteString>) {
+        let alias = match alias {
+            Some(alias) => alias.to_vec(),
+            None => match name.iter().rposition(|b| *b == b'\\') {
+                Some(position) => name[position + 1..].to_vec(),
+                None => name.to_vec(),
+            },
+        };
+
+        match kind {
+            UseKind::Normal => self.imports.insert(alias.to_ascii_lowercase(), name),
+            UseKind::Function => self
+                .function_imports
+                .insert(alias.to_ascii_lowercase(), name),
+            UseKind::Const => self.constant_imports.insert(alias, name),
+        };
+    }
+
+    fn prefix(&self, name: &ByteString) -> ByteString {
+        match &self.namespace {
+            Some(namespace) => {
+                let mut qualified = namespace.clone();
+                qualified.extend(b"\\");
+                qualified.extend(name.iter());
+                qualified
+            }
+            None => name.clone(),
+        }
+    }
+}
+
+fn strip_leading_backslash(name: &ByteString) -> ByteString {
+    name.strip_prefix(b"\\").unwrap_or(name).into()
+}
+
+// `namespace\Foo` is relative to the current namespace.
+fn strip_namespace_keyword(name: &ByteString) -> Option<ByteString> {
+    match name.get(..10) {
+        Some(prefix) if prefix.eq_ignore_ascii_case(b"namespace\\") => Some(name[10..].into()),
+        _ => None,
+    }
+}
@@ -0,0 +1,2589 @@
+//! Conversion of the ast into the JSON dump format of [nikic/php-parser](https://github.com/nikic/PHP-Parser) (5.x).
+//!
+//! Every node becomes an object with a `nodeType` (e.g. `Stmt_Echo`, `Expr_BinaryOp_Plus`),
+//! its sub-nodes, and an `attributes` object holding `startLine`, `startFilePos`, `endLine`
+//! and `endFilePos`, where `endFilePos` is inclusive. Positions are derived from the spans
+//! recorded in the ast, so nodes whose leading keyword has no span start at their first
+//! child, and nodes without any span have their positions set to `-1`.
+//!
+//! Opening and closing tags, shebangs and comments are not part of nikic's tree and are
+//! skipped.
+
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::parser::ast::arguments::Argument;
+use crate::parser::ast::arguments::ArgumentList;
+use crate::parser::ast::arguments::ArgumentPlaceholder;
+use crate::parser::ast::arguments::SingleArgument;
+use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::classes::AnonymousClassExpression;
+use crate::parser::ast::classes::AnonymousClassMember;
+use crate::parser::ast::classes::ClassExtends;
+use crate::parser::ast::classes::ClassImplements;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::constant::ConstantEntry;
+use crate::parser::ast::control_flow::IfStatementBody;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::declares::DeclareBody;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::BackedEnumType;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::functions::AbstractConstructor;
+use crate::parser::ast::functions::AbstractMethod;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::FunctionParameter;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::functions::ReturnType;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::literals::IntegerValue;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::literals::LiteralInteger;
+use crate::parser::ast::literals::LiteralStringKind;
+use crate::parser::ast::loops::ForStatementBody;
+use crate::parser::ast::loops::ForeachStatementBody;
+use crate::parser::ast::loops::ForeachStatementIterator;
+use crate::parser::ast::loops::Level;
+use crate::parser::ast::loops::WhileStatementBody;
+use crate::parser::ast::modifiers::ClassModifier;
+use crate::parser::ast::modifiers::ConstantModifier;
+use crate::parser::ast::modifiers::MethodModifier;
+use crate::parser::ast::modifiers::MethodModifierGroup;
+use crate::parser::ast::modifiers::PromotedPropertyModifier;
+use crate::parser::ast::modifiers::PropertyModifier;
+use crate::parser::ast::modifiers::VisibilityModifier;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::operators::ArithmeticOperationExpression;
+use crate::parser::ast::operators::AssignmentOperationExpression;
+use crate::parser::ast::operators::BitwiseOperationExpression;
+use crate::parser::ast::operators::ComparisonOperationExpression;
+use crate::parser::ast::operators::LogicalOperationExpression;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::traits::TraitUsageAdaptation;
+use crate::parser::ast::try_block::CatchType;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::CastKind;
+use crate::parser::ast::Ending;
+use crate::parser::ast::Expression;
+use crate::parser::ast::ListEntry;
+use crate::parser::ast::MagicConstantExpression;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StringPart;
+use crate::parser::ast::SwitchStatementBody;
+use crate::parser::ast::Use;
+use crate::parser::ast::UseKind;
+
+const MODIFIER_PUBLIC: u64 = 1;
+const MODIFIER_PROTECTED: u64 = 2;
+const MODIFIER_PRIVATE: u64 = 4;
+const MODIFIER_STATIC: u64 = 8;
+const MODIFIER_ABSTRACT: u64 = 16;
+const MODIFIER_FINAL: u64 = 32;
+const MODIFIER_READONLY: u64 = 64;
+
+const STRING_KIND_SINGLE_QUOTED: u64 = 1;
+const STRING_KIND_DOUBLE_QUOTED: u64 = 2;
+const STRING_KIND_HEREDOC: u64 = 3;
+const STRING_KIND_NOWDOC: u64 = 4;
+
+const ARRAY_KIND_LONG: u64 = 1;
+const ARRAY_KIND_SHORT: u64 = 2;
+
+/// Convert a program into the array of statements nikic/php-parser's `json_encode($ast)` produces.
+///
+/// The source is needed to compute end lines and to recover the raw text of literals.
+pub fn to_json<B: ?Sized + AsRef<[u8]>>(source: &B, program: &Program) -> Value {
+    Converter::new(source.as_ref()).statements(program)
+}
+
+struct Converter<'a> {
+    source: &'a [u8],
+    // The byte offset at which every line starts.
+    lines: Vec<usize>,
+}
+
+impl<'a> Converter<'a> {
+    fn new(source: &'a [u8]) -> Self {
+        let mut lines = vec![0];
+        lines.extend(
+            source
+                .iter()
+                .enumerate()
+                .filter(|(_, byte)| **byte == b'\n')
+                .map(|(offset, _)| offset + 1),
+        );
+
+        Self { source, lines }
+    }
+
+    fn line(&self, offset: usize) -> usize {
+        self.lines.partition_point(|start| *start <= offset)
+    }
+
+    fn text(&self, span: Span) -> String {
+        self.source
+            .get(span.position..span.end_position)
+            .map(|text| String::from_utf8_lossy(text).into_owned())
+            .unwrap_or_default()
+    }
+
+    fn node(&self, node_type: &str, spans: &[Span], fields: Vec<(&str, Value)>) -> Value {
+        let mut start = spans.iter().map(|span| span.position).min();
+        let mut end = spans.iter().map(|span| span.end_position).max();
+        for (_, value) in &fields {
+            extent(value, &mut start, &mut end);
+        }
+
+        let attributes = match (start, end) {
+            (Some(start), Some(end)) => {
+                let last = end.max(start + 1) - 1;
+
+                serde_json::json!({
+                    "startLine": self.line(start),
+                    "startFilePos": start,
+                    "endLine": self.line(last),
+                    "endFilePos": last,
+                })
+            }
+            _ => serde_json::json!({
+                "startLine": -1,
+                "startFilePos": -1,
+                "endLine": -1,
+                "endFilePos": -1,
+            }),
+        };
+
+        let mut object = Map::new();
+        object.insert("nodeType".to_string(), node_type.into());
+        for (key, value) in fields {
+            object.insert(key.to_string(), value);
+        }
+        object.insert("attributes".to_string(), attributes);
+
+        Value::Object(object)
+    }
+
+    fn statements(&self, statements: &[Statement]) -> Value {
+        Value::Array(
+            statements
+                .iter()
+                .filter_map(|statement| self.statement(statement))
+                .collect(),
+        )
+    }
+
+    // The body of a loop or `if` that is not a block is wrapped in an array, as nikic does.
+    fn body(&self, statement: &Statement) -> Value {
+        match statement {
+            Statement::Block(block) => self.statements(&block.statements),
+            statement => Value::Array(self.statement(statement).into_iter().collect()),
+        }
+    }
+
+    fn statement(&self, statement: &Statement) -> Option<Value> {
+        let value = match statement {
+            Statement::Shebang(_)
+            | Statement::FullOpeningTag(_)
+            | Statement::ShortOpeningTag(_)
+            | Statement::EchoOpeningTag(_)
+            | Statement::ClosingTag(_)
+            | Statement::Comment(_)
+            | Statement::Missing(_) => return None,
+            Statement::InlineHtml(html) => self.node(
+                "Stmt_InlineHTML",
+                &[html.span],
+                vec![("value", string(&html.html))],
+            ),
+            Statement::Label(label) => self.node(
+                "Stmt_Label",
+                &[label.colon],
+                vec![("name", self.identifier(&label.label))],
+            ),
+            Statement::Goto(goto) => self.node(
+                "Stmt_Goto",
+                &[goto.keyword, goto.semicolon],
+                vec![("name", self.identifier(&goto.label))],
+            ),
+            Statement::HaltCompiler(halt) => self.node(
+                "Stmt_HaltCompiler",
+                &[halt.span],
+                vec![(
+                    "remaining",
+                    halt.content
+                        .as_ref()
+                        .map(string)
+                        .unwrap_or_else(|| "".into()),
+                )],
+            ),
+            Statement::Static(statement) => self.node(
+                "Stmt_Static",
+                &[statement.r#static, statement.semicolon],
+                vec![(
+                    "vars",
+                    statement
+                        .vars
+                        .iter()
+                        .map(|var| {
+                            self.node(
+                                "StaticVar",
+                                &[],
+                                vec![
+                                    ("var", self.variable(&var.var)),
+                                    ("default", self.optional_expression(var.default.as_ref())),
+                                ],
+                            )
+                        })
+                        .collect(),
+                )],
+            ),
+            Statement::DoWhile(statement) => self.node(
+                "Stmt_Do",
+                &[statement.r#do, statement.semicolon],
+                vec![
+                    ("stmts", self.body(&statement.body)),
+                    ("cond", self.expression(&statement.condition)),
+                ],
+            ),
+            Statement::While(statement) => {
+                let (stmts, end) = match &statement.body {
+                    WhileStatementBody::Statement { statement } => (self.body(statement), None),
+                    WhileStatementBody::Block {
+                        statements, ending, ..
+                    } => (self.statements(statements), Some(ending_span(ending))),
+                };
+
+                self.node(
+                    "Stmt_While",
+                    &spans(statement.r#while, end),
+                    vec![
+                        ("cond", self.expression(&statement.condition)),
+                        ("stmts", stmts),
+                    ],
+                )
+            }
+            Statement::For(statement) => {
+                let (stmts, end) = match &statement.body {
+                    ForStatementBody::Statement { statement } => (self.body(statement), None),
+                    ForStatementBody::Block {
+                        statements, ending, ..
+                    } => (self.statements(statements), Some(ending_span(ending))),
+                };
+                let iterator = &statement.iterator;
+
+                self.node(
+                    "Stmt_For",
+                    &spans(statement.r#for, end),
+                    vec![
+                        ("init", self.expressions(&iterator.initializations.inner)),
+                        ("cond", self.expressions(&iterator.conditions.inner)),
+                        ("loop", self.expressions(&iterator.r#loop.inner)),
+                        ("stmts", stmts),
+                    ],
+                )
+            }
+            Statement::Foreach(statement) => {
+                let (stmts, end) = match &statement.body {
+                    ForeachStatementBody::Statement { statement } => (self.body(statement), None),
+                    ForeachStatementBody::Block {
+                        statements, ending, ..
+                    } => (self.statements(statements), Some(ending_span(ending))),
+                };
+                let (expression, ampersand, key, value) = match &statement.iterator {
+                    ForeachStatementIterator::Value {
+                        expression,
+                        ampersand,
+                        value,
+                        ..
+                    } => (expression, ampersand, Value::Null, value),
+                    ForeachStatementIterator::KeyAndValue {
+                        expression,
+                        ampersand,
+                        key,
+                        value,
+                        ..
+                    } => (expression, ampersand, self.expression(key), value),
+                };
+
+                self.node(
+                    "Stmt_Foreach",
+                    &spans(statement.foreach, end),
+                    vec![
+                        ("expr", self.expression(expression)),
+                        ("keyVar", key),
+                        ("byRef", ampersand.is_some().into()),
+                        ("valueVar", self.expression(value)),
+                        ("stmts", stmts),
+                    ],
+                )
+            }
+            Statement::Break(statement) => self.node(
+                "Stmt_Break",
+                &[statement.r#break, ending_span(&statement.ending)],
+                vec![("num", self.level(statement.level.as_ref()))],
+            ),
+            Statement::Continue(statement) => self.node(
+                "Stmt_Continue",
+                &[statement.r#continue, ending_span(&statement.ending)],
+                vec![("num", self.level(statement.level.as_ref()))],
+            ),
+            Statement::Constant(statement) => self.node(
+                "Stmt_Const",
+                &[statement.r#const, statement.semicolon],
+                vec![("consts", self.constants(&statement.entries))],
+            ),
+            Statement::Function(function) => self.node(
+                "Stmt_Function",
+                &[function.function, function.body.right_brace],
+                vec![
+                    ("attrGroups", self.attribute_groups(&function.attributes)),
+                    ("byRef", function.ampersand.is_some().into()),
+                    ("name", self.identifier(&function.name)),
+                    ("params", self.parameters(&function.parameters)),
+                    (
+                        "returnType",
+                        self.return_type(function.return_type.as_ref()),
+                    ),
+                    ("stmts", self.statements(&function.body.statements)),
+                ],
+            ),
+            Statement::Class(class) => {
+                let flags = class_flags(&class.modifiers.modifiers);
+
+                self.node(
+                    "Stmt_Class",
+                    &[class.class, class.body.right_brace],
+                    vec![
+                        ("attrGroups", self.attribute_groups(&class.attributes)),
+                        ("flags", flags.into()),
+                        ("name", self.identifier(&class.name)),
+                        ("extends", self.extends(class.extends.as_ref())),
+                        ("implements", self.implements(class.implements.as_ref())),
+                        (
+                            "stmts",
+                            Value::Array(
+                                class
+                                    .body
+                                    .members
+                                    .iter()
+                                    .map(|member| self.class_member(member))
+                                    .collect(),
+                            ),
+                        ),
+                    ],
+                )
+            }
+            Statement::Trait(statement) => self.node(
+                "Stmt_Trait",
+                &[statement.r#trait, statement.body.right_brace],
+                vec![
+                    ("attrGroups", self.attribute_groups(&statement.attributes)),
+                    ("name", self.identifier(&statement.name)),
+                    (
+                        "stmts",
+                        Value::Array(
+                            statement
+                                .body
+                                .members
+                                .iter()
+                                .map(|member| match member {
+                                    TraitMember::Constant(constant) => {
+                                        self.class_constant(constant)
+                                    }
+                                    TraitMember::TraitUsage(usage) => self.trait_usage(usage),
+                                    TraitMember::Property(property) => self.property(
+                                        &property.attributes,
+                                        property_flags(&property.modifiers.modifiers),
+                                        property.r#type.as_ref(),
+                                        &property.entries,
+                                        property.end,
+                                    ),
+                                    TraitMember::VariableProperty(property) => self.property(
+                                        &property.attributes,
+                                        0,
+                                        property.r#type.as_ref(),
+                                        &property.entries,
+                                        property.end,
+                                    ),
+                                    TraitMember::AbstractMethod(method) => {
+                                        self.abstract_method(method)
+                                    }
+                                    TraitMember::AbstractConstructor(constructor) => {
+                                        self.abstract_constructor(constructor)
+                                    }
+                                    TraitMember::ConcreteMethod(method) => {
+                                        self.concrete_method(method)
+                                    }
+                                    TraitMember::ConcreteConstructor(constructor) => {
+                                        self.concrete_constructor(constructor)
+                                    }
+                                })
+                                .collect(),
+                        ),
+                    ),
+                ],
+            ),
+            Statement::Interface(interface) => self.node(
+                "Stmt_Interface",
+                &[interface.interface, interface.body.right_brace],
+                vec![
+                    ("attrGroups", self.attribute_groups(&interface.attributes)),
+                    ("name", self.identifier(&interface.name)),
+                    (
+                        "extends",
+                        self.names(
+                            interface
+                                .extends
+                                .iter()
+                                .flat_map(|extends| extends.parents.inner.iter()),
+                        ),
+                    ),
+                    (
+                        "stmts",
+                        Value::Array(
+                            interface
+                                .body
+                                .members
+                                .iter()
+                                .map(|member| match member {
+                                    InterfaceMember::Constant(constant) => {
+                                        self.class_constant(constant)
+                                    }
+                                    InterfaceMember::Constructor(constructor) => {
+                                        self.abstract_constructor(constructor)
+                                    }
+                                    InterfaceMember::Method(method) => self.abstract_method(method),
+                                })
+                                .collect(),
+                        ),
+                    ),
+                ],
+            ),
+            Statement::If(statement) => {
+                let (stmts, elseifs, r#else, end) = match &statement.body {
+                    IfStatementBody::Statement {
+                        statement,
+                        elseifs,
+                        r#else,
+                    } => (
+                        self.body(statement),
+                        elseifs
+                            .iter()
+                            .map(|elseif| {
+                                self.node(
+                                    "Stmt_ElseIf",
+                                    &[elseif.elseif],
+                                    vec![
+                                        ("cond", self.expression(&elseif.condition)),
+                                        ("stmts", self.body(&elseif.statement)),
+                                    ],
+                                )
+                            })
+                            .collect(),
+                        r#else.as_ref().map(|r#else| {
+                            self.node(
+                                "Stmt_Else",
+                                &[r#else.r#else],
+                                vec![("stmts", self.body(&r#else.statement))],
+                            )
+                        }),
+                        None,
+                    ),
+                    IfStatementBody::Block {
+                        statements,
+                        elseifs,
+                        r#else,
+                        ending,
+                        ..
+                    } => (
+                        self.statements(statements),
+                        elseifs
+                            .iter()
+                            .map(|elseif| {
+                                self.node(
+                                    "Stmt_ElseIf",
+                                    &[elseif.elseif, elseif.colon],
+                                    vec![
+                                        ("cond", self.expression(&elseif.condition)),
+                                        ("stmts", self.statements(&elseif.statements)),
+                                    ],
+                                )
+                            })
+                            .collect(),
+                        r#else.as_ref().map(|r#else| {
+                            self.node(
+                                "Stmt_Else",
+                                &[r#else.r#else, r#else.colon],
+                                vec![("stmts", self.statements(&r#else.statements))],
+                            )
+                        }),
+                        Some(ending_span(ending)),
+                    ),
+                };
+
+                self.node(
+                    "Stmt_If",
+                    &spans(statement.r#if, end),
+                    vec![
+                        ("cond", self.expression(&statement.condition)),
+                        ("stmts", stmts),
+                        ("elseifs", Value::Array(elseifs)),
+                        ("else", r#else.unwrap_or(Value::Null)),
+                    ],
+                )
+            }
+            Statement::Switch(statement) => {
+                let (cases, end) = match &statement.body {
+                    SwitchStatementBody::Braced {
+                        cases, right_brace, ..
+                    } => (cases, *right_brace),
+                    SwitchStatementBody::Block { cases, ending, .. } => {
+                        (cases, ending_span(ending))
+                    }
+                };
+
+                self.node(
+                    "Stmt_Switch",
+                    &[statement.switch, end],
+                    vec![
+                        ("cond", self.expression(&statement.condition)),
+                        (
+                            "cases",
+                            cases
+                                .iter()
+                                .map(|case| {
+                                    self.node(
+                                        "Stmt_Case",
+                                        &[case.keyword, case.colon],
+                                        vec![
+                                            (
+                                                "cond",
+                                                self.optional_expression(case.condition.as_ref()),
+                                            ),
+                                            ("stmts", self.statements(&case.body)),
+                                        ],
+                                    )
+                                })
+                                .collect(),
+                        ),
+                    ],
+                )
+            }
+            Statement::Echo(echo) => self.node(
+                "Stmt_Echo",
+                &[echo.echo, ending_span(&echo.ending)],
+                vec![("exprs", self.expressions(&echo.values))],
+            ),
+            Statement::Expression(statement) => match &statement.expression {
+                // `unset()` is a statement in nikic/php-parser.
+                Expression::Unset(unset) => self.node(
+                    "Stmt_Unset",
+                    &[unset.unset, ending_span(&statement.ending)],
+                    vec![("vars", self.argument_values(&unset.arguments))],
+                ),
+                expression => self.node(
+                    "Stmt_Expression",
+                    &[ending_span(&statement.ending)],
+                    vec![("expr", self.expression(expression))],
+                ),
+            },
+            Statement::Return(statement) => self.node(
+                "Stmt_Return",
+                &[statement.r#return, ending_span(&statement.ending)],
+                vec![("expr", self.optional_expression(statement.value.as_ref()))],
+            ),
+            Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => with_attribute(
+                self.node(
+                    "Stmt_Namespace",
+                    &[namespace.start, namespace.end],
+                    vec![
+                        ("name", self.name(&namespace.name)),
+                        ("stmts", self.statements(&namespace.statements)),
+                    ],
+                ),
+                "kind",
+                1.into(),
+            ),
+            Statement::Namespace(NamespaceStatement::Braced(namespace)) => with_attribute(
+                self.node(
+                    "Stmt_Namespace",
+                    &[namespace.namespace, namespace.body.end],
+                    vec![
+                        (
+                            "name",
+                            namespace
+                                .name
+                                .as_ref()
+                                .map(|name| self.name(name))
+                                .unwrap_or(Value::Null),
+                        ),
+                        ("stmts", self.statements(&namespace.body.statements)),
+                    ],
+                ),
+                "kind",
+                2.into(),
+            ),
+            Statement::Use(statement) => self.node(
+                "Stmt_Use",
+                &[statement.r#use, statement.semicolon],
+                vec![
+                    ("type", use_type(Some(&statement.kind)).into()),
+                    ("uses", self.uses(&statement.uses)),
+                ],
+            ),
+            Statement::GroupUse(statement) => self.node(
+                "Stmt_GroupUse",
+                &[statement.r#use, statement.semicolon],
+                vec![
+                    ("type", use_type(Some(&statement.kind)).into()),
+                    ("prefix", self.use_name(&statement.prefix)),
+                    ("uses", self.uses(&statement.uses)),
+                ],
+            ),
+            Statement::Try(statement) => self.node(
+                "Stmt_TryCatch",
+                &[statement.start, statement.end],
+                vec![
+                    ("stmts", self.statements(&statement.body)),
+                    (
+                        "catches",
+                        statement
+                            .catches
+                            .iter()
+                            .map(|catch| {
+                                let types = match &catch.types {
+                                    CatchType::Identifier { identifier } => {
+                                        self.names(std::iter::once(identifier))
+                                    }
+                                    CatchType::Union { identifiers } => {
+                                        self.names(identifiers.iter())
+                                    }
+                                };
+
+                                self.node(
+                                    "Stmt_Catch",
+                                    &[catch.start, catch.end],
+                                    vec![
+                                        ("types", types),
+                                        (
+                                            "var",
+                                            catch
+                                                .var
+                                                .as_ref()
+                                                .map(|var| self.simple_variable(var))
+                                                .unwrap_or(Value::Null),
+                                        ),
+                                        ("stmts", self.statements(&catch.body)),
+                                    ],
+                                )
+                            })
+                            .collect(),
+                    ),
+                    (
+                        "finally",
+                        statement
+                            .finally
+                            .as_ref()
+                            .map(|finally| {
+                                self.node(
+                                    "Stmt_Finally",
+                                    &[finally.start, finally.end],
+                                    vec![("stmts", self.statements(&finally.body))],
+                                )
+                            })
+                            .unwrap_or(Value::Null),
+                    ),
+                ],
+            ),
+            Statement::UnitEnum(statement) => self.node(
+                "Stmt_Enum",
+                &[statement.r#enum, statement.body.right_brace],
+                vec![
+                    ("attrGroups", self.attribute_groups(&statement.attributes)),
+                    ("name", self.identifier(&statement.name)),
+                    ("scalarType", Value::Null),
+                    ("implements", self.names(statement.implements.iter())),
+                    (
+                        "stmts",
+                        statement
+                            .body
+                            .members
+                            .iter()
+                            .map(|member| match member {
+                                UnitEnumMember::Case(case) => self.node(
+                                    "Stmt_EnumCase",
+                                    &[case.start, case.end],
+                                    vec![
+                                        ("attrGroups", self.attribute_groups(&case.attributes)),
+                                        ("name", self.identifier(&case.name)),
+                                        ("expr", Value::Null),
+                                    ],
+                                ),
+                                UnitEnumMember::Method(method) => self.concrete_method(method),
+                                UnitEnumMember::Constant(constant) => self.class_constant(constant),
+                                UnitEnumMember::TraitUsage(usage) => self.trait_usage(usage),
+                            })
+                            .collect(),
+                    ),
+                ],
+            ),
+            Statement::BackedEnum(statement) => {
+                let scalar_type = match statement.backed_type {
+                    BackedEnumType::String(_, span) | BackedEnumType::Int(_, span) => self.node(
+                        "Identifier",
+                        &[span],
+                        vec![("name", self.text(span).into())],
+                    ),
+                };
+
+                self.node(
+                    "Stmt_Enum",
+                    &[statement.r#enum, statement.body.right_brace],
+                    vec![
+                        ("attrGroups", self.attribute_groups(&statement.attributes)),
+                        ("name", self.identifier(&statement.name)),
+                        ("scalarType", scalar_type),
+                        ("implements", self.names(statement.implements.iter())),
+                        (
+                            "stmts",
+                            statement
+                                .body
+                                .members
+                                .iter()
+                                .map(|member| match member {
+                                    BackedEnumMember::Case(case) => self.node(
+                                        "Stmt_EnumCase",
+                                        &[case.case, case.semicolon],
+                                        vec![
+                                            ("attrGroups", self.attribute_groups(&case.attributes)),
+                                            ("name", self.identifier(&case.name)),
+                                            ("expr", self.expression(&case.value)),
+                                        ],
+                                    ),
+                                    BackedEnumMember::Method(method) => {
+                                        self.concrete_method(method)
+                                    }
+                                    BackedEnumMember::Constant(constant) => {
+                                        self.class_constant(constant)
+                                    }
+                                    BackedEnumMember::TraitUsage(usage) => self.trait_usage(usage),
+                                })
+                                .collect(),
+                        ),
+                    ],
+                )
+            }
+            Statement::Block(block) => self.node(
+                "Stmt_Block",
+                &[block.left_brace, block.right_brace],
+                vec![("stmts", self.statements(&block.statements))],
+            ),
+            Statement::Global(global) => self.node(
+                "Stmt_Global",
+                &[global.global],
+                vec![(
+                    "vars",
+                    global
+                        .variables
+                        .iter()
+                        .map(|variable| self.variable(variable))
+                        .collect(),
+                )],
+            ),
+            Statement::Declare(declare) => {
+                let (stmts, end) = match &declare.body {
+                    DeclareBody::Noop { semicolon } => (Value::Null, *semicolon),
+                    DeclareBody::Braced {
+                        statements,
+                        right_brace,
+                        ..
+                    } => (self.statements(statements), *right_brace),
+                    DeclareBody::Expression {
+                        expression,
+                        semicolon,
+                    } => (
+                        Value::Array(vec![self.node(
+                            "Stmt_Expression",
+                            &[*semicolon],
+                            vec![("expr", self.expression(expression))],
+                        )]),
+                        *semicolon,
+                    ),
+                    DeclareBody::Block {
+                        statements, end, ..
+                    } => (self.statements(statements), end.1),
+                };
+
+                self.node(
+                    "Stmt_Declare",
+                    &[declare.declare, end],
+                    vec![
+                        (
+                            "declares",
+                            declare
+                                .entries
+                                .entries
+                                .iter()
+                                .map(|entry| {
+                                    self.node(
+                                        "DeclareItem",
+                                        &[],
+                                        vec![
+                                            ("key", self.identifier(&entry.key)),
+                                            ("value", self.literal(&entry.value)),
+                                        ],
+                                    )
+                                })
+                                .collect(),
+                        ),
+                        ("stmts", stmts),
+                    ],
+                )
+            }
+            Statement::Noop(span) => self.node("Stmt_Nop", &[*span], vec![]),
+        };
+
+        Some(value)
+    }
+
+    fn expressions(&self, expressions: &[Expression]) -> Value {
+        Value::Array(
+            expressions
+                .iter()
+                .map(|expression| self.expression(expression))
+                .collect(),
+        )
+    }
+
+    fn optional_expression(&self, expression: Option<&Expression>) -> Value {
+        expression
+            .map(|expression| self.expression(expression))
+            .unwrap_or(Value::Null)
+    }
+
+    fn expression(&self, expression: &Expression) -> Value {
+        match expression {
+            Expression::Eval(eval) => self.node(
+                "Expr_Eval",
+                &[eval.eval, eval.argument.right_parenthesis],
+                vec![("expr", self.single_argument(&eval.argument))],
+            ),
+            Expression::Empty(empty) => self.node(
+                "Expr_Empty",
+                &[empty.empty, empty.argument.right_parenthesis],
+                vec![("expr", self.single_argument(&empty.argument))],
+            ),
+            Expression::Die(die) => with_attribute(
+                self.node(
+                    "Expr_Exit",
+                    &[die.die],
+                    vec![(
+                        "expr",
+                        die.argument
+                            .as_ref()
+                            .map(|argument| self.single_argument(argument))
+                            .unwrap_or(Value::Null),
+                    )],
+                ),
+                "kind",
+                2.into(),
+            ),
+            Expression::Exit(exit) => with_attribute(
+                self.node(
+                    "Expr_Exit",
+                    &[exit.exit],
+                    vec![(
+                        "expr",
+                        exit.argument
+                            .as_ref()
+                            .map(|argument| self.single_argument(argument))
+                            .unwrap_or(Value::Null),
+                    )],
+                ),
+                "kind",
+                1.into(),
+            ),
+            Expression::Isset(isset) => self.node(
+                "Expr_Isset",
+                &[isset.isset, isset.arguments.right_parenthesis],
+                vec![("vars", self.argument_values(&isset.arguments))],
+            ),
+            Expression::Unset(unset) => self.node(
+                "Stmt_Unset",
+                &[unset.unset, unset.arguments.right_parenthesis],
+                vec![("vars", self.argument_values(&unset.arguments))],
+            ),
+            Expression::Print(print) => {
+                let value = match (&print.value, &print.argument) {
+                    (Some(value), _) => self.expression(value),
+                    (None, Some(argument)) => self.single_argument(argument),
+                    (None, None) => Value::Null,
+                };
+
+                self.node("Expr_Print", &[print.print], vec![("expr", value)])
+            }
+            Expression::Literal(literal) => self.literal(literal),
+            Expression::ArithmeticOperation(operation) => self.arithmetic(operation),
+            Expression::AssignmentOperation(operation) => self.assignment(operation),
+            Expression::BitwiseOperation(operation) => match operation {
+                BitwiseOperationExpression::And { left, and, right } => {
+                    self.binary("BitwiseAnd", left, *and, right)
+                }
+                BitwiseOperationExpression::Or { left, or, right } => {
+                    self.binary("BitwiseOr", left, *or, right)
+                }
+                BitwiseOperationExpression::Xor { left, xor, right } => {
+                    self.binary("BitwiseXor", left, *xor, right)
+                }
+                BitwiseOperationExpression::LeftShift {
+                    left,
+                    left_shift,
+                    right,
+                } => self.binary("ShiftLeft", left, *left_shift, right),
+                BitwiseOperationExpression::RightShift {
+                    left,
+                    right_shift,
+                    right,
+                } => self.binary("ShiftRight", left, *right_shift, right),
+                BitwiseOperationExpression::Not { not, right } => {
+                    self.unary("Expr_BitwiseNot", "expr", *not, right)
+                }
+            },
+            Expression::ComparisonOperation(operation) => match operation {
+                ComparisonOperationExpression::Equal {
+                    left,
+                    double_equals,
+                    right,
+                } => self.binary("Equal", left, *double_equals, right),
+                ComparisonOperationExpression::Identical {
+                    left,
+                    triple_equals,
+                    right,
+                } => self.binary("Identical", left, *triple_equals, right),
+                ComparisonOperationExpression::NotEqual {
+                    left,
+                    bang_equals,
+                    right,
+                } => self.binary("NotEqual", left, *bang_equals, right),
+                ComparisonOperationExpression::AngledNotEqual {
+                    left,
+                    angled_left_right,
+                    right,
+                } => self.binary("NotEqual", left, *angled_left_right, right),
+                ComparisonOperationExpression::NotIdentical {
+                    left,
+                    bang_double_equals,
+                    right,
+                } => self.binary("NotIdentical", left, *bang_double_equals, right),
+                ComparisonOperationExpression::LessThan {
+                    left,
+                    less_than,
+                    right,
+                } => self.binary("Smaller", left, *less_than, right),
+                ComparisonOperationExpression::GreaterThan {
+                    left,
+                    greater_than,
+                    right,
+                } => self.binary("Greater", left, *greater_than, right),
+                ComparisonOperationExpression::LessThanOrEqual {
+                    left,
+                    less_than_equals,
+                    right,
+                } => self.binary("SmallerOrEqual", left, *less_than_equals, right),
+                ComparisonOperationExpression::GreaterThanOrEqual {
+                    left,
+                    greater_than_equals,
+                    right,
+                } => self.binary("GreaterOrEqual", left, *greater_than_equals, right),
+                ComparisonOperationExpression::Spaceship {
+                    left,
+                    spaceship,
+                    right,
+                } => self.binary("Spaceship", left, *spaceship, right),
+            },
+            Expression::LogicalOperation(operation) => match operation {
+                LogicalOperationExpression::And {
+                    left,
+                    double_ampersand,
+                    right,
+                } => self.binary("BooleanAnd", left, *double_ampersand, right),
+                LogicalOperationExpression::Or {
+                    left,
+                    double_pipe,
+                    right,
+                } => self.binary("BooleanOr", left, *double_pipe, right),
+                LogicalOperationExpression::Not { bang, right } => {
+                    self.unary("Expr_BooleanNot", "expr", *bang, right)
+                }
+                LogicalOperationExpression::LogicalAnd { left, and, right } => {
+                    self.binary("LogicalAnd", left, *and, right)
+                }
+                LogicalOperationExpression::LogicalOr { left, or, right } => {
+                    self.binary("LogicalOr", left, *or, right)
+                }
+                LogicalOperationExpression::LogicalXor { left, xor, right } => {
+                    self.binary("LogicalXor", left, *xor, right)
+                }
+            },
+            Expression::Concat(concat) => {
+                self.binary("Concat", &concat.left, concat.dot, &concat.right)
+            }
+            Expression::Instanceof(instanceof) => self.node(
+                "Expr_Instanceof",
+                &[instanceof.instanceof],
+                vec![
+                    ("expr", self.expression(&instanceof.left)),
+                    ("class", self.class_reference(&instanceof.right)),
+                ],
+            ),
+            // references are represented by a `byRef` flag on the node that contains them.
+            Expression::Reference(reference) => self.expression(&reference.right),
+            // nikic/php-parser does not keep parentheses in the tree.
+            Expression::Parenthesized(parenthesized) => self.expression(&parenthesized.expr),
+            Expression::ErrorSuppress(suppress) => {
+                self.unary("Expr_ErrorSuppress", "expr", suppress.at, &suppress.expr)
+            }
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => self.node(
+                "Expr_ConstFetch",
+                &[],
+                vec![("name", self.name(identifier))],
+            ),
+            Expression::Identifier(Identifier::DynamicIdentifier(identifier)) => {
+                self.expression(&identifier.expr)
+            }
+            Expression::Variable(variable) => self.variable(variable),
+            Expression::Include(include) => self.include(1, include.include, &include.path),
+            Expression::IncludeOnce(include) => {
+                self.include(2, include.include_once, &include.path)
+            }
+            Expression::Require(require) => self.include(3, require.require, &require.path),
+            Expression::RequireOnce(require) => {
+                self.include(4, require.require_once, &require.path)
+            }
+            Expression::FunctionCall(call) => self.node(
+                "Expr_FuncCall",
+                &[call.arguments.right_parenthesis],
+                vec![
+                    ("name", self.function_name(&call.target)),
+                    ("args", self.arguments(&call.arguments)),
+                ],
+            ),
+            Expression::FunctionClosureCreation(call) => self.node(
+                "Expr_FuncCall",
+                &[call.placeholder.right_parenthesis],
+                vec![
+                    ("name", self.function_name(&call.target)),
+                    ("args", self.placeholder(&call.placeholder)),
+                ],
+            ),
+            Expression::MethodCall(call) => self.node(
+                "Expr_MethodCall",
+                &[call.arguments.right_parenthesis],
+                vec![
+                    ("var", self.expression(&call.target)),
+                    ("name", self.member_name(&call.method)),
+                    ("args", self.arguments(&call.arguments)),
+                ],
+            ),
+            Expression::MethodClosureCreation(call) => self.node(
+                "Expr_MethodCall",
+                &[call.placeholder.right_parenthesis],
+                vec![
+                    ("var", self.expression(&call.target)),
+                    ("name", self.member_name(&call.method)),
+                    ("args", self.placeholder(&call.placeholder)),
+                ],
+            ),
+            Expression::NullsafeMethodCall(call) => self.node(
+                "Expr_NullsafeMethodCall",
+                &[call.arguments.right_parenthesis],
+                vec![
+                    ("var", self.expression(&call.target)),
+                    ("name", self.member_name(&call.method)),
+                    ("args", self.arguments(&call.arguments)),
+                ],
+            ),
+            Expression::StaticMethodCall(call) => self.node(
+                "Expr_StaticCall",
+                &[call.arguments.right_parenthesis],
+                vec![
+                    ("class", self.class_reference(&call.target)),
+                    ("name", self.identifier_or_expression(&call.method)),
+                    ("args", self.arguments(&call.arguments)),
+                ],
+            ),
+            Expression::StaticVariableMethodCall(call) => self.node(
+                "Expr_StaticCall",
+                &[call.arguments.right_parenthesis],
+                vec![
+                    ("class", self.class_reference(&call.target)),
+                    ("name", self.variable(&call.method)),
+                    ("args", self.arguments(&call.arguments)),
+                ],
+            ),
+            Expression::StaticMethodClosureCreation(call) => self.node(
+                "Expr_StaticCall",
+                &[call.placeholder.right_parenthesis],
+                vec![
+                    ("class", self.class_reference(&call.target)),
+                    ("name", self.identifier_or_expression(&call.method)),
+                    ("args", self.placeholder(&call.placeholder)),
+                ],
+            ),
+            Expression::StaticVariableMethodClosureCreation(call) => self.node(
+                "Expr_StaticCall",
+                &[call.placeholder.right_parenthesis],
+                vec![
+                    ("class", self.class_reference(&call.target)),
+                    ("name", self.variable(&call.method)),
+                    ("args", self.placeholder(&call.placeholder)),
+                ],
+            ),
+            Expression::PropertyFetch(fetch) => self.node(
+                "Expr_PropertyFetch",
+                &[fetch.arrow],
+                vec![
+                    ("var", self.expression(&fetch.target)),
+                    ("name", self.member_name(&fetch.property)),
+                ],
+            ),
+            Expression::NullsafePropertyFetch(fetch) => self.node(
+                "Expr_NullsafePropertyFetch",
+                &[fetch.question_arrow],
+                vec![
+                    ("var", self.expression(&fetch.target)),
+                    ("name", self.member_name(&fetch.property)),
+                ],
+            ),
+            Expression::StaticPropertyFetch(fetch) => {
+                let name = match &fetch.property {
+                    Variable::SimpleVariable(variable) => self.node(
+                        "VarLikeIdentifier",
+                        &[variable.span],
+                        vec![("name", variable_name(&variable.name))],
+                    ),
+                    Variable::VariableVariable(variable) => self.variable(&variable.variable),
+                    Variable::BracedVariableVariable(variable) => {
+                        self.expression(&variable.variable)
+                    }
+                };
+
+                self.node(
+                    "Expr_StaticPropertyFetch",
+                    &[fetch.double_colon],
+                    vec![
+                        ("class", self.class_reference(&fetch.target)),
+                        ("name", name),
+                    ],
+                )
+            }
+            Expression::ConstantFetch(fetch) => self.node(
+                "Expr_ClassConstFetch",
+                &[fetch.double_colon],
+                vec![
+                    ("class", self.class_reference(&fetch.target)),
+                    ("name", self.identifier_or_expression(&fetch.constant)),
+                ],
+            ),
+            Expression::Static(span) => {
+                self.node("Name", &[*span], vec![("name", "static".into())])
+            }
+            Expression::Self_(span) => self.node("Name", &[*span], vec![("name", "self".into())]),
+            Expression::Parent(span) => {
+                self.node("Name", &[*span], vec![("name", "parent".into())])
+            }
+            Expression::ShortArray(array) => with_attribute(
+                self.node(
+                    "Expr_Array",
+                    &[array.start, array.end],
+                    vec![("items", self.array_items(&array.items.inner))],
+                ),
+                "kind",
+                ARRAY_KIND_SHORT.into(),
+            ),
+            Expression::Array(array) => with_attribute(
+                self.node(
+                    "Expr_Array",
+                    &[array.array, array.end],
+                    vec![("items", self.array_items(&array.items.inner))],
+                ),
+                "kind",
+                ARRAY_KIND_LONG.into(),
+            ),
+            Expression::List(list) => {
+                let items = list
+                    .items
+                    .iter()
+                    .map(|item| match item {
+                        ListEntry::Skipped => Value::Null,
+                        ListEntry::Value { value } => self.array_item(None, value, false, false),
+                        ListEntry::ReferencedValue { value, .. } => {
+                            self.array_item(None, value, true, false)
+                        }
+                        ListEntry::KeyValue { key, value, .. } => {
+                            self.array_item(Some(key), value, false, false)
+                        }
+                        ListEntry::ReferencedKeyValue { key, value, .. } => {
+                            self.array_item(Some(key), value, true, false)
+                        }
+                    })
+                    .collect();
+                let kind = if list.list.is_some() {
+                    ARRAY_KIND_LONG
+                } else {
+                    ARRAY_KIND_SHORT
+                };
+
+                with_attribute(
+                    self.node(
+                        "Expr_List",
+                        &spans(list.start, list.list)
+                            .into_iter()
+                            .chain([list.end])
+                            .collect::<Vec<_>>(),
+                        vec![("items", Value::Array(items))],
+                    ),
+                    "kind",
+                    kind.into(),
+                )
+            }
+            Expression::Closure(closure) => self.node(
+                "Expr_Closure",
+                &[closure.function, closure.body.right_brace],
+                vec![
+                    ("attrGroups", self.attribute_groups(&closure.attributes)),
+                    ("static", closure.r#static.is_some().into()),
+                    ("byRef", closure.ampersand.is_some().into()),
+                    ("params", self.parameters(&closure.parameters)),
+                    (
+                        "uses",
+                        closure
+                            .uses
+                            .iter()
+                            .flat_map(|uses| uses.variables.inner.iter())
+                            .map(|variable| {
+                                self.node(
+                                    "ClosureUse",
+                                    &variable.ampersand.into_iter().collect::<Vec<_>>(),
+                                    vec![
+                                        ("var", self.simple_variable(&variable.variable)),
+                                        ("byRef", variable.ampersand.is_some().into()),
+                                    ],
+                                )
+                            })
+                            .collect(),
+                    ),
+                    ("returnType", self.return_type(closure.return_type.as_ref())),
+                    ("stmts", self.statements(&closure.body.statements)),
+                ],
+            ),
+            Expression::ArrowFunction(function) => self.node(
+                "Expr_ArrowFunction",
+                &spans(function.r#fn, function.r#static),
+                vec![
+                    ("attrGroups", self.attribute_groups(&function.attributes)),
+                    ("static", function.r#static.is_some().into()),
+                    ("byRef", function.ampersand.is_some().into()),
+                    ("params", self.parameters(&function.parameters)),
+                    (
+                        "returnType",
+                        self.return_type(function.return_type.as_ref()),
+                    ),
+                    ("expr", self.expression(&function.body)),
+                ],
+            ),
+            Expression::New(new) => {
+                let class = match new.target.as_ref() {
+                    Expression::AnonymousClass(class) => self.anonymous_class(class),
+                    target => self.class_reference(target),
+                };
+                let arguments = new
+                    .arguments
+                    .as_ref()
+                    .map(|arguments| self.arguments(arguments))
+                    .unwrap_or_else(|| Value::Array(vec![]));
+
+                self.node(
+                    "Expr_New",
+                    &spans(new.new, new.arguments.as_ref().map(|a| a.right_parenthesis)),
+                    vec![("class", class), ("args", arguments)],
+                )
+            }
+            Expression::InterpolatedString(string) => with_attribute(
+                self.node(
+                    "Scalar_InterpolatedString",
+                    &[string.start, string.end],
+                    vec![("parts", self.string_parts(&string.parts))],
+                ),
+                "kind",
+                STRING_KIND_DOUBLE_QUOTED.into(),
+            ),
+            Expression::Heredoc(heredoc) => {
+                let parts = heredoc.indentation_stripped();
+                let spans = [heredoc.start, heredoc.end];
+                let node = match parts.as_slice() {
+                    [] => self.node("Scalar_String", &spans, vec![("value", "".into())]),
+                    [StringPart::Literal(part)] => self.node(
+                        "Scalar_String",
+                        &spans,
+                        vec![("value", string(&part.value))],
+                    ),
+                    parts => self.node(
+                        "Scalar_InterpolatedString",
+                        &spans,
+                        vec![("parts", self.string_parts(parts))],
+                    ),
+                };
+
+                with_attribute(
+                    with_attribute(node, "kind", STRING_KIND_HEREDOC.into()),
+                    "docLabel",
+                    string(&heredoc.label),
+                )
+            }
+            Expression::Nowdoc(nowdoc) => with_attribute(
+                with_attribute(
+                    self.node(
+                        "Scalar_String",
+                        &[nowdoc.start, nowdoc.end],
+                        vec![("value", string(&nowdoc.indentation_stripped()))],
+                    ),
+                    "kind",
+                    STRING_KIND_NOWDOC.into(),
+                ),
+                "docLabel",
+                string(&nowdoc.label),
+            ),
+            Expression::ShellExec(shell) => self.node(
+                "Expr_ShellExec",
+                &[shell.start, shell.end],
+                vec![("parts", self.string_parts(&shell.parts))],
+            ),
+            Expression::AnonymousClass(class) => self.anonymous_class(class),
+            Expression::Bool(bool) => {
+                self.constant_fetch(if bool.value { "true" } else { "false" }, bool.span)
+            }
+            Expression::ArrayIndex(index) => self.node(
+                "Expr_ArrayDimFetch",
+                &[index.left_bracket, index.right_bracket],
+                vec![
+                    ("var", self.expression(&index.array)),
+                    ("dim", self.optional_expression(index.index.as_deref())),
+                ],
+            ),
+            Expression::Null(span) => self.constant_fetch("null", *span),
+            Expression::MagicConstant(constant) => {
+                let (node_type, span) = match constant {
+                    MagicConstantExpression::Directory(span) => ("Scalar_MagicConst_Dir", span),
+                    MagicConstantExpression::File(span) => ("Scalar_MagicConst_File", span),
+                    MagicConstantExpression::Line(span) => ("Scalar_MagicConst_Line", span),
+                    MagicConstantExpression::Class(span) => ("Scalar_MagicConst_Class", span),
+                    MagicConstantExpression::Function(span) => ("Scalar_MagicConst_Function", span),
+                    MagicConstantExpression::Method(span) => ("Scalar_MagicConst_Method", span),
+                    MagicConstantExpression::Namespace(span) => {
+                        ("Scalar_MagicConst_Namespace", span)
+                    }
+                    MagicConstantExpression::Trait(span) => ("Scalar_MagicConst_Trait", span),
+                    MagicConstantExpression::CompilerHaltOffset(span) => {
+                        let name =
+                            self.node("Name", &[*span], vec![("name", self.text(*span).into())]);
+
+                        return self.node("Expr_ConstFetch", &[], vec![("name", name)]);
+                    }
+                };
+
+                self.node(node_type, &[*span], vec![])
+            }
+            Expression::ShortTernary(ternary) => self.node(
+                "Expr_Ternary",
+                &[ternary.question_colon],
+                vec![
+                    ("cond", self.expression(&ternary.condition)),
+                    ("if", Value::Null),
+                    ("else", self.expression(&ternary.r#else)),
+                ],
+            ),
+            Expression::Ternary(ternary) => self.node(
+                "Expr_Ternary",
+                &[ternary.question, ternary.colon],
+                vec![
+                    ("cond", self.expression(&ternary.condition)),
+                    ("if", self.expression(&ternary.then)),
+                    ("else", self.expression(&ternary.r#else)),
+                ],
+            ),
+            Expression::Coalesce(coalesce) => self.binary(
+                "Coalesce",
+                &coalesce.lhs,
+                coalesce.double_question,
+                &coalesce.rhs,
+            ),
+            Expression::Clone(clone) => self.node(
+                "Expr_Clone",
+                &[clone.clone],
+                vec![("expr", self.expression(&clone.target))],
+            ),
+            Expression::Match(r#match) => {
+                let mut arms: Vec<Value> = r#match
+                    .arms
+                    .iter()
+                    .map(|arm| {
+                        self.node(
+                            "MatchArm",
+                            &[arm.arrow],
+                            vec![
+                                ("conds", self.expressions(&arm.conditions.inner)),
+                                ("body", self.expression(&arm.body)),
+                            ],
+                        )
+                    })
+                    .collect();
+
+                // the default arm is stored separately, so it is put back in source order.
+                if let Some(default) = &r#match.default {
+                    let index = arms
+                        .iter()
+                        .position(|arm| {
+                            arm["attributes"]["startFilePos"]
+                                .as_u64()
+                                .is_some_and(|start| start > default.keyword.position as u64)
+                        })
+                        .unwrap_or(arms.len());
+
+                    arms.insert(
+                        index,
+                        self.node(
+                            "MatchArm",
+                            &[default.keyword],
+                            vec![
+                                ("conds", Value::Null),
+                                ("body", self.expression(&default.body)),
+                            ],
+                        ),
+                    );
+                }
+
+                self.node(
+                    "Expr_Match",
+                    &[r#match.keyword, r#match.right_brace],
+                    vec![
+                        ("cond", self.expression(&r#match.condition)),
+                        ("arms", Value::Array(arms)),
+                    ],
+                )
+            }
+            Expression::Throw(throw) => self.node(
+                "Expr_Throw",
+                &[throw.throw],
+                vec![("expr", self.expression(&throw.value))],
+            ),
+            Expression::Yield(r#yield) => self.node(
+                "Expr_Yield",
+                &[r#yield.r#yield],
+                vec![
+                    ("key", self.optional_expression(r#yield.key.as_deref())),
+                    ("value", self.optional_expression(r#yield.value.as_deref())),
+                ],
+            ),
+            Expression::YieldFrom(r#yield) => self.node(
+                "Expr_YieldFrom",
+                &[r#yield.r#yield],
+                vec![("expr", self.expression(&r#yield.value))],
+            ),
+            Expression::Cast(cast) => {
+                let node_type = match cast.kind {
+                    CastKind::Int => "Expr_Cast_Int",
+                    CastKind::Bool => "Expr_Cast_Bool",
+                    CastKind::Float => "Expr_Cast_Double",
+                    CastKind::String => "Expr_Cast_String",
+                    CastKind::Array => "Expr_Cast_Array",
+                    CastKind::Object => "Expr_Cast_Object",
+                    CastKind::Unset => "Expr_Cast_Unset",
+                };
+
+                self.unary(node_type, "expr", cast.cast, &cast.value)
+            }
+            Expression::Noop | Expression::Missing(_) => Value::Null,
+        }
+    }
+
+    fn arithmetic(&self, operation: &ArithmeticOperationExpression) -> Value {
+        match operation {
+            ArithmeticOperationExpression::Addition { left, plus, right } => {
+                self.binary("Plus", left, *plus, right)
+            }
+            ArithmeticOperationExpression::Subtraction { left, minus, right } => {
+                self.binary("Minus", left, *minus, right)
+            }
+            ArithmeticOperationExpression::Multiplication {
+                left,
+                asterisk,
+                right,
+            } => self.binary("Mul", left, *asterisk, right),
+            ArithmeticOperationExpression::Division { left, slash, right } => {
+                self.binary("Div", left, *slash, right)
+            }
+            ArithmeticOperationExpression::Modulo {
+                left,
+                percent,
+                right,
+            } => self.binary("Mod", left, *percent, right),
+            ArithmeticOperationExpression::Exponentiation { left, pow, right } => {
+                self.binary("Pow", left, *pow, right)
+            }
+            ArithmeticOperationExpression::Negative { minus, right } => {
+                self.unary("Expr_UnaryMinus", "expr", *minus, right)
+            }
+            ArithmeticOperationExpression::Positive { plus, right } => {
+                self.unary("Expr_UnaryPlus", "expr", *plus, right)
+            }
+            ArithmeticOperationExpression::PreIncrement { increment, right } => {
+                self.unary("Expr_PreInc", "var", *increment, right)
+            }
+            ArithmeticOperationExpression::PostIncrement { left, increment } => {
+                self.unary("Expr_PostInc", "var", *increment, left)
+            }
+            ArithmeticOperationExpression::PreDecrement { decrement, right } => {
+                self.unary("Expr_PreDec", "var", *decrement, right)
+            }
+            ArithmeticOperationExpression::PostDecrement { left, decrement } => {
+                self.unary("Expr_PostDec", "var", *decrement, left)
+            }
+        }
+    }
+
+    fn assignment(&self, operation: &AssignmentOperationExpression) -> Value {
+        let (node_type, left, operator, right) = match operation {
+            AssignmentOperationExpression::Assign {
+                left,
+                equals,
+                right,
+            } => match right.as_ref() {
+                Expression::Reference(reference) => {
+                    ("Expr_AssignRef", left, *equals, &reference.right)
+                }
+                _ => ("Expr_Assign", left, *equals, right),
+            },
+            AssignmentOperationExpression::Addition {
+                left,
+                plus_equals,
+                right,
+            } => ("Expr_AssignOp_Plus", left, *plus_equals, right),
+            AssignmentOperationExpression::Subtraction {
+                left,
+                minus_equals,
+                right,
+            } => ("Expr_AssignOp_Minus", left, *minus_equals, right),
+            AssignmentOperationExpression::Multiplication {
+                left,
+                asterisk_equals,
+                right,
+            } => ("Expr_AssignOp_Mul", left, *asterisk_equals, right),
+            AssignmentOperationExpression::Division {
+                left,
+                slash_equals,
+                right,
+            } => ("Expr_AssignOp_Div", left, *slash_equals, right),
+            AssignmentOperationExpression::Modulo {
+                left,
+                percent_equals,
+                right,
+            } => ("Expr_AssignOp_Mod", left, *percent_equals, right),
+            AssignmentOperationExpression::Exponentiation {
+                left,
+                pow_equals,
+                right,
+            } => ("Expr_AssignOp_Pow", left, *pow_equals, right),
+            AssignmentOperationExpression::Concat {
+                left,
+                dot_equals,
+                right,
+            } => ("Expr_AssignOp_Concat", left, *dot_equals, right),
+            AssignmentOperationExpression::BitwiseAnd {
+                left,
+                ampersand_equals,
+                right,
+            } => ("Expr_AssignOp_BitwiseAnd", left, *ampersand_equals, right),
+            AssignmentOperationExpression::BitwiseOr {
+                left,
+                pipe_equals,
+                right,
+            } => ("Expr_AssignOp_BitwiseOr", left, *pipe_equals, right),
+            AssignmentOperationExpression::BitwiseXor {
+                left,
+                caret_equals,
+                right,
+            } => ("Expr_AssignOp_BitwiseXor", left, *caret_equals, right),
+            AssignmentOperationExpression::LeftShift {
+                left,
+                left_shift_equals,
+                right,
+            } => ("Expr_AssignOp_ShiftLeft", left, *left_shift_equals, right),
+            AssignmentOperationExpression::RightShift {
+                left,
+                right_shift_equals,
+                right,
+            } => ("Expr_AssignOp_ShiftRight", left, *right_shift_equals, right),
+            AssignmentOperationExpression::Coalesce {
+                left,
+                coalesce_equals,
+                right,
+            } => ("Expr_AssignOp_Coalesce", left, *coalesce_equals, right),
+        };
+
+        self.node(
+            node_type,
+            &[operator],
+            vec![
+                ("var", self.expression(left)),
+                ("expr", self.expression(right)),
+            ],
+        )
+    }
+
+    fn binary(&self, operator: &str, left: &Expression, span: Span, right: &Expression) -> Value {
+        self.node(
+            &format!("Expr_BinaryOp_{}", operator),
+            &[span],
+            vec![
+                ("left", self.expression(left)),
+                ("right", self.expression(right)),
+            ],
+        )
+    }
+
+    fn unary(&self, node_type: &str, key: &str, span: Span, expression: &Expression) -> Value {
+        self.node(node_type, &[span], vec![(key, self.expression(expression))])
+    }
+
+    fn include(&self, kind: u64, span: Span, path: &Expression) -> Value {
+        self.node(
+            "Expr_Include",
+            &[span],
+            vec![("expr", self.expression(path)), ("type", kind.into())],
+        )
+    }
+
+    fn constant_fetch(&self, name: &str, span: Span) -> Value {
+        let name = self.node("Name", &[span], vec![("name", name.into())]);
+
+        self.node("Expr_ConstFetch", &[span], vec![("name", name)])
+    }
+
+    fn literal(&self, literal: &Literal) -> Value {
+        match literal {
+            Literal::String(string) => {
+                let kind = match string.kind {
+                    LiteralStringKind::SingleQuoted => STRING_KIND_SINGLE_QUOTED,
+                    LiteralStringKind::DoubleQuoted => STRING_KIND_DOUBLE_QUOTED,
+                };
+                let node = self.node(
+                    "Scalar_String",
+                    &[string.span],
+                    vec![("value", self::string(&string.value))],
+                );
+
+                with_attribute(
+                    with_attribute(node, "kind", kind.into()),
+                    "rawValue",
+                    self.text(string.span).into(),
+                )
+            }
+            Literal::Integer(integer) => self.integer(integer),
+            Literal::Float(float) => with_attribute(
+                self.node(
+                    "Scalar_Float",
+                    &[float.span],
+                    vec![("value", float.to_value().into())],
+                ),
+                "rawValue",
+                string(&float.value),
+            ),
+        }
+    }
+
+    fn integer(&self, integer: &LiteralInteger) -> Value {
+        let raw = string(&integer.value);
+        let kind = integer.radix();
+
+        let node = match integer.to_value() {
+            IntegerValue::Integer(value) => {
+                self.node("Scalar_Int", &[integer.span], vec![("value", value.into())])
+            }
+            // integers that overflow are floats in PHP.
+            IntegerValue::Overflowed(value) => self.node(
+                "Scalar_Float",
+                &[integer.span],
+                vec![("value", value.into())],
+            ),
+        };
+
+        with_attribute(with_attribute(node, "rawValue", raw), "kind", kind.into())
+    }
+
+    fn string_parts(&self, parts: &[StringPart]) -> Value {
+        Value::Array(
+            parts
+                .iter()
+                .map(|part| match part {
+                    StringPart::Literal(literal) => self.node(
+                        "InterpolatedStringPart",
+                        &[literal.span],
+                        vec![("value", string(&literal.value))],
+                    ),
+                    StringPart::Expression(expression) => self.expression(&expression.expression),
+                })
+                .collect(),
+        )
+    }
+
+    fn array_items(&self, items: &[ArrayItem]) -> Value {
+        Value::Array(
+            items
+                .iter()
+                .map(|item| match item {
+                    ArrayItem::Skipped => Value::Null,
+                    ArrayItem::Value { value } => self.array_item(None, value, false, false),
+                    ArrayItem::ReferencedValue { value, .. } => {
+                        self.array_item(None, value, true, false)
+                    }
+                    ArrayItem::SpreadValue { value, .. } => {
+                        self.array_item(None, value, false, true)
+                    }
+                    ArrayItem::KeyValue { key, value, .. } => {
+                        self.array_item(Some(key), value, false, false)
+                    }
+                    ArrayItem::ReferencedKeyValue { key, value, .. } => {
+                        self.array_item(Some(key), value, true, false)
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    fn array_item(
+        &self,
+        key: Option<&Expression>,
+        value: &Expression,
+        by_ref: bool,
+        unpack: bool,
+    ) -> Value {
+        self.node(
+            "ArrayItem",
+            &[],
+            vec![
+                ("key", self.optional_expression(key)),
+                ("value", self.expression(value)),
+                ("byRef", by_ref.into()),
+                ("unpack", unpack.into()),
+            ],
+        )
+    }
+
+    fn arguments(&self, arguments: &ArgumentList) -> Value {
+        Value::Array(
+            arguments
+                .arguments
+                .iter()
+                .map(|argument| {
+                    let (name, ellipsis, value) = match argument {
+                        Argument::Positional(argument) => {
+                            (Value::Null, argument.ellipsis, &argument.value)
+                        }
+                        Argument::Named(argument) => (
+                            self.identifier(&argument.name),
+                            argument.ellipsis,
+                            &argument.value,
+                        ),
+                    };
+
+                    self.node(
+                        "Arg",
+                        &ellipsis.into_iter().collect::<Vec<_>>(),
+                        vec![
+                            ("name", name),
+                            ("value", self.expression(value)),
+                            ("byRef", false.into()),
+                            ("unpack", ellipsis.is_some().into()),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    // the values of the arguments of `isset()` and `unset()`, which nikic does not wrap in `Arg` nodes.
+    fn argument_values(&self, arguments: &ArgumentList) -> Value {
+        Value::Array(
+            arguments
+                .arguments
+                .iter()
+                .map(|argument| match argument {
+                    Argument::Positional(argument) => self.expression(&argument.value),
+                    Argument::Named(argument) => self.expression(&argument.value),
+                })
+                .collect(),
+        )
+    }
+
+    fn single_argument(&self, argument: &SingleArgument) -> Value {
+        match &argument.argument {
+            Argument::Positional(argument) => self.expression(&argument.value),
+            Argument::Named(argument) => self.expression(&argument.value),
+        }
+    }
+
+    fn placeholder(&self, placeholder: &ArgumentPlaceholder) -> Value {
+        Value::Array(vec![self.node(
+            "VariadicPlaceholder",
+            &[placeholder.ellipsis],
+            vec![],
+        )])
+    }
+
+    fn level(&self, level: Option<&Level>) -> Value {
+        match level {
+            None => Value::Null,
+            Some(Level::Literal(integer)) => self.integer(integer),
+            Some(Level::Parenthesized { level, .. }) => self.level(Some(level)),
+        }
+    }
+
+    fn variable(&self, variable: &Variable) -> Value {
+        match variable {
+            Variable::SimpleVariable(variable) => self.simple_variable(variable),
+            Variable::VariableVariable(variable) => self.node(
+                "Expr_Variable",
+                &[variable.span],
+                vec![("name", self.variable(&variable.variable))],
+            ),
+            Variable::BracedVariableVariable(variable) => self.node(
+                "Expr_Variable",
+                &[variable.start, variable.end],
+                vec![("name", self.expression(&variable.variable))],
+            ),
+        }
+    }
+
+    fn simple_variable(&self, variable: &SimpleVariable) -> Value {
+        self.node(
+            "Expr_Variable",
+            &[variable.span],
+            vec![("name", variable_name(&variable.name))],
+        )
+    }
+
+    fn identifier(&self, identifier: &SimpleIdentifier) -> Value {
+        self.node(
+            "Identifier",
+            &[identifier.span],
+            vec![("name", string(&identifier.value))],
+        )
+    }
+
+    fn identifier_or_expression(&self, identifier: &Identifier) -> Value {
+        match identifier {
+            Identifier::SimpleIdentifier(identifier) => self.identifier(identifier),
+            Identifier::DynamicIdentifier(identifier) => self.expression(&identifier.expr),
+        }
+    }
+
+    // the name of a method or property, which is an `Identifier` when it is not dynamic.
+    fn member_name(&self, expression: &Expression) -> Value {
+        match expression {
+            Expression::Identifier(identifier) => self.identifier_or_expression(identifier),
+            expression => self.expression(expression),
+        }
+    }
+
+    fn name(&self, identifier: &SimpleIdentifier) -> Value {
+        let value = &identifier.value;
+        let (node_type, name) = if let Some(name) = value.strip_prefix(b"\\") {
+            ("Name_FullyQualified", name)
+        } else if value.len() > 10 && value[..10].eq_ignore_ascii_case(b"namespace\\") {
+            ("Name_Relative", &value[10..])
+        } else {
+            ("Name", &value[..])
+        };
+
+        self.node(
+            node_type,
+            &[identifier.span],
+            vec![("name", String::from_utf8_lossy(name).into())],
+        )
+    }
+
+    fn names<'b>(&self, identifiers: impl Iterator<Item = &'b SimpleIdentifier>) -> Value {
+        Value::Array(
+            identifiers
+                .map(|identifier| self.name(identifier))
+                .collect(),
+        )
+    }
+
+    // names in `use` statements are always relative to the global namespace.
+    fn use_name(&self, identifier: &SimpleIdentifier) -> Value {
+        let name = identifier
+            .value
+            .strip_prefix(b"\\")
+            .unwrap_or(&identifier.value);
+
+        self.node(
+            "Name",
+            &[identifier.span],
+            vec![("name", String::from_utf8_lossy(name).into())],
+        )
+    }
+
+    fn uses(&self, uses: &[Use]) -> Value {
+        Value::Array(
+            uses.iter()
+                .map(|r#use| {
+                    self.node(
+                        "UseItem",
+                        &[],
+                        vec![
+                            ("type", use_type(r#use.kind.as_ref()).into()),
+                            ("name", self.use_name(&r#use.name)),
+                            (
+                                "alias",
+                                r#use
+                                    .alias
+                                    .as_ref()
+                                    .map(|alias| self.identifier(alias))
+                                    .unwrap_or(Value::Null),
+                            ),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn function_name(&self, target: &Expression) -> Value {
+        match target {
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                self.name(identifier)
+            }
+            target => self.expression(target),
+        }
+    }
+
+    fn class_reference(&self, target: &Expression) -> Value {
+        match target {
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                self.name(identifier)
+            }
+            target => self.expression(target),
+        }
+    }
+
+    fn data_type(&self, data_type: &Type) -> Value {
+        match data_type {
+            Type::Named(span, name) => self.name(&SimpleIdentifier {
+                span: *span,
+                value: name.clone(),
+            }),
+            Type::Nullable(span, inner) => self.node(
+                "NullableType",
+                &[*span],
+                vec![("type", self.data_type(inner))],
+            ),
+            Type::Union(types) => self.node(
+                "UnionType",
+                &[],
+                vec![(
+                    "types",
+                    types.iter().map(|inner| self.data_type(inner)).collect(),
+                )],
+            ),
+            Type::Intersection(types) => self.node(
+                "IntersectionType",
+                &[],
+                vec![(
+                    "types",
+                    types.iter().map(|inner| self.data_type(inner)).collect(),
+                )],
+            ),
+            Type::SelfReference(span) | Type::ParentReference(span) => {
+                self.node("Name", &[*span], vec![("name", self.text(*span).into())])
+            }
+            Type::Void(span)
+            | Type::Null(span)
+            | Type::True(span)
+            | Type::False(span)
+            | Type::Never(span)
+            | Type::Float(span)
+            | Type::Boolean(span)
+            | Type::Integer(span)
+            | Type::String(span)
+            | Type::Array(span)
+            | Type::Object(span)
+            | Type::Mixed(span)
+            | Type::Callable(span)
+            | Type::Iterable(span)
+            | Type::StaticReference(span) => self.node(
+                "Identifier",
+                &[*span],
+                vec![("name", self.text(*span).into())],
+            ),
+        }
+    }
+
+    fn optional_type(&self, data_type: Option<&Type>) -> Value {
+        data_type
+            .map(|data_type| self.data_type(data_type))
+            .unwrap_or(Value::Null)
+    }
+
+    fn return_type(&self, return_type: Option<&ReturnType>) -> Value {
+        self.optional_type(return_type.map(|return_type| &return_type.data_type))
+    }
+
+    fn parameters(&self, parameters: &FunctionParameterList) -> Value {
+        Value::Array(
+            parameters
+                .parameters
+                .inner
+                .iter()
+                .map(|parameter| self.parameter(parameter))
+                .collect(),
+        )
+    }
+
+    fn parameter(&self, parameter: &FunctionParameter) -> Value {
+        self.node(
+            "Param",
+            &[],
+            vec![
+                ("attrGroups", self.attribute_groups(&parameter.attributes)),
+                ("flags", 0.into()),
+                ("type", self.optional_type(parameter.data_type.as_ref())),
+                ("byRef", parameter.ampersand.is_some().into()),
+                ("variadic", parameter.ellipsis.is_some().into()),
+                ("var", self.simple_variable(&parameter.name)),
+                (
+                    "default",
+                    self.optional_expression(parameter.default.as_ref()),
+                ),
+            ],
+        )
+    }
+
+    fn attribute_groups(&self, groups: &[AttributeGroup]) -> Value {
+        Value::Array(
+            groups
+                .iter()
+                .map(|group| {
+                    self.node(
+                        "AttributeGroup",
+                        &[group.start, group.end],
+                        vec![(
+                            "attrs",
+                            group
+                                .members
+                                .iter()
+                                .map(|attribute| {
+                                    self.node(
+                                        "Attribute",
+                                        &[attribute.start, attribute.end],
+                                        vec![
+                                            ("name", self.name(&attribute.name)),
+                                            (
+                                                "args",
+                                                attribute
+                                                    .arguments
+                                                    .as_ref()
+                                                    .map(|arguments| self.arguments(arguments))
+                                                    .unwrap_or_else(|| Value::Array(vec![])),
+                                            ),
+                                        ],
+                                    )
+                                })
+                                .collect(),
+                        )],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn constants(&self, entries: &[ConstantEntry]) -> Value {
+        Value::Array(
+            entries
+                .iter()
+                .map(|entry| {
+                    self.node(
+                        "Const",
+                        &[],
+                        vec![
+                            ("name", self.identifier(&entry.name)),
+                            ("value", self.expression(&entry.value)),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn extends(&self, extends: Option<&ClassExtends>) -> Value {
+        extends
+            .map(|extends| self.name(&extends.parent))
+            .unwrap_or(Value::Null)
+    }
+
+    fn implements(&self, implements: Option<&ClassImplements>) -> Value {
+        self.names(
+            implements
+                .iter()
+                .flat_map(|implements| implements.interfaces.inner.iter()),
+        )
+    }
+
+    fn anonymous_class(&self, class: &AnonymousClassExpression) -> Value {
+        self.node(
+            "Stmt_Class",
+            &[class.class, class.body.right_brace],
+            vec![
+                ("attrGroups", self.attribute_groups(&class.attributes)),
+                ("flags", class_flags(&class.modifiers.modifiers).into()),
+                ("name", Value::Null),
+                ("extends", self.extends(class.extends.as_ref())),
+                ("implements", self.implements(class.implements.as_ref())),
+                (
+                    "stmts",
+                    class
+                        .body
+                        .members
+                        .iter()
+                        .map(|member| match member {
+                            AnonymousClassMember::Constant(constant) => {
+                                self.class_constant(constant)
+                            }
+                            AnonymousClassMember::TraitUsage(usage) => self.trait_usage(usage),
+                            AnonymousClassMember::Property(property) => self.property(
+                                &property.attributes,
+                                property_flags(&property.modifiers.modifiers),
+                                property.r#type.as_ref(),
+                                &property.entries,
+                                property.end,
+                            ),
+                            AnonymousClassMember::VariableProperty(property) => self.property(
+                                &property.attributes,
+                                0,
+                                property.r#type.as_ref(),
+                                &property.entries,
+                                property.end,
+                            ),
+                            AnonymousClassMember::ConcreteMethod(method) => {
+                                self.concrete_method(method)
+                            }
+                            AnonymousClassMember::ConcreteConstructor(constructor) => {
+                                self.concrete_constructor(constructor)
+                            }
+                        })
+                        .collect(),
+                ),
+            ],
+        )
+    }
+
+    fn class_member(&self, member: &ClassMember) -> Value {
+        match member {
+            ClassMember::Constant(constant) => self.class_constant(constant),
+            ClassMember::TraitUsage(usage) => self.trait_usage(usage),
+            ClassMember::Property(property) => self.property(
+                &property.attributes,
+                property_flags(&property.modifiers.modifiers),
+                property.r#type.as_ref(),
+                &property.entries,
+                property.end,
+            ),
+            ClassMember::VariableProperty(property) => self.property(
+                &property.attributes,
+                0,
+                property.r#type.as_ref(),
+                &property.entries,
+                property.end,
+            ),
+            ClassMember::AbstractMethod(method) => self.abstract_method(method),
+            ClassMember::AbstractConstructor(constructor) => self.abstract_constructor(constructor),
+            ClassMember::ConcreteMethod(method) => self.concrete_method(method),
+            ClassMember::ConcreteConstructor(constructor) => self.concrete_constructor(constructor),
+        }
+    }
+
+    fn class_constant(&self, constant: &ClassishConstant) -> Value {
+        let flags = constant
+            .modifiers
+            .modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                ConstantModifier::Final(_) => MODIFIER_FINAL,
+                ConstantModifier::Public(_) => MODIFIER_PUBLIC,
+                ConstantModifier::Protected(_) => MODIFIER_PROTECTED,
+                ConstantModifier::Private(_) => MODIFIER_PRIVATE,
+            })
+            .fold(0, |flags, flag| flags | flag);
+
+        self.node(
+            "Stmt_ClassConst",
+            &[constant.r#const, constant.semicolon],
+            vec![
+                ("attrGroups", self.attribute_groups(&constant.attributes)),
+                ("flags", flags.into()),
+                ("consts", self.constants(&constant.entries)),
+                ("type", Value::Null),
+            ],
+        )
+    }
+
+    fn trait_usage(&self, usage: &TraitUsage) -> Value {
+        let optional_name = |name: &Option<SimpleIdentifier>| {
+            name.as_ref()
+                .map(|name| self.name(name))
+                .unwrap_or(Value::Null)
+        };
+
+        let adaptations = usage
+            .adaptations
+            .iter()
+            .map(|adaptation| match adaptation {
+                TraitUsageAdaptation::Alias {
+                    r#trait,
+                    method,
+                    alias,
+                    visibility,
+                } => self.node(
+                    "Stmt_TraitUseAdaptation_Alias",
+                    &[],
+                    vec![
+                        ("trait", optional_name(r#trait)),
+                        ("method", self.identifier(method)),
+                        (
+                            "newModifier",
+                            visibility
+                                .as_ref()
+                                .map(|visibility| visibility_flag(visibility).into())
+                                .unwrap_or(Value::Null),
+                        ),
+                        ("newName", self.identifier(alias)),
+                    ],
+                ),
+                TraitUsageAdaptation::Visibility {
+                    r#trait,
+                    method,
+                    visibility,
+                } => self.node(
+                    "Stmt_TraitUseAdaptation_Alias",
+                    &[],
+                    vec![
+                        ("trait", optional_name(r#trait)),
+                        ("method", self.identifier(method)),
+                        ("newModifier", visibility_flag(visibility).into()),
+                        ("newName", Value::Null),
+                    ],
+                ),
+                TraitUsageAdaptation::Precedence {
+                    r#trait,
+                    method,
+                    insteadof,
+                } => self.node(
+                    "Stmt_TraitUseAdaptation_Precedence",
+                    &[],
+                    vec![
+                        ("trait", optional_name(r#trait)),
+                        ("method", self.identifier(method)),
+                        ("insteadof", self.names(insteadof.iter())),
+                    ],
+                ),
+            })
+            .collect();
+
+        self.node(
+            "Stmt_TraitUse",
+            &[usage.r#use],
+            vec![
+                ("traits", self.names(usage.traits.iter())),
+                ("adaptations", Value::Array(adaptations)),
+            ],
+        )
+    }
+
+    fn property(
+        &self,
+        attributes: &[AttributeGroup],
+        flags: u64,
+        data_type: Option<&Type>,
+        entries: &[PropertyEntry],
+        end: Span,
+    ) -> Value {
+        let props = entries
+            .iter()
+            .map(|entry| {
+                let (variable, default) = match entry {
+                    PropertyEntry::Uninitialized { variable } => (variable, Value::Null),
+                    PropertyEntry::Initialized {
+                        variable, value, ..
+                    } => (variable, self.expression(value)),
+                };
+
+                self.node(
+                    "PropertyItem",
+                    &[],
+                    vec![
+                        (
+                            "name",
+                            self.node(
+                                "VarLikeIdentifier",
+                                &[variable.span],
+                                vec![("name", variable_name(&variable.name))],
+                            ),
+                        ),
+                        ("default", default),
+                    ],
+                )
+            })
+            .collect();
+
+        self.node(
+            "Stmt_Property",
+            &[end],
+            vec![
+                ("attrGroups", self.attribute_groups(attributes)),
+                ("flags", flags.into()),
+                ("type", self.optional_type(data_type)),
+                ("props", Value::Array(props)),
+            ],
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn method(
+        &self,
+        attributes: &[AttributeGroup],
+        modifiers: &MethodModifierGroup,
+        spans: &[Span],
+        by_ref: bool,
+        name: &SimpleIdentifier,
+        params: Value,
+        return_type: Option<&ReturnType>,
+        stmts: Value,
+    ) -> Value {
+        let flags = modifiers
+            .modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                MethodModifier::Final(_) => MODIFIER_FINAL,
+                MethodModifier::Static(_) => MODIFIER_STATIC,
+                MethodModifier::Abstract(_) => MODIFIER_ABSTRACT,
+                MethodModifier::Public(_) => MODIFIER_PUBLIC,
+                MethodModifier::Protected(_) => MODIFIER_PROTECTED,
+                MethodModifier::Private(_) => MODIFIER_PRIVATE,
+            })
+            .fold(0, |flags, flag| flags | flag);
+
+        self.node(
+            "Stmt_ClassMethod",
+            spans,
+            vec![
+                ("attrGroups", self.attribute_groups(attributes)),
+                ("flags", flags.into()),
+                ("byRef", by_ref.into()),
+                ("name", self.identifier(name)),
+                ("params", params),
+                ("returnType", self.return_type(return_type)),
+                ("stmts", stmts),
+            ],
+        )
+    }
+
+    fn abstract_method(&self, method: &AbstractMethod) -> Value {
+        self.method(
+            &method.attributes,
+            &method.modifiers,
+            &[method.function, method.semicolon],
+            method.ampersand.is_some(),
+            &method.name,
+            self.parameters(&method.parameters),
+            method.return_type.as_ref(),
+            Value::Null,
+        )
+    }
+
+    fn concrete_method(&self, method: &ConcreteMethod) -> Value {
+        self.method(
+            &method.attributes,
+            &method.modifiers,
+            &[method.function, method.body.right_brace],
+            method.ampersand.is_some(),
+            &method.name,
+            self.parameters(&method.parameters),
+            method.return_type.as_ref(),
+            self.statements(&method.body.statements),
+        )
+    }
+
+    fn abstract_constructor(&self, constructor: &AbstractConstructor) -> Value {
+        self.method(
+            &constructor.attributes,
+            &constructor.modifiers,
+            &[constructor.function, constructor.semicolon],
+            constructor.ampersand.is_some(),
+            &constructor.name,
+            self.parameters(&constructor.parameters),
+            None,
+            Value::Null,
+        )
+    }
+
+    fn concrete_constructor(&self, constructor: &ConcreteConstructor) -> Value {
+        let params = constructor
+            .parameters
+            .parameters
+            .inner
+            .iter()
+            .map(|parameter| {
+                let flags = parameter
+                    .modifiers
+                    .modifiers
+                    .iter()
+                    .map(|modifier| match modifier {
+                        PromotedPropertyModifier::Public(_) => MODIFIER_PUBLIC,
+                        PromotedPropertyModifier::Protected(_) => MODIFIER_PROTECTED,
+                        PromotedPropertyModifier::Private(_) => MODIFIER_PRIVATE,
+                        PromotedPropertyModifier::Readonly(_) => MODIFIER_READONLY,
+                    })
+                    .fold(0, |flags, flag| flags | flag);
+
+                self.node(
+                    "Param",
+                    &[],
+                    vec![
+                        ("attrGroups", self.attribute_groups(&parameter.attributes)),
+                        ("flags", flags.into()),
+                        ("type", self.optional_type(parameter.data_type.as_ref())),
+                        ("byRef", parameter.ampersand.is_some().into()),
+                        ("variadic", parameter.ellipsis.is_some().into()),
+                        ("var", self.simple_variable(&parameter.name)),
+                        (
+                            "default",
+                            self.optional_expression(parameter.default.as_ref()),
+                        ),
+                    ],
+                )
+            })
+            .collect();
+
+        self.method(
+            &constructor.attributes,
+            &constructor.modifiers,
+            &[constructor.function, constructor.body.right_brace],
+            constructor.ampersand.is_some(),
+            &constructor.name,
+            Value::Array(params),
+            None,
+            self.statements(&constructor.body.statements),
+        )
+    }
+}
+
+// Widen the given range to cover the positions of a converted node, or of every node in an array.
+fn extent(value: &Value, start: &mut Option<usize>, end: &mut Option<usize>) {
+    match value {
+        Value::Object(object) => {
+            let attributes = &object["attributes"];
+            if let (Some(node_start), Some(node_end)) = (
+                attributes["startFilePos"].as_u64(),
+                attributes["endFilePos"].as_u64(),
+            ) {
+                let (node_start, node_end) = (node_start as usize, node_end as usize + 1);
+                *start = Some(start.map_or(node_start, |start| start.min(node_start)));
+                *end = Some(end.map_or(node_end, |end| end.max(node_end)));
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                extent(value, start, end);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn with_attribute(mut node: Value, key: &str, value: Value) -> Value {
+    if let Some(attributes) = node["attributes"].as_object_mut() {
+        attributes.insert(key.to_string(), value);
+    }
+
+    node
+}
+
+fn spans(span: Span, other: Option<Span>) -> Vec<Span> {
+    std::iter::once(span).chain(other).collect()
+}
+
+fn ending_span(ending: &Ending) -> Span {
+    match ending {
+        Ending::Semicolon(span) | Ending::CloseTag(span) => *span,
+    }
+}
+
+fn string(value: &ByteString) -> Value {
+    String::from_utf8_lossy(value).into()
+}
+
+fn variable_name(name: &ByteString) -> Value {
+    String::from_utf8_lossy(name.strip_prefix(b"$").unwrap_or(name)).into()
+}
+
+fn use_type(kind: Option<&UseKind>) -> u64 {
+    match kind {
+        None => 0,
+        Some(UseKind::Normal) => 1,
+        Some(UseKind::Function) => 2,
+        Some(UseKind::Const) => 3,
+    }
+}
+
+fn visibility_flag(visibility: &VisibilityModifier) -> u64 {
+    match visibility {
+        VisibilityModifier::Public(_) => MODIFIER_PUBLIC,
+        VisibilityModifier::Protected(_) => MODIFIER_PROTECTED,
+        VisibilityModifier::Private(_) => MODIFIER_PRIVATE,
+    }
+}
+
+fn class_flags(modifiers: &[ClassModifier]) -> u64 {
+    modifiers
+        .iter()
+        .map(|modifier| match modifier {
+            ClassModifier::Final(_) => MODIFIER_FINAL,
+            ClassModifier::Abstract(_) => MODIFIER_ABSTRACT,
+            ClassModifier::Readonly(_) => MODIFIER_READONLY,
+        })
+        .fold(0, |flags, flag| flags | flag)
+}
+
+fn property_flags(modifiers: &[PropertyModifier]) -> u64 {
+    modifiers
+        .iter()
+        .map(|modifier| match modifier {
+            PropertyModifier::Public(_) => MODIFIER_PUBLIC,
+            PropertyModifier::Protected(_) => MODIFIER_PROTECTED,
+            PropertyModifier::Private(_) => MODIFIER_PRIVATE,
+            PropertyModifier::Static(_) => MODIFIER_STATIC,
+            PropertyModifier::Readonly(_) => MODIFIER_READONLY,
+        })
+        .fold(0, |flags, flag| flags | flag)
+}
@@ -1,24 +1,61 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
 use schemars::JsonSchema;
+use serde::de::MapAccess;
+use serde::de::Visitor;
+use serde::ser::SerializeMap;
 use serde::Deserialize;
 use serde::Serialize;
 
+use std::borrow::Borrow;
+use std::borrow::Cow;
 use std::ops::Deref;
 use std::ops::DerefMut;
 use std::str::from_utf8;
+use std::sync::Arc;
 
 /// A wrapper for Vec<u8> that provides a human-readable Debug impl and
 /// a few other conveniences.
 ///
 /// The Trunk lexer and parser work mainly with byte strings because
 /// valid PHP code is not required to be valid UTF-8.
+///
+/// Clones share the same bytes, which are only copied when one of them is modified, so
+/// the parser can intern repeated literals.
+///
+/// Byte strings are serialized as strings when they are valid UTF-8, and otherwise as a
+/// `{"base64": "..."}` object in human-readable formats, such as JSON, so binary string
+/// literals survive a round-trip. Formats that are not human-readable store the bytes as
+/// they are.
 #[derive(PartialOrd, PartialEq, Eq, Clone, Hash)]
 pub struct ByteString {
-    pub bytes: Vec<u8>,
+    pub bytes: Arc<Vec<u8>>,
 }
 
 impl ByteString {
     pub fn new(bytes: Vec<u8>) -> Self {
-        ByteString { bytes }
+        ByteString {
+            bytes: Arc::new(bytes),
+        }
+    }
+
+    /// The bytes of the string.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The string as UTF-8 text, with any invalid sequences replaced by `U+FFFD`.
+    ///
+    /// Unlike [`Display`](std::fmt::Display), which escapes every byte outside of ASCII,
+    /// valid UTF-8 is kept as it is.
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.bytes)
+    }
+
+    /// Whether both byte strings are backed by the same storage, as is the case for
+    /// clones and interned literals.
+    pub fn shares_storage_with(&self, other: &ByteString) -> bool {
+        Arc::ptr_eq(&self.bytes, &other.bytes)
     }
 }
 
@@ -30,7 +67,7 @@ impl Default for ByteString {
 
 impl std::fmt::Display for ByteString {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for &b in &self.bytes {
+        for &b in self.bytes.iter() {
             match b {
                 0 => write!(f, "\\0")?,
                 b'\n' | b'\r' | b'\t' => write!(f, "{}", b as char)?,
@@ -54,7 +91,7 @@ impl std::str::FromStr for ByteString {
 impl std::fmt::Debug for ByteString {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "\"")?;
-        for &b in &self.bytes {
+        for &b in self.bytes.iter() {
             match b {
                 0 => write!(f, "\\0")?,
                 b'\n' | b'\r' | b'\t' => write!(f, "{}", b.escape_ascii())?,
@@ -72,7 +109,60 @@ impl Serialize for ByteString {
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        if !serializer.is_human_readable() {
+            return serializer.serialize_bytes(&self.bytes);
+        }
+
+        match from_utf8(&self.bytes) {
+            Ok(string) => serializer.serialize_str(string),
+            Err(_) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("base64", &BASE64.encode(&*self.bytes))?;
+                map.end()
+            }
+        }
+    }
+}
+
+struct ByteStringVisitor;
+
+impl<'de> Visitor<'de> for ByteStringVisitor {
+    type Value = ByteString;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a string, bytes or a `{\"base64\": ...}` object")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_string<E: serde::de::Error>(self, value: String) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_byte_buf<E: serde::de::Error>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key != "base64" || bytes.is_some() {
+                return Err(serde::de::Error::unknown_field(&key, &["base64"]));
+            }
+
+            let encoded = map.next_value::<String>()?;
+            bytes = Some(BASE64.decode(encoded).map_err(serde::de::Error::custom)?);
+        }
+
+        bytes
+            .map(ByteString::new)
+            .ok_or_else(|| serde::de::Error::missing_field("base64"))
     }
 }
 
@@ -81,8 +171,11 @@ impl<'de> Deserialize<'de> for ByteString {
     where
         D: serde::Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        Ok(ByteString::new(s.into_bytes()))
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(ByteStringVisitor)
+        } else {
+            deserializer.deserialize_byte_buf(ByteStringVisitor)
+        }
     }
 }
 
@@ -92,10 +185,42 @@ impl JsonSchema for ByteString {
     }
 
     fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
-        schemars::schema::SchemaObject {
-            instance_type: Some(schemars::schema::InstanceType::String.into()),
+        use schemars::schema::InstanceType;
+        use schemars::schema::ObjectValidation;
+        use schemars::schema::Schema;
+        use schemars::schema::SchemaObject;
+        use schemars::schema::SubschemaValidation;
+
+        let string = SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
             format: Some("byte-string".to_string()),
             ..Default::default()
+        };
+        let base64 = SchemaObject {
+            instance_type: Some(InstanceType::Object.into()),
+            object: Some(Box::new(ObjectValidation {
+                required: ["base64".to_string()].into(),
+                properties: [(
+                    "base64".to_string(),
+                    Schema::Object(SchemaObject {
+                        instance_type: Some(InstanceType::String.into()),
+                        format: Some("byte".to_string()),
+                        ..Default::default()
+                    }),
+                )]
+                .into(),
+                additional_properties: Some(Box::new(Schema::Bool(false))),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                one_of: Some(vec![string.into(), base64.into()]),
+                ..Default::default()
+            })),
+            ..Default::default()
         }
         .into()
     }
@@ -103,13 +228,13 @@ impl JsonSchema for ByteString {
 
 impl<const N: usize> PartialEq<&[u8; N]> for ByteString {
     fn eq(&self, other: &&[u8; N]) -> bool {
-        &self.bytes == other
+        *self.bytes == other[..]
     }
 }
 
 impl<const N: usize> PartialEq<&[u8; N]> for &ByteString {
     fn eq(&self, other: &&[u8; N]) -> bool {
-        &self.bytes == other
+        *self.bytes == other[..]
     }
 }
 
@@ -149,9 +274,10 @@ impl From<String> for ByteString {
     }
 }
 
+/// Invalid UTF-8 is replaced, as with [`ByteString::to_string_lossy`].
 impl From<ByteString> for String {
     fn from(bytes: ByteString) -> Self {
-        String::from(from_utf8(&bytes.bytes).unwrap())
+        bytes.to_string_lossy().into_owned()
     }
 }
 
@@ -165,7 +291,13 @@ impl Deref for ByteString {
 
 impl DerefMut for ByteString {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.bytes
+        Arc::make_mut(&mut self.bytes)
+    }
+}
+
+impl Borrow<[u8]> for ByteString {
+    fn borrow(&self) -> &[u8] {
+        &self.bytes
     }
 }
 
@@ -185,4 +317,29 @@ mod tests {
             r#""\x01\x10\x7f\xff""#
         );
     }
+
+    #[test]
+    fn test_byte_string_serde() {
+        let text = ByteString::from("caf\u{e9}");
+        let json = serde_json::to_string(&text).unwrap();
+        assert_eq!(json, "\"caf\u{e9}\"");
+        assert_eq!(serde_json::from_str::<ByteString>(&json).unwrap(), text);
+
+        let binary = ByteString::from(b"\x00\xff\xfe");
+        let json = serde_json::to_string(&binary).unwrap();
+        assert_eq!(json, r#"{"base64":"AP/+"}"#);
+        assert_eq!(serde_json::from_str::<ByteString>(&json).unwrap(), binary);
+
+        assert!(serde_json::from_str::<ByteString>(r#"{"base64":"!"}"#).is_err());
+        assert!(serde_json::from_str::<ByteString>(r#"{"hex":"00"}"#).is_err());
+    }
+
+    #[test]
+    fn test_byte_string_lossy() {
+        let bytes = ByteString::from(b"caf\xc3\xa9 \xff");
+
+        assert_eq!(bytes.as_bytes(), b"caf\xc3\xa9 \xff");
+        assert_eq!(bytes.to_string_lossy(), "caf\u{e9} \u{fffd}");
+        assert_eq!(String::from(bytes), "caf\u{e9} \u{fffd}");
+    }
 }
@@ -4,7 +4,7 @@ use crate::lexer::token::Span;
 
 pub type SyntaxResult<T> = Result<T, SyntaxError>;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Clone)]
 pub enum SyntaxError {
     UnexpectedEndOfFile(Span),
     UnexpectedError(Span),
@@ -35,69 +35,92 @@ impl SyntaxError {
             Self::UnrecognisedToken(_, span) => *span,
         }
     }
+
+    pub(crate) fn span_mut(&mut self) -> &mut Span {
+        match self {
+            Self::UnexpectedEndOfFile(span) => span,
+            Self::UnexpectedError(span) => span,
+            Self::UnexpectedCharacter(_, span) => span,
+            Self::InvalidHaltCompiler(span) => span,
+            Self::InvalidOctalEscape(span) => span,
+            Self::InvalidOctalLiteral(span) => span,
+            Self::InvalidUnicodeEscape(span) => span,
+            Self::UnpredictableState(span) => span,
+            Self::InvalidDocIndentation(span) => span,
+            Self::InvalidDocBodyIndentationLevel(_, span) => span,
+            Self::UnrecognisedToken(_, span) => span,
+        }
+    }
 }
 
-impl Display for SyntaxError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl SyntaxError {
+    /// The description of the error, without its location.
+    pub fn message(&self) -> String {
         match self {
-            Self::UnexpectedEndOfFile(span) => write!(
-                f,
-                "Syntax Error: unexpected end of file on line {} column {}",
-                span.line, span.column
+            Self::UnexpectedEndOfFile(_) => "unexpected end of file".to_string(),
+            Self::UnexpectedError(_) => "unexpected error".to_string(),
+            Self::UnexpectedCharacter(char, _) => {
+                format!("unexpected character `{:?}`", *char as char)
+            }
+            Self::InvalidHaltCompiler(_) => "invalid halt compiler".to_string(),
+            Self::InvalidOctalEscape(_) => "invalid octal escape".to_string(),
+            Self::InvalidOctalLiteral(_) => "invalid octal literal".to_string(),
+            Self::InvalidUnicodeEscape(_) => "invalid unicode escape".to_string(),
+            Self::UnpredictableState(_) => "reached an unpredictable state".to_string(),
+            Self::InvalidDocIndentation(_) => {
+                "invalid indentation - cannot use tabs and spaces".to_string()
+            }
+            Self::InvalidDocBodyIndentationLevel(expected, _) => format!(
+                "invalid body indentation level - expecting an indentation level of at least {}",
+                expected
             ),
-            Self::UnexpectedError(span) => write!(
-                f,
-                "Syntax Error: unexpected error on line {} column {}",
-                span.line, span.column
-            ),
-            Self::UnexpectedCharacter(char, span) => write!(
-                f,
-                "Syntax Error: unexpected character `{:?}` on line {} column {}",
-                *char as char, span.line, span.column
-            ),
-            Self::InvalidHaltCompiler(span) => write!(
-                f,
-                "Syntax Error: invalid halt compiler on line {} column {}",
-                span.line, span.column
-            ),
-            Self::InvalidOctalEscape(span) => write!(
-                f,
-                "Syntax Error: invalid octal escape on line {} column {}",
-                span.line, span.column
-            ),
-            Self::InvalidOctalLiteral(span) => write!(
-                f,
-                "Syntax Error: invalid octal literal on line {} column {}",
-                span.line, span.column
-            ),
-            Self::InvalidUnicodeEscape(span) => write!(
-                f,
-                "Syntax Error: invalid unicode escape on line {} column {}",
-                span.line, span.column
-            ),
-            Self::UnpredictableState(span) => write!(
-                f,
-                "Syntax Error: Reached an unpredictable state on line {} column {}",
-                span.line, span.column
+            Self::UnrecognisedToken(token, _) => {
+                format!("unrecognised token `{:?}`", *token as char)
+            }
+        }
+    }
+
+    /// The message of the label pointing at the error in the source.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::UnexpectedCharacter(_, _) | Self::UnrecognisedToken(_, _) => "try removing this",
+            Self::InvalidDocIndentation(_) | Self::InvalidDocBodyIndentationLevel(_, _) => {
+                "indented here"
+            }
+            _ => "here",
+        }
+    }
+
+    /// An explanation of how to fix the error, if there is one.
+    pub fn note(&self) -> Option<&'static str> {
+        match self {
+            Self::InvalidOctalEscape(_) => {
+                Some("octal escapes must not be greater than `\\377`")
+            }
+            Self::InvalidUnicodeEscape(_) => Some(
+                "unicode escapes are written as `\\u{...}` with a hexadecimal codepoint, e.g. `\\u{1F418}`",
             ),
-            Self::InvalidDocIndentation(span) => write!(
-                f,
-                "Syntax Error: Invalid indentation - cannot use tabs and spaces on line {}",
-                span.line
+            Self::InvalidDocIndentation(_) => Some(
+                "the lines of a heredoc or nowdoc must be indented with either tabs or spaces, but not both",
             ),
-            Self::InvalidDocBodyIndentationLevel(expected, span) => write!(
-                f,
-                "Syntax Error: Invalid body indentation level - expecting an indentation level of at least {} on line {}",
-                expected,
-                span.line
+            Self::InvalidDocBodyIndentationLevel(_, _) => Some(
+                "the lines of a heredoc or nowdoc must be indented at least as much as its closing marker",
             ),
-            Self::UnrecognisedToken(token, span) => write!(
-                f,
-                "Syntax Error: Unrecognised token {} on line {} column {}",
-                token,
-                span.line,
-                span.column
-            )
+            _ => None,
         }
     }
 }
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let span = self.span();
+
+        write!(
+            f,
+            "Syntax Error: {} on line {} column {}",
+            self.message(),
+            span.line,
+            span.column
+        )
+    }
+}
@@ -0,0 +1,237 @@
+use std::fmt::Display;
+
+use crate::lexer::token::Span;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+// Characters that are not rendered, or that change the order in which the surrounding
+// text is rendered, making code read differently than it runs.
+const INVISIBLE_CHARACTERS: [char; 14] = [
+    '\u{200b}', // zero width space
+    '\u{200c}', // zero width non-joiner
+    '\u{200d}', // zero width joiner
+    '\u{2060}', // word joiner
+    '\u{200e}', // left-to-right mark
+    '\u{200f}', // right-to-left mark
+    '\u{061c}', // arabic letter mark
+    '\u{202a}', // left-to-right embedding
+    '\u{202b}', // right-to-left embedding
+    '\u{202c}', // pop directional formatting
+    '\u{202d}', // left-to-right override
+    '\u{202e}', // right-to-left override
+    '\u{2066}', // left-to-right isolate
+    '\u{2069}', // pop directional isolate
+];
+
+/// A valid, but suspicious, part of the source code, found by [`check`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum HygieneWarning {
+    MixedIndentation(Span),
+    InvisibleCharacter(char, Span),
+    MisplacedByteOrderMark(Span),
+}
+
+impl HygieneWarning {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::MixedIndentation(span) => *span,
+            Self::InvisibleCharacter(_, span) => *span,
+            Self::MisplacedByteOrderMark(span) => *span,
+        }
+    }
+}
+
+impl Display for HygieneWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MixedIndentation(span) => write!(
+                f,
+                "Warning: statement indented with both tabs and spaces on line {} column {}",
+                span.line, span.column
+            ),
+            Self::InvisibleCharacter(char, span) => write!(
+                f,
+                "Warning: invisible character `U+{:04X}` on line {} column {}",
+                *char as u32, span.line, span.column
+            ),
+            Self::MisplacedByteOrderMark(span) => write!(
+                f,
+                "Warning: byte order mark after the start of the file on line {} column {}",
+                span.line, span.column
+            ),
+        }
+    }
+}
+
+/// Check the source code and its tokens for problems that are not syntax errors,
+/// but usually indicate a mistake or an attempt to disguise what the code does:
+///
+/// - a statement whose lines are indented with a mix of tabs and spaces.
+/// - zero-width and bidirectional control characters, anywhere in the file.
+/// - a byte order mark anywhere but at the very start of the file.
+///
+/// The checks are heuristic, and are not run by the lexer itself.
+pub fn check(source: &[u8], tokens: &[Token]) -> Vec<HygieneWarning> {
+    let mut warnings = characters(source);
+    warnings.extend(indentation(source, tokens));
+    warnings.sort_by_key(|warning| warning.span().position);
+
+    warnings
+}
+
+fn characters(source: &[u8]) -> Vec<HygieneWarning> {
+    let mut warnings = vec![];
+    let mut line = 1;
+    let mut line_start = 0;
+
+    for (position, byte) in source.iter().enumerate() {
+        if *byte == b'\n' {
+            line += 1;
+            line_start = position + 1;
+            continue;
+        }
+
+        // all of the characters checked for start with one of these bytes in UTF-8.
+        if !matches!(byte, 0xd8 | 0xe2 | 0xef) {
+            continue;
+        }
+
+        let starts_with = |char: &char| {
+            let mut buffer = [0; 4];
+            source[position..].starts_with(char.encode_utf8(&mut buffer).as_bytes())
+        };
+        let span = Span::new(line, position - line_start + 1, position);
+
+        if starts_with(&BYTE_ORDER_MARK) {
+            if position > 0 {
+                warnings.push(HygieneWarning::MisplacedByteOrderMark(span));
+            }
+        } else if let Some(char) = INVISIBLE_CHARACTERS.iter().find(|char| starts_with(char)) {
+            warnings.push(HygieneWarning::InvisibleCharacter(*char, span));
+        }
+    }
+
+    warnings
+}
+
+fn indentation(source: &[u8], tokens: &[Token]) -> Vec<HygieneWarning> {
+    let mut warnings = vec![];
+    // the first indentation character of the current statement, and whether the
+    // statement was already reported.
+    let mut statement: Option<u8> = None;
+    let mut reported = false;
+    let mut in_doc_string = false;
+    // the start of the line of the current token, found by scanning the source up to
+    // each token once, rather than back from every token to the previous newline.
+    let mut line_start = 0;
+    let mut scanned = 0;
+
+    for token in tokens {
+        let position = token.span.position.max(scanned);
+        if let Some(newline) = source[scanned..position]
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+        {
+            line_start = scanned + newline + 1;
+        }
+        scanned = position;
+
+        match token.kind {
+            TokenKind::StartDocString(_) => in_doc_string = true,
+            TokenKind::EndDocString(..) => in_doc_string = false,
+            _ => {}
+        }
+
+        if in_doc_string || token.kind == TokenKind::InlineHtml {
+            continue;
+        }
+
+        let indentation = &source[line_start..position];
+
+        // only tokens that start a line tell how the line is indented.
+        if !indentation.is_empty() && indentation.iter().all(|byte| matches!(byte, b' ' | b'\t')) {
+            let first = indentation[0];
+            let mixed = match statement {
+                Some(character) => character != first,
+                None => {
+                    statement = Some(first);
+                    false
+                }
+            };
+
+            // spaces followed by tabs are mixed on their own, unlike tabs followed by
+            // spaces used for alignment.
+            if !reported && (mixed || (first == b' ' && indentation.contains(&b'\t'))) {
+                reported = true;
+                warnings.push(HygieneWarning::MixedIndentation(Span::new(
+                    token.span.line,
+                    1,
+                    line_start,
+                )));
+            }
+        }
+
+        if matches!(
+            token.kind,
+            TokenKind::SemiColon
+                | TokenKind::LeftBrace
+                | TokenKind::RightBrace
+                | TokenKind::OpenTag(_)
+                | TokenKind::CloseTag
+        ) {
+            statement = None;
+            reported = false;
+        }
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Lexer;
+
+    fn warnings(source: &str) -> Vec<HygieneWarning> {
+        let tokens = Lexer::new().tokenize(source).unwrap();
+
+        check(source.as_bytes(), &tokens)
+    }
+
+    #[test]
+    fn test_mixed_indentation() {
+        assert_eq!(
+            warnings("<?php\nif ($a) {\n\t$b = foo(\n        1,\n\t);\n}\n"),
+            vec![HygieneWarning::MixedIndentation(Span::new(4, 1, 27))]
+        );
+        assert_eq!(
+            warnings("<?php\nif ($a) {\n\t$b = foo(\n\t\t1,\n\t);\n}\n"),
+            vec![]
+        );
+        // each statement may use its own indentation.
+        assert_eq!(warnings("<?php\n{\n\t$a = 1;\n    $b = 2;\n}\n"), vec![]);
+        assert_eq!(
+            warnings("<?php\n{\n \t$a = 1;\n}\n"),
+            vec![HygieneWarning::MixedIndentation(Span::new(3, 1, 8))]
+        );
+        assert_eq!(warnings("<?php\n$a = <<<EOT\n\t  text\n  EOT;\n"), vec![]);
+    }
+
+    #[test]
+    fn test_invisible_characters() {
+        assert_eq!(
+            warnings("<?php\n$a = 'user\u{202e}';\n"),
+            vec![HygieneWarning::InvisibleCharacter(
+                '\u{202e}',
+                Span::new(2, 11, 16)
+            )]
+        );
+        assert_eq!(warnings("\u{feff}<?php\n$a = 1;\n"), vec![]);
+        assert_eq!(
+            warnings("<?php\n$a = '\u{feff}';\n"),
+            vec![HygieneWarning::MisplacedByteOrderMark(Span::new(2, 7, 12))]
+        );
+    }
+}
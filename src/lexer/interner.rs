@@ -0,0 +1,128 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+
+/// A set of strings shared between threads, so that identical names found in different
+/// files share the same storage.
+///
+/// ```rust
+/// use php_parser_rs::lexer::interner::Interner;
+///
+/// let interner = Interner::new();
+///
+/// let first = interner.intern(&"Foo".into());
+/// let second = interner.intern(&"Foo".into());
+///
+/// assert!(std::sync::Arc::ptr_eq(&first.bytes, &second.bytes));
+/// ```
+///
+/// The strings are split between several shards, each with its own lock, so that threads
+/// interning different strings rarely wait on each other.
+#[derive(Debug, Default)]
+pub struct Interner {
+    shards: [Mutex<HashSet<ByteString>>; SHARDS],
+}
+
+const SHARDS: usize = 16;
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The interned string equal to the given one, which is added if there is none.
+    pub fn intern(&self, value: &ByteString) -> ByteString {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+
+        let mut strings = lock(&self.shards[hasher.finish() as usize % SHARDS]);
+        if let Some(interned) = strings.get(value) {
+            return interned.clone();
+        }
+
+        strings.insert(value.clone());
+
+        value.clone()
+    }
+
+    /// Replace the value of every identifier and variable token by the interned one.
+    ///
+    /// Only the shard of each token is locked, and only while that token is interned.
+    pub fn intern_tokens(&self, tokens: &mut [Token]) {
+        for token in tokens {
+            if matches!(
+                token.kind,
+                TokenKind::Identifier
+                    | TokenKind::QualifiedIdentifier
+                    | TokenKind::FullyQualifiedIdentifier
+                    | TokenKind::Variable
+            ) {
+                token.value = self.intern(&token.value);
+            }
+        }
+    }
+
+    /// The number of distinct strings interned.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| lock(shard).len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| lock(shard).is_empty())
+    }
+}
+
+fn lock(shard: &Mutex<HashSet<ByteString>>) -> MutexGuard<'_, HashSet<ByteString>> {
+    // the set is never left in an inconsistent state, even if a thread panics.
+    shard
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use super::Interner;
+    use crate::lexer::Lexer;
+
+    #[test]
+    fn test_intern_tokens_from_threads() {
+        let interner = Interner::new();
+
+        let tokens = thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut tokens = Lexer::new()
+                            .tokenize(b"<?php $a = foo($b, Bar\\baz);")
+                            .unwrap();
+                        interner.intern_tokens(&mut tokens);
+                        tokens
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(interner.len(), 4);
+        for (first, second) in tokens[0].iter().zip(&tokens[3]) {
+            assert_eq!(first.value, second.value);
+            if first.value == b"$a" {
+                assert!(Arc::ptr_eq(&first.value.bytes, &second.value.bytes));
+            }
+        }
+    }
+}
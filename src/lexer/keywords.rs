@@ -0,0 +1,234 @@
+use std::str::FromStr;
+
+use crate::lexer::token::TokenKind;
+
+/// A PHP language version, used to decide which words are reserved.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub enum PhpVersion {
+    Php74,
+    Php80,
+    Php81,
+    Php82,
+    #[default]
+    Php83,
+}
+
+impl PhpVersion {
+    pub const OLDEST: Self = Self::Php74;
+    pub const LATEST: Self = Self::Php83;
+
+    pub const ALL: [Self; 5] = [
+        Self::Php74,
+        Self::Php80,
+        Self::Php81,
+        Self::Php82,
+        Self::Php83,
+    ];
+
+    /// The version as written by PHP itself, e.g. `8.3`.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Php74 => "7.4",
+            Self::Php80 => "8.0",
+            Self::Php81 => "8.1",
+            Self::Php82 => "8.2",
+            Self::Php83 => "8.3",
+        }
+    }
+
+    /// The version in which the given keyword first became reserved.
+    pub fn introducing(kind: &TokenKind) -> Self {
+        match kind {
+            TokenKind::Match => Self::Php80,
+            TokenKind::Enum | TokenKind::Readonly => Self::Php81,
+            _ => Self::Php74,
+        }
+    }
+}
+
+impl FromStr for PhpVersion {
+    type Err = String;
+
+    /// Parse a version as written by PHP itself, e.g. `8.3`.
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|candidate| candidate.as_str() == version)
+            .ok_or_else(|| {
+                let versions: Vec<&str> =
+                    Self::ALL.iter().map(|version| version.as_str()).collect();
+
+                format!(
+                    "unsupported PHP version `{}`, expected one of {}",
+                    version,
+                    versions.join(", ")
+                )
+            })
+    }
+}
+
+/// Map an identifier to the keyword token it is lexed as, if any.
+///
+/// The lookup is case-insensitive, just like it is in PHP.
+pub fn identifier_to_keyword(ident: &[u8]) -> Option<TokenKind> {
+    Some(match ident.to_ascii_lowercase().as_slice() {
+        b"eval" => TokenKind::Eval,
+        b"die" => TokenKind::Die,
+        b"empty" => TokenKind::Empty,
+        b"isset" => TokenKind::Isset,
+        b"unset" => TokenKind::Unset,
+        b"exit" => TokenKind::Exit,
+        b"enddeclare" => TokenKind::EndDeclare,
+        b"endswitch" => TokenKind::EndSwitch,
+        b"endfor" => TokenKind::EndFor,
+        b"endwhile" => TokenKind::EndWhile,
+        b"endforeach" => TokenKind::EndForeach,
+        b"endif" => TokenKind::EndIf,
+        b"from" => TokenKind::From,
+        b"and" => TokenKind::LogicalAnd,
+        b"or" => TokenKind::LogicalOr,
+        b"xor" => TokenKind::LogicalXor,
+        b"print" => TokenKind::Print,
+        b"__halt_compiler" => TokenKind::HaltCompiler,
+        b"readonly" => TokenKind::Readonly,
+        b"global" => TokenKind::Global,
+        b"match" => TokenKind::Match,
+        b"abstract" => TokenKind::Abstract,
+        b"array" => TokenKind::Array,
+        b"as" => TokenKind::As,
+        b"break" => TokenKind::Break,
+        b"case" => TokenKind::Case,
+        b"catch" => TokenKind::Catch,
+        b"class" => TokenKind::Class,
+        b"clone" => TokenKind::Clone,
+        b"continue" => TokenKind::Continue,
+        b"const" => TokenKind::Const,
+        b"declare" => TokenKind::Declare,
+        b"default" => TokenKind::Default,
+        b"do" => TokenKind::Do,
+        b"echo" => TokenKind::Echo,
+        b"else" => TokenKind::Else,
+        b"elseif" => TokenKind::ElseIf,
+        b"enum" => TokenKind::Enum,
+        b"extends" => TokenKind::Extends,
+        b"false" => TokenKind::False,
+        b"final" => TokenKind::Final,
+        b"finally" => TokenKind::Finally,
+        b"fn" => TokenKind::Fn,
+        b"for" => TokenKind::For,
+        b"foreach" => TokenKind::Foreach,
+        b"function" => TokenKind::Function,
+        b"goto" => TokenKind::Goto,
+        b"if" => TokenKind::If,
+        b"include" => TokenKind::Include,
+        b"include_once" => TokenKind::IncludeOnce,
+        b"implements" => TokenKind::Implements,
+        b"interface" => TokenKind::Interface,
+        b"instanceof" => TokenKind::Instanceof,
+        b"namespace" => TokenKind::Namespace,
+        b"new" => TokenKind::New,
+        b"null" => TokenKind::Null,
+        b"private" => TokenKind::Private,
+        b"protected" => TokenKind::Protected,
+        b"public" => TokenKind::Public,
+        b"require" => TokenKind::Require,
+        b"require_once" => TokenKind::RequireOnce,
+        b"return" => TokenKind::Return,
+        b"static" => TokenKind::Static,
+        b"switch" => TokenKind::Switch,
+        b"throw" => TokenKind::Throw,
+        b"trait" => TokenKind::Trait,
+        b"true" => TokenKind::True,
+        b"try" => TokenKind::Try,
+        b"use" => TokenKind::Use,
+        b"var" => TokenKind::Var,
+        b"yield" => TokenKind::Yield,
+        b"__dir__" => TokenKind::DirConstant,
+        b"__file__" => TokenKind::FileConstant,
+        b"__line__" => TokenKind::LineConstant,
+        b"__function__" => TokenKind::FunctionConstant,
+        b"__class__" => TokenKind::ClassConstant,
+        b"__method__" => TokenKind::MethodConstant,
+        b"__trait__" => TokenKind::TraitConstant,
+        b"__namespace__" => TokenKind::NamespaceConstant,
+        b"__compiler_halt_offset__" => TokenKind::CompilerHaltOffsetConstant,
+        b"while" => TokenKind::While,
+        b"insteadof" => TokenKind::Insteadof,
+        b"list" => TokenKind::List,
+        b"self" => TokenKind::Self_,
+        b"parent" => TokenKind::Parent,
+        _ => return None,
+    })
+}
+
+/// Check whether the given identifier is lexed as a keyword.
+pub fn is_keyword(ident: &[u8]) -> bool {
+    identifier_to_keyword(ident).is_some()
+}
+
+/// Check whether the given identifier is a soft keyword.
+///
+/// Soft keywords are lexed as keywords, but PHP still accepts them as
+/// ordinary names in most positions, e.g. `function enum() {}` or `const FROM = 1;`.
+pub fn is_soft_keyword(ident: &[u8]) -> bool {
+    matches!(identifier_to_keyword(ident), Some(kind) if is_soft_keyword_kind(&kind))
+}
+
+/// Check whether the given keyword token is a soft keyword.
+pub fn is_soft_keyword_kind(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::Enum | TokenKind::From | TokenKind::Readonly
+    )
+}
+
+/// Check whether the given identifier is a reserved word in the given PHP version.
+///
+/// Reserved words are keywords that can not be used as a class, function, or constant name.
+/// This includes `self`, `parent`, `true`, `false` and `null`, which are reserved class names.
+pub fn is_reserved_word(ident: &[u8], version: PhpVersion) -> bool {
+    match identifier_to_keyword(ident) {
+        Some(kind) => !is_soft_keyword_kind(&kind) && PhpVersion::introducing(&kind) <= version,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyword_lookup_is_case_insensitive() {
+        assert_eq!(
+            identifier_to_keyword(b"FuNcTiOn"),
+            Some(TokenKind::Function)
+        );
+        assert_eq!(identifier_to_keyword(b"foo"), None);
+    }
+
+    #[test]
+    fn test_reserved_words() {
+        assert!(is_reserved_word(b"class", PhpVersion::Php74));
+        assert!(is_reserved_word(b"match", PhpVersion::Php80));
+        assert!(!is_reserved_word(b"match", PhpVersion::Php74));
+        assert!(!is_reserved_word(b"enum", PhpVersion::Php83));
+        assert!(!is_reserved_word(b"foo", PhpVersion::Php83));
+        assert!(!is_reserved_word(b"from", PhpVersion::Php83));
+
+        for word in [b"self".as_ref(), b"parent", b"true", b"false", b"NULL"] {
+            assert!(is_reserved_word(word, PhpVersion::Php74));
+            assert!(is_reserved_word(word, PhpVersion::Php83));
+        }
+    }
+
+    #[test]
+    fn test_soft_keywords() {
+        assert!(is_soft_keyword(b"enum"));
+        assert!(is_soft_keyword(b"readonly"));
+        assert!(is_soft_keyword(b"from"));
+        assert!(!is_soft_keyword(b"self"));
+        assert!(!is_soft_keyword(b"null"));
+        assert!(!is_soft_keyword(b"class"));
+        assert!(!is_soft_keyword(b"foo"));
+    }
+}
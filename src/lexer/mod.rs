@@ -1,112 +1,556 @@
+use std::collections::VecDeque;
+
 use crate::ident;
 use crate::ident_start;
 use crate::lexer::byte_string::ByteString;
 use crate::lexer::error::SyntaxError;
 use crate::lexer::error::SyntaxResult;
-use crate::lexer::state::source::Source;
+use crate::lexer::keywords::identifier_to_keyword;
+use crate::lexer::source::Source;
 use crate::lexer::state::StackFrame;
 use crate::lexer::state::State;
 use crate::lexer::token::DocStringIndentationKind;
 use crate::lexer::token::DocStringKind;
+use crate::lexer::token::LexerState;
 use crate::lexer::token::OpenTagKind;
+use crate::lexer::token::Span;
+use crate::lexer::token::StateToken;
 use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
+use crate::lexer::token::TriviaToken;
 
 pub mod byte_string;
 pub mod error;
+pub mod hygiene;
+pub mod interner;
+pub mod keywords;
+pub mod source;
 pub mod stream;
 pub mod token;
 
 mod state;
 
+pub use state::LineState;
+
 mod macros;
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LexerConfig {
+    /// Whether `<?` opens a block of PHP code, like the `short_open_tag` ini setting.
+    /// `<?=` is always recognized. Enabled by default.
+    pub short_tags: bool,
+}
+
+impl Default for LexerConfig {
+    fn default() -> Self {
+        Self { short_tags: true }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
-pub struct Lexer;
+pub struct Lexer {
+    config: LexerConfig,
+}
+
+/// An iterator over the tokens of an input, created by [`Lexer::tokens`].
+#[derive(Debug)]
+pub struct Tokens<'a> {
+    lexer: &'a Lexer,
+    input: &'a [u8],
+    state: State<'a>,
+    // The tokens of the last step that have not been yielded yet, with the state the step
+    // started in.
+    pending: VecDeque<(Token, LexerState)>,
+    started: bool,
+    finished: bool,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = SyntaxResult<Token>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_state()
+            .map(|token| token.map(|(token, _)| token))
+    }
+}
+
+impl<'a> Tokens<'a> {
+    fn next_with_state(&mut self) -> Option<SyntaxResult<(Token, LexerState)>> {
+        while self.pending.is_empty() {
+            if self.finished {
+                return None;
+            }
+
+            // the stack is never empty, so the initial state only stands in for an
+            // impossible one.
+            let state = self
+                .state
+                .frame()
+                .map(LexerState::from)
+                .unwrap_or(LexerState::Initial);
+
+            let mut tokens = Vec::new();
+            if !self.started {
+                self.started = true;
+                self.lexer.shebang(&mut self.state, &mut tokens);
+            }
+
+            let done = match self.lexer.step(&mut self.state, &mut tokens) {
+                Ok(done) => done,
+                Err(error) => {
+                    self.finished = true;
+
+                    return Some(Err(error));
+                }
+            };
+
+            set_end_positions(self.input, self.state.source.span().position, &mut tokens);
+
+            if done {
+                self.finished = true;
+                tokens.push(Token {
+                    kind: TokenKind::Eof,
+                    span: self.state.source.span(),
+                    value: ByteString::default(),
+                });
+            }
+
+            self.pending
+                .extend(tokens.into_iter().map(|token| (token, state.clone())));
+        }
+
+        self.pending.pop_front().map(Ok)
+    }
+}
+
+// the error for the byte at the current position, or for the end of the input.
+fn unexpected_byte(state: &State) -> SyntaxError {
+    match state.source.current() {
+        Some(byte) => SyntaxError::UnexpectedCharacter(*byte, state.source.span()),
+        None => SyntaxError::UnexpectedEndOfFile(state.source.span()),
+    }
+}
+
+// encode a code point as UTF-8, returning whether it is in the unicode range. like PHP,
+// surrogates are encoded too, even though the result is not valid UTF-8.
+fn encode_code_point(c: u32, buffer: &mut Vec<u8>) -> bool {
+    match c {
+        0..=0x7f => buffer.push(c as u8),
+        0x80..=0x7ff => buffer.extend([0xc0 | (c >> 6) as u8, 0x80 | (c & 0x3f) as u8]),
+        0x800..=0xffff => buffer.extend([
+            0xe0 | (c >> 12) as u8,
+            0x80 | ((c >> 6) & 0x3f) as u8,
+            0x80 | (c & 0x3f) as u8,
+        ]),
+        0x10000..=0x10ffff => buffer.extend([
+            0xf0 | (c >> 18) as u8,
+            0x80 | ((c >> 12) & 0x3f) as u8,
+            0x80 | ((c >> 6) & 0x3f) as u8,
+            0x80 | (c & 0x3f) as u8,
+        ]),
+        _ => return false,
+    }
+
+    true
+}
+
+// a token whose value is its exact text ends with its value. otherwise, as the tokens
+// produced by a single step are adjacent, it ends where the next one starts, or where the
+// step stopped reading.
+fn set_end_positions(input: &[u8], end: usize, tokens: &mut [Token]) {
+    let starts: Vec<usize> = tokens
+        .iter()
+        .skip(1)
+        .map(|token| token.span.position)
+        .chain([end])
+        .collect();
+    for (token, next) in tokens.iter_mut().zip(starts) {
+        let position = token.span.position;
+        token.span.end_position =
+            if !token.value.is_empty() && input[position..].starts_with(&token.value) {
+                position + token.value.len()
+            } else {
+                next
+            };
+    }
+}
 
 impl Lexer {
     pub const fn new() -> Self {
-        Self {}
+        Self::with_config(LexerConfig { short_tags: true })
+    }
+
+    pub const fn with_config(config: LexerConfig) -> Self {
+        Self { config }
     }
 
     pub fn tokenize<B: ?Sized + AsRef<[u8]>>(&self, input: &B) -> SyntaxResult<Vec<Token>> {
-        let mut state = State::new(Source::new(input.as_ref()));
-        let mut tokens = Vec::new();
+        self.tokens(input).collect()
+    }
 
-        while !state.source.eof() {
-            match state.frame()? {
-                // The "Initial" state is used to parse inline HTML. It is essentially a catch-all
-                // state that will build up a single token buffer until it encounters an open tag
-                // of some description.
-                StackFrame::Initial => self.initial(&mut state, &mut tokens)?,
-                // The scripting state is entered when an open tag is encountered in the source code.
-                // This tells the lexer to start analysing characters at PHP tokens instead of inline HTML.
-                StackFrame::Scripting => {
-                    self.skip_whitespace(&mut state);
-
-                    // If we have consumed whitespace and then reached the end of the file, we should break.
-                    if state.source.eof() {
-                        break;
-                    }
+    /// Tokenize the input, keeping the source text of every token and the whitespace
+    /// around it.
+    ///
+    /// The whitespace at the end of the input, if any, is the leading trivia of the
+    /// [`TokenKind::Eof`] token.
+    pub fn tokenize_with_trivia<B: ?Sized + AsRef<[u8]>>(
+        &self,
+        input: &B,
+    ) -> SyntaxResult<Vec<TriviaToken>> {
+        let input = input.as_ref();
+
+        let mut last = 0;
+        self.tokens(input)
+            .map(|token| {
+                let token = token?;
+                let (start, end) = if token.kind == TokenKind::Eof {
+                    (input.len(), input.len())
+                } else {
+                    let start = token.span.position.max(last);
 
-                    tokens.push(self.scripting(&mut state)?);
-                }
-                // The "Halted" state is entered when the `__halt_compiler` token is encountered.
-                // In this state, all the text that follows is no longer parsed as PHP as is collected
-                // into a single "InlineHtml" token (kind of cheating, oh well).
-                StackFrame::Halted => {
-                    tokens.push(Token {
-                        kind: TokenKind::InlineHtml,
-                        span: state.source.span(),
-                        value: state.source.read_remaining().into(),
+                    (start, token.span.end_position.max(start))
+                };
+
+                let leading_trivia = &input[last..start];
+                let text = &input[start..end];
+                last = end;
+
+                Ok(TriviaToken {
+                    token,
+                    leading_trivia: leading_trivia.into(),
+                    text: text.into(),
+                })
+            })
+            .collect()
+    }
+
+    /// Tokenize the input, along with the state the lexer was in when it read each token.
+    ///
+    /// A token is read in the state entered by the tokens before it: the start of a string
+    /// with variables, up to the first of them, is read while scripting, and the rest of
+    /// it inside the string, except for interpolated code such as `{$a}`, which is read
+    /// while scripting again.
+    pub fn tokenize_with_states<B: ?Sized + AsRef<[u8]>>(
+        &self,
+        input: &B,
+    ) -> SyntaxResult<Vec<StateToken>> {
+        let mut tokens = self.tokens(input);
+
+        std::iter::from_fn(|| tokens.next_with_state())
+            .map(|token| token.map(|(token, state)| StateToken { token, state }))
+            .collect()
+    }
+
+    /// Tokenize a single line of an input, starting in the state left by the line before
+    /// it, and returning the state to start the next line in.
+    ///
+    /// The line includes its line ending: a line without one is the last line of the
+    /// input, which is followed by a [`TokenKind::Eof`] token. Tokens that span several
+    /// lines are returned with the line they end on. The spans of the tokens are relative
+    /// to the start of the input, as with [`Lexer::tokenize`].
+    ///
+    /// ```rust
+    /// use php_parser_rs::lexer::LineState;
+    /// use php_parser_rs::lexer::Lexer;
+    ///
+    /// let lexer = Lexer::new();
+    ///
+    /// let (tokens, state) = lexer.tokenize_line(&LineState::new(), "<?php /* a\n").unwrap();
+    /// assert_eq!(tokens.len(), 1);
+    /// assert!(state.is_pending());
+    ///
+    /// let (tokens, state) = lexer.tokenize_line(&state, "b */ $c;\n").unwrap();
+    /// assert_eq!(tokens.len(), 3);
+    /// assert_eq!(tokens[0].span.line, 1);
+    /// assert_eq!(tokens[1].span.line, 2);
+    /// assert!(!state.is_pending());
+    /// ```
+    pub fn tokenize_line<B: ?Sized + AsRef<[u8]>>(
+        &self,
+        state: &LineState,
+        line: &B,
+    ) -> SyntaxResult<(Vec<Token>, LineState)> {
+        let line = line.as_ref();
+        let last = !line.ends_with(b"\n");
+
+        let mut input = state.pending.to_vec();
+        input.extend_from_slice(line);
+
+        // the input starts where the pending text does, so the spans read from it are
+        // moved after the lines before it.
+        let base = state.span;
+        let offset = |span: Span| Span {
+            line: base.line + span.line - 1,
+            column: if span.line == 1 {
+                base.column + span.column - 1
+            } else {
+                span.column
+            },
+            position: base.position + span.position,
+            end_position: base.position + span.end_position,
+        };
+
+        let mut lexer_state =
+            State::with_stack(Source::new(&input), state.stack.iter().cloned().collect());
+        let mut started = state.started;
+        let mut result = Vec::new();
+
+        loop {
+            let start = lexer_state.source.span();
+            let stack = lexer_state.stack.clone();
+            let was_started = started;
+
+            let mut tokens = Vec::new();
+            if !started {
+                started = true;
+                self.shebang(&mut lexer_state, &mut tokens);
+            }
+
+            let step = self.step(&mut lexer_state, &mut tokens);
+            set_end_positions(&input, lexer_state.source.span().position, &mut tokens);
+
+            // a token that reaches the end of a line, or an error there, may only have
+            // stopped because the rest of it is on the next lines, so the step is read again
+            // along with them.
+            let unfinished = !last
+                && match &step {
+                    Ok(_) => tokens
+                        .last()
+                        .is_some_and(|token| token.span.end_position == input.len()),
+                    Err(_) => lexer_state.source.eof(),
+                };
+            if unfinished {
+                let next = LineState {
+                    stack: stack.into_iter().collect(),
+                    pending: input[start.position..].into(),
+                    span: offset(start),
+                    started: was_started,
+                };
+
+                return Ok((result, next));
+            }
+
+            let done = step.map_err(|mut error| {
+                let span = error.span_mut();
+                *span = offset(*span);
+
+                error
+            })?;
+
+            result.extend(tokens.into_iter().map(|token| Token {
+                span: offset(token.span),
+                ..token
+            }));
+
+            if done {
+                let end = offset(lexer_state.source.span());
+                if last {
+                    result.push(Token {
+                        kind: TokenKind::Eof,
+                        span: end,
+                        value: ByteString::default(),
                     });
-                    break;
-                }
-                // The double quote state is entered when inside a double-quoted string that
-                // contains variables.
-                StackFrame::DoubleQuote => self.double_quote(&mut state, &mut tokens)?,
-                // The shell exec state is entered when inside of a execution string (`).
-                StackFrame::ShellExec => self.shell_exec(&mut state, &mut tokens)?,
-                // The doc string state is entered when tokenizing heredocs and nowdocs.
-                StackFrame::DocString(kind, label, ..) => {
-                    let label = label.clone();
-
-                    match kind {
-                        DocStringKind::Heredoc => self.heredoc(&mut state, &mut tokens, label)?,
-                        DocStringKind::Nowdoc => self.nowdoc(&mut state, &mut tokens, label)?,
-                    }
-                }
-                // LookingForProperty is entered inside double quotes,
-                // backticks, or a heredoc, expecting a variable name.
-                // If one isn't found, it switches to scripting.
-                StackFrame::LookingForVarname => {
-                    if let Some(token) = self.looking_for_varname(&mut state)? {
-                        tokens.push(token);
-                    }
                 }
-                // LookingForProperty is entered inside double quotes,
-                // backticks, or a heredoc, expecting an arrow followed by a
-                // property name.
-                StackFrame::LookingForProperty => {
-                    tokens.push(self.looking_for_property(&mut state)?);
+
+                let next = LineState {
+                    stack: lexer_state.stack.into_iter().collect(),
+                    pending: ByteString::default(),
+                    span: end,
+                    started,
+                };
+
+                return Ok((result, next));
+            }
+        }
+    }
+
+    /// Lazily tokenize the input, producing tokens as they are read.
+    ///
+    /// The iterator ends with a [`TokenKind::Eof`] token, or with the first error, so a
+    /// consumer can stop reading a large input early without tokenizing all of it.
+    pub fn tokens<'a, B: ?Sized + AsRef<[u8]>>(&'a self, input: &'a B) -> Tokens<'a> {
+        let input = input.as_ref();
+
+        Tokens {
+            lexer: self,
+            input,
+            state: State::new(Source::new(input)),
+            pending: VecDeque::new(),
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// Lazily tokenize the input from the given span, where the lexer is known to be
+    /// scripting, such as between two statements. Without a span, this is the same as
+    /// [`Lexer::tokens`].
+    pub(crate) fn tokens_from<'a>(&'a self, input: &'a [u8], start: Option<Span>) -> Tokens<'a> {
+        let mut tokens = self.tokens(input);
+
+        if let Some(start) = start {
+            tokens.state =
+                State::with_stack(Source::new(input), VecDeque::from([StackFrame::Scripting]));
+            tokens.state.source.reset(start);
+            tokens.started = true;
+        }
+
+        tokens
+    }
+
+    /// Tokenize the input from the given span, as with [`Lexer::tokens_from`], up to the
+    /// end position, where a [`TokenKind::Eof`] token is added.
+    ///
+    /// `None` is returned if the input can't be tokenized, or if the lexer isn't back to
+    /// scripting at the end position, as when a comment or a string continues after it.
+    pub(crate) fn tokenize_between(
+        &self,
+        input: &[u8],
+        start: Option<Span>,
+        end: usize,
+    ) -> Option<Vec<Token>> {
+        let mut tokens = self.tokens_from(input, start);
+        let mut result = Vec::new();
+
+        loop {
+            if tokens.pending.is_empty() {
+                let position = tokens.state.source.position();
+                if position >= end {
+                    break;
                 }
-                StackFrame::VarOffset => {
-                    if state.source.eof() {
-                        break;
-                    }
 
-                    tokens.push(self.var_offset(&mut state)?);
+                // whitespace at the end is skipped without reading the token after it.
+                if input[position..end].iter().all(u8::is_ascii_whitespace) {
+                    tokens.state.source.skip(end - position);
+                    break;
                 }
             }
+
+            let token = tokens.next()?.ok()?;
+            if token.kind == TokenKind::Eof {
+                return None;
+            }
+
+            result.push(token);
         }
 
-        tokens.push(Token {
+        if tokens.state.source.position() != end || tokens.state.stack != [StackFrame::Scripting] {
+            return None;
+        }
+
+        result.push(Token {
             kind: TokenKind::Eof,
-            span: state.source.span(),
+            span: tokens.state.source.span(),
             value: ByteString::default(),
         });
 
-        Ok(tokens)
+        Some(result)
+    }
+
+    // Tokenize the next part of the input, returning whether the end of the input was
+    // reached. A single step may produce any number of tokens.
+    fn step(&self, state: &mut State, tokens: &mut Vec<Token>) -> SyntaxResult<bool> {
+        if state.source.eof() {
+            return Ok(true);
+        }
+
+        match state.frame()? {
+            // The "Initial" state is used to parse inline HTML. It is essentially a catch-all
+            // state that will build up a single token buffer until it encounters an open tag
+            // of some description.
+            StackFrame::Initial => self.initial(state, tokens)?,
+            // The scripting state is entered when an open tag is encountered in the source code.
+            // This tells the lexer to start analysing characters at PHP tokens instead of inline HTML.
+            StackFrame::Scripting => {
+                self.skip_whitespace(state);
+
+                // If we have consumed whitespace and then reached the end of the file, we should break.
+                if state.source.eof() {
+                    return Ok(true);
+                }
+
+                tokens.push(self.scripting(state)?);
+            }
+            // The "Halted" state is entered when the `__halt_compiler` token is encountered.
+            // In this state, all the text that follows is no longer parsed as PHP as is collected
+            // into a single "InlineHtml" token (kind of cheating, oh well).
+            StackFrame::Halted => {
+                let value: ByteString = state.source.read_remaining().into();
+                let mut span = state.source.span();
+                span.end_position += value.len();
+                state.source.skip(value.len());
+
+                tokens.push(Token {
+                    kind: TokenKind::InlineHtml,
+                    span,
+                    value,
+                });
+
+                return Ok(true);
+            }
+            // The double quote state is entered when inside a double-quoted string that
+            // contains variables.
+            StackFrame::DoubleQuote => self.double_quote(state, tokens)?,
+            // The shell exec state is entered when inside of a execution string (`).
+            StackFrame::ShellExec => self.shell_exec(state, tokens)?,
+            // The doc string state is entered when tokenizing heredocs and nowdocs.
+            StackFrame::DocString(kind, label) => {
+                let label = label.clone();
+
+                match kind {
+                    DocStringKind::Heredoc => self.heredoc(state, tokens, label)?,
+                    DocStringKind::Nowdoc => self.nowdoc(state, tokens, label)?,
+                }
+            }
+            // LookingForProperty is entered inside double quotes,
+            // backticks, or a heredoc, expecting a variable name.
+            // If one isn't found, it switches to scripting.
+            StackFrame::LookingForVarname => {
+                if let Some(token) = self.looking_for_varname(state)? {
+                    tokens.push(token);
+                }
+            }
+            // LookingForProperty is entered inside double quotes,
+            // backticks, or a heredoc, expecting an arrow followed by a
+            // property name.
+            StackFrame::LookingForProperty => {
+                tokens.push(self.looking_for_property(state)?);
+            }
+            StackFrame::VarOffset => {
+                if state.source.eof() {
+                    return Ok(true);
+                }
+
+                tokens.push(self.var_offset(state)?);
+            }
+        }
+
+        Ok(false)
+    }
+
+    // Read the `();` after `__halt_compiler`, which may be surrounded by whitespace. As with
+    // PHP, a closing tag, along with the line ending after it, can stand in for the
+    // semicolon. The data after the halt starts wherever this stops reading.
+    fn halt_compiler(&self, state: &mut State) -> SyntaxResult<()> {
+        self.skip_whitespace(state);
+        if !state.source.consume(b"(") {
+            return Err(SyntaxError::InvalidHaltCompiler(state.source.span()));
+        }
+
+        self.skip_whitespace(state);
+        if !state.source.consume(b")") {
+            return Err(SyntaxError::InvalidHaltCompiler(state.source.span()));
+        }
+
+        self.skip_whitespace(state);
+        if state.source.consume(b"?>") {
+            let _ = state.source.consume(b"\r\n") || state.source.consume(b"\n");
+        } else if !state.source.consume(b";") {
+            return Err(SyntaxError::InvalidHaltCompiler(state.source.span()));
+        }
+
+        state.replace(StackFrame::Halted);
+
+        Ok(())
     }
 
     fn skip_whitespace(&self, state: &mut State) {
@@ -117,13 +561,41 @@ impl Lexer {
 
     fn read_and_skip_whitespace(&self, state: &mut State) -> Vec<u8> {
         let mut buffer = Vec::new();
-        while let Some(true) = state.source.current().map(|u: &u8| u.is_ascii_whitespace()) {
-            buffer.push(*state.source.current().unwrap());
+        while let Some(&byte) = state.source.current().filter(|b| b.is_ascii_whitespace()) {
+            buffer.push(byte);
             state.source.next();
         }
         buffer
     }
 
+    // A `#!` line is only recognised at the very start of the file, and includes the
+    // line break that terminates it, just like the PHP CLI skips it.
+    fn shebang(&self, state: &mut State, tokens: &mut Vec<Token>) {
+        if !state.source.at(b"#!", 2) {
+            return;
+        }
+
+        let span = state.source.span();
+        let mut buffer = Vec::new();
+        while let Some(char) = state.source.current() {
+            state.source.next();
+            buffer.push(*char);
+
+            if *char == b'\n' {
+                break;
+            }
+        }
+
+        tokens.push(Token {
+            kind: TokenKind::Shebang,
+            span: Span {
+                end_position: state.source.span().position,
+                ..span
+            },
+            value: buffer.into(),
+        });
+    }
+
     fn initial(&self, state: &mut State, tokens: &mut Vec<Token>) -> SyntaxResult<()> {
         let inline_span = state.source.span();
         let mut buffer = Vec::new();
@@ -170,7 +642,7 @@ impl Lexer {
                 });
 
                 return Ok(());
-            } else if state.source.at_case_insensitive(b"<?", 2) {
+            } else if self.config.short_tags && state.source.at_case_insensitive(b"<?", 2) {
                 let tag_span = state.source.span();
 
                 state.source.skip(2);
@@ -309,6 +781,13 @@ impl Lexer {
                 self.tokenize_double_quote_string(state)?
             }
             [b'$', ident_start!(), ..] => self.tokenize_variable(state),
+            // unlike in strings, a `${` in scripting is always followed by an expression,
+            // so there is no variable name to look for.
+            [b'$', b'{', ..] => {
+                state.source.skip(2);
+                state.enter(StackFrame::Scripting);
+                (TokenKind::DollarLeftBrace, b"${".into())
+            }
             [b'$', ..] => {
                 state.source.next();
                 (TokenKind::Dollar, b"$".into())
@@ -349,7 +828,16 @@ impl Lexer {
                         kind: TokenKind::Null,
                         ..
                     } => (TokenKind::FullyQualifiedIdentifier, b"\\null".into()),
-                    s => unreachable!("{:?}", s),
+                    // like PHP, a keyword after a namespace separator is part of the name.
+                    Token {
+                        ref kind, value, ..
+                    } if identifier_to_keyword(&value).as_ref() == Some(kind) => {
+                        let mut bytes = value;
+                        bytes.insert(0, b'\\');
+
+                        (TokenKind::FullyQualifiedIdentifier, bytes)
+                    }
+                    _ => return Err(SyntaxError::UnexpectedCharacter(b'\\', span)),
                 }
             }
             [b'\\', ..] => {
@@ -483,13 +971,7 @@ impl Lexer {
                             buffer.push(b'\'');
                             state.source.next();
                         }
-                        _ => {
-                            // TODO(azjezz) this is most likely a bug, what if current is none?
-                            return Err(SyntaxError::UnexpectedCharacter(
-                                *state.source.current().unwrap(),
-                                state.source.span(),
-                            ));
-                        }
+                        _ => return Err(unexpected_byte(state)),
                     };
                 } else if let Some(b'"') = state.source.current() {
                     buffer.push(b'"');
@@ -497,18 +979,13 @@ impl Lexer {
                 }
 
                 if !matches!(state.source.current(), Some(b'\n')) {
-                    return Err(SyntaxError::UnexpectedCharacter(
-                        *state.source.current().unwrap(),
-                        state.source.span(),
-                    ));
+                    return Err(unexpected_byte(state));
                 }
 
                 state.source.next();
                 state.replace(StackFrame::DocString(
                     doc_string_kind.clone(),
                     label.clone(),
-                    DocStringIndentationKind::None,
-                    0,
                 ));
 
                 (TokenKind::StartDocString(doc_string_kind), buffer.into())
@@ -552,7 +1029,10 @@ impl Lexer {
             }
             [b'}', ..] => {
                 state.source.next();
-                state.exit();
+                // a `}` without a matching `{` is left for the parser to report.
+                if state.stack.len() > 1 {
+                    state.exit();
+                }
                 (TokenKind::RightBrace, b"}".into())
             }
             [b'(', ..] => {
@@ -560,9 +1040,8 @@ impl Lexer {
                 let mut buffer = b"(".to_vec();
 
                 // Inlined so we can add whitespace to the buffer.
-                while let Some(true) = state.source.current().map(|u: &u8| u.is_ascii_whitespace())
-                {
-                    buffer.push(*state.source.current().unwrap());
+                while let Some(&byte) = state.source.current().filter(|b| b.is_ascii_whitespace()) {
+                    buffer.push(byte);
                     state.source.next();
                 }
 
@@ -813,24 +1292,13 @@ impl Lexer {
                     let kind = identifier_to_keyword(&buffer).unwrap_or(TokenKind::Identifier);
 
                     if kind == TokenKind::HaltCompiler {
-                        match state.source.read(3) {
-                            [b'(', b')', b';'] => {
-                                state.source.skip(3);
-                                state.replace(StackFrame::Halted);
-                            }
-                            _ => return Err(SyntaxError::InvalidHaltCompiler(state.source.span())),
-                        }
+                        self.halt_compiler(state)?;
                     }
 
                     (kind, buffer.into())
                 }
             }
-            [b, ..] => unimplemented!(
-                "<scripting> char: {}, line: {}, col: {}",
-                *b as char,
-                state.source.span().line,
-                state.source.span().column
-            ),
+            [b, ..] => return Err(SyntaxError::UnrecognisedToken(*b, span)),
             // We should never reach this point since we have the empty checks surrounding
             // the call to this function, but it's better to be safe than sorry.
             [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
@@ -842,7 +1310,10 @@ impl Lexer {
     fn double_quote(&self, state: &mut State, tokens: &mut Vec<Token>) -> SyntaxResult<()> {
         let span = state.source.span();
         let mut buffer = Vec::new();
+        // the span of the token ending the string part, which starts after the part.
+        let mut end_span;
         let (kind, value) = loop {
+            end_span = state.source.span();
             match state.source.read(3) {
                 [b'$', b'{', ..] => {
                     state.source.skip(2);
@@ -899,7 +1370,9 @@ impl Lexer {
                         hex.push(*b as char);
                     }
 
-                    let b = u8::from_str_radix(&hex, 16).unwrap();
+                    let Ok(b) = u8::from_str_radix(&hex, 16) else {
+                        return Err(SyntaxError::UnexpectedError(state.source.span()));
+                    };
                     buffer.push(b);
                 }
                 &[b'\\', b'u', b'{'] => {
@@ -924,11 +1397,7 @@ impl Lexer {
                         return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
                     };
 
-                    if let Some(c) = char::from_u32(c) {
-                        let mut tmp = [0; 4];
-                        let bytes = c.encode_utf8(&mut tmp);
-                        buffer.extend(bytes.as_bytes());
-                    } else {
+                    if !encode_code_point(c, &mut buffer) {
                         return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
                     }
                 }
@@ -981,14 +1450,21 @@ impl Lexer {
             })
         }
 
-        tokens.push(Token { kind, span, value });
+        tokens.push(Token {
+            kind,
+            span: end_span,
+            value,
+        });
         Ok(())
     }
 
     fn shell_exec(&self, state: &mut State, tokens: &mut Vec<Token>) -> SyntaxResult<()> {
         let span = state.source.span();
         let mut buffer = Vec::new();
+        // the span of the token ending the string part, which starts after the part.
+        let mut end_span;
         let (kind, value) = loop {
+            end_span = state.source.span();
             match state.source.read(2) {
                 [b'$', b'{'] => {
                     state.source.skip(2);
@@ -1036,7 +1512,11 @@ impl Lexer {
             })
         }
 
-        tokens.push(Token { kind, span, value });
+        tokens.push(Token {
+            kind,
+            span: end_span,
+            value,
+        });
 
         Ok(())
     }
@@ -1050,7 +1530,10 @@ impl Lexer {
         let span = state.source.span();
         let mut buffer: Vec<u8> = Vec::new();
 
+        // the span of the token ending the string part, which starts after the part.
+        let mut end_span;
         let (kind, value) = loop {
+            end_span = state.source.span();
             match state.source.read(3) {
                 [b'$', b'{', ..] => {
                     state.source.skip(2);
@@ -1102,7 +1585,9 @@ impl Lexer {
                         hex.push(*b as char);
                     }
 
-                    let b = u8::from_str_radix(&hex, 16).unwrap();
+                    let Ok(b) = u8::from_str_radix(&hex, 16) else {
+                        return Err(SyntaxError::UnexpectedError(state.source.span()));
+                    };
                     buffer.push(b);
                 }
                 &[b'\\', b'u', b'{'] => {
@@ -1127,11 +1612,7 @@ impl Lexer {
                         return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
                     };
 
-                    if let Some(c) = char::from_u32(c) {
-                        let mut tmp = [0; 4];
-                        let bytes = c.encode_utf8(&mut tmp);
-                        buffer.extend(bytes.as_bytes());
-                    } else {
+                    if !encode_code_point(c, &mut buffer) {
                         return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
                     }
                 }
@@ -1237,8 +1718,7 @@ impl Lexer {
                         // We didn't find the label. The buffer still needs to know about
                         // the whitespace, so let's extend the buffer with the whitespace
                         // and let the loop run again to handle the rest of the line.
-                        if whitespace_kind != DocStringIndentationKind::None {
-                            let whitespace_char: u8 = whitespace_kind.into();
+                        if let Ok(whitespace_char) = u8::try_from(whitespace_kind) {
                             for _ in 0..whitespace_amount {
                                 buffer.push(whitespace_char);
                             }
@@ -1268,7 +1748,11 @@ impl Lexer {
             })
         }
 
-        tokens.push(Token { kind, span, value });
+        tokens.push(Token {
+            kind,
+            span: end_span,
+            value,
+        });
 
         Ok(())
     }
@@ -1282,7 +1766,10 @@ impl Lexer {
         let span = state.source.span();
         let mut buffer: Vec<u8> = Vec::new();
 
+        // the span of the token ending the string part, which starts after the part.
+        let mut end_span;
         let (kind, value) = loop {
+            end_span = state.source.span();
             match state.source.read(3) {
                 // If we find a new-line, we can start to check if we can see the EndHeredoc token.
                 [b'\n', ..] => {
@@ -1353,8 +1840,7 @@ impl Lexer {
                         // We didn't find the label. The buffer still needs to know about
                         // the whitespace, so let's extend the buffer with the whitespace
                         // and let the loop run again to handle the rest of the line.
-                        if whitespace_kind != DocStringIndentationKind::None {
-                            let whitespace_char: u8 = whitespace_kind.into();
+                        if let Ok(whitespace_char) = u8::try_from(whitespace_kind) {
                             for _ in 0..whitespace_amount {
                                 buffer.push(whitespace_char);
                             }
@@ -1384,7 +1870,11 @@ impl Lexer {
             })
         }
 
-        tokens.push(Token { kind, span, value });
+        tokens.push(Token {
+            kind,
+            span: end_span,
+            value,
+        });
 
         Ok(())
     }
@@ -1428,7 +1918,7 @@ impl Lexer {
                 (TokenKind::Identifier, buffer.into())
             }
             // Should be impossible as we already looked ahead this far inside double_quote.
-            _ => unreachable!(),
+            _ => return Err(unexpected_byte(state)),
         };
 
         Ok(Token { kind, span, value })
@@ -1498,6 +1988,7 @@ impl Lexer {
         &self,
         state: &mut State,
     ) -> SyntaxResult<(TokenKind, ByteString)> {
+        let start = state.source.span();
         let mut buffer = vec![];
 
         let constant = loop {
@@ -1545,7 +2036,9 @@ impl Lexer {
                         hex.push(*b as char);
                     }
 
-                    let b = u8::from_str_radix(&hex, 16).unwrap();
+                    let Ok(b) = u8::from_str_radix(&hex, 16) else {
+                        return Err(SyntaxError::UnexpectedError(state.source.span()));
+                    };
                     buffer.push(b);
                 }
                 &[b'\\', b'u', b'{'] => {
@@ -1570,11 +2063,7 @@ impl Lexer {
                         return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
                     };
 
-                    if let Some(c) = char::from_u32(c) {
-                        let mut tmp = [0; 4];
-                        let bytes = c.encode_utf8(&mut tmp);
-                        buffer.extend(bytes.as_bytes());
-                    } else {
+                    if !encode_code_point(c, &mut buffer) {
                         return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
                     }
                 }
@@ -1612,8 +2101,11 @@ impl Lexer {
         Ok(if constant {
             (TokenKind::LiteralDoubleQuotedString, buffer.into())
         } else {
+            // the opening quote is a token of its own, like the closing one, and the
+            // contents are read again as parts of the string.
+            state.source.reset(start);
             state.replace(StackFrame::DoubleQuote);
-            (TokenKind::StringPart, buffer.into())
+            (TokenKind::DoubleQuote, b'"'.into())
         })
     }
 
@@ -1751,104 +2243,12 @@ impl Lexer {
     }
 }
 
-// Parses an integer literal in the given base and converts errors to SyntaxError.
-// It returns a float token instead on overflow.
+// Integer literals are kept as written, the value (and whether it overflows to a float)
+// is computed by `LiteralInteger::to_value`.
 fn parse_int(buffer: &[u8]) -> SyntaxResult<(TokenKind, ByteString)> {
     Ok((TokenKind::LiteralInteger, buffer.into()))
 }
 
-#[inline(always)]
-fn identifier_to_keyword(ident: &[u8]) -> Option<TokenKind> {
-    Some(match ident.to_ascii_lowercase().as_slice() {
-        b"eval" => TokenKind::Eval,
-        b"die" => TokenKind::Die,
-        b"empty" => TokenKind::Empty,
-        b"isset" => TokenKind::Isset,
-        b"unset" => TokenKind::Unset,
-        b"exit" => TokenKind::Exit,
-        b"enddeclare" => TokenKind::EndDeclare,
-        b"endswitch" => TokenKind::EndSwitch,
-        b"endfor" => TokenKind::EndFor,
-        b"endwhile" => TokenKind::EndWhile,
-        b"endforeach" => TokenKind::EndForeach,
-        b"endif" => TokenKind::EndIf,
-        b"from" => TokenKind::From,
-        b"and" => TokenKind::LogicalAnd,
-        b"or" => TokenKind::LogicalOr,
-        b"xor" => TokenKind::LogicalXor,
-        b"print" => TokenKind::Print,
-        b"__halt_compiler" => TokenKind::HaltCompiler,
-        b"readonly" => TokenKind::Readonly,
-        b"global" => TokenKind::Global,
-        b"match" => TokenKind::Match,
-        b"abstract" => TokenKind::Abstract,
-        b"array" => TokenKind::Array,
-        b"as" => TokenKind::As,
-        b"break" => TokenKind::Break,
-        b"case" => TokenKind::Case,
-        b"catch" => TokenKind::Catch,
-        b"class" => TokenKind::Class,
-        b"clone" => TokenKind::Clone,
-        b"continue" => TokenKind::Continue,
-        b"const" => TokenKind::Const,
-        b"declare" => TokenKind::Declare,
-        b"default" => TokenKind::Default,
-        b"do" => TokenKind::Do,
-        b"echo" => TokenKind::Echo,
-        b"else" => TokenKind::Else,
-        b"elseif" => TokenKind::ElseIf,
-        b"enum" => TokenKind::Enum,
-        b"extends" => TokenKind::Extends,
-        b"false" => TokenKind::False,
-        b"final" => TokenKind::Final,
-        b"finally" => TokenKind::Finally,
-        b"fn" => TokenKind::Fn,
-        b"for" => TokenKind::For,
-        b"foreach" => TokenKind::Foreach,
-        b"function" => TokenKind::Function,
-        b"goto" => TokenKind::Goto,
-        b"if" => TokenKind::If,
-        b"include" => TokenKind::Include,
-        b"include_once" => TokenKind::IncludeOnce,
-        b"implements" => TokenKind::Implements,
-        b"interface" => TokenKind::Interface,
-        b"instanceof" => TokenKind::Instanceof,
-        b"namespace" => TokenKind::Namespace,
-        b"new" => TokenKind::New,
-        b"null" => TokenKind::Null,
-        b"private" => TokenKind::Private,
-        b"protected" => TokenKind::Protected,
-        b"public" => TokenKind::Public,
-        b"require" => TokenKind::Require,
-        b"require_once" => TokenKind::RequireOnce,
-        b"return" => TokenKind::Return,
-        b"static" => TokenKind::Static,
-        b"switch" => TokenKind::Switch,
-        b"throw" => TokenKind::Throw,
-        b"trait" => TokenKind::Trait,
-        b"true" => TokenKind::True,
-        b"try" => TokenKind::Try,
-        b"use" => TokenKind::Use,
-        b"var" => TokenKind::Var,
-        b"yield" => TokenKind::Yield,
-        b"__dir__" => TokenKind::DirConstant,
-        b"__file__" => TokenKind::FileConstant,
-        b"__line__" => TokenKind::LineConstant,
-        b"__function__" => TokenKind::FunctionConstant,
-        b"__class__" => TokenKind::ClassConstant,
-        b"__method__" => TokenKind::MethodConstant,
-        b"__trait__" => TokenKind::TraitConstant,
-        b"__namespace__" => TokenKind::NamespaceConstant,
-        b"__compiler_halt_offset__" => TokenKind::CompilerHaltOffsetConstant,
-        b"while" => TokenKind::While,
-        b"insteadof" => TokenKind::Insteadof,
-        b"list" => TokenKind::List,
-        b"self" => TokenKind::Self_,
-        b"parent" => TokenKind::Parent,
-        _ => return None,
-    })
-}
-
 #[derive(Debug, Eq, PartialEq)]
 enum NumberKind {
     Int,
@@ -1856,3 +2256,286 @@ enum NumberKind {
     IntOrFloat,
     OctalOrFloat,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_match_tokenize() {
+        let source =
+            "#!/usr/bin/env php\n<html><?php echo \"a{$b->c}d\"; ?>\n<?= <<<EOF\n  x $y\n  EOF ?>";
+        let lexer = Lexer::new();
+
+        let tokens: Vec<Token> = lexer.tokens(source).map(Result::unwrap).collect();
+
+        assert_eq!(tokens, lexer.tokenize(source).unwrap());
+        assert_eq!(tokens.last().unwrap().kind, TokenKind::Eof);
+    }
+
+    #[test]
+    fn test_tokens_stop_early() {
+        let lexer = Lexer::new();
+        let kinds: Vec<TokenKind> = lexer
+            .tokens("<?php $a = 1; $b = 2;")
+            .take(3)
+            .map(|token| token.unwrap().kind)
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::OpenTag(OpenTagKind::Full),
+                TokenKind::Variable,
+                TokenKind::Equals
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_with_states() {
+        let source = "<p><?php \"a {$b} $c->d\" . <<<EOF\n  $e\n  EOF;";
+        let tokens = Lexer::new().tokenize_with_states(source).unwrap();
+
+        let states: Vec<(String, LexerState)> = tokens
+            .into_iter()
+            .map(|token| (token.token.value.to_string(), token.state))
+            .collect();
+
+        let state = |value: &str, state: LexerState| (value.to_string(), state);
+        assert_eq!(
+            states,
+            vec![
+                state("<p>", LexerState::Initial),
+                state("<?php", LexerState::Initial),
+                state("\"", LexerState::Scripting),
+                state("a ", LexerState::DoubleQuote),
+                state("{", LexerState::DoubleQuote),
+                state("$b", LexerState::Scripting),
+                state("}", LexerState::Scripting),
+                state(" ", LexerState::DoubleQuote),
+                state("$c", LexerState::DoubleQuote),
+                state("->", LexerState::LookingForProperty),
+                state("d", LexerState::LookingForProperty),
+                state("\"", LexerState::DoubleQuote),
+                state(".", LexerState::Scripting),
+                state("<<<EOF", LexerState::Scripting),
+                state("  ", LexerState::DocString(DocStringKind::Heredoc)),
+                state("$e", LexerState::DocString(DocStringKind::Heredoc)),
+                state("EOF", LexerState::DocString(DocStringKind::Heredoc)),
+                state(";", LexerState::Scripting),
+                state("", LexerState::Scripting),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokens_end_with_error() {
+        let lexer = Lexer::new();
+        let mut tokens = lexer.tokens("<?php $a = \"unterminated");
+
+        assert!(tokens.by_ref().take(3).all(|token| token.is_ok()));
+        assert!(tokens.next().unwrap().is_err());
+        assert!(tokens.next().is_none());
+    }
+
+    fn tokenize_lines(lexer: &Lexer, source: &[u8]) -> SyntaxResult<Vec<Token>> {
+        let mut state = LineState::new();
+        let mut tokens = Vec::new();
+        // a final line without a line ending finishes the input, even if it is empty.
+        let lines = source
+            .split_inclusive(|b| *b == b'\n')
+            .chain(std::iter::once(&b""[..]).filter(|_| source.ends_with(b"\n")));
+        for line in lines.chain(std::iter::once(&b""[..]).filter(|_| source.is_empty())) {
+            let (line_tokens, next) = lexer.tokenize_line(&state, line)?;
+            tokens.extend(line_tokens);
+            state = next;
+        }
+
+        Ok(tokens)
+    }
+
+    #[test]
+    fn test_tokenize_lines_matches_tokenize() {
+        let lexer = Lexer::new();
+        let fixtures = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+        for entry in std::fs::read_dir(fixtures).unwrap().flatten() {
+            let source = std::fs::read(entry.path().join("code.php")).unwrap();
+
+            assert_eq!(
+                tokenize_lines(&lexer, &source),
+                lexer.tokenize(&source),
+                "{}",
+                entry.path().display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_tokenize_line_state() {
+        let lexer = Lexer::new();
+        let lines = [
+            "<?php\n",
+            "$a = <<<EOF\n",
+            "  {$b}\n",
+            "  EOF;\n",
+            "$c = 1;",
+        ];
+
+        let mut state = LineState::new();
+        let mut states = vec![state.clone()];
+        for line in lines {
+            state = lexer.tokenize_line(&state, line).unwrap().1;
+            states.push(state.clone());
+        }
+
+        // the heredoc is entered at the end of its first line, and the line ending of its
+        // body is only read with the closing label, which drops it.
+        let heredoc = StackFrame::DocString(DocStringKind::Heredoc, b"EOF".into());
+        assert!(!states[2].is_pending());
+        assert_eq!(states[2].stack, vec![heredoc.clone()]);
+        assert!(states[3].is_pending());
+        assert_eq!(states[4].stack, vec![StackFrame::Scripting]);
+        assert!(!states[4].is_pending());
+
+        // an edit to the body of the heredoc leaves the state after it unchanged, so the
+        // lines after it do not need to be tokenized again.
+        let edited = lexer.tokenize_line(&states[2], "  x {$d}\n").unwrap().1;
+        assert_eq!(edited.stack, vec![heredoc]);
+        assert!(lexer
+            .tokenize_line(&edited, "  EOF;\n")
+            .unwrap()
+            .1
+            .is_equivalent(&states[4]));
+
+        let json = serde_json::to_string(&states[2]).unwrap();
+        assert_eq!(serde_json::from_str::<LineState>(&json).unwrap(), states[2]);
+    }
+
+    #[test]
+    fn test_escapes_keep_bytes() {
+        let value = |source: &[u8]| {
+            Lexer::new()
+                .tokenize(source)
+                .map(|tokens| tokens[1].value.clone())
+        };
+
+        assert_eq!(value(br#"<?php "\xff\377""#).unwrap(), b"\xff\xff");
+        assert_eq!(
+            value(br#"<?php "\u{e9}\u{1F600}\u{D800}""#).unwrap(),
+            b"\xc3\xa9\xf0\x9f\x98\x80\xed\xa0\x80"
+        );
+        assert_eq!(value(b"<?php '\xff\xfe'").unwrap(), b"\xff\xfe");
+        assert!(matches!(
+            value(br#"<?php "\u{110000}""#),
+            Err(SyntaxError::InvalidUnicodeEscape(_))
+        ));
+    }
+
+    #[test]
+    fn test_halt_compiler_data() {
+        let lexer = Lexer::new();
+        let data = |source: &[u8]| {
+            let tokens = lexer.tokenize(source).unwrap();
+            let eof = tokens.last().unwrap();
+            assert_eq!(eof.span.position, source.len());
+
+            tokens
+                .iter()
+                .find(|token| token.kind == TokenKind::InlineHtml)
+                .map(|token| (token.span.position, token.value.clone()))
+        };
+
+        assert_eq!(
+            data(b"<?php __halt_compiler();\xff\0"),
+            Some((24, b"\xff\0".into()))
+        );
+        assert_eq!(
+            data(b"<?php __halt_compiler ( )\n;data"),
+            Some((27, b"data".into()))
+        );
+        assert_eq!(
+            data(b"<?php __halt_compiler() ?>\r\ndata"),
+            Some((28, b"data".into()))
+        );
+        assert_eq!(data(b"<?php __halt_compiler();"), None);
+        assert!(matches!(
+            lexer.tokenize("<?php __halt_compiler(;"),
+            Err(SyntaxError::InvalidHaltCompiler(_))
+        ));
+    }
+
+    #[test]
+    fn test_incomplete_input_is_an_error() {
+        let lexer = Lexer::new();
+
+        for source in ["<?php <<<A", "<?php <<<'A", "<?php <<<\"A\""] {
+            assert!(
+                matches!(
+                    lexer.tokenize(source),
+                    Err(SyntaxError::UnexpectedEndOfFile(_))
+                ),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn test_keywords_in_fully_qualified_names() {
+        let tokens = Lexer::new().tokenize("<?php \\If; \\Foo\\class;").unwrap();
+
+        assert_eq!(tokens[1].kind, TokenKind::FullyQualifiedIdentifier);
+        assert_eq!(tokens[1].value, b"\\If");
+        assert_eq!(tokens[3].kind, TokenKind::FullyQualifiedIdentifier);
+        assert_eq!(tokens[3].value, b"\\Foo\\class");
+        assert!(matches!(
+            Lexer::new().tokenize("<?php \\b'a';"),
+            Err(SyntaxError::UnexpectedCharacter(b'\\', _))
+        ));
+    }
+
+    #[test]
+    fn test_every_byte_is_tokenized_or_rejected() {
+        let lexer = Lexer::new();
+
+        for byte in 0..=u8::MAX {
+            let source = [b"<?php ".as_slice(), &[byte]].concat();
+
+            match lexer.tokenize(&source) {
+                Ok(tokens) => assert!(tokens.len() > 1, "{:#04x}", byte),
+                Err(SyntaxError::UnrecognisedToken(unrecognised, span)) => {
+                    assert_eq!(unrecognised, byte);
+                    assert_eq!(span.position, 6);
+                    assert!(byte.is_ascii_control() && !byte.is_ascii_whitespace());
+                }
+                Err(error) => assert!(
+                    matches!(error, SyntaxError::UnexpectedEndOfFile(_)),
+                    "{:#04x}: {:?}",
+                    byte,
+                    error
+                ),
+            }
+
+            // every other state, which must not panic either.
+            for prefix in [
+                "\"$a",
+                "\"$a->",
+                "\"$a[",
+                "\"${",
+                "`$a",
+                "<<<EOF\n$a",
+                "<<<'EOF'\n",
+            ] {
+                let source = [b"<?php ", prefix.as_bytes(), &[byte]].concat();
+                let _ = lexer.tokenize(&source);
+            }
+        }
+
+        assert_eq!(
+            lexer.tokenize("<?php $a = 1;\x01"),
+            Err(SyntaxError::UnrecognisedToken(1, Span::new(1, 14, 13)))
+        );
+    }
+}
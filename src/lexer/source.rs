@@ -0,0 +1,264 @@
+//! A byte scanner that keeps track of the line, column and byte offset of its position.
+//!
+//! The lexer is built on top of it, but it knows nothing about PHP, so it can be used to
+//! scan other formats found in PHP code, such as docblock types.
+//!
+//! None of the methods panic: reads and peeks past the end of the input return fewer
+//! bytes (or none), and moving past the end stops at the end.
+//!
+//! ```rust
+//! use php_parser_rs::lexer::source::Source;
+//!
+//! let mut source = Source::new(b"key = value");
+//!
+//! let key = source.read_while(|b| b.is_ascii_alphanumeric());
+//! source.skip_while(|b| b == b' ');
+//! assert!(source.consume(b"="));
+//! assert_eq!(key, b"key");
+//! ```
+
+use crate::lexer::token::Span;
+
+#[derive(Debug, Clone)]
+pub struct Source<'a> {
+    input: &'a [u8],
+    length: usize,
+    span: Span,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        let length = input.len();
+
+        Self {
+            input,
+            length,
+            span: Span::new(1, 1, 0),
+        }
+    }
+
+    /// The whole input, regardless of the current position.
+    pub const fn input(&self) -> &'a [u8] {
+        self.input
+    }
+
+    /// The byte offset of the current position.
+    pub const fn position(&self) -> usize {
+        self.span.position
+    }
+
+    /// An empty span at the current position.
+    pub const fn span(&self) -> Span {
+        Span {
+            end_position: self.span.position,
+            ..self.span
+        }
+    }
+
+    /// The span from the start of the given span to the current position.
+    pub const fn span_from(&self, start: Span) -> Span {
+        Span {
+            end_position: self.span.position,
+            ..start
+        }
+    }
+
+    /// The input covered by the span, limited to the bounds of the input.
+    pub fn slice(&self, span: Span) -> &'a [u8] {
+        let until = span.end_position.min(self.length);
+
+        &self.input[span.position.min(until)..until]
+    }
+
+    /// Move back (or forward) to the start of a span returned by this source.
+    pub fn reset(&mut self, span: Span) {
+        self.span = Span::new(span.line, span.column, span.position.min(self.length));
+    }
+
+    pub const fn eof(&self) -> bool {
+        self.span.position >= self.length
+    }
+
+    /// Move past the current byte, doing nothing at the end of the input.
+    pub fn next(&mut self) {
+        if self.eof() {
+            return;
+        }
+
+        match self.input[self.span.position] {
+            b'\n' => {
+                self.span.line += 1;
+                self.span.column = 1;
+            }
+            _ => self.span.column += 1,
+        }
+
+        self.span.position += 1;
+    }
+
+    pub fn skip(&mut self, count: usize) {
+        for _ in 0..count {
+            self.next();
+        }
+    }
+
+    /// Move past the bytes matching the predicate.
+    pub fn skip_while(&mut self, mut predicate: impl FnMut(u8) -> bool) {
+        while self.current().is_some_and(|b| predicate(*b)) {
+            self.next();
+        }
+    }
+
+    /// Move past the given bytes if the input continues with them.
+    pub fn consume(&mut self, search: &[u8]) -> bool {
+        if !self.starts_with(search) {
+            return false;
+        }
+
+        self.skip(search.len());
+
+        true
+    }
+
+    /// Read up to `count` bytes and move past them.
+    pub fn read_and_skip(&mut self, count: usize) -> &'a [u8] {
+        let (from, until) = self.to_bound(count);
+
+        self.skip(count);
+
+        &self.input[from..until]
+    }
+
+    /// Read the bytes matching the predicate and move past them.
+    pub fn read_while(&mut self, predicate: impl FnMut(u8) -> bool) -> &'a [u8] {
+        let from = self.span.position;
+
+        self.skip_while(predicate);
+
+        &self.input[from..self.span.position]
+    }
+
+    pub fn current(&self) -> Option<&'a u8> {
+        if self.span.position >= self.length {
+            None
+        } else {
+            Some(&self.input[self.span.position])
+        }
+    }
+
+    /// Read up to `n` bytes, without moving.
+    pub fn read(&self, n: usize) -> &'a [u8] {
+        let (from, until) = self.to_bound(n);
+
+        &self.input[from..until]
+    }
+
+    #[inline(always)]
+    pub fn read_remaining(&self) -> &'a [u8] {
+        &self.input[(if self.span.position >= self.length {
+            self.length
+        } else {
+            self.span.position
+        })..]
+    }
+
+    pub fn at(&self, search: &[u8], len: usize) -> bool {
+        self.read(len) == search
+    }
+
+    pub fn at_case_insensitive(&self, search: &[u8], len: usize) -> bool {
+        let (from, until) = self.to_bound(len);
+
+        let slice = &self.input[from..until];
+
+        slice.eq_ignore_ascii_case(search)
+    }
+
+    /// Whether the input continues with the given bytes.
+    pub fn starts_with(&self, search: &[u8]) -> bool {
+        self.read_remaining().starts_with(search)
+    }
+
+    /// Read up to `n` bytes, starting `i` bytes after the current position, without moving.
+    pub fn peek(&self, i: usize, n: usize) -> &'a [u8] {
+        let from = self.span.position + i;
+        if from >= self.length {
+            return &self.input[self.length..self.length];
+        }
+
+        let mut until = from + n;
+        if until >= self.length {
+            until = self.length;
+        }
+
+        &self.input[from..until]
+    }
+
+    /// The byte `i` bytes after the current position.
+    pub fn peek_byte(&self, i: usize) -> Option<&'a u8> {
+        self.input.get(self.span.position + i)
+    }
+
+    pub fn peek_ignoring_whitespace(&self, i: usize, n: usize) -> &'a [u8] {
+        let mut i = i;
+
+        loop {
+            let c = self.peek(i, 1);
+
+            if c.is_empty() {
+                return &[];
+            }
+
+            match c[0] {
+                b' ' | b'\t' | b'\r' | b'\n' => i += 1,
+                _ => break,
+            }
+        }
+
+        self.peek(i, n)
+    }
+
+    /// An iterator over the remaining bytes and their spans, which moves the source past
+    /// every byte it returns.
+    pub fn bytes<'s>(&'s mut self) -> Bytes<'s, 'a> {
+        Bytes { source: self }
+    }
+
+    const fn to_bound(&self, n: usize) -> (usize, usize) {
+        if self.span.position >= self.length {
+            return (self.length, self.length);
+        }
+
+        let mut until = self.span.position + n;
+
+        if until >= self.length {
+            until = self.length;
+        }
+
+        (self.span.position, until)
+    }
+}
+
+/// The iterator returned by [`Source::bytes`].
+#[derive(Debug)]
+pub struct Bytes<'s, 'a> {
+    source: &'s mut Source<'a>,
+}
+
+impl<'s, 'a> Iterator for Bytes<'s, 'a> {
+    type Item = (Span, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.source.span();
+        let byte = *self.source.current()?;
+        self.source.next();
+
+        Some((self.source.span_from(start), byte))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.source.read_remaining().len();
+
+        (remaining, Some(remaining))
+    }
+}
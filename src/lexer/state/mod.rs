@@ -1,33 +1,45 @@
 use std::collections::VecDeque;
 
+use serde::Deserialize;
+use serde::Serialize;
+
 use crate::lexer::byte_string::ByteString;
 use crate::lexer::error::SyntaxError;
 use crate::lexer::error::SyntaxResult;
-use crate::lexer::state::source::Source;
-use crate::lexer::token::DocStringIndentationAmount;
-use crate::lexer::token::DocStringIndentationKind;
+use crate::lexer::source::Source;
 use crate::lexer::token::DocStringKind;
+use crate::lexer::token::LexerState;
+use crate::lexer::token::Span;
 
-pub mod source;
-
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 pub enum StackFrame {
     Initial,
     Scripting,
     Halted,
     DoubleQuote,
     ShellExec,
-    DocString(
-        DocStringKind,
-        ByteString,
-        DocStringIndentationKind,
-        DocStringIndentationAmount,
-    ),
+    DocString(DocStringKind, ByteString),
     LookingForVarname,
     LookingForProperty,
     VarOffset,
 }
 
+impl From<&StackFrame> for LexerState {
+    fn from(frame: &StackFrame) -> Self {
+        match frame {
+            StackFrame::Initial => LexerState::Initial,
+            StackFrame::Scripting => LexerState::Scripting,
+            StackFrame::Halted => LexerState::Halted,
+            StackFrame::DoubleQuote => LexerState::DoubleQuote,
+            StackFrame::ShellExec => LexerState::ShellExec,
+            StackFrame::DocString(kind, _) => LexerState::DocString(kind.clone()),
+            StackFrame::LookingForVarname => LexerState::LookingForVarname,
+            StackFrame::LookingForProperty => LexerState::LookingForProperty,
+            StackFrame::VarOffset => LexerState::VarOffset,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct State<'a> {
     pub source: Source<'a>,
@@ -42,6 +54,10 @@ impl<'a> State<'a> {
         }
     }
 
+    pub fn with_stack(source: Source<'a>, stack: VecDeque<StackFrame>) -> Self {
+        Self { source, stack }
+    }
+
     pub fn frame(&self) -> SyntaxResult<&StackFrame> {
         self.stack
             .back()
@@ -62,3 +78,50 @@ impl<'a> State<'a> {
         self.stack.pop_back();
     }
 }
+
+/// The state of the lexer at the start of a line, for tokenizing an input one line at a
+/// time with [`Lexer::tokenize_line`](crate::lexer::Lexer::tokenize_line).
+///
+/// A token that may continue on the next line, such as a multi-line comment or string, is
+/// not read until the line it ends on: its text so far is kept in the state, and read
+/// again along with the next line. An editor can stop re-tokenizing the lines after an
+/// edit as soon as the state at the start of one of them is
+/// [equivalent](LineState::is_equivalent) to the one it had before.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct LineState {
+    pub(crate) stack: Vec<StackFrame>,
+    // the text of the unfinished tokens at the end of the previous lines.
+    pub(crate) pending: ByteString,
+    // where `pending` starts, or the next line if nothing is pending.
+    pub(crate) span: Span,
+    pub(crate) started: bool,
+}
+
+impl LineState {
+    /// The state at the start of an input.
+    pub fn new() -> Self {
+        Self {
+            stack: vec![StackFrame::Initial],
+            pending: ByteString::default(),
+            span: Span::new(1, 1, 0),
+            started: false,
+        }
+    }
+
+    /// Whether both states tokenize the lines after them the same way, apart from the
+    /// position their spans start at.
+    pub fn is_equivalent(&self, other: &LineState) -> bool {
+        self.stack == other.stack && self.pending == other.pending && self.started == other.started
+    }
+
+    /// Whether the text of an unfinished token is waiting for the next line.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+}
+
+impl Default for LineState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -60,18 +60,21 @@ pub struct TokenStream<'a> {
     tokens: &'a [Token],
     length: usize,
     comments: Vec<&'a Token>,
+    // The index of the first collected comment that directly precedes the current token.
+    leading: usize,
     cursor: usize,
 }
 
 /// Token stream.
 impl<'a> TokenStream<'a> {
-    pub fn new(tokens: &'a [Token]) -> TokenStream {
+    pub fn new(tokens: &'a [Token]) -> TokenStream<'a> {
         let length = tokens.len();
 
         let mut stream = TokenStream {
             tokens,
             length,
             comments: vec![],
+            leading: 0,
             cursor: 0,
         };
 
@@ -99,6 +102,16 @@ impl<'a> TokenStream<'a> {
         &self.tokens[position]
     }
 
+    /// The index of the current token.
+    pub const fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// The tokens consumed since the given cursor.
+    pub fn consumed_since(&self, cursor: usize) -> &'a [Token] {
+        &self.tokens[cursor.min(self.length)..self.cursor.min(self.length)]
+    }
+
     /// Get previous token.
     pub const fn previous(&self) -> &'a Token {
         let position = if self.cursor == 0 { 0 } else { self.cursor - 1 };
@@ -169,54 +182,43 @@ impl<'a> TokenStream<'a> {
         let mut comments = vec![];
 
         std::mem::swap(&mut self.comments, &mut comments);
+        self.leading = 0;
 
         CommentGroup {
             comments: comments
                 .iter()
-                .map(|token| match token {
-                    Token {
-                        kind: TokenKind::SingleLineComment,
-                        span,
-                        value,
-                    } => Comment {
-                        span: *span,
-                        format: CommentFormat::SingleLine,
-                        content: value.clone(),
-                    },
-                    Token {
-                        kind: TokenKind::MultiLineComment,
-                        span,
-                        value,
-                    } => Comment {
-                        span: *span,
-                        format: CommentFormat::MultiLine,
-                        content: value.clone(),
-                    },
-                    Token {
-                        kind: TokenKind::HashMarkComment,
-                        span,
-                        value,
-                    } => Comment {
-                        span: *span,
-                        format: CommentFormat::HashMark,
-                        content: value.clone(),
-                    },
-                    Token {
-                        kind: TokenKind::DocumentComment,
-                        span,
-                        value,
-                    } => Comment {
-                        span: *span,
-                        format: CommentFormat::Document,
-                        content: value.clone(),
-                    },
-                    _ => unreachable!(),
+                .filter_map(|token| {
+                    let format = match token.kind {
+                        TokenKind::SingleLineComment => CommentFormat::SingleLine,
+                        TokenKind::MultiLineComment => CommentFormat::MultiLine,
+                        TokenKind::HashMarkComment => CommentFormat::HashMark,
+                        TokenKind::DocumentComment => CommentFormat::Document,
+                        _ => return None,
+                    };
+
+                    Some(Comment {
+                        span: token.span,
+                        format,
+                        content: token.value.clone(),
+                    })
                 })
                 .collect(),
         }
     }
 
+    /// Forget the collected comments that come before the previous token, keeping only
+    /// the ones directly preceding the current token.
+    ///
+    /// Comments inside of a statement that doesn't keep them would otherwise end up in
+    /// the next declaration that does.
+    pub fn discard_stale_comments(&mut self) {
+        self.comments.drain(..self.leading);
+        self.leading = 0;
+    }
+
     fn collect_comments(&mut self) {
+        self.leading = self.comments.len();
+
         loop {
             if self.cursor >= self.length {
                 break;
@@ -6,20 +6,45 @@ use std::fmt::Display;
 
 use crate::lexer::byte_string::ByteString;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Deserialize, Serialize, JsonSchema)]
 
 pub struct Span {
     pub line: usize,
     pub column: usize,
+    // The byte offset of the start of the span.
     pub position: usize,
+    // The byte offset just past the end of the span.
+    pub end_position: usize,
 }
 
 impl Span {
+    /// Create an empty span at the given position.
     pub fn new(line: usize, column: usize, position: usize) -> Self {
         Self {
             line,
             column,
             position,
+            end_position: position,
+        }
+    }
+
+    /// The number of bytes covered by the span.
+    pub fn length(&self) -> usize {
+        self.end_position - self.position
+    }
+
+    /// The smallest span covering both spans, starting at the line and column of the
+    /// earliest one.
+    pub fn join(self, other: Span) -> Span {
+        let start = if other.position < self.position {
+            other
+        } else {
+            self
+        };
+
+        Span {
+            end_position: self.end_position.max(other.end_position),
+            ..start
         }
     }
 }
@@ -50,22 +75,26 @@ pub enum DocStringIndentationKind {
     Both,
 }
 
-impl From<u8> for DocStringIndentationKind {
-    fn from(byte: u8) -> Self {
+impl TryFrom<u8> for DocStringIndentationKind {
+    type Error = ();
+
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
         match byte {
-            b' ' => Self::Space,
-            b'\t' => Self::Tab,
-            _ => unreachable!(),
+            b' ' => Ok(Self::Space),
+            b'\t' => Ok(Self::Tab),
+            _ => Err(()),
         }
     }
 }
 
-impl From<DocStringIndentationKind> for u8 {
-    fn from(kind: DocStringIndentationKind) -> Self {
+impl TryFrom<DocStringIndentationKind> for u8 {
+    type Error = ();
+
+    fn try_from(kind: DocStringIndentationKind) -> Result<Self, Self::Error> {
         match kind {
-            DocStringIndentationKind::Space => b' ',
-            DocStringIndentationKind::Tab => b'\t',
-            _ => unreachable!(),
+            DocStringIndentationKind::Space => Ok(b' '),
+            DocStringIndentationKind::Tab => Ok(b'\t'),
+            _ => Err(()),
         }
     }
 }
@@ -188,6 +217,7 @@ pub enum TokenKind {
     IncludeOnce,
     Increment,
     InlineHtml,
+    Shebang,
     Instanceof,
     Insteadof,
     Eval,
@@ -271,6 +301,52 @@ pub struct Token {
     pub value: ByteString,
 }
 
+/// A token together with the exact source text it was read from and the text that
+/// precedes it, so that a sequence of them reproduces the input byte for byte.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+
+pub struct TriviaToken {
+    pub token: Token,
+    // The source between the previous token and this one: whitespace, or the `();`
+    // following `__halt_compiler`.
+    pub leading_trivia: ByteString,
+    // The source text of the token, which may differ from its value, e.g. a string
+    // literal including its quotes.
+    pub text: ByteString,
+}
+
+/// The state the lexer was in when it read a token, which tells the text of a string
+/// apart from the code interpolated into it, e.g. in `"a {$b} c"`.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+
+pub enum LexerState {
+    // Inline HTML, outside of `<?php ... ?>`.
+    Initial,
+    Scripting,
+    // After `__halt_compiler();`.
+    Halted,
+    // Inside a double-quoted string containing variables.
+    DoubleQuote,
+    // Inside a `` `...` `` execution string.
+    ShellExec,
+    // Inside the body of a heredoc or nowdoc.
+    DocString(DocStringKind),
+    // After `${` in a string, expecting the name of a variable.
+    LookingForVarname,
+    // After a variable in a string, expecting `->` and the name of a property.
+    LookingForProperty,
+    // After `$a[` in a string, expecting the offset.
+    VarOffset,
+}
+
+/// A token together with the state the lexer was in when it read it.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+
+pub struct StateToken {
+    pub token: Token,
+    pub state: LexerState,
+}
+
 impl Default for Token {
     fn default() -> Self {
         Self {
@@ -295,8 +371,8 @@ impl Display for TokenKind {
             Self::Self_ => "self",
             Self::Parent => "parent",
             Self::Backtick => "`",
-            Self::StartDocString(_) => todo!(),
-            Self::EndDocString(..) => todo!(),
+            Self::StartDocString(_) => "<<<",
+            Self::EndDocString(..) => "[end of doc string]",
             Self::BangEquals => "!=",
             Self::From => "from",
             Self::Print => "print",
@@ -385,6 +461,7 @@ impl Display for TokenKind {
             Self::Implements => "implements",
             Self::Increment => "++",
             Self::InlineHtml => "InlineHtml",
+            Self::Shebang => "Shebang",
             Self::LiteralInteger => return write!(f, "integer literal"),
             Self::LeftBrace => "{",
             Self::LeftBracket => "[",
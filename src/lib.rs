@@ -1,9 +1,35 @@
+pub mod analysis;
+pub mod builder;
+pub mod config;
+pub mod diagnostic;
+pub mod docblock;
 pub mod downcast;
+pub mod evaluator;
+pub mod export;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+pub mod index;
+pub mod interop;
 pub mod lexer;
+pub mod lint;
 pub mod node;
 pub mod parser;
 pub mod printer;
+pub mod query;
+pub mod refactor;
+pub mod resolver;
+pub mod snippet;
+pub mod source_map;
 pub mod traverser;
+pub mod version;
 
 pub use lexer::stream::TokenStream;
-pub use parser::{construct, parse};
+pub use parser::bench::parse_bench;
+pub use parser::files::parse_files;
+pub use parser::{
+    construct, construct_with_config, construct_with_recovery, parse, parse_lossless,
+    parse_lossless_with_config, parse_with_config, parse_with_recovery,
+    parse_with_recovery_and_config, parse_with_tokens, parse_with_tokens_and_config, schema,
+    ParserConfig, DEFAULT_MAX_DEPTH,
+};
+pub use version::VERSION_INFO;
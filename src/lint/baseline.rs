@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::lint::Finding;
+
+/// A record of known findings, so that only new findings are reported when adopting
+/// the linter on existing code.
+///
+/// Findings are matched by file, rule, and message rather than by position, so that
+/// unrelated edits which move code around do not resurface them. Each entry counts
+/// how many identical findings are known; any beyond that count are new.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub entries: Vec<BaselineEntry>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub file: String,
+    pub rule: String,
+    pub message: String,
+    pub count: usize,
+}
+
+impl Baseline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the findings of the given file, replacing any previously recorded for it.
+    pub fn record(&mut self, file: &str, findings: &[Finding]) {
+        self.entries.retain(|entry| entry.file != file);
+
+        let mut counts: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+        for finding in findings {
+            *counts.entry((finding.rule, &finding.message)).or_default() += 1;
+        }
+
+        self.entries.extend(
+            counts
+                .into_iter()
+                .map(|((rule, message), count)| BaselineEntry {
+                    file: file.to_owned(),
+                    rule: rule.to_owned(),
+                    message: message.to_owned(),
+                    count,
+                }),
+        );
+        self.entries
+            .sort_by(|a, b| (&a.file, &a.rule, &a.message).cmp(&(&b.file, &b.rule, &b.message)));
+    }
+
+    /// Remove the findings of the given file that are part of the baseline, keeping
+    /// only new ones.
+    pub fn filter(&self, file: &str, findings: Vec<Finding>) -> Vec<Finding> {
+        let mut known: BTreeMap<(String, String), usize> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.file == file)
+            .map(|entry| ((entry.rule.clone(), entry.message.clone()), entry.count))
+            .collect();
+
+        findings
+            .into_iter()
+            .filter(|finding| {
+                match known.get_mut(&(finding.rule.to_owned(), finding.message.clone())) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        false
+                    }
+                    _ => true,
+                }
+            })
+            .collect()
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+
+        Ok(Self::from_json(&json)?)
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::lint::Severity;
+
+/// The severity a rule reports its findings with, or `off` to disable it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Off,
+    Info,
+    Warning,
+    Error,
+}
+
+impl RuleLevel {
+    pub fn severity(&self) -> Option<Severity> {
+        match self {
+            RuleLevel::Off => None,
+            RuleLevel::Info => Some(Severity::Info),
+            RuleLevel::Warning => Some(Severity::Warning),
+            RuleLevel::Error => Some(Severity::Error),
+        }
+    }
+}
+
+/// The configuration of the linter, usually loaded from a TOML file:
+///
+/// ```toml
+/// baseline = "lint-baseline.json"
+///
+/// [rules]
+/// nested-ternary = "error"
+/// call-arguments = "off"
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    // Severity overrides by rule name. Rules that are not listed keep the severity
+    // they report findings with.
+    #[serde(default)]
+    pub rules: HashMap<String, RuleLevel>,
+    // The baseline of known findings, see [`crate::lint::baseline::Baseline`]. When
+    // loaded from a file, the path is relative to the directory of that file.
+    pub baseline: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub enum LintConfigError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+}
+
+impl Display for LintConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LintConfigError::Io(error) => write!(f, "failed to read lint config: {}", error),
+            LintConfigError::Toml(error) => write!(f, "invalid lint config: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for LintConfigError {}
+
+impl LintConfig {
+    pub fn from_toml(source: &str) -> Result<Self, LintConfigError> {
+        toml::from_str(source).map_err(LintConfigError::Toml)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, LintConfigError> {
+        let source = std::fs::read_to_string(path).map_err(LintConfigError::Io)?;
+        let mut config = Self::from_toml(&source)?;
+
+        if let (Some(baseline), Some(directory)) = (&config.baseline, path.parent()) {
+            config.baseline = Some(directory.join(baseline));
+        }
+
+        Ok(config)
+    }
+
+    /// Apply the settings of a more specific config on top of this one. Rule levels
+    /// are merged by rule, and the other config's baseline replaces this one's.
+    pub fn merge(&mut self, other: LintConfig) {
+        self.rules.extend(other.rules);
+        if other.baseline.is_some() {
+            self.baseline = other.baseline;
+        }
+    }
+
+    /// The configured level of the given rule, if any.
+    pub fn level(&self, rule: &str) -> Option<RuleLevel> {
+        self.rules.get(rule).copied()
+    }
+}
@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+
+use crate::index::Index;
+use crate::lexer::keywords::PhpVersion;
+use crate::lexer::token::Span;
+use crate::lint::config::LintConfig;
+use crate::lint::config::RuleLevel;
+use crate::parser::ast::Program;
+use crate::snippet::code_frame;
+use crate::snippet::ColumnUnit;
+use crate::source_map::SourceMap;
+
+pub mod baseline;
+pub mod config;
+pub mod rules;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A problem reported by a lint rule.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Finding {
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+    pub fix: Option<Fix>,
+}
+
+/// A set of edits that resolve a finding when applied to the original source.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Fix {
+    pub description: String,
+    pub edits: Vec<Edit>,
+}
+
+/// Replace `length` bytes at `position` with `replacement`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Edit {
+    pub position: usize,
+    pub length: usize,
+    pub replacement: Vec<u8>,
+}
+
+impl Edit {
+    pub fn insert(position: usize, text: impl Into<Vec<u8>>) -> Self {
+        Self {
+            position,
+            length: 0,
+            replacement: text.into(),
+        }
+    }
+}
+
+/// Limits applied while collecting findings.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiagnosticsConfig {
+    /// Stop collecting after this many findings per file.
+    pub max_findings: Option<usize>,
+    /// Include a code frame with this many lines of context before and after each
+    /// finding when serializing findings with [`findings_to_json`]. Code frames are
+    /// left out by default, as they duplicate parts of the source.
+    pub code_frame_lines: Option<usize>,
+    /// The unit of the columns, and of the code frame highlights, when serializing
+    /// findings with [`findings_to_json`]. Bytes by default.
+    pub column_unit: ColumnUnit,
+}
+
+/// The findings collected for a single file, bounded by [`DiagnosticsConfig::max_findings`].
+#[derive(Debug, Clone, Default)]
+pub struct Findings {
+    findings: Vec<Finding>,
+    limit: Option<usize>,
+    dropped: bool,
+}
+
+impl Findings {
+    pub fn new(config: &DiagnosticsConfig) -> Self {
+        Self {
+            findings: vec![],
+            limit: config.max_findings,
+            dropped: false,
+        }
+    }
+
+    /// Add a finding, unless the limit has already been reached, in which case the
+    /// finding is discarded.
+    pub fn push(&mut self, finding: Finding) {
+        match self.limit {
+            Some(limit) if self.findings.len() >= limit => self.dropped = true,
+            _ => self.findings.push(finding),
+        }
+    }
+
+    /// Whether a finding has been discarded because the limit was reached. Rules should
+    /// use this to stop checking early.
+    ///
+    /// Reaching the limit alone is not enough, as a file with exactly as many findings
+    /// as the limit has not lost any of them.
+    pub fn is_truncated(&self) -> bool {
+        self.dropped
+    }
+
+    pub fn len(&self) -> usize {
+        self.findings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Finding> {
+        self.findings.iter()
+    }
+
+    pub fn into_vec(self) -> Vec<Finding> {
+        self.findings
+    }
+}
+
+/// Everything a rule has access to while checking a single file.
+#[derive(Debug, Clone, Copy)]
+pub struct Context<'a> {
+    pub source: &'a [u8],
+    pub index: &'a Index,
+    pub version: PhpVersion,
+}
+
+impl<'a> Context<'a> {
+    /// The whitespace that precedes the given span on its line.
+    pub fn indentation(&self, span: Span) -> &'a [u8] {
+        let start = self.source[..span.position]
+            .iter()
+            .rposition(|b| *b == b'\n')
+            .map(|position| position + 1)
+            .unwrap_or(0);
+
+        let line = &self.source[start..span.position];
+        let end = line
+            .iter()
+            .position(|b| *b != b' ' && *b != b'\t')
+            .unwrap_or(line.len());
+
+        &line[..end]
+    }
+}
+
+pub trait Rule {
+    fn name(&self) -> &'static str;
+
+    fn check(&self, context: &Context, program: &mut Program, findings: &mut Findings);
+}
+
+#[derive(Default)]
+pub struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+    config: DiagnosticsConfig,
+    levels: HashMap<String, RuleLevel>,
+}
+
+impl Linter {
+    pub const TRUNCATED: &'static str = "truncated";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rule(mut self, rule: impl Rule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    pub fn with_config(mut self, config: DiagnosticsConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Apply the rule levels of the given configuration, skipping rules that are
+    /// turned off and overriding the severity of the findings of the others.
+    pub fn with_lint_config(mut self, config: &LintConfig) -> Self {
+        self.levels = config.rules.clone();
+        self
+    }
+
+    /// Run all rules against the program, returning their findings ordered by position.
+    ///
+    /// When a finding is discarded because [`DiagnosticsConfig::max_findings`] was reached,
+    /// the remaining rules are skipped and a final finding of the [`Linter::TRUNCATED`] rule
+    /// is added at the position of the last finding kept.
+    pub fn check(&self, context: &Context, program: &mut Program) -> Vec<Finding> {
+        let mut findings = Findings::new(&self.config);
+        for rule in &self.rules {
+            if findings.is_truncated() {
+                break;
+            }
+
+            if self.levels.get(rule.name()) == Some(&RuleLevel::Off) {
+                continue;
+            }
+
+            rule.check(context, program, &mut findings);
+        }
+
+        let truncated = findings.is_truncated();
+        let mut findings = findings.into_vec();
+        for finding in &mut findings {
+            if let Some(severity) = self
+                .levels
+                .get(finding.rule)
+                .and_then(|level| level.severity())
+            {
+                finding.severity = severity;
+            }
+        }
+
+        findings.sort_by_key(|finding| finding.span.position);
+
+        if truncated {
+            if let Some(last) = findings.last() {
+                findings.push(Finding {
+                    rule: Self::TRUNCATED,
+                    severity: Severity::Info,
+                    message: format!("Stopped after {} findings", findings.len()),
+                    span: last.span,
+                    fix: None,
+                });
+            }
+        }
+
+        findings
+    }
+}
+
+/// Apply the fixes of the given findings to the source.
+///
+/// Edits that overlap an already applied edit are skipped.
+pub fn apply_fixes(source: &[u8], findings: &[Finding]) -> Vec<u8> {
+    apply_edits(
+        source,
+        findings
+            .iter()
+            .filter_map(|finding| finding.fix.as_ref())
+            .flat_map(|fix| fix.edits.iter()),
+    )
+}
+
+/// Apply the given edits to the source, in order of position.
+///
+/// Edits that overlap an already applied edit are skipped. Insertions at the same
+/// position are applied in the order given.
+pub fn apply_edits<'a>(source: &[u8], edits: impl IntoIterator<Item = &'a Edit>) -> Vec<u8> {
+    let mut edits: Vec<&Edit> = edits.into_iter().collect();
+    edits.sort_by_key(|edit| edit.position);
+
+    let mut result = Vec::with_capacity(source.len());
+    let mut cursor = 0;
+    for edit in edits {
+        if edit.position < cursor || edit.position + edit.length > source.len() {
+            continue;
+        }
+
+        result.extend_from_slice(&source[cursor..edit.position]);
+        result.extend_from_slice(&edit.replacement);
+        cursor = edit.position + edit.length;
+    }
+
+    result.extend_from_slice(&source[cursor..]);
+    result
+}
+
+/// Serialize findings to JSON, for consumers such as web dashboards.
+///
+/// When [`DiagnosticsConfig::code_frame_lines`] is set, each finding includes a
+/// `code_frame` with the surrounding lines, so it can be displayed without access to
+/// the source. The highlighted range covers the finding's span.
+pub fn findings_to_json(
+    source: &[u8],
+    findings: &[Finding],
+    config: &DiagnosticsConfig,
+) -> serde_json::Value {
+    let map = SourceMap::new(source);
+    let findings = findings
+        .iter()
+        .map(|finding| {
+            let mut json = serde_json::json!({
+                "rule": finding.rule,
+                "severity": match finding.severity {
+                    Severity::Info => "info",
+                    Severity::Warning => "warning",
+                    Severity::Error => "error",
+                },
+                "message": finding.message,
+                "line": finding.span.line,
+                "column": map.line_column(finding.span.position, config.column_unit).column,
+                "position": finding.span.position,
+                "fix": finding.fix.as_ref().map(|fix| &fix.description),
+            });
+
+            if let Some(context_lines) = config.code_frame_lines {
+                let length = finding
+                    .span
+                    .end_position
+                    .saturating_sub(finding.span.position);
+
+                let mut frame = code_frame(source, finding.span, length, context_lines);
+                for line in &mut frame.lines {
+                    if let Some((start, end)) = &mut line.highlight {
+                        // measured on the original line, as the text of the frame
+                        // replaces invalid UTF-8 sequences.
+                        let text = map.line_text(line.number).unwrap_or_default();
+                        *end = config.column_unit.width(&text[..(*end).min(text.len())]);
+                        *start = config.column_unit.width(&text[..(*start).min(text.len())]);
+                    }
+                }
+
+                json["code_frame"] = serde_json::json!(frame);
+            }
+
+            json
+        })
+        .collect();
+
+    serde_json::Value::Array(findings)
+}
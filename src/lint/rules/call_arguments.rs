@@ -0,0 +1,543 @@
+use crate::downcast::downcast;
+use crate::downcast::downcast_mut;
+use crate::export::first_span;
+use crate::index::names::NameContext;
+use crate::index::ParameterEntry;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::lint::Context;
+use crate::lint::Finding;
+use crate::lint::Findings;
+use crate::lint::Rule;
+use crate::lint::Severity;
+use crate::node::Node;
+use crate::parser::ast::arguments::Argument;
+use crate::parser::ast::classes::AnonymousClassExpression;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::declares::DeclareEntry;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::interfaces::InterfaceStatement;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::namespaces::BracedNamespace;
+use crate::parser::ast::namespaces::UnbracedNamespace;
+use crate::parser::ast::traits::TraitStatement;
+use crate::parser::ast::Expression;
+use crate::parser::ast::FunctionCallExpression;
+use crate::parser::ast::GroupUseStatement;
+use crate::parser::ast::NewExpression;
+use crate::parser::ast::Program;
+use crate::parser::ast::StaticMethodCallExpression;
+use crate::parser::ast::UseStatement;
+
+/// Validate the arguments of function calls, static method calls and `new` expressions
+/// against the signature of the callee, as found in the index.
+///
+/// Scalar literals are checked against scalar parameter types, following the coercion
+/// rules of the calling file: with `declare(strict_types=1)` only an `int` may be passed
+/// where a `float` is expected, otherwise numeric strings, numbers and booleans are
+/// converted as PHP would.
+///
+/// Calls with a dynamic callee, or a callee that is not present in the index, are skipped.
+#[derive(Debug, Clone, Default)]
+pub struct CallArguments;
+
+impl CallArguments {
+    pub const NAME: &'static str = "call-arguments";
+}
+
+impl Rule for CallArguments {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn check(&self, context: &Context, program: &mut Program, findings: &mut Findings) {
+        let mut walker = Walker {
+            context,
+            names: NameContext::default(),
+            class: None,
+            strict_types: false,
+            findings,
+        };
+
+        for statement in program.iter_mut() {
+            walker.walk(statement);
+        }
+    }
+}
+
+struct Walker<'a, 'b> {
+    context: &'b Context<'a>,
+    names: NameContext,
+    // The class-like `self` and `parent` refer to, if known.
+    class: Option<ByteString>,
+    // Whether the file declares `strict_types=1`.
+    strict_types: bool,
+    findings: &'b mut Findings,
+}
+
+impl<'a, 'b> Walker<'a, 'b> {
+    fn walk(&mut self, node: &mut dyn Node) {
+        if self.findings.is_truncated() {
+            return;
+        }
+
+        if let Some(namespace) = downcast::<UnbracedNamespace>(node) {
+            self.names.enter_namespace(Some(&namespace.name.value));
+        } else if let Some(namespace) = downcast::<BracedNamespace>(node) {
+            self.names
+                .enter_namespace(namespace.name.as_ref().map(|name| &name.value));
+        } else if let Some(r#use) = downcast::<UseStatement>(node) {
+            self.names.add_use(r#use);
+        } else if let Some(r#use) = downcast::<GroupUseStatement>(node) {
+            self.names.add_group_use(r#use);
+        } else if let Some(entry) = downcast::<DeclareEntry>(node) {
+            if entry.key.value.eq_ignore_ascii_case(b"strict_types") {
+                self.strict_types =
+                    matches!(&entry.value, Literal::Integer(value) if value.value == b"1");
+            }
+        }
+
+        let class = if let Some(class) = downcast::<ClassStatement>(node) {
+            Some(Some(self.names.declare(&class.name.value)))
+        } else if let Some(interface) = downcast::<InterfaceStatement>(node) {
+            Some(Some(self.names.declare(&interface.name.value)))
+        } else if let Some(r#enum) = downcast::<UnitEnumStatement>(node) {
+            Some(Some(self.names.declare(&r#enum.name.value)))
+        } else if let Some(r#enum) = downcast::<BackedEnumStatement>(node) {
+            Some(Some(self.names.declare(&r#enum.name.value)))
+        } else if downcast::<TraitStatement>(node).is_some()
+            || downcast::<AnonymousClassExpression>(node).is_some()
+        {
+            // `self` depends on the class using the trait, and anonymous classes are not indexed.
+            Some(None)
+        } else {
+            None
+        };
+
+        if let Some(call) = downcast_mut::<FunctionCallExpression>(node) {
+            self.function_call(call);
+        } else if let Some(call) = downcast_mut::<StaticMethodCallExpression>(node) {
+            self.static_method_call(call);
+        } else if let Some(new) = downcast_mut::<NewExpression>(node) {
+            self.new_expression(new);
+        }
+
+        let previous = class.map(|class| std::mem::replace(&mut self.class, class));
+        for child in node.children() {
+            self.walk(child);
+        }
+
+        if let Some(previous) = previous {
+            self.class = previous;
+        }
+    }
+
+    fn function_call(&mut self, call: &mut FunctionCallExpression) {
+        let identifier = match call.target.as_ref() {
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => identifier,
+            _ => return,
+        };
+
+        let index = self.context.index;
+        let function = match self
+            .names
+            .resolve_function(&identifier.value)
+            .iter()
+            .find_map(|name| index.function(name))
+        {
+            Some(function) => function,
+            None => return,
+        };
+
+        let callee = format!("function {}()", function.name);
+        let span = identifier.span;
+        self.arguments(
+            &callee,
+            span,
+            &function.parameters,
+            &mut call.arguments.arguments,
+        );
+    }
+
+    fn static_method_call(&mut self, call: &mut StaticMethodCallExpression) {
+        let method = match &call.method {
+            Identifier::SimpleIdentifier(method) => method,
+            Identifier::DynamicIdentifier(_) => return,
+        };
+
+        // `static::` depends on the runtime class, so it is not checked.
+        let class = match self.class_name(&call.target) {
+            Some(class) => class,
+            None => return,
+        };
+
+        let index = self.context.index;
+        let entry = match index.find_method(&class, &method.value) {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        let callee = format!("method {}::{}()", class, entry.name);
+        let span = method.span;
+        self.arguments(
+            &callee,
+            span,
+            &entry.parameters,
+            &mut call.arguments.arguments,
+        );
+    }
+
+    fn new_expression(&mut self, new: &mut NewExpression) {
+        let span = match new.target.as_ref() {
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => identifier.span,
+            _ => new.new,
+        };
+
+        let class = match self.class_name(&new.target) {
+            Some(class) => class,
+            None => return,
+        };
+
+        let index = self.context.index;
+        let constructor = match index.find_method(&class, b"__construct") {
+            Some(constructor) => constructor,
+            None => return,
+        };
+
+        let callee = format!("method {}::{}()", class, constructor.name);
+        let arguments = match &mut new.arguments {
+            Some(arguments) => &mut arguments.arguments[..],
+            None => &mut [],
+        };
+
+        self.arguments(&callee, span, &constructor.parameters, arguments);
+    }
+
+    fn class_name(&self, target: &Expression) -> Option<ByteString> {
+        match target {
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                Some(self.names.resolve_class(&identifier.value))
+            }
+            Expression::Self_(_) => self.class.clone(),
+            Expression::Parent(_) => {
+                let class = self.context.index.class(self.class.as_ref()?)?;
+                class.parent.clone()
+            }
+            _ => None,
+        }
+    }
+
+    fn arguments(
+        &mut self,
+        callee: &str,
+        span: Span,
+        parameters: &[ParameterEntry],
+        arguments: &mut [Argument],
+    ) {
+        let variadic = parameters.last().filter(|parameter| parameter.is_variadic);
+        let mut positional = 0;
+        let mut unpacked = false;
+        let mut named: Vec<&ParameterEntry> = vec![];
+        let mut seen_named = false;
+
+        for argument in arguments.iter_mut() {
+            match argument {
+                Argument::Positional(argument) => {
+                    if seen_named {
+                        let message = match argument.ellipsis {
+                            Some(_) => "Cannot use argument unpacking after named arguments",
+                            None => "Cannot use positional argument after named argument",
+                        };
+
+                        let span = first_span(&mut argument.value).unwrap_or(span);
+                        self.report(Severity::Error, message.to_owned(), span);
+                        continue;
+                    }
+
+                    if argument.ellipsis.is_some() {
+                        unpacked = true;
+                        continue;
+                    }
+
+                    let parameter = parameters.get(positional).or(variadic);
+                    positional += 1;
+
+                    if let Some(parameter) = parameter {
+                        self.argument(callee, span, positional, parameter, &mut argument.value);
+                    }
+                }
+                Argument::Named(argument) => {
+                    seen_named = true;
+
+                    let name = &argument.name.value;
+                    let position = match parameters
+                        .iter()
+                        .position(|parameter| &parameter.name == name && !parameter.is_variadic)
+                    {
+                        Some(position) => position,
+                        // Unknown named arguments are collected by a variadic parameter.
+                        None if variadic.is_some() => continue,
+                        None => {
+                            self.report(
+                                Severity::Error,
+                                format!("Unknown named parameter ${} passed to {}", name, callee),
+                                argument.name.span,
+                            );
+                            continue;
+                        }
+                    };
+
+                    let parameter = &parameters[position];
+                    if (position < positional && !unpacked)
+                        || named.iter().any(|named| named.name == parameter.name)
+                    {
+                        self.report(
+                            Severity::Error,
+                            format!("Named parameter ${} overwrites previous argument", name),
+                            argument.name.span,
+                        );
+                        continue;
+                    }
+
+                    named.push(parameter);
+                    self.argument(callee, span, position + 1, parameter, &mut argument.value);
+                }
+            }
+        }
+
+        // The number of arguments passed using `...` is only known at runtime.
+        if unpacked {
+            return;
+        }
+
+        let passed = positional + named.len();
+        if positional > parameters.len() && variadic.is_none() {
+            self.report(
+                Severity::Warning,
+                format!(
+                    "Too many arguments to {}, {} passed and at most {} expected",
+                    callee,
+                    passed,
+                    parameters.len()
+                ),
+                span,
+            );
+        }
+
+        let required = parameters
+            .iter()
+            .rposition(|parameter| !parameter.is_optional)
+            .map(|position| position + 1)
+            .unwrap_or(0);
+
+        let missing = parameters[..required]
+            .iter()
+            .enumerate()
+            .any(|(position, parameter)| {
+                position >= positional && !named.iter().any(|named| named.name == parameter.name)
+            });
+
+        if missing {
+            let bound = if required == parameters.len() && variadic.is_none() {
+                "exactly"
+            } else {
+                "at least"
+            };
+
+            self.report(
+                Severity::Error,
+                format!(
+                    "Too few arguments to {}, {} passed and {} {} expected",
+                    callee, passed, bound, required
+                ),
+                span,
+            );
+        }
+    }
+
+    fn argument(
+        &mut self,
+        callee: &str,
+        span: Span,
+        position: usize,
+        parameter: &ParameterEntry,
+        value: &mut Expression,
+    ) {
+        if parameter.is_by_reference && !is_referenceable(value) {
+            let span = first_span(value).unwrap_or(span);
+            self.report(
+                Severity::Error,
+                format!(
+                    "{}: Argument #{} (${}) could not be passed by reference",
+                    callee, position, parameter.name
+                ),
+                span,
+            );
+        }
+
+        let (data_type, scalar) = match (&parameter.data_type, Scalar::from_expression(value)) {
+            (Some(data_type), Some(scalar)) => (data_type, scalar),
+            _ => return,
+        };
+
+        // A `null` default makes the parameter implicitly nullable.
+        if scalar == Scalar::Null && parameter.has_null_default {
+            return;
+        }
+
+        if !accepts(data_type, scalar, value, self.strict_types) {
+            let span = first_span(value).unwrap_or(span);
+            self.report(
+                Severity::Error,
+                format!(
+                    "{}: Argument #{} (${}) must be of type {}, {} given",
+                    callee,
+                    position,
+                    parameter.name,
+                    data_type,
+                    scalar.name()
+                ),
+                span,
+            );
+        }
+    }
+
+    fn report(&mut self, severity: Severity, message: String, span: Span) {
+        self.findings.push(Finding {
+            rule: CallArguments::NAME,
+            severity,
+            message,
+            span,
+            fix: None,
+        });
+    }
+}
+
+// Whether the expression can be passed by reference. Calls are allowed, as
+// they may return a reference.
+fn is_referenceable(expression: &Expression) -> bool {
+    matches!(
+        expression,
+        Expression::Variable(_)
+            | Expression::ArrayIndex(_)
+            | Expression::PropertyFetch(_)
+            | Expression::StaticPropertyFetch(_)
+            | Expression::Parenthesized(_)
+            | Expression::FunctionCall(_)
+            | Expression::MethodCall(_)
+            | Expression::NullsafeMethodCall(_)
+            | Expression::StaticMethodCall(_)
+            | Expression::StaticVariableMethodCall(_)
+            | Expression::New(_)
+    )
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Scalar {
+    Int,
+    Float,
+    String,
+    Bool,
+    Null,
+}
+
+impl Scalar {
+    fn from_expression(expression: &Expression) -> Option<Self> {
+        match expression {
+            Expression::Literal(Literal::Integer(_)) => Some(Self::Int),
+            Expression::Literal(Literal::Float(_)) => Some(Self::Float),
+            Expression::Literal(Literal::String(_)) => Some(Self::String),
+            Expression::Bool(_) => Some(Self::Bool),
+            Expression::Null(_) => Some(Self::Null),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Int => "int",
+            Self::Float => "float",
+            Self::String => "string",
+            Self::Bool => "bool",
+            Self::Null => "null",
+        }
+    }
+}
+
+// Whether a parameter of the given type accepts the scalar. Types that can not be
+// checked without knowing about classes are assumed to accept anything.
+fn accepts(data_type: &Type, scalar: Scalar, value: &Expression, strict_types: bool) -> bool {
+    match data_type {
+        Type::Nullable(_, inner) => {
+            scalar == Scalar::Null || accepts(inner, scalar, value, strict_types)
+        }
+        Type::Union(types) => types
+            .iter()
+            .any(|inner| accepts(inner, scalar, value, strict_types)),
+        Type::Null(_) => scalar == Scalar::Null,
+        Type::True(_) | Type::False(_) | Type::Boolean(_) if scalar == Scalar::Bool => true,
+        Type::Integer(_) if scalar == Scalar::Int => true,
+        Type::Float(_) if matches!(scalar, Scalar::Int | Scalar::Float) => true,
+        Type::String(_) | Type::Callable(_) if scalar == Scalar::String => true,
+        Type::Boolean(_) | Type::Integer(_) | Type::Float(_) | Type::String(_) => {
+            if strict_types || scalar == Scalar::Null {
+                return false;
+            }
+
+            match (data_type, scalar) {
+                (Type::Integer(_) | Type::Float(_), Scalar::String) => match value {
+                    Expression::Literal(Literal::String(string)) => is_numeric(&string.value),
+                    _ => true,
+                },
+                _ => true,
+            }
+        }
+        Type::True(_)
+        | Type::False(_)
+        | Type::Array(_)
+        | Type::Iterable(_)
+        | Type::Callable(_)
+        | Type::Object(_)
+        | Type::Void(_)
+        | Type::Never(_) => false,
+        _ => true,
+    }
+}
+
+// Whether the string is numeric, following PHP's `is_numeric()`.
+fn is_numeric(value: &[u8]) -> bool {
+    let value = value.trim_ascii();
+    let value = value
+        .strip_prefix(b"-")
+        .or_else(|| value.strip_prefix(b"+"))
+        .unwrap_or(value);
+
+    let (mantissa, exponent) = match value.iter().position(|b| *b == b'e' || *b == b'E') {
+        Some(position) => (&value[..position], Some(&value[position + 1..])),
+        None => (value, None),
+    };
+
+    let mut parts = mantissa.splitn(2, |b| *b == b'.');
+    let integer = parts.next().unwrap_or_default();
+    let fraction = parts.next().unwrap_or_default();
+    let digits = |part: &[u8]| part.iter().all(u8::is_ascii_digit);
+
+    if integer.is_empty() && fraction.is_empty() || !digits(integer) || !digits(fraction) {
+        return false;
+    }
+
+    match exponent {
+        Some(exponent) => {
+            let exponent = exponent
+                .strip_prefix(b"-")
+                .or_else(|| exponent.strip_prefix(b"+"))
+                .unwrap_or(exponent);
+
+            !exponent.is_empty() && digits(exponent)
+        }
+        None => true,
+    }
+}
@@ -0,0 +1,497 @@
+use std::collections::HashSet;
+
+use crate::analysis::attributes::declaration_attributes;
+use crate::downcast::downcast;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::TokenKind;
+use crate::lexer::Lexer;
+use crate::lint::Context;
+use crate::lint::Edit;
+use crate::lint::Finding;
+use crate::lint::Findings;
+use crate::lint::Fix;
+use crate::lint::Rule;
+use crate::lint::Severity;
+use crate::node::Node;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::constant::ConstantStatement;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceStatement;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::traits::TraitStatement;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::parser::ast::Use;
+use crate::parser::ast::UseKind;
+
+/// Validate the `use` imports of each namespace, reporting:
+///
+/// - the same name imported under the same alias more than once.
+/// - different names imported under the same alias.
+/// - imports whose alias clashes with a declaration of the namespace itself.
+/// - imports that are never used.
+///
+/// Duplicate and unused imports come with a fix removing them.
+///
+/// Usage is determined by name only: any identifier or type whose first segment matches
+/// an alias counts as a use of the import, as does any word of a docblock, so that imports
+/// only referenced by `@var` or `@param` tags are kept.
+#[derive(Debug, Clone, Default)]
+pub struct Imports;
+
+impl Imports {
+    pub const NAME: &'static str = "imports";
+}
+
+impl Rule for Imports {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn check(&self, context: &Context, program: &mut Program, findings: &mut Findings) {
+        let docblocks = docblock_references(context.source);
+
+        let mut global = vec![];
+        for statement in program.iter_mut() {
+            match statement {
+                Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => check_namespace(
+                    context,
+                    Some(&namespace.name.value),
+                    namespace.statements.iter_mut().collect(),
+                    &docblocks,
+                    findings,
+                ),
+                Statement::Namespace(NamespaceStatement::Braced(namespace)) => check_namespace(
+                    context,
+                    namespace.name.as_ref().map(|name| &name.value),
+                    namespace.body.statements.iter_mut().collect(),
+                    &docblocks,
+                    findings,
+                ),
+                statement => global.push(statement),
+            }
+        }
+
+        check_namespace(context, None, global, &docblocks, findings);
+    }
+}
+
+// A single name imported by a `use` statement.
+struct Import<'a> {
+    kind: UseKind,
+    // The fully qualified name, without a leading backslash.
+    name: ByteString,
+    alias: ByteString,
+    // The alias if given, otherwise the name, used to report findings.
+    identifier: &'a SimpleIdentifier,
+    // The statement the import belongs to, and the bytes removing only this import
+    // from it, or the whole statement.
+    statement: usize,
+    removal: (usize, usize),
+    statement_removal: (usize, usize),
+}
+
+impl<'a> Import<'a> {
+    fn describe(&self) -> String {
+        match self.kind {
+            UseKind::Normal => format!("`{}`", self.name),
+            UseKind::Function => format!("function `{}`", self.name),
+            UseKind::Const => format!("constant `{}`", self.name),
+        }
+    }
+}
+
+fn check_namespace(
+    context: &Context,
+    namespace: Option<&ByteString>,
+    mut statements: Vec<&mut Statement>,
+    docblocks: &HashSet<Vec<u8>>,
+    findings: &mut Findings,
+) {
+    let mut declarations = Declarations {
+        namespace: namespace
+            .map(|namespace| namespace.strip_prefix(b"\\").unwrap_or(namespace).into()),
+        ..Declarations::default()
+    };
+    let mut references = docblocks.clone();
+    for statement in statements.iter_mut() {
+        collect(*statement, &mut declarations, &mut references);
+    }
+
+    let mut imports = vec![];
+    for (index, statement) in statements.iter().enumerate() {
+        match &**statement {
+            Statement::Use(statement) => {
+                imports.extend(imports_of(
+                    context.source,
+                    index,
+                    &statement.kind,
+                    None,
+                    &statement.uses,
+                ));
+            }
+            Statement::GroupUse(statement) => {
+                imports.extend(imports_of(
+                    context.source,
+                    index,
+                    &statement.kind,
+                    Some(&statement.prefix),
+                    &statement.uses,
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    // the findings for each import, and whether the import should be removed.
+    let mut reported: Vec<Option<(Finding, bool)>> = Vec::with_capacity(imports.len());
+    for (position, import) in imports.iter().enumerate() {
+        let previous = imports[..position].iter().find(|previous| {
+            previous.kind == import.kind && previous.alias.eq_ignore_ascii_case(&import.alias)
+        });
+
+        let finding = |severity, message| Finding {
+            rule: Imports::NAME,
+            severity,
+            message,
+            span: import.identifier.span,
+            fix: None,
+        };
+
+        reported.push(match previous {
+            Some(previous) if previous.name.eq_ignore_ascii_case(&import.name) => Some((
+                finding(
+                    Severity::Error,
+                    format!("{} is already imported", import.describe()),
+                ),
+                true,
+            )),
+            Some(_) => Some((
+                finding(
+                    Severity::Error,
+                    format!(
+                        "Cannot import {} as `{}` because the name is already in use",
+                        import.describe(),
+                        import.alias
+                    ),
+                ),
+                false,
+            )),
+            None => match declarations.conflict(import) {
+                Some(declaration) => Some((
+                    finding(
+                        Severity::Error,
+                        format!(
+                            "Cannot import {} as `{}` because the namespace declares {}",
+                            import.describe(),
+                            import.alias,
+                            declaration
+                        ),
+                    ),
+                    false,
+                )),
+                None if !references.contains(&import.alias.to_ascii_lowercase()) => Some((
+                    finding(
+                        Severity::Warning,
+                        format!("{} is imported, but never used", import.describe()),
+                    ),
+                    true,
+                )),
+                None => None,
+            },
+        });
+    }
+
+    let removed: Vec<bool> = reported
+        .iter()
+        .map(|reported| matches!(reported, Some((_, true))))
+        .collect();
+
+    for (import, reported) in imports.iter().zip(reported) {
+        let Some((mut finding, remove)) = reported else {
+            continue;
+        };
+
+        if remove {
+            // once every import of a statement is removed, remove the statement itself.
+            let whole = imports
+                .iter()
+                .zip(&removed)
+                .filter(|(other, _)| other.statement == import.statement)
+                .all(|(_, removed)| *removed);
+
+            let (start, end) = if whole {
+                import.statement_removal
+            } else {
+                import.removal
+            };
+
+            finding.fix = Some(Fix {
+                description: format!("remove the import of {}", import.describe()),
+                edits: vec![Edit {
+                    position: start,
+                    length: end - start,
+                    replacement: vec![],
+                }],
+            });
+        }
+
+        findings.push(finding);
+    }
+}
+
+// Names declared by the statements of a namespace.
+#[derive(Debug, Default)]
+struct Declarations {
+    namespace: Option<ByteString>,
+    classes: Vec<ByteString>,
+    functions: Vec<ByteString>,
+    constants: Vec<ByteString>,
+}
+
+impl Declarations {
+    // The declaration an import clashes with, if any. Importing a name declared in the
+    // namespace itself is allowed, as both refer to the same symbol.
+    fn conflict(&self, import: &Import) -> Option<String> {
+        let (declarations, kind) = match import.kind {
+            UseKind::Normal => (&self.classes, "class"),
+            UseKind::Function => (&self.functions, "function"),
+            UseKind::Const => (&self.constants, "constant"),
+        };
+
+        // constant names are case-sensitive, unlike class and function names.
+        let equals = |a: &[u8], b: &[u8]| match import.kind {
+            UseKind::Const => a == b,
+            _ => a.eq_ignore_ascii_case(b),
+        };
+
+        declarations
+            .iter()
+            .find(|declared| {
+                let qualified = match &self.namespace {
+                    Some(namespace) => {
+                        let mut qualified = namespace.clone();
+                        qualified.extend(b"\\");
+                        qualified.extend(declared.iter());
+                        qualified
+                    }
+                    None => (*declared).clone(),
+                };
+
+                equals(&import.alias, declared) && !equals(&import.name, &qualified)
+            })
+            .map(|declared| format!("{} `{}`", kind, declared))
+    }
+}
+
+fn collect(
+    node: &mut dyn Node,
+    declarations: &mut Declarations,
+    references: &mut HashSet<Vec<u8>>,
+) {
+    if let Some(statement) = downcast::<Statement>(node) {
+        if matches!(statement, Statement::Use(_) | Statement::GroupUse(_)) {
+            return;
+        }
+    }
+
+    if let Some(groups) = declaration_attributes(node) {
+        for attribute in groups.iter().flat_map(|group| group.iter()) {
+            reference(&attribute.name.value, references);
+        }
+    }
+
+    let class = if let Some(class) = downcast::<ClassStatement>(node) {
+        Some(&class.name)
+    } else if let Some(interface) = downcast::<InterfaceStatement>(node) {
+        Some(&interface.name)
+    } else if let Some(r#trait) = downcast::<TraitStatement>(node) {
+        Some(&r#trait.name)
+    } else if let Some(r#enum) = downcast::<UnitEnumStatement>(node) {
+        Some(&r#enum.name)
+    } else {
+        downcast::<BackedEnumStatement>(node).map(|r#enum| &r#enum.name)
+    };
+
+    if let Some(name) = class {
+        declarations.classes.push(name.value.clone());
+    } else if let Some(function) = downcast::<FunctionStatement>(node) {
+        declarations.functions.push(function.name.value.clone());
+    } else if let Some(constant) = downcast::<ConstantStatement>(node) {
+        declarations.constants.extend(
+            constant
+                .entries
+                .iter()
+                .map(|entry| entry.name.value.clone()),
+        );
+    } else if let Some(identifier) = downcast::<SimpleIdentifier>(node) {
+        reference(&identifier.value, references);
+    } else if let Some(Identifier::SimpleIdentifier(identifier)) = downcast::<Identifier>(node) {
+        reference(&identifier.value, references);
+    } else if let Some(Type::Named(_, name)) = downcast::<Type>(node) {
+        reference(name, references);
+    }
+
+    for child in node.children() {
+        collect(child, declarations, references);
+    }
+}
+
+// Record the first segment of a name, which is the part an import would resolve.
+fn reference(name: &[u8], references: &mut HashSet<Vec<u8>>) {
+    if name.starts_with(b"\\") {
+        return;
+    }
+
+    let first = match name.iter().position(|b| *b == b'\\') {
+        Some(position) => &name[..position],
+        None => name,
+    };
+
+    references.insert(first.to_ascii_lowercase());
+}
+
+// Every word of the docblocks of the file, which may refer to imported names.
+fn docblock_references(source: &[u8]) -> HashSet<Vec<u8>> {
+    let mut references = HashSet::new();
+    let Ok(tokens) = Lexer::new().tokenize(source) else {
+        return references;
+    };
+
+    for token in tokens {
+        if token.kind != TokenKind::DocumentComment {
+            continue;
+        }
+
+        let words = token
+            .value
+            .split(|b| !(b.is_ascii_alphanumeric() || matches!(b, b'_' | b'\\') || *b >= 0x80));
+
+        for word in words.filter(|word| !word.is_empty()) {
+            reference(word, &mut references);
+        }
+    }
+
+    references
+}
+
+fn imports_of<'a>(
+    source: &[u8],
+    statement: usize,
+    kind: &UseKind,
+    prefix: Option<&SimpleIdentifier>,
+    uses: &'a [Use],
+) -> Vec<Import<'a>> {
+    let first = match (prefix, uses.first()) {
+        (Some(prefix), _) => prefix.span.position,
+        (None, Some(r#use)) => r#use.name.span.position,
+        (None, None) => return vec![],
+    };
+
+    let last = uses.last().map_or(first, end);
+    let statement_removal = statement_range(source, first, last);
+    let starts: Vec<usize> = uses.iter().map(|r#use| start(source, r#use)).collect();
+
+    uses.iter()
+        .enumerate()
+        .map(|(position, r#use)| {
+            let mut name = match prefix {
+                Some(prefix) => {
+                    // the prefix includes the trailing backslash.
+                    let mut name = prefix.value.clone();
+                    name.extend(r#use.name.value.iter());
+                    name
+                }
+                None => r#use.name.value.clone(),
+            };
+
+            if let Some(stripped) = name.strip_prefix(b"\\") {
+                name = stripped.into();
+            }
+
+            let alias = match &r#use.alias {
+                Some(alias) => alias.value.clone(),
+                None => match name.iter().rposition(|b| *b == b'\\') {
+                    Some(separator) => name[separator + 1..].into(),
+                    None => name.clone(),
+                },
+            };
+
+            // `use A, B;` removes `A, ` for `A`, and `, B` for `B`.
+            let removal = match starts.get(position + 1) {
+                Some(next) => (starts[position], *next),
+                None if position > 0 => (end(&uses[position - 1]), end(r#use)),
+                None => statement_removal,
+            };
+
+            Import {
+                kind: r#use.kind.clone().unwrap_or_else(|| kind.clone()),
+                name,
+                alias,
+                identifier: r#use.alias.as_ref().unwrap_or(&r#use.name),
+                statement,
+                removal,
+                statement_removal,
+            }
+        })
+        .collect()
+}
+
+// The position of an import within its statement, including a `function` or `const`
+// keyword of a mixed group use.
+fn start(source: &[u8], r#use: &Use) -> usize {
+    let position = r#use.name.span.position;
+    if r#use.kind.is_none() {
+        return position;
+    }
+
+    let before = source[..position].trim_ascii_end();
+    before
+        .iter()
+        .rposition(|b| !b.is_ascii_alphabetic())
+        .map_or(0, |separator| separator + 1)
+}
+
+fn end(r#use: &Use) -> usize {
+    let identifier = r#use.alias.as_ref().unwrap_or(&r#use.name);
+
+    identifier.span.position + identifier.value.len()
+}
+
+// The bytes of a `use` statement, from the `use` keyword before `first` to the
+// semicolon after `last`, along with its indentation and the rest of the line if
+// the statement is on a line of its own.
+fn statement_range(source: &[u8], first: usize, last: usize) -> (usize, usize) {
+    let mut start = source[..first]
+        .windows(3)
+        .rposition(|window| window.eq_ignore_ascii_case(b"use"))
+        .unwrap_or(first);
+
+    let mut end = source[last..]
+        .iter()
+        .position(|b| *b == b';')
+        .map_or(last, |semicolon| last + semicolon + 1);
+
+    let line_start = source[..start]
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map_or(0, |newline| newline + 1);
+    let line_end = source[end..]
+        .iter()
+        .position(|b| *b == b'\n')
+        .map_or(source.len(), |newline| end + newline + 1);
+
+    if source[line_start..start].trim_ascii().is_empty()
+        && source[end..line_end].trim_ascii().is_empty()
+    {
+        start = line_start;
+        end = line_end;
+    }
+
+    (start, end)
+}
@@ -0,0 +1,103 @@
+use crate::downcast::downcast;
+use crate::lint::Context;
+use crate::lint::Finding;
+use crate::lint::Findings;
+use crate::lint::Rule;
+use crate::lint::Severity;
+use crate::node::Node;
+use crate::parser::ast::functions::ArrowFunctionExpression;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::Program;
+
+/// Report uses of `$this` where no object is bound: inside free functions, static
+/// methods, and static closures or arrow functions, including any closures nested
+/// within them.
+///
+/// Code outside of any function is not checked, as it may be included from a method.
+#[derive(Debug, Clone, Default)]
+pub struct InvalidThis;
+
+impl InvalidThis {
+    pub const NAME: &'static str = "invalid-this";
+}
+
+impl Rule for InvalidThis {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn check(&self, _: &Context, program: &mut Program, findings: &mut Findings) {
+        for statement in program.iter_mut() {
+            walk(statement, Scope::Unknown, findings);
+        }
+    }
+}
+
+// Whether `$this` is available in the current scope, and if not, why.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    Unknown,
+    Object,
+    Function,
+    StaticMethod,
+    StaticClosure,
+    StaticArrowFunction,
+}
+
+fn walk(node: &mut dyn Node, scope: Scope, findings: &mut Findings) {
+    if findings.is_truncated() {
+        return;
+    }
+
+    let scope = if downcast::<FunctionStatement>(node).is_some() {
+        Scope::Function
+    } else if let Some(method) = downcast::<ConcreteMethod>(node) {
+        if method.modifiers.has_static() {
+            Scope::StaticMethod
+        } else {
+            Scope::Object
+        }
+    } else if downcast::<ConcreteConstructor>(node).is_some() {
+        Scope::Object
+    } else if let Some(closure) = downcast::<ClosureExpression>(node) {
+        match closure.r#static {
+            Some(_) => Scope::StaticClosure,
+            None => scope,
+        }
+    } else if let Some(function) = downcast::<ArrowFunctionExpression>(node) {
+        match function.r#static {
+            Some(_) => Scope::StaticArrowFunction,
+            None => scope,
+        }
+    } else {
+        scope
+    };
+
+    if let Some(variable) = downcast::<SimpleVariable>(node) {
+        let context = match scope {
+            Scope::Function => Some("a function"),
+            Scope::StaticMethod => Some("a static method"),
+            Scope::StaticClosure => Some("a static closure"),
+            Scope::StaticArrowFunction => Some("a static arrow function"),
+            Scope::Unknown | Scope::Object => None,
+        };
+
+        if let Some(context) = context.filter(|_| variable.name == b"$this") {
+            findings.push(Finding {
+                rule: InvalidThis::NAME,
+                severity: Severity::Error,
+                message: format!("Cannot use $this inside {}", context),
+                span: variable.span,
+                fix: None,
+            });
+        }
+    }
+
+    for child in node.children() {
+        walk(child, scope, findings);
+    }
+}
@@ -0,0 +1,13 @@
+mod call_arguments;
+mod imports;
+mod invalid_this;
+mod nested_ternary;
+mod override_attribute;
+mod uninitialized_property;
+
+pub use call_arguments::CallArguments;
+pub use imports::Imports;
+pub use invalid_this::InvalidThis;
+pub use nested_ternary::NestedTernary;
+pub use override_attribute::OverrideAttribute;
+pub use uninitialized_property::UninitializedProperty;
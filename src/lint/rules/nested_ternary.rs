@@ -0,0 +1,93 @@
+use crate::downcast::downcast;
+use crate::lexer::keywords::PhpVersion;
+use crate::lexer::token::Span;
+use crate::lint::Context;
+use crate::lint::Finding;
+use crate::lint::Findings;
+use crate::lint::Rule;
+use crate::lint::Severity;
+use crate::node::Node;
+use crate::parser::ast::Expression;
+use crate::parser::ast::Program;
+use crate::parser::ast::ShortTernaryExpression;
+use crate::parser::ast::TernaryExpression;
+
+/// Report nested ternary expressions that are not explicitly parenthesized, such as
+/// `$a ? $b : $c ? $d : $e`.
+///
+/// PHP 7.4 deprecates them, and PHP 8.0 rejects them, as the left associativity PHP
+/// used to apply differs from every other language. Chained short ternaries, such as
+/// `$a ?: $b ?: $c`, behave the same either way and are allowed.
+#[derive(Debug, Clone, Default)]
+pub struct NestedTernary;
+
+impl NestedTernary {
+    pub const NAME: &'static str = "nested-ternary";
+}
+
+impl Rule for NestedTernary {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn check(&self, context: &Context, program: &mut Program, findings: &mut Findings) {
+        for statement in program.iter_mut() {
+            walk(context, statement, findings);
+        }
+    }
+}
+
+fn walk(context: &Context, node: &mut dyn Node, findings: &mut Findings) {
+    if findings.is_truncated() {
+        return;
+    }
+
+    let nested = if let Some(ternary) = downcast::<TernaryExpression>(node) {
+        nested_operator(&ternary.r#else, is_short(ternary))
+    } else if let Some(ternary) = downcast::<ShortTernaryExpression>(node) {
+        nested_operator(&ternary.r#else, true)
+    } else {
+        None
+    };
+
+    if let Some(span) = nested {
+        let (severity, message) = if context.version >= PhpVersion::Php80 {
+            (
+                Severity::Error,
+                "Unparenthesized `a ? b : c ? d : e` is not supported, use either `(a ? b : c) ? d : e` or `a ? b : (c ? d : e)`",
+            )
+        } else {
+            (
+                Severity::Warning,
+                "Nested ternary expressions without explicit parentheses are deprecated",
+            )
+        };
+
+        findings.push(Finding {
+            rule: NestedTernary::NAME,
+            severity,
+            message: message.to_owned(),
+            span,
+            fix: None,
+        });
+    }
+
+    for child in node.children() {
+        walk(context, child, findings);
+    }
+}
+
+// The operator of a ternary nested in the `else` branch of another, unless the nesting
+// is allowed.
+fn nested_operator(r#else: &Expression, short: bool) -> Option<Span> {
+    match r#else {
+        Expression::Ternary(ternary) if !(short && is_short(ternary)) => Some(ternary.question),
+        Expression::ShortTernary(ternary) if !short => Some(ternary.question_colon),
+        _ => None,
+    }
+}
+
+// `foo() ? : bar()` behaves like a short ternary.
+fn is_short(ternary: &TernaryExpression) -> bool {
+    matches!(ternary.then.as_ref(), Expression::Noop)
+}
@@ -0,0 +1,128 @@
+use crate::analysis::views::ClassLikeKind;
+use crate::index::walk_class_likes;
+use crate::index::ClassLikeEntry;
+use crate::index::Index;
+use crate::lexer::keywords::PhpVersion;
+use crate::lint::Context;
+use crate::lint::Edit;
+use crate::lint::Finding;
+use crate::lint::Findings;
+use crate::lint::Fix;
+use crate::lint::Rule;
+use crate::lint::Severity;
+use crate::parser::ast::modifiers::Visibility;
+use crate::parser::ast::Program;
+
+/// Verify that methods marked with `#[\Override]` actually override a method
+/// of a parent class or an interface, and optionally suggest adding the attribute
+/// to methods that do override one.
+///
+/// Classes whose inheritance chain can not be fully resolved using the index are skipped.
+#[derive(Debug, Clone, Default)]
+pub struct OverrideAttribute {
+    pub suggest_missing: bool,
+}
+
+impl OverrideAttribute {
+    pub const NAME: &'static str = "override-attribute";
+}
+
+impl Rule for OverrideAttribute {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn check(&self, context: &Context, program: &mut Program, findings: &mut Findings) {
+        walk_class_likes(program, &mut |names, view| {
+            if findings.is_truncated() {
+                return;
+            }
+
+            // Attributes on trait methods are verified in the class using the trait.
+            if view.kind == ClassLikeKind::Trait {
+                return;
+            }
+
+            let entry = match context.index.class(&names.declare(&view.name.value)) {
+                Some(entry) => entry,
+                None => return,
+            };
+
+            let (inherited, traits) = match overridable(context.index, entry) {
+                Some(ancestors) => ancestors,
+                None => return,
+            };
+
+            for method in &view.methods {
+                let name = &method.name.value;
+                let overrides = inherited.iter().any(|ancestor| {
+                    matches!(ancestor.method(name), Some(method) if method.visibility != Visibility::Private)
+                }) || traits.iter().any(|ancestor| {
+                    matches!(ancestor.method(name), Some(method) if method.is_abstract)
+                });
+
+                if method.flags.r#override && !overrides {
+                    findings.push(Finding {
+                        rule: Self::NAME,
+                        severity: Severity::Error,
+                        message: format!(
+                            "{}::{}() has #[\\Override] attribute, but no matching parent method exists",
+                            view.name, name
+                        ),
+                        span: method.name.span,
+                        fix: None,
+                    });
+                }
+
+                if !method.flags.r#override
+                    && overrides
+                    && self.suggest_missing
+                    && context.version >= PhpVersion::Php83
+                    && !name.eq_ignore_ascii_case(b"__construct")
+                {
+                    let start = method.start();
+                    let mut attribute = b"#[\\Override]\n".to_vec();
+                    attribute.extend_from_slice(context.indentation(start));
+
+                    findings.push(Finding {
+                        rule: Self::NAME,
+                        severity: Severity::Info,
+                        message: format!(
+                            "{}::{}() overrides a parent method, but is missing the #[\\Override] attribute",
+                            view.name, name
+                        ),
+                        span: method.name.span,
+                        fix: Some(Fix {
+                            description: "add the #[\\Override] attribute".to_owned(),
+                            edits: vec![Edit::insert(start.position, attribute)],
+                        }),
+                    });
+                }
+            }
+        });
+    }
+}
+
+/// Split the ancestors of a class-like into those whose methods can be overridden
+/// (parent classes and interfaces), and the traits used by the class-like itself,
+/// whose abstract methods can be implemented.
+fn overridable<'a>(
+    index: &'a Index,
+    entry: &ClassLikeEntry,
+) -> Option<(Vec<&'a ClassLikeEntry>, Vec<&'a ClassLikeEntry>)> {
+    let mut inherited = vec![];
+    for name in entry.parent.iter().chain(entry.interfaces.iter()) {
+        let ancestor = index.class(name)?;
+        inherited.push(ancestor);
+        inherited.extend(index.ancestors(ancestor)?);
+    }
+
+    let mut traits = vec![];
+    for name in &entry.traits {
+        let ancestor = index.class(name)?;
+        traits.push(ancestor);
+        traits.extend(index.ancestors(ancestor)?);
+    }
+
+    Some((inherited, traits))
+}
@@ -0,0 +1,462 @@
+use std::collections::HashSet;
+
+use crate::downcast::downcast;
+use crate::downcast::downcast_mut;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::lint::Context;
+use crate::lint::Finding;
+use crate::lint::Findings;
+use crate::lint::Rule;
+use crate::lint::Severity;
+use crate::node::Node;
+use crate::parser::ast::classes::AnonymousClassExpression;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::control_flow::IfStatementBody;
+use crate::parser::ast::declares::DeclareBody;
+use crate::parser::ast::functions::ArrowFunctionExpression;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::loops::ForStatementBody;
+use crate::parser::ast::loops::ForeachStatementBody;
+use crate::parser::ast::loops::WhileStatementBody;
+use crate::parser::ast::operators::AssignmentOperationExpression;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::Expression;
+use crate::parser::ast::Program;
+use crate::parser::ast::PropertyFetchExpression;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StaticMethodCallExpression;
+
+/// Report typed properties without a default value that a class constructor may
+/// return without assigning, leaving them uninitialized.
+///
+/// The check is intra-class and best-effort: it follows `if`, `switch`, `try`, and
+/// loop statements in the constructor body, treating `throw` and `exit` as ending a
+/// path, and `return` as completing one. Loop bodies may not run at all. Once the
+/// constructor hands `$this` to other code, such as by calling a method or the
+/// parent constructor, the class is assumed to be fully initialized.
+#[derive(Debug, Clone, Default)]
+pub struct UninitializedProperty;
+
+impl UninitializedProperty {
+    pub const NAME: &'static str = "uninitialized-property";
+}
+
+impl Rule for UninitializedProperty {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn check(&self, _: &Context, program: &mut Program, findings: &mut Findings) {
+        for statement in program.iter_mut() {
+            walk(statement, findings);
+        }
+    }
+}
+
+fn walk(node: &mut dyn Node, findings: &mut Findings) {
+    if findings.is_truncated() {
+        return;
+    }
+
+    if let Some(class) = downcast_mut::<ClassStatement>(node) {
+        check_class(class, findings);
+    }
+
+    for child in node.children() {
+        walk(child, findings);
+    }
+}
+
+// A typed property that must be assigned by the constructor.
+struct Tracked {
+    name: ByteString,
+    span: Span,
+    readonly: bool,
+}
+
+fn check_class(class: &mut ClassStatement, findings: &mut Findings) {
+    let mut tracked = vec![];
+    for member in class.body.members.iter() {
+        let (r#type, readonly, entries) = match member {
+            ClassMember::Property(property) if !property.modifiers.has_static() => (
+                &property.r#type,
+                property.modifiers.has_readonly(),
+                &property.entries,
+            ),
+            ClassMember::VariableProperty(property) => (&property.r#type, false, &property.entries),
+            _ => continue,
+        };
+
+        if r#type.is_none() {
+            continue;
+        }
+
+        for entry in entries {
+            if let PropertyEntry::Uninitialized { variable } = entry {
+                tracked.push(Tracked {
+                    // strip the leading `$`.
+                    name: variable.name[1..].into(),
+                    span: variable.span,
+                    readonly,
+                });
+            }
+        }
+    }
+
+    if tracked.is_empty() {
+        return;
+    }
+
+    let constructor = class
+        .body
+        .members
+        .iter_mut()
+        .find_map(|member| match member {
+            ClassMember::ConcreteConstructor(constructor) => Some(constructor),
+            _ => None,
+        });
+
+    let constructor = match constructor {
+        Some(constructor) => constructor,
+        None => return,
+    };
+
+    let mut analysis = Analysis::default();
+    let end = analysis.block(&mut constructor.body.statements, HashSet::new());
+    if analysis.escaped {
+        return;
+    }
+
+    // The properties assigned on every path that leaves the constructor normally.
+    let mut paths = analysis.returned;
+    paths.extend(end);
+
+    let mut assigned = match paths.pop() {
+        Some(assigned) => assigned,
+        None => return,
+    };
+    for path in paths {
+        assigned.retain(|name| path.contains(name));
+    }
+
+    for property in tracked {
+        if assigned.contains(&property.name) {
+            continue;
+        }
+
+        findings.push(Finding {
+            rule: UninitializedProperty::NAME,
+            severity: Severity::Warning,
+            message: format!(
+                "{} property {}::${} may be left uninitialized by the constructor",
+                if property.readonly {
+                    "Readonly"
+                } else {
+                    "Typed"
+                },
+                class.name.value,
+                property.name
+            ),
+            span: property.span,
+            fix: None,
+        });
+    }
+}
+
+type Assigned = HashSet<ByteString>;
+
+#[derive(Default)]
+struct Analysis {
+    // The properties assigned at each `return` statement.
+    returned: Vec<Assigned>,
+    // Whether `$this` was passed to code that may assign properties itself.
+    escaped: bool,
+}
+
+impl Analysis {
+    // Returns the properties assigned once the statements complete, or `None` if
+    // they never do.
+    fn block(&mut self, statements: &mut [Statement], assigned: Assigned) -> Option<Assigned> {
+        let mut assigned = assigned;
+        for statement in statements {
+            assigned = self.statement(statement, assigned)?;
+        }
+
+        Some(assigned)
+    }
+
+    fn statement(&mut self, statement: &mut Statement, assigned: Assigned) -> Option<Assigned> {
+        let mut assigned = assigned;
+        match statement {
+            Statement::Expression(statement) => {
+                self.expression(&mut statement.expression, &mut assigned);
+                if matches!(
+                    statement.expression,
+                    Expression::Throw(_) | Expression::Exit(_) | Expression::Die(_)
+                ) {
+                    return None;
+                }
+            }
+            Statement::Return(statement) => {
+                if let Some(value) = &mut statement.value {
+                    self.expression(value, &mut assigned);
+                }
+                self.returned.push(assigned);
+
+                return None;
+            }
+            Statement::Block(block) => return self.block(&mut block.statements, assigned),
+            Statement::If(statement) => {
+                self.expression(&mut statement.condition, &mut assigned);
+
+                let mut branches = vec![];
+                let mut has_else = false;
+                match &mut statement.body {
+                    IfStatementBody::Statement {
+                        statement,
+                        elseifs,
+                        r#else,
+                    } => {
+                        branches.push(self.statement(statement, assigned.clone()));
+                        for elseif in elseifs {
+                            self.expression(&mut elseif.condition, &mut assigned);
+                            branches.push(self.statement(&mut elseif.statement, assigned.clone()));
+                        }
+                        if let Some(r#else) = r#else {
+                            has_else = true;
+                            branches.push(self.statement(&mut r#else.statement, assigned.clone()));
+                        }
+                    }
+                    IfStatementBody::Block {
+                        statements,
+                        elseifs,
+                        r#else,
+                        ..
+                    } => {
+                        branches.push(self.block(statements, assigned.clone()));
+                        for elseif in elseifs {
+                            self.expression(&mut elseif.condition, &mut assigned);
+                            branches.push(self.block(&mut elseif.statements, assigned.clone()));
+                        }
+                        if let Some(r#else) = r#else {
+                            has_else = true;
+                            branches.push(self.block(&mut r#else.statements, assigned.clone()));
+                        }
+                    }
+                }
+
+                if !has_else {
+                    branches.push(Some(assigned));
+                }
+
+                return merge(branches);
+            }
+            Statement::Switch(statement) => {
+                self.expression(&mut statement.condition, &mut assigned);
+
+                // Cases are treated as separate branches, ignoring fallthrough.
+                let mut branches = vec![];
+                let mut has_default = false;
+                for case in statement.body.cases_mut() {
+                    match &mut case.condition {
+                        Some(condition) => self.expression(condition, &mut assigned),
+                        None => has_default = true,
+                    }
+                    branches.push(self.block(&mut case.body, assigned.clone()));
+                }
+
+                if !has_default {
+                    branches.push(Some(assigned));
+                }
+
+                return merge(branches);
+            }
+            Statement::Try(statement) => {
+                // Any statement in the `try` block may throw before its assignments run.
+                let mut branches = vec![self.block(&mut statement.body, assigned.clone())];
+                for catch in statement.catches.iter_mut() {
+                    branches.push(self.block(&mut catch.body, assigned.clone()));
+                }
+
+                let merged = merge(branches);
+
+                return match &mut statement.finally {
+                    Some(finally) => {
+                        let after =
+                            self.block(&mut finally.body, merged.clone().unwrap_or(assigned))?;
+                        merged.map(|_| after)
+                    }
+                    None => merged,
+                };
+            }
+            Statement::DoWhile(statement) => {
+                assigned = self.statement(&mut statement.body, assigned)?;
+                self.expression(&mut statement.condition, &mut assigned);
+            }
+            Statement::While(statement) => {
+                self.expression(&mut statement.condition, &mut assigned);
+                match &mut statement.body {
+                    WhileStatementBody::Statement { statement } => {
+                        self.statement(statement, assigned.clone());
+                    }
+                    WhileStatementBody::Block { statements, .. } => {
+                        self.block(statements, assigned.clone());
+                    }
+                }
+            }
+            Statement::For(statement) => {
+                for initialization in statement.iterator.initializations.inner.iter_mut() {
+                    self.expression(initialization, &mut assigned);
+                }
+                match &mut statement.body {
+                    ForStatementBody::Statement { statement } => {
+                        self.statement(statement, assigned.clone());
+                    }
+                    ForStatementBody::Block { statements, .. } => {
+                        self.block(statements, assigned.clone());
+                    }
+                }
+            }
+            Statement::Foreach(statement) => {
+                self.expression(&mut statement.iterator, &mut assigned);
+                match &mut statement.body {
+                    ForeachStatementBody::Statement { statement } => {
+                        self.statement(statement, assigned.clone());
+                    }
+                    ForeachStatementBody::Block { statements, .. } => {
+                        self.block(statements, assigned.clone());
+                    }
+                }
+            }
+            Statement::Declare(statement) => match &mut statement.body {
+                DeclareBody::Braced { statements, .. } | DeclareBody::Block { statements, .. } => {
+                    return self.block(statements, assigned);
+                }
+                body => self.expression(body, &mut assigned),
+            },
+            // Statements such as `break` are treated as no-ops, so assignments that
+            // follow them in the same block are still counted.
+            statement => self.expression(statement, &mut assigned),
+        }
+
+        Some(assigned)
+    }
+
+    fn expression(&mut self, node: &mut dyn Node, assigned: &mut Assigned) {
+        // Closures and anonymous classes are not run as part of the constructor, but
+        // a closure may still capture `$this`.
+        if downcast::<AnonymousClassExpression>(node).is_some() {
+            return;
+        }
+
+        if downcast::<ClosureExpression>(node).is_some()
+            || downcast::<ArrowFunctionExpression>(node).is_some()
+        {
+            self.escaped |= uses_this(node);
+
+            return;
+        }
+
+        if let Some(variable) = downcast::<SimpleVariable>(node) {
+            if variable.name == b"$this" {
+                self.escaped = true;
+            }
+
+            return;
+        }
+
+        if let Some(fetch) = downcast::<PropertyFetchExpression>(node) {
+            if this_property(fetch).is_some() {
+                return;
+            }
+        }
+
+        if let Some(call) = downcast::<StaticMethodCallExpression>(node) {
+            if matches!(
+                call.target.as_ref(),
+                Expression::Parent(_) | Expression::Self_(_) | Expression::Static(_)
+            ) {
+                self.escaped = true;
+            }
+        }
+
+        if let Some(
+            AssignmentOperationExpression::Assign { left, right, .. }
+            | AssignmentOperationExpression::Coalesce { left, right, .. },
+        ) = downcast_mut::<AssignmentOperationExpression>(node)
+        {
+            self.expression(right.as_mut(), assigned);
+            self.expression(left.as_mut(), assigned);
+
+            let mut properties = vec![];
+            assignment_targets(left.as_mut(), &mut properties);
+            assigned.extend(properties);
+
+            return;
+        }
+
+        for child in node.children() {
+            self.expression(child, assigned);
+        }
+    }
+}
+
+fn uses_this(node: &mut dyn Node) -> bool {
+    if let Some(variable) = downcast::<SimpleVariable>(node) {
+        return variable.name == b"$this";
+    }
+
+    node.children().into_iter().any(uses_this)
+}
+
+// The names of the `$this->name` properties that an assignment target writes to,
+// including those within `list()` and `[...]` destructuring.
+fn assignment_targets(node: &mut dyn Node, properties: &mut Vec<ByteString>) {
+    if let Some(fetch) = downcast::<PropertyFetchExpression>(node) {
+        if let Some(name) = this_property(fetch) {
+            properties.push(name);
+        }
+
+        return;
+    }
+
+    if let Some(expression) = downcast::<Expression>(node) {
+        if !matches!(
+            expression,
+            Expression::List(_) | Expression::PropertyFetch(_)
+        ) {
+            return;
+        }
+    }
+
+    for child in node.children() {
+        assignment_targets(child, properties);
+    }
+}
+
+// The name of the property in a `$this->name` fetch.
+fn this_property(fetch: &PropertyFetchExpression) -> Option<ByteString> {
+    match (fetch.target.as_ref(), fetch.property.as_ref()) {
+        (
+            Expression::Variable(Variable::SimpleVariable(variable)),
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)),
+        ) if variable.name == b"$this" => Some(identifier.value.clone()),
+        _ => None,
+    }
+}
+
+// The properties assigned on every branch that completes, or `None` if none do.
+fn merge(branches: Vec<Option<Assigned>>) -> Option<Assigned> {
+    let mut branches = branches.into_iter().flatten();
+    let mut assigned = branches.next()?;
+    for branch in branches {
+        assigned.retain(|name| branch.contains(name));
+    }
+
+    Some(assigned)
+}
@@ -1,36 +1,221 @@
 use clap::Parser;
+use clap::ValueEnum;
+use php_parser_rs::analysis::features::FeatureStats;
+use php_parser_rs::config::Config;
+use php_parser_rs::config::ConfigResolver;
+use php_parser_rs::diagnostic::diagnostics_to_json;
+use php_parser_rs::diagnostic::diagnostics_to_sarif;
+use php_parser_rs::diagnostic::Diagnostic;
+use php_parser_rs::index::Index;
+use php_parser_rs::lint::rules::CallArguments;
+use php_parser_rs::lint::rules::Imports;
+use php_parser_rs::lint::rules::InvalidThis;
+use php_parser_rs::lint::rules::NestedTernary;
+use php_parser_rs::lint::rules::OverrideAttribute;
+use php_parser_rs::lint::rules::UninitializedProperty;
+use php_parser_rs::lint::Context;
+use php_parser_rs::lint::Linter;
+use php_parser_rs::parser::ast::Program;
+use php_parser_rs::parser::incremental::reparse;
+use php_parser_rs::parser::incremental::TextEdit;
+use php_parser_rs::refactor::Refactoring;
+use php_parser_rs::snippet::ColumnUnit;
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
 use std::io::Result;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
 
 #[derive(Parser, Default, Debug)]
 #[clap(version, about = "A PHP Parser")]
 struct Arguments {
-    file: String,
+    #[clap(required_unless_present = "schema")]
+    file: Option<PathBuf>,
     #[clap(short, long)]
     /// Don't print anything
     silent: bool,
     #[clap(short, long)]
     /// Print as json
     json: bool,
+    #[clap(long)]
+    /// Print only the ast with --json, instead of an object holding it along with the version of the parser that produced it
+    without_version: bool,
+    #[clap(long, value_name = "FORMAT")]
+    /// Print the ast in the given format
+    format: Option<FormatArgument>,
+    #[clap(long)]
+    /// Print TypeScript definitions for the classes and enums
+    typescript: bool,
+    #[clap(long)]
+    /// Print how often language features are used in the file, or in the PHP files of the directory
+    features: bool,
+    #[clap(short, long, num_args = 0.., value_name = "PATHS")]
+    /// Watch the file, and any additional files or directories, re-validating them on change
+    watch: Option<Vec<String>>,
+    #[clap(long, value_name = "REFACTORING")]
+    /// Print the file with the given refactoring applied
+    refactor: Option<RefactoringArgument>,
+    #[clap(long)]
+    /// Print the file without comments and unnecessary whitespace
+    minify: bool,
+    #[clap(long)]
+    /// Print the JSON Schema of the ast printed with --json
+    schema: bool,
+    #[clap(long, value_name = "FORMAT", default_value = "human")]
+    /// Print syntax errors in the given format
+    error_format: ErrorFormatArgument,
+    #[clap(long)]
+    /// Don't color syntax error reports, which are only colored when printed to a terminal
+    no_color: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum FormatArgument {
+    /// The Rust debug representation of the ast
+    Debug,
+    /// The ast as json, the same as --json
+    Json,
+    /// The json dump format of nikic/php-parser
+    NikicJson,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Default)]
+enum ErrorFormatArgument {
+    /// A report with an excerpt of the source, colored when printed to a terminal
+    #[default]
+    Human,
+    /// A json array of diagnostics, with their file, span, code, and severity
+    Json,
+    /// A SARIF log, as read by code scanning tools
+    Sarif,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum RefactoringArgument {
+    ClosureToArrowFunction,
+    ArrowFunctionToClosure,
+}
+
+impl From<RefactoringArgument> for Refactoring {
+    fn from(argument: RefactoringArgument) -> Self {
+        match argument {
+            RefactoringArgument::ClosureToArrowFunction => Refactoring::ClosureToArrowFunction,
+            RefactoringArgument::ArrowFunctionToClosure => Refactoring::ArrowFunctionToClosure,
+        }
+    }
 }
 
 fn main() -> Result<()> {
     let args = Arguments::parse();
 
-    let file = args.file;
-    let contents = std::fs::read_to_string(&file)?;
+    if args.schema {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&php_parser_rs::schema())?
+        );
+
+        return Ok(());
+    }
+
+    // clap ensures the file is given unless --schema is passed.
+    let file = args.file.unwrap_or_default();
+    let colored = !args.no_color && std::io::stdout().is_terminal();
+
+    if let Some(paths) = args.watch {
+        let mut paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+        paths.insert(0, file);
+
+        return watch(&paths, colored);
+    }
+
+    if args.features {
+        return features(&file, args.json);
+    }
+
+    // files are read as bytes, since PHP source does not have to be valid UTF-8.
+    let contents = std::fs::read(&file)?;
+
+    if args.minify {
+        match php_parser_rs::printer::minify(&contents) {
+            // written as is, since string literals may hold any bytes.
+            Ok(output) => std::io::stdout().lock().write_all(&output)?,
+            Err(error) => {
+                print_diagnostics(
+                    &file,
+                    &contents,
+                    &[Diagnostic::from(&error)],
+                    args.error_format,
+                    colored,
+                )?;
+
+                std::process::exit(1);
+            }
+        }
+
+        return Ok(());
+    }
     let silent = args.silent;
-    let print_json = args.json;
+    let format = match args.format {
+        Some(format) => format,
+        None if args.json => FormatArgument::Json,
+        None => FormatArgument::Debug,
+    };
+
+    let config = match ConfigResolver::new().resolve(&file) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{}", error);
+
+            std::process::exit(1);
+        }
+    };
+
+    match php_parser_rs::parse_with_config(&contents, config.parser_config()) {
+        Ok(mut ast) => {
+            if let Some(refactoring) = args.refactor {
+                let fixes = Refactoring::from(refactoring).fixes(&contents, &mut ast);
+                let output = php_parser_rs::refactor::apply(&contents, &fixes);
+                std::io::stdout().lock().write_all(&output)?;
+
+                return Ok(());
+            }
 
-    match php_parser_rs::parse(&contents) {
-        Ok(ast) => {
             // if --silent is passed, don't print anything
             if silent {
                 return Ok(());
             }
 
-            // if --json is passed, print as json
-            if print_json {
-                match serde_json::to_string_pretty(&ast) {
+            if args.typescript {
+                print!(
+                    "{}",
+                    php_parser_rs::export::typescript::typescript_definitions(&ast)
+                );
+
+                return Ok(());
+            }
+
+            if format == FormatArgument::NikicJson {
+                let output = php_parser_rs::interop::nikic::to_json(&contents, &ast);
+                println!("{}", serde_json::to_string_pretty(&output)?);
+
+                return Ok(());
+            }
+
+            // if --json is passed, print as json, along with the parser version unless --without-version is passed
+            if format == FormatArgument::Json {
+                let output = if args.without_version {
+                    serde_json::to_string_pretty(&ast)
+                } else {
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "version_info": php_parser_rs::VERSION_INFO,
+                        "ast": ast,
+                    }))
+                };
+
+                match output {
                     Ok(json) => println!("{}", json),
                     Err(error) => {
                         eprintln!("Failed to convert ast to json: {}", error);
@@ -44,7 +229,13 @@ fn main() -> Result<()> {
             }
         }
         Err(error) => {
-            println!("{}", error.report(&contents, Some(&file), true, false)?);
+            print_diagnostics(
+                &file,
+                &contents,
+                &error.diagnostics(),
+                args.error_format,
+                colored,
+            )?;
 
             std::process::exit(1);
         }
@@ -52,3 +243,284 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+fn print_diagnostics(
+    path: &Path,
+    contents: &[u8],
+    diagnostics: &[Diagnostic],
+    format: ErrorFormatArgument,
+    colored: bool,
+) -> Result<()> {
+    let path = path.display().to_string();
+
+    match format {
+        ErrorFormatArgument::Human => {
+            let source = String::from_utf8_lossy(contents);
+
+            let mut reports = Vec::new();
+            for diagnostic in diagnostics {
+                reports.push(diagnostic.report(&source, Some(&path), colored, false)?);
+            }
+
+            println!("{}", reports.join("\n"));
+        }
+        ErrorFormatArgument::Json => {
+            let output = diagnostics_to_json(&path, contents, diagnostics, ColumnUnit::Bytes);
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        ErrorFormatArgument::Sarif => {
+            let output = diagnostics_to_sarif(&path, contents, diagnostics);
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+    }
+
+    Ok(())
+}
+
+fn features(path: &Path, print_json: bool) -> Result<()> {
+    let mut paths = vec![];
+    if path.is_dir() {
+        collect_php_files(path, &mut paths)?;
+    } else {
+        paths.push(path.to_path_buf());
+    }
+
+    let mut resolver = ConfigResolver::new();
+    let mut stats = FeatureStats::new();
+    for path in paths {
+        let contents = std::fs::read(&path)?;
+        let config = resolver.resolve(&path).unwrap_or_default();
+
+        match php_parser_rs::parse_with_config(&contents, config.parser_config()) {
+            Ok(mut program) => stats.add_program(&mut program),
+            Err(error) => eprintln!(
+                "{}: skipped, {} syntax errors",
+                path.display(),
+                error.errors.len()
+            ),
+        }
+    }
+
+    if print_json {
+        println!("{}", serde_json::to_string_pretty(&stats.to_json())?);
+    } else {
+        print!("{}", stats.report());
+    }
+
+    Ok(())
+}
+
+// The last known state of a watched file.
+struct WatchedFile {
+    modified: SystemTime,
+    config: Config,
+    contents: Vec<u8>,
+    program: std::result::Result<Program, String>,
+}
+
+// Poll the given paths for changes, only reparsing the files that were added or
+// modified since the last check, from the first statement affected by the change, and
+// print a summary whenever anything changed.
+fn watch(paths: &[PathBuf], colored: bool) -> Result<()> {
+    let mut files: BTreeMap<PathBuf, WatchedFile> = BTreeMap::new();
+    let mut reported = vec![];
+
+    loop {
+        // config files may change as well, so they are discovered again on every check.
+        let mut resolver = ConfigResolver::new();
+        let mut errors = vec![];
+
+        // files in a directory that could not be listed are not removed, as they are
+        // likely still there, e.g. while an editor replaces a file.
+        let mut listed = true;
+        let mut found = vec![];
+        for path in paths {
+            if path.is_dir() {
+                if let Err(error) = collect_php_files(path, &mut found) {
+                    errors.push(format!("{}: {}", path.display(), error));
+                    listed = false;
+                }
+            } else if path.exists() {
+                found.push(path.clone());
+            }
+        }
+
+        let count = files.len();
+        if listed {
+            files.retain(|path, _| found.contains(path));
+        }
+        let mut changed = files.len() != count;
+
+        for path in found {
+            // a file that can't be read is checked again on the next poll, keeping its
+            // last known state until then.
+            let (modified, contents) = match read_modified(&path, files.get(&path)) {
+                Ok(Some(file)) => file,
+                Ok(None) => continue,
+                Err(error) => {
+                    errors.push(format!("{}: {}", path.display(), error));
+                    continue;
+                }
+            };
+
+            let previous = files.remove(&path);
+            let (config, program) = match resolver.resolve(&path) {
+                Ok(config) => {
+                    let program =
+                        parse_watched(&path, &contents, &config, previous.as_ref(), colored);
+
+                    (config, program)
+                }
+                Err(error) => (Config::default(), Err(error.to_string())),
+            };
+
+            files.insert(
+                path,
+                WatchedFile {
+                    modified,
+                    config,
+                    contents,
+                    program,
+                },
+            );
+            changed = true;
+        }
+
+        if changed {
+            print_summary(&mut files);
+        }
+
+        // printed after the summary, which clears the screen, and only once for as long
+        // as they persist.
+        if changed || errors != reported {
+            for error in &errors {
+                eprintln!("{}", error);
+            }
+        }
+        reported = errors;
+
+        std::thread::sleep(Duration::from_millis(500));
+    }
+}
+
+// The modification time and contents of a file, if it changed since it was last read.
+fn read_modified(
+    path: &Path,
+    previous: Option<&WatchedFile>,
+) -> Result<Option<(SystemTime, Vec<u8>)>> {
+    let modified = std::fs::metadata(path)?.modified()?;
+    if matches!(previous, Some(file) if file.modified == modified) {
+        return Ok(None);
+    }
+
+    Ok(Some((modified, std::fs::read(path)?)))
+}
+
+// Parse a changed file, reusing the statements of its previous program that come
+// before the change, as long as it was parsed without errors and with the same
+// configuration.
+fn parse_watched(
+    path: &Path,
+    contents: &[u8],
+    config: &Config,
+    previous: Option<&WatchedFile>,
+    colored: bool,
+) -> std::result::Result<Program, String> {
+    let result = match previous {
+        Some(WatchedFile {
+            config: previous_config,
+            contents: previous_contents,
+            program: Ok(program),
+            ..
+        }) if previous_config == config => reparse(
+            program,
+            previous_contents,
+            &TextEdit::between(previous_contents, contents),
+            config.parser_config(),
+        ),
+        _ => php_parser_rs::parse_with_config(contents, config.parser_config()),
+    };
+
+    result.map_err(|error| {
+        error
+            .report(
+                &String::from_utf8_lossy(contents),
+                Some(&path.display().to_string()),
+                colored,
+                false,
+            )
+            .unwrap_or_else(|error| error.to_string())
+    })
+}
+
+fn collect_php_files(directory: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_php_files(&path, files)?;
+        } else if path.extension().is_some_and(|extension| extension == "php") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn print_summary(files: &mut BTreeMap<PathBuf, WatchedFile>) {
+    // The index is rebuilt from the already parsed programs, since a change to one
+    // file can affect the findings of all others.
+    let mut index = Index::new();
+    for file in files.values() {
+        if let Ok(program) = &file.program {
+            index.add_program(program);
+        }
+    }
+
+    // Clear the screen, and move the cursor to the top left corner.
+    print!("\x1b[2J\x1b[H");
+
+    let mut errors = 0;
+    let mut findings = 0;
+    for (path, file) in files.iter_mut() {
+        match &mut file.program {
+            Ok(program) => {
+                let context = Context {
+                    source: &file.contents,
+                    index: &index,
+                    version: file.config.php_version(),
+                };
+                let linter = Linter::new()
+                    .with_rule(OverrideAttribute::default())
+                    .with_rule(CallArguments)
+                    .with_rule(NestedTernary)
+                    .with_rule(InvalidThis)
+                    .with_rule(UninitializedProperty)
+                    .with_rule(Imports)
+                    .with_lint_config(&file.config.lint);
+
+                for finding in linter.check(&context, program) {
+                    findings += 1;
+                    println!(
+                        "{}:{}:{}: [{}] {}",
+                        path.display(),
+                        finding.span.line,
+                        finding.span.column,
+                        finding.rule,
+                        finding.message
+                    );
+                }
+            }
+            Err(report) => {
+                errors += 1;
+                println!("{}", report);
+            }
+        }
+    }
+
+    println!(
+        "{} files, {} with syntax errors, {} findings. Watching for changes...",
+        files.len(),
+        errors,
+        findings
+    );
+}
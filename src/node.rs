@@ -1,7 +1,65 @@
 use std::any::Any;
 
-pub trait Node: Any {
+use serde::Serialize;
+
+use crate::lexer::token::Span;
+
+pub trait Node: Any + ToJson {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![]
     }
+
+    /// The same children as [`Node::children`], borrowed immutably.
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![]
+    }
+
+    /// The name of the node's type without its path or generic parameters, e.g.
+    /// `ClassStatement` or `CommaSeparated`.
+    fn kind(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        let name = name.split('<').next().unwrap_or(name);
+
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
+    /// The range covering the node and everything within it, from the start of its first
+    /// token to the end of its last, or `None` if the node contains no tokens.
+    ///
+    /// Nodes that hold tokens of their own, such as keywords or braces, compute it from
+    /// those, while the others cover their first and last child.
+    fn span(&self) -> Option<Span> {
+        let children = self.children_ref();
+        let first = children.iter().find_map(|child| child.span());
+        let last = children.iter().rev().find_map(|child| child.span());
+
+        join([first, last])
+    }
+}
+
+/// The value serialized to JSON, which lets the fields of a node be read by name without
+/// knowing its type, as [`crate::query`] does.
+///
+/// Implemented for every serializable type, which includes all nodes.
+pub trait ToJson {
+    fn to_json(&self) -> Option<serde_json::Value>;
+}
+
+impl<T: Serialize> ToJson for T {
+    fn to_json(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(self).ok()
+    }
+}
+
+/// The smallest span covering all of the given spans, or `None` if there are none.
+pub fn join(spans: impl IntoIterator<Item = Option<Span>>) -> Option<Span> {
+    spans.into_iter().flatten().reduce(Span::join)
+}
+
+/// The range covering a list of nodes, from the first of them with a span to the last.
+pub fn span_of<T: Node>(nodes: &[T]) -> Option<Span> {
+    let first = nodes.iter().find_map(Node::span);
+    let last = nodes.iter().rev().find_map(Node::span);
+
+    join([first, last])
 }
@@ -5,9 +5,11 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::join;
 use crate::node::Node;
 use crate::parser::ast::comments::CommentGroup;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::relocate::relocate;
 use crate::parser::ast::Expression;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -21,6 +23,14 @@ impl Node for PositionalArgument {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.value]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.value]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.comments.span(), self.ellipsis, self.value.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -36,6 +46,14 @@ impl Node for NamedArgument {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.name, &mut self.value]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.name, &self.value]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.comments.span(), self.name.span(), self.value.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -52,6 +70,20 @@ impl Node for Argument {
             Argument::Named(argument) => vec![argument],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Argument::Positional(argument) => vec![argument],
+            Argument::Named(argument) => vec![argument],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Argument::Positional(argument) => argument.span(),
+            Argument::Named(argument) => argument.span(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -85,6 +117,15 @@ impl Node for ArgumentList {
             .map(|a| a as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.arguments.iter().map(|a| a as &dyn Node).collect()
+    }
+
+    // the comments are whatever preceded the `(`, which can be well before the call.
+    fn span(&self) -> Option<Span> {
+        Some(self.left_parenthesis.join(self.right_parenthesis))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -100,6 +141,15 @@ impl Node for SingleArgument {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.argument]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.argument]
+    }
+
+    // the comments are whatever preceded the `(`, which can be well before the call.
+    fn span(&self) -> Option<Span> {
+        Some(self.left_parenthesis.join(self.right_parenthesis))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -110,3 +160,25 @@ pub struct ArgumentPlaceholder {
     pub ellipsis: Span,          // `...`
     pub right_parenthesis: Span, // `)`
 }
+
+impl ArgumentPlaceholder {
+    pub fn span(&self) -> Span {
+        let span = self.left_parenthesis.join(self.right_parenthesis);
+
+        self.comments
+            .span()
+            .map_or(span, |comments| comments.join(span))
+    }
+}
+
+relocate! {
+    struct PositionalArgument { comments, ellipsis, value }
+    struct NamedArgument { comments, name, colon, ellipsis, value }
+    enum Argument {
+        Positional(value),
+        Named(value),
+    }
+    struct ArgumentList { comments, left_parenthesis, arguments, right_parenthesis }
+    struct SingleArgument { comments, left_parenthesis, argument, right_parenthesis }
+    struct ArgumentPlaceholder { comments, left_parenthesis, ellipsis, right_parenthesis }
+}
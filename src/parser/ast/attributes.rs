@@ -7,6 +7,7 @@ use serde::Serialize;
 use crate::lexer::token::Span;
 use crate::parser::ast::arguments::ArgumentList;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::relocate::relocate;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
@@ -26,6 +27,15 @@ pub struct AttributeGroup {
 }
 
 impl AttributeGroup {
+    /// The range covering the attribute groups, or `None` if there are none.
+    pub fn span_of(groups: &[AttributeGroup]) -> Option<Span> {
+        Some(groups.first()?.start.join(groups.last()?.end))
+    }
+
+    pub fn span(&self) -> Span {
+        self.start.join(self.end)
+    }
+
     pub fn iter(&self) -> Iter<'_, Attribute> {
         self.members.iter()
     }
@@ -39,3 +49,8 @@ impl IntoIterator for AttributeGroup {
         self.members.into_iter()
     }
 }
+
+relocate! {
+    struct Attribute { start, end, name, arguments }
+    struct AttributeGroup { start, end, members }
+}
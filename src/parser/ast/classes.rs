@@ -5,8 +5,10 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::join;
 use crate::node::Node;
 use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::comments::CommentGroup;
 use crate::parser::ast::constant::ClassishConstant;
 use crate::parser::ast::functions::AbstractConstructor;
 use crate::parser::ast::functions::AbstractMethod;
@@ -16,6 +18,7 @@ use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::modifiers::ClassModifierGroup;
 use crate::parser::ast::properties::Property;
 use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::relocate::relocate;
 use crate::parser::ast::traits::TraitUsage;
 use crate::parser::ast::utils::CommaSeparated;
 
@@ -49,11 +52,23 @@ impl Node for ClassBody {
             .map(|member| member as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.members
+            .iter()
+            .map(|member| member as &dyn Node)
+            .collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.left_brace.join(self.right_brace))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct ClassStatement {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>, // `#[Qux]`
     #[serde(flatten)]
     pub modifiers: ClassModifierGroup, // `abstract`, `final`
@@ -76,6 +91,28 @@ impl Node for ClassStatement {
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name];
+        if let Some(extends) = &self.extends {
+            children.push(extends);
+        }
+        if let Some(implements) = &self.implements {
+            children.push(implements);
+        }
+        children.push(&self.body);
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            self.modifiers.span(),
+            Some(self.class),
+            Some(self.body.right_brace),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -108,16 +145,29 @@ impl Node for AnonymousClassBody {
             .map(|member| member as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.members
+            .iter()
+            .map(|member| member as &dyn Node)
+            .collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.left_brace.join(self.right_brace))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct AnonymousClassExpression {
-    pub attributes: Vec<AttributeGroup>,     // `#[Qux]`
-    pub class: Span,                         // `class`
-    pub extends: Option<ClassExtends>,       // `extends Foo`
+    pub attributes: Vec<AttributeGroup>, // `#[Qux]`
+    #[serde(flatten)]
+    pub modifiers: ClassModifierGroup, // `readonly`
+    pub class: Span,                     // `class`
+    pub extends: Option<ClassExtends>,   // `extends Foo`
     pub implements: Option<ClassImplements>, // `implements Baz, Baz`
-    pub body: AnonymousClassBody,            // `{ ... }`
+    pub body: AnonymousClassBody,        // `{ ... }`
 }
 
 impl Node for AnonymousClassExpression {
@@ -132,6 +182,27 @@ impl Node for AnonymousClassExpression {
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![];
+        if let Some(extends) = &self.extends {
+            children.push(extends);
+        }
+        if let Some(implements) = &self.implements {
+            children.push(implements);
+        }
+        children.push(&self.body);
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            AttributeGroup::span_of(&self.attributes),
+            self.modifiers.span(),
+            Some(self.class),
+            Some(self.body.right_brace),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -145,6 +216,14 @@ impl Node for ClassExtends {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.parent]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.parent]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.extends), self.parent.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -173,6 +252,14 @@ impl Node for ClassImplements {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.interfaces.children()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.interfaces.children_ref()
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.implements), self.interfaces.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -201,6 +288,32 @@ impl Node for ClassMember {
             ClassMember::ConcreteConstructor(method) => vec![method],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ClassMember::Constant(constant) => vec![constant],
+            ClassMember::TraitUsage(usage) => vec![usage],
+            ClassMember::Property(property) => vec![property],
+            ClassMember::VariableProperty(property) => vec![property],
+            ClassMember::AbstractMethod(method) => vec![method],
+            ClassMember::AbstractConstructor(method) => vec![method],
+            ClassMember::ConcreteMethod(method) => vec![method],
+            ClassMember::ConcreteConstructor(method) => vec![method],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            ClassMember::Constant(member) => member.span(),
+            ClassMember::TraitUsage(member) => member.span(),
+            ClassMember::Property(member) => member.span(),
+            ClassMember::VariableProperty(member) => member.span(),
+            ClassMember::AbstractMethod(member) => member.span(),
+            ClassMember::AbstractConstructor(member) => member.span(),
+            ClassMember::ConcreteMethod(member) => member.span(),
+            ClassMember::ConcreteConstructor(member) => member.span(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -225,4 +338,62 @@ impl Node for AnonymousClassMember {
             AnonymousClassMember::ConcreteConstructor(method) => vec![method],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            AnonymousClassMember::Constant(constant) => vec![constant],
+            AnonymousClassMember::TraitUsage(usage) => vec![usage],
+            AnonymousClassMember::Property(property) => vec![property],
+            AnonymousClassMember::VariableProperty(property) => vec![property],
+            AnonymousClassMember::ConcreteMethod(method) => vec![method],
+            AnonymousClassMember::ConcreteConstructor(method) => vec![method],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            AnonymousClassMember::Constant(member) => member.span(),
+            AnonymousClassMember::TraitUsage(member) => member.span(),
+            AnonymousClassMember::Property(member) => member.span(),
+            AnonymousClassMember::VariableProperty(member) => member.span(),
+            AnonymousClassMember::ConcreteMethod(member) => member.span(),
+            AnonymousClassMember::ConcreteConstructor(member) => member.span(),
+        }
+    }
+}
+
+relocate! {
+    struct ClassBody { left_brace, members, right_brace }
+    struct ClassStatement {
+        comments,
+        attributes,
+        modifiers,
+        class,
+        name,
+        extends,
+        implements,
+        body,
+    }
+    struct AnonymousClassBody { left_brace, members, right_brace }
+    struct AnonymousClassExpression { attributes, modifiers, class, extends, implements, body }
+    struct ClassExtends { extends, parent }
+    struct ClassImplements { implements, interfaces }
+    enum ClassMember {
+        Constant(value),
+        TraitUsage(value),
+        Property(value),
+        VariableProperty(value),
+        AbstractMethod(value),
+        AbstractConstructor(value),
+        ConcreteMethod(value),
+        ConcreteConstructor(value),
+    }
+    enum AnonymousClassMember {
+        Constant(value),
+        TraitUsage(value),
+        Property(value),
+        VariableProperty(value),
+        ConcreteMethod(value),
+        ConcreteConstructor(value),
+    }
 }
@@ -6,7 +6,9 @@ use serde::Serialize;
 
 use crate::lexer::byte_string::ByteString;
 use crate::lexer::token::Span;
+use crate::node::span_of;
 use crate::node::Node;
+use crate::parser::ast::relocate::relocate;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type")]
@@ -25,7 +27,11 @@ pub struct Comment {
     pub content: ByteString,
 }
 
-impl Node for Comment {}
+impl Node for Comment {
+    fn span(&self) -> Option<Span> {
+        Some(self.span)
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
@@ -34,6 +40,11 @@ pub struct CommentGroup {
 }
 
 impl CommentGroup {
+    /// The range covering the comments, or `None` if there are none.
+    pub fn span(&self) -> Option<Span> {
+        span_of(&self.comments)
+    }
+
     pub fn iter(&self) -> Iter<'_, Comment> {
         self.comments.iter()
     }
@@ -47,3 +58,14 @@ impl IntoIterator for CommentGroup {
         self.comments.into_iter()
     }
 }
+
+relocate! {
+    enum CommentFormat {
+        SingleLine,
+        MultiLine,
+        HashMark,
+        Document,
+    }
+    struct Comment { span, format, content }
+    struct CommentGroup { comments }
+}
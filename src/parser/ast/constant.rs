@@ -5,11 +5,13 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::join;
 use crate::node::Node;
 use crate::parser::ast::attributes::AttributeGroup;
 use crate::parser::ast::comments::CommentGroup;
 use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::modifiers::ConstantModifierGroup;
+use crate::parser::ast::relocate::relocate;
 use crate::parser::ast::Expression;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -24,6 +26,14 @@ impl Node for ConstantEntry {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.name, &mut self.value]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.name, &self.value]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.name.span(), self.value.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -57,6 +67,18 @@ impl Node for ConstantStatement {
             .map(|e| e as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.entries.iter().map(|e| e as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            Some(self.r#const),
+            Some(self.semicolon),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -66,8 +88,7 @@ pub struct ClassishConstant {
     pub attributes: Vec<AttributeGroup>,  // `#[Foo]`
     pub modifiers: ConstantModifierGroup, // `public`
     pub r#const: Span,                    // `const`
-    #[serde(flatten)]
-    pub entries: Vec<ConstantEntry>, // `FOO = 123`
+    pub entries: Vec<ConstantEntry>,      // `FOO = 123`
     pub semicolon: Span,                  // `;`
 }
 
@@ -93,4 +114,24 @@ impl Node for ClassishConstant {
             .map(|e| e as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.entries.iter().map(|e| e as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            self.modifiers.span(),
+            Some(self.r#const),
+            Some(self.semicolon),
+        ])
+    }
+}
+
+relocate! {
+    struct ConstantEntry { name, equals, value }
+    struct ConstantStatement { comments, r#const, entries, semicolon }
+    struct ClassishConstant { comments, attributes, modifiers, r#const, entries, semicolon }
 }
@@ -3,7 +3,10 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::join;
+use crate::node::span_of;
 use crate::node::Node;
+use crate::parser::ast::relocate::relocate;
 use crate::parser::ast::Ending;
 use crate::parser::ast::Expression;
 use crate::parser::ast::Statement;
@@ -22,6 +25,14 @@ impl Node for IfStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.condition, &mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.condition, &self.body]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.r#if), self.body.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -82,6 +93,57 @@ impl Node for IfStatementBody {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            IfStatementBody::Statement {
+                statement,
+                elseifs,
+                r#else,
+            } => {
+                let mut children: Vec<&dyn Node> = vec![statement.as_ref()];
+                children.extend(
+                    elseifs
+                        .iter()
+                        .map(|elseif| elseif as &dyn Node)
+                        .collect::<Vec<&dyn Node>>(),
+                );
+                if let Some(r#else) = r#else {
+                    children.push(r#else as &dyn Node);
+                }
+                children
+            }
+            IfStatementBody::Block {
+                statements,
+                elseifs,
+                r#else,
+                ..
+            } => {
+                let mut children: Vec<&dyn Node> = vec![];
+                children.extend(statements.iter().map(|statement| statement as &dyn Node));
+                children.extend(elseifs.iter().map(|elseif| elseif as &dyn Node));
+                if let Some(r#else) = r#else {
+                    children.push(r#else as &dyn Node);
+                }
+                children
+            }
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            IfStatementBody::Statement {
+                statement,
+                elseifs,
+                r#else,
+            } => join([
+                statement.span(),
+                span_of(elseifs),
+                r#else.as_ref().and_then(Node::span),
+            ]),
+            IfStatementBody::Block { colon, ending, .. } => Some(colon.join(ending.span())),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -98,6 +160,14 @@ impl Node for IfStatementElseIf {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.condition, self.statement.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.condition, self.statement.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.elseif), self.statement.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -111,6 +181,14 @@ impl Node for IfStatementElse {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.statement.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.statement.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.r#else), self.statement.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -134,6 +212,24 @@ impl Node for IfStatementElseIfBlock {
         );
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.condition];
+        children.extend(
+            self.statements
+                .iter()
+                .map(|statement| statement as &dyn Node),
+        );
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            Some(self.elseif),
+            Some(self.colon),
+            span_of(&self.statements),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -151,4 +247,38 @@ impl Node for IfStatementElseBlock {
             .map(|statement| statement as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.statements
+            .iter()
+            .map(|statement| statement as &dyn Node)
+            .collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            Some(self.r#else),
+            Some(self.colon),
+            span_of(&self.statements),
+        ])
+    }
+}
+
+relocate! {
+    struct IfStatement { r#if, left_parenthesis, condition, right_parenthesis, body }
+    enum IfStatementBody {
+        Statement { statement, elseifs, r#else },
+        Block { colon, statements, elseifs, r#else, endif, ending },
+    }
+    struct IfStatementElseIf { elseif, left_parenthesis, condition, right_parenthesis, statement }
+    struct IfStatementElse { r#else, statement }
+    struct IfStatementElseIfBlock {
+        elseif,
+        left_parenthesis,
+        condition,
+        right_parenthesis,
+        colon,
+        statements,
+    }
+    struct IfStatementElseBlock { r#else, colon, statements }
 }
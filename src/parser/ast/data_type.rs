@@ -6,7 +6,10 @@ use std::fmt::Display;
 
 use crate::lexer::byte_string::ByteString;
 use crate::lexer::token::Span;
+use crate::node::join;
+use crate::node::span_of;
 use crate::node::Node;
+use crate::parser::ast::relocate::relocate;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type", content = "value")]
@@ -70,6 +73,18 @@ impl Type {
         matches!(self, Type::Never(_) | Type::Void(_))
     }
 
+    /// Whether the type can only be used as a return type, such as `void`, `never` and `static`.
+    pub fn is_return_only(&self) -> bool {
+        match &self {
+            Self::Void(_) | Self::Never(_) | Self::StaticReference(_) => true,
+            Self::Nullable(_, inner) => inner.is_return_only(),
+            Self::Union(types) | Self::Intersection(types) => {
+                types.iter().any(|x| x.is_return_only())
+            }
+            _ => false,
+        }
+    }
+
     pub fn first_span(&self) -> Span {
         match &self {
             Type::Named(span, _) => *span,
@@ -107,7 +122,10 @@ impl Display for Type {
                 "{}",
                 inner
                     .iter()
-                    .map(|t| t.to_string())
+                    .map(|t| match t {
+                        Type::Intersection(_) => format!("({})", t),
+                        _ => t.to_string(),
+                    })
                     .collect::<Vec<String>>()
                     .join("|")
             ),
@@ -116,7 +134,10 @@ impl Display for Type {
                 "{}",
                 inner
                     .iter()
-                    .map(|t| t.to_string())
+                    .map(|t| match t {
+                        Type::Union(_) => format!("({})", t),
+                        _ => t.to_string(),
+                    })
                     .collect::<Vec<String>>()
                     .join("&")
             ),
@@ -150,4 +171,64 @@ impl Node for Type {
             _ => vec![],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Type::Nullable(_, t) => vec![t.as_ref() as &dyn Node],
+            Type::Union(ts) => ts.iter().map(|x| x as &dyn Node).collect(),
+            Type::Intersection(ts) => ts.iter().map(|x| x as &dyn Node).collect(),
+            _ => vec![],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Type::Nullable(span, inner) => join([Some(*span), inner.span()]),
+            Type::Union(types) | Type::Intersection(types) => span_of(types),
+            Type::Named(span, _)
+            | Type::Void(span)
+            | Type::Null(span)
+            | Type::True(span)
+            | Type::False(span)
+            | Type::Never(span)
+            | Type::Float(span)
+            | Type::Boolean(span)
+            | Type::Integer(span)
+            | Type::String(span)
+            | Type::Array(span)
+            | Type::Object(span)
+            | Type::Mixed(span)
+            | Type::Callable(span)
+            | Type::Iterable(span)
+            | Type::StaticReference(span)
+            | Type::SelfReference(span)
+            | Type::ParentReference(span) => Some(*span),
+        }
+    }
+}
+
+relocate! {
+    enum Type {
+        Named(span, name),
+        Nullable(span, r#type),
+        Union(value),
+        Intersection(value),
+        Void(value),
+        Null(value),
+        True(value),
+        False(value),
+        Never(value),
+        Float(value),
+        Boolean(value),
+        Integer(value),
+        String(value),
+        Array(value),
+        Object(value),
+        Mixed(value),
+        Callable(value),
+        Iterable(value),
+        StaticReference(value),
+        SelfReference(value),
+        ParentReference(value),
+    }
 }
@@ -3,9 +3,11 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::join;
 use crate::node::Node;
 use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::literals::Literal;
+use crate::parser::ast::relocate::relocate;
 use crate::parser::ast::Expression;
 use crate::parser::ast::Statement;
 
@@ -21,6 +23,14 @@ impl Node for DeclareEntry {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.key, &mut self.value]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.key, &self.value]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.key.span(), self.value.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -38,8 +48,17 @@ impl Node for DeclareEntryGroup {
             .map(|e| e as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.entries.iter().map(|e| e as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.left_parenthesis.join(self.right_parenthesis))
+    }
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type", content = "value")]
 pub enum DeclareBody {
@@ -79,6 +98,35 @@ impl Node for DeclareBody {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            DeclareBody::Noop { .. } => vec![],
+            DeclareBody::Braced { statements, .. } => {
+                statements.iter().map(|s| s as &dyn Node).collect()
+            }
+            DeclareBody::Expression { expression, .. } => vec![expression],
+            DeclareBody::Block { statements, .. } => {
+                statements.iter().map(|s| s as &dyn Node).collect()
+            }
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            DeclareBody::Noop { semicolon } => Some(*semicolon),
+            DeclareBody::Braced {
+                left_brace,
+                right_brace,
+                ..
+            } => Some(left_brace.join(*right_brace)),
+            DeclareBody::Expression {
+                expression,
+                semicolon,
+            } => join([expression.span(), Some(*semicolon)]),
+            DeclareBody::Block { colon, end, .. } => Some(colon.join(end.1)),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -93,4 +141,24 @@ impl Node for DeclareStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.entries, &mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.entries, &self.body]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.declare), self.body.span()])
+    }
+}
+
+relocate! {
+    struct DeclareEntry { key, equals, value }
+    struct DeclareEntryGroup { left_parenthesis, right_parenthesis, entries }
+    enum DeclareBody {
+        Noop { semicolon },
+        Braced { left_brace, statements, right_brace },
+        Expression { expression, semicolon },
+        Block { colon, statements, end },
+    }
+    struct DeclareStatement { declare, entries, body }
 }
@@ -3,11 +3,14 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::join;
 use crate::node::Node;
 use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::comments::CommentGroup;
 use crate::parser::ast::constant::ClassishConstant;
 use crate::parser::ast::functions::ConcreteMethod;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::relocate::relocate;
 use crate::parser::ast::Expression;
 
 use super::traits::TraitUsage;
@@ -15,6 +18,7 @@ use super::traits::TraitUsage;
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct UnitEnumCase {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>, // `#[Foo]`
     pub start: Span,                     // `case`
     pub name: SimpleIdentifier,          // `Bar`
@@ -25,8 +29,22 @@ impl Node for UnitEnumCase {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.name]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.name]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            Some(self.start),
+            Some(self.end),
+        ])
+    }
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type", content = "value")]
 pub enum UnitEnumMember {
@@ -45,6 +63,24 @@ impl Node for UnitEnumMember {
             UnitEnumMember::TraitUsage(trait_usage) => vec![trait_usage],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            UnitEnumMember::Case(case) => vec![case],
+            UnitEnumMember::Method(method) => vec![method],
+            UnitEnumMember::Constant(constant) => vec![constant],
+            UnitEnumMember::TraitUsage(trait_usage) => vec![trait_usage],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            UnitEnumMember::Case(member) => member.span(),
+            UnitEnumMember::Method(member) => member.span(),
+            UnitEnumMember::Constant(member) => member.span(),
+            UnitEnumMember::TraitUsage(member) => member.span(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -62,11 +98,20 @@ impl Node for UnitEnumBody {
             .map(|m| m as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.members.iter().map(|m| m as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.left_brace.join(self.right_brace))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct UnitEnumStatement {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>,   // `#[Foo]`
     pub r#enum: Span,                      // `enum`
     pub name: SimpleIdentifier,            // `Foo`
@@ -83,6 +128,24 @@ impl Node for UnitEnumStatement {
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name];
+        for implement in &self.implements {
+            children.push(implement);
+        }
+        children.push(&self.body);
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            Some(self.r#enum),
+            Some(self.body.right_brace),
+        ])
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
@@ -93,12 +156,19 @@ pub enum BackedEnumType {
 }
 
 impl Node for BackedEnumType {
-    //
+    fn span(&self) -> Option<Span> {
+        match self {
+            BackedEnumType::String(colon, name) | BackedEnumType::Int(colon, name) => {
+                Some(colon.join(*name))
+            }
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct BackedEnumCase {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>, // `#[Foo]`
     pub case: Span,                      // `case`
     pub name: SimpleIdentifier,          // `Bar`
@@ -111,8 +181,22 @@ impl Node for BackedEnumCase {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.name, &mut self.value]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.name, &self.value]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            Some(self.case),
+            Some(self.semicolon),
+        ])
+    }
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type", content = "value")]
 pub enum BackedEnumMember {
@@ -131,6 +215,24 @@ impl Node for BackedEnumMember {
             BackedEnumMember::TraitUsage(trait_usage) => vec![trait_usage],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            BackedEnumMember::Case(case) => vec![case],
+            BackedEnumMember::Method(method) => vec![method],
+            BackedEnumMember::Constant(constant) => vec![constant],
+            BackedEnumMember::TraitUsage(trait_usage) => vec![trait_usage],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            BackedEnumMember::Case(member) => member.span(),
+            BackedEnumMember::Method(member) => member.span(),
+            BackedEnumMember::Constant(member) => member.span(),
+            BackedEnumMember::TraitUsage(member) => member.span(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -148,11 +250,20 @@ impl Node for BackedEnumBody {
             .map(|m| m as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.members.iter().map(|m| m as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.left_brace.join(self.right_brace))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct BackedEnumStatement {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>,   // `#[Foo]`
     pub r#enum: Span,                      // `enum`
     pub name: SimpleIdentifier,            // `Foo`
@@ -170,4 +281,47 @@ impl Node for BackedEnumStatement {
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name, &self.backed_type];
+        for implement in &self.implements {
+            children.push(implement);
+        }
+        children.push(&self.body);
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            Some(self.r#enum),
+            Some(self.body.right_brace),
+        ])
+    }
+}
+
+relocate! {
+    struct UnitEnumCase { comments, attributes, start, name, end }
+    enum UnitEnumMember {
+        Case(value),
+        Method(value),
+        Constant(value),
+        TraitUsage(value),
+    }
+    struct UnitEnumBody { left_brace, members, right_brace }
+    struct UnitEnumStatement { comments, attributes, r#enum, name, implements, body }
+    enum BackedEnumType {
+        String(colon, identifier),
+        Int(colon, identifier),
+    }
+    struct BackedEnumCase { comments, attributes, case, name, equals, value, semicolon }
+    enum BackedEnumMember {
+        Case(value),
+        Method(value),
+        Constant(value),
+        TraitUsage(value),
+    }
+    struct BackedEnumBody { left_brace, members, right_brace }
+    struct BackedEnumStatement { comments, attributes, r#enum, name, backed_type, implements, body }
 }
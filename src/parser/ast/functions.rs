@@ -5,6 +5,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::join;
 use crate::node::Node;
 use crate::parser::ast::attributes::AttributeGroup;
 use crate::parser::ast::comments::CommentGroup;
@@ -12,6 +13,7 @@ use crate::parser::ast::data_type::Type;
 use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::modifiers::MethodModifierGroup;
 use crate::parser::ast::modifiers::PromotedPropertyModifierGroup;
+use crate::parser::ast::relocate::relocate;
 use crate::parser::ast::utils::CommaSeparated;
 use crate::parser::ast::variables::SimpleVariable;
 use crate::parser::ast::Expression;
@@ -28,6 +30,14 @@ impl Node for ReturnType {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.data_type]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.data_type]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.colon), self.data_type.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -53,6 +63,29 @@ impl Node for FunctionParameter {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name];
+        if let Some(data_type) = &self.data_type {
+            children.push(data_type);
+        }
+        if let Some(default) = &self.default {
+            children.push(default);
+        }
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            self.data_type.as_ref().and_then(Node::span),
+            self.ampersand,
+            self.ellipsis,
+            self.name.span(),
+            self.default.as_ref().and_then(Node::span),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -83,6 +116,18 @@ impl Node for FunctionParameterList {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.parameters.children()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.parameters.children_ref()
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            Some(self.left_parenthesis),
+            Some(self.right_parenthesis),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -101,6 +146,18 @@ impl Node for FunctionBody {
             .map(|x| x as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.statements.iter().map(|x| x as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            Some(self.left_brace),
+            Some(self.right_brace),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -125,6 +182,23 @@ impl Node for FunctionStatement {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name, &self.parameters, &self.body];
+        if let Some(return_type) = &self.return_type {
+            children.push(return_type);
+        }
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            Some(self.function),
+            self.body.span(),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -139,6 +213,14 @@ impl Node for ClosureUseVariable {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.variable]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.variable]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.comments.span(), self.ampersand, self.variable.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -155,6 +237,18 @@ impl Node for ClosureUse {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.variables.children()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.variables.children_ref()
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            Some(self.r#use),
+            Some(self.right_parenthesis),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -183,6 +277,28 @@ impl Node for ClosureExpression {
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.parameters];
+        if let Some(uses) = &self.uses {
+            children.push(uses);
+        }
+        if let Some(return_type) = &self.return_type {
+            children.push(return_type);
+        }
+        children.push(&self.body);
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            self.r#static,
+            Some(self.function),
+            self.body.span(),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -208,6 +324,26 @@ impl Node for ArrowFunctionExpression {
         children.push(self.body.as_mut());
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.parameters];
+        if let Some(return_type) = &self.return_type {
+            children.push(return_type);
+        }
+        children.push(self.body.as_ref());
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            self.r#static,
+            Some(self.r#fn),
+            Some(self.double_arrow),
+            self.body.span(),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -235,6 +371,30 @@ impl Node for ConstructorParameter {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name];
+        if let Some(data_type) = &self.data_type {
+            children.push(data_type);
+        }
+        if let Some(default) = &self.default {
+            children.push(default);
+        }
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            self.modifiers.span(),
+            self.data_type.as_ref().and_then(Node::span),
+            self.ampersand,
+            self.ellipsis,
+            self.name.span(),
+            self.default.as_ref().and_then(Node::span),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -250,6 +410,18 @@ impl Node for ConstructorParameterList {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.parameters.children()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.parameters.children_ref()
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            Some(self.left_parenthesis),
+            Some(self.right_parenthesis),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -272,6 +444,20 @@ impl Node for AbstractConstructor {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.name, &mut self.parameters]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.name, &self.parameters]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            self.modifiers.span(),
+            Some(self.function),
+            Some(self.semicolon),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -294,6 +480,20 @@ impl Node for ConcreteConstructor {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.name, &mut self.parameters, &mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.name, &self.parameters, &self.body]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            self.modifiers.span(),
+            Some(self.function),
+            self.body.span(),
+        ])
+    }
 }
 
 impl ConcreteConstructor {
@@ -337,6 +537,24 @@ impl Node for AbstractMethod {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name, &self.parameters];
+        if let Some(return_type) = &self.return_type {
+            children.push(return_type);
+        }
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            self.modifiers.span(),
+            Some(self.function),
+            Some(self.semicolon),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -363,6 +581,25 @@ impl Node for ConcreteMethod {
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name, &self.parameters];
+        if let Some(return_type) = &self.return_type {
+            children.push(return_type);
+        }
+        children.push(&self.body);
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            self.modifiers.span(),
+            Some(self.function),
+            self.body.span(),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -381,4 +618,111 @@ impl Node for MethodBody {
             .map(|s| s as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.statements.iter().map(|s| s as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            Some(self.left_brace),
+            Some(self.right_brace),
+        ])
+    }
+}
+
+relocate! {
+    struct ReturnType { colon, data_type }
+    struct FunctionParameter { comments, name, attributes, data_type, ellipsis, default, ampersand }
+    struct FunctionParameterList { comments, left_parenthesis, parameters, right_parenthesis }
+    struct FunctionBody { comments, left_brace, statements, right_brace }
+    struct FunctionStatement {
+        comments,
+        attributes,
+        function,
+        ampersand,
+        name,
+        parameters,
+        return_type,
+        body,
+    }
+    struct ClosureUseVariable { comments, ampersand, variable }
+    struct ClosureUse { comments, r#use, left_parenthesis, variables, right_parenthesis }
+    struct ClosureExpression {
+        comments,
+        attributes,
+        r#static,
+        function,
+        ampersand,
+        parameters,
+        uses,
+        return_type,
+        body,
+    }
+    struct ArrowFunctionExpression {
+        comments,
+        r#static,
+        ampersand,
+        r#fn,
+        attributes,
+        parameters,
+        return_type,
+        double_arrow,
+        body,
+    }
+    struct ConstructorParameter {
+        attributes,
+        comments,
+        ampersand,
+        name,
+        data_type,
+        ellipsis,
+        default,
+        modifiers,
+    }
+    struct ConstructorParameterList { comments, left_parenthesis, parameters, right_parenthesis }
+    struct AbstractConstructor {
+        comments,
+        attributes,
+        modifiers,
+        function,
+        ampersand,
+        name,
+        parameters,
+        semicolon,
+    }
+    struct ConcreteConstructor {
+        comments,
+        attributes,
+        modifiers,
+        function,
+        ampersand,
+        name,
+        parameters,
+        body,
+    }
+    struct AbstractMethod {
+        comments,
+        attributes,
+        modifiers,
+        function,
+        ampersand,
+        name,
+        parameters,
+        return_type,
+        semicolon,
+    }
+    struct ConcreteMethod {
+        comments,
+        attributes,
+        modifiers,
+        function,
+        ampersand,
+        name,
+        parameters,
+        return_type,
+        body,
+    }
+    struct MethodBody { comments, left_brace, statements, right_brace }
 }
@@ -3,9 +3,11 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::join;
 use crate::node::Node;
 use crate::parser::ast::comments::CommentGroup;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::relocate::relocate;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
@@ -19,6 +21,14 @@ impl Node for LabelStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.label]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.label]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.comments.span(), self.label.span(), Some(self.colon)])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -34,4 +44,21 @@ impl Node for GotoStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.label]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.label]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            Some(self.keyword),
+            Some(self.semicolon),
+        ])
+    }
+}
+
+relocate! {
+    struct LabelStatement { comments, label, colon }
+    struct GotoStatement { comments, keyword, label, semicolon }
 }
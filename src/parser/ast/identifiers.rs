@@ -7,6 +7,7 @@ use serde::Serialize;
 use crate::lexer::byte_string::ByteString;
 use crate::lexer::token::Span;
 use crate::node::Node;
+use crate::parser::ast::relocate::relocate;
 use crate::parser::ast::Expression;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -23,6 +24,20 @@ impl Node for Identifier {
             Identifier::DynamicIdentifier(identifier) => identifier.children(),
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Identifier::SimpleIdentifier(identifier) => identifier.children_ref(),
+            Identifier::DynamicIdentifier(identifier) => identifier.children_ref(),
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Identifier::SimpleIdentifier(identifier) => identifier.span(),
+            Identifier::DynamicIdentifier(identifier) => identifier.span(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -33,7 +48,9 @@ pub struct SimpleIdentifier {
 }
 
 impl Node for SimpleIdentifier {
-    //
+    fn span(&self) -> Option<Span> {
+        Some(self.span)
+    }
 }
 
 impl Display for SimpleIdentifier {
@@ -54,4 +71,21 @@ impl Node for DynamicIdentifier {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.expr.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.expr.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.start.join(self.end))
+    }
+}
+
+relocate! {
+    enum Identifier {
+        SimpleIdentifier(value),
+        DynamicIdentifier(value),
+    }
+    struct SimpleIdentifier { span, value }
+    struct DynamicIdentifier { start, expr, end }
 }
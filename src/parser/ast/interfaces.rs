@@ -3,12 +3,15 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::join;
 use crate::node::Node;
 use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::comments::CommentGroup;
 use crate::parser::ast::constant::ClassishConstant;
 use crate::parser::ast::functions::AbstractConstructor;
 use crate::parser::ast::functions::AbstractMethod;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::relocate::relocate;
 use crate::parser::ast::utils::CommaSeparated;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -27,6 +30,22 @@ impl Node for InterfaceMember {
             InterfaceMember::Method(method) => vec![method],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            InterfaceMember::Constant(constant) => vec![constant],
+            InterfaceMember::Constructor(constructor) => vec![constructor],
+            InterfaceMember::Method(method) => vec![method],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            InterfaceMember::Constant(member) => member.span(),
+            InterfaceMember::Constructor(member) => member.span(),
+            InterfaceMember::Method(member) => member.span(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -40,6 +59,14 @@ impl Node for InterfaceExtends {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.parents.children()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.parents.children_ref()
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.extends), self.parents.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -57,11 +84,23 @@ impl Node for InterfaceBody {
             .map(|member| member as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.members
+            .iter()
+            .map(|member| member as &dyn Node)
+            .collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.left_brace.join(self.right_brace))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct InterfaceStatement {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>,   // `#[Foo]`
     pub interface: Span,                   // `interface`
     pub name: SimpleIdentifier,            // `Foo`
@@ -77,4 +116,32 @@ impl Node for InterfaceStatement {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name];
+        if let Some(extends) = &self.extends {
+            children.push(extends);
+        }
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            Some(self.interface),
+            Some(self.body.right_brace),
+        ])
+    }
+}
+
+relocate! {
+    enum InterfaceMember {
+        Constant(value),
+        Constructor(value),
+        Method(value),
+    }
+    struct InterfaceExtends { extends, parents }
+    struct InterfaceBody { left_brace, members, right_brace }
+    struct InterfaceStatement { comments, attributes, interface, name, extends, body }
 }
@@ -5,6 +5,7 @@ use serde::Serialize;
 use crate::lexer::byte_string::ByteString;
 use crate::lexer::token::Span;
 use crate::node::Node;
+use crate::parser::ast::relocate::relocate;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type", content = "value")]
@@ -22,6 +23,22 @@ impl Node for Literal {
             Literal::Float(literal) => vec![literal],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Literal::String(literal) => vec![literal],
+            Literal::Integer(literal) => vec![literal],
+            Literal::Float(literal) => vec![literal],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Literal::String(literal) => literal.span(),
+            Literal::Integer(literal) => literal.span(),
+            Literal::Float(literal) => literal.span(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -39,7 +56,9 @@ pub enum LiteralStringKind {
 }
 
 impl Node for LiteralString {
-    //
+    fn span(&self) -> Option<Span> {
+        Some(self.span)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -49,8 +68,75 @@ pub struct LiteralInteger {
     pub span: Span,
 }
 
+impl LiteralInteger {
+    /// The radix of the literal: 16 for `0x1A`, 8 for `0o17` and `017`, 2 for `0b1010`
+    /// and 10 otherwise.
+    pub fn radix(&self) -> u32 {
+        match self.value.get(..2) {
+            Some(b"0x" | b"0X") => 16,
+            Some(b"0b" | b"0B") => 2,
+            Some(b"0o" | b"0O") => 8,
+            _ if self.value.len() > 1 && self.value.starts_with(b"0") => 8,
+            _ => 10,
+        }
+    }
+
+    /// The value PHP evaluates the literal to.
+    ///
+    /// Literals that do not fit into an `int` overflow to a `float`, as they do in PHP.
+    /// Underscore separators are ignored, and a legacy octal literal stops at the first
+    /// digit that is not octal.
+    pub fn to_value(&self) -> IntegerValue {
+        let radix = self.radix();
+        let digits = match self.value.get(1) {
+            Some(b'x' | b'X' | b'b' | b'B' | b'o' | b'O') => &self.value[2..],
+            _ => &self.value[..],
+        };
+
+        let digits = digits
+            .iter()
+            .filter(|byte| **byte != b'_')
+            .map_while(|byte| (*byte as char).to_digit(radix));
+
+        let mut value: i64 = 0;
+        let mut overflowed: Option<f64> = None;
+        for digit in digits {
+            match overflowed.as_mut() {
+                Some(float) => *float = *float * radix as f64 + digit as f64,
+                None => match value
+                    .checked_mul(radix as i64)
+                    .and_then(|value| value.checked_add(digit as i64))
+                {
+                    Some(next) => value = next,
+                    None => overflowed = Some(value as f64 * radix as f64 + digit as f64),
+                },
+            }
+        }
+
+        match overflowed {
+            Some(float) => IntegerValue::Overflowed(float),
+            None => IntegerValue::Integer(value),
+        }
+    }
+
+    /// Whether the literal does not fit into an `int`, and is a `float` in PHP.
+    pub fn overflows(&self) -> bool {
+        matches!(self.to_value(), IntegerValue::Overflowed(_))
+    }
+}
+
 impl Node for LiteralInteger {
-    //
+    fn span(&self) -> Option<Span> {
+        Some(self.span)
+    }
+}
+
+/// The value of an integer literal, see [`LiteralInteger::to_value`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum IntegerValue {
+    Integer(i64),
+    /// The literal does not fit into an `int`, e.g. `0xFFFFFFFFFFFFFFFF`.
+    Overflowed(f64),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -60,6 +146,43 @@ pub struct LiteralFloat {
     pub span: Span,
 }
 
+impl LiteralFloat {
+    /// The value PHP evaluates the literal to, ignoring underscore separators.
+    ///
+    /// Literals that are too large to be represented are infinite, e.g. `1e999`.
+    pub fn to_value(&self) -> f64 {
+        let value: String = self
+            .value
+            .iter()
+            .filter(|byte| **byte != b'_')
+            .map(|byte| *byte as char)
+            .collect();
+
+        value.parse().unwrap_or(0.0)
+    }
+}
+
 impl Node for LiteralFloat {
-    //
+    fn span(&self) -> Option<Span> {
+        Some(self.span)
+    }
+}
+
+relocate! {
+    enum Literal {
+        String(value),
+        Integer(value),
+        Float(value),
+    }
+    struct LiteralString { value, span, kind }
+    enum LiteralStringKind {
+        SingleQuoted,
+        DoubleQuoted,
+    }
+    struct LiteralInteger { value, span }
+    enum IntegerValue {
+        Integer(value),
+        Overflowed(value),
+    }
+    struct LiteralFloat { value, span }
 }
@@ -3,8 +3,10 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::join;
 use crate::node::Node;
 use crate::parser::ast::literals::LiteralInteger;
+use crate::parser::ast::relocate::relocate;
 use crate::parser::ast::utils::CommaSeparated;
 use crate::parser::ast::Ending;
 use crate::parser::ast::Expression;
@@ -24,8 +26,17 @@ impl Node for ForeachStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.iterator, &mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.iterator, &self.body]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.foreach), self.body.span()])
+    }
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type", content = "value")]
 pub enum ForeachStatementIterator {
@@ -63,6 +74,33 @@ impl Node for ForeachStatementIterator {
             } => vec![expression, key, value],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ForeachStatementIterator::Value {
+                expression, value, ..
+            } => {
+                vec![expression, value]
+            }
+            ForeachStatementIterator::KeyAndValue {
+                expression,
+                key,
+                value,
+                ..
+            } => vec![expression, key, value],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            ForeachStatementIterator::Value {
+                expression, value, ..
+            }
+            | ForeachStatementIterator::KeyAndValue {
+                expression, value, ..
+            } => join([expression.span(), value.span()]),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -88,6 +126,22 @@ impl Node for ForeachStatementBody {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ForeachStatementBody::Statement { statement } => vec![statement.as_ref()],
+            ForeachStatementBody::Block { statements, .. } => {
+                statements.iter().map(|s| s as &dyn Node).collect()
+            }
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            ForeachStatementBody::Statement { statement } => statement.span(),
+            ForeachStatementBody::Block { colon, ending, .. } => Some(colon.join(ending.span())),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -104,6 +158,14 @@ impl Node for ForStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.iterator, &mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.iterator, &self.body]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.r#for), self.body.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -129,6 +191,23 @@ impl Node for ForStatementIterator {
         children.extend(self.r#loop.inner.iter_mut().map(|x| x as &mut dyn Node));
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children = vec![];
+        children.extend(self.initializations.inner.iter().map(|x| x as &dyn Node));
+        children.extend(self.conditions.inner.iter().map(|x| x as &dyn Node));
+        children.extend(self.r#loop.inner.iter().map(|x| x as &dyn Node));
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.initializations.span(),
+            Some(self.initializations_semicolon),
+            Some(self.conditions_semicolon),
+            self.r#loop.span(),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -154,6 +233,22 @@ impl Node for ForStatementBody {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ForStatementBody::Statement { statement } => vec![statement.as_ref()],
+            ForStatementBody::Block { statements, .. } => {
+                statements.iter().map(|x| x as &dyn Node).collect()
+            }
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            ForStatementBody::Statement { statement } => statement.span(),
+            ForStatementBody::Block { colon, ending, .. } => Some(colon.join(ending.span())),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -172,6 +267,14 @@ impl Node for DoWhileStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.body.as_mut(), &mut self.condition]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.body.as_ref(), &self.condition]
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.r#do.join(self.semicolon))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -188,6 +291,14 @@ impl Node for WhileStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.condition, &mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.condition, &self.body]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.r#while), self.body.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -213,6 +324,22 @@ impl Node for WhileStatementBody {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            WhileStatementBody::Statement { statement } => vec![statement.as_ref()],
+            WhileStatementBody::Block { statements, .. } => {
+                statements.iter().map(|s| s as &dyn Node).collect()
+            }
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            WhileStatementBody::Statement { statement } => statement.span(),
+            WhileStatementBody::Block { colon, ending, .. } => Some(colon.join(ending.span())),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -233,6 +360,24 @@ impl Node for Level {
             Level::Parenthesized { level, .. } => level.children(),
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Level::Literal(literal) => vec![literal],
+            Level::Parenthesized { level, .. } => level.children_ref(),
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Level::Literal(literal) => literal.span(),
+            Level::Parenthesized {
+                left_parenthesis,
+                right_parenthesis,
+                ..
+            } => Some(left_parenthesis.join(*right_parenthesis)),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -250,6 +395,17 @@ impl Node for BreakStatement {
             None => vec![],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match &self.level {
+            Some(level) => vec![level],
+            None => vec![],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.r#break.join(self.ending.span()))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -267,4 +423,59 @@ impl Node for ContinueStatement {
             None => vec![],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match &self.level {
+            Some(level) => vec![level],
+            None => vec![],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.r#continue.join(self.ending.span()))
+    }
+}
+
+relocate! {
+    struct ForeachStatement { foreach, left_parenthesis, iterator, right_parenthesis, body }
+    enum ForeachStatementIterator {
+        Value { expression, r#as, ampersand, value },
+        KeyAndValue { expression, r#as, ampersand, key, double_arrow, value },
+    }
+    enum ForeachStatementBody {
+        Statement { statement },
+        Block { colon, statements, endforeach, ending },
+    }
+    struct ForStatement { r#for, left_parenthesis, iterator, right_parenthesis, body }
+    struct ForStatementIterator {
+        initializations,
+        initializations_semicolon,
+        conditions,
+        conditions_semicolon,
+        r#loop,
+    }
+    enum ForStatementBody {
+        Statement { statement },
+        Block { colon, statements, endfor, ending },
+    }
+    struct DoWhileStatement {
+        r#do,
+        body,
+        r#while,
+        left_parenthesis,
+        condition,
+        right_parenthesis,
+        semicolon,
+    }
+    struct WhileStatement { r#while, left_parenthesis, condition, right_parenthesis, body }
+    enum WhileStatementBody {
+        Statement { statement },
+        Block { colon, statements, endwhile, ending },
+    }
+    enum Level {
+        Literal(value),
+        Parenthesized { left_parenthesis, level, right_parenthesis },
+    }
+    struct BreakStatement { r#break, level, ending }
+    struct ContinueStatement { r#continue, level, ending }
 }
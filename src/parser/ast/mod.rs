@@ -1,10 +1,16 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::downcast::downcast_mut;
 use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::DocStringIndentationKind;
 use crate::lexer::token::Span;
 use crate::lexer::token::TokenKind;
+use crate::node::join;
 use crate::node::Node;
 use crate::parser::ast::arguments::ArgumentPlaceholder;
 use crate::parser::ast::arguments::{ArgumentList, SingleArgument};
@@ -37,6 +43,9 @@ use crate::parser::ast::operators::AssignmentOperationExpression;
 use crate::parser::ast::operators::BitwiseOperationExpression;
 use crate::parser::ast::operators::ComparisonOperationExpression;
 use crate::parser::ast::operators::LogicalOperationExpression;
+use crate::parser::ast::relocate::relocate;
+use crate::parser::ast::relocate::Relocate;
+use crate::parser::ast::relocate::Shift;
 use crate::parser::ast::traits::TraitStatement;
 use crate::parser::ast::try_block::TryStatement;
 use crate::parser::ast::utils::CommaSeparated;
@@ -61,6 +70,7 @@ pub mod modifiers;
 pub mod namespaces;
 pub mod operators;
 pub mod properties;
+pub(crate) mod relocate;
 pub mod traits;
 pub mod try_block;
 pub mod utils;
@@ -72,6 +82,10 @@ impl Node for Block {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.iter_mut().map(|s| s as &mut dyn Node).collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.iter().map(|s| s as &dyn Node).collect()
+    }
 }
 
 pub type Program = Block;
@@ -99,6 +113,18 @@ impl Node for StaticVar {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.var];
+        if let Some(default) = &self.default {
+            children.push(default);
+        }
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.var.span(), self.default.as_ref().and_then(Node::span)])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -108,24 +134,66 @@ pub enum Ending {
     CloseTag(Span),
 }
 
+impl Ending {
+    pub fn span(&self) -> Span {
+        match self {
+            Ending::Semicolon(span) | Ending::CloseTag(span) => *span,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type")]
 pub struct HaltCompilerStatement {
+    // From `__halt_compiler` up to the start of the content, so including the `();`.
+    pub span: Span,
+    // The raw bytes after the halt, such as the payload of a PHAR archive.
     pub content: Option<ByteString>,
+    // The byte offset the content starts at, or the end of the input if there is none, as
+    // with `__COMPILER_HALT_OFFSET__`.
+    pub offset: usize,
 }
 
-impl Node for HaltCompilerStatement {}
+impl Node for HaltCompilerStatement {
+    fn span(&self) -> Option<Span> {
+        Some(self.span)
+    }
+}
+
+impl Relocate for HaltCompilerStatement {
+    fn relocate(&mut self, shift: Shift) {
+        let Self {
+            span,
+            content,
+            offset,
+        } = self;
+
+        span.relocate(shift);
+        content.relocate(shift);
+        *offset = offset.wrapping_add_signed(shift.position);
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type")]
 pub struct StaticStatement {
+    pub r#static: Span,
     pub vars: Vec<StaticVar>,
+    pub semicolon: Span,
 }
 
 impl Node for StaticStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.vars.iter_mut().map(|v| v as &mut dyn Node).collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.vars.iter().map(|v| v as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.r#static.join(self.semicolon))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -135,14 +203,76 @@ pub struct SwitchStatement {
     pub left_parenthesis: Span,
     pub condition: Expression,
     pub right_parenthesis: Span,
-    pub cases: Vec<Case>,
+    pub body: SwitchStatementBody,
 }
 
 impl Node for SwitchStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
-        let mut children: Vec<&mut dyn Node> = vec![&mut self.condition];
-        children.extend(self.cases.iter_mut().map(|c| c as &mut dyn Node));
-        children
+        vec![&mut self.condition, &mut self.body]
+    }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.condition, &self.body]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.switch), self.body.span()])
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(tag = "type", content = "value")]
+pub enum SwitchStatementBody {
+    Braced {
+        left_brace: Span,  // `{`
+        cases: Vec<Case>,  // `case *expression*: *statements*`
+        right_brace: Span, // `}`
+    },
+    Block {
+        colon: Span,      // `:`
+        cases: Vec<Case>, // `case *expression*: *statements*`
+        endswitch: Span,  // `endswitch`
+        ending: Ending,   // `;` or `?>`
+    },
+}
+
+impl SwitchStatementBody {
+    pub fn cases(&self) -> &[Case] {
+        match self {
+            SwitchStatementBody::Braced { cases, .. }
+            | SwitchStatementBody::Block { cases, .. } => cases,
+        }
+    }
+
+    pub fn cases_mut(&mut self) -> &mut [Case] {
+        match self {
+            SwitchStatementBody::Braced { cases, .. }
+            | SwitchStatementBody::Block { cases, .. } => cases,
+        }
+    }
+}
+
+impl Node for SwitchStatementBody {
+    fn children(&mut self) -> Vec<&mut dyn Node> {
+        self.cases_mut()
+            .iter_mut()
+            .map(|case| case as &mut dyn Node)
+            .collect()
+    }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.cases().iter().map(|case| case as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            SwitchStatementBody::Braced {
+                left_brace,
+                right_brace,
+                ..
+            } => Some(left_brace.join(*right_brace)),
+            SwitchStatementBody::Block { colon, ending, .. } => Some(colon.join(ending.span())),
+        }
     }
 }
 
@@ -158,6 +288,14 @@ impl Node for EchoStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.values.iter_mut().map(|v| v as &mut dyn Node).collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.values.iter().map(|v| v as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.echo.join(self.ending.span()))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -176,27 +314,51 @@ impl Node for ReturnStatement {
             vec![]
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        if let Some(value) = &self.value {
+            vec![value]
+        } else {
+            vec![]
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.r#return.join(self.ending.span()))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type")]
 pub struct UseStatement {
+    pub r#use: Span,
     pub kind: UseKind,
     pub uses: Vec<Use>,
+    pub semicolon: Span,
 }
 
 impl Node for UseStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.uses.iter_mut().map(|u| u as &mut dyn Node).collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.uses.iter().map(|u| u as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.r#use.join(self.semicolon))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type")]
 pub struct GroupUseStatement {
+    pub r#use: Span,
     pub prefix: SimpleIdentifier,
     pub kind: UseKind,
     pub uses: Vec<Use>,
+    pub semicolon: Span,
 }
 
 impl Node for GroupUseStatement {
@@ -205,11 +367,25 @@ impl Node for GroupUseStatement {
         children.extend(self.uses.iter_mut().map(|u| u as &mut dyn Node));
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.prefix];
+        children.extend(self.uses.iter().map(|u| u as &dyn Node));
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.r#use.join(self.semicolon))
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+// `Clone`, `PartialEq`, `Debug`, `Serialize`, and `Relocate` are implemented by
+// `stack_safe!` below.
+#[allow(clippy::large_enum_variant)]
+#[derive(Eq, Deserialize, JsonSchema)]
 #[serde(tag = "type", content = "value")]
 pub enum Statement {
+    Shebang(ShebangStatement),
     FullOpeningTag(FullOpeningTagStatement),
     ShortOpeningTag(ShortOpeningTagStatement),
     EchoOpeningTag(EchoOpeningTagStatement),
@@ -246,11 +422,21 @@ pub enum Statement {
     Global(GlobalStatement),
     Declare(DeclareStatement),
     Noop(Span),
+    // A statement that failed to parse, when parsing with recovery.
+    Missing(Span),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+
+pub struct ShebangStatement {
+    pub span: Span,
+    pub value: ByteString, // `#!/usr/bin/env php\n`
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct InlineHtmlStatement {
+    pub span: Span,
     pub html: ByteString,
 }
 
@@ -314,6 +500,84 @@ impl Node for Statement {
             _ => vec![],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Statement::Label(statement) => vec![statement],
+            Statement::Goto(statement) => vec![statement],
+            Statement::HaltCompiler(statement) => vec![statement],
+            Statement::Static(statement) => vec![statement],
+            Statement::DoWhile(statement) => vec![statement],
+            Statement::While(statement) => vec![statement],
+            Statement::For(statement) => vec![statement],
+            Statement::Foreach(statement) => vec![statement],
+            Statement::Break(statement) => vec![statement],
+            Statement::Continue(statement) => vec![statement],
+            Statement::Constant(statement) => vec![statement],
+            Statement::Function(statement) => vec![statement],
+            Statement::Class(statement) => vec![statement],
+            Statement::Trait(statement) => vec![statement],
+            Statement::Interface(statement) => vec![statement],
+            Statement::If(statement) => vec![statement],
+            Statement::Switch(statement) => vec![statement],
+            Statement::Echo(statement) => vec![statement],
+            Statement::Expression(statement) => vec![statement],
+            Statement::Return(statement) => vec![statement],
+            Statement::Namespace(statement) => vec![statement],
+            Statement::Use(statement) => vec![statement],
+            Statement::GroupUse(statement) => vec![statement],
+            Statement::Comment(statement) => vec![statement],
+            Statement::Try(statement) => vec![statement],
+            Statement::UnitEnum(statement) => vec![statement],
+            Statement::BackedEnum(statement) => vec![statement],
+            Statement::Block(statement) => vec![statement],
+            Statement::Global(statement) => vec![statement],
+            Statement::Declare(statement) => vec![statement],
+            _ => vec![],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || match self {
+            Statement::Shebang(statement) => Some(statement.span),
+            Statement::FullOpeningTag(statement) => Some(statement.span),
+            Statement::ShortOpeningTag(statement) => Some(statement.span),
+            Statement::EchoOpeningTag(statement) => Some(statement.span),
+            Statement::ClosingTag(statement) => Some(statement.span),
+            Statement::InlineHtml(statement) => Some(statement.span),
+            Statement::Label(statement) => statement.span(),
+            Statement::Goto(statement) => statement.span(),
+            Statement::HaltCompiler(statement) => statement.span(),
+            Statement::Static(statement) => statement.span(),
+            Statement::DoWhile(statement) => statement.span(),
+            Statement::While(statement) => statement.span(),
+            Statement::For(statement) => statement.span(),
+            Statement::Foreach(statement) => statement.span(),
+            Statement::Break(statement) => statement.span(),
+            Statement::Continue(statement) => statement.span(),
+            Statement::Constant(statement) => statement.span(),
+            Statement::Function(statement) => statement.span(),
+            Statement::Class(statement) => statement.span(),
+            Statement::Trait(statement) => statement.span(),
+            Statement::Interface(statement) => statement.span(),
+            Statement::If(statement) => statement.span(),
+            Statement::Switch(statement) => statement.span(),
+            Statement::Echo(statement) => statement.span(),
+            Statement::Expression(statement) => statement.span(),
+            Statement::Return(statement) => statement.span(),
+            Statement::Namespace(statement) => statement.span(),
+            Statement::Use(statement) => statement.span(),
+            Statement::GroupUse(statement) => statement.span(),
+            Statement::Comment(statement) => statement.span(),
+            Statement::Try(statement) => statement.span(),
+            Statement::UnitEnum(statement) => statement.span(),
+            Statement::BackedEnum(statement) => statement.span(),
+            Statement::Block(statement) => statement.span(),
+            Statement::Global(statement) => statement.span(),
+            Statement::Declare(statement) => statement.span(),
+            Statement::Noop(span) | Statement::Missing(span) => Some(*span),
+        })
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -327,6 +591,14 @@ impl Node for ExpressionStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.expression]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.expression]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.expression.span(), Some(self.ending.span())])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -334,6 +606,7 @@ impl Node for ExpressionStatement {
 pub struct GlobalStatement {
     pub global: Span,
     pub variables: Vec<Variable>,
+    pub semicolon: Span,
 }
 
 impl Node for GlobalStatement {
@@ -343,6 +616,14 @@ impl Node for GlobalStatement {
             .map(|v| v as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.variables.iter().map(|v| v as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.global.join(self.semicolon))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -360,6 +641,14 @@ impl Node for BlockStatement {
             .map(|s| s as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.statements.iter().map(|s| s as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.left_brace.join(self.right_brace))
+    }
 }
 
 // See https://www.php.net/manual/en/language.types.type-juggling.php#language.types.typecasting for more info.
@@ -375,9 +664,11 @@ pub enum CastKind {
     Unset,
 }
 
-impl From<TokenKind> for CastKind {
-    fn from(kind: TokenKind) -> Self {
-        match kind {
+impl TryFrom<&TokenKind> for CastKind {
+    type Error = ();
+
+    fn try_from(kind: &TokenKind) -> Result<Self, Self::Error> {
+        Ok(match kind {
             TokenKind::StringCast | TokenKind::BinaryCast => Self::String,
             TokenKind::ObjectCast => Self::Object,
             TokenKind::BoolCast | TokenKind::BooleanCast => Self::Bool,
@@ -385,21 +676,25 @@ impl From<TokenKind> for CastKind {
             TokenKind::FloatCast | TokenKind::DoubleCast | TokenKind::RealCast => Self::Float,
             TokenKind::UnsetCast => Self::Unset,
             TokenKind::ArrayCast => Self::Array,
-            _ => unreachable!(),
-        }
+            _ => return Err(()),
+        })
     }
 }
 
-impl From<&TokenKind> for CastKind {
-    fn from(kind: &TokenKind) -> Self {
-        kind.clone().into()
+impl TryFrom<TokenKind> for CastKind {
+    type Error = ();
+
+    fn try_from(kind: TokenKind) -> Result<Self, Self::Error> {
+        Self::try_from(&kind)
     }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct Case {
+    pub keyword: Span, // `case` or `default`
     pub condition: Option<Expression>,
+    pub colon: Span, // `:` or `;`
     pub body: Block,
 }
 
@@ -417,6 +712,28 @@ impl Node for Case {
         );
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![];
+        if let Some(condition) = &self.condition {
+            children.push(condition);
+        }
+        children.extend(
+            self.body
+                .iter()
+                .map(|statement| statement as &dyn Node)
+                .collect::<Vec<&dyn Node>>(),
+        );
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            Some(self.keyword),
+            Some(self.colon),
+            self.body.last().and_then(|statement| statement.span()),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -435,6 +752,14 @@ impl Node for Use {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name];
+        if let Some(alias) = &self.alias {
+            children.push(alias);
+        }
+        children
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -556,6 +881,14 @@ impl Node for FunctionCallExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), &mut self.arguments]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), &self.arguments]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.target.span(), self.arguments.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -569,6 +902,14 @@ impl Node for FunctionClosureCreationExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.target.span(), Some(self.placeholder.span())])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -590,6 +931,14 @@ impl Node for MethodCallExpression {
             &mut self.arguments,
         ]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), self.method.as_ref(), &self.arguments]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.target.span(), Some(self.arrow), self.arguments.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -607,6 +956,18 @@ impl Node for MethodClosureCreationExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), self.method.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), self.method.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.target.span(),
+            Some(self.arrow),
+            Some(self.placeholder.span()),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -628,6 +989,18 @@ impl Node for NullsafeMethodCallExpression {
             &mut self.arguments,
         ]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), self.method.as_ref(), &self.arguments]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.target.span(),
+            Some(self.question_arrow),
+            self.arguments.span(),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -645,6 +1018,18 @@ impl Node for StaticMethodCallExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), &mut self.method, &mut self.arguments]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), &self.method, &self.arguments]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.target.span(),
+            Some(self.double_colon),
+            self.arguments.span(),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -662,6 +1047,18 @@ impl Node for StaticVariableMethodCallExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), &mut self.method, &mut self.arguments]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), &self.method, &self.arguments]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.target.span(),
+            Some(self.double_colon),
+            self.arguments.span(),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -679,6 +1076,18 @@ impl Node for StaticMethodClosureCreationExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), &mut self.method]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), &self.method]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.target.span(),
+            Some(self.double_colon),
+            Some(self.placeholder.span()),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -696,6 +1105,18 @@ impl Node for StaticVariableMethodClosureCreationExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), &mut self.method]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), &self.method]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.target.span(),
+            Some(self.double_colon),
+            Some(self.placeholder.span()),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -711,6 +1132,14 @@ impl Node for PropertyFetchExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), self.property.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), self.property.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.target.span(), Some(self.arrow), self.property.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -726,6 +1155,18 @@ impl Node for NullsafePropertyFetchExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), self.property.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), self.property.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.target.span(),
+            Some(self.question_arrow),
+            self.property.span(),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -741,6 +1182,18 @@ impl Node for StaticPropertyFetchExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), &mut self.property]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), &self.property]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.target.span(),
+            Some(self.double_colon),
+            self.property.span(),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -756,6 +1209,18 @@ impl Node for ConstantFetchExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), &mut self.constant]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), &self.constant]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.target.span(),
+            Some(self.double_colon),
+            self.constant.span(),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -771,6 +1236,14 @@ impl Node for ShortArrayExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.items]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.items]
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.start.join(self.end))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -788,23 +1261,39 @@ impl Node for ArrayExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.items]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.items]
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.array.join(self.end))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ListExpression {
-    pub list: Span,
-    // `list`
+    pub list: Option<Span>,
+    // `list`, or `None` for `[$a, $b]`
     pub start: Span,
-    // `(`
+    // `(` or `[`
     pub items: Vec<ListEntry>,
     // `$a, $b`
-    pub end: Span, // `)`
+    pub end: Span, // `)` or `]`
 }
 
 impl Node for ListExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.items.iter_mut().map(|i| i as &mut dyn Node).collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.items.iter().map(|i| i as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.list, Some(self.start), Some(self.end)])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -824,11 +1313,29 @@ impl Node for NewExpression {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![self.target.as_ref()];
+        if let Some(arguments) = &self.arguments {
+            children.push(arguments);
+        }
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            Some(self.new),
+            self.target.span(),
+            self.arguments.as_ref().and_then(Node::span),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct InterpolatedStringExpression {
+    pub start: Span, // `"` or `b"`
     pub parts: Vec<StringPart>,
+    pub end: Span, // `"`
 }
 
 impl Node for InterpolatedStringExpression {
@@ -838,12 +1345,56 @@ impl Node for InterpolatedStringExpression {
             .map(|part| part as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.parts.iter().map(|part| part as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.start.join(self.end))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct HeredocExpression {
+    pub start: Span, // `<<<LABEL`
     pub label: ByteString,
+    // The parts as written, including the indentation removed by PHP, see
+    // [`HeredocExpression::indentation_stripped`].
     pub parts: Vec<StringPart>,
+    // The whitespace before the closing label.
+    pub indentation_kind: DocStringIndentationKind,
+    pub indentation_amount: usize,
+    pub end: Span, // the closing label, along with the line ending and whitespace before it
+}
+
+impl HeredocExpression {
+    /// The parts with the indentation of the closing label removed from the start of
+    /// every line, which is the string PHP evaluates.
+    pub fn indentation_stripped(&self) -> Vec<StringPart> {
+        let mut line_start = true;
+
+        self.parts
+            .iter()
+            .filter_map(|part| match part {
+                StringPart::Literal(literal) => {
+                    let value =
+                        strip_indentation(&literal.value, &mut line_start, self.indentation_amount);
+
+                    (!value.is_empty()).then(|| {
+                        StringPart::Literal(LiteralStringPart {
+                            span: literal.span,
+                            value: value.into(),
+                        })
+                    })
+                }
+                StringPart::Expression(_) => {
+                    line_start = false;
+                    Some(part.clone())
+                }
+            })
+            .collect()
+    }
 }
 
 impl Node for HeredocExpression {
@@ -853,19 +1404,73 @@ impl Node for HeredocExpression {
             .map(|part| part as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.parts.iter().map(|part| part as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.start.join(self.end))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct NowdocExpression {
+    pub start: Span, // `<<<'LABEL'`
     pub label: ByteString,
+    // The value as written, including the indentation removed by PHP, see
+    // [`NowdocExpression::indentation_stripped`].
     pub value: ByteString,
+    // The whitespace before the closing label.
+    pub indentation_kind: DocStringIndentationKind,
+    pub indentation_amount: usize,
+    pub end: Span, // the closing label, along with the line ending and whitespace before it
 }
 
-impl Node for NowdocExpression {}
+impl NowdocExpression {
+    /// The value with the indentation of the closing label removed from the start of
+    /// every line, which is the string PHP evaluates.
+    pub fn indentation_stripped(&self) -> ByteString {
+        strip_indentation(&self.value, &mut true, self.indentation_amount).into()
+    }
+}
+
+impl Node for NowdocExpression {
+    fn span(&self) -> Option<Span> {
+        Some(self.start.join(self.end))
+    }
+}
+
+// Remove up to `amount` bytes of whitespace from the start of every line. The
+// parser already rejected lines that are indented less than the closing label, so
+// only empty lines can have less.
+fn strip_indentation(bytes: &[u8], line_start: &mut bool, amount: usize) -> Vec<u8> {
+    let mut stripped = Vec::with_capacity(bytes.len());
+    let mut skip = if *line_start { amount } else { 0 };
+
+    for byte in bytes {
+        if skip > 0 && matches!(byte, b' ' | b'\t') {
+            skip -= 1;
+            continue;
+        }
+
+        skip = 0;
+        stripped.push(*byte);
+        if *byte == b'\n' {
+            skip = amount;
+        }
+    }
+
+    *line_start = bytes.last().map_or(*line_start, |byte| *byte == b'\n');
+
+    stripped
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ShellExecExpression {
+    pub start: Span, // the opening backtick
     pub parts: Vec<StringPart>,
+    pub end: Span, // the closing backtick
 }
 
 impl Node for ShellExecExpression {
@@ -875,14 +1480,27 @@ impl Node for ShellExecExpression {
             .map(|part| part as &mut dyn Node)
             .collect()
     }
-}
 
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.parts.iter().map(|part| part as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.start.join(self.end))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct BoolExpression {
+    pub span: Span,
     pub value: bool,
 }
 
-impl Node for BoolExpression {}
+impl Node for BoolExpression {
+    fn span(&self) -> Option<Span> {
+        Some(self.span)
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ArrayIndexExpression {
@@ -894,12 +1512,24 @@ pub struct ArrayIndexExpression {
 
 impl Node for ArrayIndexExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
-        let mut children: Vec<&mut dyn Node> = vec![];
+        let mut children: Vec<&mut dyn Node> = vec![self.array.as_mut()];
         if let Some(index) = &mut self.index {
             children.push(index.as_mut());
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![self.array.as_ref()];
+        if let Some(index) = &self.index {
+            children.push(index.as_ref());
+        }
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.array.span(), Some(self.right_bracket)])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -915,6 +1545,18 @@ impl Node for ShortTernaryExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.condition.as_mut(), self.r#else.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.condition.as_ref(), self.r#else.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.condition.span(),
+            Some(self.question_colon),
+            self.r#else.span(),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -938,6 +1580,22 @@ impl Node for TernaryExpression {
             self.r#else.as_mut(),
         ]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![
+            self.condition.as_ref(),
+            self.then.as_ref(),
+            self.r#else.as_ref(),
+        ]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.condition.span(),
+            Some(self.question),
+            self.r#else.span(),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -951,10 +1609,19 @@ impl Node for CoalesceExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.lhs.as_mut(), self.rhs.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.lhs.as_ref(), self.rhs.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.lhs.span(), Some(self.double_question), self.rhs.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct CloneExpression {
+    pub clone: Span,
     pub target: Box<Expression>,
 }
 
@@ -962,6 +1629,14 @@ impl Node for CloneExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.clone), self.target.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -973,6 +1648,7 @@ pub struct MatchExpression {
     pub left_brace: Span,
     pub default: Option<Box<DefaultMatchArm>>,
     pub arms: Vec<MatchArm>,
+    pub commas: Vec<Span>, // `,` separating the arms, including a trailing one
     pub right_brace: Span,
 }
 
@@ -990,10 +1666,29 @@ impl Node for MatchExpression {
         );
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![self.condition.as_ref()];
+        if let Some(default) = &self.default {
+            children.push(default.as_ref());
+        }
+        children.extend(
+            self.arms
+                .iter()
+                .map(|arm| arm as &dyn Node)
+                .collect::<Vec<&dyn Node>>(),
+        );
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.keyword.join(self.right_brace))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ThrowExpression {
+    pub throw: Span,
     pub value: Box<Expression>,
 }
 
@@ -1001,10 +1696,19 @@ impl Node for ThrowExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.value.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.value.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.throw), self.value.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct YieldExpression {
+    pub r#yield: Span,
     pub key: Option<Box<Expression>>,
     pub value: Option<Box<Expression>>,
 }
@@ -1020,10 +1724,31 @@ impl Node for YieldExpression {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![];
+        if let Some(key) = &self.key {
+            children.push(key.as_ref());
+        }
+        if let Some(value) = &self.value {
+            children.push(value.as_ref());
+        }
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            Some(self.r#yield),
+            self.key.as_ref().and_then(|key| key.span()),
+            self.value.as_ref().and_then(|value| value.span()),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct YieldFromExpression {
+    pub r#yield: Span,
+    pub from: Span,
     pub value: Box<Expression>,
 }
 
@@ -1031,6 +1756,14 @@ impl Node for YieldFromExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.value.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.value.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.r#yield), self.value.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -1044,9 +1777,20 @@ impl Node for CastExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.value.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.value.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.cast), self.value.span()])
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+// `Clone`, `PartialEq`, `Debug`, `Serialize`, and `Relocate` are implemented by
+// `stack_safe!` below.
+#[allow(clippy::large_enum_variant)]
+#[derive(Eq, Deserialize, JsonSchema)]
 #[serde(tag = "type", content = "value")]
 pub enum Expression {
     // eval("$a = 1")
@@ -1118,11 +1862,11 @@ pub enum Expression {
     // `foo()::bar` or `foo()::{$name}`
     ConstantFetch(ConstantFetchExpression),
     // `static`
-    Static,
+    Static(Span),
     // `self`
-    Self_,
+    Self_(Span),
     // `parent`
-    Parent,
+    Parent(Span),
     // `[1, 2, 3]`
     ShortArray(ShortArrayExpression),
     // `array(1, 2, 3)`
@@ -1150,7 +1894,7 @@ pub enum Expression {
     // `$foo[0]`
     ArrayIndex(ArrayIndexExpression),
     // `null`
-    Null,
+    Null(Span),
     // `__DIR__`, etc
     MagicConstant(MagicConstantExpression),
     // `foo() ?: bar()`
@@ -1173,18 +1917,36 @@ pub enum Expression {
     Cast(CastExpression),
     // ;
     Noop,
+    // An expression that is expected but absent, when parsing with recovery.
+    Missing(Span),
 }
 
 impl Node for EvalExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.argument.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.argument.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.eval), self.argument.span()])
+    }
 }
 
 impl Node for EmptyExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.argument.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.argument.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.empty), self.argument.span()])
+    }
 }
 
 impl Node for DieExpression {
@@ -1195,6 +1957,21 @@ impl Node for DieExpression {
             vec![]
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        if let Some(argument) = &self.argument {
+            vec![argument.as_ref()]
+        } else {
+            vec![]
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            Some(self.die),
+            self.argument.as_ref().and_then(|argument| argument.span()),
+        ])
+    }
 }
 
 impl Node for ExitExpression {
@@ -1205,18 +1982,49 @@ impl Node for ExitExpression {
             vec![]
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        if let Some(argument) = &self.argument {
+            vec![argument.as_ref()]
+        } else {
+            vec![]
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            Some(self.exit),
+            self.argument.as_ref().and_then(|argument| argument.span()),
+        ])
+    }
 }
 
 impl Node for IssetExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.arguments]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.arguments]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.isset), self.arguments.span()])
+    }
 }
 
 impl Node for UnsetExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.arguments]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.arguments]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.unset), self.arguments.span()])
+    }
 }
 
 impl Node for PrintExpression {
@@ -1229,60 +2037,150 @@ impl Node for PrintExpression {
             vec![]
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        if let Some(argument) = &self.argument {
+            vec![argument.as_ref()]
+        } else if let Some(value) = &self.value {
+            vec![value.as_ref()]
+        } else {
+            vec![]
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            Some(self.print),
+            self.value.as_ref().and_then(|value| value.span()),
+            self.argument.as_ref().and_then(|argument| argument.span()),
+        ])
+    }
 }
 
 impl Node for ConcatExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.left.as_mut(), self.right.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.left.span(), Some(self.dot), self.right.span()])
+    }
 }
 
 impl Node for InstanceofExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.left.as_mut(), self.right.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.left.span(), Some(self.instanceof), self.right.span()])
+    }
 }
 
 impl Node for ReferenceExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.right.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.right.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.ampersand), self.right.span()])
+    }
 }
 
 impl Node for ParenthesizedExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.expr.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.expr.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.start.join(self.end))
+    }
 }
 
 impl Node for ErrorSuppressExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.expr.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.expr.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.at), self.expr.span()])
+    }
 }
 
 impl Node for IncludeExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.path.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.path.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.include), self.path.span()])
+    }
 }
 
 impl Node for IncludeOnceExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.path.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.path.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.include_once), self.path.span()])
+    }
 }
 
 impl Node for RequireExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.path.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.path.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.require), self.path.span()])
+    }
 }
 
 impl Node for RequireOnceExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.path.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.path.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.require_once), self.path.span()])
+    }
 }
 
 impl Node for Expression {
@@ -1325,9 +2223,9 @@ impl Node for Expression {
             Expression::NullsafePropertyFetch(expression) => vec![expression],
             Expression::StaticPropertyFetch(expression) => vec![expression],
             Expression::ConstantFetch(expression) => vec![expression],
-            Expression::Static => vec![],
-            Expression::Self_ => vec![],
-            Expression::Parent => vec![],
+            Expression::Static(_) => vec![],
+            Expression::Self_(_) => vec![],
+            Expression::Parent(_) => vec![],
             Expression::ShortArray(expression) => vec![expression],
             Expression::Array(expression) => vec![expression],
             Expression::List(expression) => vec![expression],
@@ -1341,7 +2239,7 @@ impl Node for Expression {
             Expression::AnonymousClass(expression) => vec![expression],
             Expression::Bool(_) => vec![],
             Expression::ArrayIndex(expression) => vec![expression],
-            Expression::Null => vec![],
+            Expression::Null(_) => vec![],
             Expression::MagicConstant(constant) => vec![constant],
             Expression::ShortTernary(expression) => vec![expression],
             Expression::Ternary(expression) => vec![expression],
@@ -1353,42 +2251,532 @@ impl Node for Expression {
             Expression::YieldFrom(expression) => vec![expression],
             Expression::Cast(expression) => vec![expression],
             Expression::Noop => vec![],
+            Expression::Missing(_) => vec![],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Expression::Eval(expression) => vec![expression],
+            Expression::Empty(expression) => vec![expression],
+            Expression::Die(expression) => vec![expression],
+            Expression::Exit(expression) => vec![expression],
+            Expression::Isset(expression) => vec![expression],
+            Expression::Unset(expression) => vec![expression],
+            Expression::Print(expression) => vec![expression],
+            Expression::Literal(literal) => vec![literal],
+            Expression::ArithmeticOperation(operation) => vec![operation],
+            Expression::AssignmentOperation(operation) => vec![operation],
+            Expression::BitwiseOperation(operation) => vec![operation],
+            Expression::ComparisonOperation(operation) => vec![operation],
+            Expression::LogicalOperation(operation) => vec![operation],
+            Expression::Concat(expression) => vec![expression],
+            Expression::Instanceof(expression) => vec![expression],
+            Expression::Reference(expression) => vec![expression],
+            Expression::Parenthesized(expression) => vec![expression],
+            Expression::ErrorSuppress(expression) => vec![expression],
+            Expression::Identifier(identifier) => vec![identifier],
+            Expression::Variable(variable) => vec![variable],
+            Expression::Include(expression) => vec![expression],
+            Expression::IncludeOnce(expression) => vec![expression],
+            Expression::Require(expression) => vec![expression],
+            Expression::RequireOnce(expression) => vec![expression],
+            Expression::FunctionCall(expression) => vec![expression],
+            Expression::FunctionClosureCreation(expression) => vec![expression],
+            Expression::MethodCall(expression) => vec![expression],
+            Expression::MethodClosureCreation(expression) => vec![expression],
+            Expression::NullsafeMethodCall(expression) => vec![expression],
+            Expression::StaticMethodCall(expression) => vec![expression],
+            Expression::StaticVariableMethodCall(expression) => vec![expression],
+            Expression::StaticMethodClosureCreation(expression) => vec![expression],
+            Expression::StaticVariableMethodClosureCreation(expression) => vec![expression],
+            Expression::PropertyFetch(expression) => vec![expression],
+            Expression::NullsafePropertyFetch(expression) => vec![expression],
+            Expression::StaticPropertyFetch(expression) => vec![expression],
+            Expression::ConstantFetch(expression) => vec![expression],
+            Expression::Static(_) => vec![],
+            Expression::Self_(_) => vec![],
+            Expression::Parent(_) => vec![],
+            Expression::ShortArray(expression) => vec![expression],
+            Expression::Array(expression) => vec![expression],
+            Expression::List(expression) => vec![expression],
+            Expression::Closure(expression) => vec![expression],
+            Expression::ArrowFunction(expression) => vec![expression],
+            Expression::New(expression) => vec![expression],
+            Expression::InterpolatedString(expression) => vec![expression],
+            Expression::Heredoc(expression) => vec![expression],
+            Expression::Nowdoc(expression) => vec![expression],
+            Expression::ShellExec(expression) => vec![expression],
+            Expression::AnonymousClass(expression) => vec![expression],
+            Expression::Bool(_) => vec![],
+            Expression::ArrayIndex(expression) => vec![expression],
+            Expression::Null(_) => vec![],
+            Expression::MagicConstant(constant) => vec![constant],
+            Expression::ShortTernary(expression) => vec![expression],
+            Expression::Ternary(expression) => vec![expression],
+            Expression::Coalesce(expression) => vec![expression],
+            Expression::Clone(expression) => vec![expression],
+            Expression::Match(expression) => vec![expression],
+            Expression::Throw(expression) => vec![expression],
+            Expression::Yield(expression) => vec![expression],
+            Expression::YieldFrom(expression) => vec![expression],
+            Expression::Cast(expression) => vec![expression],
+            Expression::Noop => vec![],
+            Expression::Missing(_) => vec![],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        let key = self as *const Expression as usize;
+        if let Some(span) = SPANS.with(|spans| spans.borrow().as_ref()?.get(&key).copied()) {
+            return span;
+        }
+
+        let span = stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || match self {
+            Expression::Eval(expression) => expression.span(),
+            Expression::Empty(expression) => expression.span(),
+            Expression::Die(expression) => expression.span(),
+            Expression::Exit(expression) => expression.span(),
+            Expression::Isset(expression) => expression.span(),
+            Expression::Unset(expression) => expression.span(),
+            Expression::Print(expression) => expression.span(),
+            Expression::Literal(expression) => expression.span(),
+            Expression::ArithmeticOperation(expression) => expression.span(),
+            Expression::AssignmentOperation(expression) => expression.span(),
+            Expression::BitwiseOperation(expression) => expression.span(),
+            Expression::ComparisonOperation(expression) => expression.span(),
+            Expression::LogicalOperation(expression) => expression.span(),
+            Expression::Concat(expression) => expression.span(),
+            Expression::Instanceof(expression) => expression.span(),
+            Expression::Reference(expression) => expression.span(),
+            Expression::Parenthesized(expression) => expression.span(),
+            Expression::ErrorSuppress(expression) => expression.span(),
+            Expression::Identifier(expression) => expression.span(),
+            Expression::Variable(expression) => expression.span(),
+            Expression::Include(expression) => expression.span(),
+            Expression::IncludeOnce(expression) => expression.span(),
+            Expression::Require(expression) => expression.span(),
+            Expression::RequireOnce(expression) => expression.span(),
+            Expression::FunctionCall(expression) => expression.span(),
+            Expression::FunctionClosureCreation(expression) => expression.span(),
+            Expression::MethodCall(expression) => expression.span(),
+            Expression::MethodClosureCreation(expression) => expression.span(),
+            Expression::NullsafeMethodCall(expression) => expression.span(),
+            Expression::StaticMethodCall(expression) => expression.span(),
+            Expression::StaticVariableMethodCall(expression) => expression.span(),
+            Expression::StaticMethodClosureCreation(expression) => expression.span(),
+            Expression::StaticVariableMethodClosureCreation(expression) => expression.span(),
+            Expression::PropertyFetch(expression) => expression.span(),
+            Expression::NullsafePropertyFetch(expression) => expression.span(),
+            Expression::StaticPropertyFetch(expression) => expression.span(),
+            Expression::ConstantFetch(expression) => expression.span(),
+            Expression::ShortArray(expression) => expression.span(),
+            Expression::Array(expression) => expression.span(),
+            Expression::List(expression) => expression.span(),
+            Expression::Closure(expression) => expression.span(),
+            Expression::ArrowFunction(expression) => expression.span(),
+            Expression::New(expression) => expression.span(),
+            Expression::InterpolatedString(expression) => expression.span(),
+            Expression::Heredoc(expression) => expression.span(),
+            Expression::Nowdoc(expression) => expression.span(),
+            Expression::ShellExec(expression) => expression.span(),
+            Expression::AnonymousClass(expression) => expression.span(),
+            Expression::Bool(expression) => expression.span(),
+            Expression::ArrayIndex(expression) => expression.span(),
+            Expression::MagicConstant(expression) => expression.span(),
+            Expression::ShortTernary(expression) => expression.span(),
+            Expression::Ternary(expression) => expression.span(),
+            Expression::Coalesce(expression) => expression.span(),
+            Expression::Clone(expression) => expression.span(),
+            Expression::Match(expression) => expression.span(),
+            Expression::Throw(expression) => expression.span(),
+            Expression::Yield(expression) => expression.span(),
+            Expression::YieldFrom(expression) => expression.span(),
+            Expression::Cast(expression) => expression.span(),
+            Expression::Static(span)
+            | Expression::Self_(span)
+            | Expression::Parent(span)
+            | Expression::Null(span) => Some(*span),
+            Expression::Noop => None,
+            Expression::Missing(span) => Some(*span),
+        });
+
+        SPANS.with(|spans| {
+            if let Some(spans) = spans.borrow_mut().as_mut() {
+                spans.insert(key, span);
+            }
+        });
+
+        span
+    }
+}
+
+thread_local! {
+    // The spans of the expressions computed within `with_cached_spans`, by address.
+    static SPANS: RefCell<Option<HashMap<usize, Option<Span>>>> = const { RefCell::new(None) };
+}
+
+/// Run the function with the span of every expression computed at most once.
+///
+/// The span of an expression covers all of the expressions within it, so asking for the
+/// span of every node of a chain of operators, calls, or fetches takes quadratic time
+/// otherwise. The program must not change while the function runs.
+pub(crate) fn with_cached_spans<R>(f: impl FnOnce() -> R) -> R {
+    // empties the cache once the outermost call returns, or unwinds.
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            SPANS.with(|spans| *spans.borrow_mut() = None);
+        }
+    }
+
+    let outermost = SPANS.with(|spans| {
+        let mut spans = spans.borrow_mut();
+        if spans.is_some() {
+            return false;
+        }
+
+        *spans = Some(HashMap::new());
+        true
+    });
+
+    let _guard = outermost.then_some(Guard);
+
+    f()
+}
+
+// Statements and expressions are cloned, compared, printed, and relocated recursively,
+// and chains of operators, calls, and fetches can be nested without limit, so the stack
+// is extended on the heap whenever it runs low. These match the derived implementations.
+macro_rules! stack_safe {
+    ($name:ident { units: [$($unit:ident),* $(,)?], tuples: [$($tuple:ident),* $(,)?] $(,)? }) => {
+        impl Clone for $name {
+            fn clone(&self) -> Self {
+                stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || match self {
+                    $($name::$unit => $name::$unit,)*
+                    $($name::$tuple(value) => $name::$tuple(value.clone()),)*
+                })
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || match (self, other) {
+                    $(($name::$unit, $name::$unit) => true,)*
+                    $(($name::$tuple(left), $name::$tuple(right)) => left == right,)*
+                    _ => false,
+                })
+            }
+        }
+
+        impl Relocate for $name {
+            fn relocate(&mut self, shift: Shift) {
+                stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || match self {
+                    $($name::$unit => {})*
+                    $($name::$tuple(value) => value.relocate(shift),)*
+                })
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || match self {
+                    $($name::$unit => f.write_str(stringify!($unit)),)*
+                    $(
+                        $name::$tuple(value) => {
+                            f.debug_tuple(stringify!($tuple)).field(value).finish()
+                        }
+                    )*
+                })
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                use serde::ser::SerializeStruct;
+
+                stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || match self {
+                    $(
+                        $name::$unit => {
+                            let mut state = serializer.serialize_struct(stringify!($name), 1)?;
+                            state.serialize_field("type", stringify!($unit))?;
+                            state.end()
+                        }
+                    )*
+                    $(
+                        $name::$tuple(value) => {
+                            let mut state = serializer.serialize_struct(stringify!($name), 2)?;
+                            state.serialize_field("type", stringify!($tuple))?;
+                            state.serialize_field("value", value)?;
+                            state.end()
+                        }
+                    )*
+                })
+            }
+        }
+    };
+}
+
+const STACK_RED_ZONE: usize = 64 * 1024;
+const STACK_SEGMENT_SIZE: usize = 1024 * 1024;
+
+stack_safe!(Statement {
+    units: [],
+    tuples: [
+        Shebang,
+        FullOpeningTag,
+        ShortOpeningTag,
+        EchoOpeningTag,
+        ClosingTag,
+        InlineHtml,
+        Label,
+        Goto,
+        HaltCompiler,
+        Static,
+        DoWhile,
+        While,
+        For,
+        Foreach,
+        Break,
+        Continue,
+        Constant,
+        Function,
+        Class,
+        Trait,
+        Interface,
+        If,
+        Switch,
+        Echo,
+        Expression,
+        Return,
+        Namespace,
+        Use,
+        GroupUse,
+        Comment,
+        Try,
+        UnitEnum,
+        BackedEnum,
+        Block,
+        Global,
+        Declare,
+        Noop,
+        Missing,
+    ],
+});
+
+stack_safe!(Expression {
+    units: [Noop],
+    tuples: [
+        Static,
+        Self_,
+        Parent,
+        Null,
+        Eval,
+        Empty,
+        Die,
+        Exit,
+        Isset,
+        Unset,
+        Print,
+        Literal,
+        ArithmeticOperation,
+        AssignmentOperation,
+        BitwiseOperation,
+        ComparisonOperation,
+        LogicalOperation,
+        Concat,
+        Instanceof,
+        Reference,
+        Parenthesized,
+        ErrorSuppress,
+        Identifier,
+        Variable,
+        Include,
+        IncludeOnce,
+        Require,
+        RequireOnce,
+        FunctionCall,
+        FunctionClosureCreation,
+        MethodCall,
+        MethodClosureCreation,
+        NullsafeMethodCall,
+        StaticMethodCall,
+        StaticVariableMethodCall,
+        StaticMethodClosureCreation,
+        StaticVariableMethodClosureCreation,
+        PropertyFetch,
+        NullsafePropertyFetch,
+        StaticPropertyFetch,
+        ConstantFetch,
+        ShortArray,
+        Array,
+        List,
+        Closure,
+        ArrowFunction,
+        New,
+        InterpolatedString,
+        Heredoc,
+        Nowdoc,
+        ShellExec,
+        AnonymousClass,
+        Bool,
+        ArrayIndex,
+        MagicConstant,
+        ShortTernary,
+        Ternary,
+        Coalesce,
+        Clone,
+        Match,
+        Throw,
+        Yield,
+        YieldFrom,
+        Cast,
+        Missing,
+    ],
+});
+
+// Operators, calls, and fetches can be chained without limit, as in `$a . $b . $c ...`
+// or `$a->b()->c()->d() ...`, with each link nested in the next, so the expressions
+// within them are dropped one at a time instead of recursively, which would overflow
+// the stack.
+macro_rules! drop_iteratively {
+    ($($node:ty),* $(,)?) => {
+        $(
+            impl Drop for $node {
+                fn drop(&mut self) {
+                    let mut expressions = vec![];
+                    take_expressions(self, &mut expressions);
+                    while let Some(mut expression) = expressions.pop() {
+                        take_expressions(&mut expression, &mut expressions);
+                    }
+                }
+            }
+        )*
+    };
+}
+
+drop_iteratively!(
+    ArithmeticOperationExpression,
+    AssignmentOperationExpression,
+    BitwiseOperationExpression,
+    ComparisonOperationExpression,
+    LogicalOperationExpression,
+    ConcatExpression,
+    InstanceofExpression,
+    CoalesceExpression,
+    TernaryExpression,
+    ShortTernaryExpression,
+    FunctionCallExpression,
+    FunctionClosureCreationExpression,
+    MethodCallExpression,
+    MethodClosureCreationExpression,
+    NullsafeMethodCallExpression,
+    StaticMethodCallExpression,
+    StaticVariableMethodCallExpression,
+    StaticMethodClosureCreationExpression,
+    StaticVariableMethodClosureCreationExpression,
+    PropertyFetchExpression,
+    NullsafePropertyFetchExpression,
+    StaticPropertyFetchExpression,
+    ConstantFetchExpression,
+    ArrayIndexExpression,
+);
+
+// Whether the expression contains no other expressions.
+fn is_leaf(expression: &Expression) -> bool {
+    matches!(
+        expression,
+        Expression::Literal(_)
+            | Expression::Bool(_)
+            | Expression::MagicConstant(_)
+            | Expression::Static(_)
+            | Expression::Self_(_)
+            | Expression::Parent(_)
+            | Expression::Null(_)
+            | Expression::Noop
+            | Expression::Missing(_)
+    )
+}
+
+// Move the outermost expressions within the node, other than leaves, to the given list,
+// leaving no-ops in their place.
+fn take_expressions(node: &mut dyn Node, expressions: &mut Vec<Expression>) {
+    for child in node.children() {
+        if let Some(expression) = downcast_mut::<Expression>(&mut *child) {
+            if !is_leaf(expression) {
+                expressions.push(std::mem::replace(expression, Expression::Noop));
+            }
+
+            continue;
+        }
+
+        take_expressions(child, expressions);
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct DefaultMatchArm {
-    pub keyword: Span,      // `default`
-    pub double_arrow: Span, // `=>`
-    pub body: Expression,   // `foo()`
+    pub keyword: Span,       // `default`
+    pub comma: Option<Span>, // `,`
+    pub double_arrow: Span,  // `=>`
+    pub body: Expression,    // `foo()`
 }
 
 impl Node for DefaultMatchArm {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.body]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            Some(self.keyword),
+            self.comma,
+            Some(self.double_arrow),
+            self.body.span(),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct MatchArm {
-    pub conditions: Vec<Expression>,
-    pub arrow: Span,
-    pub body: Expression,
+    pub conditions: CommaSeparated<Expression>, // `1, 2`
+    pub arrow: Span,                            // `=>`
+    pub body: Expression,                       // `foo()`
 }
 
 impl Node for MatchArm {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         let mut children: Vec<&mut dyn Node> = self
             .conditions
+            .inner
             .iter_mut()
             .map(|condition| condition as &mut dyn Node)
             .collect();
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = self
+            .conditions
+            .inner
+            .iter()
+            .map(|condition| condition as &dyn Node)
+            .collect();
+        children.push(&self.body);
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([self.conditions.span(), Some(self.arrow), self.body.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -1406,7 +2794,19 @@ pub enum MagicConstantExpression {
 }
 
 impl Node for MagicConstantExpression {
-    //
+    fn span(&self) -> Option<Span> {
+        Some(match self {
+            MagicConstantExpression::Directory(span)
+            | MagicConstantExpression::File(span)
+            | MagicConstantExpression::Line(span)
+            | MagicConstantExpression::Class(span)
+            | MagicConstantExpression::Function(span)
+            | MagicConstantExpression::Method(span)
+            | MagicConstantExpression::Namespace(span)
+            | MagicConstantExpression::Trait(span)
+            | MagicConstantExpression::CompilerHaltOffset(span) => *span,
+        })
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -1419,11 +2819,14 @@ pub enum StringPart {
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct LiteralStringPart {
+    pub span: Span,
     pub value: ByteString,
 }
 
 impl Node for LiteralStringPart {
-    //
+    fn span(&self) -> Option<Span> {
+        Some(self.span)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -1436,6 +2839,10 @@ impl Node for ExpressionStringPart {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.expression.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.expression.as_ref()]
+    }
 }
 
 impl Node for StringPart {
@@ -1445,6 +2852,20 @@ impl Node for StringPart {
             StringPart::Expression(part) => vec![part],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            StringPart::Literal(part) => vec![part],
+            StringPart::Expression(part) => vec![part],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            StringPart::Literal(part) => part.span(),
+            StringPart::Expression(part) => part.span(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -1498,6 +2919,56 @@ impl Node for ArrayItem {
             } => vec![key, value],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ArrayItem::Skipped => vec![],
+            ArrayItem::Value { value } => vec![value],
+            ArrayItem::ReferencedValue {
+                ampersand: _,
+                value,
+            } => vec![value],
+            ArrayItem::SpreadValue { ellipsis: _, value } => vec![value],
+            ArrayItem::KeyValue {
+                key,
+                double_arrow: _,
+                value,
+            } => vec![key, value],
+            ArrayItem::ReferencedKeyValue {
+                key,
+                double_arrow: _,
+                ampersand: _,
+                value,
+            } => vec![key, value],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            ArrayItem::Skipped => None,
+            ArrayItem::Value { value } => value.span(),
+            ArrayItem::ReferencedValue { ampersand, value } => {
+                join([Some(*ampersand), value.span()])
+            }
+            ArrayItem::SpreadValue { ellipsis, value } => join([Some(*ellipsis), value.span()]),
+            ArrayItem::KeyValue {
+                key,
+                double_arrow,
+                value,
+            } => join([key.span(), Some(*double_arrow), value.span()]),
+            ArrayItem::ReferencedKeyValue {
+                key,
+                double_arrow,
+                ampersand,
+                value,
+            } => join([
+                key.span(),
+                Some(*double_arrow),
+                Some(*ampersand),
+                value.span(),
+            ]),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -1507,11 +2978,21 @@ pub enum ListEntry {
     Value {
         value: Expression, // `$foo`
     },
+    ReferencedValue {
+        ampersand: Span,   // `&`
+        value: Expression, // `$foo`
+    },
     KeyValue {
         key: Expression,    // `$foo`
         double_arrow: Span, // `=>`
         value: Expression,  // `$bar`
     },
+    ReferencedKeyValue {
+        key: Expression,    // `$foo`
+        double_arrow: Span, // `=>`
+        ampersand: Span,    // `&`
+        value: Expression,  // `$bar`
+    },
 }
 
 impl Node for ListEntry {
@@ -1519,11 +3000,209 @@ impl Node for ListEntry {
         match self {
             ListEntry::Skipped => vec![],
             ListEntry::Value { value } => vec![value],
+            ListEntry::ReferencedValue {
+                ampersand: _,
+                value,
+            } => vec![value],
             ListEntry::KeyValue {
                 key,
                 double_arrow: _,
                 value,
             } => vec![key, value],
+            ListEntry::ReferencedKeyValue {
+                key,
+                double_arrow: _,
+                ampersand: _,
+                value,
+            } => vec![key, value],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ListEntry::Skipped => vec![],
+            ListEntry::Value { value } => vec![value],
+            ListEntry::ReferencedValue {
+                ampersand: _,
+                value,
+            } => vec![value],
+            ListEntry::KeyValue {
+                key,
+                double_arrow: _,
+                value,
+            } => vec![key, value],
+            ListEntry::ReferencedKeyValue {
+                key,
+                double_arrow: _,
+                ampersand: _,
+                value,
+            } => vec![key, value],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            ListEntry::Skipped => None,
+            ListEntry::Value { value } => value.span(),
+            ListEntry::ReferencedValue { ampersand, value } => {
+                join([Some(*ampersand), value.span()])
+            }
+            ListEntry::KeyValue {
+                key,
+                double_arrow,
+                value,
+            } => join([key.span(), Some(*double_arrow), value.span()]),
+            ListEntry::ReferencedKeyValue {
+                key,
+                double_arrow,
+                ampersand,
+                value,
+            } => join([
+                key.span(),
+                Some(*double_arrow),
+                Some(*ampersand),
+                value.span(),
+            ]),
+        }
+    }
+}
+
+relocate! {
+    enum UseKind {
+        Normal,
+        Function,
+        Const,
+    }
+    struct StaticVar { var, default }
+    enum Ending {
+        Semicolon(value),
+        CloseTag(value),
+    }
+    struct StaticStatement { r#static, vars, semicolon }
+    struct SwitchStatement { switch, left_parenthesis, condition, right_parenthesis, body }
+    enum SwitchStatementBody {
+        Braced { left_brace, cases, right_brace },
+        Block { colon, cases, endswitch, ending },
+    }
+    struct EchoStatement { echo, values, ending }
+    struct ReturnStatement { r#return, value, ending }
+    struct UseStatement { r#use, kind, uses, semicolon }
+    struct GroupUseStatement { r#use, prefix, kind, uses, semicolon }
+    struct ShebangStatement { span, value }
+    struct InlineHtmlStatement { span, html }
+    struct FullOpeningTagStatement { span }
+    struct ShortOpeningTagStatement { span }
+    struct EchoOpeningTagStatement { span }
+    struct ClosingTagStatement { span }
+    struct ExpressionStatement { expression, ending }
+    struct GlobalStatement { global, variables, semicolon }
+    struct BlockStatement { left_brace, statements, right_brace }
+    enum CastKind {
+        Int,
+        Bool,
+        Float,
+        String,
+        Array,
+        Object,
+        Unset,
+    }
+    struct Case { keyword, condition, colon, body }
+    struct Use { name, alias, kind }
+    struct EvalExpression { eval, argument }
+    struct EmptyExpression { empty, argument }
+    struct DieExpression { die, argument }
+    struct ExitExpression { exit, argument }
+    struct IssetExpression { isset, arguments }
+    struct UnsetExpression { unset, arguments }
+    struct PrintExpression { print, value, argument }
+    struct ConcatExpression { left, dot, right }
+    struct InstanceofExpression { left, instanceof, right }
+    struct ReferenceExpression { ampersand, right }
+    struct ParenthesizedExpression { start, expr, end }
+    struct ErrorSuppressExpression { at, expr }
+    struct IncludeExpression { include, path }
+    struct IncludeOnceExpression { include_once, path }
+    struct RequireExpression { require, path }
+    struct RequireOnceExpression { require_once, path }
+    struct FunctionCallExpression { target, arguments }
+    struct FunctionClosureCreationExpression { target, placeholder }
+    struct MethodCallExpression { target, arrow, method, arguments }
+    struct MethodClosureCreationExpression { target, arrow, method, placeholder }
+    struct NullsafeMethodCallExpression { target, question_arrow, method, arguments }
+    struct StaticMethodCallExpression { target, double_colon, method, arguments }
+    struct StaticVariableMethodCallExpression { target, double_colon, method, arguments }
+    struct StaticMethodClosureCreationExpression { target, double_colon, method, placeholder }
+    struct StaticVariableMethodClosureCreationExpression {
+        target,
+        double_colon,
+        method,
+        placeholder,
+    }
+    struct PropertyFetchExpression { target, arrow, property }
+    struct NullsafePropertyFetchExpression { target, question_arrow, property }
+    struct StaticPropertyFetchExpression { target, double_colon, property }
+    struct ConstantFetchExpression { target, double_colon, constant }
+    struct ShortArrayExpression { start, items, end }
+    struct ArrayExpression { array, start, items, end }
+    struct ListExpression { list, start, items, end }
+    struct NewExpression { new, target, arguments }
+    struct InterpolatedStringExpression { start, parts, end }
+    struct HeredocExpression { start, label, parts, indentation_kind, indentation_amount, end }
+    struct NowdocExpression { start, label, value, indentation_kind, indentation_amount, end }
+    struct ShellExecExpression { start, parts, end }
+    struct BoolExpression { span, value }
+    struct ArrayIndexExpression { array, left_bracket, index, right_bracket }
+    struct ShortTernaryExpression { condition, question_colon, r#else }
+    struct TernaryExpression { condition, question, then, colon, r#else }
+    struct CoalesceExpression { lhs, double_question, rhs }
+    struct CloneExpression { clone, target }
+    struct MatchExpression {
+        keyword,
+        left_parenthesis,
+        condition,
+        right_parenthesis,
+        left_brace,
+        default,
+        arms,
+        commas,
+        right_brace,
+    }
+    struct ThrowExpression { throw, value }
+    struct YieldExpression { r#yield, key, value }
+    struct YieldFromExpression { r#yield, from, value }
+    struct CastExpression { cast, kind, value }
+    struct DefaultMatchArm { keyword, comma, double_arrow, body }
+    struct MatchArm { conditions, arrow, body }
+    enum MagicConstantExpression {
+        Directory(value),
+        File(value),
+        Line(value),
+        Class(value),
+        Function(value),
+        Method(value),
+        Namespace(value),
+        Trait(value),
+        CompilerHaltOffset(value),
+    }
+    enum StringPart {
+        Literal(value),
+        Expression(value),
+    }
+    struct LiteralStringPart { span, value }
+    struct ExpressionStringPart { expression }
+    enum ArrayItem {
+        Skipped,
+        Value { value },
+        ReferencedValue { ampersand, value },
+        SpreadValue { ellipsis, value },
+        KeyValue { key, double_arrow, value },
+        ReferencedKeyValue { key, double_arrow, ampersand, value },
+    }
+    enum ListEntry {
+        Skipped,
+        Value { value },
+        ReferencedValue { ampersand, value },
+        KeyValue { key, double_arrow, value },
+        ReferencedKeyValue { key, double_arrow, ampersand, value },
+    }
 }
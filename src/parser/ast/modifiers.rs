@@ -3,6 +3,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::parser::ast::relocate::relocate;
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type")]
@@ -20,6 +21,16 @@ pub enum VisibilityModifier {
     Private(Span),
 }
 
+impl VisibilityModifier {
+    pub fn span(&self) -> Span {
+        match self {
+            VisibilityModifier::Public(span) => *span,
+            VisibilityModifier::Protected(span) => *span,
+            VisibilityModifier::Private(span) => *span,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type", content = "value")]
 pub enum PromotedPropertyModifier {
@@ -62,6 +73,14 @@ impl PromotedPropertyModifierGroup {
         self.modifiers.is_empty()
     }
 
+    /// The range covering the modifiers, or `None` if there are none.
+    pub fn span(&self) -> Option<Span> {
+        let first = self.modifiers.first()?.span();
+        let last = self.modifiers.last()?.span();
+
+        Some(first.join(last))
+    }
+
     pub fn get_readonly(&self) -> Option<&PromotedPropertyModifier> {
         self.modifiers
             .iter()
@@ -120,6 +139,14 @@ impl PropertyModifierGroup {
         self.modifiers.is_empty()
     }
 
+    /// The range covering the modifiers, or `None` if there are none.
+    pub fn span(&self) -> Option<Span> {
+        let first = self.modifiers.first()?.span();
+        let last = self.modifiers.last()?.span();
+
+        Some(first.join(last))
+    }
+
     pub fn get_readonly(&self) -> Option<&PropertyModifier> {
         self.modifiers
             .iter()
@@ -192,6 +219,14 @@ impl MethodModifierGroup {
         self.modifiers.is_empty()
     }
 
+    /// The range covering the modifiers, or `None` if there are none.
+    pub fn span(&self) -> Option<Span> {
+        let first = self.modifiers.first()?.span();
+        let last = self.modifiers.last()?.span();
+
+        Some(first.join(last))
+    }
+
     pub fn has_final(&self) -> bool {
         self.modifiers
             .iter()
@@ -237,6 +272,16 @@ pub enum ClassModifier {
     Readonly(Span),
 }
 
+impl ClassModifier {
+    pub fn span(&self) -> Span {
+        match self {
+            ClassModifier::Final(span) => *span,
+            ClassModifier::Abstract(span) => *span,
+            ClassModifier::Readonly(span) => *span,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[repr(transparent)]
 pub struct ClassModifierGroup {
@@ -248,6 +293,14 @@ impl ClassModifierGroup {
         self.modifiers.is_empty()
     }
 
+    /// The range covering the modifiers, or `None` if there are none.
+    pub fn span(&self) -> Option<Span> {
+        let first = self.modifiers.first()?.span();
+        let last = self.modifiers.last()?.span();
+
+        Some(first.join(last))
+    }
+
     pub fn has_final(&self) -> bool {
         self.modifiers
             .iter()
@@ -276,6 +329,17 @@ pub enum ConstantModifier {
     Private(Span),
 }
 
+impl ConstantModifier {
+    pub fn span(&self) -> Span {
+        match self {
+            ConstantModifier::Final(span) => *span,
+            ConstantModifier::Public(span) => *span,
+            ConstantModifier::Protected(span) => *span,
+            ConstantModifier::Private(span) => *span,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[repr(transparent)]
 pub struct ConstantModifierGroup {
@@ -287,6 +351,14 @@ impl ConstantModifierGroup {
         self.modifiers.is_empty()
     }
 
+    /// The range covering the modifiers, or `None` if there are none.
+    pub fn span(&self) -> Option<Span> {
+        let first = self.modifiers.first()?.span();
+        let last = self.modifiers.last()?.span();
+
+        Some(first.join(last))
+    }
+
     pub fn has_final(&self) -> bool {
         self.modifiers
             .iter()
@@ -305,3 +377,53 @@ impl ConstantModifierGroup {
             .unwrap_or(Visibility::Public)
     }
 }
+
+relocate! {
+    enum Visibility {
+        Public,
+        Protected,
+        Private,
+    }
+    enum VisibilityModifier {
+        Public(value),
+        Protected(value),
+        Private(value),
+    }
+    enum PromotedPropertyModifier {
+        Public(value),
+        Protected(value),
+        Private(value),
+        Readonly(value),
+    }
+    struct PromotedPropertyModifierGroup { modifiers }
+    enum PropertyModifier {
+        Public(value),
+        Protected(value),
+        Private(value),
+        Static(value),
+        Readonly(value),
+    }
+    struct PropertyModifierGroup { modifiers }
+    enum MethodModifier {
+        Final(value),
+        Static(value),
+        Abstract(value),
+        Public(value),
+        Protected(value),
+        Private(value),
+    }
+    struct MethodModifierGroup { modifiers }
+    enum ClassModifier {
+        Final(value),
+        Abstract(value),
+        Readonly(value),
+    }
+    struct ClassModifierGroup { modifiers }
+    enum ConstantModifier {
+        Final(value),
+        Public(value),
+        Protected(value),
+        Private(value),
+    }
+    struct ConstantModifierGroup { modifiers }
+}
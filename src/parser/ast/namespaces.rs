@@ -3,8 +3,11 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::join;
+use crate::node::span_of;
 use crate::node::Node;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::relocate::relocate;
 use crate::parser::ast::Statement;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -27,6 +30,21 @@ impl Node for UnbracedNamespace {
         );
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children = vec![&self.name as &dyn Node];
+        children.extend(
+            self.statements
+                .iter()
+                .map(|s| s as &dyn Node)
+                .collect::<Vec<&dyn Node>>(),
+        );
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.start), Some(self.end), span_of(&self.statements)])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -46,6 +64,19 @@ impl Node for BracedNamespace {
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![];
+        if let Some(name) = &self.name {
+            children.push(name);
+        }
+        children.push(&self.body);
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.namespace), self.body.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -63,6 +94,14 @@ impl Node for BracedNamespaceBody {
             .map(|s| s as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.statements.iter().map(|s| s as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.start.join(self.end))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -79,4 +118,28 @@ impl Node for NamespaceStatement {
             NamespaceStatement::Braced(namespace) => vec![namespace],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            NamespaceStatement::Unbraced(namespace) => vec![namespace],
+            NamespaceStatement::Braced(namespace) => vec![namespace],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            NamespaceStatement::Unbraced(namespace) => namespace.span(),
+            NamespaceStatement::Braced(namespace) => namespace.span(),
+        }
+    }
+}
+
+relocate! {
+    struct UnbracedNamespace { start, name, end, statements }
+    struct BracedNamespace { namespace, name, body }
+    struct BracedNamespaceBody { start, end, statements }
+    enum NamespaceStatement {
+        Unbraced(value),
+        Braced(value),
+    }
 }
@@ -3,7 +3,9 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::join;
 use crate::node::Node;
+use crate::parser::ast::relocate::relocate;
 use crate::parser::ast::Expression;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -94,6 +96,80 @@ impl Node for ArithmeticOperationExpression {
             ArithmeticOperationExpression::PostDecrement { left, .. } => vec![left.as_mut()],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ArithmeticOperationExpression::Addition { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ArithmeticOperationExpression::Subtraction { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ArithmeticOperationExpression::Multiplication { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ArithmeticOperationExpression::Division { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ArithmeticOperationExpression::Modulo { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ArithmeticOperationExpression::Exponentiation { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ArithmeticOperationExpression::Negative { right, .. } => vec![right.as_ref()],
+            ArithmeticOperationExpression::Positive { right, .. } => vec![right.as_ref()],
+            ArithmeticOperationExpression::PreIncrement { right, .. } => vec![right.as_ref()],
+            ArithmeticOperationExpression::PostIncrement { left, .. } => vec![left.as_ref()],
+            ArithmeticOperationExpression::PreDecrement { right, .. } => vec![right.as_ref()],
+            ArithmeticOperationExpression::PostDecrement { left, .. } => vec![left.as_ref()],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            ArithmeticOperationExpression::Addition { left, plus, right } => {
+                join([left.span(), Some(*plus), right.span()])
+            }
+            ArithmeticOperationExpression::Subtraction { left, minus, right } => {
+                join([left.span(), Some(*minus), right.span()])
+            }
+            ArithmeticOperationExpression::Multiplication {
+                left,
+                asterisk,
+                right,
+            } => join([left.span(), Some(*asterisk), right.span()]),
+            ArithmeticOperationExpression::Division { left, slash, right } => {
+                join([left.span(), Some(*slash), right.span()])
+            }
+            ArithmeticOperationExpression::Modulo {
+                left,
+                percent,
+                right,
+            } => join([left.span(), Some(*percent), right.span()]),
+            ArithmeticOperationExpression::Exponentiation { left, pow, right } => {
+                join([left.span(), Some(*pow), right.span()])
+            }
+            ArithmeticOperationExpression::Negative { minus, right } => {
+                join([Some(*minus), right.span()])
+            }
+            ArithmeticOperationExpression::Positive { plus, right } => {
+                join([Some(*plus), right.span()])
+            }
+            ArithmeticOperationExpression::PreIncrement { increment, right } => {
+                join([Some(*increment), right.span()])
+            }
+            ArithmeticOperationExpression::PostIncrement { left, increment } => {
+                join([left.span(), Some(*increment)])
+            }
+            ArithmeticOperationExpression::PreDecrement { decrement, right } => {
+                join([Some(*decrement), right.span()])
+            }
+            ArithmeticOperationExpression::PostDecrement { left, decrement } => {
+                join([left.span(), Some(*decrement)])
+            }
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -287,6 +363,128 @@ impl Node for AssignmentOperationExpression {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            AssignmentOperationExpression::Assign { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Addition { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Subtraction { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Multiplication { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Division { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Modulo { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Exponentiation { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Concat { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::BitwiseAnd { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::BitwiseOr { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::BitwiseXor { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::LeftShift { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::RightShift { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Coalesce { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            AssignmentOperationExpression::Assign {
+                left,
+                equals,
+                right,
+            } => join([left.span(), Some(*equals), right.span()]),
+            AssignmentOperationExpression::Addition {
+                left,
+                plus_equals,
+                right,
+            } => join([left.span(), Some(*plus_equals), right.span()]),
+            AssignmentOperationExpression::Subtraction {
+                left,
+                minus_equals,
+                right,
+            } => join([left.span(), Some(*minus_equals), right.span()]),
+            AssignmentOperationExpression::Multiplication {
+                left,
+                asterisk_equals,
+                right,
+            } => join([left.span(), Some(*asterisk_equals), right.span()]),
+            AssignmentOperationExpression::Division {
+                left,
+                slash_equals,
+                right,
+            } => join([left.span(), Some(*slash_equals), right.span()]),
+            AssignmentOperationExpression::Modulo {
+                left,
+                percent_equals,
+                right,
+            } => join([left.span(), Some(*percent_equals), right.span()]),
+            AssignmentOperationExpression::Exponentiation {
+                left,
+                pow_equals,
+                right,
+            } => join([left.span(), Some(*pow_equals), right.span()]),
+            AssignmentOperationExpression::Concat {
+                left,
+                dot_equals,
+                right,
+            } => join([left.span(), Some(*dot_equals), right.span()]),
+            AssignmentOperationExpression::BitwiseAnd {
+                left,
+                ampersand_equals,
+                right,
+            } => join([left.span(), Some(*ampersand_equals), right.span()]),
+            AssignmentOperationExpression::BitwiseOr {
+                left,
+                pipe_equals,
+                right,
+            } => join([left.span(), Some(*pipe_equals), right.span()]),
+            AssignmentOperationExpression::BitwiseXor {
+                left,
+                caret_equals,
+                right,
+            } => join([left.span(), Some(*caret_equals), right.span()]),
+            AssignmentOperationExpression::LeftShift {
+                left,
+                left_shift_equals,
+                right,
+            } => join([left.span(), Some(*left_shift_equals), right.span()]),
+            AssignmentOperationExpression::RightShift {
+                left,
+                right_shift_equals,
+                right,
+            } => join([left.span(), Some(*right_shift_equals), right.span()]),
+            AssignmentOperationExpression::Coalesce {
+                left,
+                coalesce_equals,
+                right,
+            } => join([left.span(), Some(*coalesce_equals), right.span()]),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -344,6 +542,52 @@ impl Node for BitwiseOperationExpression {
             BitwiseOperationExpression::Not { right, .. } => vec![right.as_mut()],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            BitwiseOperationExpression::And { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            BitwiseOperationExpression::Or { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            BitwiseOperationExpression::Xor { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            BitwiseOperationExpression::LeftShift { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            BitwiseOperationExpression::RightShift { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            BitwiseOperationExpression::Not { right, .. } => vec![right.as_ref()],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            BitwiseOperationExpression::And { left, and, right } => {
+                join([left.span(), Some(*and), right.span()])
+            }
+            BitwiseOperationExpression::Or { left, or, right } => {
+                join([left.span(), Some(*or), right.span()])
+            }
+            BitwiseOperationExpression::Xor { left, xor, right } => {
+                join([left.span(), Some(*xor), right.span()])
+            }
+            BitwiseOperationExpression::LeftShift {
+                left,
+                left_shift,
+                right,
+            } => join([left.span(), Some(*left_shift), right.span()]),
+            BitwiseOperationExpression::RightShift {
+                left,
+                right_shift,
+                right,
+            } => join([left.span(), Some(*right_shift), right.span()]),
+            BitwiseOperationExpression::Not { not, right } => join([Some(*not), right.span()]),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -436,6 +680,96 @@ impl Node for ComparisonOperationExpression {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ComparisonOperationExpression::Equal { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::Identical { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::NotEqual { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::AngledNotEqual { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::NotIdentical { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::LessThan { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::GreaterThan { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::LessThanOrEqual { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::GreaterThanOrEqual { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::Spaceship { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            ComparisonOperationExpression::Equal {
+                left,
+                double_equals,
+                right,
+            } => join([left.span(), Some(*double_equals), right.span()]),
+            ComparisonOperationExpression::Identical {
+                left,
+                triple_equals,
+                right,
+            } => join([left.span(), Some(*triple_equals), right.span()]),
+            ComparisonOperationExpression::NotEqual {
+                left,
+                bang_equals,
+                right,
+            } => join([left.span(), Some(*bang_equals), right.span()]),
+            ComparisonOperationExpression::AngledNotEqual {
+                left,
+                angled_left_right,
+                right,
+            } => join([left.span(), Some(*angled_left_right), right.span()]),
+            ComparisonOperationExpression::NotIdentical {
+                left,
+                bang_double_equals,
+                right,
+            } => join([left.span(), Some(*bang_double_equals), right.span()]),
+            ComparisonOperationExpression::LessThan {
+                left,
+                less_than,
+                right,
+            } => join([left.span(), Some(*less_than), right.span()]),
+            ComparisonOperationExpression::GreaterThan {
+                left,
+                greater_than,
+                right,
+            } => join([left.span(), Some(*greater_than), right.span()]),
+            ComparisonOperationExpression::LessThanOrEqual {
+                left,
+                less_than_equals,
+                right,
+            } => join([left.span(), Some(*less_than_equals), right.span()]),
+            ComparisonOperationExpression::GreaterThanOrEqual {
+                left,
+                greater_than_equals,
+                right,
+            } => join([left.span(), Some(*greater_than_equals), right.span()]),
+            ComparisonOperationExpression::Spaceship {
+                left,
+                spaceship,
+                right,
+            } => join([left.span(), Some(*spaceship), right.span()]),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -493,4 +827,111 @@ impl Node for LogicalOperationExpression {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            LogicalOperationExpression::And { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            LogicalOperationExpression::Or { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            LogicalOperationExpression::Not { right, .. } => vec![right.as_ref()],
+            LogicalOperationExpression::LogicalAnd { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            LogicalOperationExpression::LogicalOr { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            LogicalOperationExpression::LogicalXor { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            LogicalOperationExpression::And {
+                left,
+                double_ampersand,
+                right,
+            } => join([left.span(), Some(*double_ampersand), right.span()]),
+            LogicalOperationExpression::Or {
+                left,
+                double_pipe,
+                right,
+            } => join([left.span(), Some(*double_pipe), right.span()]),
+            LogicalOperationExpression::Not { bang, right } => join([Some(*bang), right.span()]),
+            LogicalOperationExpression::LogicalAnd { left, and, right } => {
+                join([left.span(), Some(*and), right.span()])
+            }
+            LogicalOperationExpression::LogicalOr { left, or, right } => {
+                join([left.span(), Some(*or), right.span()])
+            }
+            LogicalOperationExpression::LogicalXor { left, xor, right } => {
+                join([left.span(), Some(*xor), right.span()])
+            }
+        }
+    }
+}
+
+relocate! {
+    enum ArithmeticOperationExpression {
+        Addition { left, plus, right },
+        Subtraction { left, minus, right },
+        Multiplication { left, asterisk, right },
+        Division { left, slash, right },
+        Modulo { left, percent, right },
+        Exponentiation { left, pow, right },
+        Negative { minus, right },
+        Positive { plus, right },
+        PreIncrement { increment, right },
+        PostIncrement { left, increment },
+        PreDecrement { decrement, right },
+        PostDecrement { left, decrement },
+    }
+    enum AssignmentOperationExpression {
+        Assign { left, equals, right },
+        Addition { left, plus_equals, right },
+        Subtraction { left, minus_equals, right },
+        Multiplication { left, asterisk_equals, right },
+        Division { left, slash_equals, right },
+        Modulo { left, percent_equals, right },
+        Exponentiation { left, pow_equals, right },
+        Concat { left, dot_equals, right },
+        BitwiseAnd { left, ampersand_equals, right },
+        BitwiseOr { left, pipe_equals, right },
+        BitwiseXor { left, caret_equals, right },
+        LeftShift { left, left_shift_equals, right },
+        RightShift { left, right_shift_equals, right },
+        Coalesce { left, coalesce_equals, right },
+    }
+    enum BitwiseOperationExpression {
+        And { left, and, right },
+        Or { left, or, right },
+        Xor { left, xor, right },
+        LeftShift { left, left_shift, right },
+        RightShift { left, right_shift, right },
+        Not { not, right },
+    }
+    enum ComparisonOperationExpression {
+        Equal { left, double_equals, right },
+        Identical { left, triple_equals, right },
+        NotEqual { left, bang_equals, right },
+        AngledNotEqual { left, angled_left_right, right },
+        NotIdentical { left, bang_double_equals, right },
+        LessThan { left, less_than, right },
+        GreaterThan { left, greater_than, right },
+        LessThanOrEqual { left, less_than_equals, right },
+        GreaterThanOrEqual { left, greater_than_equals, right },
+        Spaceship { left, spaceship, right },
+    }
+    enum LogicalOperationExpression {
+        And { left, double_ampersand, right },
+        Or { left, double_pipe, right },
+        Not { bang, right },
+        LogicalAnd { left, and, right },
+        LogicalOr { left, or, right },
+        LogicalXor { left, xor, right },
+    }
 }
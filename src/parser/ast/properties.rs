@@ -3,16 +3,21 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::join;
+use crate::node::span_of;
 use crate::node::Node;
 use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::comments::CommentGroup;
 use crate::parser::ast::data_type::Type;
 use crate::parser::ast::modifiers::PropertyModifierGroup;
+use crate::parser::ast::relocate::relocate;
 use crate::parser::ast::variables::SimpleVariable;
 use crate::parser::ast::Expression;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct Property {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>,
     #[serde(flatten)]
     pub modifiers: PropertyModifierGroup,
@@ -35,11 +40,37 @@ impl Node for Property {
         );
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![];
+        if let Some(r#type) = &self.r#type {
+            children.push(r#type);
+        }
+        children.extend(
+            self.entries
+                .iter()
+                .map(|e| e as &dyn Node)
+                .collect::<Vec<&dyn Node>>(),
+        );
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            self.modifiers.span(),
+            self.r#type.as_ref().and_then(Node::span),
+            span_of(&self.entries),
+            Some(self.end),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct VariableProperty {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>,
     pub r#type: Option<Type>,
     pub entries: Vec<PropertyEntry>,
@@ -60,8 +91,33 @@ impl Node for VariableProperty {
         );
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![];
+        if let Some(r#type) = &self.r#type {
+            children.push(r#type);
+        }
+        children.extend(
+            self.entries
+                .iter()
+                .map(|e| e as &dyn Node)
+                .collect::<Vec<&dyn Node>>(),
+        );
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            self.r#type.as_ref().and_then(Node::span),
+            span_of(&self.entries),
+            Some(self.end),
+        ])
+    }
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type", content = "value")]
 pub enum PropertyEntry {
@@ -84,6 +140,24 @@ impl Node for PropertyEntry {
             } => vec![variable, value],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            PropertyEntry::Uninitialized { variable } => vec![variable],
+            PropertyEntry::Initialized {
+                variable, value, ..
+            } => vec![variable, value],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            PropertyEntry::Uninitialized { variable } => variable.span(),
+            PropertyEntry::Initialized {
+                variable, value, ..
+            } => join([variable.span(), value.span()]),
+        }
+    }
 }
 
 impl PropertyEntry {
@@ -94,3 +168,12 @@ impl PropertyEntry {
         }
     }
 }
+
+relocate! {
+    struct Property { comments, attributes, modifiers, r#type, entries, end }
+    struct VariableProperty { comments, attributes, r#type, entries, end }
+    enum PropertyEntry {
+        Uninitialized { variable },
+        Initialized { variable, equals, value },
+    }
+}
@@ -0,0 +1,116 @@
+//! Moving the spans of a node, for nodes that are kept when the code before them is
+//! edited, as [`crate::parser::incremental::reparse`] does.
+//!
+//! Every node lists all of its fields with [`relocate!`], which destructures them without
+//! `..`, so a field added to a node can't be forgotten.
+
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::DocStringIndentationKind;
+use crate::lexer::token::Span;
+
+/// How far the code after an edit moved, in bytes and in lines.
+///
+/// The columns stay the same, so the code must start on a line after the edit.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct Shift {
+    pub position: isize,
+    pub line: isize,
+}
+
+pub(crate) trait Relocate {
+    fn relocate(&mut self, shift: Shift);
+}
+
+impl Relocate for Span {
+    fn relocate(&mut self, shift: Shift) {
+        self.line = self.line.wrapping_add_signed(shift.line);
+        self.position = self.position.wrapping_add_signed(shift.position);
+        self.end_position = self.end_position.wrapping_add_signed(shift.position);
+    }
+}
+
+impl<T: Relocate + ?Sized> Relocate for Box<T> {
+    fn relocate(&mut self, shift: Shift) {
+        T::relocate(self, shift);
+    }
+}
+
+impl<T: Relocate> Relocate for Option<T> {
+    fn relocate(&mut self, shift: Shift) {
+        if let Some(value) = self {
+            value.relocate(shift);
+        }
+    }
+}
+
+impl<T: Relocate> Relocate for Vec<T> {
+    fn relocate(&mut self, shift: Shift) {
+        for value in self {
+            value.relocate(shift);
+        }
+    }
+}
+
+impl<A: Relocate, B: Relocate> Relocate for (A, B) {
+    fn relocate(&mut self, shift: Shift) {
+        self.0.relocate(shift);
+        self.1.relocate(shift);
+    }
+}
+
+// values without a position.
+macro_rules! fixed {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl Relocate for $type {
+                fn relocate(&mut self, _: Shift) {}
+            }
+        )*
+    };
+}
+
+fixed!(bool, usize, i64, f64, ByteString, DocStringIndentationKind);
+
+/// Implement [`Relocate`] for structs and enums by relocating each of their fields.
+///
+/// ```ignore
+/// relocate! {
+///     struct Variable { span, name }
+///     enum Type { Named(span, name), Union(types), Static }
+/// }
+/// ```
+macro_rules! relocate {
+    ($($kind:ident $name:ident $body:tt)*) => {
+        $(
+            $crate::parser::ast::relocate::relocate!(@item $kind $name $body);
+        )*
+    };
+    (@item struct $name:ident { $($field:ident),* $(,)? }) => {
+        impl $crate::parser::ast::relocate::Relocate for $name {
+            #[allow(unused_variables)]
+            fn relocate(&mut self, shift: $crate::parser::ast::relocate::Shift) {
+                let Self { $($field),* } = self;
+                $($crate::parser::ast::relocate::Relocate::relocate($field, shift);)*
+            }
+        }
+    };
+    (@item enum $name:ident {
+        $($variant:ident $(($($value:ident),* $(,)?))? $({$($field:ident),* $(,)?})?),* $(,)?
+    }) => {
+        impl $crate::parser::ast::relocate::Relocate for $name {
+            #[allow(unused_variables)]
+            fn relocate(&mut self, shift: $crate::parser::ast::relocate::Shift) {
+                match self {
+                    $(
+                        Self::$variant $(($($value),*))? $({$($field),*})? => {
+                            $($($crate::parser::ast::relocate::Relocate::relocate($value, shift);)*)?
+                            $($($crate::parser::ast::relocate::Relocate::relocate($field, shift);)*)?
+                        }
+                    )*
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use relocate;
@@ -3,8 +3,11 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::join;
+use crate::node::span_of;
 use crate::node::Node;
 use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::comments::CommentGroup;
 use crate::parser::ast::constant::ClassishConstant;
 use crate::parser::ast::functions::AbstractConstructor;
 use crate::parser::ast::functions::AbstractMethod;
@@ -14,6 +17,7 @@ use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::modifiers::VisibilityModifier;
 use crate::parser::ast::properties::Property;
 use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::relocate::relocate;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type", content = "value")]
@@ -41,6 +45,32 @@ impl Node for TraitMember {
             TraitMember::ConcreteConstructor(constructor) => vec![constructor],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            TraitMember::Constant(constant) => vec![constant],
+            TraitMember::TraitUsage(usage) => vec![usage],
+            TraitMember::Property(property) => vec![property],
+            TraitMember::VariableProperty(property) => vec![property],
+            TraitMember::AbstractMethod(method) => vec![method],
+            TraitMember::AbstractConstructor(constructor) => vec![constructor],
+            TraitMember::ConcreteMethod(method) => vec![method],
+            TraitMember::ConcreteConstructor(constructor) => vec![constructor],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            TraitMember::Constant(member) => member.span(),
+            TraitMember::TraitUsage(member) => member.span(),
+            TraitMember::Property(member) => member.span(),
+            TraitMember::VariableProperty(member) => member.span(),
+            TraitMember::AbstractMethod(member) => member.span(),
+            TraitMember::AbstractConstructor(member) => member.span(),
+            TraitMember::ConcreteMethod(member) => member.span(),
+            TraitMember::ConcreteConstructor(member) => member.span(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -58,11 +88,23 @@ impl Node for TraitBody {
             .map(|member| member as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.members
+            .iter()
+            .map(|member| member as &dyn Node)
+            .collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.left_brace.join(self.right_brace))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct TraitStatement {
+    pub comments: CommentGroup,
     pub r#trait: Span,
     pub name: SimpleIdentifier,
     pub attributes: Vec<AttributeGroup>,
@@ -73,6 +115,19 @@ impl Node for TraitStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.name, &mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.name, &self.body]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            self.comments.span(),
+            AttributeGroup::span_of(&self.attributes),
+            Some(self.r#trait),
+            Some(self.body.right_brace),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -87,6 +142,21 @@ impl Node for TraitUsage {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.traits.iter_mut().map(|t| t as &mut dyn Node).collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.traits.iter().map(|t| t as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            Some(self.r#use),
+            span_of(&self.traits),
+            self.adaptations
+                .iter()
+                .rev()
+                .find_map(TraitUsageAdaptation::span),
+        ])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -109,3 +179,60 @@ pub enum TraitUsageAdaptation {
         insteadof: Vec<SimpleIdentifier>,
     },
 }
+
+impl TraitUsageAdaptation {
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            TraitUsageAdaptation::Alias {
+                r#trait,
+                method,
+                alias,
+                visibility,
+            } => join([
+                r#trait.as_ref().and_then(Node::span),
+                method.span(),
+                alias.span(),
+                visibility.as_ref().map(VisibilityModifier::span),
+            ]),
+            TraitUsageAdaptation::Visibility {
+                r#trait,
+                method,
+                visibility,
+            } => join([
+                r#trait.as_ref().and_then(Node::span),
+                method.span(),
+                Some(visibility.span()),
+            ]),
+            TraitUsageAdaptation::Precedence {
+                r#trait,
+                method,
+                insteadof,
+            } => join([
+                r#trait.as_ref().and_then(Node::span),
+                method.span(),
+                span_of(insteadof),
+            ]),
+        }
+    }
+}
+
+relocate! {
+    enum TraitMember {
+        Constant(value),
+        TraitUsage(value),
+        Property(value),
+        VariableProperty(value),
+        AbstractMethod(value),
+        AbstractConstructor(value),
+        ConcreteMethod(value),
+        ConcreteConstructor(value),
+    }
+    struct TraitBody { left_brace, members, right_brace }
+    struct TraitStatement { comments, r#trait, name, attributes, body }
+    struct TraitUsage { r#use, traits, adaptations }
+    enum TraitUsageAdaptation {
+        Alias { r#trait, method, alias, visibility },
+        Visibility { r#trait, method, visibility },
+        Precedence { r#trait, method, insteadof },
+    }
+}
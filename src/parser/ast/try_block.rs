@@ -3,8 +3,10 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::span_of;
 use crate::node::Node;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::relocate::relocate;
 use crate::parser::ast::Block;
 
 use super::variables::SimpleVariable;
@@ -25,6 +27,22 @@ impl Node for CatchType {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            CatchType::Identifier { identifier } => vec![identifier],
+            CatchType::Union { identifiers } => {
+                identifiers.iter().map(|i| i as &dyn Node).collect()
+            }
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            CatchType::Identifier { identifier } => identifier.span(),
+            CatchType::Union { identifiers } => span_of(identifiers),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -48,6 +66,21 @@ impl Node for TryStatement {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.body];
+        for catch in &self.catches {
+            children.push(catch);
+        }
+        if let Some(finally) = &self.finally {
+            children.push(finally);
+        }
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.start.join(self.end))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -69,6 +102,19 @@ impl Node for CatchBlock {
         children.push(&mut self.body as &mut dyn Node);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children = vec![&self.types as &dyn Node];
+        if let Some(var) = &self.var {
+            children.push(var as &dyn Node);
+        }
+        children.push(&self.body as &dyn Node);
+        children
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.start.join(self.end))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -83,4 +129,22 @@ impl Node for FinallyBlock {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.body as &mut dyn Node]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.body as &dyn Node]
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.start.join(self.end))
+    }
+}
+
+relocate! {
+    enum CatchType {
+        Identifier { identifier },
+        Union { identifiers },
+    }
+    struct TryStatement { start, end, body, catches, finally }
+    struct CatchBlock { start, end, types, var, body }
+    struct FinallyBlock { start, end, body }
 }
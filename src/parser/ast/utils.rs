@@ -6,7 +6,11 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::lexer::token::Span;
+use crate::node::join;
+use crate::node::span_of;
 use crate::node::Node;
+use crate::parser::ast::relocate::Relocate;
+use crate::parser::ast::relocate::Shift;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
@@ -25,10 +29,22 @@ impl<T> CommaSeparated<T> {
     }
 }
 
-impl<T: Node> Node for CommaSeparated<T> {
+impl<T: Node + Serialize> Node for CommaSeparated<T> {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.inner.iter_mut().map(|x| x as &mut dyn Node).collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.inner.iter().map(|x| x as &dyn Node).collect()
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([
+            span_of(&self.inner),
+            self.commas.first().copied(),
+            self.commas.last().copied(),
+        ])
+    }
 }
 
 impl<T> IntoIterator for CommaSeparated<T> {
@@ -39,3 +55,10 @@ impl<T> IntoIterator for CommaSeparated<T> {
         self.inner.into_iter()
     }
 }
+
+impl<T: Relocate> Relocate for CommaSeparated<T> {
+    fn relocate(&mut self, shift: Shift) {
+        self.inner.relocate(shift);
+        self.commas.relocate(shift);
+    }
+}
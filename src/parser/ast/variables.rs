@@ -6,7 +6,9 @@ use std::fmt::Display;
 
 use crate::lexer::byte_string::ByteString;
 use crate::lexer::token::Span;
+use crate::node::join;
 use crate::node::Node;
+use crate::parser::ast::relocate::relocate;
 use crate::parser::ast::Expression;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -20,9 +22,25 @@ pub enum Variable {
 impl Node for Variable {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         match self {
-            Variable::SimpleVariable(variable) => variable.children(),
-            Variable::VariableVariable(variable) => variable.children(),
-            Variable::BracedVariableVariable(variable) => variable.children(),
+            Variable::SimpleVariable(variable) => vec![variable],
+            Variable::VariableVariable(variable) => vec![variable],
+            Variable::BracedVariableVariable(variable) => vec![variable],
+        }
+    }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Variable::SimpleVariable(variable) => vec![variable],
+            Variable::VariableVariable(variable) => vec![variable],
+            Variable::BracedVariableVariable(variable) => vec![variable],
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Variable::SimpleVariable(variable) => variable.span(),
+            Variable::VariableVariable(variable) => variable.span(),
+            Variable::BracedVariableVariable(variable) => variable.span(),
         }
     }
 }
@@ -35,7 +53,9 @@ pub struct SimpleVariable {
 }
 
 impl Node for SimpleVariable {
-    //
+    fn span(&self) -> Option<Span> {
+        Some(self.span)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -49,6 +69,14 @@ impl Node for VariableVariable {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.variable.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.variable.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        join([Some(self.span), self.variable.span()])
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -63,6 +91,14 @@ impl Node for BracedVariableVariable {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.variable.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.variable.as_ref()]
+    }
+
+    fn span(&self) -> Option<Span> {
+        Some(self.start.join(self.end))
+    }
 }
 
 impl Display for SimpleVariable {
@@ -70,3 +106,14 @@ impl Display for SimpleVariable {
         write!(f, "{}", self.name)
     }
 }
+
+relocate! {
+    enum Variable {
+        SimpleVariable(value),
+        VariableVariable(value),
+        BracedVariableVariable(value),
+    }
+    struct SimpleVariable { span, name }
+    struct VariableVariable { span, variable }
+    struct BracedVariableVariable { start, variable, end }
+}
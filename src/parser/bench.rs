@@ -0,0 +1,119 @@
+//! Measuring how fast the lexer and parser get through a program.
+//!
+//! [`parse_bench`] times the two stages separately and counts what they produce, so that
+//! throughput can be compared across inputs of different sizes and shapes. The criterion
+//! benchmarks in `benches/` are built on it, and it can be called directly to report the
+//! effect of a change on some other code base:
+//!
+//! ```
+//! use php_parser_rs::parse_bench;
+//!
+//! let result = parse_bench("<?php if ($a) { echo 1; } echo 2;").unwrap();
+//!
+//! // the opening tag, `if`, its block, and both `echo`s.
+//! assert_eq!(result.statements, 5);
+//! println!("{:.0} tokens/s", result.tokens_per_second());
+//! ```
+
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::lexer::Lexer;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::parser::construct_with_config;
+use crate::parser::error::ParseErrorStack;
+use crate::parser::ParserConfig;
+use crate::traverser::walk;
+use crate::traverser::Visitor;
+
+/// The sizes and timings of a single run of the lexer and the parser.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BenchResult {
+    /// The length of the input, in bytes.
+    pub bytes: usize,
+    /// The number of tokens, including the end of file.
+    pub tokens: usize,
+    /// The number of statements, including those nested in blocks and declarations.
+    pub statements: usize,
+    /// The time spent tokenizing the input.
+    pub lex_time: Duration,
+    /// The time spent building the AST from the tokens, not including the lexing.
+    pub parse_time: Duration,
+}
+
+impl BenchResult {
+    pub fn total_time(&self) -> Duration {
+        self.lex_time + self.parse_time
+    }
+
+    pub fn bytes_per_second(&self) -> f64 {
+        per_second(self.bytes, self.total_time())
+    }
+
+    pub fn tokens_per_second(&self) -> f64 {
+        per_second(self.tokens, self.lex_time)
+    }
+
+    pub fn statements_per_second(&self) -> f64 {
+        per_second(self.statements, self.parse_time)
+    }
+}
+
+fn per_second(count: usize, time: Duration) -> f64 {
+    count as f64 / time.as_secs_f64().max(f64::MIN_POSITIVE)
+}
+
+/// Lex and parse the input once with the default configuration, timing each stage.
+pub fn parse_bench<B: ?Sized + AsRef<[u8]>>(input: &B) -> Result<BenchResult, ParseErrorStack> {
+    parse_bench_with_config(input, ParserConfig::default())
+}
+
+pub fn parse_bench_with_config<B: ?Sized + AsRef<[u8]>>(
+    input: &B,
+    config: ParserConfig,
+) -> Result<BenchResult, ParseErrorStack> {
+    let input = input.as_ref();
+
+    let start = Instant::now();
+    let tokens = match Lexer::with_config(config.lexer).tokenize(input) {
+        Ok(tokens) => tokens,
+        Err(error) => {
+            return Err(ParseErrorStack {
+                errors: vec![error.into()],
+                partial: Vec::new(),
+            })
+        }
+    };
+    let lex_time = start.elapsed();
+
+    let start = Instant::now();
+    let program = construct_with_config(&tokens, config)?;
+    let parse_time = start.elapsed();
+
+    Ok(BenchResult {
+        bytes: input.len(),
+        tokens: tokens.len(),
+        statements: count_statements(&program),
+        lex_time,
+        parse_time,
+    })
+}
+
+/// The number of statements in the program, at any depth.
+pub fn count_statements(program: &Program) -> usize {
+    struct Statements(usize);
+
+    impl Visitor for Statements {
+        fn enter_statement(&mut self, _: &Statement) {
+            self.0 += 1;
+        }
+    }
+
+    let mut statements = Statements(0);
+    for statement in program {
+        walk(&mut statements, statement);
+    }
+
+    statements.0
+}
@@ -1,14 +1,15 @@
 use std::fmt::{Display, Formatter};
 
-use ariadne::{CharSet, Color, Config, Label, Report, ReportKind, Source};
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::diagnostic::Diagnostic;
 use crate::lexer::error::SyntaxError;
 use crate::lexer::token::{Span, Token, TokenKind};
 use crate::parser::ast::attributes::AttributeGroup;
 use crate::parser::ast::data_type::Type;
+use crate::parser::ast::literals::Literal;
 use crate::parser::ast::modifiers::PromotedPropertyModifier;
 use crate::parser::ast::Program;
 
@@ -33,6 +34,15 @@ pub struct ParseErrorAnnotation {
     pub length: usize,
 }
 
+/// A replacement of `length` bytes at `position` that fixes the error.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct ParseErrorSuggestion {
+    pub message: String,
+    pub position: usize,
+    pub length: usize,
+    pub replacement: String,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ParseError {
     pub id: String,
@@ -40,6 +50,7 @@ pub struct ParseError {
     pub span: Span,
     pub annotations: Vec<ParseErrorAnnotation>,
     pub note: Option<String>,
+    pub suggestions: Vec<ParseErrorSuggestion>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -74,6 +85,7 @@ impl ParseError {
             span,
             annotations: Vec::new(),
             note: None,
+            suggestions: Vec::new(),
         }
     }
 
@@ -105,6 +117,23 @@ impl ParseError {
         self
     }
 
+    pub fn suggest<T: ToString, R: ToString>(
+        mut self,
+        message: T,
+        position: usize,
+        length: usize,
+        replacement: R,
+    ) -> Self {
+        self.suggestions.push(ParseErrorSuggestion {
+            message: message.to_string(),
+            position,
+            length,
+            replacement: replacement.to_string(),
+        });
+
+        self
+    }
+
     pub fn report<'a>(
         &self,
         source: &'a str,
@@ -112,58 +141,7 @@ impl ParseError {
         colored: bool,
         ascii: bool,
     ) -> std::io::Result<String> {
-        let origin = origin.unwrap_or("input");
-
-        let mut report = Report::build(ReportKind::Error, origin, self.span.position)
-            .with_code(&self.id)
-            .with_message(&self.message)
-            .with_config(
-                Config::default()
-                    .with_color(colored)
-                    .with_char_set(if ascii {
-                        CharSet::Ascii
-                    } else {
-                        CharSet::Unicode
-                    }),
-            );
-
-        for (order, annotation) in self.annotations.iter().enumerate() {
-            let mut label = Label::new((
-                origin,
-                annotation.position..annotation.position + annotation.length,
-            ))
-            .with_order(order.try_into().unwrap());
-
-            if !annotation.message.is_empty() {
-                label = label.with_message(&annotation.message);
-            }
-
-            if colored {
-                label = match annotation.r#type {
-                    ParseErrorAnnotationType::Hint => label.with_color(Color::Cyan),
-                    ParseErrorAnnotationType::Error => label.with_color(Color::Red),
-                };
-            }
-
-            report = report.with_label(label);
-        }
-
-        if let Some(note) = &self.note {
-            report = report.with_note(note);
-        }
-
-        let code = (origin, Source::from(source));
-
-        let mut bytes = Vec::new();
-
-        report.finish().write(code, &mut bytes)?;
-
-        let string = unsafe {
-            // SAFETY: We know that the bytes are valid UTF-8
-            String::from_utf8_unchecked(bytes)
-        };
-
-        Ok(string)
+        Diagnostic::from(self).report(source, origin, colored, ascii)
     }
 }
 
@@ -227,6 +205,57 @@ pub fn unexpected_token(expected: Vec<String>, found: &Token) -> ParseError {
     )
 }
 
+// A `}` closing an alternative block, as in `if ($a): ... }`, is most likely meant to
+// be its end keyword, which is suggested instead.
+pub fn unexpected_end_of_alternative_block(
+    error: ParseError,
+    found: &Token,
+    until: &[TokenKind],
+) -> ParseError {
+    let end = until.iter().rev().find(|kind| {
+        matches!(
+            kind,
+            TokenKind::EndIf
+                | TokenKind::EndWhile
+                | TokenKind::EndFor
+                | TokenKind::EndForeach
+                | TokenKind::EndSwitch
+                | TokenKind::EndDeclare
+        )
+    });
+
+    match end {
+        Some(end) if found.kind == TokenKind::RightBrace && error.span == found.span => {
+            let span = found.span;
+
+            error.suggest(
+                format!("did you mean `{};`?", end),
+                span.position,
+                1,
+                format!("{};", end),
+            )
+        }
+        _ => error,
+    }
+}
+
+// A semicolon is most likely missing at the end of the previous line when the
+// unexpected token is on the next one.
+pub fn missing_semicolon(previous: &Token, found: &Token) -> ParseError {
+    let error = unexpected_token(vec![";".to_string()], found);
+
+    if found.span.line > previous.span.line {
+        error.suggest(
+            format!("did you forget a `;` on line {}?", previous.span.line),
+            previous.span.end_position,
+            0,
+            ";",
+        )
+    } else {
+        error
+    }
+}
+
 pub fn unexpected_identifier(expected: Vec<String>, found: String, span: Span) -> ParseError {
     let length = expected.len();
     let expected = if length >= 2 {
@@ -447,6 +476,29 @@ pub fn constructor_in_enum(
     .highlight(r#enum.span.position, r#enum.value.len())
 }
 
+pub fn enum_cannot_contain_property(
+    state: &mut State,
+    r#enum: &SimpleIdentifier,
+    property: &SimpleVariable,
+) -> ParseError {
+    ParseError::new(
+        "E057",
+        format!(
+            "enum `{}` cannot include property `{}`",
+            state.named(&r#enum),
+            property.name
+        ),
+        property.span,
+    )
+    .error(
+        "try removing this property",
+        property.span.position,
+        property.name.len(),
+    )
+    .highlight(r#enum.span.position, r#enum.value.len())
+    .note("enums may only include cases, constants, methods, and trait usages")
+}
+
 pub fn magic_method_in_enum(
     state: &mut State,
     r#enum: &SimpleIdentifier,
@@ -578,6 +630,19 @@ pub fn modifier_cannot_be_used_for_class(modifier: String, modifier_span: Span)
     .note("only `final`, `abstract`, and `readonly` modifiers can be used on classes")
 }
 
+pub fn modifier_cannot_be_used_for_anonymous_class(
+    modifier: String,
+    modifier_span: Span,
+) -> ParseError {
+    ParseError::new(
+        "E067",
+        format!("cannot use '{}' as an anonymous class modifier", modifier),
+        modifier_span,
+    )
+    .error("try removing this", modifier_span.position, modifier.len())
+    .note("only the `readonly` modifier can be used on anonymous classes")
+}
+
 pub fn modifier_cannot_be_used_for_class_method(
     modifier: String,
     modifier_span: Span,
@@ -794,7 +859,7 @@ pub fn forbidden_type_used_in_property(
         type_span.position,
         type_string.len(),
     )
-    .note("`void`, `never`, and `callable` types are not allowed in properties");
+    .note("`void`, `never`, `static`, and `callable` types are not allowed in properties");
 
     // If the class is anonymous, we don't have a span to highlight
     if let Some(class) = class {
@@ -850,6 +915,7 @@ pub fn missing_item_definition_after_attributes(
         span: current.span,
         annotations,
         note: None,
+        suggestions: Vec::new(),
     }
 }
 
@@ -972,14 +1038,278 @@ pub fn argument_is_required(span: Span, current_span: Span) -> ParseError {
     )
 }
 
+pub fn maximum_depth_exceeded(span: Span, max_depth: usize) -> ParseError {
+    ParseError::new(
+        "E052".to_string(),
+        format!("maximum nesting depth of {} exceeded", max_depth),
+        span,
+    )
+    .note("consider splitting the code into smaller statements or expressions")
+}
+
+pub fn maximum_attributes_exceeded(span: Span, max_attributes: usize) -> ParseError {
+    ParseError::new(
+        "E053".to_string(),
+        format!(
+            "maximum number of {} attributes on a declaration exceeded",
+            max_attributes
+        ),
+        span,
+    )
+    .note("consider removing repeated attributes")
+}
+
+pub fn return_only_type_used_in_parameter(ty: &Type, variable: &SimpleVariable) -> ParseError {
+    let type_string = ty.to_string();
+    let type_span = ty.first_span();
+
+    ParseError::new(
+        "E054".to_string(),
+        format!(
+            "parameter `{}` cannot have type `{}`",
+            variable.name, type_string
+        ),
+        type_span,
+    )
+    .highlight(variable.span.position, variable.name.len())
+    .error(
+        "try using a different type",
+        type_span.position,
+        type_string.len(),
+    )
+    .note("`void`, `never`, and `static` can only be used as return types")
+}
+
+pub fn promoted_property_outside_constructor(span: Span, modifier: &TokenKind) -> ParseError {
+    let modifier = modifier.to_string();
+
+    ParseError::new(
+        "E055".to_string(),
+        "cannot declare promoted property outside a constructor",
+        span,
+    )
+    .error("try removing this", span.position, modifier.len())
+    .note(
+        "promoted properties can only be declared in the parameters of a non-abstract constructor",
+    )
+}
+
+pub fn duplicate_parameter(first: &SimpleVariable, second: &SimpleVariable) -> ParseError {
+    ParseError::new(
+        "E056".to_string(),
+        format!("cannot redeclare parameter `{}`", second.name),
+        second.span,
+    )
+    .highlight(first.span.position, first.name.len())
+    .error(
+        "try renaming this parameter",
+        second.span.position,
+        second.name.len(),
+    )
+}
+
+pub fn cannot_use_this_as_lexical_variable(variable: &SimpleVariable) -> ParseError {
+    ParseError::new(
+        "E068".to_string(),
+        "cannot use `$this` as a lexical variable",
+        variable.span,
+    )
+    .error(
+        "try removing this variable",
+        variable.span.position,
+        variable.name.len(),
+    )
+    .note("`$this` is bound automatically in non-static closures")
+}
+
+pub fn duplicate_lexical_variable(first: &SimpleVariable, second: &SimpleVariable) -> ParseError {
+    ParseError::new(
+        "E069".to_string(),
+        format!("cannot use lexical variable `{}` twice", second.name),
+        second.span,
+    )
+    .highlight(first.span.position, first.name.len())
+    .error(
+        "try removing this variable",
+        second.span.position,
+        second.name.len(),
+    )
+}
+
+pub fn cannot_use_parameter_as_lexical_variable(
+    parameter: &SimpleVariable,
+    variable: &SimpleVariable,
+) -> ParseError {
+    ParseError::new(
+        "E070".to_string(),
+        format!(
+            "cannot use lexical variable `{}` as it is also a parameter",
+            variable.name
+        ),
+        variable.span,
+    )
+    .highlight(parameter.span.position, parameter.name.len())
+    .error(
+        "try removing this variable",
+        variable.span.position,
+        variable.name.len(),
+    )
+}
+
+pub fn arrow_function_cannot_have_use_clause(r#use: Span, right_parenthesis: Span) -> ParseError {
+    ParseError::new(
+        "E071".to_string(),
+        "arrow functions cannot have a `use` clause",
+        r#use,
+    )
+    .error(
+        "try removing this",
+        r#use.position,
+        right_parenthesis.end_position - r#use.position,
+    )
+    .note("arrow functions capture the variables they use by value automatically")
+}
+
+pub fn cannot_use_argument_unpacking_after_named_argument(
+    span: Span,
+    current_span: Span,
+) -> ParseError {
+    ParseError::new(
+        "E058".to_string(),
+        "cannot use argument unpacking after named arguments",
+        span,
+    )
+    .error(
+        "try moving this argument before the named arguments",
+        span.position,
+        current_span.position - span.position,
+    )
+}
+
+pub fn cannot_unpack_named_argument(name: &SimpleIdentifier, ellipsis: Span) -> ParseError {
+    ParseError::new(
+        "E059".to_string(),
+        format!("cannot unpack named argument `{}`", name),
+        ellipsis,
+    )
+    .highlight(name.span.position, name.value.len())
+    .error("try removing this", ellipsis.position, 3)
+}
+
+pub fn duplicate_named_argument(first: &SimpleIdentifier, second: &SimpleIdentifier) -> ParseError {
+    ParseError::new(
+        "E060".to_string(),
+        format!("named argument `{}` overwrites previous argument", second),
+        second.span,
+    )
+    .highlight(first.span.position, first.value.len())
+    .error(
+        "try removing this argument",
+        second.span.position,
+        second.value.len(),
+    )
+}
+
+pub fn first_class_callable_with_arguments(ellipsis: Span) -> ParseError {
+    ParseError::new(
+        "E061".to_string(),
+        "cannot combine first-class callable syntax with other arguments",
+        ellipsis,
+    )
+    .error("try removing this", ellipsis.position, 3)
+    .note("a first-class callable is created with `(...)`, e.g. `strlen(...)`")
+}
+
+pub fn strict_types_must_be_first_statement(span: Span) -> ParseError {
+    ParseError::new(
+        "E062".to_string(),
+        "strict_types declaration must be the very first statement in the script",
+        span,
+    )
+    .error(
+        "try moving this declaration to the top of the script",
+        span.position,
+        "strict_types".len(),
+    )
+}
+
+pub fn strict_types_value_must_be_zero_or_one(value: &Literal) -> ParseError {
+    let (span, length) = match value {
+        Literal::String(string) => (string.span, string.span.length()),
+        Literal::Integer(integer) => (integer.span, integer.value.len()),
+        Literal::Float(float) => (float.span, float.value.len()),
+    };
+
+    ParseError::new(
+        "E063".to_string(),
+        "strict_types declaration value must be literally 0 or 1",
+        span,
+    )
+    .error("try using `0` or `1`", span.position, length)
+}
+
+pub fn strict_types_cannot_use_block_mode(span: Span) -> ParseError {
+    ParseError::new(
+        "E064".to_string(),
+        "strict_types declaration must not use block mode",
+        span,
+    )
+    .error("try replacing this with `;`", span.position, 1)
+}
+
+pub fn cannot_use_construct_on_expression(construct: &str, span: Span) -> ParseError {
+    ParseError::new(
+        "E065".to_string(),
+        format!("cannot use {}() on the result of an expression", construct),
+        span,
+    )
+    .error("try using a variable", span.position, span.length())
+}
+
+pub fn unset_cannot_be_used_as_value(span: Span) -> ParseError {
+    ParseError::new(
+        "E066".to_string(),
+        "cannot use unset() as a value, it is a statement",
+        span,
+    )
+    .error(
+        "try moving this to its own statement",
+        span.position,
+        span.length(),
+    )
+}
+
+pub fn new_without_parentheses_followed_by_member_access(current: &Token) -> ParseError {
+    ParseError::new(
+        "E072".to_string(),
+        format!(
+            "cannot use `{}` on a `new` expression without parentheses",
+            current.value
+        ),
+        current.span,
+    )
+    .error(
+        "try adding `()` before this",
+        current.span.position,
+        current.value.len(),
+    )
+    .suggest("add an empty argument list", current.span.position, 0, "()")
+}
+
 impl From<SyntaxError> for ParseError {
     fn from(e: SyntaxError) -> Self {
-        Self {
-            id: "E001".to_string(),
-            message: format!("syntax error, {}", e),
-            annotations: vec![],
-            span: e.span(),
-            note: None,
+        let span = e.span();
+        let error = ParseError::new("E001", format!("syntax error, {}", e.message()), span);
+
+        // the end of the file can't be pointed at.
+        let error = match e {
+            SyntaxError::UnexpectedEndOfFile(_) => error,
+            _ => error.error(e.label(), span.position, span.length().max(1)),
+        };
+
+        match e.note() {
+            Some(note) => error.note(note),
+            None => error,
         }
     }
 }
@@ -1,7 +1,11 @@
 use crate::expected_token_err;
 use crate::lexer::token::DocStringKind;
+use crate::lexer::token::Span;
 use crate::lexer::token::TokenKind;
+use crate::parser::ast::arguments::Argument;
 use crate::parser::ast::arguments::ArgumentPlaceholder;
+use crate::parser::ast::arguments::PositionalArgument;
+use crate::parser::ast::arguments::SingleArgument;
 use crate::parser::ast::identifiers::DynamicIdentifier;
 use crate::parser::ast::identifiers::Identifier;
 use crate::parser::ast::identifiers::SimpleIdentifier;
@@ -43,6 +47,7 @@ use crate::parser::state::State;
 use super::ast::literals::LiteralStringKind;
 use super::ast::BoolExpression;
 use super::ast::CastExpression;
+use super::ast::CastKind;
 use super::ast::CloneExpression;
 use super::ast::DieExpression;
 use super::ast::EmptyExpression;
@@ -70,11 +75,21 @@ fn null_coalesce_precedence(state: &mut State) -> ParseResult<Expression> {
     for_precedence(state, Precedence::NullCoalesce)
 }
 
+// The else branch of a ternary, which ends before `and`, `or`, and `xor`, while a
+// nested ternary in it is still part of it.
+fn ternary_else(state: &mut State) -> ParseResult<Expression> {
+    for_precedence(state, Precedence::Assignment)
+}
+
 fn clone_or_new_precedence(state: &mut State) -> ParseResult<Expression> {
     for_precedence(state, Precedence::CloneOrNew)
 }
 
 fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expression> {
+    state.nested(|state| nested_for_precedence(state, precedence))
+}
+
+fn nested_for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expression> {
     let mut left = left(state, &precedence)?;
 
     loop {
@@ -87,7 +102,9 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
         }
 
         if is_postfix(kind) {
-            let lpred = Precedence::postfix(kind);
+            let Some(lpred) = Precedence::postfix(kind) else {
+                return Err(error::reached_unpredictable_state(span));
+            };
 
             if lpred < precedence {
                 break;
@@ -98,9 +115,15 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
         }
 
         if is_infix(kind) {
-            let rpred = Precedence::infix(kind);
+            let Some(rpred) = Precedence::infix(kind) else {
+                return Err(error::reached_unpredictable_state(span));
+            };
 
-            if rpred < precedence {
+            // an assignment applies to the variable right before it, whatever the
+            // operators before that, e.g. `!$a = foo()` is `!($a = foo())`.
+            let assignment = rpred == Precedence::Assignment && is_assignable(&left);
+
+            if rpred < precedence && !assignment {
                 break;
             }
 
@@ -124,7 +147,7 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                     if op.kind == TokenKind::Colon {
                         state.stream.next();
 
-                        let r#else = create(state)?;
+                        let r#else = ternary_else(state)?;
 
                         Expression::Ternary(TernaryExpression {
                             condition: Box::new(left),
@@ -136,7 +159,7 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                     } else {
                         let then = create(state)?;
                         let colon = utils::skip_colon(state)?;
-                        let r#else = create(state)?;
+                        let r#else = ternary_else(state)?;
 
                         Expression::Ternary(TernaryExpression {
                             condition: Box::new(left),
@@ -148,7 +171,7 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                     }
                 }
                 TokenKind::QuestionColon => {
-                    let r#else = create(state)?;
+                    let r#else = ternary_else(state)?;
                     Expression::ShortTernary(ShortTernaryExpression {
                         condition: Box::new(left),
                         question_colon: span,
@@ -177,7 +200,7 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                     Expression::Instanceof(InstanceofExpression {
                         left: Box::new(left),
                         instanceof: span,
-                        right: Box::new(Expression::Self_),
+                        right: Box::new(Expression::Self_(op.span)),
                     })
                 }
                 TokenKind::Instanceof if op.kind == TokenKind::Parent => {
@@ -186,7 +209,7 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                     Expression::Instanceof(InstanceofExpression {
                         left: Box::new(left),
                         instanceof: span,
-                        right: Box::new(Expression::Parent),
+                        right: Box::new(Expression::Parent(op.span)),
                     })
                 }
                 TokenKind::Instanceof if op.kind == TokenKind::Static => {
@@ -195,7 +218,7 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                     Expression::Instanceof(InstanceofExpression {
                         left: Box::new(left),
                         instanceof: span,
-                        right: Box::new(Expression::Static),
+                        right: Box::new(Expression::Static(op.span)),
                     })
                 }
                 TokenKind::Instanceof if op.kind == TokenKind::Enum => {
@@ -277,7 +300,7 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                         ),
                         TokenKind::Equals => {
                             Expression::AssignmentOperation(AssignmentOperationExpression::Assign {
-                                left,
+                                left: Box::new(arrays::destructuring_target(state, *left)),
                                 equals: span,
                                 right,
                             })
@@ -523,7 +546,7 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                             instanceof: span,
                             right,
                         }),
-                        _ => todo!(),
+                        _ => return Err(error::reached_unpredictable_state(span)),
                     }
                 }
             };
@@ -539,7 +562,7 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
 
 fn left(state: &mut State, precedence: &Precedence) -> ParseResult<Expression> {
     if state.stream.is_eof() {
-        return Err(error::unexpected_token(vec![], state.stream.current()));
+        return unexpected_token(state, precedence);
     }
 
     attributes(state, precedence)
@@ -623,7 +646,10 @@ expressions! {
         let eval = state.stream.current().span;
         state.stream.next();
 
-        let argument = Box::new(parameters::single_argument(state, true, true).unwrap()?);
+        let argument = match parameters::single_argument(state, true, true) {
+            Some(argument) => Box::new(argument?),
+            None => return Err(error::reached_unpredictable_state(eval)),
+        };
 
         Ok(Expression::Eval(EvalExpression { eval, argument }))
     })
@@ -633,7 +659,10 @@ expressions! {
         let empty = state.stream.current().span;
         state.stream.next();
 
-        let argument = Box::new(parameters::single_argument(state, true, true).unwrap()?);
+        let argument = match parameters::single_argument(state, true, true) {
+            Some(argument) => Box::new(argument?),
+            None => return Err(error::reached_unpredictable_state(empty)),
+        };
 
         Ok(Expression::Empty(EmptyExpression { empty, argument }))
     })
@@ -668,18 +697,20 @@ expressions! {
     isset({
         let isset = state.stream.current().span;
         state.stream.next();
-        let arguments = parameters::argument_list(state)?;
+        let arguments = parameters::variable_list(state, "isset")?;
 
         Ok(Expression::Isset(IssetExpression { isset, arguments}))
     })
 
     #[before(print), current(TokenKind::Unset), peek(TokenKind::LeftParen)]
     unset({
-        let unset = state.stream.current().span;
-        state.stream.next();
-        let arguments = parameters::argument_list(state)?;
+        let unset = unset_statement(state)?;
+
+        state.record(error::unset_cannot_be_used_as_value(
+            unset.unset.join(unset.arguments.right_parenthesis),
+        ));
 
-        Ok(Expression::Unset(UnsetExpression { unset, arguments}))
+        Ok(Expression::Unset(unset))
     })
 
     #[before(reserved_identifier_function_call), current(TokenKind::Print)]
@@ -687,16 +718,32 @@ expressions! {
         let print = state.stream.current().span;
         state.stream.next();
 
-        let mut value = None;
-        let mut argument = None;
-
-        if let Some(arg) = parameters::single_argument(state, false, true) {
-            argument = Some(Box::new(arg?));
-        } else {
-            value = Some(Box::new(create(state)?));
-        }
+        let comments = state.stream.comments();
+        let value = for_precedence(state, Precedence::Print)?;
 
-        Ok(Expression::Print(PrintExpression { print, value, argument }))
+        // in `print($a)` the parentheses are kept as its argument, while in
+        // `print($a) . $b` they only group `$a`, and `$a . $b` is printed.
+        Ok(Expression::Print(match value {
+            Expression::Parenthesized(ParenthesizedExpression { start, expr, end }) => PrintExpression {
+                print,
+                value: None,
+                argument: Some(Box::new(SingleArgument {
+                    comments,
+                    left_parenthesis: start,
+                    argument: Argument::Positional(PositionalArgument {
+                        comments: state.stream.comments(),
+                        ellipsis: None,
+                        value: *expr,
+                    }),
+                    right_parenthesis: end,
+                })),
+            },
+            value => PrintExpression {
+                print,
+                value: Some(Box::new(value)),
+                argument: None,
+            },
+        }))
     })
 
     #[before(reserved_identifier_static_call), precedence(Precedence::CallDim), current(
@@ -724,56 +771,77 @@ expressions! {
         arrays::list_expression(state)
     })
 
-    #[before(throw), current(TokenKind::New), peek(TokenKind::Class | TokenKind::Attribute)]
+    #[before(throw), current(TokenKind::New), peek(
+        | TokenKind::Class | TokenKind::Attribute
+        | TokenKind::Readonly | TokenKind::Final | TokenKind::Abstract
+    )]
     anonymous_class({
         classes::parse_anonymous(state, None)
     })
 
     #[before(r#yield), current(TokenKind::Throw)]
     throw({
-        state.stream.next();
+        let throw = utils::skip(state, TokenKind::Throw)?;
 
         Ok(Expression::Throw(ThrowExpression {
+            throw,
             value: Box::new(for_precedence(state, Precedence::Lowest)?)
         }))
     })
 
     #[before(clone), current(TokenKind::Yield)]
     r#yield({
-        state.stream.next();
-        if state.stream.current().kind == TokenKind::SemiColon || state.stream.current().kind == TokenKind::RightParen {
+        let r#yield = utils::skip(state, TokenKind::Yield)?;
+        // `yield` without a value, e.g. `yield;`, `foo(yield, 1)` or `$a ? yield : 1`.
+        if matches!(
+            state.stream.current().kind,
+            TokenKind::SemiColon
+                | TokenKind::CloseTag
+                | TokenKind::Eof
+                | TokenKind::RightParen
+                | TokenKind::RightBracket
+                | TokenKind::RightBrace
+                | TokenKind::Comma
+                | TokenKind::Colon
+                | TokenKind::DoubleArrow
+                | TokenKind::As
+                | TokenKind::LogicalAnd
+                | TokenKind::LogicalOr
+                | TokenKind::LogicalXor
+        ) {
             Ok(Expression::Yield(YieldExpression {
+                r#yield,
                 key: None,
                 value: None,
             }))
         } else {
-            let mut from = false;
+            let mut from = None;
 
             if state.stream.current().kind == TokenKind::From {
-                state.stream.next();
-                from = true;
+                from = Some(utils::skip(state, TokenKind::From)?);
             }
 
             let mut key = None;
             let mut value = Box::new(for_precedence(
                 state,
-                if from {
+                if from.is_some() {
                     Precedence::YieldFrom
                 } else {
                     Precedence::Yield
                 },
             )?);
 
-            if state.stream.current().kind == TokenKind::DoubleArrow && !from {
+            if state.stream.current().kind == TokenKind::DoubleArrow && from.is_none() {
                 state.stream.next();
                 key = Some(value.clone());
                 value = Box::new(for_precedence(state, Precedence::Yield)?);
             }
 
-            if from {
-                Ok(Expression::YieldFrom(YieldFromExpression { value }))
+            if let Some(from) = from {
+                Ok(Expression::YieldFrom(YieldFromExpression { r#yield, from, value }))
             } else {
                 Ok(Expression::Yield(YieldExpression {
+                    r#yield,
                     key,
                     value: Some(value),
                 }))
@@ -783,34 +851,35 @@ expressions! {
 
     #[before(r#true), current(TokenKind::Clone)]
     clone({
-        state.stream.next();
+        let clone = utils::skip(state, TokenKind::Clone)?;
 
-        let target = for_precedence(state, Precedence::CloneOrNew)?;
+        let target = for_precedence(state, Precedence::CallDim)?;
 
         Ok(Expression::Clone(CloneExpression {
+            clone,
             target: Box::new(target),
         }))
     })
 
     #[before(r#false), current(TokenKind::True)]
     r#true({
-        state.stream.next();
+        let span = utils::skip(state, TokenKind::True)?;
 
-        Ok(Expression::Bool(BoolExpression { value: true }))
+        Ok(Expression::Bool(BoolExpression { span, value: true }))
     })
 
     #[before(null), current(TokenKind::False)]
     r#false({
-        state.stream.next();
+        let span = utils::skip(state, TokenKind::False)?;
 
-        Ok(Expression::Bool(BoolExpression { value: false }))
+        Ok(Expression::Bool(BoolExpression { span, value: false }))
     })
 
     #[before(literal_integer), current(TokenKind::Null)]
     null({
-        state.stream.next();
+        let span = utils::skip(state, TokenKind::Null)?;
 
-        Ok(Expression::Null)
+        Ok(Expression::Null(span))
     })
 
     #[before(literal_float), current(TokenKind::LiteralInteger)]
@@ -827,7 +896,7 @@ expressions! {
                 }
             )))
         } else {
-            unreachable!("{}:{}", file!(), line!());
+            Err(error::reached_unpredictable_state(current.span))
         }
     })
 
@@ -845,7 +914,7 @@ expressions! {
                 })
             ))
         } else {
-            unreachable!("{}:{}", file!(), line!());
+            Err(error::reached_unpredictable_state(current.span))
         }
     })
 
@@ -859,7 +928,7 @@ expressions! {
             Ok(Expression::Literal(
                 Literal::String(LiteralString {
                     span: current.span,
-                    value: current.value.clone(),
+                    value: state.literal(&current.value),
                     kind: LiteralStringKind::SingleQuoted,
                 })
             ))
@@ -869,16 +938,16 @@ expressions! {
             Ok(Expression::Literal(
                 Literal::String(LiteralString {
                     span: current.span,
-                    value: current.value.clone(),
+                    value: state.literal(&current.value),
                     kind: LiteralStringKind::DoubleQuoted,
                 })
             ))
         } else {
-            unreachable!("{}:{}", file!(), line!());
+            Err(error::reached_unpredictable_state(current.span))
         }
     })
 
-    #[before(heredoc), current(TokenKind::StringPart)]
+    #[before(heredoc), current(TokenKind::DoubleQuote)]
     string_part({
         strings::interpolated(state)
     })
@@ -905,23 +974,23 @@ expressions! {
 
     #[before(self_identifier), current(TokenKind::Static)]
     static_postfix({
-        state.stream.next();
+        let span = utils::skip(state, TokenKind::Static)?;
 
-        postfix(state, Expression::Static, &TokenKind::DoubleColon)
+        postfix(state, Expression::Static(span), &TokenKind::DoubleColon)
     })
 
     #[before(parent_identifier), current(TokenKind::Self_)]
     self_identifier({
-        state.stream.next();
+        let span = utils::skip(state, TokenKind::Self_)?;
 
-        Ok(Expression::Self_)
+        Ok(Expression::Self_(span))
     })
 
     #[before(left_parenthesis), current(TokenKind::Parent)]
     parent_identifier({
-        state.stream.next();
+        let span = utils::skip(state, TokenKind::Parent)?;
 
-        Ok(Expression::Parent)
+        Ok(Expression::Parent(span))
     })
 
     #[before(r#match), current(TokenKind::LeftParen)]
@@ -957,26 +1026,18 @@ expressions! {
 
         state.stream.next();
 
-        if state.stream.current().kind == TokenKind::Class || state.stream.current().kind == TokenKind::Attribute {
+        if matches!(
+            state.stream.current().kind,
+            | TokenKind::Class | TokenKind::Attribute
+            | TokenKind::Readonly | TokenKind::Final | TokenKind::Abstract
+        ) {
             return classes::parse_anonymous(state, Some(new));
         };
 
         let target = match state.stream.current().kind {
-            TokenKind::Self_ => {
-                state.stream.next();
-
-                Expression::Self_
-            }
-            TokenKind::Static => {
-                state.stream.next();
-
-                Expression::Static
-            }
-            TokenKind::Parent => {
-                state.stream.next();
-
-                Expression::Parent
-            }
+            TokenKind::Self_ => Expression::Self_(utils::skip(state, TokenKind::Self_)?),
+            TokenKind::Static => Expression::Static(utils::skip(state, TokenKind::Static)?),
+            TokenKind::Parent => Expression::Parent(utils::skip(state, TokenKind::Parent)?),
             TokenKind::Enum => {
                 let span = state.stream.current().span;
 
@@ -994,12 +1055,24 @@ expressions! {
             _ => clone_or_new_precedence(state)?,
         };
 
+        let target = new_target_member_access(state, target)?;
+
         let arguments = if state.stream.current().kind == TokenKind::LeftParen {
             Some(parameters::argument_list(state)?)
         } else {
             None
         };
 
+        // `new Foo()->bar()` calls `bar` on the new instance, but without the parentheses
+        // it is ambiguous, and not accepted.
+        let current = state.stream.current();
+        if arguments.is_none() && matches!(
+            current.kind,
+            TokenKind::Arrow | TokenKind::QuestionArrow | TokenKind::DoubleColon | TokenKind::LeftBracket
+        ) {
+            state.record(error::new_without_parentheses_followed_by_member_access(current));
+        }
+
         Ok(Expression::New(NewExpression {
             target: Box::new(target),
             new,
@@ -1093,7 +1166,7 @@ expressions! {
             TokenKind::IncludeOnce => Expression::IncludeOnce(IncludeOnceExpression { include_once: span, path }),
             TokenKind::Require => Expression::Require(RequireExpression { require: span, path }),
             TokenKind::RequireOnce => Expression::RequireOnce(RequireOnceExpression { require_once: span, path }),
-            _ => unreachable!()
+            _ => return Err(error::reached_unpredictable_state(span)),
         })
     })
 
@@ -1107,7 +1180,9 @@ expressions! {
         let current = state.stream.current();
 
         let span = current.span;
-        let kind = current.kind.clone().into();
+        let Ok(kind) = CastKind::try_from(&current.kind) else {
+            return Err(error::reached_unpredictable_state(span));
+        };
 
         state.stream.next();
 
@@ -1135,7 +1210,7 @@ expressions! {
             TokenKind::Plus => Expression::ArithmeticOperation(ArithmeticOperationExpression::Positive { plus: span, right }),
             TokenKind::Decrement => Expression::ArithmeticOperation(ArithmeticOperationExpression::PreDecrement { decrement: span, right }),
             TokenKind::Increment => Expression::ArithmeticOperation(ArithmeticOperationExpression::PreIncrement { increment: span, right }),
-            _ => unreachable!(),
+            _ => return Err(error::reached_unpredictable_state(span)),
         };
 
         Ok(expr)
@@ -1186,10 +1261,32 @@ expressions! {
     })
 }
 
+/// `unset($a, $b)`, which is only valid on its own as a statement.
+pub(in crate::parser) fn unset_statement(state: &mut State) -> ParseResult<UnsetExpression> {
+    let unset = state.stream.current().span;
+    state.stream.next();
+    let arguments = parameters::variable_list(state, "unset")?;
+
+    Ok(UnsetExpression { unset, arguments })
+}
+
 fn unexpected_token(state: &mut State, _: &Precedence) -> ParseResult<Expression> {
     let current = state.stream.current();
+    let error = error::unexpected_token(vec![], current);
+
+    // the token is left for the enclosing statement, which either expects it, or
+    // fails and is skipped in turn.
+    if state.recover {
+        state.record(error);
+
+        return Ok(Expression::Missing(Span::new(
+            current.span.line,
+            current.span.column,
+            current.span.position,
+        )));
+    }
 
-    Err(error::unexpected_token(vec![], current))
+    Err(error)
 }
 
 fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Expression> {
@@ -1319,7 +1416,7 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
                                 },
                             )
                         }
-                        _ => unreachable!(),
+                        _ => return Err(error::reached_unpredictable_state(span)),
                     }
                 } else {
                     let arguments = parameters::argument_list(state)?;
@@ -1341,7 +1438,7 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
                                 arguments,
                             },
                         ),
-                        _ => unreachable!(),
+                        _ => return Err(error::reached_unpredictable_state(span)),
                     }
                 }
             } else {
@@ -1360,7 +1457,7 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
                             property: variable,
                         })
                     }
-                    _ => unreachable!(),
+                    _ => return Err(error::reached_unpredictable_state(span)),
                 }
             }
         }
@@ -1368,33 +1465,7 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
             let span = state.stream.current().span;
             state.stream.next();
 
-            let property = match state.stream.current().kind {
-                TokenKind::Variable | TokenKind::Dollar | TokenKind::DollarLeftBrace => {
-                    Expression::Variable(variables::dynamic_variable(state)?)
-                }
-                _ if identifiers::is_identifier_maybe_reserved(&state.stream.current().kind) => {
-                    Expression::Identifier(Identifier::SimpleIdentifier(
-                        identifiers::identifier_maybe_reserved(state)?,
-                    ))
-                }
-                TokenKind::LeftBrace => {
-                    let start = state.stream.current().span;
-                    state.stream.next();
-
-                    let name = create(state)?;
-
-                    let end = utils::skip_right_brace(state)?;
-
-                    Expression::Identifier(Identifier::DynamicIdentifier(DynamicIdentifier {
-                        start,
-                        expr: Box::new(name),
-                        end,
-                    }))
-                }
-                _ => {
-                    return expected_token_err!(["`{`", "`$`", "an identifier"], state);
-                }
-            };
+            let property = property_name(state)?;
 
             if state.stream.current().kind == TokenKind::LeftParen {
                 if op == &TokenKind::QuestionArrow {
@@ -1471,10 +1542,111 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
                 decrement: span,
             })
         }
-        _ => todo!("postfix: {:?}", op),
+        _ => {
+            return Err(error::reached_unpredictable_state(
+                state.stream.current().span,
+            ))
+        }
     })
 }
 
+// The name after `->` or `?->`: `$a->b`, `$a->$b`, or `$a->{$b}`.
+fn property_name(state: &mut State) -> ParseResult<Expression> {
+    Ok(match state.stream.current().kind {
+        TokenKind::Variable | TokenKind::Dollar | TokenKind::DollarLeftBrace => {
+            Expression::Variable(variables::dynamic_variable(state)?)
+        }
+        _ if identifiers::is_identifier_maybe_reserved(&state.stream.current().kind) => {
+            Expression::Identifier(Identifier::SimpleIdentifier(
+                identifiers::identifier_maybe_reserved(state)?,
+            ))
+        }
+        TokenKind::LeftBrace => {
+            let start = state.stream.current().span;
+            state.stream.next();
+
+            let name = create(state)?;
+
+            let end = utils::skip_right_brace(state)?;
+
+            Expression::Identifier(Identifier::DynamicIdentifier(DynamicIdentifier {
+                start,
+                expr: Box::new(name),
+                end,
+            }))
+        }
+        _ => {
+            return expected_token_err!(["`{`", "`$`", "an identifier"], state);
+        }
+    })
+}
+
+// The class name of a `new` expression may access properties, array items and static
+// properties, but not call methods: `new $a->b['c']::$d()` instantiates the class named
+// by `$a->b['c']::$d`. A name such as `Foo` can only be followed by a static property,
+// and an expression in parentheses by nothing.
+fn new_target_member_access(state: &mut State, target: Expression) -> ParseResult<Expression> {
+    if matches!(target, Expression::Parenthesized(_)) {
+        return Ok(target);
+    }
+
+    let mut is_variable = !matches!(
+        target,
+        Expression::Identifier(_)
+            | Expression::Self_(_)
+            | Expression::Static(_)
+            | Expression::Parent(_)
+    );
+    let mut target = target;
+
+    loop {
+        let current = state.stream.current();
+
+        target = match current.kind {
+            TokenKind::Arrow | TokenKind::QuestionArrow | TokenKind::LeftBracket
+                if !is_variable =>
+            {
+                return Ok(target)
+            }
+            TokenKind::Arrow => {
+                state.stream.next();
+
+                Expression::PropertyFetch(PropertyFetchExpression {
+                    target: Box::new(target),
+                    arrow: current.span,
+                    property: Box::new(property_name(state)?),
+                })
+            }
+            TokenKind::QuestionArrow => {
+                state.stream.next();
+
+                Expression::NullsafePropertyFetch(NullsafePropertyFetchExpression {
+                    target: Box::new(target),
+                    question_arrow: current.span,
+                    property: Box::new(property_name(state)?),
+                })
+            }
+            TokenKind::LeftBracket => postfix(state, target, &TokenKind::LeftBracket)?,
+            TokenKind::DoubleColon
+                if matches!(
+                    state.stream.peek().kind,
+                    TokenKind::Variable | TokenKind::Dollar | TokenKind::DollarLeftBrace
+                ) =>
+            {
+                state.stream.next();
+                is_variable = true;
+
+                Expression::StaticPropertyFetch(StaticPropertyFetchExpression {
+                    target: Box::new(target),
+                    double_colon: current.span,
+                    property: variables::dynamic_variable(state)?,
+                })
+            }
+            _ => return Ok(target),
+        };
+    }
+}
+
 fn is_infix(t: &TokenKind) -> bool {
     matches!(
         t,
@@ -1525,6 +1697,16 @@ fn is_infix(t: &TokenKind) -> bool {
     )
 }
 
+fn is_assignable(expression: &Expression) -> bool {
+    matches!(
+        expression,
+        Expression::Variable(_)
+            | Expression::ArrayIndex(_)
+            | Expression::PropertyFetch(_)
+            | Expression::StaticPropertyFetch(_)
+    )
+}
+
 #[inline(always)]
 fn is_postfix(t: &TokenKind) -> bool {
     matches!(
@@ -0,0 +1,108 @@
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::lexer::interner::Interner;
+use crate::lexer::Lexer;
+use crate::parser::ast::Program;
+use crate::parser::construct_with_config;
+use crate::parser::error::ParseError;
+use crate::parser::ParserConfig;
+
+/// A file parsed by [`parse_files`].
+#[derive(Debug)]
+pub struct ParsedFile {
+    pub path: PathBuf,
+    // The program, which is partial if the file has errors.
+    pub program: Program,
+    pub errors: Vec<ParseError>,
+}
+
+/// The files parsed by [`parse_files`], in the order their paths were given.
+#[derive(Debug, Default)]
+pub struct ParsedFiles {
+    pub files: Vec<ParsedFile>,
+    // The files that could not be read.
+    pub unreadable: Vec<(PathBuf, io::Error)>,
+}
+
+impl ParsedFiles {
+    /// The errors of every file, along with the path of the file they were found in.
+    pub fn errors(&self) -> impl Iterator<Item = (&Path, &ParseError)> {
+        self.files.iter().flat_map(|file| {
+            file.errors
+                .iter()
+                .map(move |error| (file.path.as_path(), error))
+        })
+    }
+
+    /// Whether every file was read and parsed without errors.
+    pub fn is_valid(&self) -> bool {
+        self.unreadable.is_empty() && self.files.iter().all(|file| file.errors.is_empty())
+    }
+}
+
+/// Parse many files, in parallel when the `rayon` feature is enabled.
+///
+/// The names found in the files, such as identifiers and variables, are interned, so
+/// that the programs share the storage of identical names rather than each holding a
+/// copy of them.
+pub fn parse_files<P: AsRef<Path> + Sync>(paths: &[P], config: ParserConfig) -> ParsedFiles {
+    parse_files_with_interner(paths, config, &Interner::new())
+}
+
+/// Parse many files like [`parse_files`], interning names in the given interner, so
+/// that they can also be shared with files parsed before or after.
+pub fn parse_files_with_interner<P: AsRef<Path> + Sync>(
+    paths: &[P],
+    config: ParserConfig,
+    interner: &Interner,
+) -> ParsedFiles {
+    #[cfg(feature = "rayon")]
+    let results: Vec<io::Result<ParsedFile>> = {
+        use rayon::prelude::*;
+
+        paths
+            .par_iter()
+            .map(|path| parse_file(path.as_ref(), config, interner))
+            .collect()
+    };
+
+    #[cfg(not(feature = "rayon"))]
+    let results: Vec<io::Result<ParsedFile>> = paths
+        .iter()
+        .map(|path| parse_file(path.as_ref(), config, interner))
+        .collect();
+
+    let mut parsed = ParsedFiles::default();
+    for (path, result) in paths.iter().zip(results) {
+        match result {
+            Ok(file) => parsed.files.push(file),
+            Err(error) => parsed.unreadable.push((path.as_ref().to_path_buf(), error)),
+        }
+    }
+
+    parsed
+}
+
+fn parse_file(path: &Path, config: ParserConfig, interner: &Interner) -> io::Result<ParsedFile> {
+    let input = std::fs::read(path)?;
+
+    let (program, errors) = match Lexer::with_config(config.lexer).tokenize(&input) {
+        Ok(mut tokens) => {
+            interner.intern_tokens(&mut tokens);
+
+            match construct_with_config(&tokens, config) {
+                Ok(program) => (program, vec![]),
+                Err(stack) => (stack.partial, stack.errors),
+            }
+        }
+        Err(error) => (Program::new(), vec![error.into()]),
+    };
+
+    Ok(ParsedFile {
+        path: path.to_path_buf(),
+        program,
+        errors,
+    })
+}
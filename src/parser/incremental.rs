@@ -0,0 +1,513 @@
+use std::ops::Range;
+
+use crate::lexer::error::SyntaxResult;
+use crate::lexer::stream::TokenStream;
+use crate::lexer::token::Span;
+use crate::lexer::token::TokenKind;
+use crate::lexer::Lexer;
+use crate::node::Node;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::namespaces::UnbracedNamespace;
+use crate::parser::ast::relocate::Relocate;
+use crate::parser::ast::relocate::Shift;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::parser::error::ParseErrorStack;
+use crate::parser::error::ParseResult;
+use crate::parser::recoverable;
+use crate::parser::state::NamespaceType;
+use crate::parser::state::Scope;
+use crate::parser::state::State;
+use crate::parser::top_level_statement;
+use crate::parser::ParserConfig;
+
+/// A change to a source: the bytes in `range` are replaced by `replacement`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TextEdit {
+    pub range: Range<usize>,
+    pub replacement: Vec<u8>,
+}
+
+impl TextEdit {
+    pub fn new(range: Range<usize>, replacement: impl Into<Vec<u8>>) -> Self {
+        Self {
+            range,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// The smallest edit that turns `before` into `after`, replacing everything between
+    /// their common prefix and their common suffix.
+    pub fn between(before: &[u8], after: &[u8]) -> Self {
+        let prefix = before.iter().zip(after).take_while(|(a, b)| a == b).count();
+        let suffix = before[prefix..]
+            .iter()
+            .rev()
+            .zip(after[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        Self::new(
+            prefix..before.len() - suffix,
+            &after[prefix..after.len() - suffix],
+        )
+    }
+
+    /// The source once edited, keeping the range within the bounds of the source.
+    pub fn apply(&self, source: &[u8]) -> Vec<u8> {
+        let end = self.range.end.min(source.len());
+        let start = self.range.start.min(end);
+
+        let mut edited = Vec::with_capacity(source.len() - (end - start) + self.replacement.len());
+        edited.extend_from_slice(&source[..start]);
+        edited.extend_from_slice(&self.replacement);
+        edited.extend_from_slice(&source[end..]);
+
+        edited
+    }
+}
+
+/// Parse a source again after an edit, given the program previously parsed from it.
+///
+/// Only the statements around the edit are tokenized and parsed again: the statements
+/// before them are kept as they are, and the ones after them are kept with their spans
+/// moved by the edit, which gives the same program as parsing the edited source from
+/// scratch. Statements are kept at the top level, and inside of an unbraced namespace,
+/// which is where most of the code of a file is.
+///
+/// When the edit may change how the code after it is read, such as by opening a comment
+/// or a string, or when the statements around it don't parse on their own, the rest of
+/// the source is parsed again instead.
+///
+/// The previous program must have been parsed from the source without errors.
+pub fn reparse(
+    previous: &[Statement],
+    source: &[u8],
+    edit: &TextEdit,
+    config: ParserConfig,
+) -> Result<Program, ParseErrorStack> {
+    let input = edit.apply(source);
+    let lexer = Lexer::with_config(config.lexer);
+
+    let end = edit.range.end.min(source.len());
+    let start = edit.range.start.min(end);
+    let shift = Shift {
+        position: input.len() as isize - source.len() as isize,
+        line: lines(&edit.replacement) as isize - lines(&source[start..end]) as isize,
+    };
+
+    let container = Container::new(previous, source, start);
+    let (kept, from) = resume(&lexer, &container, source, start);
+
+    if let Some(program) = reparse_between(
+        &lexer, &container, kept, from, source, &input, end, shift, config,
+    ) {
+        return Ok(program);
+    }
+
+    let tokens = match lexer
+        .tokens_from(&input, from)
+        .collect::<SyntaxResult<Vec<_>>>()
+    {
+        Ok(tokens) => tokens,
+        Err(error) => {
+            return Err(ParseErrorStack {
+                errors: vec![error.into()],
+                partial: Vec::new(),
+            })
+        }
+    };
+
+    let mut program = container.before().to_vec();
+    let namespace = match container.namespace {
+        Some(namespace) => Some((namespace, container.statements[..kept].to_vec())),
+        None => {
+            program.extend_from_slice(&container.statements[..kept]);
+            None
+        }
+    };
+
+    let mut stream = TokenStream::new(&tokens);
+    let mut state = state(&mut stream, &program, namespace.is_some(), config);
+
+    let result = rest(&mut state, &mut program, namespace);
+
+    let mut errors = state.errors;
+    if let Err(error) = result {
+        errors.push(error);
+    }
+
+    if !errors.is_empty() {
+        return Err(ParseErrorStack {
+            errors,
+            partial: program,
+        });
+    }
+
+    Ok(program)
+}
+
+// The statements the edit is among: those of the unbraced namespace it is in, if any, or
+// else those of the program.
+struct Container<'a> {
+    program: &'a [Statement],
+    // the index of the namespace in the program.
+    index: usize,
+    namespace: Option<&'a UnbracedNamespace>,
+    statements: &'a [Statement],
+    // where the statements start, unless they start the source.
+    start: Option<Span>,
+}
+
+impl<'a> Container<'a> {
+    fn new(program: &'a [Statement], source: &[u8], offset: usize) -> Self {
+        let index = ending_before(program, offset);
+
+        if let Some(Statement::Namespace(NamespaceStatement::Unbraced(namespace))) =
+            program.get(index)
+        {
+            if namespace.end.end_position < offset {
+                return Self {
+                    program,
+                    index,
+                    namespace: Some(namespace),
+                    statements: &namespace.statements,
+                    start: Some(location(source, namespace.end.end_position)),
+                };
+            }
+        }
+
+        Self {
+            program,
+            index: 0,
+            namespace: None,
+            statements: program,
+            start: None,
+        }
+    }
+
+    // The top-level statements before the namespace.
+    fn before(&self) -> &'a [Statement] {
+        match self.namespace {
+            Some(_) => &self.program[..self.index],
+            None => &[],
+        }
+    }
+
+    // The top-level statements after the namespace.
+    fn after(&self) -> &'a [Statement] {
+        match self.namespace {
+            Some(_) => &self.program[self.index + 1..],
+            None => &[],
+        }
+    }
+}
+
+// The state to parse the statements following the given ones with.
+fn state<'a>(
+    stream: &'a mut TokenStream<'a>,
+    program: &[Statement],
+    in_namespace: bool,
+    config: ParserConfig,
+) -> State<'a> {
+    let mut state = State::new(stream);
+    state.max_depth = config.max_depth;
+    state.max_attributes = config.max_attributes;
+    state.intern_literals = config.intern_literals;
+    state.has_statements = in_namespace || program.iter().any(is_statement);
+    state.namespace_type = match in_namespace {
+        true => Some(NamespaceType::Unbraced),
+        false => program.iter().rev().find_map(|statement| match statement {
+            Statement::Namespace(NamespaceStatement::Braced(_)) => Some(NamespaceType::Braced),
+            Statement::Namespace(NamespaceStatement::Unbraced(_)) => Some(NamespaceType::Unbraced),
+            _ => None,
+        }),
+    };
+
+    state
+}
+
+// Parse the statements from the kept ones up to the first statement after the edit that
+// can be kept too, which is moved along with the ones after it. `None` if there is no
+// such statement, or if the code in between doesn't tokenize or parse on its own the
+// same way it would along with the rest.
+#[allow(clippy::too_many_arguments)]
+fn reparse_between(
+    lexer: &Lexer,
+    container: &Container,
+    kept: usize,
+    from: Option<Span>,
+    source: &[u8],
+    input: &[u8],
+    edit_end: usize,
+    shift: Shift,
+    config: ParserConfig,
+) -> Option<Program> {
+    let statements = container.statements;
+    let (next, end) = next_kept(lexer, statements, source, kept, edit_end)?;
+    let tokens = lexer.tokenize_between(input, from, end.checked_add_signed(shift.position)?)?;
+
+    let mut program = container.before().to_vec();
+    if container.namespace.is_none() {
+        program.extend_from_slice(&statements[..kept]);
+    }
+
+    let mut stream = TokenStream::new(&tokens);
+    let mut state = state(&mut stream, &program, container.namespace.is_some(), config);
+    let had_statements = state.has_statements || statements[kept..next].iter().any(is_statement);
+
+    let parsed = between(&mut state, container.namespace).ok()?;
+    if !state.errors.is_empty() || !state.attributes.is_empty() || !state.stream.is_eof() {
+        return None;
+    }
+
+    // a namespace or a `declare` after the edit is only valid in the same context.
+    let namespaces = statements[kept..next]
+        .iter()
+        .chain(&parsed)
+        .any(|statement| matches!(statement, Statement::Namespace(_)));
+    if namespaces || state.has_statements != had_statements {
+        return None;
+    }
+
+    let mut moved = statements[next..].to_vec();
+    moved.relocate(shift);
+
+    let Some(namespace) = container.namespace else {
+        program.extend(parsed);
+        program.extend(moved);
+
+        return Some(program);
+    };
+
+    let mut inner = statements[..kept].to_vec();
+    inner.extend(parsed);
+    inner.extend(moved);
+
+    program.push(Statement::Namespace(NamespaceStatement::Unbraced(
+        UnbracedNamespace {
+            start: namespace.start,
+            name: namespace.name.clone(),
+            end: namespace.end,
+            statements: inner,
+        },
+    )));
+
+    let mut after = container.after().to_vec();
+    after.relocate(shift);
+    program.extend(after);
+
+    Some(program)
+}
+
+// The statements in between the kept ones, which are the statements of the namespace if
+// the edit is in one.
+fn between(state: &mut State, namespace: Option<&UnbracedNamespace>) -> ParseResult<Program> {
+    let mut statements = vec![];
+
+    match namespace {
+        Some(namespace) => {
+            state.enter(Scope::Namespace(namespace.name.clone()));
+            let result = namespace_statements(state, &mut statements);
+            state.exit();
+
+            result?;
+        }
+        None => {
+            while !state.stream.is_eof() {
+                statements.push(top_level_statement(state)?);
+            }
+        }
+    }
+
+    Ok(statements)
+}
+
+// The number of statements to keep, and where to tokenize the source again from: the end
+// of the last statement kept, or the start of the statements if none are kept.
+//
+// The last statement before the edit may continue after it, e.g. an `if` statement
+// followed by a new `else`, so it is parsed again. The span of a statement doesn't
+// cover all of its tokens, such as the `use` keyword or the semicolon ending some
+// statements, so parsing only resumes at a statement which is preceded by nothing
+// but comments since the end of the one before.
+fn resume(
+    lexer: &Lexer,
+    container: &Container,
+    source: &[u8],
+    offset: usize,
+) -> (usize, Option<Span>) {
+    let statements = container.statements;
+
+    let mut index = ending_before(statements, offset);
+    while index > 1 {
+        index -= 1;
+
+        if let Some((end, _)) = follows(lexer, source, &statements[index - 1], &statements[index]) {
+            return (index, Some(location(source, end)));
+        }
+    }
+
+    (0, container.start)
+}
+
+// The index of the first statement after the edit that can be kept, and the end of the
+// statement before it, which is where the code parsed again ends.
+//
+// The statement must start on a line after the edit, along with the comments before it,
+// so that only the lines of its spans change.
+fn next_kept(
+    lexer: &Lexer,
+    statements: &[Statement],
+    source: &[u8],
+    kept: usize,
+    edit_end: usize,
+) -> Option<(usize, usize)> {
+    let mut index = ending_before(statements, edit_end).max(kept);
+    while index + 1 < statements.len() {
+        index += 1;
+
+        let Some((end, comments)) =
+            follows(lexer, source, &statements[index - 1], &statements[index])
+        else {
+            continue;
+        };
+
+        // statements without a span may come before the edit.
+        if end >= edit_end && source[edit_end..comments].contains(&b'\n') {
+            return Some((index, end));
+        }
+    }
+
+    None
+}
+
+// The end of the first statement and the start of the comments before the second one,
+// if the second one follows it with nothing but comments in between, and the code is
+// scripting at the end of the first one, such that it can be tokenized again from there.
+fn follows(
+    lexer: &Lexer,
+    source: &[u8],
+    statement: &Statement,
+    next: &Statement,
+) -> Option<(usize, usize)> {
+    let span = statement.span()?;
+
+    let scripting = match statement {
+        Statement::FullOpeningTag(_) | Statement::ShortOpeningTag(_) => true,
+        Statement::InlineHtml(_) | Statement::HaltCompiler(_) => false,
+        _ => matches!(source[..span.end_position].last(), Some(b';' | b'}')),
+    };
+
+    if !scripting {
+        return None;
+    }
+
+    let comments = comments_between(lexer, source, span.end_position, next.span()?.position)?;
+
+    Some((span.end_position, comments))
+}
+
+// Where the comments between the given positions start, or the end position if there are
+// none. `None` if anything else is in between, or if the end position isn't the start of
+// a token.
+fn comments_between(lexer: &Lexer, source: &[u8], start: usize, end: usize) -> Option<usize> {
+    let mut first = None;
+    for token in lexer.tokens_from(source, Some(location(source, start))) {
+        let token = token.ok()?;
+        if token.span.position >= end {
+            return (token.span.position == end).then_some(first.unwrap_or(end));
+        }
+
+        if !is_comment(&token.kind) {
+            return None;
+        }
+
+        first.get_or_insert(token.span.position);
+    }
+
+    None
+}
+
+// The span of the position in the source.
+fn location(source: &[u8], position: usize) -> Span {
+    let before = &source[..position];
+    let line_start = before
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |newline| newline + 1);
+
+    Span::new(lines(before) + 1, position - line_start + 1, position)
+}
+
+fn lines(text: &[u8]) -> usize {
+    text.iter().filter(|byte| **byte == b'\n').count()
+}
+
+// The number of statements that end before the offset.
+fn ending_before(statements: &[Statement], offset: usize) -> usize {
+    statements.partition_point(|statement| {
+        statement
+            .span()
+            .is_some_and(|span| span.end_position < offset)
+    })
+}
+
+// Whether the statement comes after the ones that must be first, such as a `declare`.
+fn is_statement(statement: &Statement) -> bool {
+    !matches!(
+        statement,
+        Statement::FullOpeningTag(_)
+            | Statement::ShortOpeningTag(_)
+            | Statement::Shebang(_)
+            | Statement::Declare(_)
+    )
+}
+
+fn is_comment(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::SingleLineComment
+            | TokenKind::MultiLineComment
+            | TokenKind::HashMarkComment
+            | TokenKind::DocumentComment
+    )
+}
+
+fn rest(
+    state: &mut State,
+    program: &mut Program,
+    namespace: Option<(&UnbracedNamespace, Program)>,
+) -> ParseResult<()> {
+    if let Some((namespace, mut statements)) = namespace {
+        state.enter(Scope::Namespace(namespace.name.clone()));
+        let result = namespace_statements(state, &mut statements);
+        state.exit();
+
+        program.push(Statement::Namespace(NamespaceStatement::Unbraced(
+            UnbracedNamespace {
+                start: namespace.start,
+                name: namespace.name.clone(),
+                end: namespace.end,
+                statements,
+            },
+        )));
+
+        result?;
+    }
+
+    while !state.stream.is_eof() {
+        program.push(top_level_statement(state)?);
+    }
+
+    Ok(())
+}
+
+// The statements of an unbraced namespace, which end at the next namespace.
+fn namespace_statements(state: &mut State, statements: &mut Program) -> ParseResult<()> {
+    while state.stream.current().kind != TokenKind::Namespace && !state.stream.is_eof() {
+        statements.push(recoverable(state, top_level_statement)?);
+    }
+
+    Ok(())
+}
@@ -10,7 +10,7 @@ use crate::parser::state::State;
 
 pub fn list_expression(state: &mut State) -> ParseResult<Expression> {
     Ok(Expression::List(ListExpression {
-        list: utils::skip(state, TokenKind::List)?,
+        list: Some(utils::skip(state, TokenKind::List)?),
         start: utils::skip_left_parenthesis(state)?,
         items: {
             let mut items = Vec::new();
@@ -34,13 +34,13 @@ pub fn list_expression(state: &mut State) -> ParseResult<Expression> {
                     state.record(error::illegal_spread_operator_usage(current.span));
                 }
 
-                if current.kind == TokenKind::Ampersand {
+                let ampersand = if current.kind == TokenKind::Ampersand {
                     state.stream.next();
 
-                    state.record(error::cannot_assign_reference_to_non_referencable_value(
-                        current.span,
-                    ));
-                }
+                    Some(current.span)
+                } else {
+                    None
+                };
 
                 let mut value = expressions::create(state)?;
                 current = state.stream.current();
@@ -49,6 +49,13 @@ pub fn list_expression(state: &mut State) -> ParseResult<Expression> {
                         state.record(error::mixing_keyed_and_unkeyed_list_entries(current.span));
                     }
 
+                    // keys can not be taken by reference, only the values they point to.
+                    if let Some(ampersand) = ampersand {
+                        state.record(error::cannot_assign_reference_to_non_referencable_value(
+                            ampersand,
+                        ));
+                    }
+
                     let double_arrow = current.span;
 
                     state.stream.next();
@@ -60,23 +67,32 @@ pub fn list_expression(state: &mut State) -> ParseResult<Expression> {
                         state.record(error::illegal_spread_operator_usage(current.span));
                     }
 
-                    if current.kind == TokenKind::Ampersand {
+                    let ampersand = if current.kind == TokenKind::Ampersand {
                         state.stream.next();
 
-                        state.record(error::cannot_assign_reference_to_non_referencable_value(
-                            current.span,
-                        ));
-                    }
+                        Some(current.span)
+                    } else {
+                        None
+                    };
 
                     let mut key = expressions::create(state)?;
                     current = state.stream.current();
 
                     std::mem::swap(&mut key, &mut value);
 
-                    items.push(ListEntry::KeyValue {
-                        key,
-                        double_arrow,
-                        value,
+                    let value = destructuring_target(state, value);
+                    items.push(match ampersand {
+                        Some(ampersand) => ListEntry::ReferencedKeyValue {
+                            key,
+                            double_arrow,
+                            ampersand,
+                            value,
+                        },
+                        None => ListEntry::KeyValue {
+                            key,
+                            double_arrow,
+                            value,
+                        },
                     });
 
                     has_at_least_one_key = true;
@@ -85,7 +101,11 @@ pub fn list_expression(state: &mut State) -> ParseResult<Expression> {
                         state.record(error::mixing_keyed_and_unkeyed_list_entries(current.span));
                     }
 
-                    items.push(ListEntry::Value { value });
+                    let value = destructuring_target(state, value);
+                    items.push(match ampersand {
+                        Some(ampersand) => ListEntry::ReferencedValue { ampersand, value },
+                        None => ListEntry::Value { value },
+                    });
                 }
 
                 if current.kind == TokenKind::Comma {
@@ -106,6 +126,112 @@ pub fn list_expression(state: &mut State) -> ParseResult<Expression> {
     }))
 }
 
+/// Convert a short array that is assigned to, such as `[$a, [$b, $c]] = $x`, into
+/// the list it actually is. Other expressions are returned as-is.
+pub fn destructuring_target(state: &mut State, expression: Expression) -> Expression {
+    let array = match expression {
+        Expression::ShortArray(array) => array,
+        expression => return expression,
+    };
+
+    let mut items = Vec::new();
+    let mut has_at_least_one_key = false;
+    for (index, item) in array.items.inner.into_iter().enumerate() {
+        let entry = match item {
+            ArrayItem::Skipped => ListEntry::Skipped,
+            ArrayItem::Value { value } => ListEntry::Value { value },
+            ArrayItem::KeyValue {
+                key,
+                double_arrow,
+                value,
+            } => ListEntry::KeyValue {
+                key,
+                double_arrow,
+                value,
+            },
+            ArrayItem::SpreadValue { ellipsis, value } => {
+                state.record(error::illegal_spread_operator_usage(ellipsis));
+
+                ListEntry::Value { value }
+            }
+            ArrayItem::ReferencedValue { ampersand, value } => {
+                ListEntry::ReferencedValue { ampersand, value }
+            }
+            ArrayItem::ReferencedKeyValue {
+                key,
+                double_arrow,
+                ampersand,
+                value,
+            } => ListEntry::ReferencedKeyValue {
+                key,
+                double_arrow,
+                ampersand,
+                value,
+            },
+        };
+
+        match &entry {
+            ListEntry::KeyValue { double_arrow, .. }
+            | ListEntry::ReferencedKeyValue { double_arrow, .. } => {
+                if !has_at_least_one_key && !items.is_empty() {
+                    state.record(error::mixing_keyed_and_unkeyed_list_entries(*double_arrow));
+                }
+                has_at_least_one_key = true;
+            }
+            ListEntry::Value { .. } | ListEntry::ReferencedValue { .. } => {
+                if has_at_least_one_key {
+                    // like `list()`, report the token that follows the entry.
+                    let span = array.items.commas.get(index).copied();
+                    state.record(error::mixing_keyed_and_unkeyed_list_entries(
+                        span.unwrap_or(array.end),
+                    ));
+                }
+            }
+            ListEntry::Skipped => {}
+        }
+
+        let entry = match entry {
+            ListEntry::Skipped => ListEntry::Skipped,
+            ListEntry::Value { value } => ListEntry::Value {
+                value: destructuring_target(state, value),
+            },
+            ListEntry::ReferencedValue { ampersand, value } => ListEntry::ReferencedValue {
+                ampersand,
+                value: destructuring_target(state, value),
+            },
+            ListEntry::KeyValue {
+                key,
+                double_arrow,
+                value,
+            } => ListEntry::KeyValue {
+                key,
+                double_arrow,
+                value: destructuring_target(state, value),
+            },
+            ListEntry::ReferencedKeyValue {
+                key,
+                double_arrow,
+                ampersand,
+                value,
+            } => ListEntry::ReferencedKeyValue {
+                key,
+                double_arrow,
+                ampersand,
+                value: destructuring_target(state, value),
+            },
+        };
+
+        items.push(entry);
+    }
+
+    Expression::List(ListExpression {
+        list: None,
+        start: array.start,
+        items,
+        end: array.end,
+    })
+}
+
 pub fn short_array_expression(state: &mut State) -> ParseResult<Expression> {
     Ok(Expression::ShortArray(ShortArrayExpression {
         start: utils::skip(state, TokenKind::LeftBracket)?,
@@ -1,6 +1,7 @@
 use crate::lexer::token::TokenKind;
 use crate::parser::ast::attributes::Attribute;
 use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::internal::identifiers;
 use crate::parser::internal::parameters;
@@ -12,49 +13,63 @@ pub fn gather_attributes(state: &mut State) -> ParseResult<bool> {
         return Ok(false);
     }
 
-    let start = state.stream.current().span;
-    let mut members = vec![];
+    // looping, rather than recursing, for each of multiple attribute brackets after each other.
+    let mut count: usize = state
+        .attributes
+        .iter()
+        .map(|group| group.members.len())
+        .sum();
+    while state.stream.current().kind == TokenKind::Attribute {
+        let start = state.stream.current().span;
+        let mut members = vec![];
 
-    state.stream.next();
+        state.stream.next();
 
-    loop {
-        let start = state.stream.current().span;
-        let name = identifiers::full_type_name_including_self(state)?;
-        let arguments = if state.stream.current().kind == TokenKind::LeftParen {
-            Some(parameters::argument_list(state)?)
-        } else {
-            None
-        };
-        let end = state.stream.current().span;
-
-        members.push(Attribute {
-            start,
-            name,
-            arguments,
-            end,
-        });
+        loop {
+            let start = state.stream.current().span;
+            if let Some(max_attributes) = state.max_attributes {
+                if count >= max_attributes {
+                    return Err(error::maximum_attributes_exceeded(start, max_attributes));
+                }
+            }
+
+            let name = identifiers::full_type_name_including_self(state)?;
+            let arguments = if state.stream.current().kind == TokenKind::LeftParen {
+                Some(parameters::argument_list(state)?)
+            } else {
+                None
+            };
+            let end = state.stream.current().span;
+
+            members.push(Attribute {
+                start,
+                name,
+                arguments,
+                end,
+            });
+            count += 1;
 
-        if state.stream.current().kind == TokenKind::Comma {
-            state.stream.next();
+            if state.stream.current().kind == TokenKind::Comma {
+                state.stream.next();
 
-            if state.stream.current().kind == TokenKind::RightBracket {
-                break;
+                if state.stream.current().kind == TokenKind::RightBracket {
+                    break;
+                }
+
+                continue;
             }
 
-            continue;
+            break;
         }
 
-        break;
-    }
-
-    let end = utils::skip_right_bracket(state)?;
+        let end = utils::skip_right_bracket(state)?;
 
-    state.attribute(AttributeGroup {
-        start,
-        members,
-        end,
-    });
+        state.attribute(AttributeGroup {
+            start,
+            members,
+            end,
+        });
+    }
 
-    // recursive, looking for multiple attribute brackets after each other.
-    gather_attributes(state).map(|_| true)
+    Ok(true)
 }
@@ -3,6 +3,7 @@ use crate::lexer::token::TokenKind;
 use crate::parser;
 use crate::parser::ast::BlockStatement;
 use crate::parser::ast::Statement;
+use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::internal::utils;
 use crate::parser::state::State;
@@ -26,7 +27,7 @@ pub fn multiple_statements_until(
     let mut statements = Vec::new();
 
     let mut current = state.stream.current();
-    while &current.kind != until {
+    while &current.kind != until && !state.recovering_at_eof() {
         if let TokenKind::OpenTag(OpenTagKind::Full) = current.kind {
             state.stream.next();
 
@@ -34,7 +35,15 @@ pub fn multiple_statements_until(
             continue;
         }
 
-        statements.push(parser::statement(state)?);
+        statements.push(
+            parser::recoverable(state, parser::statement).map_err(|error| {
+                error::unexpected_end_of_alternative_block(
+                    error,
+                    current,
+                    std::slice::from_ref(until),
+                )
+            })?,
+        );
         current = state.stream.current();
     }
 
@@ -48,7 +57,7 @@ pub fn multiple_statements_until_any(
     let mut statements = Vec::new();
 
     let mut current = state.stream.current();
-    while !until.contains(&current.kind) {
+    while !until.contains(&current.kind) && !state.recovering_at_eof() {
         if let TokenKind::OpenTag(OpenTagKind::Full) = current.kind {
             state.stream.next();
 
@@ -56,7 +65,11 @@ pub fn multiple_statements_until_any(
             continue;
         }
 
-        statements.push(parser::statement(state)?);
+        statements.push(
+            parser::recoverable(state, parser::statement).map_err(|error| {
+                error::unexpected_end_of_alternative_block(error, current, until)
+            })?,
+        );
         current = state.stream.current();
     }
 
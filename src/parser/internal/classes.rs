@@ -27,6 +27,7 @@ use crate::parser::internal::utils;
 use crate::parser::state::State;
 
 pub fn parse(state: &mut State) -> ParseResult<Statement> {
+    let comments = state.stream.comments();
     let attributes = state.get_attributes();
 
     let modifiers = modifiers::class_group(modifiers::collect(state)?)?;
@@ -71,7 +72,8 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
         left_brace: utils::skip_left_brace(state)?,
         members: {
             let mut members = Vec::new();
-            while state.stream.current().kind != TokenKind::RightBrace {
+            while state.stream.current().kind != TokenKind::RightBrace && !state.recovering_at_eof()
+            {
                 members.push(member(state, has_abstract, &name)?);
             }
 
@@ -81,6 +83,7 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
     };
 
     Ok(Statement::Class(ClassStatement {
+        comments,
         class,
         name,
         modifiers,
@@ -101,6 +104,7 @@ pub fn parse_anonymous(state: &mut State, span: Option<Span>) -> ParseResult<Exp
 
     let attributes = state.get_attributes();
 
+    let modifiers = modifiers::anonymous_class_group(modifiers::collect(state)?)?;
     let class = utils::skip(state, TokenKind::Class)?;
 
     let arguments = if state.stream.current().kind == TokenKind::LeftParen {
@@ -143,7 +147,8 @@ pub fn parse_anonymous(state: &mut State, span: Option<Span>) -> ParseResult<Exp
         left_brace: utils::skip_left_brace(state)?,
         members: {
             let mut members = Vec::new();
-            while state.stream.current().kind != TokenKind::RightBrace {
+            while state.stream.current().kind != TokenKind::RightBrace && !state.recovering_at_eof()
+            {
                 members.push(anonymous_member(state)?);
             }
             members
@@ -157,6 +162,7 @@ pub fn parse_anonymous(state: &mut State, span: Option<Span>) -> ParseResult<Exp
             extends,
             implements,
             attributes,
+            modifiers,
             body,
         })),
         new,
@@ -169,6 +175,8 @@ fn member(
     has_abstract: bool,
     name: &SimpleIdentifier,
 ) -> ParseResult<ClassMember> {
+    state.stream.discard_stale_comments();
+
     let has_attributes = attributes::gather_attributes(state)?;
 
     if !has_attributes && state.stream.current().kind == TokenKind::Use {
@@ -194,33 +202,31 @@ fn member(
         )?;
 
         return match method {
-            Method::Abstract(method) => {
-                if has_abstract {
-                    Ok(ClassMember::AbstractMethod(method))
-                } else {
+            Method::Abstract(method) => match method.modifiers.get_abstract() {
+                Some(modifier) if !has_abstract => {
                     Err(error::abstract_method_on_a_non_abstract_class(
                         state,
                         name,
                         &method.name,
-                        method.modifiers.get_abstract().unwrap().span(),
+                        modifier.span(),
                         method.semicolon,
                     ))
                 }
-            }
+                _ => Ok(ClassMember::AbstractMethod(method)),
+            },
             Method::Concrete(method) => Ok(ClassMember::ConcreteMethod(method)),
-            Method::AbstractConstructor(ctor) => {
-                if has_abstract {
-                    Ok(ClassMember::AbstractConstructor(ctor))
-                } else {
+            Method::AbstractConstructor(ctor) => match ctor.modifiers.get_abstract() {
+                Some(modifier) if !has_abstract => {
                     Err(error::abstract_method_on_a_non_abstract_class(
                         state,
                         name,
                         &ctor.name,
-                        ctor.modifiers.get_abstract().unwrap().span(),
+                        modifier.span(),
                         ctor.semicolon,
                     ))
                 }
-            }
+                _ => Ok(ClassMember::AbstractConstructor(ctor)),
+            },
             Method::ConcreteConstructor(ctor) => Ok(ClassMember::ConcreteConstructor(ctor)),
         };
     }
@@ -232,6 +238,8 @@ fn member(
 }
 
 fn anonymous_member(state: &mut State) -> ParseResult<AnonymousClassMember> {
+    state.stream.discard_stale_comments();
+
     let has_attributes = attributes::gather_attributes(state)?;
 
     if !has_attributes && state.stream.current().kind == TokenKind::Use {
@@ -264,7 +272,11 @@ fn anonymous_member(state: &mut State) -> ParseResult<AnonymousClassMember> {
             Method::ConcreteConstructor(ctor) => {
                 return Ok(AnonymousClassMember::ConcreteConstructor(ctor));
             }
-            Method::Abstract(_) | Method::AbstractConstructor(_) => unreachable!(),
+            Method::Abstract(_) | Method::AbstractConstructor(_) => {
+                return Err(error::reached_unpredictable_state(
+                    state.stream.current().span,
+                ));
+            }
         }
     }
 
@@ -1,5 +1,6 @@
 use crate::expected_token_err;
 
+use crate::lexer::token::OpenTagKind;
 use crate::lexer::token::TokenKind;
 use crate::parser;
 use crate::parser::ast::control_flow::IfStatement;
@@ -8,12 +9,14 @@ use crate::parser::ast::control_flow::IfStatementElse;
 use crate::parser::ast::control_flow::IfStatementElseBlock;
 use crate::parser::ast::control_flow::IfStatementElseIf;
 use crate::parser::ast::control_flow::IfStatementElseIfBlock;
+use crate::parser::ast::utils::CommaSeparated;
 use crate::parser::ast::Case;
 use crate::parser::ast::DefaultMatchArm;
 use crate::parser::ast::Expression;
 use crate::parser::ast::MatchArm;
 use crate::parser::ast::Statement;
 use crate::parser::ast::SwitchStatement;
+use crate::parser::ast::SwitchStatementBody;
 use crate::parser::ast::{Block, MatchExpression};
 use crate::parser::error;
 use crate::parser::error::ParseResult;
@@ -34,6 +37,7 @@ pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
 
     let mut default: Option<Box<DefaultMatchArm>> = None;
     let mut arms = Vec::new();
+    let mut commas = Vec::new();
     while state.stream.current().kind != TokenKind::RightBrace {
         let current = state.stream.current();
         if current.kind == TokenKind::Default {
@@ -47,9 +51,14 @@ pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
             state.stream.next();
 
             // match conditions can have an extra comma at the end, including `default`.
-            if state.stream.current().kind == TokenKind::Comma {
+            let comma = if state.stream.current().kind == TokenKind::Comma {
+                let comma = state.stream.current().span;
                 state.stream.next();
-            }
+
+                Some(comma)
+            } else {
+                None
+            };
 
             let arrow = utils::skip_double_arrow(state)?;
 
@@ -57,22 +66,27 @@ pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
 
             default = Some(Box::new(DefaultMatchArm {
                 keyword: current.span,
+                comma,
                 double_arrow: arrow,
                 body,
             }));
         } else {
-            let mut conditions = Vec::new();
+            let mut conditions = CommaSeparated {
+                inner: Vec::new(),
+                commas: Vec::new(),
+            };
             while state.stream.current().kind != TokenKind::DoubleArrow {
-                conditions.push(expressions::create(state)?);
+                conditions.inner.push(expressions::create(state)?);
 
                 if state.stream.current().kind == TokenKind::Comma {
+                    conditions.commas.push(state.stream.current().span);
                     state.stream.next();
                 } else {
                     break;
                 }
             }
 
-            if conditions.is_empty() {
+            if conditions.inner.is_empty() {
                 break;
             }
 
@@ -88,6 +102,7 @@ pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
         }
 
         if state.stream.current().kind == TokenKind::Comma {
+            commas.push(state.stream.current().span);
             state.stream.next();
         } else {
             break;
@@ -104,6 +119,7 @@ pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
         left_brace,
         default,
         arms,
+        commas,
         right_brace,
     }))
 }
@@ -114,23 +130,43 @@ pub fn switch_statement(state: &mut State) -> ParseResult<Statement> {
     let (left_parenthesis, condition, right_parenthesis) =
         utils::parenthesized(state, &expressions::create)?;
 
-    let end_token = if state.stream.current().kind == TokenKind::Colon {
-        utils::skip_colon(state)?;
-        TokenKind::EndSwitch
+    let (opening, end_token) = if state.stream.current().kind == TokenKind::Colon {
+        (utils::skip_colon(state)?, TokenKind::EndSwitch)
     } else {
-        utils::skip_left_brace(state)?;
-        TokenKind::RightBrace
+        (utils::skip_left_brace(state)?, TokenKind::RightBrace)
     };
 
+    // the case list may start with a `;`, or with `?>` when the cases are placed in
+    // separate `<?php` blocks. only the newline following `?>` may appear in between.
+    match state.stream.current().kind {
+        TokenKind::SemiColon => state.stream.next(),
+        TokenKind::CloseTag => {
+            let mut tokens = 1;
+            let newline = state.stream.peek();
+            if newline.kind == TokenKind::InlineHtml
+                && (newline.value == b"\n" || newline.value == b"\r\n")
+            {
+                tokens += 1;
+            }
+
+            if state.stream.lookahead(tokens - 1).kind == TokenKind::OpenTag(OpenTagKind::Full) {
+                for _ in 0..=tokens {
+                    state.stream.next();
+                }
+            }
+        }
+        _ => {}
+    }
+
     let mut cases = Vec::new();
     while state.stream.current().kind != end_token {
         match state.stream.current().kind {
             TokenKind::Case => {
-                state.stream.next();
+                let keyword = utils::skip(state, TokenKind::Case)?;
 
                 let condition = expressions::create(state)?;
 
-                utils::skip_any_of(state, &[TokenKind::Colon, TokenKind::SemiColon])?;
+                let colon = utils::skip_any_of(state, &[TokenKind::Colon, TokenKind::SemiColon])?;
 
                 let mut body = Block::new();
 
@@ -143,14 +179,16 @@ pub fn switch_statement(state: &mut State) -> ParseResult<Statement> {
                 }
 
                 cases.push(Case {
+                    keyword,
                     condition: Some(condition),
+                    colon,
                     body,
                 });
             }
             TokenKind::Default => {
-                state.stream.next();
+                let keyword = utils::skip(state, TokenKind::Default)?;
 
-                utils::skip_any_of(state, &[TokenKind::Colon, TokenKind::SemiColon])?;
+                let colon = utils::skip_any_of(state, &[TokenKind::Colon, TokenKind::SemiColon])?;
 
                 let mut body = Block::new();
 
@@ -162,7 +200,9 @@ pub fn switch_statement(state: &mut State) -> ParseResult<Statement> {
                 }
 
                 cases.push(Case {
+                    keyword,
                     condition: None,
+                    colon,
                     body,
                 });
             }
@@ -172,19 +212,27 @@ pub fn switch_statement(state: &mut State) -> ParseResult<Statement> {
         }
     }
 
-    if end_token == TokenKind::EndSwitch {
-        utils::skip(state, TokenKind::EndSwitch)?;
-        utils::skip_ending(state)?;
+    let body = if end_token == TokenKind::EndSwitch {
+        SwitchStatementBody::Block {
+            colon: opening,
+            cases,
+            endswitch: utils::skip(state, TokenKind::EndSwitch)?,
+            ending: utils::skip_ending(state)?,
+        }
     } else {
-        utils::skip_right_brace(state)?;
-    }
+        SwitchStatementBody::Braced {
+            left_brace: opening,
+            cases,
+            right_brace: utils::skip_right_brace(state)?,
+        }
+    };
 
     Ok(Statement::Switch(SwitchStatement {
         switch,
         left_parenthesis,
         condition,
         right_parenthesis,
-        cases,
+        body,
     }))
 }
 
@@ -21,12 +21,14 @@ use crate::parser::internal::functions;
 use crate::parser::internal::functions::Method;
 use crate::parser::internal::identifiers;
 use crate::parser::internal::modifiers;
+use crate::parser::internal::properties;
 use crate::parser::internal::utils;
 use crate::parser::state::State;
 
 use super::traits;
 
 pub fn parse(state: &mut State) -> ParseResult<Statement> {
+    let comments = state.stream.comments();
     let span = utils::skip(state, TokenKind::Enum)?;
 
     let name = identifiers::type_identifier(state)?;
@@ -38,7 +40,7 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
         Some(match &identifier.value[..] {
             b"string" => BackedEnumType::String(span, identifier.span),
             b"int" => BackedEnumType::Int(span, identifier.span),
-            _ => unreachable!(),
+            _ => return Err(error::reached_unpredictable_state(identifier.span)),
         })
     } else {
         None
@@ -65,7 +67,9 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
             left_brace: utils::skip_left_brace(state)?,
             members: {
                 let mut members = Vec::new();
-                while state.stream.current().kind != TokenKind::RightBrace {
+                while state.stream.current().kind != TokenKind::RightBrace
+                    && !state.recovering_at_eof()
+                {
                     if let Some(member) = backed_member(state, &name)? {
                         members.push(member);
                     }
@@ -77,6 +81,7 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
         };
 
         Ok(Statement::BackedEnum(BackedEnumStatement {
+            comments,
             r#enum: span,
             name,
             backed_type,
@@ -89,7 +94,9 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
             left_brace: utils::skip_left_brace(state)?,
             members: {
                 let mut members = Vec::new();
-                while state.stream.current().kind != TokenKind::RightBrace {
+                while state.stream.current().kind != TokenKind::RightBrace
+                    && !state.recovering_at_eof()
+                {
                     if let Some(member) = unit_member(state, &name)? {
                         members.push(member);
                     }
@@ -100,6 +107,7 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
         };
 
         Ok(Statement::UnitEnum(UnitEnumStatement {
+            comments,
             r#enum: span,
             name,
             attributes,
@@ -113,6 +121,8 @@ fn unit_member(
     state: &mut State,
     enum_name: &SimpleIdentifier,
 ) -> ParseResult<Option<UnitEnumMember>> {
+    state.stream.discard_stale_comments();
+
     let has_attributes = attributes::gather_attributes(state)?;
 
     if !has_attributes && state.stream.current().kind == TokenKind::Use {
@@ -121,8 +131,13 @@ fn unit_member(
             .map(Some);
     }
 
+    if state.stream.current().kind == TokenKind::Var {
+        return property(state, vec![], enum_name).map(|_| None);
+    }
+
     let current = state.stream.current();
     if current.kind == TokenKind::Case {
+        let comments = state.stream.comments();
         let attributes = state.get_attributes();
 
         let start = current.span;
@@ -147,6 +162,7 @@ fn unit_member(
         let end = utils::skip_semicolon(state)?;
 
         return Ok(Some(UnitEnumMember::Case(UnitEnumCase {
+            comments,
             start,
             end,
             name,
@@ -162,6 +178,10 @@ fn unit_member(
             .map(Some);
     }
 
+    if state.stream.current().kind != TokenKind::Function {
+        return property(state, modifiers, enum_name).map(|_| None);
+    }
+
     method(state, modifiers, enum_name).map(|method| method.map(UnitEnumMember::Method))
 }
 
@@ -169,6 +189,8 @@ fn backed_member(
     state: &mut State,
     enum_name: &SimpleIdentifier,
 ) -> ParseResult<Option<BackedEnumMember>> {
+    state.stream.discard_stale_comments();
+
     let has_attributes = attributes::gather_attributes(state)?;
 
     if !has_attributes && state.stream.current().kind == TokenKind::Use {
@@ -177,8 +199,13 @@ fn backed_member(
             .map(Some);
     }
 
+    if state.stream.current().kind == TokenKind::Var {
+        return property(state, vec![], enum_name).map(|_| None);
+    }
+
     let current = state.stream.current();
     if current.kind == TokenKind::Case {
+        let comments = state.stream.comments();
         let attributes = state.get_attributes();
 
         let case = current.span;
@@ -206,6 +233,7 @@ fn backed_member(
         let semicolon = utils::skip_semicolon(state)?;
 
         return Ok(Some(BackedEnumMember::Case(BackedEnumCase {
+            comments,
             attributes,
             case,
             name,
@@ -223,9 +251,40 @@ fn backed_member(
             .map(Some);
     }
 
+    if state.stream.current().kind != TokenKind::Function {
+        return property(state, modifiers, enum_name).map(|_| None);
+    }
+
     method(state, modifiers, enum_name).map(|method| method.map(BackedEnumMember::Method))
 }
 
+// Enums cannot contain properties, but we parse them anyway to report a
+// meaningful error and continue parsing the rest of the enum.
+fn property(
+    state: &mut State,
+    modifiers: Vec<(Span, TokenKind)>,
+    enum_name: &SimpleIdentifier,
+) -> ParseResult<()> {
+    let entries = if state.stream.current().kind == TokenKind::Var {
+        properties::parse_var(state, Some(enum_name))?.entries
+    } else {
+        properties::parse(
+            state,
+            Some(enum_name),
+            modifiers::property_group(modifiers)?,
+        )?
+        .entries
+    };
+
+    for entry in &entries {
+        let error = error::enum_cannot_contain_property(state, enum_name, entry.variable());
+
+        state.record(error);
+    }
+
+    Ok(())
+}
+
 fn method(
     state: &mut State,
     modifiers: Vec<(Span, TokenKind)>,
@@ -260,6 +319,8 @@ fn method(
 
             Ok(Some(method))
         }
-        Method::Abstract(_) | Method::AbstractConstructor(_) => unreachable!(),
+        Method::Abstract(_) | Method::AbstractConstructor(_) => Err(
+            error::reached_unpredictable_state(state.stream.current().span),
+        ),
     }
 }
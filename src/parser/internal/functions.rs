@@ -8,13 +8,16 @@ use crate::parser::ast::functions::ClosureUseVariable;
 use crate::parser::ast::functions::ConcreteConstructor;
 use crate::parser::ast::functions::ConcreteMethod;
 use crate::parser::ast::functions::FunctionBody;
+use crate::parser::ast::functions::FunctionParameterList;
 use crate::parser::ast::functions::FunctionStatement;
 use crate::parser::ast::functions::MethodBody;
 use crate::parser::ast::functions::ReturnType;
 use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::modifiers::MethodModifierGroup;
+use crate::parser::ast::variables::SimpleVariable;
 use crate::parser::ast::Expression;
 use crate::parser::ast::Statement;
+use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::expressions;
 use crate::parser::internal::blocks;
@@ -63,39 +66,12 @@ pub fn anonymous_function(state: &mut State) -> ParseResult<Expression> {
 
     let parameters = parameters::function_parameter_list(state)?;
 
-    let current = state.stream.current();
-    let uses = if current.kind == TokenKind::Use {
-        state.stream.next();
+    let uses = if state.stream.current().kind == TokenKind::Use {
+        let uses = closure_use(state)?;
 
-        Some(ClosureUse {
-            comments: state.stream.comments(),
-            r#use: current.span,
-            left_parenthesis: utils::skip_left_parenthesis(state)?,
-            variables: utils::comma_separated::<ClosureUseVariable>(
-                state,
-                &|state| {
-                    let use_comments = state.stream.comments();
-                    let current = state.stream.current();
-                    let use_ampersand = if current.kind == TokenKind::Ampersand {
-                        state.stream.next();
-
-                        Some(current.span)
-                    } else {
-                        None
-                    };
-
-                    let var = variables::simple_variable(state)?;
-
-                    Ok(ClosureUseVariable {
-                        comments: use_comments,
-                        variable: var,
-                        ampersand: use_ampersand,
-                    })
-                },
-                TokenKind::RightParen,
-            )?,
-            right_parenthesis: utils::skip_right_parenthesis(state)?,
-        })
+        lexical_variables(state, &parameters, &uses);
+
+        Some(uses)
     } else {
         None
     };
@@ -129,6 +105,64 @@ pub fn anonymous_function(state: &mut State) -> ParseResult<Expression> {
     }))
 }
 
+fn closure_use(state: &mut State) -> ParseResult<ClosureUse> {
+    let r#use = utils::skip(state, TokenKind::Use)?;
+
+    Ok(ClosureUse {
+        comments: state.stream.comments(),
+        r#use,
+        left_parenthesis: utils::skip_left_parenthesis(state)?,
+        variables: utils::comma_separated::<ClosureUseVariable>(
+            state,
+            &|state| {
+                let use_comments = state.stream.comments();
+                let current = state.stream.current();
+                let use_ampersand = if current.kind == TokenKind::Ampersand {
+                    state.stream.next();
+
+                    Some(current.span)
+                } else {
+                    None
+                };
+
+                let var = variables::simple_variable(state)?;
+
+                Ok(ClosureUseVariable {
+                    comments: use_comments,
+                    variable: var,
+                    ampersand: use_ampersand,
+                })
+            },
+            TokenKind::RightParen,
+        )?,
+        right_parenthesis: utils::skip_right_parenthesis(state)?,
+    })
+}
+
+// the variables of a `use` clause can't be `$this`, repeated, or named after a parameter.
+fn lexical_variables(state: &mut State, parameters: &FunctionParameterList, uses: &ClosureUse) {
+    let mut seen: Vec<&SimpleVariable> = Vec::new();
+    for variable in uses.variables.inner.iter().map(|use_| &use_.variable) {
+        if variable.name == b"$this" {
+            state.record(error::cannot_use_this_as_lexical_variable(variable));
+        } else if let Some(first) = seen.iter().find(|other| other.name == variable.name) {
+            state.record(error::duplicate_lexical_variable(first, variable));
+        } else if let Some(parameter) = parameters
+            .parameters
+            .inner
+            .iter()
+            .find(|parameter| parameter.name.name == variable.name)
+        {
+            state.record(error::cannot_use_parameter_as_lexical_variable(
+                &parameter.name,
+                variable,
+            ));
+        }
+
+        seen.push(variable);
+    }
+}
+
 pub fn arrow_function(state: &mut State) -> ParseResult<Expression> {
     let comments = state.stream.comments();
     let current = state.stream.current();
@@ -153,6 +187,18 @@ pub fn arrow_function(state: &mut State) -> ParseResult<Expression> {
 
     let attributes = state.get_attributes();
     let parameters = parameters::function_parameter_list(state)?;
+
+    // arrow functions capture the variables they use by value, `fn() use ($a) => $a`
+    // is parsed to report the clause, and it is then ignored.
+    if state.stream.current().kind == TokenKind::Use {
+        let uses = closure_use(state)?;
+
+        state.record(error::arrow_function_cannot_have_use_clause(
+            uses.r#use,
+            uses.right_parenthesis,
+        ));
+    }
+
     let return_type = if state.stream.current().kind == TokenKind::Colon {
         Some(ReturnType {
             colon: utils::skip_colon(state)?,
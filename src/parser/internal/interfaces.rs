@@ -5,6 +5,7 @@ use crate::parser::ast::interfaces::InterfaceExtends;
 use crate::parser::ast::interfaces::InterfaceMember;
 use crate::parser::ast::interfaces::InterfaceStatement;
 use crate::parser::ast::Statement;
+use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::internal::attributes;
 use crate::parser::internal::constants;
@@ -17,6 +18,7 @@ use crate::parser::internal::utils;
 use crate::parser::state::State;
 
 pub fn parse(state: &mut State) -> ParseResult<Statement> {
+    let comments = state.stream.comments();
     let span = utils::skip(state, TokenKind::Interface)?;
 
     let name = identifiers::type_identifier(state)?;
@@ -46,7 +48,8 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
         left_brace: utils::skip_left_brace(state)?,
         members: {
             let mut members = Vec::new();
-            while state.stream.current().kind != TokenKind::RightBrace {
+            while state.stream.current().kind != TokenKind::RightBrace && !state.recovering_at_eof()
+            {
                 members.push(member(state, &name)?);
             }
 
@@ -56,6 +59,7 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
     };
 
     Ok(Statement::Interface(InterfaceStatement {
+        comments,
         interface: span,
         name,
         attributes,
@@ -65,6 +69,8 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
 }
 
 fn member(state: &mut State, interface_name: &SimpleIdentifier) -> ParseResult<InterfaceMember> {
+    state.stream.discard_stale_comments();
+
     attributes::gather_attributes(state)?;
 
     let modifiers = modifiers::collect(state)?;
@@ -83,7 +89,9 @@ fn member(state: &mut State, interface_name: &SimpleIdentifier) -> ParseResult<I
         match method {
             Method::Abstract(method) => Ok(InterfaceMember::Method(method)),
             Method::AbstractConstructor(ctor) => Ok(InterfaceMember::Constructor(ctor)),
-            Method::ConcreteConstructor(_) | Method::Concrete(_) => unreachable!(),
+            Method::ConcreteConstructor(_) | Method::Concrete(_) => Err(
+                error::reached_unpredictable_state(state.stream.current().span),
+            ),
         }
     }
 }
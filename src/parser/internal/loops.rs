@@ -17,6 +17,7 @@ use crate::parser::ast::loops::WhileStatementBody;
 use crate::parser::ast::Statement;
 use crate::parser::error::ParseResult;
 use crate::parser::expressions;
+use crate::parser::internal::arrays;
 use crate::parser::internal::blocks;
 use crate::parser::internal::utils;
 use crate::parser::state::State;
@@ -56,6 +57,7 @@ pub fn foreach_statement(state: &mut State) -> ParseResult<Statement> {
                 let mut key = expressions::create(state)?;
 
                 std::mem::swap(&mut value, &mut key);
+                let value = arrays::destructuring_target(state, value);
 
                 Ok(ForeachStatementIterator::KeyAndValue {
                     expression,
@@ -70,7 +72,7 @@ pub fn foreach_statement(state: &mut State) -> ParseResult<Statement> {
                     expression,
                     r#as,
                     ampersand,
-                    value,
+                    value: arrays::destructuring_target(state, value),
                 })
             }
         })?;
@@ -54,6 +54,22 @@ pub fn class_group(input: Vec<(Span, TokenKind)>) -> ParseResult<ClassModifierGr
     Ok(ClassModifierGroup { modifiers })
 }
 
+#[inline(always)]
+pub fn anonymous_class_group(input: Vec<(Span, TokenKind)>) -> ParseResult<ClassModifierGroup> {
+    let modifiers = input
+        .iter()
+        .map(|(span, token)| match token {
+            TokenKind::Readonly => Ok(ClassModifier::Readonly(*span)),
+            _ => Err(error::modifier_cannot_be_used_for_anonymous_class(
+                token.to_string(),
+                *span,
+            )),
+        })
+        .collect::<ParseResult<Vec<ClassModifier>>>()?;
+
+    Ok(ClassModifierGroup { modifiers })
+}
+
 #[inline(always)]
 pub fn method_group(input: Vec<(Span, TokenKind)>) -> ParseResult<MethodModifierGroup> {
     let mut final_span = None;
@@ -195,7 +211,7 @@ pub fn constant_group(input: Vec<(Span, TokenKind)>) -> ParseResult<ConstantModi
                         final_span, *span,
                     ))
                 } else {
-                    Ok(ConstantModifier::Final(*span))
+                    Ok(ConstantModifier::Private(*span))
                 }
             }
             TokenKind::Final => {
@@ -240,7 +256,7 @@ pub fn interface_constant_group(
 pub fn collect(state: &mut State) -> ParseResult<Vec<(Span, TokenKind)>> {
     let mut collected: Vec<(Span, TokenKind)> = vec![];
 
-    let collectable_tokens = vec![
+    let collectable_tokens = [
         TokenKind::Private,
         TokenKind::Protected,
         TokenKind::Public,
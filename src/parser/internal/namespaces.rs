@@ -59,7 +59,7 @@ fn unbraced_namespace(
         // `namespace` token as a top level statement, this namespace scope ends.
         // otherwise we will end up with nested namespace statements.
         while state.stream.current().kind != TokenKind::Namespace && !state.stream.is_eof() {
-            statements.push(parser::top_level_statement(state)?);
+            statements.push(parser::recoverable(state, parser::top_level_statement)?);
         }
 
         statements
@@ -85,7 +85,7 @@ fn braced_namespace(
 
         let mut statements = Block::new();
         while state.stream.current().kind != TokenKind::RightBrace && !state.stream.is_eof() {
-            statements.push(parser::top_level_statement(state)?);
+            statements.push(parser::recoverable(state, parser::top_level_statement)?);
         }
 
         let end = utils::skip_right_brace(state)?;
@@ -1,4 +1,5 @@
 use crate::lexer::token::TokenKind;
+use crate::node::Node;
 use crate::parser::ast::arguments::{Argument, SingleArgument};
 use crate::parser::ast::arguments::{ArgumentList, NamedArgument, PositionalArgument};
 use crate::parser::ast::functions::ConstructorParameter;
@@ -6,6 +7,9 @@ use crate::parser::ast::functions::ConstructorParameterList;
 use crate::parser::ast::functions::FunctionParameter;
 use crate::parser::ast::functions::FunctionParameterList;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::Expression;
+use crate::parser::ast::ParenthesizedExpression;
 use crate::parser::error;
 use crate::parser::error::ParseError;
 use crate::parser::error::ParseResult;
@@ -26,6 +30,27 @@ pub fn function_parameter_list(state: &mut State) -> Result<FunctionParameterLis
         &|state| {
             attributes::gather_attributes(state)?;
 
+            // Promoted properties are only allowed in constructors with a body,
+            // see `constructor_parameter_list`.
+            loop {
+                let current = state.stream.current();
+                if !matches!(
+                    current.kind,
+                    TokenKind::Public
+                        | TokenKind::Protected
+                        | TokenKind::Private
+                        | TokenKind::Readonly
+                ) {
+                    break;
+                }
+
+                state.record(error::promoted_property_outside_constructor(
+                    current.span,
+                    &current.kind,
+                ));
+                state.stream.next();
+            }
+
             let ty = data_type::optional_data_type(state)?;
 
             let mut current = state.stream.current();
@@ -48,6 +73,12 @@ pub fn function_parameter_list(state: &mut State) -> Result<FunctionParameterLis
             // 2. Then expect a variable.
             let var = variables::simple_variable(state)?;
 
+            if let Some(ty) = &ty {
+                if ty.is_return_only() {
+                    state.record(error::return_only_type_used_in_parameter(ty, &var));
+                }
+            }
+
             let mut default = None;
             if state.stream.current().kind == TokenKind::Equals {
                 state.stream.next();
@@ -69,6 +100,8 @@ pub fn function_parameter_list(state: &mut State) -> Result<FunctionParameterLis
 
     let right_parenthesis = utils::skip_right_parenthesis(state)?;
 
+    duplicate_parameters(state, parameters.inner.iter().map(|p| &p.name));
+
     Ok(FunctionParameterList {
         comments,
         left_parenthesis,
@@ -107,13 +140,13 @@ pub fn constructor_parameter_list(
             let (ellipsis, var) = if matches!(current.kind, TokenKind::Ellipsis) {
                 state.stream.next();
                 let var = variables::simple_variable(state)?;
-                if !modifiers.is_empty() {
+                if let Some(modifier) = modifiers.modifiers.first() {
                     return Err(error::variadic_promoted_property(
                         state,
                         class,
                         &var,
                         current.span,
-                        modifiers.modifiers.first().unwrap(),
+                        modifier,
                     ));
                 }
 
@@ -124,10 +157,16 @@ pub fn constructor_parameter_list(
 
             // 2. Then expect a variable.
 
-            if !modifiers.is_empty() {
+            if modifiers.is_empty() {
+                if let Some(ty) = &ty {
+                    if ty.is_return_only() {
+                        state.record(error::return_only_type_used_in_parameter(ty, &var));
+                    }
+                }
+            } else {
                 match &ty {
                     Some(ty) => {
-                        if ty.includes_callable() || ty.is_bottom() {
+                        if ty.includes_callable() || ty.is_return_only() {
                             return Err(error::forbidden_type_used_in_property(
                                 state,
                                 class,
@@ -171,6 +210,8 @@ pub fn constructor_parameter_list(
 
     let right_parenthesis = utils::skip_right_parenthesis(state)?;
 
+    duplicate_parameters(state, parameters.inner.iter().map(|p| &p.name));
+
     Ok(ConstructorParameterList {
         comments,
         left_parenthesis,
@@ -179,23 +220,71 @@ pub fn constructor_parameter_list(
     })
 }
 
+fn duplicate_parameters<'a>(state: &mut State, names: impl Iterator<Item = &'a SimpleVariable>) {
+    let mut seen: Vec<&SimpleVariable> = Vec::new();
+    for name in names {
+        match seen.iter().find(|other| other.name == name.name) {
+            Some(first) => state.record(error::duplicate_parameter(first, name)),
+            None => seen.push(name),
+        }
+    }
+}
+
 pub fn argument_list(state: &mut State) -> ParseResult<ArgumentList> {
     let comments = state.stream.comments();
     let start = utils::skip_left_parenthesis(state)?;
 
     let mut arguments = Vec::new();
     let mut has_used_named_arguments = false;
+    let mut names: Vec<SimpleIdentifier> = Vec::new();
 
     while !state.stream.is_eof() && state.stream.current().kind != TokenKind::RightParen {
-        let span = state.stream.current().span;
-        let (named, argument) = argument(state)?;
-        if named {
-            has_used_named_arguments = true;
-        } else if has_used_named_arguments {
-            return Err(error::cannot_use_positional_argument_after_named_argument(
-                span,
-                state.stream.current().span,
-            ));
+        let current = state.stream.current();
+        // `foo(1, ...)`, the `...` placeholder must be the only argument,
+        // `foo(...)` is handled before reaching the argument list.
+        if current.kind == TokenKind::Ellipsis
+            && matches!(
+                state.stream.peek().kind,
+                TokenKind::Comma | TokenKind::RightParen
+            )
+        {
+            state.record(error::first_class_callable_with_arguments(current.span));
+            state.stream.next();
+
+            if state.stream.current().kind == TokenKind::Comma {
+                state.stream.next();
+                continue;
+            }
+
+            break;
+        }
+
+        let span = current.span;
+        let (_, argument) = argument(state)?;
+        match &argument {
+            Argument::Named(NamedArgument { name, .. }) => {
+                has_used_named_arguments = true;
+
+                match names.iter().find(|other| other.value == name.value) {
+                    Some(first) => state.record(error::duplicate_named_argument(first, name)),
+                    None => names.push(name.clone()),
+                }
+            }
+            Argument::Positional(PositionalArgument {
+                ellipsis: Some(_), ..
+            }) if has_used_named_arguments => {
+                state.record(error::cannot_use_argument_unpacking_after_named_argument(
+                    span,
+                    state.stream.current().span,
+                ));
+            }
+            Argument::Positional(_) if has_used_named_arguments => {
+                return Err(error::cannot_use_positional_argument_after_named_argument(
+                    span,
+                    state.stream.current().span,
+                ));
+            }
+            Argument::Positional(_) => {}
         }
 
         arguments.push(argument);
@@ -217,6 +306,66 @@ pub fn argument_list(state: &mut State) -> ParseResult<ArgumentList> {
     })
 }
 
+/// The variables given to `isset()` or `unset()`, which must be at least one, and
+/// neither named nor spread.
+pub fn variable_list(state: &mut State, construct: &str) -> ParseResult<ArgumentList> {
+    let arguments = argument_list(state)?;
+
+    if arguments.arguments.is_empty() {
+        state.record(error::argument_is_required(
+            arguments.left_parenthesis,
+            arguments.right_parenthesis,
+        ));
+    }
+
+    for argument in &arguments.arguments {
+        let value = match argument {
+            Argument::Named(NamedArgument {
+                name, colon, value, ..
+            }) => {
+                state.record(error::only_positional_arguments_are_accepted(
+                    name.span,
+                    value.span().unwrap_or(*colon),
+                ));
+
+                value
+            }
+            Argument::Positional(PositionalArgument {
+                ellipsis: Some(ellipsis),
+                ..
+            }) => {
+                state.record(error::illegal_spread_operator_usage(*ellipsis));
+
+                continue;
+            }
+            Argument::Positional(PositionalArgument { value, .. }) => value,
+        };
+
+        let mut variable = value;
+        while let Expression::Parenthesized(ParenthesizedExpression { expr, .. }) = variable {
+            variable = expr;
+        }
+
+        // the nullsafe operator can only be used to read, so `unset($a?->b)` is an error.
+        let is_variable = match variable {
+            Expression::Variable(_)
+            | Expression::ArrayIndex(_)
+            | Expression::PropertyFetch(_)
+            | Expression::StaticPropertyFetch(_) => true,
+            Expression::NullsafePropertyFetch(_) => construct == "isset",
+            _ => false,
+        };
+
+        if !is_variable {
+            if let Some(span) = value.span() {
+                state.record(error::cannot_use_construct_on_expression(construct, span));
+            }
+        }
+    }
+
+    Ok(arguments)
+}
+
 pub fn single_argument(
     state: &mut State,
     required: bool,
@@ -229,7 +378,10 @@ pub fn single_argument(
 
     while !state.stream.is_eof() && state.stream.current().kind != TokenKind::RightParen {
         let span = state.stream.current().span;
-        let (named, argument) = argument(state).ok()?;
+        let (named, argument) = match argument(state) {
+            Ok(argument) => argument,
+            Err(error) => return Some(Err(error)),
+        };
         if only_positional && named {
             return Some(Err(error::only_positional_arguments_are_accepted(
                 span,
@@ -237,6 +389,16 @@ pub fn single_argument(
             )));
         }
 
+        if let Argument::Positional(PositionalArgument {
+            ellipsis: Some(ellipsis),
+            ..
+        }) = &argument
+        {
+            if only_positional {
+                return Some(Err(error::illegal_spread_operator_usage(*ellipsis)));
+            }
+        }
+
         if first_argument.is_some() {
             return Some(Err(error::only_one_argument_is_accepted(
                 span,
@@ -260,15 +422,16 @@ pub fn single_argument(
         )));
     }
 
-    let end = utils::skip_right_parenthesis(state).ok()?;
-
-    first_argument.as_ref()?;
+    let end = match utils::skip_right_parenthesis(state) {
+        Ok(end) => end,
+        Err(error) => return Some(Err(error)),
+    };
 
     Some(Ok(SingleArgument {
         comments,
         left_parenthesis: start,
         right_parenthesis: end,
-        argument: first_argument.unwrap(),
+        argument: first_argument?,
     }))
 }
 
@@ -279,7 +442,10 @@ fn argument(state: &mut State) -> ParseResult<(bool, Argument)> {
         let name = identifiers::identifier_maybe_reserved(state)?;
         let colon = utils::skip(state, TokenKind::Colon)?;
         let ellipsis = if state.stream.current().kind == TokenKind::Ellipsis {
-            Some(utils::skip(state, TokenKind::Ellipsis)?)
+            let ellipsis = utils::skip(state, TokenKind::Ellipsis)?;
+            state.record(error::cannot_unpack_named_argument(&name, ellipsis));
+
+            Some(ellipsis)
         } else {
             None
         };
@@ -17,6 +17,7 @@ pub fn parse(
     class_name: Option<&SimpleIdentifier>,
     modifiers: PropertyModifierGroup,
 ) -> ParseResult<Property> {
+    let comments = state.stream.comments();
     let ty = data_type::optional_data_type(state)?;
 
     let mut entries = vec![];
@@ -26,13 +27,15 @@ pub fn parse(
 
         if !type_checked {
             type_checked = true;
-            if modifiers.has_readonly() && modifiers.has_static() {
+            if let (Some(r#static), Some(readonly)) =
+                (modifiers.get_static(), modifiers.get_readonly())
+            {
                 let error = error::static_property_cannot_be_readonly(
                     state,
                     class_name,
                     &variable,
-                    modifiers.get_static().unwrap().span(),
-                    modifiers.get_readonly().unwrap().span(),
+                    r#static.span(),
+                    readonly.span(),
                 );
 
                 state.record(error);
@@ -40,7 +43,7 @@ pub fn parse(
 
             match &ty {
                 Some(ty) => {
-                    if ty.includes_callable() || ty.is_bottom() {
+                    if ty.includes_callable() || ty.is_return_only() {
                         let error = error::forbidden_type_used_in_property(
                             state,
                             class_name,
@@ -102,6 +105,7 @@ pub fn parse(
     let end = utils::skip_semicolon(state)?;
 
     Ok(Property {
+        comments,
         r#type: ty,
         modifiers,
         attributes: state.get_attributes(),
@@ -114,6 +118,7 @@ pub fn parse_var(
     state: &mut State,
     class_name: Option<&SimpleIdentifier>,
 ) -> ParseResult<VariableProperty> {
+    let comments = state.stream.comments();
     utils::skip(state, TokenKind::Var)?;
 
     let ty = data_type::optional_data_type(state)?;
@@ -127,7 +132,7 @@ pub fn parse_var(
             type_checked = true;
 
             if let Some(ty) = &ty {
-                if ty.includes_callable() || ty.is_bottom() {
+                if ty.includes_callable() || ty.is_return_only() {
                     let error = error::forbidden_type_used_in_property(
                         state,
                         class_name,
@@ -165,6 +170,7 @@ pub fn parse_var(
     let end = utils::skip_semicolon(state)?;
 
     Ok(VariableProperty {
+        comments,
         r#type: ty,
         attributes: state.get_attributes(),
         entries,
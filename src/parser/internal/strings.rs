@@ -1,7 +1,7 @@
 use crate::expect_token;
 use crate::expected_token_err;
 use crate::lexer::error::SyntaxError;
-use crate::lexer::token::DocStringIndentationKind;
+use crate::lexer::token::Span;
 use crate::lexer::token::TokenKind;
 use crate::parser::ast::identifiers::Identifier;
 use crate::parser::ast::literals::Literal;
@@ -9,6 +9,7 @@ use crate::parser::ast::literals::LiteralInteger;
 use crate::parser::ast::literals::LiteralString;
 use crate::parser::ast::literals::LiteralStringKind;
 use crate::parser::ast::operators::ArithmeticOperationExpression;
+use crate::parser::ast::variables::SimpleVariable;
 use crate::parser::ast::variables::Variable;
 use crate::parser::ast::ExpressionStringPart;
 use crate::parser::ast::LiteralStringPart;
@@ -27,6 +28,8 @@ use crate::parser::state::State;
 
 #[inline(always)]
 pub fn interpolated(state: &mut State) -> ParseResult<Expression> {
+    let start = utils::skip(state, TokenKind::DoubleQuote)?;
+
     let mut parts = Vec::new();
 
     while state.stream.current().kind != TokenKind::DoubleQuote {
@@ -35,16 +38,16 @@ pub fn interpolated(state: &mut State) -> ParseResult<Expression> {
         }
     }
 
-    state.stream.next();
+    let end = utils::skip(state, TokenKind::DoubleQuote)?;
 
     Ok(Expression::InterpolatedString(
-        InterpolatedStringExpression { parts },
+        InterpolatedStringExpression { start, parts, end },
     ))
 }
 
 #[inline(always)]
 pub fn shell_exec(state: &mut State) -> ParseResult<Expression> {
-    state.stream.next();
+    let start = utils::skip(state, TokenKind::Backtick)?;
 
     let mut parts = Vec::new();
 
@@ -54,14 +57,18 @@ pub fn shell_exec(state: &mut State) -> ParseResult<Expression> {
         }
     }
 
-    state.stream.next();
+    let end = utils::skip(state, TokenKind::Backtick)?;
 
-    Ok(Expression::ShellExec(ShellExecExpression { parts }))
+    Ok(Expression::ShellExec(ShellExecExpression {
+        start,
+        parts,
+        end,
+    }))
 }
 
 #[inline(always)]
 pub fn heredoc(state: &mut State) -> ParseResult<Expression> {
-    let span = state.stream.current().span;
+    let start = state.stream.current().span;
     let label = state.stream.current().value.clone();
     state.stream.next();
 
@@ -77,165 +84,125 @@ pub fn heredoc(state: &mut State) -> ParseResult<Expression> {
         TokenKind::EndDocString(indentation_type, indentation_amount) => {
             (indentation_type.clone(), *indentation_amount)
         }
-        _ => unreachable!(),
+        _ => return expected_token_err!("the end of the string", state),
     };
 
+    let end = state.stream.current().span;
     state.stream.next();
 
-    let mut new_line = true;
-    if indentation_type != DocStringIndentationKind::None {
-        let indentation_char: u8 = indentation_type.into();
-
-        for part in parts.iter_mut() {
-            // We only need to strip and validate indentation
-            // for individual lines, so we can skip checks if
-            // we know we're not on a new line.
-            if !new_line {
-                continue;
-            }
-
+    if let Ok(indentation_char) = u8::try_from(indentation_type.clone()) {
+        // the indentation is only validated here, and left in place. only lines that
+        // start with a literal part need to be checked.
+        let mut new_line = true;
+        for part in parts.iter() {
             match part {
-                StringPart::Literal(LiteralStringPart { value: bytes }) => {
-                    // 1. If this line doesn't start with any whitespace,
-                    //    we can return an error early because we know
-                    //    the label was indented.
-                    if !bytes.starts_with(&[b' ']) && !bytes.starts_with(&[b'\t']) {
-                        return Err(SyntaxError::InvalidDocBodyIndentationLevel(
-                            indentation_amount,
-                            span,
-                        )
-                        .into());
-                    }
-
-                    // 2. If this line doesn't start with the correct
-                    //    type of whitespace, we can also return an error.
-                    if !bytes.starts_with(&[indentation_char]) {
-                        return Err(SyntaxError::InvalidDocIndentation(span).into());
-                    }
-
-                    // 3. We now know that the whitespace at the start of
-                    //    this line is correct, so we need to check that the
-                    //    amount of whitespace is correct too. In this case,
-                    //    the amount of whitespace just needs to be at least
-                    //    the same, so we can create a vector containing the
-                    //    minimum and check using `starts_with()`.
-                    let expected_whitespace_buffer = vec![indentation_char; indentation_amount];
-                    if !bytes.starts_with(&expected_whitespace_buffer) {
-                        return Err(SyntaxError::InvalidDocBodyIndentationLevel(
-                            indentation_amount,
-                            span,
-                        )
-                        .into());
+                StringPart::Literal(LiteralStringPart { value: bytes, .. }) => {
+                    for (i, line) in bytes.split(|b| *b == b'\n').enumerate() {
+                        if (i > 0 || new_line) && !line.is_empty() {
+                            check_indentation(line, indentation_char, indentation_amount, start)?;
+                        }
                     }
 
-                    // 4. All of the above checks have passed, so we know
-                    //    there are no more possible errors. Let's now
-                    //    strip the leading whitespace accordingly.
-                    *bytes = bytes
-                        .strip_prefix(&expected_whitespace_buffer[..])
-                        .unwrap()
-                        .into();
-                    new_line = bytes.ends_with(&[b'\n']);
+                    new_line = bytes.ends_with(b"\n");
                 }
-                _ => continue,
+                StringPart::Expression(_) => new_line = false,
             }
         }
     }
 
-    Ok(Expression::Heredoc(HeredocExpression { label, parts }))
+    Ok(Expression::Heredoc(HeredocExpression {
+        start,
+        label,
+        parts,
+        indentation_kind: indentation_type,
+        indentation_amount,
+        end,
+    }))
 }
 
 #[inline(always)]
 pub fn nowdoc(state: &mut State) -> ParseResult<Expression> {
-    let span = state.stream.current().span;
+    let start = state.stream.current().span;
     let label = state.stream.current().value.clone();
 
     state.stream.next();
 
-    let mut string_part = state.stream.current().value.clone();
+    let string_part = state.stream.current().value.clone();
     expect_token!([TokenKind::StringPart => ()], state, "constant string");
 
     let (indentation_type, indentation_amount) = match &state.stream.current().kind {
         TokenKind::EndDocString(indentation_type, indentation_amount) => {
             (indentation_type.clone(), *indentation_amount)
         }
-        _ => unreachable!(),
+        _ => return expected_token_err!("the end of the string", state),
     };
 
+    let end = state.stream.current().span;
     state.stream.next();
 
-    if indentation_type != DocStringIndentationKind::None {
-        let indentation_char: u8 = indentation_type.into();
-
-        let mut lines = string_part
-            .split(|b| *b == b'\n')
-            .map(|s| s.to_vec())
-            .collect::<Vec<Vec<u8>>>();
-
-        for line in lines.iter_mut() {
-            if line.is_empty() {
-                continue;
-            }
-
-            // 1. If this line doesn't start with any whitespace,
-            //    we can return an error early because we know
-            //    the label was indented.
-            if !line.starts_with(&[b' ']) && !line.starts_with(&[b'\t']) {
-                return Err(
-                    SyntaxError::InvalidDocBodyIndentationLevel(indentation_amount, span).into(),
-                );
+    if let Ok(indentation_char) = u8::try_from(indentation_type.clone()) {
+        for line in string_part.split(|b| *b == b'\n') {
+            if !line.is_empty() {
+                check_indentation(line, indentation_char, indentation_amount, start)?;
             }
-
-            // 2. If this line doesn't start with the correct
-            //    type of whitespace, we can also return an error.
-            if !line.starts_with(&[indentation_char]) {
-                return Err(SyntaxError::InvalidDocIndentation(span).into());
-            }
-
-            // 3. We now know that the whitespace at the start of
-            //    this line is correct, so we need to check that the
-            //    amount of whitespace is correct too. In this case,
-            //    the amount of whitespace just needs to be at least
-            //    the same, so we can create a vector containing the
-            //    minimum and check using `starts_with()`.
-            let expected_whitespace_buffer = vec![indentation_char; indentation_amount];
-            if !line.starts_with(&expected_whitespace_buffer) {
-                return Err(
-                    SyntaxError::InvalidDocBodyIndentationLevel(indentation_amount, span).into(),
-                );
-            }
-
-            // 4. All of the above checks have passed, so we know
-            //    there are no more possible errors. Let's now
-            //    strip the leading whitespace accordingly.
-            *line = line
-                .strip_prefix(&expected_whitespace_buffer[..])
-                .unwrap()
-                .into();
         }
-
-        let mut bytes = Vec::new();
-        for (i, line) in lines.iter().enumerate() {
-            bytes.extend(line);
-            if i < lines.len() - 1 {
-                bytes.push(b'\n');
-            }
-        }
-        string_part = bytes.into();
     }
 
     Ok(Expression::Nowdoc(NowdocExpression {
+        start,
         label,
         value: string_part,
+        indentation_kind: indentation_type,
+        indentation_amount,
+        end,
     }))
 }
 
+// Check that a line of a heredoc or nowdoc is indented at least as much as the closing
+// label, using the same whitespace.
+fn check_indentation(
+    line: &[u8],
+    indentation_char: u8,
+    indentation_amount: usize,
+    span: Span,
+) -> ParseResult<()> {
+    // 1. If this line doesn't start with any whitespace,
+    //    we can return an error early because we know
+    //    the label was indented.
+    if !line.starts_with(b" ") && !line.starts_with(b"\t") {
+        return Err(SyntaxError::InvalidDocBodyIndentationLevel(indentation_amount, span).into());
+    }
+
+    // 2. If this line doesn't start with the correct
+    //    type of whitespace, we can also return an error.
+    if !line.starts_with(&[indentation_char]) {
+        return Err(SyntaxError::InvalidDocIndentation(span).into());
+    }
+
+    // 3. We now know that the whitespace at the start of
+    //    this line is correct, so we need to check that the
+    //    amount of whitespace is correct too. In this case,
+    //    the amount of whitespace just needs to be at least
+    //    the same, so we can create a vector containing the
+    //    minimum and check using `starts_with()`.
+    let expected_whitespace_buffer = vec![indentation_char; indentation_amount];
+    if !line.starts_with(&expected_whitespace_buffer) {
+        return Err(SyntaxError::InvalidDocBodyIndentationLevel(indentation_amount, span).into());
+    }
+
+    Ok(())
+}
+
 fn part(state: &mut State) -> ParseResult<Option<StringPart>> {
     Ok(match &state.stream.current().kind {
         TokenKind::StringPart => {
-            let s = state.stream.current().value.clone();
-            let part = if s.len() > 0 {
-                Some(StringPart::Literal(LiteralStringPart { value: s }))
+            let current = state.stream.current();
+            let s = current.value.clone();
+            let part = if !s.is_empty() {
+                Some(StringPart::Literal(LiteralStringPart {
+                    span: current.span,
+                    value: s,
+                }))
             } else {
                 None
             };
@@ -243,6 +210,38 @@ fn part(state: &mut State) -> ParseResult<Option<StringPart>> {
             state.stream.next();
             part
         }
+        TokenKind::DollarLeftBrace if state.stream.peek().kind == TokenKind::Identifier => {
+            // "${name}" and "${name[expr]}" refer to `$name`, not to a variable variable.
+            state.stream.next();
+
+            let name = state.stream.current();
+            let variable = Expression::Variable(Variable::SimpleVariable(SimpleVariable {
+                span: name.span,
+                name: [b"$", &name.value[..]].concat().into(),
+            }));
+            state.stream.next();
+
+            let expression = if state.stream.current().kind == TokenKind::LeftBracket {
+                let left_bracket = utils::skip_left_bracket(state)?;
+                let index = create(state)?;
+                let right_bracket = utils::skip_right_bracket(state)?;
+
+                Expression::ArrayIndex(ArrayIndexExpression {
+                    array: Box::new(variable),
+                    left_bracket,
+                    index: Some(Box::new(index)),
+                    right_bracket,
+                })
+            } else {
+                variable
+            };
+
+            utils::skip_right_brace(state)?;
+
+            Some(StringPart::Expression(ExpressionStringPart {
+                expression: Box::new(expression),
+            }))
+        }
         TokenKind::DollarLeftBrace => {
             let variable = variables::dynamic_variable(state)?;
 
@@ -306,7 +305,7 @@ fn part(state: &mut State) -> ParseResult<Option<StringPart>> {
 
                             Expression::Literal(Literal::String(LiteralString {
                                 span: current.span,
-                                value: current.value.clone(),
+                                value: state.literal(&current.value),
                                 kind: LiteralStringKind::SingleQuoted,
                             }))
                         }
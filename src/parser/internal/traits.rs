@@ -160,6 +160,7 @@ pub fn usage(state: &mut State) -> ParseResult<TraitUsage> {
 }
 
 pub fn parse(state: &mut State) -> ParseResult<Statement> {
+    let comments = state.stream.comments();
     let span = utils::skip(state, TokenKind::Trait)?;
     let name = identifiers::type_identifier(state)?;
     let attributes = state.get_attributes();
@@ -177,6 +178,7 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
     };
 
     Ok(Statement::Trait(TraitStatement {
+        comments,
         r#trait: span,
         name,
         attributes,
@@ -185,6 +187,8 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
 }
 
 fn member(state: &mut State, class_name: &SimpleIdentifier) -> ParseResult<TraitMember> {
+    state.stream.discard_stale_comments();
+
     let has_attributes = attributes::gather_attributes(state)?;
 
     if !has_attributes && state.stream.current().kind == TokenKind::Use {
@@ -11,7 +11,7 @@ use crate::parser::internal::utils;
 use crate::parser::state::State;
 
 pub fn use_statement(state: &mut State) -> ParseResult<Statement> {
-    state.stream.next();
+    let r#use = utils::skip(state, TokenKind::Use)?;
 
     let kind = match state.stream.current().kind {
         TokenKind::Function => {
@@ -76,16 +76,18 @@ pub fn use_statement(state: &mut State) -> ParseResult<Statement> {
         }
 
         utils::skip_right_brace(state)?;
-        utils::skip_semicolon(state)?;
+        let semicolon = utils::skip_semicolon(state)?;
 
         Ok(Statement::GroupUse(GroupUseStatement {
+            r#use,
             prefix,
             kind,
             uses,
+            semicolon,
         }))
     } else {
         let mut uses = Vec::new();
-        while !state.stream.is_eof() {
+        let semicolon = loop {
             let name = identifiers::full_type_name(state)?;
             let mut alias = None;
             if state.stream.current().kind == TokenKind::As {
@@ -104,10 +106,14 @@ pub fn use_statement(state: &mut State) -> ParseResult<Statement> {
                 continue;
             }
 
-            utils::skip_semicolon(state)?;
-            break;
-        }
+            break utils::skip_semicolon(state)?;
+        };
 
-        Ok(Statement::Use(UseStatement { uses, kind }))
+        Ok(Statement::Use(UseStatement {
+            r#use,
+            kind,
+            uses,
+            semicolon,
+        }))
     }
 }
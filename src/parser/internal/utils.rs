@@ -18,7 +18,7 @@ pub fn skip_ending(state: &mut State) -> ParseResult<Ending> {
 
         Ok(Ending::Semicolon(current.span))
     } else {
-        Err(error::unexpected_token(vec![";".to_string()], current))
+        Err(error::missing_semicolon(state.stream.previous(), current))
     }
 }
 
@@ -30,7 +30,7 @@ pub fn skip_semicolon(state: &mut State) -> ParseResult<Span> {
 
         Ok(current.span)
     } else {
-        Err(error::unexpected_token(vec!["`;`".to_string()], current))
+        Err(error::missing_semicolon(state.stream.previous(), current))
     }
 }
 
@@ -79,6 +79,14 @@ pub fn skip(state: &mut State, kind: TokenKind) -> ParseResult<Span> {
         state.stream.next();
 
         Ok(end)
+    } else if kind == TokenKind::RightBrace && state.recovering_at_eof() {
+        state.record(error::unexpected_token(vec![kind.to_string()], current));
+
+        Ok(Span::new(
+            current.span.line,
+            current.span.column,
+            current.span.position,
+        ))
     } else {
         Err(error::unexpected_token(vec![kind.to_string()], current))
     }
@@ -107,7 +115,7 @@ pub fn skip_any_of(state: &mut State, kinds: &[TokenKind]) -> ParseResult<Span>
 /// and then skip the right parenthesis.
 pub fn parenthesized<T>(
     state: &mut State,
-    func: &(dyn Fn(&mut State) -> ParseResult<T>),
+    func: &dyn Fn(&mut State) -> ParseResult<T>,
 ) -> ParseResult<(Span, T, Span)> {
     let left_parenthesis = skip_left_parenthesis(state)?;
     let inner = func(state)?;
@@ -122,7 +130,7 @@ pub fn parenthesized<T>(
 /// and then skip the right brace.
 pub fn braced<T>(
     state: &mut State,
-    func: &(dyn Fn(&mut State) -> ParseResult<T>),
+    func: &dyn Fn(&mut State) -> ParseResult<T>,
 ) -> ParseResult<(Span, T, Span)> {
     let left_brace = skip_left_brace(state)?;
     let inner = func(state)?;
@@ -133,7 +141,7 @@ pub fn braced<T>(
 
 pub fn semicolon_terminated<T>(
     state: &mut State,
-    func: &(dyn Fn(&mut State) -> ParseResult<T>),
+    func: &dyn Fn(&mut State) -> ParseResult<T>,
 ) -> ParseResult<(Span, T)> {
     let inner = func(state)?;
     let semicolon = skip_semicolon(state)?;
@@ -143,7 +151,7 @@ pub fn semicolon_terminated<T>(
 /// Parse a comma-separated list of items, allowing a trailing comma.
 pub fn comma_separated<T>(
     state: &mut State,
-    func: &(dyn Fn(&mut State) -> ParseResult<T>),
+    func: &dyn Fn(&mut State) -> ParseResult<T>,
     until: TokenKind,
 ) -> ParseResult<CommaSeparated<T>> {
     let mut inner: Vec<T> = vec![];
@@ -171,7 +179,7 @@ pub fn comma_separated<T>(
 /// Parse a comma-separated list of items, not allowing trailing commas.
 pub fn comma_separated_no_trailing<T>(
     state: &mut State,
-    func: &(dyn Fn(&mut State) -> ParseResult<T>),
+    func: &dyn Fn(&mut State) -> ParseResult<T>,
     until: TokenKind,
 ) -> ParseResult<CommaSeparated<T>> {
     let mut inner: Vec<T> = vec![];
@@ -205,7 +213,7 @@ pub fn comma_separated_no_trailing<T>(
 /// Parse a comma-separated list of items, requiring at least one item, and not allowing trailing commas.
 pub fn at_least_one_comma_separated_no_trailing<T>(
     state: &mut State,
-    func: &(dyn Fn(&mut State) -> ParseResult<T>),
+    func: &dyn Fn(&mut State) -> ParseResult<T>,
 ) -> ParseResult<CommaSeparated<T>> {
     let mut inner: Vec<T> = vec![];
     let mut commas: Vec<Span> = vec![];
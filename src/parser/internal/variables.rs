@@ -46,7 +46,7 @@ pub fn dynamic_variable(state: &mut State) -> ParseResult<Variable> {
                 end,
             }))
         }
-        // todo(azjezz): figure out why the lexer does this.
+        // `$ {$name}`, with whitespace between the dollar and the brace.
         TokenKind::Dollar if state.stream.peek().kind == TokenKind::LeftBrace => {
             let start = current.span;
             state.stream.next();
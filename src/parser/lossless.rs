@@ -0,0 +1,364 @@
+use std::ops::Range;
+
+use crate::downcast::downcast;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+use crate::lexer::token::TriviaToken;
+use crate::node::Node;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::literals::LiteralFloat;
+use crate::parser::ast::literals::LiteralInteger;
+use crate::parser::ast::literals::LiteralString;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::with_cached_spans;
+use crate::parser::ast::Program;
+use crate::parser::incremental::TextEdit;
+use crate::printer::tokens_to_source;
+
+/// A program together with everything the AST leaves out: the whitespace and comments
+/// between tokens and the original text of every token, so that the source can be
+/// reproduced byte for byte, created by [`parse_lossless`](crate::parser::parse_lossless).
+///
+/// The whitespace and comments around a node are its trivia. Trailing trivia runs up to
+/// and including the end of the line the node ends on, and leading trivia is everything
+/// before the node that is not the trailing trivia of the previous node, which is how
+/// comments on their own line end up attached to the node below them.
+///
+/// The program can be changed before it is printed again with [`LosslessProgram::print`]:
+/// nodes removed from it are left out along with their trivia, and identifiers, variables
+/// and literals given a new value are printed with it, while everything else is printed
+/// exactly as it was.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LosslessProgram {
+    pub program: Program,
+    pub tokens: Vec<TriviaToken>,
+    source: Vec<u8>,
+    // the owner of each of the tokens as the program was parsed, see `owners`.
+    parsed: Vec<Option<Owner>>,
+}
+
+/// The whitespace and comments surrounding a node.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Trivia {
+    pub leading: ByteString,
+    pub trailing: ByteString,
+}
+
+impl LosslessProgram {
+    pub fn new(program: Program, tokens: Vec<TriviaToken>) -> Self {
+        let source = tokens_to_source(&tokens);
+        let parsed = owners(&program, &tokens);
+
+        Self {
+            program,
+            tokens,
+            source,
+            parsed,
+        }
+    }
+
+    /// Print the program, which gives back the source it was parsed from byte for byte
+    /// unless the program was changed since.
+    ///
+    /// Tokens that are no longer part of the program are removed along with the trivia
+    /// of the node they formed, and tokens whose value changed, such as the name of a
+    /// renamed function, are printed with the new value in place of the old one, keeping
+    /// the quotes of a string or the `$` of a variable. New nodes have no source to print,
+    /// and are left out.
+    ///
+    /// ```
+    /// use php_parser_rs::parse_lossless;
+    ///
+    /// let mut lossless = parse_lossless("<?php\n// one\n$a = 1;\n$b = 2; // two\n").unwrap();
+    /// lossless.program.remove(2);
+    ///
+    /// assert_eq!(lossless.print(), b"<?php\n// one\n$a = 1;\n");
+    /// ```
+    pub fn print(&self) -> Vec<u8> {
+        let current = owners(&self.program, &self.tokens);
+
+        let mut edits = vec![];
+        // the first and last of a run of removed tokens, which are removed together.
+        let mut removed: Option<(Span, Span)> = None;
+        for (token, (parsed, current)) in self.tokens.iter().zip(self.parsed.iter().zip(current)) {
+            let span = token.token.span;
+            let Some(parsed) = parsed else {
+                continue;
+            };
+
+            match current {
+                Some(current) if current.is(parsed) => {
+                    if let (Some(value), Some(parsed)) = (&current.value, &parsed.value) {
+                        if value != parsed {
+                            let text = &self.source[span.position..span.end_position];
+                            edits.push(TextEdit::new(
+                                span.position..span.end_position,
+                                replace_value(text, parsed, value),
+                            ));
+                        }
+                    }
+                }
+                _ => {
+                    removed = Some(match removed {
+                        Some((first, _)) => (first, span),
+                        None => (span, span),
+                    });
+                    continue;
+                }
+            }
+
+            if let Some((first, last)) = removed.take() {
+                edits.push(self.removal(first, last));
+            }
+        }
+
+        if let Some((first, last)) = removed {
+            edits.push(self.removal(first, last));
+        }
+
+        let mut output = Vec::with_capacity(self.source.len());
+        let mut cursor = 0;
+        for edit in edits {
+            output.extend_from_slice(&self.source[cursor..edit.range.start]);
+            output.extend_from_slice(&edit.replacement);
+            cursor = edit.range.end;
+        }
+        output.extend_from_slice(&self.source[cursor..]);
+
+        output
+    }
+
+    // Remove the tokens from the first to the last given, along with their trivia.
+    fn removal(&self, first: Span, last: Span) -> TextEdit {
+        let (start, end) = self.bounds(first.join(last));
+
+        TextEdit::new(start..end, "")
+    }
+
+    /// The original text of the node, without its trivia, or `None` if the node contains
+    /// no tokens.
+    pub fn text(&self, node: &dyn Node) -> Option<ByteString> {
+        let span = node.span()?;
+
+        Some(self.source[span.position..span.end_position].into())
+    }
+
+    /// The whitespace and comments surrounding the node, or `None` if the node contains
+    /// no tokens.
+    pub fn trivia(&self, node: &dyn Node) -> Option<Trivia> {
+        let span = node.span()?;
+        let (start, end) = self.bounds(span);
+
+        Some(Trivia {
+            leading: self.source[start..span.position].into(),
+            trailing: self.source[span.end_position..end].into(),
+        })
+    }
+
+    /// The tokens the node covers, with the whitespace before each of them, see
+    /// [`token_range`].
+    pub fn tokens_of(&self, node: &dyn Node) -> Option<&[TriviaToken]> {
+        let span = node.span()?;
+
+        Some(
+            &self.tokens[range(&self.tokens, span.position..span.end_position, |token| {
+                &token.token
+            })],
+        )
+    }
+
+    /// The original text of the node along with its trivia.
+    pub fn print_node(&self, node: &dyn Node) -> Option<ByteString> {
+        let span = node.span()?;
+        let (start, end) = self.bounds(span);
+
+        Some(self.source[start..end].into())
+    }
+
+    // The start of the leading trivia and the end of the trailing trivia of the span.
+    fn bounds(&self, span: Span) -> (usize, usize) {
+        let first = self
+            .tokens
+            .partition_point(|token| token.token.span.position < span.position);
+        let start = match self.tokens[..first]
+            .iter()
+            .rposition(|token| !is_trivia(&token.token.kind))
+        {
+            Some(previous) => self
+                .trailing_end(self.tokens[previous].token.span.end_position)
+                .min(span.position),
+            None => 0,
+        };
+
+        (start, self.trailing_end(span.end_position))
+    }
+
+    // The end of the trivia following the given position that belongs to the token before
+    // it: the whitespace and comments up to the end of its line, or up to the next token.
+    fn trailing_end(&self, position: usize) -> usize {
+        let next = self
+            .tokens
+            .partition_point(|token| token.token.span.position < position);
+
+        let mut cursor = position;
+        for token in &self.tokens[next..] {
+            let start = if token.token.kind == TokenKind::Eof {
+                self.source.len()
+            } else {
+                token.token.span.position
+            };
+
+            if let Some(newline) = self.source[cursor..start].iter().position(|b| *b == b'\n') {
+                return cursor + newline + 1;
+            }
+
+            if !is_trivia(&token.token.kind) {
+                return start;
+            }
+
+            cursor = token.token.span.end_position;
+        }
+
+        self.source.len()
+    }
+}
+
+/// The indices of the first and past the last of the tokens the node covers, or `None`
+/// if the node contains no tokens.
+///
+/// Comments are tokens too, so the range of a node preceded by a docblock or other
+/// comments it holds on to, such as a function, starts with them.
+///
+/// ```
+/// use php_parser_rs::parser::lossless::token_range;
+/// use php_parser_rs::parser::parse_with_tokens;
+///
+/// let (program, tokens) = parse_with_tokens("<?php echo $a + 1;").unwrap();
+/// let range = token_range(&tokens, &program[1]).unwrap();
+///
+/// assert_eq!(range, 1..6);
+/// assert_eq!(tokens[range.start].value, b"echo");
+/// ```
+pub fn token_range(tokens: &[Token], node: &dyn Node) -> Option<Range<usize>> {
+    let span = node.span()?;
+
+    Some(range(tokens, span.position..span.end_position, |token| {
+        token
+    }))
+}
+
+fn range<T>(tokens: &[T], bytes: Range<usize>, token: impl Fn(&T) -> &Token) -> Range<usize> {
+    let start = tokens.partition_point(|t| token(t).span.position < bytes.start);
+    let end = start + tokens[start..].partition_point(|t| token(t).span.position < bytes.end);
+
+    start..end
+}
+
+// The innermost node covering a token, along with the value of the token if it is an
+// identifier, variable or literal.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Owner {
+    kind: &'static str,
+    span: Span,
+    value: Option<ByteString>,
+}
+
+impl Owner {
+    // Whether both are the same node, which keeps its kind and at least one of its ends
+    // when the nodes within it are changed.
+    fn is(&self, other: &Owner) -> bool {
+        self.kind == other.kind
+            && (self.span.position == other.span.position
+                || self.span.end_position == other.span.end_position)
+    }
+}
+
+// The owner of each of the tokens, or `None` for tokens outside of every node. A node owns
+// the tokens within its span that are not within the span of one of its children, so the
+// tokens of a node removed from the program are owned by another node, or none at all.
+//
+// The nodes are visited in order from a stack of their own rather than recursively, as
+// chains of operators, calls, and fetches can be nested without limit.
+fn owners(program: &Program, tokens: &[TriviaToken]) -> Vec<Option<Owner>> {
+    with_cached_spans(|| visit(program, tokens))
+}
+
+fn visit(program: &Program, tokens: &[TriviaToken]) -> Vec<Option<Owner>> {
+    let mut owners = vec![None; tokens.len()];
+    let mut nodes: Vec<&dyn Node> = program
+        .iter()
+        .rev()
+        .map(|statement| statement as &dyn Node)
+        .collect();
+
+    while let Some(node) = nodes.pop() {
+        let children = node.children_ref();
+
+        if let Some(span) = node.span() {
+            let value = if let Some(identifier) = downcast::<SimpleIdentifier>(node) {
+                Some(identifier.value.clone())
+            } else if let Some(variable) = downcast::<SimpleVariable>(node) {
+                Some(variable.name.clone())
+            } else if let Some(literal) = downcast::<LiteralString>(node) {
+                Some(literal.value.clone())
+            } else if let Some(literal) = downcast::<LiteralInteger>(node) {
+                Some(literal.value.clone())
+            } else {
+                downcast::<LiteralFloat>(node).map(|literal| literal.value.clone())
+            };
+
+            let owner = Owner {
+                kind: node.kind(),
+                span,
+                value,
+            };
+
+            let mut inner: Vec<Span> = children.iter().filter_map(|child| child.span()).collect();
+            inner.sort_by_key(|span| span.position);
+
+            let mut cursor = span.position;
+            for (start, end) in inner
+                .iter()
+                .map(|child| (child.position, child.end_position))
+                .chain([(span.end_position, span.end_position)])
+            {
+                let gap = range(tokens, cursor..start, |token| &token.token);
+                for owned in &mut owners[gap] {
+                    *owned = Some(owner.clone());
+                }
+
+                cursor = cursor.max(end);
+            }
+        }
+
+        nodes.extend(children.into_iter().rev());
+    }
+
+    owners
+}
+
+// The text of a token with its value replaced, which keeps whatever surrounds the value
+// in the text, such as quotes.
+fn replace_value(text: &[u8], parsed: &[u8], value: &[u8]) -> Vec<u8> {
+    let Some(offset) = text
+        .windows(parsed.len().max(1))
+        .position(|window| window == parsed)
+        .filter(|_| !parsed.is_empty())
+    else {
+        return value.to_vec();
+    };
+
+    [&text[..offset], value, &text[offset + parsed.len()..]].concat()
+}
+
+fn is_trivia(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::SingleLineComment
+            | TokenKind::HashMarkComment
+            | TokenKind::MultiLineComment
+            | TokenKind::DocumentComment
+    )
+}
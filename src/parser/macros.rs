@@ -79,7 +79,7 @@ macro_rules! expect_literal {
                 $crate::parser::ast::literals::Literal::String(
                     $crate::parser::ast::literals::LiteralString {
                         span: current.span,
-                        value: current.value.clone(),
+                        value: $state.literal(&current.value),
                         kind: if matches!(current.kind, TokenKind::LiteralSingleQuotedString) {
                             $crate::parser::ast::literals::LiteralStringKind::SingleQuoted
                         } else {
@@ -1,14 +1,25 @@
+// every parser function returns a `ParseError` by value.
+#![allow(clippy::result_large_err)]
+
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+
 use crate::expect_literal;
 use crate::lexer::token::OpenTagKind;
+use crate::lexer::token::Span;
 use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
 use crate::lexer::Lexer;
+use crate::lexer::LexerConfig;
 use crate::parser::ast::declares::DeclareBody;
 use crate::parser::ast::declares::DeclareEntry;
 use crate::parser::ast::declares::DeclareEntryGroup;
 use crate::parser::ast::declares::DeclareStatement;
+use crate::parser::ast::literals::IntegerValue;
+use crate::parser::ast::literals::Literal;
 use crate::parser::ast::variables::Variable;
-use crate::parser::ast::{Program, Statement, StaticVar};
+use crate::parser::ast::{Expression, Program, Statement, StaticVar};
+use crate::parser::error::ParseError;
 use crate::parser::error::ParseErrorStack;
 use crate::parser::error::ParseResult;
 use crate::parser::internal::attributes;
@@ -28,6 +39,7 @@ use crate::parser::internal::try_block;
 use crate::parser::internal::uses;
 use crate::parser::internal::utils;
 use crate::parser::internal::variables;
+use crate::parser::lossless::LosslessProgram;
 use crate::parser::state::State;
 
 pub use crate::lexer::stream::TokenStream;
@@ -41,20 +53,122 @@ use self::ast::GlobalStatement;
 use self::ast::HaltCompilerStatement;
 use self::ast::InlineHtmlStatement;
 use self::ast::ReturnStatement;
+use self::ast::ShebangStatement;
 use self::ast::ShortOpeningTagStatement;
 use self::ast::StaticStatement;
 use self::internal::precedences::Precedence;
 
 pub mod ast;
+pub mod bench;
 pub mod error;
+pub mod files;
+pub mod incremental;
+pub mod lossless;
 
 mod expressions;
 mod internal;
 mod macros;
 mod state;
 
+/// The maximum number of nested statements and expressions accepted by default.
+///
+/// Each level of nesting takes stack space, which is grown on the heap when the native
+/// stack runs low, so the limit bounds the memory spent on pathological input, such as
+/// a hundred thousand nested parentheses, and keeps the AST shallow enough to be dropped
+/// and walked recursively, while leaving room for any code written by hand or generated,
+/// such as deeply nested configuration arrays. Chains of operators, calls and fetches,
+/// such as long concatenations, are parsed in a loop and don't count as nesting.
+pub const DEFAULT_MAX_DEPTH: usize = 2048;
+
+/// Options that control how a program is parsed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ParserConfig {
+    /// The maximum number of nested statements and expressions, after which parsing
+    /// fails with an error.
+    ///
+    /// Defaults to [`DEFAULT_MAX_DEPTH`]. A lower limit is useful when the AST is
+    /// consumed by other recursive code, while `None` removes the limit, letting
+    /// deeply nested input use an unbounded amount of memory.
+    pub max_depth: Option<usize>,
+
+    /// The maximum number of attributes on a single declaration, counting every
+    /// attribute of every `#[...]` group, after which parsing fails with an error.
+    ///
+    /// Attributes are gathered in linear time, so this only bounds the size of the
+    /// resulting AST for untrusted input. There is no limit by default.
+    pub max_attributes: Option<usize>,
+
+    /// The length in bytes of the longest string literal whose storage is shared with
+    /// identical literals, or `None` to not share any.
+    ///
+    /// Files such as configuration arrays repeat the same keys and values many times,
+    /// which then take up memory only once. Long literals are rarely repeated, so they
+    /// are left out to keep the lookups cheap. Disabled by default.
+    pub intern_literals: Option<usize>,
+
+    /// The configuration used to tokenize the input, when parsing source code.
+    pub lexer: LexerConfig,
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: Some(DEFAULT_MAX_DEPTH),
+            max_attributes: None,
+            intern_literals: None,
+            lexer: LexerConfig::default(),
+        }
+    }
+}
+
+/// The JSON Schema of a [`Program`] serialized with serde, which is what the `ast` of the
+/// `--json` output of the binary follows.
+///
+/// Structs are objects with one snake_case property per field, named as in Rust. Most
+/// enums are objects with a `type` property holding the name of the variant, and its
+/// contents either alongside it or under a `value` property. A few enums that only
+/// describe a token, such as the kind of a string literal, are the name of the variant
+/// as a string.
+pub fn schema() -> RootSchema {
+    schema_for!(Program)
+}
+
 pub fn parse<B: ?Sized + AsRef<[u8]>>(input: &B) -> Result<Program, ParseErrorStack> {
-    let lexer = Lexer::new();
+    parse_with_config(input, ParserConfig::default())
+}
+
+pub fn parse_with_config<B: ?Sized + AsRef<[u8]>>(
+    input: &B,
+    config: ParserConfig,
+) -> Result<Program, ParseErrorStack> {
+    let lexer = Lexer::with_config(config.lexer);
+    let tokens = match lexer.tokenize(input) {
+        Ok(tokens) => tokens,
+        Err(error) => {
+            return Err(ParseErrorStack {
+                errors: vec![error.into()],
+                partial: Vec::new(),
+            })
+        }
+    };
+
+    construct_with_config(&tokens, config)
+}
+
+/// Parse the input, returning the tokens it was read as along with the program, so that
+/// nodes can be mapped back to the tokens they cover with
+/// [`token_range`](lossless::token_range).
+pub fn parse_with_tokens<B: ?Sized + AsRef<[u8]>>(
+    input: &B,
+) -> Result<(Program, Vec<Token>), ParseErrorStack> {
+    parse_with_tokens_and_config(input, ParserConfig::default())
+}
+
+pub fn parse_with_tokens_and_config<B: ?Sized + AsRef<[u8]>>(
+    input: &B,
+    config: ParserConfig,
+) -> Result<(Program, Vec<Token>), ParseErrorStack> {
+    let lexer = Lexer::with_config(config.lexer);
     let tokens = match lexer.tokenize(input) {
         Ok(tokens) => tokens,
         Err(error) => {
@@ -65,12 +179,58 @@ pub fn parse<B: ?Sized + AsRef<[u8]>>(input: &B) -> Result<Program, ParseErrorSt
         }
     };
 
-    construct(&tokens)
+    let program = construct_with_config(&tokens, config)?;
+
+    Ok((program, tokens))
+}
+
+/// Parse the input, keeping the whitespace, comments and original text of every token
+/// alongside the AST, so that the source can be printed back byte for byte.
+pub fn parse_lossless<B: ?Sized + AsRef<[u8]>>(
+    input: &B,
+) -> Result<LosslessProgram, ParseErrorStack> {
+    parse_lossless_with_config(input, ParserConfig::default())
+}
+
+pub fn parse_lossless_with_config<B: ?Sized + AsRef<[u8]>>(
+    input: &B,
+    config: ParserConfig,
+) -> Result<LosslessProgram, ParseErrorStack> {
+    let lexer = Lexer::with_config(config.lexer);
+    let tokens = match lexer.tokenize_with_trivia(input) {
+        Ok(tokens) => tokens,
+        Err(error) => {
+            return Err(ParseErrorStack {
+                errors: vec![error.into()],
+                partial: Vec::new(),
+            })
+        }
+    };
+
+    let program = construct_with_config(
+        &tokens
+            .iter()
+            .map(|token| token.token.clone())
+            .collect::<Vec<Token>>(),
+        config,
+    )?;
+
+    Ok(LosslessProgram::new(program, tokens))
 }
 
 pub fn construct(tokens: &[Token]) -> Result<Program, ParseErrorStack> {
+    construct_with_config(tokens, ParserConfig::default())
+}
+
+pub fn construct_with_config(
+    tokens: &[Token],
+    config: ParserConfig,
+) -> Result<Program, ParseErrorStack> {
     let mut stream = TokenStream::new(tokens);
     let mut state = State::new(&mut stream);
+    state.max_depth = config.max_depth;
+    state.max_attributes = config.max_attributes;
+    state.intern_literals = config.intern_literals;
 
     let mut program = Program::new();
 
@@ -99,17 +259,191 @@ pub fn construct(tokens: &[Token]) -> Result<Program, ParseErrorStack> {
         });
     }
 
-    Ok(program.to_vec())
+    Ok(program)
+}
+
+/// Parse the input, recovering from syntax errors instead of stopping at the first one.
+///
+/// Statements that fail to parse are skipped up to the next statement boundary and
+/// replaced by [`Statement::Missing`](ast::Statement::Missing), expressions that are
+/// expected but absent are replaced by [`Expression::Missing`](ast::Expression::Missing),
+/// and blocks left open at the end of the file are closed implicitly. The program is
+/// returned along with every error encountered, which are empty if the input is valid.
+///
+/// Input that can not be tokenized, such as an unterminated string, ends the program
+/// early: the statements before it are still parsed.
+pub fn parse_with_recovery<B: ?Sized + AsRef<[u8]>>(input: &B) -> (Program, Vec<ParseError>) {
+    parse_with_recovery_and_config(input, ParserConfig::default())
+}
+
+pub fn parse_with_recovery_and_config<B: ?Sized + AsRef<[u8]>>(
+    input: &B,
+    config: ParserConfig,
+) -> (Program, Vec<ParseError>) {
+    let lexer = Lexer::with_config(config.lexer);
+    let mut tokens = Vec::new();
+    for token in lexer.tokens(input) {
+        let error = match token {
+            Ok(token) => {
+                tokens.push(token);
+                continue;
+            }
+            Err(error) => error,
+        };
+
+        // the tokens read before the error are parsed as if the file ended there, and
+        // the errors caused by it ending early are replaced by the error of the lexer.
+        let span = error.span();
+        tokens.push(Token {
+            kind: TokenKind::Eof,
+            span,
+            value: Default::default(),
+        });
+
+        let (program, mut errors) = construct_with_recovery(&tokens, config);
+        errors.retain(|error| error.span.position < span.position);
+        errors.push(error.into());
+
+        return (program, errors);
+    }
+
+    construct_with_recovery(&tokens, config)
+}
+
+pub fn construct_with_recovery(
+    tokens: &[Token],
+    config: ParserConfig,
+) -> (Program, Vec<ParseError>) {
+    let mut stream = TokenStream::new(tokens);
+    let mut state = State::new(&mut stream);
+    state.max_depth = config.max_depth;
+    state.max_attributes = config.max_attributes;
+    state.intern_literals = config.intern_literals;
+    state.recover = true;
+
+    let mut program = Program::new();
+    while !state.stream.is_eof() {
+        match recoverable(&mut state, top_level_statement) {
+            Ok(statement) => program.push(statement),
+            Err(error) => {
+                state.record(error);
+
+                break;
+            }
+        }
+    }
+
+    (program, state.errors)
+}
+
+/// Parse a statement with the given function.
+///
+/// When recovering from errors, a statement that fails to parse is recorded, skipped up
+/// to the next statement boundary, and replaced by a `Statement::Missing`.
+fn recoverable(
+    state: &mut State,
+    parse: fn(&mut State) -> ParseResult<Statement>,
+) -> ParseResult<Statement> {
+    if !state.recover {
+        return parse(state);
+    }
+
+    let cursor = state.stream.cursor();
+    let start = state.stream.current().span;
+
+    let error = match parse(state) {
+        Ok(statement) => return Ok(statement),
+        Err(error) => error,
+    };
+
+    // an expression that is missing has already been recorded at the same position.
+    if state.errors.last().map(|previous| previous.span) != Some(error.span) {
+        state.record(error);
+    }
+
+    state.get_attributes();
+    skip_statement(state, cursor);
+
+    let span = match state.stream.consumed_since(cursor).last() {
+        Some(last) => start.join(last.span),
+        None => Span::new(start.line, start.column, start.position),
+    };
+
+    Ok(Statement::Missing(span))
+}
+
+// Skip to the end of the statement that started at the given cursor: past the next `;`
+// or closing tag outside of any braces, or past the brace that closes the statement's
+// block. A closing brace of an enclosing block is left in place, unless nothing else
+// was consumed, to always make progress.
+fn skip_statement(state: &mut State, cursor: usize) {
+    let mut depth = 0usize;
+    for token in state.stream.consumed_since(cursor) {
+        match token.kind {
+            TokenKind::LeftBrace | TokenKind::DollarLeftBrace => depth += 1,
+            TokenKind::RightBrace => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    loop {
+        let current = state.stream.current();
+        match current.kind {
+            TokenKind::Eof => break,
+            TokenKind::LeftBrace | TokenKind::DollarLeftBrace => depth += 1,
+            TokenKind::RightBrace if depth == 0 => {
+                if state.stream.cursor() == cursor {
+                    state.stream.next();
+                }
+
+                break;
+            }
+            TokenKind::RightBrace => {
+                depth -= 1;
+                if depth == 0 {
+                    state.stream.next();
+
+                    break;
+                }
+            }
+            TokenKind::SemiColon | TokenKind::CloseTag if depth == 0 => {
+                state.stream.next();
+
+                break;
+            }
+            _ => {}
+        }
+
+        state.stream.next();
+    }
 }
 
 fn top_level_statement(state: &mut State) -> ParseResult<Statement> {
+    state.stream.discard_stale_comments();
+
+    if !matches!(
+        state.stream.current().kind,
+        TokenKind::OpenTag(OpenTagKind::Full | OpenTagKind::Short)
+            | TokenKind::Shebang
+            | TokenKind::Declare
+    ) {
+        state.has_statements = true;
+    }
+
     let statement = match &state.stream.current().kind {
         TokenKind::Namespace => namespaces::namespace(state)?,
         TokenKind::Use => uses::use_statement(state)?,
         TokenKind::Const => Statement::Constant(constants::parse(state)?),
         TokenKind::HaltCompiler => {
+            let halt = state.stream.current().span;
             state.stream.next();
 
+            let offset = state.stream.current().span.position;
+            // the lexer consumes the `();` along with the keyword.
+            let span = Span {
+                end_position: offset,
+                ..halt
+            };
             let content = if let TokenKind::InlineHtml = state.stream.current().kind.clone() {
                 let content = state.stream.current().value.clone();
                 state.stream.next();
@@ -118,7 +452,11 @@ fn top_level_statement(state: &mut State) -> ParseResult<Statement> {
                 None
             };
 
-            Statement::HaltCompiler(HaltCompilerStatement { content })
+            Statement::HaltCompiler(HaltCompilerStatement {
+                span,
+                content,
+                offset,
+            })
         }
         _ => statement(state)?,
     };
@@ -127,6 +465,12 @@ fn top_level_statement(state: &mut State) -> ParseResult<Statement> {
 }
 
 fn statement(state: &mut State) -> ParseResult<Statement> {
+    state.nested(nested_statement)
+}
+
+fn nested_statement(state: &mut State) -> ParseResult<Statement> {
+    state.stream.discard_stale_comments();
+
     let has_attributes = attributes::gather_attributes(state)?;
 
     let current = state.stream.current();
@@ -173,11 +517,41 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
         }
     } else {
         match &current.kind {
+            TokenKind::Shebang => {
+                let span = current.span;
+                let value = current.value.clone();
+                state.stream.next();
+
+                Statement::Shebang(ShebangStatement { span, value })
+            }
             TokenKind::OpenTag(OpenTagKind::Echo) => {
                 let span = current.span;
                 state.stream.next();
 
-                Statement::EchoOpeningTag(EchoOpeningTagStatement { span })
+                // `<?=` is followed by the values it echoes, as if it was `<?php echo`.
+                if matches!(
+                    state.stream.current().kind,
+                    TokenKind::OpenTag(OpenTagKind::Echo) | TokenKind::CloseTag | TokenKind::Eof
+                ) {
+                    return Ok(Statement::EchoOpeningTag(EchoOpeningTagStatement { span }));
+                }
+
+                let mut values = Vec::new();
+                loop {
+                    values.push(expressions::create(state)?);
+
+                    if state.stream.current().kind == TokenKind::Comma {
+                        state.stream.next();
+                    } else {
+                        break;
+                    }
+                }
+
+                Statement::Echo(EchoStatement {
+                    echo: span,
+                    values,
+                    ending: utils::skip_ending(state)?,
+                })
             }
             TokenKind::OpenTag(OpenTagKind::Full) => {
                 let span = current.span;
@@ -269,6 +643,36 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                     }
                 };
 
+                let strict_types = entries
+                    .entries
+                    .iter()
+                    .find(|entry| entry.key.value.eq_ignore_ascii_case(b"strict_types"));
+
+                if let Some(entry) = strict_types {
+                    // `declare(ticks=1); declare(strict_types=1);` is allowed, but
+                    // nothing else may come before it, not even inside a block.
+                    if state.has_statements || state.depth > 1 {
+                        state.record(error::strict_types_must_be_first_statement(entry.key.span));
+                    }
+
+                    if !matches!(
+                        &entry.value,
+                        Literal::Integer(integer)
+                            if matches!(integer.to_value(), IntegerValue::Integer(0 | 1))
+                    ) {
+                        state.record(error::strict_types_value_must_be_zero_or_one(&entry.value));
+                    }
+
+                    if matches!(
+                        state.stream.current().kind,
+                        TokenKind::LeftBrace | TokenKind::Colon
+                    ) {
+                        state.record(error::strict_types_cannot_use_block_mode(
+                            state.stream.current().span,
+                        ));
+                    }
+                }
+
                 let body = match state.stream.current().kind.clone() {
                     TokenKind::SemiColon => {
                         let span = utils::skip_semicolon(state)?;
@@ -335,13 +739,15 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                     }
                 }
 
-                utils::skip_semicolon(state)?;
+                let semicolon = utils::skip_semicolon(state)?;
                 Statement::Global(GlobalStatement {
                     global: span,
                     variables,
+                    semicolon,
                 })
             }
             TokenKind::Static if matches!(peek.kind, TokenKind::Variable) => {
+                let r#static = current.span;
                 state.stream.next();
 
                 let mut vars = vec![];
@@ -369,15 +775,20 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                     }
                 }
 
-                utils::skip_semicolon(state)?;
+                let semicolon = utils::skip_semicolon(state)?;
 
-                Statement::Static(StaticStatement { vars })
+                Statement::Static(StaticStatement {
+                    r#static,
+                    vars,
+                    semicolon,
+                })
             }
             TokenKind::InlineHtml => {
-                let html = state.stream.current().value.clone();
+                let span = current.span;
+                let html = current.value.clone();
                 state.stream.next();
 
-                Statement::InlineHtml(InlineHtmlStatement { html })
+                Statement::InlineHtml(InlineHtmlStatement { span, html })
             }
             TokenKind::Do => loops::do_while_statement(state)?,
             TokenKind::While => loops::while_statement(state)?,
@@ -416,6 +827,12 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                     ending: utils::skip_ending(state)?,
                 })
             }
+            TokenKind::Unset if peek.kind == TokenKind::LeftParen => {
+                Statement::Expression(ExpressionStatement {
+                    expression: Expression::Unset(expressions::unset_statement(state)?),
+                    ending: utils::skip_ending(state)?,
+                })
+            }
             TokenKind::Return => {
                 state.stream.next();
 
@@ -1,10 +1,18 @@
+use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::fmt::Display;
 
+use crate::lexer::byte_string::ByteString;
 use crate::lexer::stream::TokenStream;
 use crate::parser::ast::attributes::AttributeGroup;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::error;
 use crate::parser::error::ParseError;
+use crate::parser::error::ParseResult;
+
+// A single level of nesting can take over 100KiB of stack in debug builds.
+const STACK_RED_ZONE: usize = 512 * 1024;
+const STACK_SEGMENT_SIZE: usize = 8 * 1024 * 1024;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum NamespaceType {
@@ -25,6 +33,16 @@ pub struct State<'a> {
     pub attributes: Vec<AttributeGroup>,
     pub namespace_type: Option<NamespaceType>,
     pub errors: Vec<ParseError>,
+    pub depth: usize,
+    pub max_depth: Option<usize>,
+    pub max_attributes: Option<usize>,
+    pub recover: bool,
+    // Whether a top-level statement other than an opening tag or a `declare` was
+    // parsed, after which `strict_types` can no longer be declared.
+    pub has_statements: bool,
+    // The longest string literal to intern, if interning is enabled.
+    pub intern_literals: Option<usize>,
+    pub literals: HashSet<ByteString>,
 }
 
 impl<'a> State<'a> {
@@ -35,6 +53,13 @@ impl<'a> State<'a> {
             namespace_type: None,
             attributes: vec![],
             errors: vec![],
+            depth: 0,
+            max_depth: None,
+            max_attributes: None,
+            recover: false,
+            has_statements: false,
+            intern_literals: None,
+            literals: HashSet::new(),
         }
     }
 
@@ -50,10 +75,54 @@ impl<'a> State<'a> {
         attributes
     }
 
+    /// Parse a nested statement or expression, failing if that exceeds the maximum depth.
+    ///
+    /// The parser recurses for every level of nesting, so the stack is extended on the
+    /// heap whenever it runs low, up to the maximum depth.
+    pub fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> ParseResult<T>) -> ParseResult<T> {
+        if let Some(max_depth) = self.max_depth {
+            if self.depth >= max_depth {
+                return Err(error::maximum_depth_exceeded(
+                    self.stream.current().span,
+                    max_depth,
+                ));
+            }
+        }
+
+        self.depth += 1;
+        let result = stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || parse(self));
+        self.depth -= 1;
+
+        result
+    }
+
     pub fn record(&mut self, error: ParseError) {
         self.errors.push(error);
     }
 
+    /// The value of a string literal, sharing the storage of an identical literal seen
+    /// before when interning is enabled.
+    pub fn literal(&mut self, value: &ByteString) -> ByteString {
+        match self.intern_literals {
+            Some(length) if value.len() <= length => {
+                if let Some(interned) = self.literals.get(&value[..]) {
+                    return interned.clone();
+                }
+
+                self.literals.insert(value.clone());
+
+                value.clone()
+            }
+            _ => value.clone(),
+        }
+    }
+
+    /// Whether the end of the file was reached while recovering from errors, in which
+    /// case blocks that are still open are closed implicitly.
+    pub fn recovering_at_eof(&self) -> bool {
+        self.recover && self.stream.is_eof()
+    }
+
     /// Return the namespace type used in the current state
     ///
     /// The namespace type is retrieve from the last entered
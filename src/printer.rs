@@ -1,8 +1,12 @@
 use std::cmp;
 use std::collections::HashMap;
 
+use crate::lexer::error::SyntaxResult;
+use crate::lexer::token::OpenTagKind;
 use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
+use crate::lexer::token::TriviaToken;
+use crate::lexer::Lexer;
 
 /// Prints the tokens as a string
 ///
@@ -77,3 +81,110 @@ pub fn print(tokens: &[Token]) -> String {
 
     output.join("\n")
 }
+
+/// Reconstructs the source from tokens carrying their trivia, without going through the AST
+///
+/// # Example
+///
+/// ```
+/// use php_parser_rs::lexer::Lexer;
+/// use php_parser_rs::printer::tokens_to_source;
+///
+/// let code = "<?php\n\n$a = 'Hello, World'; // greeting\n?>\n<p>HTML</p>\n";
+///
+/// let tokens = Lexer::new().tokenize_with_trivia(code).unwrap();
+///
+/// assert_eq!(tokens_to_source(&tokens), code.as_bytes());
+/// ```
+pub fn tokens_to_source(tokens: &[TriviaToken]) -> Vec<u8> {
+    let mut source = vec![];
+    for token in tokens {
+        source.extend_from_slice(&token.leading_trivia);
+        source.extend_from_slice(&token.text);
+    }
+
+    source
+}
+
+/// Strips comments and collapses whitespace, without changing what the code does
+///
+/// Docblocks are kept, since they can be read at runtime through reflection. String
+/// contents, heredocs, inline HTML and the data following `__halt_compiler();` are
+/// left as they are.
+///
+/// # Example
+///
+/// ```
+/// use php_parser_rs::printer::minify;
+///
+/// let code = "<?php\n\n// greet\necho 'Hello, ' . $name;\n\n$a = 1 . 2;\n";
+///
+/// assert_eq!(minify(code).unwrap(), b"<?php echo'Hello, '.$name;$a=1 . 2;");
+/// ```
+pub fn minify<B: ?Sized + AsRef<[u8]>>(source: &B) -> SyntaxResult<Vec<u8>> {
+    let tokens = Lexer::new().tokenize_with_trivia(source)?;
+
+    let mut output: Vec<u8> = vec![];
+    let mut previous: Option<&TokenKind> = None;
+    // the text between the previous token written and the next one, including any
+    // comments that were left out.
+    let mut gap: Vec<u8> = vec![];
+    for token in &tokens {
+        gap.extend_from_slice(&token.leading_trivia);
+
+        if matches!(
+            token.token.kind,
+            TokenKind::SingleLineComment | TokenKind::HashMarkComment | TokenKind::MultiLineComment
+        ) {
+            gap.extend_from_slice(&token.text);
+
+            continue;
+        }
+
+        let verbatim = token.token.kind == TokenKind::InlineHtml
+            || matches!(
+                previous,
+                Some(TokenKind::CloseTag | TokenKind::StartDocString(_) | TokenKind::HaltCompiler)
+            );
+
+        if verbatim {
+            output.extend_from_slice(&gap);
+        } else if !gap.is_empty() && token.token.kind != TokenKind::Eof {
+            let separate = match (previous, output.last(), token.text.first()) {
+                // `<?php` must be followed by whitespace.
+                (Some(TokenKind::OpenTag(OpenTagKind::Full)), ..) => true,
+                (_, Some(&last), Some(&first)) => needs_separator(last, first),
+                _ => false,
+            };
+
+            if separate {
+                output.push(b' ');
+            }
+        }
+
+        output.extend_from_slice(&token.text);
+        gap.clear();
+        previous = Some(&token.token.kind);
+    }
+
+    Ok(output)
+}
+
+// Whether two tokens would be read differently if they were written without any
+// whitespace between them, e.g. `else if` as `elseif`, `$a - -1` as `$a--1`,
+// `1 . 2` as `1.2`, or `$ {$a}` as `${$a}`.
+fn needs_separator(last: u8, first: u8) -> bool {
+    fn is_word(byte: u8) -> bool {
+        byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'\\' | b'$') || byte >= 0x80
+    }
+
+    fn is_operator(byte: u8) -> bool {
+        b"+-*/%.<>=!&|^?:~@".contains(&byte)
+    }
+
+    (is_word(last) && is_word(first))
+        || (is_operator(last) && is_operator(first))
+        || (last == b'.' && first.is_ascii_digit())
+        || (last.is_ascii_digit() && first == b'.')
+        || (last == b'$' && first == b'{')
+}
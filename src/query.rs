@@ -0,0 +1,299 @@
+//! Finds nodes in the AST, either by their type with [`find_all`], or with a selector
+//! such as `ClassStatement MethodCall[method=save]` with [`query`].
+//!
+//! A selector is a list of steps separated by whitespace, each matching a node within
+//! the nodes matched by the steps before it. A step is the kind of a node, see
+//! [`Node::kind`], with or without its `Expression` or `Statement` suffix, or `*` for any
+//! node, followed by any number of conditions on the node's fields:
+//!
+//! - `[field]` matches nodes where the field is present, e.g. `Closure[ampersand]`.
+//! - `[field=value]` matches nodes where the field has the given name or value, e.g.
+//!   `FunctionCall[target=strlen]` or `SimpleVariable[name=$this]`.
+//!
+//! ```
+//! use php_parser_rs::parser;
+//! use php_parser_rs::query::query;
+//!
+//! let program = parser::parse("<?php $a->save(); $b->load(); $c->save();").unwrap();
+//! let matches = query(&program, "MethodCall[method=save]").unwrap();
+//!
+//! assert_eq!(matches.len(), 2);
+//! assert_eq!(matches[1].span.unwrap().line, 1);
+//! ```
+
+use std::fmt::Display;
+
+use crate::downcast::downcast;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::Statement;
+
+/// A node matched by a [`query`].
+pub struct Match<'a> {
+    pub node: &'a dyn Node,
+    /// The range covering the node, see [`Node::span`].
+    pub span: Option<Span>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SelectorError {
+    pub message: String,
+    /// The byte offset in the selector where the error was found.
+    pub position: usize,
+}
+
+impl Display for SelectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid selector: {} at offset {}",
+            self.message, self.position
+        )
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+/// A parsed selector, which can be reused for many queries.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Selector {
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Step {
+    // `None` for `*`.
+    kind: Option<String>,
+    conditions: Vec<Condition>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Condition {
+    field: String,
+    value: Option<String>,
+}
+
+/// Every node of the given type within the program, in the order they appear.
+///
+/// ```
+/// use php_parser_rs::parser;
+/// use php_parser_rs::parser::ast::FunctionCallExpression;
+/// use php_parser_rs::query::find_all;
+///
+/// let program = parser::parse("<?php foo(bar());").unwrap();
+///
+/// assert_eq!(find_all::<FunctionCallExpression>(&program).len(), 2);
+/// ```
+pub fn find_all<T: Node>(program: &[Statement]) -> Vec<&T> {
+    fn find<'a, T: Node>(node: &'a dyn Node, found: &mut Vec<&'a T>) {
+        if let Some(node) = downcast::<T>(node) {
+            found.push(node);
+        }
+
+        for child in node.children_ref() {
+            find(child, found);
+        }
+    }
+
+    let mut found = Vec::new();
+    for statement in program {
+        find(statement, &mut found);
+    }
+
+    found
+}
+
+/// The nodes within the program matched by the selector, in the order they appear.
+pub fn query<'a>(
+    program: &'a [Statement],
+    selector: &str,
+) -> Result<Vec<Match<'a>>, SelectorError> {
+    Ok(selector.parse::<Selector>()?.find(program))
+}
+
+impl Selector {
+    /// The nodes within the program matched by the selector, in the order they appear.
+    pub fn find<'a>(&self, program: &'a [Statement]) -> Vec<Match<'a>> {
+        let mut matches = Vec::new();
+        for statement in program {
+            self.visit(statement, 0, &mut matches);
+        }
+
+        matches
+    }
+
+    // `matched` is the number of steps matched by the ancestors of the node, only the
+    // last step is left to match once all the others are.
+    fn visit<'a>(&self, node: &'a dyn Node, matched: usize, matches: &mut Vec<Match<'a>>) {
+        let last = self.steps.len() - 1;
+
+        let mut matched = matched;
+        if self.steps[matched].matches(node) {
+            if matched == last {
+                matches.push(Match {
+                    node,
+                    span: node.span(),
+                });
+            } else {
+                matched += 1;
+            }
+        }
+
+        for child in node.children_ref() {
+            self.visit(child, matched, matches);
+        }
+    }
+}
+
+impl Step {
+    fn matches(&self, node: &dyn Node) -> bool {
+        if let Some(kind) = &self.kind {
+            let actual = node.kind();
+            let matches_kind = actual == kind
+                || matches!(
+                    actual.strip_prefix(kind.as_str()),
+                    Some("Expression" | "Statement")
+                );
+
+            if !matches_kind {
+                return false;
+            }
+        }
+
+        if self.conditions.is_empty() {
+            return true;
+        }
+
+        // conditions are only checked for nodes of the right kind, serializing the node is
+        // the only way to read its fields without knowing its type.
+        let Some(serde_json::Value::Object(fields)) = node.to_json() else {
+            return false;
+        };
+
+        self.conditions.iter().all(|condition| {
+            match (fields.get(&condition.field), &condition.value) {
+                (None | Some(serde_json::Value::Null), _) => false,
+                (Some(_), None) => true,
+                (Some(field), Some(value)) => text(field).as_deref() == Some(value.as_str()),
+            }
+        })
+    }
+}
+
+// The text of a field: a string or number as is, or the first name or value of a node,
+// such as `bar` for the identifier of `$foo->bar()`.
+fn text(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(string) => Some(string.clone()),
+        serde_json::Value::Number(number) => Some(number.to_string()),
+        serde_json::Value::Bool(bool) => Some(bool.to_string()),
+        serde_json::Value::Array(values) => values.iter().find_map(text),
+        // a span has no text, only the position of one.
+        serde_json::Value::Object(object) if object.contains_key("end_position") => None,
+        serde_json::Value::Object(object) => object
+            .get("value")
+            .or_else(|| object.get("name"))
+            .and_then(text)
+            .or_else(|| {
+                object
+                    .iter()
+                    .filter(|(key, _)| *key != "type")
+                    .find_map(|(_, value)| text(value))
+            }),
+        serde_json::Value::Null => None,
+    }
+}
+
+impl std::str::FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(selector: &str) -> Result<Self, Self::Err> {
+        let bytes = selector.as_bytes();
+        let error = |message: &str, position: usize| SelectorError {
+            message: message.to_string(),
+            position,
+        };
+
+        let mut steps = Vec::new();
+        let mut position = 0;
+
+        loop {
+            while position < bytes.len() && bytes[position].is_ascii_whitespace() {
+                position += 1;
+            }
+
+            if position == bytes.len() {
+                break;
+            }
+
+            let start = position;
+            let kind = if bytes[position] == b'*' {
+                position += 1;
+
+                None
+            } else {
+                while position < bytes.len()
+                    && (bytes[position].is_ascii_alphanumeric() || bytes[position] == b'_')
+                {
+                    position += 1;
+                }
+
+                if position == start {
+                    return Err(error("expected a node kind or `*`", position));
+                }
+
+                Some(selector[start..position].to_string())
+            };
+
+            let mut conditions = Vec::new();
+            while position < bytes.len() && bytes[position] == b'[' {
+                let end = match selector[position..].find(']') {
+                    Some(end) => position + end,
+                    None => return Err(error("unclosed `[`", position)),
+                };
+
+                let condition = &selector[position + 1..end];
+                let (field, value) = match condition.split_once('=') {
+                    Some((field, value)) => {
+                        let value = value.trim();
+                        let value = value
+                            .strip_prefix('"')
+                            .and_then(|value| value.strip_suffix('"'))
+                            .or_else(|| {
+                                value
+                                    .strip_prefix('\'')
+                                    .and_then(|value| value.strip_suffix('\''))
+                            })
+                            .unwrap_or(value);
+
+                        (field.trim(), Some(value.to_string()))
+                    }
+                    None => (condition.trim(), None),
+                };
+
+                if field.is_empty() {
+                    return Err(error("expected a field name", position + 1));
+                }
+
+                conditions.push(Condition {
+                    field: field.to_string(),
+                    value,
+                });
+
+                position = end + 1;
+            }
+
+            if position < bytes.len() && !bytes[position].is_ascii_whitespace() {
+                return Err(error("unexpected character", position));
+            }
+
+            steps.push(Step { kind, conditions });
+        }
+
+        if steps.is_empty() {
+            return Err(error("empty selector", 0));
+        }
+
+        Ok(Selector { steps })
+    }
+}
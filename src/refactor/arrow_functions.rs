@@ -0,0 +1,208 @@
+use crate::analysis::captures::Captures;
+use crate::analysis::functions::FunctionFlags;
+use crate::downcast::downcast_mut;
+use crate::lexer::token::Span;
+use crate::lint::Edit;
+use crate::lint::Fix;
+use crate::node::Node;
+use crate::parser::ast::functions::ArrowFunctionExpression;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+
+/// Convert closures whose body only returns an expression into arrow functions.
+///
+/// A closure is only converted when doing so does not change its behavior: it must
+/// import every variable its body refers to by value, must not be a generator, and
+/// must not contain comments that would be lost.
+pub fn closures_to_arrow_functions(source: &[u8], program: &mut Program) -> Vec<Fix> {
+    let mut fixes = vec![];
+    for statement in program.iter_mut() {
+        walk(
+            statement,
+            &mut |node| {
+                downcast_mut::<ClosureExpression>(node)
+                    .and_then(|closure| to_arrow_function(source, closure))
+            },
+            &mut fixes,
+        );
+    }
+
+    fixes
+}
+
+/// Convert arrow functions into closures, importing the variables they capture with `use`.
+///
+/// Arrow functions that refer to variables by a computed name, or that return `never`,
+/// are left as they are.
+pub fn arrow_functions_to_closures(source: &[u8], program: &mut Program) -> Vec<Fix> {
+    let mut fixes = vec![];
+    for statement in program.iter_mut() {
+        walk(
+            statement,
+            &mut |node| {
+                downcast_mut::<ArrowFunctionExpression>(node)
+                    .and_then(|function| to_closure(source, function))
+            },
+            &mut fixes,
+        );
+    }
+
+    fixes
+}
+
+fn walk(
+    node: &mut dyn Node,
+    convert: &mut dyn FnMut(&mut dyn Node) -> Option<Fix>,
+    fixes: &mut Vec<Fix>,
+) {
+    if let Some(fix) = convert(node) {
+        fixes.push(fix);
+    }
+
+    for child in node.children() {
+        walk(child, convert, fixes);
+    }
+}
+
+fn to_arrow_function(source: &[u8], closure: &mut ClosureExpression) -> Option<Fix> {
+    if FunctionFlags::of(closure)?.is_generator != Some(false) {
+        return None;
+    }
+
+    let captures = Captures::of(closure)?;
+    if captures.dynamic {
+        return None;
+    }
+
+    let mut imported = vec![];
+    if let Some(uses) = &closure.uses {
+        for r#use in uses.variables.iter() {
+            // changes made through a reference would no longer be visible outside.
+            if r#use.ampersand.is_some() {
+                return None;
+            }
+
+            imported.push(&r#use.variable.name);
+        }
+    }
+
+    if !captures
+        .variables
+        .iter()
+        .all(|variable| imported.contains(&variable))
+    {
+        return None;
+    }
+
+    let [Statement::Return(statement)] = closure.body.statements.as_mut_slice() else {
+        return None;
+    };
+    let value = statement.value.as_mut()?.span()?;
+
+    // everything but the returned expression is removed, so only keywords and
+    // whitespace may surround it.
+    let before = &source[closure.body.left_brace.position..value.position];
+    let before = trim(&before[1..]);
+    if before.len() < 6
+        || !before[..6].eq_ignore_ascii_case(b"return")
+        || !trim(&before[6..]).is_empty()
+    {
+        return None;
+    }
+
+    let after = trim(&source[value.end_position..closure.body.right_brace.position]);
+    if after != b";" {
+        return None;
+    }
+
+    let mut edits = vec![replace(closure.function, b"fn")];
+    if let Some(uses) = &closure.uses {
+        let start = closure.parameters.right_parenthesis.end_position;
+        edits.push(Edit {
+            position: start,
+            length: uses.right_parenthesis.end_position - start,
+            replacement: vec![],
+        });
+    }
+
+    edits.push(Edit {
+        position: closure.body.left_brace.position,
+        length: value.position - closure.body.left_brace.position,
+        replacement: b"=> ".to_vec(),
+    });
+    edits.push(Edit {
+        position: value.end_position,
+        length: closure.body.right_brace.end_position - value.end_position,
+        replacement: vec![],
+    });
+
+    Some(Fix {
+        description: "Convert the closure to an arrow function".to_owned(),
+        edits,
+    })
+}
+
+fn to_closure(source: &[u8], function: &mut ArrowFunctionExpression) -> Option<Fix> {
+    if matches!(&function.return_type, Some(return_type) if return_type.data_type.is_bottom()) {
+        return None;
+    }
+
+    let captures = Captures::of(function)?;
+    if captures.dynamic {
+        return None;
+    }
+
+    let body = function.body.span()?;
+    if !trim(&source[function.double_arrow.end_position..body.position]).is_empty() {
+        return None;
+    }
+
+    let mut edits = vec![replace(function.r#fn, b"function")];
+    if !captures.variables.is_empty() {
+        let variables: Vec<String> = captures
+            .variables
+            .iter()
+            .map(|variable| variable.to_string())
+            .collect();
+
+        edits.push(Edit::insert(
+            function.parameters.right_parenthesis.end_position,
+            format!(" use ({})", variables.join(", ")),
+        ));
+    }
+
+    edits.push(Edit {
+        position: function.double_arrow.position,
+        length: body.position - function.double_arrow.position,
+        replacement: b"{ return ".to_vec(),
+    });
+    edits.push(Edit::insert(body.end_position, "; }"));
+
+    Some(Fix {
+        description: "Convert the arrow function to a closure".to_owned(),
+        edits,
+    })
+}
+
+fn replace(span: Span, replacement: &[u8]) -> Edit {
+    Edit {
+        position: span.position,
+        length: span.length(),
+        replacement: replacement.to_vec(),
+    }
+}
+
+fn trim(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    let end = bytes
+        .iter()
+        .rposition(|b| !b.is_ascii_whitespace())
+        .map(|position| position + 1)
+        .unwrap_or(start);
+
+    &bytes[start..end]
+}
@@ -0,0 +1,36 @@
+//! Source-to-source refactorings, built on the analyses of [`crate::analysis`].
+//!
+//! A refactoring produces a [`Fix`] for every place it applies to, which can be applied
+//! to the original source with [`apply`].
+
+use crate::lint::apply_edits;
+use crate::lint::Fix;
+use crate::parser::ast::Program;
+
+pub mod arrow_functions;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Refactoring {
+    /// `function ($a) use ($b) { return $a + $b; }` to `fn ($a) => $a + $b`.
+    ClosureToArrowFunction,
+    /// `fn ($a) => $a + $b` to `function ($a) use ($b) { return $a + $b; }`.
+    ArrowFunctionToClosure,
+}
+
+impl Refactoring {
+    pub fn fixes(&self, source: &[u8], program: &mut Program) -> Vec<Fix> {
+        match self {
+            Self::ClosureToArrowFunction => {
+                arrow_functions::closures_to_arrow_functions(source, program)
+            }
+            Self::ArrowFunctionToClosure => {
+                arrow_functions::arrow_functions_to_closures(source, program)
+            }
+        }
+    }
+}
+
+/// Apply the edits of the given fixes to the source.
+pub fn apply(source: &[u8], fixes: &[Fix]) -> Vec<u8> {
+    apply_edits(source, fixes.iter().flat_map(|fix| fix.edits.iter()))
+}
@@ -0,0 +1,361 @@
+//! Resolves the names of a program to their fully qualified form, following the namespaces
+//! and `use` imports in effect where they appear, as nikic/php-parser's `NameResolver` does.
+//!
+//! The AST is left untouched: the result is a side table from the span of each name to
+//! what it resolves to.
+//!
+//! ```
+//! use php_parser_rs::parser;
+//! use php_parser_rs::resolver::resolve;
+//!
+//! let program = parser::parse("<?php namespace App; use Foo\\Bar; new Bar(); strlen('');").unwrap();
+//!
+//! let mut names: Vec<_> = resolve(&program).iter().map(|(span, name)| (*span, name.clone())).collect();
+//! names.sort_by_key(|(span, _)| span.position);
+//!
+//! assert_eq!(names[0].1.name.to_string(), "Foo\\Bar");
+//! // `strlen` is `App\strlen` if it exists, and the global function otherwise.
+//! assert_eq!(names[1].1.name.to_string(), "App\\strlen");
+//! assert_eq!(names[1].1.fallback.as_ref().unwrap().to_string(), "strlen");
+//! ```
+
+use std::collections::HashMap;
+
+use crate::analysis::attributes::declaration_attributes;
+use crate::index::names::NameContext;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::classes::ClassExtends;
+use crate::parser::ast::classes::ClassImplements;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::constant::ConstantStatement;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceExtends;
+use crate::parser::ast::interfaces::InterfaceStatement;
+use crate::parser::ast::namespaces::BracedNamespace;
+use crate::parser::ast::namespaces::UnbracedNamespace;
+use crate::parser::ast::traits::TraitStatement;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::try_block::CatchType;
+use crate::parser::ast::ConstantFetchExpression;
+use crate::parser::ast::Expression;
+use crate::parser::ast::FunctionCallExpression;
+use crate::parser::ast::FunctionClosureCreationExpression;
+use crate::parser::ast::GroupUseStatement;
+use crate::parser::ast::InstanceofExpression;
+use crate::parser::ast::NewExpression;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StaticMethodCallExpression;
+use crate::parser::ast::StaticMethodClosureCreationExpression;
+use crate::parser::ast::StaticPropertyFetchExpression;
+use crate::parser::ast::StaticVariableMethodCallExpression;
+use crate::parser::ast::StaticVariableMethodClosureCreationExpression;
+use crate::parser::ast::UseStatement;
+use crate::traverser::walk;
+use crate::traverser::Visitor;
+
+/// What a name refers to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum NameKind {
+    // Classes, interfaces, traits and enums, which share the same symbol table.
+    Class,
+    Function,
+    Constant,
+}
+
+/// The fully qualified form of a name, without a leading backslash.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ResolvedName {
+    pub kind: NameKind,
+    pub name: ByteString,
+    // The global name PHP falls back to at runtime when `name` does not exist, which is
+    // only the case for unqualified function and constant names inside a namespace.
+    pub fallback: Option<ByteString>,
+    // Whether the name is declared, rather than referenced, here, e.g. `class Foo {}`.
+    pub declaration: bool,
+}
+
+/// The names of a program, indexed by their span.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct ResolvedNames {
+    names: HashMap<Span, ResolvedName>,
+}
+
+impl ResolvedNames {
+    /// The resolved name at the given span, which is the span of a `SimpleIdentifier`, or
+    /// of a `Type::Named`.
+    pub fn get(&self, span: Span) -> Option<&ResolvedName> {
+        self.names.get(&span)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Span, &ResolvedName)> {
+        self.names.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// Resolve the class, function and constant names of a program.
+///
+/// `self`, `static` and `parent` are left as they are, since they depend on the class
+/// they appear in rather than on the namespace.
+pub fn resolve(program: &[Statement]) -> ResolvedNames {
+    let mut resolver = Resolver::default();
+    for statement in program {
+        walk(&mut resolver, statement);
+    }
+
+    resolver.names
+}
+
+#[derive(Default)]
+struct Resolver {
+    context: NameContext,
+    names: ResolvedNames,
+}
+
+impl Resolver {
+    fn class(&mut self, identifier: &SimpleIdentifier) {
+        let name = self.context.resolve_class(&identifier.value);
+
+        self.insert(identifier.span, NameKind::Class, vec![name], false);
+    }
+
+    fn class_expression(&mut self, expression: &Expression) {
+        if let Expression::Identifier(Identifier::SimpleIdentifier(identifier)) = expression {
+            self.class(identifier);
+        }
+    }
+
+    fn declare(&mut self, identifier: &SimpleIdentifier, kind: NameKind) {
+        let name = self.context.declare(&identifier.value);
+
+        self.insert(identifier.span, kind, vec![name], true);
+    }
+
+    fn insert(
+        &mut self,
+        span: Span,
+        kind: NameKind,
+        candidates: Vec<ByteString>,
+        declaration: bool,
+    ) {
+        let mut candidates = candidates.into_iter();
+        let Some(name) = candidates.next() else {
+            return;
+        };
+
+        self.names.names.insert(
+            span,
+            ResolvedName {
+                kind,
+                name,
+                fallback: candidates.next(),
+                declaration,
+            },
+        );
+    }
+}
+
+impl Visitor for Resolver {
+    fn enter_node(&mut self, node: &dyn Node) {
+        // attributes are not children of the declarations they belong to.
+        for attribute in declaration_attributes(node)
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|group| group.iter())
+        {
+            self.class(&attribute.name);
+
+            if let Some(arguments) = &attribute.arguments {
+                walk(self, arguments);
+            }
+        }
+    }
+
+    fn enter_unbraced_namespace(&mut self, namespace: &UnbracedNamespace) {
+        self.context.enter_namespace(Some(&namespace.name.value));
+    }
+
+    fn enter_braced_namespace(&mut self, namespace: &BracedNamespace) {
+        self.context
+            .enter_namespace(namespace.name.as_ref().map(|name| &name.value));
+    }
+
+    fn leave_braced_namespace(&mut self, _: &BracedNamespace) {
+        self.context.enter_namespace(None);
+    }
+
+    fn enter_use_statement(&mut self, statement: &UseStatement) {
+        self.context.add_use(statement);
+    }
+
+    fn enter_group_use_statement(&mut self, statement: &GroupUseStatement) {
+        self.context.add_group_use(statement);
+    }
+
+    fn enter_class_statement(&mut self, class: &ClassStatement) {
+        self.declare(&class.name, NameKind::Class);
+    }
+
+    fn enter_interface_statement(&mut self, interface: &InterfaceStatement) {
+        self.declare(&interface.name, NameKind::Class);
+    }
+
+    fn enter_trait_statement(&mut self, r#trait: &TraitStatement) {
+        self.declare(&r#trait.name, NameKind::Class);
+    }
+
+    fn enter_unit_enum_statement(&mut self, r#enum: &UnitEnumStatement) {
+        self.declare(&r#enum.name, NameKind::Class);
+        for interface in &r#enum.implements {
+            self.class(interface);
+        }
+    }
+
+    fn enter_backed_enum_statement(&mut self, r#enum: &BackedEnumStatement) {
+        self.declare(&r#enum.name, NameKind::Class);
+        for interface in &r#enum.implements {
+            self.class(interface);
+        }
+    }
+
+    fn enter_function_statement(&mut self, function: &FunctionStatement) {
+        self.declare(&function.name, NameKind::Function);
+    }
+
+    fn enter_constant_statement(&mut self, constant: &ConstantStatement) {
+        for entry in &constant.entries {
+            self.declare(&entry.name, NameKind::Constant);
+        }
+    }
+
+    fn enter_class_extends(&mut self, extends: &ClassExtends) {
+        self.class(&extends.parent);
+    }
+
+    fn enter_class_implements(&mut self, implements: &ClassImplements) {
+        for interface in implements.interfaces.iter() {
+            self.class(interface);
+        }
+    }
+
+    fn enter_interface_extends(&mut self, extends: &InterfaceExtends) {
+        for parent in extends.parents.iter() {
+            self.class(parent);
+        }
+    }
+
+    fn enter_trait_usage(&mut self, usage: &TraitUsage) {
+        for r#trait in &usage.traits {
+            self.class(r#trait);
+        }
+    }
+
+    fn enter_catch_type(&mut self, catch: &CatchType) {
+        match catch {
+            CatchType::Identifier { identifier } => self.class(identifier),
+            CatchType::Union { identifiers } => {
+                for identifier in identifiers {
+                    self.class(identifier);
+                }
+            }
+        }
+    }
+
+    fn enter_type(&mut self, ty: &Type) {
+        if let Type::Named(span, name) = ty {
+            let name = self.context.resolve_class(name);
+
+            self.insert(*span, NameKind::Class, vec![name], false);
+        }
+    }
+
+    fn enter_new_expression(&mut self, new: &NewExpression) {
+        self.class_expression(&new.target);
+    }
+
+    fn enter_instanceof_expression(&mut self, instanceof: &InstanceofExpression) {
+        self.class_expression(&instanceof.right);
+    }
+
+    fn enter_static_method_call_expression(&mut self, call: &StaticMethodCallExpression) {
+        self.class_expression(&call.target);
+    }
+
+    fn enter_static_variable_method_call_expression(
+        &mut self,
+        call: &StaticVariableMethodCallExpression,
+    ) {
+        self.class_expression(&call.target);
+    }
+
+    fn enter_static_method_closure_creation_expression(
+        &mut self,
+        closure: &StaticMethodClosureCreationExpression,
+    ) {
+        self.class_expression(&closure.target);
+    }
+
+    fn enter_static_variable_method_closure_creation_expression(
+        &mut self,
+        closure: &StaticVariableMethodClosureCreationExpression,
+    ) {
+        self.class_expression(&closure.target);
+    }
+
+    fn enter_static_property_fetch_expression(&mut self, fetch: &StaticPropertyFetchExpression) {
+        self.class_expression(&fetch.target);
+    }
+
+    fn enter_constant_fetch_expression(&mut self, fetch: &ConstantFetchExpression) {
+        self.class_expression(&fetch.target);
+    }
+
+    fn enter_function_call_expression(&mut self, call: &FunctionCallExpression) {
+        if let Expression::Identifier(Identifier::SimpleIdentifier(identifier)) =
+            call.target.as_ref()
+        {
+            let candidates = self.context.resolve_function(&identifier.value);
+
+            self.insert(identifier.span, NameKind::Function, candidates, false);
+        }
+    }
+
+    fn enter_function_closure_creation_expression(
+        &mut self,
+        closure: &FunctionClosureCreationExpression,
+    ) {
+        if let Expression::Identifier(Identifier::SimpleIdentifier(identifier)) =
+            closure.target.as_ref()
+        {
+            let candidates = self.context.resolve_function(&identifier.value);
+
+            self.insert(identifier.span, NameKind::Function, candidates, false);
+        }
+    }
+
+    fn enter_expression(&mut self, expression: &Expression) {
+        // any other bare name is a constant, names in the positions handled above are
+        // resolved by their parent, which is entered first.
+        if let Expression::Identifier(Identifier::SimpleIdentifier(identifier)) = expression {
+            if self.names.get(identifier.span).is_none() {
+                let candidates = self.context.resolve_constant(&identifier.value);
+
+                self.insert(identifier.span, NameKind::Constant, candidates, false);
+            }
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::lexer::token::Span;
+
+/// The unit in which columns are counted when reporting positions to consumers.
+///
+/// Spans always count columns in bytes, while editors may expect code points, or UTF-16
+/// code units as used by the Language Server Protocol.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum ColumnUnit {
+    #[default]
+    Bytes,
+    CodePoints,
+    Utf16CodeUnits,
+}
+
+impl ColumnUnit {
+    /// The 1-based column of the byte at `position`, counted in this unit.
+    pub fn column(&self, source: &[u8], position: usize) -> usize {
+        let position = position.min(source.len());
+        let start = source[..position]
+            .iter()
+            .rposition(|b| *b == b'\n')
+            .map(|newline| newline + 1)
+            .unwrap_or(0);
+
+        self.width(&source[start..position]) + 1
+    }
+
+    /// The width of the given text, counted in this unit. Invalid UTF-8 sequences count
+    /// as a single replacement character.
+    pub fn width(&self, text: &[u8]) -> usize {
+        match self {
+            Self::Bytes => text.len(),
+            Self::CodePoints => String::from_utf8_lossy(text).chars().count(),
+            Self::Utf16CodeUnits => String::from_utf8_lossy(text)
+                .chars()
+                .map(char::len_utf16)
+                .sum(),
+        }
+    }
+}
+
+/// Options used when rendering a source snippet.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SnippetOptions {
+    // The number of lines to show before and after the highlighted lines.
+    pub context_lines: usize,
+    // Whether to prefix each line with its line number.
+    pub line_numbers: bool,
+    // The character used to underline the highlighted range.
+    pub marker: char,
+    // An optional message displayed after the underline of the last highlighted line.
+    pub label: Option<String>,
+}
+
+impl Default for SnippetOptions {
+    fn default() -> Self {
+        Self {
+            context_lines: 1,
+            line_numbers: true,
+            marker: '^',
+            label: None,
+        }
+    }
+}
+
+/// Render a window of the source around `length` bytes starting at `span`, with the
+/// highlighted range underlined.
+///
+/// ```text
+/// 2 | function foo() {
+/// 3 |     return $bar;
+///   |            ^^^^ undefined variable
+/// 4 | }
+/// ```
+pub fn render_snippet(
+    source: &[u8],
+    span: Span,
+    length: usize,
+    options: &SnippetOptions,
+) -> String {
+    let Window {
+        lines,
+        start,
+        end,
+        first,
+        last,
+        from,
+        to,
+    } = window(source, span, length, options.context_lines);
+    let width = (to + 1).to_string().len();
+
+    let mut output = String::new();
+    for (number, (offset, line)) in lines.iter().enumerate().take(to + 1).skip(from) {
+        let text = String::from_utf8_lossy(line);
+        if options.line_numbers {
+            let _ = writeln!(output, "{:>width$} | {}", number + 1, text);
+        } else {
+            let _ = writeln!(output, "{}", text);
+        }
+
+        if number < first || number > last {
+            continue;
+        }
+
+        let (highlight_start, highlight_end) = highlight(*offset, line, start, end);
+
+        if options.line_numbers {
+            let _ = write!(output, "{:>width$} | ", "");
+        }
+
+        // Preserve tabs so that the markers line up with the text above them, which is
+        // printed one column per character.
+        for character in String::from_utf8_lossy(&line[..highlight_start]).chars() {
+            output.push(if character == '\t' { '\t' } else { ' ' });
+        }
+
+        let markers = ColumnUnit::CodePoints.width(&line[highlight_start..highlight_end]);
+        for _ in 0..markers.max(1) {
+            output.push(options.marker);
+        }
+
+        if number == last {
+            if let Some(label) = &options.label {
+                let _ = write!(output, " {}", label);
+            }
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
+
+/// A window of the source around a highlighted range, for displaying diagnostics
+/// without access to the source, such as in a web UI.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct CodeFrame {
+    pub lines: Vec<CodeFrameLine>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct CodeFrameLine {
+    // The 1-based line number.
+    pub number: usize,
+    // The text of the line, without the line terminator.
+    pub text: String,
+    // The highlighted range of the line as byte offsets into the text, or in the
+    // configured column unit when serialized by the linter, `None` for context lines.
+    pub highlight: Option<(usize, usize)>,
+}
+
+/// Collect the lines around `length` bytes starting at `span`, along with the
+/// highlighted range of each line. This is the data [`render_snippet`] renders.
+pub fn code_frame(source: &[u8], span: Span, length: usize, context_lines: usize) -> CodeFrame {
+    let Window {
+        lines,
+        start,
+        end,
+        first,
+        last,
+        from,
+        to,
+    } = window(source, span, length, context_lines);
+
+    let lines = lines
+        .iter()
+        .enumerate()
+        .take(to + 1)
+        .skip(from)
+        .map(|(number, (offset, line))| CodeFrameLine {
+            number: number + 1,
+            text: String::from_utf8_lossy(line).into_owned(),
+            highlight: (first..=last)
+                .contains(&number)
+                .then(|| highlight(*offset, line, start, end)),
+        })
+        .collect();
+
+    CodeFrame { lines }
+}
+
+struct Window<'a> {
+    lines: Vec<(usize, &'a [u8])>,
+    // the highlighted byte range.
+    start: usize,
+    end: usize,
+    // the indices of the first and last highlighted lines.
+    first: usize,
+    last: usize,
+    // the indices of the first and last lines shown, including context.
+    from: usize,
+    to: usize,
+}
+
+fn window(source: &[u8], span: Span, length: usize, context_lines: usize) -> Window<'_> {
+    let start = span.position.min(source.len());
+    let end = (start + length.max(1)).min(source.len().max(start + 1));
+
+    let lines = lines(source);
+    let first = lines
+        .iter()
+        .position(|(offset, line)| start <= offset + line.len())
+        .unwrap_or(lines.len().saturating_sub(1));
+    let last = lines
+        .iter()
+        .position(|(offset, line)| end <= offset + line.len() + 1)
+        .unwrap_or(lines.len().saturating_sub(1))
+        .max(first);
+
+    let from = first.saturating_sub(context_lines);
+    let to = (last + context_lines).min(lines.len().saturating_sub(1));
+
+    Window {
+        lines,
+        start,
+        end,
+        first,
+        last,
+        from,
+        to,
+    }
+}
+
+// The part of the line starting at `offset` that is within the highlighted range.
+fn highlight(offset: usize, line: &[u8], start: usize, end: usize) -> (usize, usize) {
+    let highlight_start = start.saturating_sub(offset).min(line.len());
+    let highlight_end = (end - offset).min(line.len()).max(highlight_start + 1);
+
+    (highlight_start, highlight_end)
+}
+
+// Split the source into lines, keeping track of the byte offset each line starts at.
+// Line terminators are not included in the lines.
+fn lines(source: &[u8]) -> Vec<(usize, &[u8])> {
+    let mut lines = vec![];
+    let mut offset = 0;
+
+    for line in source.split(|b| *b == b'\n') {
+        let text = line.strip_suffix(b"\r").unwrap_or(line);
+        lines.push((offset, text));
+        offset += line.len() + 1;
+    }
+
+    lines
+}
@@ -0,0 +1,193 @@
+//! Conversion between the byte offsets of spans and the lines and columns shown to users.
+//!
+//! Spans count columns in bytes, while editors may expect code points, or UTF-16 code
+//! units as used by the Language Server Protocol. A [`LineIndex`] finds the line of an
+//! offset without scanning the source again, and a [`SourceMap`] pairs it with the source
+//! to count columns in any [`ColumnUnit`] and to slice the text of spans.
+//!
+//! ```rust
+//! use php_parser_rs::snippet::ColumnUnit;
+//! use php_parser_rs::source_map::LineColumn;
+//! use php_parser_rs::source_map::SourceMap;
+//!
+//! let map = SourceMap::new("<?php\n$café = 1;".as_bytes());
+//!
+//! assert_eq!(map.line_column(13, ColumnUnit::Bytes), LineColumn::new(2, 8));
+//! assert_eq!(map.line_column(13, ColumnUnit::Utf16CodeUnits), LineColumn::new(2, 7));
+//! assert_eq!(map.offset(LineColumn::new(2, 7), ColumnUnit::Utf16CodeUnits), Some(13));
+//! ```
+
+use std::ops::Range;
+
+use serde::Serialize;
+
+use crate::lexer::token::Span;
+use crate::snippet::ColumnUnit;
+
+/// A 1-based line and column, as in spans, with the column counted in some [`ColumnUnit`].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl LineColumn {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+/// The byte offset of the start of every line of a source.
+///
+/// Lines end after a `\n`, so a `\r\n` line ending belongs to the line it ends, as with the
+/// lines of spans.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LineIndex {
+    starts: Vec<usize>,
+    length: usize,
+}
+
+impl LineIndex {
+    pub fn new(source: &[u8]) -> Self {
+        let starts = std::iter::once(0)
+            .chain(
+                source
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, byte)| **byte == b'\n')
+                    .map(|(position, _)| position + 1),
+            )
+            .collect();
+
+        Self {
+            starts,
+            length: source.len(),
+        }
+    }
+
+    /// The number of lines, which is one more than the number of line endings.
+    pub fn line_count(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// The 1-based line of the byte at `offset`. Offsets past the end of the source are
+    /// on the last line.
+    pub fn line(&self, offset: usize) -> usize {
+        self.starts
+            .partition_point(|start| *start <= offset.min(self.length))
+    }
+
+    /// The byte offsets of the given 1-based line, including its line ending.
+    pub fn line_range(&self, line: usize) -> Option<Range<usize>> {
+        let start = *self.starts.get(line.checked_sub(1)?)?;
+        let end = self.starts.get(line).copied().unwrap_or(self.length);
+
+        Some(start..end)
+    }
+
+    /// The line of the byte at `offset`, and its column counted in bytes.
+    pub fn line_column(&self, offset: usize) -> LineColumn {
+        let offset = offset.min(self.length);
+        let line = self.line(offset);
+
+        LineColumn::new(line, offset - self.starts[line - 1] + 1)
+    }
+}
+
+/// A source along with the index of its lines.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SourceMap<'a> {
+    source: &'a [u8],
+    index: LineIndex,
+}
+
+impl<'a> SourceMap<'a> {
+    pub fn new(source: &'a [u8]) -> Self {
+        Self {
+            source,
+            index: LineIndex::new(source),
+        }
+    }
+
+    pub fn source(&self) -> &'a [u8] {
+        self.source
+    }
+
+    pub fn line_index(&self) -> &LineIndex {
+        &self.index
+    }
+
+    /// The line of the byte at `offset`, and its column counted in the given unit.
+    pub fn line_column(&self, offset: usize, unit: ColumnUnit) -> LineColumn {
+        let LineColumn { line, column } = self.index.line_column(offset);
+        let start = self.index.starts[line - 1];
+
+        LineColumn::new(
+            line,
+            unit.width(&self.source[start..start + column - 1]) + 1,
+        )
+    }
+
+    /// The positions of the start and the end of a span, with columns counted in the given
+    /// unit.
+    pub fn span_range(&self, span: &Span, unit: ColumnUnit) -> (LineColumn, LineColumn) {
+        let end = span.end_position.max(span.position);
+
+        (
+            self.line_column(span.position, unit),
+            self.line_column(end, unit),
+        )
+    }
+
+    /// The byte offset of a line and column counted in the given unit, as sent by an editor.
+    ///
+    /// A column past the end of its line is the end of the line, before its line ending,
+    /// and a column inside of a character is the start of that character. Returns `None`
+    /// for a line that does not exist.
+    pub fn offset(&self, position: LineColumn, unit: ColumnUnit) -> Option<usize> {
+        let range = self.index.line_range(position.line)?;
+        let text = strip_line_ending(&self.source[range.clone()]);
+
+        let mut offset = 0;
+        let mut column = 1;
+        while offset < text.len() {
+            let length = char_length(&text[offset..]);
+            let next = column + unit.width(&text[offset..offset + length]);
+            if next > position.column {
+                break;
+            }
+
+            offset += length;
+            column = next;
+        }
+
+        Some(range.start + offset)
+    }
+
+    /// The source text covered by a span, clamped to the source.
+    pub fn slice(&self, span: &Span) -> &'a [u8] {
+        let end = span.end_position.min(self.source.len());
+
+        &self.source[span.position.min(end)..end]
+    }
+
+    /// The text of the given 1-based line, without its line ending.
+    pub fn line_text(&self, line: usize) -> Option<&'a [u8]> {
+        let range = self.index.line_range(line)?;
+
+        Some(strip_line_ending(&self.source[range]))
+    }
+}
+
+fn strip_line_ending(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+// the length of the UTF-8 character at the start of the text, or 1 for an invalid byte.
+fn char_length(text: &[u8]) -> usize {
+    (1..=text.len().min(4))
+        .find(|length| std::str::from_utf8(&text[..*length]).is_ok())
+        .unwrap_or(1)
+}
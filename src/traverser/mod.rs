@@ -1,17 +1,316 @@
-use std::fmt::Debug;
+//! Walks over the AST, calling the hooks of a [`Visitor`] or [`VisitorMut`] for every node.
+//!
+//! Both traits have a pair of `enter_*` and `leave_*` hooks for every node type, called
+//! before and after the node's children are walked, along with [`Visitor::enter_node`]
+//! and [`Visitor::leave_node`], which are called for every node regardless of its type.
+//! All hooks do nothing by default, so a visitor only implements the ones it needs.
+//!
+//! To replace or remove nodes rather than modify them, [`transform`] the program with a
+//! [`Transformer`] instead.
+//!
+//! ```
+//! use php_parser_rs::parser;
+//! use php_parser_rs::parser::ast::FunctionCallExpression;
+//! use php_parser_rs::traverser::walk;
+//! use php_parser_rs::traverser::Visitor;
+//!
+//! struct Calls(usize);
+//!
+//! impl Visitor for Calls {
+//!     fn enter_function_call_expression(&mut self, _: &FunctionCallExpression) {
+//!         self.0 += 1;
+//!     }
+//! }
+//!
+//! let program = parser::parse("<?php foo(bar());").unwrap();
+//!
+//! let mut calls = Calls(0);
+//! for statement in &program {
+//!     walk(&mut calls, statement);
+//! }
+//!
+//! assert_eq!(calls.0, 2);
+//! ```
 
+use crate::downcast::downcast;
+use crate::downcast::downcast_mut;
 use crate::node::Node;
+use crate::parser::ast;
 
-pub trait Visitor<E: Debug> {
-    fn visit_node(&mut self, node: &mut dyn Node) -> Result<(), E> {
-        self.visit(node)?;
+mod transform;
 
-        for child in node.children() {
-            self.visit_node(child)?;
-        }
+pub use transform::transform;
+pub use transform::Transformer;
+
+/// Walk the node and all nodes within it, depth-first, calling the hooks of the visitor.
+pub fn walk<V: Visitor + ?Sized>(visitor: &mut V, node: &dyn Node) {
+    visitor.enter_node(node);
+    enter(visitor, node);
+
+    for child in node.children_ref() {
+        walk(visitor, child);
+    }
+
+    leave(visitor, node);
+    visitor.leave_node(node);
+}
+
+/// Walk the node and all nodes within it, depth-first, calling the hooks of the visitor,
+/// which may modify the nodes in place.
+pub fn walk_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut dyn Node) {
+    visitor.enter_node(node);
+    enter_mut(visitor, node);
 
-        Ok(())
+    for child in node.children() {
+        walk_mut(visitor, child);
     }
 
-    fn visit(&mut self, node: &mut dyn Node) -> Result<(), E>;
+    leave_mut(visitor, node);
+    visitor.leave_node(node);
+}
+
+macro_rules! visitors {
+    ($($node:ty => $enter:ident, $leave:ident;)*) => {
+        pub trait Visitor {
+            /// Called for every node, before its typed hook and its children.
+            fn enter_node(&mut self, _node: &dyn Node) {}
+
+            /// Called for every node, after its children and its typed hook.
+            fn leave_node(&mut self, _node: &dyn Node) {}
+
+            $(
+                #[doc = concat!("Called before the children of a `", stringify!($node), "`.")]
+                fn $enter(&mut self, _node: &$node) {}
+
+                #[doc = concat!("Called after the children of a `", stringify!($node), "`.")]
+                fn $leave(&mut self, _node: &$node) {}
+            )*
+        }
+
+        pub trait VisitorMut {
+            /// Called for every node, before its typed hook and its children.
+            fn enter_node(&mut self, _node: &mut dyn Node) {}
+
+            /// Called for every node, after its children and its typed hook.
+            fn leave_node(&mut self, _node: &mut dyn Node) {}
+
+            $(
+                #[doc = concat!("Called before the children of a `", stringify!($node), "`.")]
+                fn $enter(&mut self, _node: &mut $node) {}
+
+                #[doc = concat!("Called after the children of a `", stringify!($node), "`.")]
+                fn $leave(&mut self, _node: &mut $node) {}
+            )*
+        }
+
+        fn enter<V: Visitor + ?Sized>(visitor: &mut V, node: &dyn Node) {
+            $(
+                if let Some(node) = downcast::<$node>(node) {
+                    return visitor.$enter(node);
+                }
+            )*
+        }
+
+        fn leave<V: Visitor + ?Sized>(visitor: &mut V, node: &dyn Node) {
+            $(
+                if let Some(node) = downcast::<$node>(node) {
+                    return visitor.$leave(node);
+                }
+            )*
+        }
+
+        fn enter_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut dyn Node) {
+            $(
+                if let Some(node) = downcast_mut::<$node>(node) {
+                    return visitor.$enter(node);
+                }
+            )*
+        }
+
+        fn leave_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut dyn Node) {
+            $(
+                if let Some(node) = downcast_mut::<$node>(node) {
+                    return visitor.$leave(node);
+                }
+            )*
+        }
+    };
+}
+
+visitors! {
+    ast::arguments::PositionalArgument => enter_positional_argument, leave_positional_argument;
+    ast::arguments::NamedArgument => enter_named_argument, leave_named_argument;
+    ast::arguments::Argument => enter_argument, leave_argument;
+    ast::arguments::ArgumentList => enter_argument_list, leave_argument_list;
+    ast::arguments::SingleArgument => enter_single_argument, leave_single_argument;
+    ast::classes::ClassBody => enter_class_body, leave_class_body;
+    ast::classes::ClassStatement => enter_class_statement, leave_class_statement;
+    ast::classes::AnonymousClassBody => enter_anonymous_class_body, leave_anonymous_class_body;
+    ast::classes::AnonymousClassExpression => enter_anonymous_class_expression, leave_anonymous_class_expression;
+    ast::classes::ClassExtends => enter_class_extends, leave_class_extends;
+    ast::classes::ClassImplements => enter_class_implements, leave_class_implements;
+    ast::classes::ClassMember => enter_class_member, leave_class_member;
+    ast::classes::AnonymousClassMember => enter_anonymous_class_member, leave_anonymous_class_member;
+    ast::comments::Comment => enter_comment, leave_comment;
+    ast::constant::ConstantEntry => enter_constant_entry, leave_constant_entry;
+    ast::constant::ConstantStatement => enter_constant_statement, leave_constant_statement;
+    ast::constant::ClassishConstant => enter_classish_constant, leave_classish_constant;
+    ast::control_flow::IfStatement => enter_if_statement, leave_if_statement;
+    ast::control_flow::IfStatementBody => enter_if_statement_body, leave_if_statement_body;
+    ast::control_flow::IfStatementElseIf => enter_if_statement_else_if, leave_if_statement_else_if;
+    ast::control_flow::IfStatementElse => enter_if_statement_else, leave_if_statement_else;
+    ast::control_flow::IfStatementElseIfBlock => enter_if_statement_else_if_block, leave_if_statement_else_if_block;
+    ast::control_flow::IfStatementElseBlock => enter_if_statement_else_block, leave_if_statement_else_block;
+    ast::data_type::Type => enter_type, leave_type;
+    ast::declares::DeclareEntry => enter_declare_entry, leave_declare_entry;
+    ast::declares::DeclareEntryGroup => enter_declare_entry_group, leave_declare_entry_group;
+    ast::declares::DeclareBody => enter_declare_body, leave_declare_body;
+    ast::declares::DeclareStatement => enter_declare_statement, leave_declare_statement;
+    ast::enums::UnitEnumCase => enter_unit_enum_case, leave_unit_enum_case;
+    ast::enums::UnitEnumMember => enter_unit_enum_member, leave_unit_enum_member;
+    ast::enums::UnitEnumBody => enter_unit_enum_body, leave_unit_enum_body;
+    ast::enums::UnitEnumStatement => enter_unit_enum_statement, leave_unit_enum_statement;
+    ast::enums::BackedEnumType => enter_backed_enum_type, leave_backed_enum_type;
+    ast::enums::BackedEnumCase => enter_backed_enum_case, leave_backed_enum_case;
+    ast::enums::BackedEnumMember => enter_backed_enum_member, leave_backed_enum_member;
+    ast::enums::BackedEnumBody => enter_backed_enum_body, leave_backed_enum_body;
+    ast::enums::BackedEnumStatement => enter_backed_enum_statement, leave_backed_enum_statement;
+    ast::functions::ReturnType => enter_return_type, leave_return_type;
+    ast::functions::FunctionParameter => enter_function_parameter, leave_function_parameter;
+    ast::functions::FunctionParameterList => enter_function_parameter_list, leave_function_parameter_list;
+    ast::functions::FunctionBody => enter_function_body, leave_function_body;
+    ast::functions::FunctionStatement => enter_function_statement, leave_function_statement;
+    ast::functions::ClosureUseVariable => enter_closure_use_variable, leave_closure_use_variable;
+    ast::functions::ClosureUse => enter_closure_use, leave_closure_use;
+    ast::functions::ClosureExpression => enter_closure_expression, leave_closure_expression;
+    ast::functions::ArrowFunctionExpression => enter_arrow_function_expression, leave_arrow_function_expression;
+    ast::functions::ConstructorParameter => enter_constructor_parameter, leave_constructor_parameter;
+    ast::functions::ConstructorParameterList => enter_constructor_parameter_list, leave_constructor_parameter_list;
+    ast::functions::AbstractConstructor => enter_abstract_constructor, leave_abstract_constructor;
+    ast::functions::ConcreteConstructor => enter_concrete_constructor, leave_concrete_constructor;
+    ast::functions::AbstractMethod => enter_abstract_method, leave_abstract_method;
+    ast::functions::ConcreteMethod => enter_concrete_method, leave_concrete_method;
+    ast::functions::MethodBody => enter_method_body, leave_method_body;
+    ast::goto::LabelStatement => enter_label_statement, leave_label_statement;
+    ast::goto::GotoStatement => enter_goto_statement, leave_goto_statement;
+    ast::identifiers::Identifier => enter_identifier, leave_identifier;
+    ast::identifiers::SimpleIdentifier => enter_simple_identifier, leave_simple_identifier;
+    ast::identifiers::DynamicIdentifier => enter_dynamic_identifier, leave_dynamic_identifier;
+    ast::interfaces::InterfaceMember => enter_interface_member, leave_interface_member;
+    ast::interfaces::InterfaceExtends => enter_interface_extends, leave_interface_extends;
+    ast::interfaces::InterfaceBody => enter_interface_body, leave_interface_body;
+    ast::interfaces::InterfaceStatement => enter_interface_statement, leave_interface_statement;
+    ast::literals::Literal => enter_literal, leave_literal;
+    ast::literals::LiteralString => enter_literal_string, leave_literal_string;
+    ast::literals::LiteralInteger => enter_literal_integer, leave_literal_integer;
+    ast::literals::LiteralFloat => enter_literal_float, leave_literal_float;
+    ast::loops::ForeachStatement => enter_foreach_statement, leave_foreach_statement;
+    ast::loops::ForeachStatementIterator => enter_foreach_statement_iterator, leave_foreach_statement_iterator;
+    ast::loops::ForeachStatementBody => enter_foreach_statement_body, leave_foreach_statement_body;
+    ast::loops::ForStatement => enter_for_statement, leave_for_statement;
+    ast::loops::ForStatementIterator => enter_for_statement_iterator, leave_for_statement_iterator;
+    ast::loops::ForStatementBody => enter_for_statement_body, leave_for_statement_body;
+    ast::loops::DoWhileStatement => enter_do_while_statement, leave_do_while_statement;
+    ast::loops::WhileStatement => enter_while_statement, leave_while_statement;
+    ast::loops::WhileStatementBody => enter_while_statement_body, leave_while_statement_body;
+    ast::loops::Level => enter_level, leave_level;
+    ast::loops::BreakStatement => enter_break_statement, leave_break_statement;
+    ast::loops::ContinueStatement => enter_continue_statement, leave_continue_statement;
+    ast::Block => enter_block, leave_block;
+    ast::StaticVar => enter_static_var, leave_static_var;
+    ast::HaltCompilerStatement => enter_halt_compiler_statement, leave_halt_compiler_statement;
+    ast::StaticStatement => enter_static_statement, leave_static_statement;
+    ast::SwitchStatement => enter_switch_statement, leave_switch_statement;
+    ast::SwitchStatementBody => enter_switch_statement_body, leave_switch_statement_body;
+    ast::EchoStatement => enter_echo_statement, leave_echo_statement;
+    ast::ReturnStatement => enter_return_statement, leave_return_statement;
+    ast::UseStatement => enter_use_statement, leave_use_statement;
+    ast::GroupUseStatement => enter_group_use_statement, leave_group_use_statement;
+    ast::Statement => enter_statement, leave_statement;
+    ast::ExpressionStatement => enter_expression_statement, leave_expression_statement;
+    ast::GlobalStatement => enter_global_statement, leave_global_statement;
+    ast::BlockStatement => enter_block_statement, leave_block_statement;
+    ast::Case => enter_case, leave_case;
+    ast::Use => enter_use, leave_use;
+    ast::FunctionCallExpression => enter_function_call_expression, leave_function_call_expression;
+    ast::FunctionClosureCreationExpression => enter_function_closure_creation_expression, leave_function_closure_creation_expression;
+    ast::MethodCallExpression => enter_method_call_expression, leave_method_call_expression;
+    ast::MethodClosureCreationExpression => enter_method_closure_creation_expression, leave_method_closure_creation_expression;
+    ast::NullsafeMethodCallExpression => enter_nullsafe_method_call_expression, leave_nullsafe_method_call_expression;
+    ast::StaticMethodCallExpression => enter_static_method_call_expression, leave_static_method_call_expression;
+    ast::StaticVariableMethodCallExpression => enter_static_variable_method_call_expression, leave_static_variable_method_call_expression;
+    ast::StaticMethodClosureCreationExpression => enter_static_method_closure_creation_expression, leave_static_method_closure_creation_expression;
+    ast::StaticVariableMethodClosureCreationExpression => enter_static_variable_method_closure_creation_expression, leave_static_variable_method_closure_creation_expression;
+    ast::PropertyFetchExpression => enter_property_fetch_expression, leave_property_fetch_expression;
+    ast::NullsafePropertyFetchExpression => enter_nullsafe_property_fetch_expression, leave_nullsafe_property_fetch_expression;
+    ast::StaticPropertyFetchExpression => enter_static_property_fetch_expression, leave_static_property_fetch_expression;
+    ast::ConstantFetchExpression => enter_constant_fetch_expression, leave_constant_fetch_expression;
+    ast::ShortArrayExpression => enter_short_array_expression, leave_short_array_expression;
+    ast::ArrayExpression => enter_array_expression, leave_array_expression;
+    ast::ListExpression => enter_list_expression, leave_list_expression;
+    ast::NewExpression => enter_new_expression, leave_new_expression;
+    ast::InterpolatedStringExpression => enter_interpolated_string_expression, leave_interpolated_string_expression;
+    ast::HeredocExpression => enter_heredoc_expression, leave_heredoc_expression;
+    ast::NowdocExpression => enter_nowdoc_expression, leave_nowdoc_expression;
+    ast::ShellExecExpression => enter_shell_exec_expression, leave_shell_exec_expression;
+    ast::BoolExpression => enter_bool_expression, leave_bool_expression;
+    ast::ArrayIndexExpression => enter_array_index_expression, leave_array_index_expression;
+    ast::ShortTernaryExpression => enter_short_ternary_expression, leave_short_ternary_expression;
+    ast::TernaryExpression => enter_ternary_expression, leave_ternary_expression;
+    ast::CoalesceExpression => enter_coalesce_expression, leave_coalesce_expression;
+    ast::CloneExpression => enter_clone_expression, leave_clone_expression;
+    ast::MatchExpression => enter_match_expression, leave_match_expression;
+    ast::ThrowExpression => enter_throw_expression, leave_throw_expression;
+    ast::YieldExpression => enter_yield_expression, leave_yield_expression;
+    ast::YieldFromExpression => enter_yield_from_expression, leave_yield_from_expression;
+    ast::CastExpression => enter_cast_expression, leave_cast_expression;
+    ast::EvalExpression => enter_eval_expression, leave_eval_expression;
+    ast::EmptyExpression => enter_empty_expression, leave_empty_expression;
+    ast::DieExpression => enter_die_expression, leave_die_expression;
+    ast::ExitExpression => enter_exit_expression, leave_exit_expression;
+    ast::IssetExpression => enter_isset_expression, leave_isset_expression;
+    ast::UnsetExpression => enter_unset_expression, leave_unset_expression;
+    ast::PrintExpression => enter_print_expression, leave_print_expression;
+    ast::ConcatExpression => enter_concat_expression, leave_concat_expression;
+    ast::InstanceofExpression => enter_instanceof_expression, leave_instanceof_expression;
+    ast::ReferenceExpression => enter_reference_expression, leave_reference_expression;
+    ast::ParenthesizedExpression => enter_parenthesized_expression, leave_parenthesized_expression;
+    ast::ErrorSuppressExpression => enter_error_suppress_expression, leave_error_suppress_expression;
+    ast::IncludeExpression => enter_include_expression, leave_include_expression;
+    ast::IncludeOnceExpression => enter_include_once_expression, leave_include_once_expression;
+    ast::RequireExpression => enter_require_expression, leave_require_expression;
+    ast::RequireOnceExpression => enter_require_once_expression, leave_require_once_expression;
+    ast::Expression => enter_expression, leave_expression;
+    ast::DefaultMatchArm => enter_default_match_arm, leave_default_match_arm;
+    ast::MatchArm => enter_match_arm, leave_match_arm;
+    ast::MagicConstantExpression => enter_magic_constant_expression, leave_magic_constant_expression;
+    ast::LiteralStringPart => enter_literal_string_part, leave_literal_string_part;
+    ast::ExpressionStringPart => enter_expression_string_part, leave_expression_string_part;
+    ast::StringPart => enter_string_part, leave_string_part;
+    ast::ArrayItem => enter_array_item, leave_array_item;
+    ast::ListEntry => enter_list_entry, leave_list_entry;
+    ast::namespaces::UnbracedNamespace => enter_unbraced_namespace, leave_unbraced_namespace;
+    ast::namespaces::BracedNamespace => enter_braced_namespace, leave_braced_namespace;
+    ast::namespaces::BracedNamespaceBody => enter_braced_namespace_body, leave_braced_namespace_body;
+    ast::namespaces::NamespaceStatement => enter_namespace_statement, leave_namespace_statement;
+    ast::operators::ArithmeticOperationExpression => enter_arithmetic_operation_expression, leave_arithmetic_operation_expression;
+    ast::operators::AssignmentOperationExpression => enter_assignment_operation_expression, leave_assignment_operation_expression;
+    ast::operators::BitwiseOperationExpression => enter_bitwise_operation_expression, leave_bitwise_operation_expression;
+    ast::operators::ComparisonOperationExpression => enter_comparison_operation_expression, leave_comparison_operation_expression;
+    ast::operators::LogicalOperationExpression => enter_logical_operation_expression, leave_logical_operation_expression;
+    ast::properties::Property => enter_property, leave_property;
+    ast::properties::VariableProperty => enter_variable_property, leave_variable_property;
+    ast::properties::PropertyEntry => enter_property_entry, leave_property_entry;
+    ast::traits::TraitMember => enter_trait_member, leave_trait_member;
+    ast::traits::TraitBody => enter_trait_body, leave_trait_body;
+    ast::traits::TraitStatement => enter_trait_statement, leave_trait_statement;
+    ast::traits::TraitUsage => enter_trait_usage, leave_trait_usage;
+    ast::try_block::CatchType => enter_catch_type, leave_catch_type;
+    ast::try_block::TryStatement => enter_try_statement, leave_try_statement;
+    ast::try_block::CatchBlock => enter_catch_block, leave_catch_block;
+    ast::try_block::FinallyBlock => enter_finally_block, leave_finally_block;
+    ast::variables::Variable => enter_variable, leave_variable;
+    ast::variables::SimpleVariable => enter_simple_variable, leave_simple_variable;
+    ast::variables::VariableVariable => enter_variable_variable, leave_variable_variable;
+    ast::variables::BracedVariableVariable => enter_braced_variable_variable, leave_braced_variable_variable;
 }
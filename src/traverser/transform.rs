@@ -0,0 +1,222 @@
+use std::mem;
+
+use crate::downcast::downcast_mut;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::control_flow::IfStatementBody;
+use crate::parser::ast::control_flow::IfStatementElse;
+use crate::parser::ast::control_flow::IfStatementElseBlock;
+use crate::parser::ast::control_flow::IfStatementElseIf;
+use crate::parser::ast::control_flow::IfStatementElseIfBlock;
+use crate::parser::ast::declares::DeclareBody;
+use crate::parser::ast::functions::FunctionBody;
+use crate::parser::ast::functions::MethodBody;
+use crate::parser::ast::loops::DoWhileStatement;
+use crate::parser::ast::loops::ForStatementBody;
+use crate::parser::ast::loops::ForeachStatementBody;
+use crate::parser::ast::loops::WhileStatementBody;
+use crate::parser::ast::namespaces::BracedNamespaceBody;
+use crate::parser::ast::namespaces::UnbracedNamespace;
+use crate::parser::ast::try_block::CatchBlock;
+use crate::parser::ast::try_block::FinallyBlock;
+use crate::parser::ast::try_block::TryStatement;
+use crate::parser::ast::BlockStatement;
+use crate::parser::ast::Case;
+use crate::parser::ast::Expression;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+
+/// Rewrites the AST during a [`transform`], replacing the statements and expressions it
+/// is given with the ones it returns.
+///
+/// Both hooks are called after the children of the node were transformed, and return
+/// the node unchanged by default.
+pub trait Transformer {
+    /// Called for every statement, which is replaced by the returned statements: an
+    /// empty list removes it, and more than one are spliced in its place.
+    ///
+    /// Where a single statement is expected, such as the body of an `if` without
+    /// braces, no statements become a `;`, and several are wrapped in a block.
+    fn transform_statement(&mut self, statement: Statement) -> Vec<Statement> {
+        vec![statement]
+    }
+
+    /// Called for every expression, which is replaced by the returned expression.
+    fn transform_expression(&mut self, expression: Expression) -> Expression {
+        expression
+    }
+}
+
+/// Transform the program depth-first, returning the rewritten program.
+///
+/// ```
+/// use php_parser_rs::parser;
+/// use php_parser_rs::parser::ast::Statement;
+/// use php_parser_rs::traverser::transform;
+/// use php_parser_rs::traverser::Transformer;
+///
+/// struct RemoveEcho;
+///
+/// impl Transformer for RemoveEcho {
+///     fn transform_statement(&mut self, statement: Statement) -> Vec<Statement> {
+///         match statement {
+///             Statement::Echo(_) => vec![],
+///             statement => vec![statement],
+///         }
+///     }
+/// }
+///
+/// let program = parser::parse("<?php echo 1; foo(); echo 2;").unwrap();
+/// let program = transform(&mut RemoveEcho, program);
+///
+/// assert!(matches!(&program[..], [Statement::FullOpeningTag(_), Statement::Expression(_)]));
+/// ```
+pub fn transform<T: Transformer + ?Sized>(transformer: &mut T, program: Program) -> Program {
+    let mut program = program;
+
+    for statement in program.iter_mut() {
+        visit(transformer, statement);
+    }
+
+    splice(transformer, &mut program);
+
+    program
+}
+
+fn visit<T: Transformer + ?Sized>(transformer: &mut T, node: &mut dyn Node) {
+    for child in node.children() {
+        visit(transformer, child);
+    }
+
+    // statements are replaced by the node holding them, as they may be spliced into a
+    // list of statements.
+    statements(transformer, node);
+
+    if let Some(expression) = downcast_mut::<Expression>(node) {
+        let transformed =
+            transformer.transform_expression(mem::replace(expression, Expression::Noop));
+
+        *expression = transformed;
+    }
+}
+
+fn statements<T: Transformer + ?Sized>(transformer: &mut T, node: &mut dyn Node) {
+    if let Some(block) = downcast_mut::<BlockStatement>(node) {
+        return splice(transformer, &mut block.statements);
+    }
+
+    if let Some(body) = downcast_mut::<FunctionBody>(node) {
+        return splice(transformer, &mut body.statements);
+    }
+
+    if let Some(body) = downcast_mut::<MethodBody>(node) {
+        return splice(transformer, &mut body.statements);
+    }
+
+    if let Some(case) = downcast_mut::<Case>(node) {
+        return splice(transformer, &mut case.body);
+    }
+
+    if let Some(namespace) = downcast_mut::<UnbracedNamespace>(node) {
+        return splice(transformer, &mut namespace.statements);
+    }
+
+    if let Some(body) = downcast_mut::<BracedNamespaceBody>(node) {
+        return splice(transformer, &mut body.statements);
+    }
+
+    if let Some(r#try) = downcast_mut::<TryStatement>(node) {
+        return splice(transformer, &mut r#try.body);
+    }
+
+    if let Some(catch) = downcast_mut::<CatchBlock>(node) {
+        return splice(transformer, &mut catch.body);
+    }
+
+    if let Some(finally) = downcast_mut::<FinallyBlock>(node) {
+        return splice(transformer, &mut finally.body);
+    }
+
+    if let Some(body) = downcast_mut::<IfStatementBody>(node) {
+        return match body {
+            IfStatementBody::Statement { statement, .. } => replace(transformer, statement),
+            IfStatementBody::Block { statements, .. } => splice(transformer, statements),
+        };
+    }
+
+    if let Some(elseif) = downcast_mut::<IfStatementElseIf>(node) {
+        return replace(transformer, &mut elseif.statement);
+    }
+
+    if let Some(r#else) = downcast_mut::<IfStatementElse>(node) {
+        return replace(transformer, &mut r#else.statement);
+    }
+
+    if let Some(elseif) = downcast_mut::<IfStatementElseIfBlock>(node) {
+        return splice(transformer, &mut elseif.statements);
+    }
+
+    if let Some(r#else) = downcast_mut::<IfStatementElseBlock>(node) {
+        return splice(transformer, &mut r#else.statements);
+    }
+
+    if let Some(body) = downcast_mut::<ForeachStatementBody>(node) {
+        return match body {
+            ForeachStatementBody::Statement { statement } => replace(transformer, statement),
+            ForeachStatementBody::Block { statements, .. } => splice(transformer, statements),
+        };
+    }
+
+    if let Some(body) = downcast_mut::<ForStatementBody>(node) {
+        return match body {
+            ForStatementBody::Statement { statement } => replace(transformer, statement),
+            ForStatementBody::Block { statements, .. } => splice(transformer, statements),
+        };
+    }
+
+    if let Some(body) = downcast_mut::<WhileStatementBody>(node) {
+        return match body {
+            WhileStatementBody::Statement { statement } => replace(transformer, statement),
+            WhileStatementBody::Block { statements, .. } => splice(transformer, statements),
+        };
+    }
+
+    if let Some(r#do) = downcast_mut::<DoWhileStatement>(node) {
+        return replace(transformer, &mut r#do.body);
+    }
+
+    if let Some(DeclareBody::Braced { statements, .. } | DeclareBody::Block { statements, .. }) =
+        downcast_mut::<DeclareBody>(node)
+    {
+        splice(transformer, statements);
+    }
+}
+
+fn splice<T: Transformer + ?Sized>(transformer: &mut T, statements: &mut Vec<Statement>) {
+    *statements = mem::take(statements)
+        .into_iter()
+        .flat_map(|statement| transformer.transform_statement(statement))
+        .collect();
+}
+
+fn replace<T: Transformer + ?Sized>(transformer: &mut T, statement: &mut Statement) {
+    // the statements replacing it have no braces or `;` of their own, they are given an
+    // empty span where the original statement started.
+    let start = statement
+        .span()
+        .map(|span| Span::new(span.line, span.column, span.position))
+        .unwrap_or(Span::new(0, 0, 0));
+
+    let mut statements =
+        transformer.transform_statement(mem::replace(statement, Statement::Noop(start)));
+
+    *statement = match statements.len() {
+        0 => Statement::Noop(start),
+        1 => statements.remove(0),
+        _ => Statement::Block(BlockStatement {
+            left_brace: start,
+            statements,
+            right_brace: start,
+        }),
+    };
+}
@@ -0,0 +1,58 @@
+use serde::Serialize;
+
+use crate::lexer::keywords::PhpVersion;
+
+/// Information identifying the exact parser build, so tools can invalidate caches
+/// and reproduce analyses.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub struct VersionInfo {
+    /// The crate version.
+    pub version: &'static str,
+    /// The oldest and latest PHP versions the parser supports.
+    pub min_php_version: &'static str,
+    pub max_php_version: &'static str,
+    /// The enabled cargo features.
+    pub features: &'static [&'static str],
+    /// A hash of the lexer, parser and AST sources, computed by the build script. It
+    /// changes whenever the grammar or the shape of the AST may have changed, even if
+    /// the crate version did not.
+    pub grammar_hash: &'static str,
+}
+
+pub const VERSION_INFO: VersionInfo = VersionInfo {
+    version: env!("CARGO_PKG_VERSION"),
+    min_php_version: PhpVersion::OLDEST.as_str(),
+    max_php_version: PhpVersion::LATEST.as_str(),
+    features: &[
+        #[cfg(feature = "arrow")]
+        "arrow",
+        #[cfg(feature = "fuzz")]
+        "fuzz",
+        #[cfg(feature = "rayon")]
+        "rayon",
+    ],
+    grammar_hash: env!("PHP_PARSER_GRAMMAR_HASH"),
+};
+
+#[cfg(test)]
+mod tests {
+    use super::VERSION_INFO;
+
+    #[test]
+    fn test_version_info() {
+        assert_eq!(VERSION_INFO.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(VERSION_INFO.min_php_version, "7.4");
+        assert_eq!(VERSION_INFO.grammar_hash.len(), 16);
+        assert_eq!(
+            VERSION_INFO.features.contains(&"rayon"),
+            cfg!(feature = "rayon")
+        );
+        assert_eq!(
+            VERSION_INFO.features.contains(&"fuzz"),
+            cfg!(feature = "fuzz")
+        );
+
+        let json = serde_json::to_value(VERSION_INFO).unwrap();
+        assert_eq!(json["max_php_version"], "8.3");
+    }
+}
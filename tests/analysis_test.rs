@@ -0,0 +1,638 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use php_parser_rs::analysis::captures::Captures;
+use php_parser_rs::analysis::compat::compare;
+use php_parser_rs::analysis::declares::Declares;
+use php_parser_rs::analysis::enums::EnumBackingType;
+use php_parser_rs::analysis::enums::EnumBackingValue;
+use php_parser_rs::analysis::enums::EnumShape;
+use php_parser_rs::analysis::features::Feature;
+use php_parser_rs::analysis::features::FeatureStats;
+use php_parser_rs::analysis::features::FeatureUsage;
+use php_parser_rs::analysis::functions::FunctionFlags;
+use php_parser_rs::analysis::includes::resolve_include;
+use php_parser_rs::analysis::scopes::scopes;
+use php_parser_rs::analysis::scopes::DefinitionKind;
+use php_parser_rs::analysis::scopes::ScopeKind;
+use php_parser_rs::analysis::views::ClassLikeView;
+use php_parser_rs::evaluator::PhpConstValue;
+use php_parser_rs::index::Index;
+use php_parser_rs::lexer::keywords::PhpVersion;
+use php_parser_rs::node::Node;
+use php_parser_rs::parser;
+use php_parser_rs::parser::ast::variables::SimpleVariable;
+use php_parser_rs::parser::ast::Statement;
+
+#[test]
+fn test_well_known_attribute_flags() {
+    let program = parser::parse(
+        "<?php
+        #[\\AllowDynamicProperties]
+        class Foo extends Bar {
+            #[\\Override]
+            public function baz(): void {}
+
+            #[ReturnTypeWillChange, Other]
+            public function qux() {}
+        }",
+    )
+    .unwrap();
+
+    let class = program
+        .iter()
+        .find_map(ClassLikeView::from_statement)
+        .unwrap();
+
+    assert!(class.flags.allow_dynamic_properties);
+    assert_eq!(class.parent.unwrap().value, b"Bar");
+
+    let baz = class.method(b"BAZ").unwrap();
+    assert!(baz.flags.r#override);
+    assert!(!baz.flags.return_type_will_change);
+
+    let qux = class.method(b"qux").unwrap();
+    assert!(!qux.flags.r#override);
+    assert!(qux.flags.return_type_will_change);
+}
+
+#[test]
+fn test_resolve_include_paths() {
+    let program = parser::parse(
+        "<?php
+        require __DIR__ . '/../config.php';
+        include_once dirname(__FILE__) . '/helpers/./strings.php';
+        require_once \\dirname(__DIR__, 2) . DIRECTORY_SEPARATOR . 'vendor/autoload.php';
+        include ('/etc/app.php');
+        include 'relative.php';
+        include $path . '/file.php';
+        ",
+    )
+    .unwrap();
+
+    let file = Path::new("/srv/app/src/bootstrap.php");
+    let resolved: Vec<Option<PathBuf>> = program
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Expression(statement) => Some(resolve_include(file, &statement.expression)),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        resolved,
+        vec![
+            Some(PathBuf::from("/srv/app/config.php")),
+            Some(PathBuf::from("/srv/app/src/helpers/strings.php")),
+            Some(PathBuf::from("/srv/vendor/autoload.php")),
+            Some(PathBuf::from("/etc/app.php")),
+            None,
+            None,
+        ]
+    );
+}
+
+#[test]
+fn test_enum_shape() {
+    let program = parser::parse(
+        "<?php
+        enum Suit: string implements HasLabel {
+            const PREFIX = 'suit_';
+
+            case Hearts = self::PREFIX . 'hearts';
+            #[Red]
+            case Diamonds = Suit::PREFIX . 'diamonds';
+            case Spades = SPADES;
+
+            public function label(): string { return ucfirst($this->value); }
+        }
+
+        enum Status: int {
+            case Active = 1 << 0;
+            case Archived = (1 + 1) * 2;
+        }
+
+        enum Direction {
+            case Up;
+            case Down;
+        }
+        ",
+    )
+    .unwrap();
+
+    let shapes: Vec<EnumShape> = program
+        .iter()
+        .filter_map(EnumShape::from_statement)
+        .collect();
+    assert_eq!(shapes.len(), 3);
+
+    let suit = &shapes[0];
+    assert_eq!(suit.name.value, b"Suit");
+    assert_eq!(suit.backing_type, Some(EnumBackingType::String));
+    assert_eq!(suit.interfaces[0].value, b"HasLabel");
+    assert_eq!(suit.methods[0].name.value, b"label");
+    assert_eq!(
+        suit.case(b"Hearts").unwrap().value,
+        Some(PhpConstValue::String("suit_hearts".into()))
+    );
+    assert_eq!(
+        suit.case(b"Diamonds").unwrap().value,
+        Some(PhpConstValue::String("suit_diamonds".into()))
+    );
+    assert_eq!(suit.case(b"Diamonds").unwrap().attributes.len(), 1);
+    assert_eq!(suit.case(b"Spades").unwrap().value, None);
+
+    let status = &shapes[1];
+    assert_eq!(status.backing_type, Some(EnumBackingType::Int));
+    let values: Vec<Option<PhpConstValue>> =
+        status.cases.iter().map(|case| case.value.clone()).collect();
+    assert_eq!(
+        values,
+        vec![Some(PhpConstValue::Int(1)), Some(PhpConstValue::Int(4))]
+    );
+
+    let direction = &shapes[2];
+    assert_eq!(direction.backing_type, None);
+    assert_eq!(direction.cases.len(), 2);
+    assert!(direction.cases.iter().all(|case| case.value.is_none()));
+}
+
+#[test]
+fn test_enum_lookup_table() {
+    let program = parser::parse(
+        "<?php
+        enum Status: int {
+            const BASE = 10;
+
+            case Active = self::BASE + 1;
+            case Archived = 20;
+            case Deleted = DELETED;
+        }
+
+        enum Suit: string {
+            case Hearts = 'H';
+            case One = '1';
+        }
+
+        enum Direction {
+            case Up;
+        }
+        ",
+    )
+    .unwrap();
+
+    let shapes: Vec<EnumShape> = program
+        .iter()
+        .filter_map(EnumShape::from_statement)
+        .collect();
+
+    let status = &shapes[0];
+    assert_eq!(status.lookup_table.len(), 2);
+    assert_eq!(
+        status.lookup_table[&EnumBackingValue::Int(11)].value,
+        b"Active"
+    );
+
+    let try_from = |shape: &EnumShape, value: PhpConstValue| {
+        shape
+            .try_from(&value)
+            .map(|case| case.name.value.to_string())
+    };
+
+    assert_eq!(
+        try_from(status, PhpConstValue::Int(20)).as_deref(),
+        Some("Archived")
+    );
+    assert_eq!(
+        try_from(status, PhpConstValue::String("11".into())).as_deref(),
+        Some("Active")
+    );
+    assert_eq!(
+        try_from(status, PhpConstValue::Float(20.0)).as_deref(),
+        Some("Archived")
+    );
+    assert_eq!(try_from(status, PhpConstValue::Float(20.5)), None);
+    assert_eq!(try_from(status, PhpConstValue::String("foo".into())), None);
+    assert_eq!(try_from(status, PhpConstValue::Int(30)), None);
+
+    let suit = &shapes[1];
+    assert_eq!(
+        suit.lookup_table
+            .get(&EnumBackingValue::String("H".into()))
+            .map(|name| &name.value),
+        Some(&"Hearts".into())
+    );
+    assert_eq!(
+        try_from(suit, PhpConstValue::Int(1)).as_deref(),
+        Some("One")
+    );
+    assert_eq!(
+        try_from(suit, PhpConstValue::Bool(true)).as_deref(),
+        Some("One")
+    );
+    assert_eq!(try_from(suit, PhpConstValue::Null), None);
+
+    let direction = &shapes[2];
+    assert!(direction.lookup_table.is_empty());
+    assert_eq!(try_from(direction, PhpConstValue::Int(0)), None);
+}
+
+#[test]
+fn test_feature_stats() {
+    let mut stats = FeatureStats::new();
+
+    let mut program = parser::parse(
+        "<?php
+        #[Entity, Table('users')]
+        final class User {
+            public function __construct(
+                #[Id] public readonly int $id,
+                private ?Address $address = null,
+            ) {}
+
+            public function city(): ?string {
+                return $this->address?->city();
+            }
+        }
+
+        enum Status { case Active; }
+
+        $label = match ($status) { Status::Active => 'active', default => 'other' };
+        $users = array_filter($users, fn($user) => $user->city() !== null);
+        json_encode($users, flags: JSON_PRETTY_PRINT);",
+    )
+    .unwrap();
+    stats.add_program(&mut program);
+
+    let mut program = parser::parse("<?php $double = fn($x) => $x * 2;").unwrap();
+    stats.add_program(&mut program);
+
+    assert_eq!(stats.files, 2);
+    assert_eq!(
+        stats.usage(Feature::Attribute),
+        FeatureUsage { count: 3, files: 1 }
+    );
+    assert_eq!(
+        stats.usage(Feature::ArrowFunction),
+        FeatureUsage { count: 2, files: 2 }
+    );
+    assert_eq!(stats.usage(Feature::Match).count, 1);
+    assert_eq!(stats.usage(Feature::Nullsafe).count, 1);
+    assert_eq!(stats.usage(Feature::NamedArgument).count, 1);
+    assert_eq!(stats.usage(Feature::Enum).count, 1);
+    assert_eq!(stats.usage(Feature::ReadonlyProperty).count, 1);
+    assert_eq!(stats.usage(Feature::ReadonlyClass).count, 0);
+    assert_eq!(stats.minimum_version(), PhpVersion::Php81);
+
+    let report = stats.report();
+    assert!(report.contains("attribute                  3       1  PHP 8.0\n"));
+    assert!(report.ends_with("2 files, requiring at least PHP 8.1\n"));
+    assert_eq!(stats.to_json()["features"]["enum"]["count"], 1);
+}
+
+#[test]
+fn test_compare_compat() {
+    let old = Index::from_program(
+        &parser::parse(
+            "<?php
+            namespace App;
+
+            class Client {
+                public const VERSION = '1.0';
+                protected const TIMEOUT = 30;
+                private const SECRET = 'a';
+
+                public string $name;
+                public int $retries = 3;
+
+                public function send(string $body, array $headers = []): Response {}
+                public function close() {}
+                public function flush(): void {}
+                private function sign(): string {}
+            }
+
+            class Response {}
+
+            function connect(string $host): Client {}
+            function disconnect(): void {}",
+        )
+        .unwrap(),
+    );
+
+    let new = Index::from_program(
+        &parser::parse(
+            "<?php
+            namespace App;
+
+            final class Client extends Base {
+                public const VERSION = '2.0';
+                protected const TIMEOUT = 30;
+
+                protected string $name;
+
+                public function send(string $body, array $headers, int $timeout): ?Response {}
+                protected function close() {}
+                private function sign(): int {}
+            }
+
+            class Base {
+                public function flush(): void {}
+            }
+
+            class Response {}
+
+            function connect(string $host, int $port = 80): Client {}",
+        )
+        .unwrap(),
+    );
+
+    let findings: Vec<(&str, String)> = compare(&old, &new)
+        .into_iter()
+        .map(|finding| (finding.kind.name(), finding.message))
+        .collect();
+
+    assert_eq!(
+        findings,
+        vec![
+            ("class-made-final", "`App\\Client` was made final".to_owned()),
+            (
+                "visibility-narrowed",
+                "`App\\Client::$name` was made protected".to_owned()
+            ),
+            (
+                "property-removed",
+                "`App\\Client::$retries` was removed".to_owned()
+            ),
+            (
+                "constant-value-changed",
+                "the value of `App\\Client::VERSION` was changed".to_owned()
+            ),
+            (
+                "visibility-narrowed",
+                "`App\\Client::close()` was made protected".to_owned()
+            ),
+            (
+                "parameter-made-required",
+                "parameter `$headers` of `App\\Client::send()` is no longer optional".to_owned()
+            ),
+            (
+                "required-parameter-added",
+                "required parameter `$timeout` was added to `App\\Client::send()`".to_owned()
+            ),
+            (
+                "return-type-changed",
+                "the return type of `App\\Client::send()` was changed from `Response` to `?Response`"
+                    .to_owned()
+            ),
+            ("function-removed", "`App\\disconnect()` was removed".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn test_function_flags() {
+    fn collect(node: &mut dyn Node, flags: &mut Vec<(&'static str, FunctionFlags)>) {
+        if let Some(found) = FunctionFlags::of(node) {
+            flags.push((node.kind(), found));
+        }
+
+        for child in node.children() {
+            collect(child, flags);
+        }
+    }
+
+    let mut program = parser::parse(
+        "<?php
+        function &numbers() {
+            $inner = function () { yield 1; };
+
+            return $inner;
+        }
+
+        function lines($file) {
+            foreach (file($file) as $line) {
+                yield from explode(' ', $line);
+            }
+        }
+
+        abstract class Repository {
+            abstract public static function all(): iterable;
+
+            public function each() {
+                $callback = static fn () => yield 1;
+            }
+        }
+        ",
+    )
+    .unwrap();
+
+    let mut found = vec![];
+    for statement in program.iter_mut() {
+        collect(statement, &mut found);
+    }
+
+    let flags = |is_generator, returns_by_reference, is_static| FunctionFlags {
+        is_generator,
+        returns_by_reference,
+        is_static,
+    };
+
+    assert_eq!(
+        found,
+        vec![
+            ("FunctionStatement", flags(Some(false), true, false)),
+            ("ClosureExpression", flags(Some(true), false, false)),
+            ("FunctionStatement", flags(Some(true), false, false)),
+            ("AbstractMethod", flags(None, false, true)),
+            ("ConcreteMethod", flags(Some(false), false, false)),
+            ("ArrowFunctionExpression", flags(Some(true), false, true)),
+        ]
+    );
+}
+
+#[test]
+fn test_captures() {
+    fn collect(node: &mut dyn Node, captures: &mut Vec<Captures>) {
+        if let Some(found) = Captures::of(node) {
+            captures.push(found);
+        }
+
+        for child in node.children() {
+            collect(child, captures);
+        }
+    }
+
+    let mut program = parser::parse(
+        "<?php
+        $a = fn ($x) => $x + $y + $this->z + fn () => $w + $y;
+        $b = function ($x) use ($y) {
+            $inner = function () use ($v) { return $unrelated; };
+
+            return $x . $y . $$name;
+        };",
+    )
+    .unwrap();
+
+    let mut captures = vec![];
+    for statement in program.iter_mut() {
+        collect(statement, &mut captures);
+    }
+
+    let variables = |captures: &Captures| -> Vec<String> {
+        captures
+            .variables
+            .iter()
+            .map(|variable| variable.to_string())
+            .collect()
+    };
+
+    assert_eq!(captures.len(), 4);
+    assert_eq!(variables(&captures[0]), vec!["$y", "$w"]);
+    assert!(!captures[0].dynamic);
+    assert_eq!(variables(&captures[1]), vec!["$w", "$y"]);
+    assert_eq!(variables(&captures[2]), vec!["$inner", "$v", "$y", "$name"]);
+    assert!(captures[2].dynamic);
+    assert_eq!(variables(&captures[3]), vec!["$unrelated"]);
+}
+
+#[test]
+fn test_declares() {
+    let program = parser::parse(
+        "<?php
+        declare(ticks=1);
+        declare(strict_types=1);
+
+        namespace Foo;
+
+        declare(encoding='UTF-8');
+        ",
+    )
+    .unwrap();
+
+    let keys: Vec<String> = program
+        .declares()
+        .iter()
+        .map(|entry| entry.key.to_string())
+        .collect();
+
+    assert_eq!(keys, vec!["ticks", "strict_types", "encoding"]);
+    assert!(program.strict_types());
+
+    let program = parser::parse("<?php declare(strict_types=0); echo 1;").unwrap();
+    assert!(!program.strict_types());
+
+    let program = parser::parse("<?php echo 1;").unwrap();
+    assert!(!program.strict_types());
+}
+
+#[test]
+fn test_scopes() {
+    let program = parser::parse(
+        "<?php
+        $a = $b;
+        ['list' => $c, 'key' => $d] = $a;
+        foreach ($c as $key => $value) {}
+        echo isset($e) ? $e : $f ?? $GLOBALS['g'], $h;
+        preg_match('/a/', $a, $matches);
+
+        function foo($x) {
+            global $config;
+            static $count = 0;
+
+            try {
+                $y .= $x . $config . $count . $matches;
+            } catch (Exception $e) {
+                return $e;
+            }
+
+            $closure = function ($z) use ($x, &$later, $missing) {
+                return $z . $x . $later . $w;
+            };
+
+            return fn ($z) => $x . $y . $z . $closure . $later . $v;
+        }
+
+        class Foo {
+            public $property;
+
+            public function bar() {
+                return $this->property . self::$property . $property;
+            }
+        }
+
+        function dynamic() {
+            extract($_GET);
+
+            return $anything;
+        }",
+    )
+    .unwrap();
+
+    let scopes = scopes(&program);
+    let names = |variables: &[SimpleVariable]| -> Vec<String> {
+        variables
+            .iter()
+            .map(|variable| variable.name.to_string())
+            .collect()
+    };
+
+    assert_eq!(
+        scopes.iter().map(|scope| scope.kind).collect::<Vec<_>>(),
+        vec![
+            ScopeKind::File,
+            ScopeKind::Function,
+            ScopeKind::Closure,
+            ScopeKind::ArrowFunction,
+            ScopeKind::Method,
+            ScopeKind::Function,
+        ]
+    );
+
+    let file = &scopes[0];
+    assert_eq!(file.span, None);
+    assert_eq!(names(&file.undefined), vec!["$b", "$h"]);
+    assert!(file.defines(b"$d") && file.defines(b"$value") && file.defines(b"$matches"));
+    assert!(!file.defines(b"$e"));
+
+    let function = &scopes[1];
+    assert_eq!(function.span.unwrap().line, 8);
+    assert_eq!(
+        names(&function.undefined),
+        vec!["$y", "$matches", "$missing"]
+    );
+    assert_eq!(
+        function
+            .definitions
+            .iter()
+            .map(|definition| (definition.name.to_string(), definition.kind))
+            .collect::<Vec<_>>(),
+        vec![
+            ("$x".to_string(), DefinitionKind::Parameter),
+            ("$config".to_string(), DefinitionKind::Global),
+            ("$count".to_string(), DefinitionKind::Static),
+            ("$y".to_string(), DefinitionKind::Assignment),
+            ("$e".to_string(), DefinitionKind::Catch),
+            ("$later".to_string(), DefinitionKind::Reference),
+            ("$closure".to_string(), DefinitionKind::Assignment),
+        ]
+    );
+
+    let closure = &scopes[2];
+    assert_eq!(names(&closure.undefined), vec!["$w"]);
+    assert!(closure.defines(b"$later") && closure.defines(b"$missing"));
+
+    let arrow_function = &scopes[3];
+    assert_eq!(names(&arrow_function.undefined), vec!["$v"]);
+    assert_eq!(
+        names(&arrow_function.reads),
+        vec!["$x", "$y", "$z", "$closure", "$later", "$v"]
+    );
+
+    let method = &scopes[4];
+    assert_eq!(names(&method.reads), vec!["$property"]);
+    assert_eq!(names(&method.undefined), vec!["$property"]);
+
+    let dynamic = &scopes[5];
+    assert!(dynamic.dynamic);
+    assert!(dynamic.undefined.is_empty());
+}
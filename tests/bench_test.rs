@@ -0,0 +1,27 @@
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parse_bench;
+use php_parser_rs::parser::bench::count_statements;
+use php_parser_rs::parser::parse;
+
+#[test]
+fn test_parse_bench_counts() {
+    let code = "<?php if ($a) { echo 1; } echo 2;";
+    let result = parse_bench(code).unwrap();
+
+    assert_eq!(result.bytes, code.len());
+    assert_eq!(result.tokens, Lexer::new().tokenize(code).unwrap().len());
+    assert_eq!(result.statements, 5);
+    assert_eq!(result.total_time(), result.lex_time + result.parse_time);
+    assert!(result.tokens_per_second() > 0.0);
+    assert!(result.statements_per_second() > 0.0);
+}
+
+#[test]
+fn test_count_statements_in_declarations() {
+    let program =
+        parse("<?php namespace A { class B { function c() { return 1; } } function d() { e(); } }")
+            .unwrap();
+
+    // the opening tag, the namespace, the class, `return`, the function and the call.
+    assert_eq!(count_statements(&program), 6);
+}
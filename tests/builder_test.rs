@@ -0,0 +1,85 @@
+use php_parser_rs::builder::*;
+use php_parser_rs::parser;
+use php_parser_rs::parser::ast::Statement;
+
+// The statements parsed from the code, serialized without their spans and comments so
+// that they can be compared with built ones.
+fn parsed(code: &str) -> serde_json::Value {
+    let program = parser::parse(code).unwrap();
+
+    without_spans(serde_json::to_value(&program[1..]).unwrap())
+}
+
+fn built(statements: Vec<Statement>) -> serde_json::Value {
+    without_spans(serde_json::to_value(statements).unwrap())
+}
+
+fn without_spans(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Array(values) => values.into_iter().map(without_spans).collect(),
+        serde_json::Value::Object(object) => {
+            let is_span = object.len() == 4
+                && ["line", "column", "position", "end_position"]
+                    .iter()
+                    .all(|key| object.contains_key(*key));
+
+            if is_span {
+                return serde_json::Value::Null;
+            }
+
+            object
+                .into_iter()
+                .filter(|(key, _)| key != "comments")
+                .map(|(key, value)| (key, without_spans(value)))
+                .collect()
+        }
+        value => value,
+    }
+}
+
+#[test]
+fn test_expressions() {
+    assert_eq!(
+        built(vec![
+            statement(assign(
+                variable("result"),
+                method_call(
+                    variable("$client"),
+                    "send",
+                    vec![new(name("Request"), vec![string("GET"), int(3)])],
+                ),
+            )),
+            statement(call(
+                name("foo"),
+                vec![float(1.0), bool(true), null(), array(vec![int(1), int(2)])],
+            )),
+            statement(static_call(
+                name("Foo"),
+                "bar",
+                vec![class_constant(name("Foo"), "BAZ")],
+            )),
+            statement(concat(
+                property(variable("this"), "name"),
+                index(variable("parts"), int(0)),
+            )),
+        ]),
+        parsed(
+            "<?php
+$result = $client->send(new Request('GET', 3));
+foo(1.0, true, null, [1, 2]);
+Foo::bar(Foo::BAZ);
+$this->name . $parts[0];"
+        ),
+    );
+}
+
+#[test]
+fn test_statements() {
+    assert_eq!(
+        built(vec![
+            echo(vec![string("a"), variable("b")]),
+            block(vec![r#return(Some(int(1))), r#return(None)]),
+        ]),
+        parsed("<?php echo 'a', $b; { return 1; return; }"),
+    );
+}
@@ -0,0 +1,240 @@
+use std::fs;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Output;
+use std::process::Stdio;
+
+// A fresh directory for the files of a test.
+fn directory(name: &str) -> PathBuf {
+    let directory = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("cli_test")
+        .join(name);
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+
+    directory
+}
+
+fn run(arguments: &[&std::ffi::OsStr]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_php-parser-rs"))
+        .args(arguments)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_crlf_file() {
+    let file = directory("crlf").join("code.php");
+    fs::write(&file, "<?php\r\n$é = 'ü'; $a = ;\r\n").unwrap();
+
+    let output = run(&["--minify".as_ref(), file.as_os_str()]);
+    assert!(output.status.success());
+    assert_eq!(output.stdout, "<?php $é='ü';$a=;".as_bytes());
+
+    let output = run(&[file.as_os_str()]);
+    assert!(!output.status.success());
+
+    let report = String::from_utf8(output.stdout).unwrap();
+    // columns count characters, not bytes.
+    assert!(
+        report.contains(&format!("{}:2:16", file.display())),
+        "{}",
+        report
+    );
+    assert!(!report.contains('\r'), "{}", report);
+}
+
+#[test]
+fn test_non_utf8_contents() {
+    let file = directory("latin1").join("code.php");
+    fs::write(&file, b"<?php\n$a = '\xe9t\xe9';\n").unwrap();
+
+    let output = run(&["--json".as_ref(), file.as_os_str()]);
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_minify_non_utf8_contents() {
+    let file = directory("minify_latin1").join("code.php");
+    fs::write(&file, b"<?php\n$a = '\xff\xfe';\n").unwrap();
+
+    let output = run(&["--minify".as_ref(), file.as_os_str()]);
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"<?php $a='\xff\xfe';");
+}
+
+#[test]
+fn test_refactor_non_utf8_contents() {
+    let file = directory("refactor_latin1").join("code.php");
+    fs::write(&file, b"<?php\n$a = fn () => '\xff' . $b;\n").unwrap();
+
+    let output = run(&[
+        "--refactor".as_ref(),
+        "arrow-function-to-closure".as_ref(),
+        file.as_os_str(),
+    ]);
+    assert!(output.status.success());
+    assert_eq!(
+        output.stdout,
+        b"<?php\n$a = function () use ($b) { return '\xff' . $b; };\n"
+    );
+}
+
+#[test]
+fn test_json_with_version() {
+    let file = directory("json_with_version").join("code.php");
+    fs::write(&file, "<?php\n$a = 1;\n").unwrap();
+
+    let output = run(&["--json".as_ref(), file.as_os_str()]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["version_info"]["version"], env!("CARGO_PKG_VERSION"));
+    assert!(json["ast"].is_array(), "{}", json);
+
+    let output = run(&[
+        "--json".as_ref(),
+        "--without-version".as_ref(),
+        file.as_os_str(),
+    ]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json.is_array(), "{}", json);
+}
+
+#[test]
+fn test_windows_style_file_name() {
+    let file = directory("backslash").join("src\\Foo.php");
+    fs::write(&file, "<?php\nfoo(;\n").unwrap();
+
+    let output = run(&[file.as_os_str()]);
+    assert!(!output.status.success());
+
+    let report = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        report.contains(&format!("{}:2:5", file.display())),
+        "{}",
+        report
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_non_utf8_file_name() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let file = directory("non_utf8_name").join(OsStr::from_bytes(b"caf\xe9.php"));
+    fs::write(&file, "<?php\nfoo(;\n").unwrap();
+
+    let output = run(&[file.as_os_str()]);
+    assert!(!output.status.success());
+
+    let report = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        report.contains(&format!("{}:2:5", file.display())),
+        "{}",
+        report
+    );
+}
+
+#[test]
+fn test_nikic_json_format() {
+    let file = directory("nikic").join("code.php");
+    fs::write(&file, "<?php\necho 1;\n").unwrap();
+
+    let output = run(&["--format".as_ref(), "nikic-json".as_ref(), file.as_os_str()]);
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["nodeType"], "Stmt_Echo");
+    assert_eq!(json[0]["exprs"][0]["nodeType"], "Scalar_Int");
+}
+
+#[test]
+fn test_error_report() {
+    let file = directory("report").join("code.php");
+    fs::write(&file, "<?php\nif ($a):\n    echo $a;\n}\n").unwrap();
+
+    let output = run(&[file.as_os_str()]);
+    assert!(!output.status.success());
+
+    // the output is not a terminal, so it is not colored.
+    let report = String::from_utf8(output.stdout).unwrap();
+    assert!(!report.contains('\x1b'), "{}", report);
+    assert!(report.contains("4 │ }"), "{}", report);
+    assert!(report.contains("did you mean `endif;`?"), "{}", report);
+}
+
+#[test]
+fn test_watch_report() {
+    let file = directory("watch").join("code.php");
+    fs::write(&file, "<?php\nif ($a):\n    echo $a;\n}\n").unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_php-parser-rs"))
+        .args([file.as_os_str(), "--watch".as_ref()])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut report = String::new();
+    for line in BufReader::new(child.stdout.take().unwrap()).lines() {
+        let line = line.unwrap();
+        report.push_str(&line);
+        report.push('\n');
+        if line.contains("Watching for changes...") {
+            break;
+        }
+    }
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    // only the screen is cleared, the output is not a terminal, so it is not colored.
+    let report = report.strip_prefix("\x1b[2J\x1b[H").unwrap_or(&report);
+    assert!(!report.contains('\x1b'), "{}", report);
+    assert!(report.contains("did you mean `endif;`?"), "{}", report);
+    assert!(
+        report.contains("1 files, 1 with syntax errors"),
+        "{}",
+        report
+    );
+}
+
+#[test]
+fn test_error_format() {
+    let file = directory("error_format").join("code.php");
+    fs::write(&file, "<?php\n$a = 1\n$b = 2;\n").unwrap();
+
+    let output = run(&["--error-format".as_ref(), "json".as_ref(), file.as_os_str()]);
+    assert!(!output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["file"], file.display().to_string());
+    assert_eq!(json[0]["code"], "E005");
+    assert_eq!(json[0]["severity"], "error");
+    assert_eq!(json[0]["line"], 3);
+
+    let output = run(&[
+        "--error-format".as_ref(),
+        "sarif".as_ref(),
+        file.as_os_str(),
+    ]);
+    assert!(!output.status.success());
+
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+
+    let result = &sarif["runs"][0]["results"][0];
+    assert_eq!(result["ruleId"], "E005");
+    assert_eq!(result["level"], "error");
+    assert_eq!(
+        result["locations"][0]["physicalLocation"]["region"]["startLine"],
+        3
+    );
+    assert_eq!(
+        result["fixes"][0]["artifactChanges"][0]["replacements"][0]["insertedContent"]["text"],
+        ";"
+    );
+}
@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use php_parser_rs::config::Config;
+use php_parser_rs::config::ConfigResolver;
+use php_parser_rs::config::CONFIG_FILE_NAME;
+use php_parser_rs::lexer::keywords::PhpVersion;
+use php_parser_rs::lint::config::RuleLevel;
+
+#[test]
+fn test_config() {
+    let config = Config::from_toml(
+        r#"
+php-version = "8.0"
+short-tags = false
+
+[lint.rules]
+nested-ternary = "off"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(config.php_version(), PhpVersion::Php80);
+    assert!(!config.parser_config().lexer.short_tags);
+    assert_eq!(config.lint.level("nested-ternary"), Some(RuleLevel::Off));
+
+    assert_eq!(Config::default().php_version(), PhpVersion::LATEST);
+    assert!(Config::from_toml("php-version = \"5.6\"").is_err());
+    assert!(Config::from_toml("unknown = true").is_err());
+}
+
+#[test]
+fn test_config_resolver() {
+    let root = std::env::temp_dir().join(format!("php-parser-rs-config-{}", std::process::id()));
+    let legacy = root.join("packages/legacy");
+    let modern = root.join("packages/modern");
+    let standalone = root.join("standalone");
+    for directory in [&legacy, &modern, &standalone] {
+        std::fs::create_dir_all(directory).unwrap();
+    }
+
+    let write = |directory: &PathBuf, config: &str| {
+        std::fs::write(directory.join(CONFIG_FILE_NAME), config).unwrap();
+    };
+
+    write(
+        &root,
+        "root = true\nphp-version = \"8.2\"\n\n[lint.rules]\nnested-ternary = \"error\"\ncall-arguments = \"off\"\n",
+    );
+    write(
+        &legacy,
+        "php-version = \"7.4\"\n\n[lint]\nbaseline = \"baseline.json\"\n\n[lint.rules]\nnested-ternary = \"info\"\n",
+    );
+    write(&standalone, "root = true\nshort-tags = false\n");
+
+    let mut resolver = ConfigResolver::new();
+
+    let config = resolver.resolve(&legacy.join("index.php")).unwrap();
+    assert_eq!(config.php_version(), PhpVersion::Php74);
+    assert_eq!(config.lint.level("nested-ternary"), Some(RuleLevel::Info));
+    assert_eq!(config.lint.level("call-arguments"), Some(RuleLevel::Off));
+    assert_eq!(
+        config.lint.baseline,
+        Some(legacy.canonicalize().unwrap().join("baseline.json"))
+    );
+
+    let config = resolver.resolve(&modern.join("index.php")).unwrap();
+    assert_eq!(config.php_version(), PhpVersion::Php82);
+    assert_eq!(config.lint.level("nested-ternary"), Some(RuleLevel::Error));
+    assert_eq!(config.lint.baseline, None);
+
+    let config = resolver.resolve(&standalone.join("index.php")).unwrap();
+    assert_eq!(config.php_version(), PhpVersion::LATEST);
+    assert!(!config.parser_config().lexer.short_tags);
+    assert!(config.lint.rules.is_empty());
+
+    write(&modern, "php-version = \"9.0\"\n");
+    assert!(ConfigResolver::new()
+        .resolve(&modern.join("index.php"))
+        .is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,67 @@
+use php_parser_rs::diagnostic::diagnostics_to_json;
+use php_parser_rs::diagnostic::Diagnostic;
+use php_parser_rs::diagnostic::LabelStyle;
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parse;
+use php_parser_rs::snippet::ColumnUnit;
+
+#[test]
+fn test_syntax_error_diagnostic() {
+    let code = "<?php\n$a = \"\\u{zz}\";\n";
+    let error = Lexer::new().tokenize(code).unwrap_err();
+    let diagnostic = Diagnostic::from(&error);
+
+    assert_eq!(diagnostic.code, "E001");
+    assert_eq!(diagnostic.message, "syntax error, invalid unicode escape");
+    assert_eq!(diagnostic.labels.len(), 1);
+    assert_eq!(diagnostic.labels[0].style, LabelStyle::Primary);
+    assert_eq!(diagnostic.notes.len(), 1);
+
+    let report = diagnostic
+        .report(code, Some("code.php"), false, true)
+        .unwrap();
+    assert!(report.contains("2 | $a = \"\\u{zz}\";"), "{}", report);
+    assert!(report.contains("Note: unicode escapes"), "{}", report);
+}
+
+#[test]
+fn test_parse_error_suggestions() {
+    let code = "<?php\n$a = 1\n$b = 2;\n";
+    let diagnostics = parse(code).unwrap_err().diagnostics();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].suggestions[0].description,
+        "did you forget a `;` on line 2?"
+    );
+
+    let fixed = php_parser_rs::refactor::apply(code.as_bytes(), &diagnostics[0].suggestions);
+    assert!(parse(&fixed).is_ok());
+
+    // the fix for a `}` closing an alternative block.
+    let code = "<?php\nwhile ($a):\n    $a--;\n}\n";
+    let diagnostics = parse(code).unwrap_err().diagnostics();
+
+    let fixed = php_parser_rs::refactor::apply(code.as_bytes(), &diagnostics[0].suggestions);
+    assert_eq!(fixed, b"<?php\nwhile ($a):\n    $a--;\nendwhile;\n");
+}
+
+#[test]
+fn test_diagnostics_to_json() {
+    let code = "<?php\n$é = 1\n$b = 2;\n";
+    let diagnostics = parse(code).unwrap_err().diagnostics();
+
+    let json = diagnostics_to_json("code.php", code.as_bytes(), &diagnostics, ColumnUnit::Bytes);
+    assert_eq!(json[0]["file"], "code.php");
+    assert_eq!(json[0]["line"], 3);
+    assert_eq!(json[0]["column"], 1);
+    assert_eq!(json[0]["suggestions"][0]["edits"][0]["position"], 13);
+
+    let json = diagnostics_to_json(
+        "code.php",
+        code.as_bytes(),
+        &diagnostics,
+        ColumnUnit::CodePoints,
+    );
+    assert_eq!(json[0]["end_column"], 3);
+}
@@ -0,0 +1,73 @@
+use php_parser_rs::lexer::byte_string::ByteString;
+use php_parser_rs::parser;
+use php_parser_rs::parser::ast::Expression;
+use php_parser_rs::parser::ast::ExpressionStatement;
+use php_parser_rs::parser::ast::LiteralStringPart;
+use php_parser_rs::parser::ast::Statement;
+use php_parser_rs::parser::ast::StringPart;
+
+fn expression(code: &str) -> Expression {
+    let program = parser::parse(code).unwrap();
+
+    match program.into_iter().nth(1) {
+        Some(Statement::Expression(ExpressionStatement { expression, .. })) => expression,
+        statement => panic!("expected an expression statement, got {:?}", statement),
+    }
+}
+
+fn literal(part: &StringPart) -> &ByteString {
+    match part {
+        StringPart::Literal(LiteralStringPart { value, .. }) => value,
+        part => panic!("expected a literal part, got {:?}", part),
+    }
+}
+
+#[test]
+fn test_heredoc_indentation_stripped() {
+    let Expression::Heredoc(heredoc) =
+        expression("<?php\n<<<EOF\n    Hello, {$name}!\n      Indented\n\n    EOF;\n")
+    else {
+        panic!("expected a heredoc");
+    };
+
+    assert_eq!(heredoc.indentation_amount, 4);
+    assert_eq!(literal(&heredoc.parts[0]), &ByteString::from("    Hello, "));
+    assert_eq!(
+        literal(&heredoc.parts[2]),
+        &ByteString::from("!\n      Indented\n")
+    );
+
+    let stripped = heredoc.indentation_stripped();
+    assert_eq!(stripped.len(), 3);
+    assert_eq!(literal(&stripped[0]), &ByteString::from("Hello, "));
+    assert_eq!(literal(&stripped[2]), &ByteString::from("!\n  Indented\n"));
+}
+
+#[test]
+fn test_nowdoc_indentation_stripped() {
+    let Expression::Nowdoc(nowdoc) =
+        expression("<?php\n<<<'EOF'\n\t\tHello,\n\t\t\tworld!\n\t\tEOF;\n")
+    else {
+        panic!("expected a nowdoc");
+    };
+
+    assert_eq!(nowdoc.value, ByteString::from("\t\tHello,\n\t\t\tworld!"));
+    assert_eq!(
+        nowdoc.indentation_stripped(),
+        ByteString::from("Hello,\n\tworld!")
+    );
+
+    // without an indented closing label, nothing is stripped.
+    let Expression::Nowdoc(nowdoc) = expression("<?php\n<<<'EOF'\n  Hello\nEOF;\n") else {
+        panic!("expected a nowdoc");
+    };
+
+    assert_eq!(nowdoc.indentation_stripped(), ByteString::from("  Hello"));
+}
+
+#[test]
+fn test_doc_string_indentation_is_validated_on_every_line() {
+    assert!(parser::parse("<?php\n<<<EOF\n    a\n  b\n    EOF;\n").is_err());
+    assert!(parser::parse("<?php\n<<<EOF\n    {$a}\n  b\n    EOF;\n").is_err());
+    assert!(parser::parse("<?php\n<<<'EOF'\n    a\n\tb\n    EOF;\n").is_err());
+}
@@ -0,0 +1,406 @@
+use php_parser_rs::docblock::docblock_comment;
+use php_parser_rs::docblock::inheritance::class_documentation;
+use php_parser_rs::docblock::inheritance::member_documentation;
+use php_parser_rs::docblock::inheritance::MemberKind;
+use php_parser_rs::docblock::parse_docblock;
+use php_parser_rs::docblock::parse_type;
+use php_parser_rs::docblock::parse_type_prefix;
+use php_parser_rs::docblock::ArrayShapeEntry;
+use php_parser_rs::docblock::ArrayShapeKey;
+use php_parser_rs::docblock::ArrayShapeKind;
+use php_parser_rs::docblock::DocblockType;
+use php_parser_rs::docblock::TagKind;
+use php_parser_rs::docblock::TemplateVariance;
+use php_parser_rs::index::Index;
+use php_parser_rs::parser;
+use php_parser_rs::parser::ast::Statement;
+
+fn named(name: &str) -> DocblockType {
+    DocblockType::Named(name.to_owned())
+}
+
+fn entry(key: Option<&str>, optional: bool, value: DocblockType) -> ArrayShapeEntry {
+    ArrayShapeEntry {
+        key: key.map(|key| ArrayShapeKey::String(key.to_owned())),
+        optional,
+        value,
+    }
+}
+
+#[test]
+fn test_parse_array_shapes() {
+    assert_eq!(
+        parse_type("array{id: int, name?: string, 'first-name': ?string, 3: list{int, Foo[]}}"),
+        Some(DocblockType::ArrayShape {
+            kind: ArrayShapeKind::Array,
+            entries: vec![
+                entry(Some("id"), false, named("int")),
+                entry(Some("name"), true, named("string")),
+                entry(
+                    Some("first-name"),
+                    false,
+                    DocblockType::Nullable(Box::new(named("string")))
+                ),
+                ArrayShapeEntry {
+                    key: Some(ArrayShapeKey::Integer(3)),
+                    optional: false,
+                    value: DocblockType::ArrayShape {
+                        kind: ArrayShapeKind::List,
+                        entries: vec![
+                            entry(None, false, named("int")),
+                            entry(None, false, DocblockType::Array(Box::new(named("Foo")))),
+                        ],
+                        sealed: true,
+                    },
+                },
+            ],
+            sealed: true,
+        })
+    );
+
+    assert_eq!(
+        parse_type("array{type: 'user'|'admin', ...}"),
+        Some(DocblockType::ArrayShape {
+            kind: ArrayShapeKind::Array,
+            entries: vec![entry(
+                Some("type"),
+                false,
+                DocblockType::Union(vec![
+                    DocblockType::String("user".to_owned()),
+                    DocblockType::String("admin".to_owned()),
+                ])
+            )],
+            sealed: false,
+        })
+    );
+
+    assert_eq!(parse_type("array{id: int"), None);
+    assert_eq!(parse_type("Foo{id: int}"), None);
+}
+
+#[test]
+fn test_parse_generic_types() {
+    assert_eq!(
+        parse_type("array<array-key, list<int>>|iterable<Foo>"),
+        Some(DocblockType::Union(vec![
+            DocblockType::Generic {
+                name: "array".to_owned(),
+                arguments: vec![
+                    named("array-key"),
+                    DocblockType::Generic {
+                        name: "list".to_owned(),
+                        arguments: vec![named("int")],
+                    },
+                ],
+            },
+            DocblockType::Generic {
+                name: "iterable".to_owned(),
+                arguments: vec![named("Foo")],
+            },
+        ]))
+    );
+
+    assert_eq!(
+        parse_type("class-string<Foo>|class-string"),
+        Some(DocblockType::Union(vec![
+            DocblockType::ClassString(Some(Box::new(named("Foo")))),
+            DocblockType::ClassString(None),
+        ]))
+    );
+
+    assert_eq!(
+        parse_type("key-of<Foo::TYPES>"),
+        Some(DocblockType::KeyOf(Box::new(DocblockType::ClassConstant {
+            class: "Foo".to_owned(),
+            constant: "TYPES".to_owned(),
+        })))
+    );
+
+    assert_eq!(
+        parse_type("value-of<Status>&Countable"),
+        Some(DocblockType::Intersection(vec![
+            DocblockType::ValueOf(Box::new(named("Status"))),
+            named("Countable"),
+        ]))
+    );
+
+    assert_eq!(parse_type("key-of<A, B>"), None);
+}
+
+#[test]
+fn test_parse_type_prefix() {
+    let (r#type, rest) = parse_type_prefix(" array<int, string> $names The names.").unwrap();
+
+    assert_eq!(
+        r#type,
+        DocblockType::Generic {
+            name: "array".to_owned(),
+            arguments: vec![named("int"), named("string")],
+        }
+    );
+    assert_eq!(rest, "$names The names.");
+
+    let (r#type, rest) = parse_type_prefix("int &$count").unwrap();
+
+    assert_eq!(r#type, named("int"));
+    assert_eq!(rest, "&$count");
+}
+
+#[test]
+fn test_parse_deeply_nested_types() {
+    let nested = |depth: usize| format!("{}int{}", "(".repeat(depth), ")".repeat(depth));
+
+    assert_eq!(parse_type(&nested(32)), Some(named("int")));
+    assert_eq!(parse_type(&nested(100_000)), None);
+
+    let generics = format!("{}int{}", "array<".repeat(100_000), ">".repeat(100_000));
+    assert_eq!(parse_type(&generics), None);
+
+    let nullables = format!("{}int", "?".repeat(100_000));
+    assert_eq!(parse_type(&nullables), None);
+}
+
+#[test]
+fn test_inherit_doc() {
+    let program = parser::parse(
+        "<?php
+        namespace App;
+
+        interface Shape {
+            /** The area of the shape. */
+            public function area(): float;
+
+            /**
+             * The name of the shape.
+             *
+             * @return string
+             */
+            public function name(): string;
+        }
+
+        abstract class Base implements Shape {
+            /** @inheritDoc */
+            public function name(): string { return static::class; }
+        }
+
+        class Square extends Base {
+            /** The length of each side. */
+            public float $side = 1.0;
+
+            public function area(): float { return $this->side ** 2; }
+
+            /**
+             * {@inheritDoc} Always `square`.
+             */
+            public function name(): string { return 'square'; }
+        }",
+    )
+    .unwrap();
+
+    let index = Index::from_program(&program);
+
+    let area = member_documentation(&index, b"App\\Square", MemberKind::Method, b"area").unwrap();
+    assert_eq!(area.declared_in.to_string(), "App\\Shape");
+    assert_eq!(area.docblock.to_string(), "/** The area of the shape. */");
+
+    let name = member_documentation(&index, b"App\\Base", MemberKind::Method, b"NAME").unwrap();
+    assert_eq!(name.declared_in.to_string(), "App\\Shape");
+
+    let name = member_documentation(&index, b"App\\Square", MemberKind::Method, b"name").unwrap();
+    assert_eq!(name.declared_in.to_string(), "App\\Square");
+    assert_eq!(
+        name.docblock.to_string(),
+        "/**\n             * The name of the shape. Always `square`.\n             */"
+    );
+
+    assert!(member_documentation(&index, b"App\\Square", MemberKind::Method, b"missing").is_none());
+
+    let members = class_documentation(&index, b"App\\Square");
+    let members: Vec<(MemberKind, String, Option<String>)> = members
+        .into_iter()
+        .map(|member| {
+            (
+                member.kind,
+                member.name.to_string(),
+                member
+                    .documentation
+                    .map(|documentation| documentation.declared_in.to_string()),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        members,
+        vec![
+            (
+                MemberKind::Property,
+                "side".to_owned(),
+                Some("App\\Square".to_owned())
+            ),
+            (
+                MemberKind::Method,
+                "area".to_owned(),
+                Some("App\\Shape".to_owned())
+            ),
+            (
+                MemberKind::Method,
+                "name".to_owned(),
+                Some("App\\Square".to_owned())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_declaration_docblocks() {
+    let program = parser::parse(
+        "<?php
+        namespace App;
+
+        /** License header, followed by code. */
+        $config = [/** Not a declaration. */ 'debug' => true];
+
+        /** A shape. */
+        #[Immutable]
+        final class Square {
+            /** The length of each side. */
+            public float $side = 1.0;
+
+            public function area(): float { return $this->side ** 2; }
+        }
+
+        /** Create a square. */
+        function square(float $side): Square { return new Square(); }
+
+        function undocumented() {}",
+    )
+    .unwrap();
+
+    let index = Index::from_program(&program);
+
+    let square = index.class(b"App\\Square").unwrap();
+    assert_eq!(
+        square.docblock.as_ref().unwrap().to_string(),
+        "/** A shape. */"
+    );
+    assert_eq!(
+        square
+            .property(b"side")
+            .unwrap()
+            .docblock
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "/** The length of each side. */"
+    );
+    assert!(square.method(b"area").unwrap().docblock.is_none());
+
+    let function = index.function(b"App\\square").unwrap();
+    assert_eq!(
+        function.docblock.as_ref().unwrap().to_string(),
+        "/** Create a square. */"
+    );
+    assert!(index
+        .function(b"App\\undocumented")
+        .unwrap()
+        .docblock
+        .is_none());
+}
+
+#[test]
+fn test_parse_docblock_tags() {
+    let code = "<?php
+/**
+ * Map the values.
+ *
+ * @template-covariant T of object
+ * @psalm-param array{
+ *     id: int,
+ *     name?: string,
+ * } &...$items The items.
+ * @param $callback
+ * @return list<T>
+ * @throws \\RuntimeException When it fails.
+ * @deprecated Use `map()` instead.
+ */
+function f() {}
+";
+
+    let program = parser::parse(code).unwrap();
+    let Statement::Function(function) = &program[1] else {
+        panic!("expected a function");
+    };
+    let docblock = parse_docblock(docblock_comment(&function.comments).unwrap());
+
+    assert_eq!(docblock.description, "Map the values.");
+    assert_eq!(
+        docblock
+            .tags
+            .iter()
+            .map(|tag| tag.name.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "template-covariant",
+            "psalm-param",
+            "param",
+            "return",
+            "throws",
+            "deprecated"
+        ]
+    );
+
+    assert_eq!(
+        docblock.tags[0].kind,
+        TagKind::Template {
+            name: "T".to_owned(),
+            variance: TemplateVariance::Covariant,
+            bound: Some(named("object")),
+            description: String::new(),
+        }
+    );
+
+    let items = docblock.param("$items").unwrap();
+    assert_eq!(
+        items.kind,
+        TagKind::Param {
+            r#type: Some(DocblockType::ArrayShape {
+                kind: ArrayShapeKind::Array,
+                entries: vec![
+                    entry(Some("id"), false, named("int")),
+                    entry(Some("name"), true, named("string")),
+                ],
+                sealed: true,
+            }),
+            variable: "$items".to_owned(),
+            by_reference: true,
+            variadic: true,
+            description: "The items.".to_owned(),
+        }
+    );
+    // the tag spans the lines of its type, and its span is relative to the file.
+    assert_eq!((items.span.line, items.span.column), (6, 4));
+    assert_eq!(
+        &code[items.span.position..items.span.end_position],
+        "@psalm-param array{\n *     id: int,\n *     name?: string,\n * } &...$items The items."
+    );
+
+    assert!(matches!(
+        &docblock.tags[2].kind,
+        TagKind::Param { r#type: None, variable, .. } if variable == "$callback"
+    ));
+    assert_eq!(docblock.tags_named("param").count(), 2);
+    assert_eq!(
+        docblock.tags[4].kind,
+        TagKind::Throws {
+            r#type: named("\\RuntimeException"),
+            description: "When it fails.".to_owned(),
+        }
+    );
+    assert_eq!(
+        docblock.tags[5].kind,
+        TagKind::Other {
+            body: "Use `map()` instead.".to_owned()
+        }
+    );
+    assert_eq!(docblock.tags[5].span.line, 13);
+}
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+
+use php_parser_rs::evaluator::const_expr;
+use php_parser_rs::evaluator::const_expr_with;
+use php_parser_rs::evaluator::execute;
+use php_parser_rs::evaluator::Evaluator;
+use php_parser_rs::evaluator::Flow;
+use php_parser_rs::evaluator::IncludeKind;
+use php_parser_rs::evaluator::PhpArrayKey;
+use php_parser_rs::evaluator::PhpConstValue;
+use php_parser_rs::parser;
+use php_parser_rs::parser::ast::literals::IntegerValue;
+use php_parser_rs::parser::ast::literals::Literal;
+use php_parser_rs::parser::ast::operators::ArithmeticOperationExpression;
+use php_parser_rs::parser::ast::operators::AssignmentOperationExpression;
+use php_parser_rs::parser::ast::operators::ComparisonOperationExpression;
+use php_parser_rs::parser::ast::variables::Variable;
+use php_parser_rs::parser::ast::Expression;
+use php_parser_rs::parser::ast::FunctionCallExpression;
+use php_parser_rs::parser::ast::MagicConstantExpression;
+use php_parser_rs::parser::ast::Statement;
+
+// An engine that only knows about integers, variables, and a few operators.
+#[derive(Default)]
+struct Integers {
+    variables: HashMap<String, i64>,
+    output: Vec<i64>,
+    calls: Vec<String>,
+    includes: Vec<IncludeKind>,
+}
+
+impl Evaluator for Integers {
+    type Value = i64;
+
+    fn eval_other(&mut self, expression: &Expression) -> i64 {
+        match expression {
+            Expression::Literal(Literal::Integer(integer)) => {
+                integer.value.to_string().parse().unwrap()
+            }
+            Expression::Variable(Variable::SimpleVariable(variable)) => {
+                self.variables[&variable.name.to_string()]
+            }
+            Expression::AssignmentOperation(AssignmentOperationExpression::Assign {
+                left,
+                right,
+                ..
+            }) => {
+                let value = self.eval_expr(right);
+                self.assign(left, value);
+
+                value
+            }
+            Expression::ArithmeticOperation(ArithmeticOperationExpression::Addition {
+                left,
+                right,
+                ..
+            }) => self.eval_expr(left) + self.eval_expr(right),
+            Expression::ArithmeticOperation(ArithmeticOperationExpression::PostIncrement {
+                left,
+                ..
+            }) => {
+                let value = self.eval_expr(left);
+                self.assign(left, value + 1);
+
+                value
+            }
+            Expression::ComparisonOperation(ComparisonOperationExpression::LessThan {
+                left,
+                right,
+                ..
+            }) => (self.eval_expr(left) < self.eval_expr(right)) as i64,
+            expression => panic!("unsupported expression: {:?}", expression),
+        }
+    }
+
+    fn call_function(&mut self, call: &FunctionCallExpression) -> i64 {
+        if let Expression::Identifier(identifier) = call.target.as_ref() {
+            self.calls.push(format!("{:?}", identifier));
+        }
+
+        0
+    }
+
+    fn include(&mut self, kind: IncludeKind, _: &Expression) -> i64 {
+        self.includes.push(kind);
+
+        1
+    }
+
+    fn is_truthy(&mut self, value: &i64) -> bool {
+        *value != 0
+    }
+
+    fn loose_equals(&mut self, left: &i64, right: &i64) -> bool {
+        left == right
+    }
+
+    fn echo(&mut self, value: i64) {
+        self.output.push(value);
+    }
+
+    fn assign(&mut self, target: &Expression, value: i64) {
+        match target {
+            Expression::Variable(Variable::SimpleVariable(variable)) => {
+                self.variables.insert(variable.name.to_string(), value);
+            }
+            target => panic!("unsupported assignment target: {:?}", target),
+        }
+    }
+
+    fn iterate(&mut self, value: i64) -> Vec<(i64, i64)> {
+        (0..value).map(|key| (key, key * 10)).collect()
+    }
+
+    fn exec_other(&mut self, statement: &Statement) -> Flow<i64> {
+        panic!("unsupported statement: {:?}", statement)
+    }
+}
+
+#[test]
+fn test_execute_control_flow() {
+    let source = "<?php
+namespace App;
+
+for ($i = 0; $i < 10; $i++) {
+    if ($i < 2) {
+        continue;
+    } elseif ($i < 5) {
+        echo $i;
+    } else {
+        break;
+    }
+}
+
+foreach (3 as $key => $value) {
+    switch ($key) {
+        case 0:
+            echo $value;
+        case 1:
+            echo $value + 1;
+            break;
+        default:
+            echo 100;
+    }
+}
+
+$j = 0;
+while (1) {
+    do {
+        $j++;
+        if ($j < 3) {
+            continue 2;
+        }
+        break 2;
+    } while (0);
+}
+echo $j;
+
+require 'a.php';
+include_once 'b.php';
+log(1);
+
+return 42;
+echo 0;
+";
+    let program = parser::parse(source).unwrap();
+    let mut evaluator = Integers::default();
+
+    let flow = execute(&mut evaluator, &program);
+
+    assert_eq!(flow, Flow::Return(Some(42)));
+    assert_eq!(evaluator.output, vec![2, 3, 4, 0, 1, 11, 100, 3]);
+    assert_eq!(
+        evaluator.includes,
+        vec![IncludeKind::Require, IncludeKind::IncludeOnce]
+    );
+    assert_eq!(evaluator.calls.len(), 1);
+}
+
+#[test]
+fn test_const_expr() {
+    let program = parser::parse(
+        "<?php
+        1_000 + 0x10 - 0b11 * 0o7;
+        7 / 2;
+        -(2 ** 3) % 5;
+        'a' . 1 . true . null;
+        '10' + 5;
+        1 / 0;
+        PHP_EOL;
+        1.5 . 'x';
+        ~0 & 0xFF | 1 << 8;
+        ",
+    )
+    .unwrap();
+
+    let values: Vec<Option<PhpConstValue>> = program
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Expression(statement) => Some(const_expr(&statement.expression)),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        values,
+        vec![
+            Some(PhpConstValue::Int(995)),
+            Some(PhpConstValue::Float(3.5)),
+            Some(PhpConstValue::Int(-3)),
+            Some(PhpConstValue::String("a11".into())),
+            Some(PhpConstValue::Int(15)),
+            None,
+            None,
+            None,
+            Some(PhpConstValue::Int(511)),
+        ]
+    );
+}
+
+#[test]
+fn test_const_expr_arrays_and_magic_constants() {
+    let program = parser::parse(
+        "<?php
+        ['a', 5 => 'b', 'c', '7' => 'd', '07' => 'e', true => 'f', null => 'g', 'c' . 'd' => 'h'];
+        [-5 => 'a', 'b'];
+        [...['x' => 1, 2], ...['x' => 3, 4]];
+        ['a' => 1, 'b' => 2]['b'];
+        [1, 2][5];
+        array(1, [$a]);
+        __LINE__;
+        __CLASS__ . '::' . __FUNCTION__;
+        ",
+    )
+    .unwrap();
+
+    let values: Vec<Option<PhpConstValue>> = program
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Expression(statement) => Some(const_expr_with(
+                &statement.expression,
+                &mut |expression| match expression {
+                    Expression::MagicConstant(MagicConstantExpression::Class(_)) => {
+                        Some(PhpConstValue::String("App\\Foo".into()))
+                    }
+                    _ => None,
+                },
+            )),
+            _ => None,
+        })
+        .collect();
+
+    let int = |key: i64, value: &str| (PhpArrayKey::Int(key), PhpConstValue::String(value.into()));
+    let string = |key: &str, value: &str| {
+        (
+            PhpArrayKey::String(key.into()),
+            PhpConstValue::String(value.into()),
+        )
+    };
+
+    assert_eq!(
+        values[0],
+        Some(PhpConstValue::Array(vec![
+            int(0, "a"),
+            int(5, "b"),
+            int(6, "c"),
+            int(7, "d"),
+            string("07", "e"),
+            int(1, "f"),
+            string("", "g"),
+            string("cd", "h"),
+        ]))
+    );
+    assert_eq!(
+        values[1],
+        Some(PhpConstValue::Array(vec![int(-5, "a"), int(-4, "b")]))
+    );
+    assert_eq!(
+        values[2],
+        Some(PhpConstValue::Array(vec![
+            (PhpArrayKey::String("x".into()), PhpConstValue::Int(3)),
+            (PhpArrayKey::Int(0), PhpConstValue::Int(2)),
+            (PhpArrayKey::Int(1), PhpConstValue::Int(4)),
+        ]))
+    );
+    assert_eq!(values[3], Some(PhpConstValue::Int(2)));
+    assert_eq!(values[4], None);
+    assert_eq!(values[5], None);
+    assert_eq!(values[6], Some(PhpConstValue::Int(8)));
+    // `__FUNCTION__` isn't resolved.
+    assert_eq!(values[7], None);
+}
+
+#[test]
+fn test_literal_values() {
+    let program = parser::parse(
+        "<?php
+        0x1A; 0o17; 017; 0b1010; 1_000_000; 9223372036854775807;
+        9223372036854775808; 0xFFFFFFFFFFFFFFFF; 0b1_0000000000000000000000000000000000000000000000000000000000000000;
+        1_000.5; 1e3; .5; 1e999;
+        ",
+    )
+    .unwrap();
+
+    let literals: Vec<&Literal> = program
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Expression(statement) => match &statement.expression {
+                Expression::Literal(literal) => Some(literal),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+
+    let integers: Vec<IntegerValue> = literals
+        .iter()
+        .filter_map(|literal| match literal {
+            Literal::Integer(integer) => Some(integer.to_value()),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        integers,
+        vec![
+            IntegerValue::Integer(26),
+            IntegerValue::Integer(15),
+            IntegerValue::Integer(15),
+            IntegerValue::Integer(10),
+            IntegerValue::Integer(1_000_000),
+            IntegerValue::Integer(i64::MAX),
+            IntegerValue::Overflowed(9223372036854775808.0),
+            IntegerValue::Overflowed(18446744073709551615.0),
+            IntegerValue::Overflowed(18446744073709551616.0),
+        ]
+    );
+
+    let floats: Vec<f64> = literals
+        .iter()
+        .filter_map(|literal| match literal {
+            Literal::Float(float) => Some(float.to_value()),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(floats, vec![1000.5, 1000.0, 0.5, f64::INFINITY]);
+}
@@ -0,0 +1,130 @@
+use php_parser_rs::export::node_rows;
+use php_parser_rs::export::token_rows;
+use php_parser_rs::export::typescript::typescript_definitions;
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parser;
+
+const SOURCE: &str = "<?php\nclass Foo {\n    public function bar() {}\n}\n";
+
+#[test]
+fn test_token_rows() {
+    let tokens = Lexer::new().tokenize(SOURCE).unwrap();
+    let rows = token_rows(&tokens);
+
+    assert_eq!(rows.len(), tokens.len());
+    assert_eq!(rows[1].kind, "Class");
+    assert_eq!(rows[2].kind, "Identifier");
+    assert_eq!(rows[2].value, b"Foo");
+    assert_eq!((rows[2].line, rows[2].column, rows[2].position), (2, 7, 12));
+}
+
+#[test]
+fn test_node_rows() {
+    let mut program = parser::parse(SOURCE).unwrap();
+    let rows = node_rows(&mut program);
+
+    assert_eq!(rows[0].kind, "Program");
+    assert_eq!(rows[0].parent, None);
+
+    let class = rows
+        .iter()
+        .find(|row| row.kind == "ClassStatement")
+        .unwrap();
+    assert_eq!(class.name.as_ref().unwrap(), &b"Foo");
+    assert_eq!(class.span.unwrap().line, 2);
+
+    let method = rows
+        .iter()
+        .find(|row| row.kind == "ConcreteMethod")
+        .unwrap();
+    assert_eq!(method.name.as_ref().unwrap(), &b"bar");
+
+    // Every row but the root refers to a parent that precedes it.
+    assert!(rows[1..].iter().all(|row| row.parent.unwrap() < row.id));
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn test_record_batches() {
+    use php_parser_rs::export::arrow::nodes_to_record_batch;
+    use php_parser_rs::export::arrow::tokens_to_record_batch;
+
+    let tokens = Lexer::new().tokenize(SOURCE).unwrap();
+    let batch = tokens_to_record_batch(&token_rows(&tokens)).unwrap();
+    assert_eq!(batch.num_rows(), tokens.len());
+
+    let mut program = parser::parse(SOURCE).unwrap();
+    let rows = node_rows(&mut program);
+    let batch = nodes_to_record_batch(&rows).unwrap();
+    assert_eq!(batch.num_rows(), rows.len());
+}
+
+#[test]
+fn test_typescript_definitions() {
+    let program = parser::parse(
+        "<?php
+namespace App;
+
+class User extends Model {
+    public int $id;
+    public ?string $email = null;
+    /** @var list<Role> */
+    public array $roles = [];
+    /** @var array<string, int|float>|null */
+    public $scores;
+    /** @var int<0, 100> */
+    public $score;
+    /** @var class-string<Model> */
+    public $type;
+    /** @var array{street: string, 'zip-code'?: int, tags: list{string, ?int}} */
+    public array $address;
+    public static int $count = 0;
+    protected string $password;
+
+    /**
+     * @param non-empty-string $name
+     */
+    public function __construct(public readonly string $name, private int $age, public self|Foo\\Bar $next) {}
+}
+
+enum Status: string {
+    const PREFIX = 'status-';
+
+    case Active = self::PREFIX . 'active';
+    case Inactive = 'inactive';
+}
+
+enum Suit {
+    case Hearts;
+    case Spades;
+}
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+        typescript_definitions(&program),
+        "export interface User extends Model {
+    id: number;
+    email: string | null;
+    roles: Role[];
+    scores: Record<string, number> | null;
+    score: number;
+    type: string;
+    address: { street: string; \"zip-code\"?: number; tags: [string, number | null] };
+    readonly name: string;
+    next: User | Bar;
+}
+
+export enum Status {
+    Active = \"status-active\",
+    Inactive = \"inactive\",
+}
+
+export enum Suit {
+    Hearts = \"Hearts\",
+    Spades = \"Spades\",
+}
+"
+    );
+}
@@ -0,0 +1,4 @@
+#!/usr/bin/env php
+<?php
+
+echo "Hello";
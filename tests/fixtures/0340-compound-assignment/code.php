@@ -0,0 +1,7 @@
+<?php
+
+$a ??= $b ??= 1;
+$a **= $b **= 2;
+$a <<= $b >>= 3;
+$a %= $b &= $c |= $d ^= 4;
+$a = $b .= $c -= 5;
@@ -0,0 +1,38 @@
+<?php
+
+if ($a):
+    echo 1;
+elseif ($b):
+    echo 2;
+else:
+    echo 3;
+endif;
+
+while ($a):
+    echo 4;
+endwhile;
+
+for ($i = 0; $i < 10; $i++):
+    echo $i;
+endfor;
+
+foreach ($items as $key => $item):
+    echo $item;
+endforeach;
+
+switch ($a):
+    case 1:
+        echo 5;
+        break;
+    default:
+        echo 6;
+endswitch;
+
+declare(ticks=1):
+    echo 7;
+enddeclare;
+
+switch ($a): ?>
+<?php case 1: ?>
+    one
+<?php endswitch ?>
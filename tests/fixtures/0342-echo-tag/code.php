@@ -0,0 +1,6 @@
+<ul>
+<?php foreach ($items as $item): ?>
+    <li><?= $item->name, " (", $item->count, ")" ?></li>
+<?php endforeach ?>
+</ul>
+<?= $footer; ?>
@@ -0,0 +1,8 @@
+<?php
+
+[$a, [$b, , $c]] = $x;
+['id' => $id, 'tags' => [$first]] = $x;
+list($a, [$b, $c]) = $x;
+[$a, $b] = [$b, $a];
+
+foreach ($pairs as $key => [$left, $right]) {}
@@ -0,0 +1,7 @@
+<?php
+
+$result = match ($value) {
+    1, 2, => "low",
+    default, => "other",
+    3 => "high",
+};
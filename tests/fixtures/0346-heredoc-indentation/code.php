@@ -0,0 +1,11 @@
+<?php
+
+$a = <<<EOF
+    Hello, {$name}!
+      Indented
+    EOF;
+
+$b = <<<'EOF'
+    Hello,
+      world!
+    EOF;
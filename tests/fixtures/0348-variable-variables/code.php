@@ -0,0 +1,11 @@
+<?php
+
+$$value = 1;
+$$$a;
+${$expr};
+$ {$expr};
+$${'name'};
+$$a['key'];
+$$a->b();
+$$a::$$b;
+echo "${name} ${name[1 + 1]} ${$name} {$$name}";
@@ -0,0 +1,13 @@
+<?php
+
+$obj->{$name};
+$obj?->{$name};
+$obj->{'prefix' . $name}();
+$obj->{$name}(...);
+Foo::{$name};
+Foo::{$name}();
+Foo::{$name}(...);
+$class::{$name}::{$other}();
+static::{$name}();
+new $obj->{$name}();
+new $obj->factory['key']::$class($argument);
@@ -0,0 +1,11 @@
+<?php
+
+function a(iterable $a, object $b, mixed $c, null|false $d, true $e): never {}
+
+function b((A&B)|C|null $a): (A&B)|(C&D) {}
+
+class Foo {
+    public (A&B)|null $a;
+
+    public function b(self $a, ?parent $b): static {}
+}
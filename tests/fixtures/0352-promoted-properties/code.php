@@ -0,0 +1,11 @@
+<?php
+
+final class Point {
+    public function __construct(
+        #[Positive] public readonly int $x = 1,
+        #[Positive, Since('2.0')] protected readonly int $y = 2,
+        private ?Point &$parent = null,
+        readonly public array $tags = [],
+        string ...$labels,
+    ) {}
+}
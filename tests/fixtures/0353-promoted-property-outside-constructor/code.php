@@ -0,0 +1,5 @@
+<?php
+
+abstract class Point {
+    abstract public function __construct(public int $x);
+}
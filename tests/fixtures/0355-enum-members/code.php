@@ -0,0 +1,19 @@
+<?php
+
+enum Suit: string implements HasColor, JsonSerializable {
+    use Describes;
+
+    #[Deprecated]
+    case Hearts = 'H';
+    case Spades = 'S';
+
+    final public const Wild = self::Spades;
+
+    public static function fromChar(string $char): static {
+        return self::from($char);
+    }
+
+    final protected function color(): string {
+        return 'red';
+    }
+}
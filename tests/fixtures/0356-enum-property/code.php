@@ -0,0 +1,7 @@
+<?php
+
+enum Suit {
+    case Hearts;
+
+    public string $color = 'red';
+}
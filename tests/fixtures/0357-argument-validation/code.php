@@ -0,0 +1,6 @@
+<?php
+
+foo(a: ...$b);
+foo(a: 1, ...$rest);
+foo(a: 1, b: 2, a: 3);
+foo(1, ...);
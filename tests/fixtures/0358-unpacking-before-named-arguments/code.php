@@ -0,0 +1,3 @@
+<?php
+
+foo(...$a, ...$b, c: 1, d: 2);
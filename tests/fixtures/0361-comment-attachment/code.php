@@ -0,0 +1,26 @@
+<?php
+
+// not attached to anything.
+$a = 1 /* nor is this */;
+
+/** Foo. */
+#[Bar]
+final class Foo {
+    /** The bar. */
+    public function bar() {}
+}
+
+/** Baz. */
+interface Baz {}
+
+/** Qux. */
+trait Qux {}
+
+/** Suit. */
+enum Suit: string {
+    /** Hearts. */
+    case Hearts = 'H';
+}
+
+/** Quux. */
+function quux() {}
@@ -0,0 +1,13 @@
+<?php
+
+function gen() {
+    yield;
+    $received = yield;
+    yield $value;
+    yield $key => $value;
+    yield from other();
+    foo(yield, [yield]);
+    $a = $b ? yield : 1;
+    $c = yield $d + 1;
+    yield $e or $f;
+}
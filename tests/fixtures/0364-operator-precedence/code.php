@@ -0,0 +1,13 @@
+<?php
+
+$a + $b++;
+-$a-- ** 2;
+!$a = foo();
+@$a = foo();
+$a ? $b : $c or $d;
+$a ?? $b or $c;
+$a . $b << 2 + $c;
+$a & $b == $c | $d ^ $e;
+$a <=> $b % $c;
+$a or $b xor $c and $d;
+$a %= $b <<= $c;
@@ -0,0 +1,8 @@
+<?php
+
+$result = print "x";
+print("a");
+print("a") . "b";
+print $a and $b;
+$a or print "failed";
+echo print 1, 2;
@@ -0,0 +1,17 @@
+<?php
+
+(binary) $a;
+(unset) $a;
+(real) $a;
+(integer) $a;
+(boolean) $a;
+(double) $a;
+( int ) $a;
++$a;
+~$a;
+-$a;
+++$a;
+--$a;
+$a++;
+$a--;
+-(int) $a ** 2;
@@ -0,0 +1,7 @@
+<?php
+exit();
+EXIT;
+Die("x");
+$a or die;
+$a ?: exit(2);
+fn() => exit;
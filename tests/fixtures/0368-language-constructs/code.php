@@ -0,0 +1,6 @@
+<?php
+
+isset($a, $b[0], ($c)->d, $e?->f, G::$h,);
+empty(foo());
+unset($a[0], $b->c, D::$e,);
+eval($code);
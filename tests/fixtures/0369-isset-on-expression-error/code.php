@@ -0,0 +1,4 @@
+<?php
+
+if (isset($a, foo())) {
+}
@@ -0,0 +1,7 @@
+<?php
+
+$a = new #[Attr] readonly class (1, 2) extends A implements B, C {
+    public function __construct(public int $a, public int $b) {}
+};
+
+$b = new readonly class {};
@@ -0,0 +1,3 @@
+<?php
+
+$a = new final class {};
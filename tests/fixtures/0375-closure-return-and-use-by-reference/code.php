@@ -0,0 +1,6 @@
+<?php
+
+$a = function &($b) use (&$c, $d) {
+    return $c;
+};
+$e = static fn &(array &$f) => $f;
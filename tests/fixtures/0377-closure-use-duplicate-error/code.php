@@ -0,0 +1,3 @@
+<?php
+
+$a = function () use ($b, &$b) {};
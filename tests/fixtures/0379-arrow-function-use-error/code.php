@@ -0,0 +1,3 @@
+<?php
+
+$a = fn ($b) use ($c) => $b + $c;
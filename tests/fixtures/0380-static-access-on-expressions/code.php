@@ -0,0 +1,19 @@
+<?php
+
+$obj::class;
+$obj::CONSTANT;
+$obj::$property;
+$obj::method();
+(new Foo)::CONSTANT;
+(new Foo)::class;
+'Foo'::$bar;
+'Foo'::BAR;
+"Foo"::bar();
+$a[0]::B;
+$a->b::C;
+foo()::$b;
+$a::$b::c();
+Foo::{$name};
+$obj::{$name};
+Foo::{'BAR'};
+static::{$method}();
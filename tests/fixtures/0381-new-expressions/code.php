@@ -0,0 +1,18 @@
+<?php
+
+new $className;
+new $className();
+new $a->b['c']::$d();
+new ($factory->class())(1);
+new static;
+new parent();
+new self;
+new Foo::$bar;
+new Foo()->bar();
+new Foo()::CONSTANT;
+new Foo()['key'];
+new Foo()?->bar;
+new class {}->foo();
+new $a->b()->c;
+new Foo() instanceof Foo;
+clone new Foo();
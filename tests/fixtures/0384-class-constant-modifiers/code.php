@@ -0,0 +1,7 @@
+<?php
+
+class Foo {
+    private const A = 1;
+    final protected const B = 2;
+    final public const C = 3;
+}
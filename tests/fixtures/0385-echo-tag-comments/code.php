@@ -0,0 +1,5 @@
+<p><?= /* name */ $a ?></p>
+<p><?= // name
+$b ?></p>
+<p><?= # name
+$c, $d; ?></p>
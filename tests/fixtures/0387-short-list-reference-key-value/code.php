@@ -0,0 +1,3 @@
+<?php
+
+['k' => &$v, 'l' => [&$w]] = $x;
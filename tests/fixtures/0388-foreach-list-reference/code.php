@@ -0,0 +1,4 @@
+<?php
+
+foreach ($xs as [&$a, $b]) {}
+foreach ($xs as $k => list('v' => &$v)) {}
@@ -0,0 +1,6 @@
+<?php
+
+clone $a->b;
+clone $a->b();
+clone $a[0];
+clone $a::$b->c;
@@ -0,0 +1,96 @@
+#![cfg(feature = "fuzz")]
+
+use std::fs;
+use std::panic;
+use std::path::PathBuf;
+
+use php_parser_rs::fuzz;
+use php_parser_rs::lexer::Lexer;
+
+// The fixtures, along with every prefix of them ending at a token boundary or just before
+// the end of a token, and every copy of them with a single token removed, which is where
+// odd-but-real code is found.
+fn corpus() -> Vec<(String, Vec<u8>)> {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    let mut corpus = vec![];
+    for entry in fs::read_dir(fixtures).unwrap().flatten() {
+        let Ok(code) = fs::read(entry.path().join("code.php")) else {
+            continue;
+        };
+        let name = entry.file_name().to_string_lossy().into_owned();
+
+        let boundaries: Vec<(usize, usize)> = Lexer::new()
+            .tokens(&code)
+            .map_while(Result::ok)
+            .map(|token| (token.span.position, token.span.end_position))
+            .collect();
+
+        for &(start, end) in &boundaries {
+            corpus.push((format!("{} [..{}]", name, start), code[..start].to_vec()));
+            if end > start + 1 && end <= code.len() {
+                let truncated = code[..end - 1].to_vec();
+                corpus.push((format!("{} [..{}]", name, end - 1), truncated));
+            }
+
+            let mut removed = code[..start].to_vec();
+            removed.extend_from_slice(&code[end.min(code.len())..]);
+            corpus.push((format!("{} [{}..{}] removed", name, start, end), removed));
+        }
+
+        corpus.push((name, code));
+    }
+
+    corpus
+}
+
+#[test]
+fn test_fuzz_corpus_does_not_panic() {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let failures: Vec<String> = corpus()
+        .into_iter()
+        .filter_map(|(name, input)| {
+            panic::catch_unwind(|| fuzz::exercise(&input))
+                .err()
+                .map(|error| {
+                    let message = error
+                        .downcast_ref::<String>()
+                        .cloned()
+                        .or_else(|| {
+                            error
+                                .downcast_ref::<&str>()
+                                .map(|message| message.to_string())
+                        })
+                        .unwrap_or_default();
+
+                    format!("{}: {}", name, message)
+                })
+        })
+        .collect();
+
+    panic::set_hook(hook);
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_exercise_long_chains() {
+    for input in [
+        format!("<?php $a = $a{};", " . $a".repeat(6000)),
+        format!("<?php $a = $b{};", "->c()".repeat(6000)),
+    ] {
+        assert!(fuzz::parse_unchecked(input.as_bytes()).is_ok());
+        fuzz::exercise(input.as_bytes());
+    }
+}
+
+#[test]
+fn test_parse_unchecked_limits_depth() {
+    let input = format!("<?php {}1{};", "(".repeat(10_000), ")".repeat(10_000));
+
+    assert!(fuzz::parse_unchecked(input.as_bytes()).is_err());
+    assert!(fuzz::parse_unchecked(b"<?php (((1)));").is_ok());
+    assert!(fuzz::parse_unchecked(b"\xff\xfe<?php \xff").is_err());
+}
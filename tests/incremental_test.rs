@@ -0,0 +1,176 @@
+use php_parser_rs::parse;
+use php_parser_rs::parser::incremental::reparse;
+use php_parser_rs::parser::incremental::TextEdit;
+use php_parser_rs::ParserConfig;
+
+const CODE: &str = "<?php
+
+declare(strict_types=1);
+
+namespace App;
+
+use Foo\\Bar;
+
+// not attached to anything.
+$a = 1;
+
+if ($a) {
+    echo $a;
+}
+
+/** A class. */
+final class Baz extends Bar {
+    public function qux(): int { return 1; }
+}
+
+function quux() {}
+";
+
+// Code which the lexer reads in other states than scripting.
+const STRINGS: &str = "<?php
+
+$a = <<<EOF
+    {$b} }
+    EOF;
+
+/* a; } */ $c = \"$d[0] {$e->f}\";
+
+?>
+<p><?= $g ?></p>
+<?php
+
+function h() { return `ls`; }
+
+echo 'i';
+";
+
+// Reparsing after an edit gives the same result as parsing the edited code.
+fn assert_reparse(code: &str, edit: TextEdit) {
+    let previous = parse(code).unwrap();
+    let edited = edit.apply(code.as_bytes());
+
+    let expected = parse(&edited);
+    let actual = reparse(&previous, code.as_bytes(), &edit, ParserConfig::default());
+
+    match (expected, actual) {
+        (Ok(expected), Ok(actual)) => assert_eq!(
+            expected,
+            actual,
+            "{:?} in {}",
+            edit,
+            String::from_utf8_lossy(&edited)
+        ),
+        (Err(expected), Err(actual)) => assert_eq!(
+            expected.errors,
+            actual.errors,
+            "{:?} in {}",
+            edit,
+            String::from_utf8_lossy(&edited)
+        ),
+        (expected, actual) => panic!(
+            "{:?} in {}: expected {:?}, found {:?}",
+            edit,
+            String::from_utf8_lossy(&edited),
+            expected.map(|_| ()),
+            actual.map(|_| ())
+        ),
+    }
+}
+
+#[test]
+fn test_reparse_at_every_offset() {
+    for offset in 0..=CODE.len() {
+        for replacement in [" ", "x", ";", "}", "\n/** Doc. */ function f() {}\n"] {
+            assert_reparse(CODE, TextEdit::new(offset..offset, replacement));
+        }
+
+        if offset < CODE.len() {
+            assert_reparse(CODE, TextEdit::new(offset..offset + 1, ""));
+        }
+    }
+}
+
+#[test]
+fn test_reparse_strings_at_every_offset() {
+    for offset in 0..=STRINGS.len() {
+        for replacement in [" ", "\n", "\"", "'", "/*", "*/", "?>", "<?php ", "<<<EOF\n"] {
+            assert_reparse(STRINGS, TextEdit::new(offset..offset, replacement));
+        }
+
+        if offset < STRINGS.len() {
+            assert_reparse(STRINGS, TextEdit::new(offset..offset + 1, ""));
+        }
+    }
+}
+
+#[test]
+fn test_reparse_large_file() {
+    let mut code = String::from("<?php\n\nnamespace App;\n\n");
+    for i in 0..100 {
+        code.push_str(&format!(
+            "/** {} */\nfunction f{}() {{\n    return {};\n}}\n\n",
+            i, i, i
+        ));
+    }
+    code.push_str("namespace Other;\n\nfunction g() {}\n");
+
+    for function in [0, 50, 99] {
+        let offset = code.find(&format!("return {};", function)).unwrap();
+        for replacement in ["return -1;\n    ", "/*", "}\nfunction h() {\n", "\"", ""] {
+            assert_reparse(&code, TextEdit::new(offset..offset, replacement));
+            assert_reparse(&code, TextEdit::new(offset..offset + 7, replacement));
+        }
+    }
+}
+
+#[test]
+fn test_reparse_edits() {
+    let else_offset = CODE.find("\n\n/** A class").unwrap();
+    assert_reparse(
+        CODE,
+        TextEdit::new(else_offset..else_offset, " else { exit; }"),
+    );
+
+    let class = CODE.find("public function").unwrap();
+    assert_reparse(CODE, TextEdit::new(class..class + 6, "private"));
+
+    let end = CODE.len();
+    assert_reparse(
+        CODE,
+        TextEdit::new(end..end, "namespace Other;\nfunction f() {}\n"),
+    );
+
+    // strict_types must still be the first statement.
+    let use_offset = CODE.find("use Foo").unwrap();
+    assert_reparse(
+        CODE,
+        TextEdit::new(use_offset..use_offset, "declare(strict_types=1);\n"),
+    );
+
+    let braced = "<?php namespace A { function f() {} } namespace B { function g() {} }";
+    let offset = braced.find("function g").unwrap();
+    assert_reparse(braced, TextEdit::new(offset..offset, "namespace C; "));
+    assert_reparse(braced, TextEdit::new(offset..offset + 10, "function h"));
+}
+
+#[test]
+fn test_text_edit_between() {
+    let cases = [
+        ("", "", TextEdit::new(0..0, "")),
+        ("abc", "abc", TextEdit::new(3..3, "")),
+        ("abc", "abxc", TextEdit::new(2..2, "x")),
+        ("abc", "ac", TextEdit::new(1..2, "")),
+        ("aaa", "aa", TextEdit::new(2..3, "")),
+        ("abc", "xyz", TextEdit::new(0..3, "xyz")),
+    ];
+
+    for (before, after, expected) in cases {
+        let edit = TextEdit::between(before.as_bytes(), after.as_bytes());
+
+        assert_eq!(edit, expected, "{:?} -> {:?}", before, after);
+        assert_eq!(edit.apply(before.as_bytes()), after.as_bytes());
+    }
+
+    let edited = CODE.replace("public function", "private function");
+    assert_reparse(CODE, TextEdit::between(CODE.as_bytes(), edited.as_bytes()));
+}
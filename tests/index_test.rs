@@ -0,0 +1,283 @@
+use std::path::Path;
+
+use php_parser_rs::evaluator::PhpConstValue;
+use php_parser_rs::index::autoload::Autoloader;
+use php_parser_rs::index::Index;
+use php_parser_rs::parser;
+
+fn write(path: &Path, contents: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents).unwrap();
+}
+
+#[test]
+fn test_global_constants() {
+    let program = parser::parse(
+        "<?php
+        namespace App;
+
+        /** The current version. */
+        const VERSION = '1.0', DEBUG = false;
+
+        define('App\\Config\\PATH', __DIR__);
+        \\define('LIMIT', 10 * 2);
+        define($name, 1);
+
+        if (true) {
+            define('CONDITIONAL', 1);
+        }",
+    )
+    .unwrap();
+
+    let index = Index::from_program(&program);
+
+    let version = index.constant(b"app\\VERSION").unwrap();
+    assert_eq!(version.name, b"App\\VERSION");
+    assert_eq!(version.value, Some(PhpConstValue::String("1.0".into())));
+    assert_eq!(
+        version.docblock.as_ref().unwrap(),
+        b"/** The current version. */"
+    );
+
+    assert_eq!(
+        index.constant(b"\\App\\DEBUG").unwrap().value,
+        Some(PhpConstValue::Bool(false))
+    );
+    assert!(index.constant(b"App\\debug").is_none());
+
+    let path = index.constant(b"App\\Config\\PATH").unwrap();
+    assert_eq!(path.value, None);
+
+    let limit = index.constant(b"LIMIT").unwrap();
+    assert_eq!(limit.value, Some(PhpConstValue::Int(20)));
+    assert_eq!(limit.docblock, None);
+
+    assert_eq!(index.constants().count(), 4);
+}
+
+#[test]
+fn test_json() {
+    let program = parser::parse(
+        "<?php
+        namespace App;
+
+        const MAX = [1, 'a' => 2];
+
+        interface Shape {}
+
+        abstract class Base implements Shape {
+            protected const SIDES = 0;
+            public static ?Base $default;
+
+            abstract public function area(int $precision = 2, &...$rest): float;
+        }
+
+        function square(int $side): int {}",
+    )
+    .unwrap();
+
+    let index = Index::from_program(&program);
+    let json = serde_json::to_value(&index).unwrap();
+
+    assert_eq!(
+        json["classes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|class| class["name"].as_str().unwrap())
+            .collect::<Vec<_>>(),
+        vec!["App\\Base", "App\\Shape"]
+    );
+
+    let base = &json["classes"][0];
+    assert_eq!(base["kind"], "Class");
+    assert_eq!(base["interfaces"][0], "App\\Shape");
+    assert_eq!(base["constants"][0]["name"], "SIDES");
+    assert_eq!(base["constants"][0]["value"]["value"], 0);
+    assert_eq!(base["properties"][0]["name"], "default");
+    assert_eq!(base["properties"][0]["is_static"], true);
+
+    let area = &base["methods"][0];
+    assert_eq!(area["is_abstract"], true);
+    assert_eq!(area["parameters"][0]["is_optional"], true);
+    assert_eq!(area["parameters"][1]["is_variadic"], true);
+    assert_eq!(area["parameters"][1]["is_by_reference"], true);
+
+    assert_eq!(json["functions"][0]["name"], "App\\square");
+    assert_eq!(json["constants"][0]["name"], "App\\MAX");
+    assert_eq!(
+        json["constants"][0]["value"],
+        serde_json::json!({
+            "type": "Array",
+            "value": [
+                [{"type": "Int", "value": 0}, {"type": "Int", "value": 1}],
+                [{"type": "String", "value": "a"}, {"type": "Int", "value": 2}],
+            ],
+        })
+    );
+}
+
+#[test]
+fn test_autoload_composer_json() {
+    let root = std::env::temp_dir().join(format!("php-parser-rs-autoload-{}", std::process::id()));
+
+    write(
+        &root.join("composer.json"),
+        r#"{
+            "autoload": {
+                "psr-4": {"App\\": "src/", "App\\Legacy\\": ["lib/", "old/"]},
+                "psr-0": {"Twig_": "vendor/twig/lib/"},
+                "classmap": ["scripts/"]
+            },
+            "autoload-dev": {
+                "psr-4": {"Tests\\": "tests/"}
+            }
+        }"#,
+    );
+    write(
+        &root.join("src/Http/Controller.php"),
+        "<?php namespace App\\Http; class Controller {}",
+    );
+    write(
+        &root.join("old/Mailer.php"),
+        "<?php namespace App\\Legacy; class Mailer {}",
+    );
+    write(
+        &root.join("vendor/twig/lib/Twig/Environment.php"),
+        "<?php class Twig_Environment {}",
+    );
+    write(
+        &root.join("scripts/tasks/build.inc"),
+        "<?php namespace Build; interface Task {}",
+    );
+    write(&root.join("scripts/README.md"), "class Ignored {}");
+    write(
+        &root.join("tests/HomeTest.php"),
+        "<?php
+        namespace Tests;
+
+        use App\\Http\\Controller;
+        use App\\Legacy\\{Mailer, Missing};
+        use function App\\helper;
+
+        class HomeTest extends Controller {}",
+    );
+
+    let autoloader = Autoloader::from_project(&root).unwrap();
+
+    assert_eq!(
+        autoloader.candidates(b"\\App\\Legacy\\Mailer"),
+        vec![
+            root.join("lib/Mailer.php"),
+            root.join("old/Mailer.php"),
+            root.join("src/Legacy/Mailer.php"),
+        ]
+    );
+    assert_eq!(
+        autoloader.find_file(b"App\\Legacy\\Mailer"),
+        Some(root.join("old/Mailer.php"))
+    );
+    assert_eq!(
+        autoloader.find_file(b"Twig_Environment"),
+        Some(root.join("vendor/twig/lib/Twig/Environment.php"))
+    );
+    assert_eq!(
+        autoloader.find_file(b"Build\\Task"),
+        Some(root.join("scripts/tasks/build.inc"))
+    );
+    assert_eq!(autoloader.find_file(b"Ignored"), None);
+    assert_eq!(autoloader.find_file(b"App\\Missing"), None);
+
+    let test = parser::parse(&std::fs::read(root.join("tests/HomeTest.php")).unwrap()).unwrap();
+    let imports: Vec<(String, bool)> = autoloader
+        .resolve_imports(&test)
+        .into_iter()
+        .map(|(name, file)| (name.to_string(), file.is_some()))
+        .collect();
+    assert_eq!(
+        imports,
+        vec![
+            ("App\\Http\\Controller".to_string(), true),
+            ("App\\Legacy\\Mailer".to_string(), true),
+            ("App\\Legacy\\Missing".to_string(), false),
+        ]
+    );
+
+    let mut index = Index::from_program(&test);
+    let parent = index
+        .class(b"Tests\\HomeTest")
+        .unwrap()
+        .parent
+        .clone()
+        .unwrap();
+    assert_eq!(
+        index.autoload(&autoloader, &parent),
+        Some(root.join("src/Http/Controller.php"))
+    );
+    assert!(index.class(b"App\\Http\\Controller").is_some());
+    assert_eq!(index.autoload(&autoloader, &parent), None);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_autoload_generated_maps() {
+    let root = std::env::temp_dir().join(format!(
+        "php-parser-rs-autoload-generated-{}",
+        std::process::id()
+    ));
+
+    write(
+        &root.join("composer.json"),
+        r#"{"config": {"vendor-dir": "deps"}, "autoload": {"psr-4": {"Ignored\\": "src/"}}}"#,
+    );
+    write(
+        &root.join("deps/composer/autoload_psr4.php"),
+        r#"<?php
+
+// autoload_psr4.php @generated by Composer
+
+$vendorDir = dirname(__DIR__);
+$baseDir = dirname($vendorDir);
+
+return array(
+    'Psr\\Log\\' => array($vendorDir . '/psr/log/src'),
+    'App\\' => array($baseDir . '/app'),
+);
+"#,
+    );
+    write(
+        &root.join("deps/composer/autoload_classmap.php"),
+        r#"<?php
+$vendorDir = dirname(__DIR__);
+$baseDir = dirname($vendorDir);
+
+return array(
+    'Composer\\InstalledVersions' => $vendorDir . '/composer/InstalledVersions.php',
+);
+"#,
+    );
+
+    let autoloader = Autoloader::from_project(&root).unwrap();
+    assert_eq!(
+        autoloader.candidates(b"Psr\\Log\\LoggerInterface"),
+        vec![root.join("deps/psr/log/src/LoggerInterface.php")]
+    );
+    assert_eq!(
+        autoloader.candidates(b"App\\Kernel"),
+        vec![root.join("app/Kernel.php")]
+    );
+    assert_eq!(
+        autoloader.candidates(b"Composer\\InstalledVersions"),
+        vec![root.join("deps/composer/InstalledVersions.php")]
+    );
+    assert!(autoloader.candidates(b"Ignored\\Foo").is_empty());
+
+    write(
+        &root.join("deps/composer/autoload_psr4.php"),
+        "<?php return foo();",
+    );
+    assert!(Autoloader::from_project(&root).is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,147 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use php_parser_rs::interop::nikic;
+use php_parser_rs::parser;
+
+// Check every object is a node with a `nodeType` and position attributes inside the source.
+fn check_nodes(value: &Value, source: &[u8]) {
+    match value {
+        Value::Object(object) => {
+            let node_type = object["nodeType"].as_str().unwrap();
+            let attributes = &object["attributes"];
+            let start = attributes["startFilePos"].as_i64().unwrap();
+            let end = attributes["endFilePos"].as_i64().unwrap();
+            assert!(start <= end, "{}: {} > {}", node_type, start, end);
+            assert!(end < source.len() as i64, "{}: {}", node_type, end);
+
+            for (key, value) in object {
+                if key != "attributes" {
+                    check_nodes(value, source);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                check_nodes(value, source);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[test]
+fn test_nikic_json_converts_fixtures() {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    for entry in fs::read_dir(fixtures).unwrap().flatten() {
+        let Ok(code) = fs::read(entry.path().join("code.php")) else {
+            continue;
+        };
+
+        if let Ok(program) = parser::parse(&code) {
+            check_nodes(&nikic::to_json(&code, &program), &code);
+        }
+    }
+}
+
+#[test]
+fn test_nikic_json_nodes() {
+    let code = "<?php\n\necho $a + 1, 'b';\n$c = &$d;\n";
+    let program = parser::parse(code).unwrap();
+    let json = nikic::to_json(code, &program);
+
+    assert_eq!(
+        json,
+        serde_json::json!([
+            {
+                "nodeType": "Stmt_Echo",
+                "exprs": [
+                    {
+                        "nodeType": "Expr_BinaryOp_Plus",
+                        "left": {
+                            "nodeType": "Expr_Variable",
+                            "name": "a",
+                            "attributes": {"startLine": 3, "startFilePos": 12, "endLine": 3, "endFilePos": 13},
+                        },
+                        "right": {
+                            "nodeType": "Scalar_Int",
+                            "value": 1,
+                            "attributes": {"startLine": 3, "startFilePos": 17, "endLine": 3, "endFilePos": 17, "rawValue": "1", "kind": 10},
+                        },
+                        "attributes": {"startLine": 3, "startFilePos": 12, "endLine": 3, "endFilePos": 17},
+                    },
+                    {
+                        "nodeType": "Scalar_String",
+                        "value": "b",
+                        "attributes": {"startLine": 3, "startFilePos": 20, "endLine": 3, "endFilePos": 22, "rawValue": "'b'", "kind": 1},
+                    },
+                ],
+                "attributes": {"startLine": 3, "startFilePos": 7, "endLine": 3, "endFilePos": 23},
+            },
+            {
+                "nodeType": "Stmt_Expression",
+                "expr": {
+                    "nodeType": "Expr_AssignRef",
+                    "var": {
+                        "nodeType": "Expr_Variable",
+                        "name": "c",
+                        "attributes": {"startLine": 4, "startFilePos": 25, "endLine": 4, "endFilePos": 26},
+                    },
+                    "expr": {
+                        "nodeType": "Expr_Variable",
+                        "name": "d",
+                        "attributes": {"startLine": 4, "startFilePos": 31, "endLine": 4, "endFilePos": 32},
+                    },
+                    "attributes": {"startLine": 4, "startFilePos": 25, "endLine": 4, "endFilePos": 32},
+                },
+                "attributes": {"startLine": 4, "startFilePos": 25, "endLine": 4, "endFilePos": 33},
+            },
+        ])
+    );
+}
+
+#[test]
+fn test_nikic_json_names_and_calls() {
+    let code = "<?php\nuse Foo\\Bar;\n\\strlen(...$a);\nnamespace\\f(x: 1);\nA::B;\n";
+    let program = parser::parse(code).unwrap();
+    let json = nikic::to_json(code, &program);
+
+    assert_eq!(json[0]["nodeType"], "Stmt_Use");
+    assert_eq!(json[0]["uses"][0]["name"]["name"], "Foo\\Bar");
+
+    let call = &json[1]["expr"];
+    assert_eq!(call["nodeType"], "Expr_FuncCall");
+    assert_eq!(call["name"]["nodeType"], "Name_FullyQualified");
+    assert_eq!(call["name"]["name"], "strlen");
+    assert_eq!(call["args"][0]["unpack"], true);
+
+    let call = &json[2]["expr"];
+    assert_eq!(call["name"]["nodeType"], "Name_Relative");
+    assert_eq!(call["args"][0]["name"]["name"], "x");
+
+    let fetch = &json[3]["expr"];
+    assert_eq!(fetch["nodeType"], "Expr_ClassConstFetch");
+    assert_eq!(fetch["class"]["nodeType"], "Name");
+    assert_eq!(fetch["name"]["nodeType"], "Identifier");
+}
+
+#[test]
+fn test_nikic_json_indented_doc_strings() {
+    let code = "<?php\nfunction f() {\n    $a = <<<EOT\n        foo\n          $b bar\n        EOT;\n    $c = <<<'EOT'\n        foo\n          bar\n        EOT;\n}\n";
+    let program = parser::parse(code).unwrap();
+    let json = nikic::to_json(code, &program);
+    check_nodes(&json, code.as_bytes());
+
+    let heredoc = &json[0]["stmts"][0]["expr"]["expr"];
+    assert_eq!(heredoc["nodeType"], "Scalar_InterpolatedString");
+    assert_eq!(heredoc["parts"][0]["value"], "foo\n  ");
+    assert_eq!(heredoc["parts"][1]["nodeType"], "Expr_Variable");
+    assert_eq!(heredoc["parts"][2]["value"], " bar");
+
+    let nowdoc = &json[0]["stmts"][1]["expr"]["expr"];
+    assert_eq!(nowdoc["nodeType"], "Scalar_String");
+    assert_eq!(nowdoc["value"], "foo\n  bar");
+}
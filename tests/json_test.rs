@@ -0,0 +1,153 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use php_parser_rs::parser;
+use php_parser_rs::parser::ast::Program;
+
+fn programs() -> Vec<(PathBuf, Program)> {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    let mut programs = vec![];
+    for entry in fs::read_dir(fixtures).unwrap().flatten() {
+        let Ok(code) = fs::read(entry.path().join("code.php")) else {
+            continue;
+        };
+
+        if let Ok(program) = parser::parse(&code) {
+            programs.push((entry.path(), program));
+        }
+    }
+
+    programs
+}
+
+// Check every object key is snake_case, and collect the values of `type` tags.
+fn check_keys(value: &Value, tags: &mut Vec<String>) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                assert!(
+                    key.chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+                    "{} is not snake_case",
+                    key
+                );
+
+                if key == "type" {
+                    if let Value::String(tag) = value {
+                        tags.push(tag.clone());
+                    }
+                }
+
+                check_keys(value, tags);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                check_keys(value, tags);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[test]
+fn test_json_round_trips_fixtures() {
+    for (path, program) in programs() {
+        let json = serde_json::to_value(&program)
+            .unwrap_or_else(|error| panic!("{}: {}", path.display(), error));
+        let deserialized: Program = serde_json::from_value(json.clone())
+            .unwrap_or_else(|error| panic!("{}: {}", path.display(), error));
+
+        assert_eq!(
+            serde_json::to_value(&deserialized).unwrap(),
+            json,
+            "{}",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn test_json_keeps_binary_strings() {
+    let program = parser::parse(b"<?php echo \"\\xff\\0\", '\xc3\xa9', '\xfe';").unwrap();
+
+    let json = serde_json::to_string(&program).unwrap();
+    assert!(json.contains(r#"{"base64":"/wA="}"#));
+    assert!(json.contains("\"\u{e9}\""));
+
+    let deserialized: Program = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, program);
+}
+
+#[test]
+fn test_json_representation() {
+    let program = parser::parse("<?php $a = 1;").unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&program).unwrap()[1],
+        serde_json::json!({
+            "type": "Expression",
+            "value": {
+                "type": "ExpressionStatement",
+                "expression": {
+                    "type": "AssignmentOperation",
+                    "value": {
+                        "type": "Assign",
+                        "value": {
+                            "left": {
+                                "type": "Variable",
+                                "value": {
+                                    "type": "SimpleVariable",
+                                    "value": {
+                                        "name": "$a",
+                                        "span": { "line": 1, "column": 7, "position": 6, "end_position": 8 },
+                                    },
+                                },
+                            },
+                            "equals": { "line": 1, "column": 10, "position": 9, "end_position": 10 },
+                            "right": {
+                                "type": "Literal",
+                                "value": {
+                                    "type": "Integer",
+                                    "value": {
+                                        "value": "1",
+                                        "span": { "line": 1, "column": 12, "position": 11, "end_position": 12 },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+                "ending": {
+                    "type": "Semicolon",
+                    "value": { "line": 1, "column": 13, "position": 12, "end_position": 13 },
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn test_schema_describes_fixtures() {
+    let schema = serde_json::to_value(php_parser_rs::schema()).unwrap();
+    assert_eq!(schema["type"], "array");
+    assert_eq!(schema["items"]["$ref"], "#/definitions/Statement");
+
+    let schema = schema.to_string();
+    for (path, program) in programs() {
+        let mut tags = vec![];
+        check_keys(&serde_json::to_value(&program).unwrap(), &mut tags);
+
+        for tag in tags {
+            assert!(
+                schema.contains(&format!("\"{}\"", tag)),
+                "{}: {} is missing from the schema",
+                path.display(),
+                tag
+            );
+        }
+    }
+}
@@ -0,0 +1,769 @@
+use php_parser_rs::index::Index;
+use php_parser_rs::lexer::keywords::PhpVersion;
+use php_parser_rs::lexer::token::Span;
+use php_parser_rs::lint::apply_fixes;
+use php_parser_rs::lint::baseline::Baseline;
+use php_parser_rs::lint::config::LintConfig;
+use php_parser_rs::lint::config::RuleLevel;
+use php_parser_rs::lint::findings_to_json;
+use php_parser_rs::lint::rules::CallArguments;
+use php_parser_rs::lint::rules::Imports;
+use php_parser_rs::lint::rules::InvalidThis;
+use php_parser_rs::lint::rules::NestedTernary;
+use php_parser_rs::lint::rules::OverrideAttribute;
+use php_parser_rs::lint::rules::UninitializedProperty;
+use php_parser_rs::lint::Context;
+use php_parser_rs::lint::DiagnosticsConfig;
+use php_parser_rs::lint::Finding;
+use php_parser_rs::lint::Linter;
+use php_parser_rs::lint::Severity;
+use php_parser_rs::parser;
+use php_parser_rs::snippet::ColumnUnit;
+
+#[test]
+fn test_override_attribute() {
+    let source = "<?php
+namespace App;
+
+interface HasName {
+    public function name(): string;
+}
+
+abstract class Base {
+    public function run(): void {}
+    private function secret(): void {}
+}
+
+class Child extends Base implements HasName {
+    #[\\Override]
+    public function run(): void {}
+
+    #[\\Override]
+    public function secret(): void {}
+
+    public function name(): string { return ''; }
+}
+
+class External extends \\Vendor\\Unknown {
+    #[\\Override]
+    public function anything(): void {}
+}
+";
+    let mut program = parser::parse(source).unwrap();
+    let index = Index::from_program(&program);
+    let context = Context {
+        source: source.as_bytes(),
+        index: &index,
+        version: PhpVersion::Php83,
+    };
+
+    let findings = Linter::new()
+        .with_rule(OverrideAttribute {
+            suggest_missing: true,
+        })
+        .check(&context, &mut program);
+
+    assert_eq!(findings.len(), 2);
+    assert_eq!(findings[0].severity, Severity::Error);
+    assert_eq!(
+        findings[0].message,
+        "Child::secret() has #[\\Override] attribute, but no matching parent method exists"
+    );
+    assert_eq!(findings[1].severity, Severity::Info);
+
+    let fixed = String::from_utf8(apply_fixes(source.as_bytes(), &findings)).unwrap();
+    assert!(fixed.contains("    #[\\Override]\n    public function name(): string { return ''; }"));
+}
+
+#[test]
+fn test_call_arguments() {
+    let source = "<?php
+namespace App;
+
+use function Vendor\\unknown;
+
+function greet(string $name, string $greeting = 'Hello', string ...$rest) {}
+function swap(&$a, &$b) {}
+
+class Point {
+    public function __construct(public int $x, public int $y = 0) {}
+
+    public static function origin(): static { return new static(0); }
+}
+
+greet();
+greet('a', 'b', 'c', 'd');
+greet(name: 'a', greeting: 'b', other: 'c');
+greet('a', name: 'b');
+swap($a, 1);
+swap(...$values);
+unknown(1, 2, 3);
+new Point();
+new Point(1, 2, 3);
+new Point(x: 1, z: 2);
+Point::origin(1);
+strlen();
+";
+    let mut program = parser::parse(source).unwrap();
+    let index = Index::from_program(&program);
+    let context = Context {
+        source: source.as_bytes(),
+        index: &index,
+        version: PhpVersion::Php83,
+    };
+
+    let findings = Linter::new()
+        .with_rule(CallArguments)
+        .check(&context, &mut program);
+
+    let messages: Vec<(usize, &str)> = findings
+        .iter()
+        .map(|finding| (finding.span.line, finding.message.as_str()))
+        .collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            (
+                15,
+                "Too few arguments to function App\\greet(), 0 passed and at least 1 expected"
+            ),
+            (18, "Named parameter $name overwrites previous argument"),
+            (
+                19,
+                "function App\\swap(): Argument #2 ($b) could not be passed by reference"
+            ),
+            (
+                22,
+                "Too few arguments to method App\\Point::__construct(), 0 passed and at least 1 expected"
+            ),
+            (
+                23,
+                "Too many arguments to method App\\Point::__construct(), 3 passed and at most 2 expected"
+            ),
+            (
+                24,
+                "Unknown named parameter $z passed to method App\\Point::__construct()"
+            ),
+            (
+                25,
+                "Too many arguments to method App\\Point::origin(), 1 passed and at most 0 expected"
+            ),
+        ]
+    );
+    assert_eq!(findings[4].severity, Severity::Warning);
+}
+
+#[test]
+fn test_call_arguments_scalar_types() {
+    let functions = "<?php
+function repeat(string $value, int $times, ?float $ratio = null, int|bool $flag = false) {}
+function limit(int $x = 5, string $y = null) {}
+";
+
+    let cases = [
+        ("repeat('a', 2, 1, true);", None),
+        ("repeat('a', '2', null);", None),
+        (
+            "repeat('a', 'two');",
+            Some("function repeat(): Argument #2 ($times) must be of type int, string given"),
+        ),
+        (
+            "repeat(null, 2);",
+            Some("function repeat(): Argument #1 ($value) must be of type string, null given"),
+        ),
+        (
+            "declare(strict_types=1); repeat('a', '2');",
+            Some("function repeat(): Argument #2 ($times) must be of type int, string given"),
+        ),
+        (
+            "declare(strict_types=1); repeat(1, 2);",
+            Some("function repeat(): Argument #1 ($value) must be of type string, int given"),
+        ),
+        ("declare(strict_types=1); repeat('a', 2, 3, false);", None),
+        (
+            "limit(null);",
+            Some("function limit(): Argument #1 ($x) must be of type int, null given"),
+        ),
+        ("limit(5, null);", None),
+    ];
+
+    let index = Index::from_program(&parser::parse(functions).unwrap());
+    for (call, expected) in cases {
+        let source = format!("<?php {}", call);
+        let mut program = parser::parse(&source).unwrap();
+        let context = Context {
+            source: source.as_bytes(),
+            index: &index,
+            version: PhpVersion::Php83,
+        };
+
+        let findings = Linter::new()
+            .with_rule(CallArguments)
+            .check(&context, &mut program);
+
+        let messages: Vec<&str> = findings
+            .iter()
+            .map(|finding| finding.message.as_str())
+            .collect();
+
+        assert_eq!(
+            messages,
+            expected.into_iter().collect::<Vec<_>>(),
+            "{}",
+            call
+        );
+    }
+}
+
+#[test]
+fn test_max_findings() {
+    let source = "<?php
+function one(int $value) {}
+
+one();
+one();
+one();
+";
+    let mut program = parser::parse(source).unwrap();
+    let index = Index::from_program(&program);
+    let context = Context {
+        source: source.as_bytes(),
+        index: &index,
+        version: PhpVersion::Php83,
+    };
+
+    let findings = Linter::new()
+        .with_rule(CallArguments)
+        .with_config(DiagnosticsConfig {
+            max_findings: Some(2),
+            ..Default::default()
+        })
+        .check(&context, &mut program);
+
+    let rules: Vec<(&str, usize)> = findings
+        .iter()
+        .map(|finding| (finding.rule, finding.span.line))
+        .collect();
+
+    assert_eq!(
+        rules,
+        vec![
+            (CallArguments::NAME, 4),
+            (CallArguments::NAME, 5),
+            (Linter::TRUNCATED, 5)
+        ]
+    );
+}
+
+#[test]
+fn test_max_findings_reached_exactly() {
+    let source = "<?php
+function one(int $value) {}
+
+one();
+one();
+";
+    let mut program = parser::parse(source).unwrap();
+    let index = Index::from_program(&program);
+    let context = Context {
+        source: source.as_bytes(),
+        index: &index,
+        version: PhpVersion::Php83,
+    };
+
+    let findings = Linter::new()
+        .with_rule(CallArguments)
+        .with_config(DiagnosticsConfig {
+            max_findings: Some(2),
+            ..Default::default()
+        })
+        .check(&context, &mut program);
+
+    let rules: Vec<&str> = findings.iter().map(|finding| finding.rule).collect();
+
+    assert_eq!(rules, vec![CallArguments::NAME, CallArguments::NAME]);
+}
+
+#[test]
+fn test_nested_ternary() {
+    let source = "<?php
+$a ? $b : $c ? $d : $e;
+$a ?: $b ? $c : $d;
+$a ? $b : ($c ? $d : $e);
+$a ? ($b ? $c : $d) : $e;
+$a ?: $b ?: $c;
+";
+    let index = Index::new();
+
+    for (version, severity) in [
+        (PhpVersion::Php74, Severity::Warning),
+        (PhpVersion::Php80, Severity::Error),
+    ] {
+        let mut program = parser::parse(source).unwrap();
+        let context = Context {
+            source: source.as_bytes(),
+            index: &index,
+            version,
+        };
+
+        let findings = Linter::new()
+            .with_rule(NestedTernary)
+            .check(&context, &mut program);
+
+        let spans: Vec<(usize, usize, Severity)> = findings
+            .iter()
+            .map(|finding| (finding.span.line, finding.span.column, finding.severity))
+            .collect();
+
+        assert_eq!(spans, vec![(2, 14, severity), (3, 10, severity)]);
+    }
+}
+
+#[test]
+fn test_invalid_this() {
+    let source = "<?php
+function free() { return $this; }
+
+class Foo {
+    public function run() {
+        $a = fn() => $this;
+        $b = static fn() => $this;
+        $c = static function() {
+            return function() { return $this; };
+        };
+    }
+
+    public static function make() { return $this; }
+}
+
+$this->run();
+";
+    let mut program = parser::parse(source).unwrap();
+    let index = Index::new();
+    let context = Context {
+        source: source.as_bytes(),
+        index: &index,
+        version: PhpVersion::Php83,
+    };
+
+    let findings = Linter::new()
+        .with_rule(InvalidThis)
+        .check(&context, &mut program);
+
+    let messages: Vec<(usize, &str)> = findings
+        .iter()
+        .map(|finding| (finding.span.line, finding.message.as_str()))
+        .collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            (2, "Cannot use $this inside a function"),
+            (7, "Cannot use $this inside a static arrow function"),
+            (9, "Cannot use $this inside a static closure"),
+            (13, "Cannot use $this inside a static method"),
+        ]
+    );
+}
+
+#[test]
+fn test_uninitialized_property() {
+    let source = "<?php
+final class User {
+    public readonly int $id;
+    public string $name;
+    public ?string $email;
+    public int $age = 0;
+    public $untyped;
+    private array $roles;
+
+    public function __construct(int $id, ?string $name, public bool $admin = false) {
+        $this->id = $id;
+        if ($name === null) {
+            throw new InvalidArgumentException();
+        } elseif ($name === '') {
+            $this->name = 'anonymous';
+        } else {
+            $this->name = $name;
+        }
+
+        try {
+            $this->email = lookup($id);
+        } catch (Exception $e) {
+            return;
+        }
+
+        foreach ([] as $role) {
+            $this->roles[] = $role;
+        }
+    }
+}
+
+class Lazy {
+    private int $value;
+
+    public function __construct() {
+        $this->initialize();
+    }
+}
+
+class Destructured {
+    private int $a;
+    private int $b;
+
+    public function __construct(array $values) {
+        [$this->a, $this->b] = $values;
+    }
+}
+";
+    let mut program = parser::parse(source).unwrap();
+    let index = Index::new();
+    let context = Context {
+        source: source.as_bytes(),
+        index: &index,
+        version: PhpVersion::Php83,
+    };
+
+    let findings = Linter::new()
+        .with_rule(UninitializedProperty)
+        .check(&context, &mut program);
+
+    let messages: Vec<(usize, &str)> = findings
+        .iter()
+        .map(|finding| (finding.span.line, finding.message.as_str()))
+        .collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            (
+                5,
+                "Typed property User::$email may be left uninitialized by the constructor"
+            ),
+            (
+                8,
+                "Typed property User::$roles may be left uninitialized by the constructor"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_imports() {
+    let source = "<?php
+namespace App;
+
+use Vendor\\Logger;
+use Vendor\\Logger;
+use Vendor\\Cache, Vendor\\Unused;
+use Other\\Logger;
+use Vendor\\Request;
+use App\\Handler;
+use function Vendor\\{helper, unused_helper};
+use Vendor\\Models\\{User, Post};
+
+/** @var Post[] $posts */
+$posts = [];
+
+class Request {}
+class Handler {}
+
+function run(Logger $logger): Cache {
+    helper(User::class);
+}
+";
+    let mut program = parser::parse(source).unwrap();
+    let index = Index::new();
+    let context = Context {
+        source: source.as_bytes(),
+        index: &index,
+        version: PhpVersion::Php83,
+    };
+
+    let findings = Linter::new()
+        .with_rule(Imports)
+        .check(&context, &mut program);
+
+    let messages: Vec<(usize, Severity, &str)> = findings
+        .iter()
+        .map(|finding| {
+            (
+                finding.span.line,
+                finding.severity,
+                finding.message.as_str(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            (5, Severity::Error, "`Vendor\\Logger` is already imported"),
+            (
+                6,
+                Severity::Warning,
+                "`Vendor\\Unused` is imported, but never used"
+            ),
+            (
+                7,
+                Severity::Error,
+                "Cannot import `Other\\Logger` as `Logger` because the name is already in use"
+            ),
+            (
+                8,
+                Severity::Error,
+                "Cannot import `Vendor\\Request` as `Request` because the namespace declares class `Request`"
+            ),
+            (
+                10,
+                Severity::Warning,
+                "function `Vendor\\unused_helper` is imported, but never used"
+            ),
+        ]
+    );
+
+    let fixed = String::from_utf8(apply_fixes(source.as_bytes(), &findings)).unwrap();
+    assert!(fixed.starts_with(
+        "<?php
+namespace App;
+
+use Vendor\\Logger;
+use Vendor\\Cache;
+use Other\\Logger;
+use Vendor\\Request;
+use App\\Handler;
+use function Vendor\\{helper};
+use Vendor\\Models\\{User, Post};
+"
+    ));
+
+    // removing every import of a statement removes the whole line.
+    let source = "<?php\nuse Foo, Bar;\n\nnew Baz();\n";
+    let mut program = parser::parse(source).unwrap();
+    let context = Context {
+        source: source.as_bytes(),
+        ..context
+    };
+    let findings = Linter::new()
+        .with_rule(Imports)
+        .check(&context, &mut program);
+
+    assert_eq!(findings.len(), 2);
+    assert_eq!(
+        apply_fixes(source.as_bytes(), &findings),
+        b"<?php\n\nnew Baz();\n"
+    );
+}
+
+#[test]
+fn test_findings_to_json() {
+    let source = "<?php
+function one(int $value) {}
+
+one();
+";
+    let mut program = parser::parse(source).unwrap();
+    let index = Index::from_program(&program);
+    let context = Context {
+        source: source.as_bytes(),
+        index: &index,
+        version: PhpVersion::Php83,
+    };
+
+    let findings = Linter::new()
+        .with_rule(CallArguments)
+        .check(&context, &mut program);
+
+    let json = findings_to_json(source.as_bytes(), &findings, &DiagnosticsConfig::default());
+    assert_eq!(json[0]["rule"], CallArguments::NAME);
+    assert_eq!(json[0]["severity"], "error");
+    assert_eq!(json[0]["line"], 4);
+    assert!(json[0].get("code_frame").is_none());
+
+    let json = findings_to_json(
+        source.as_bytes(),
+        &findings,
+        &DiagnosticsConfig {
+            code_frame_lines: Some(1),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        json[0]["code_frame"],
+        serde_json::json!({
+            "lines": [
+                {"number": 3, "text": "", "highlight": null},
+                {"number": 4, "text": "one();", "highlight": [0, 3]},
+                {"number": 5, "text": "", "highlight": null},
+            ]
+        })
+    );
+}
+
+#[test]
+fn test_findings_to_json_column_unit() {
+    let source = "<?php
+function one(int $value) {}
+
+/* ünïcödé */ one();
+";
+    let mut program = parser::parse(source).unwrap();
+    let index = Index::from_program(&program);
+    let context = Context {
+        source: source.as_bytes(),
+        index: &index,
+        version: PhpVersion::Php83,
+    };
+
+    let findings = Linter::new()
+        .with_rule(CallArguments)
+        .check(&context, &mut program);
+
+    let json = findings_to_json(source.as_bytes(), &findings, &DiagnosticsConfig::default());
+    assert_eq!(json[0]["column"], 19);
+
+    let json = findings_to_json(
+        source.as_bytes(),
+        &findings,
+        &DiagnosticsConfig {
+            code_frame_lines: Some(0),
+            column_unit: ColumnUnit::Utf16CodeUnits,
+            ..Default::default()
+        },
+    );
+    assert_eq!(json[0]["column"], 15);
+    assert_eq!(
+        json[0]["code_frame"]["lines"][0]["highlight"],
+        serde_json::json!([14, 17])
+    );
+}
+
+#[test]
+fn test_findings_to_json_highlights_the_span() {
+    // an `é`, followed by an invalid byte.
+    let source = b"<?php\n/* \xc3\xa9\xff */ foo(1, 2);\n";
+    let position = 16;
+    let finding = Finding {
+        rule: "test",
+        severity: Severity::Warning,
+        message: "message".to_string(),
+        span: Span {
+            line: 2,
+            column: 11,
+            position,
+            end_position: position + b"foo(1, 2)".len(),
+        },
+        fix: None,
+    };
+
+    for (column_unit, highlight) in [
+        (ColumnUnit::Bytes, [10, 19]),
+        (ColumnUnit::CodePoints, [9, 18]),
+        (ColumnUnit::Utf16CodeUnits, [9, 18]),
+    ] {
+        let json = findings_to_json(
+            source,
+            std::slice::from_ref(&finding),
+            &DiagnosticsConfig {
+                code_frame_lines: Some(0),
+                column_unit,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            json[0]["code_frame"]["lines"][0]["highlight"],
+            serde_json::json!(highlight),
+            "{:?}",
+            column_unit
+        );
+    }
+}
+
+#[test]
+fn test_lint_config() {
+    let config = LintConfig::from_toml(
+        r#"
+baseline = "baseline.json"
+
+[rules]
+nested-ternary = "info"
+call-arguments = "off"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(config.level(NestedTernary::NAME), Some(RuleLevel::Info));
+    assert_eq!(config.level(CallArguments::NAME), Some(RuleLevel::Off));
+    assert_eq!(config.level(InvalidThis::NAME), None);
+    assert!(LintConfig::from_toml("[rules]\nnested-ternary = \"loud\"").is_err());
+    assert!(LintConfig::from_toml("unknown = true").is_err());
+
+    let source = "<?php
+function one(int $value) {}
+
+one();
+$a ? $b : $c ? $d : $e;
+";
+    let mut program = parser::parse(source).unwrap();
+    let index = Index::from_program(&program);
+    let context = Context {
+        source: source.as_bytes(),
+        index: &index,
+        version: PhpVersion::Php74,
+    };
+
+    let findings = Linter::new()
+        .with_rule(CallArguments)
+        .with_rule(NestedTernary)
+        .with_lint_config(&config)
+        .check(&context, &mut program);
+
+    let findings: Vec<(&str, Severity)> = findings
+        .iter()
+        .map(|finding| (finding.rule, finding.severity))
+        .collect();
+
+    assert_eq!(findings, vec![(NestedTernary::NAME, Severity::Info)]);
+}
+
+#[test]
+fn test_baseline() {
+    let source = "<?php
+function one(int $value) {}
+
+one();
+one();
+";
+    let lint = |source: &str| {
+        let mut program = parser::parse(source).unwrap();
+        let index = Index::from_program(&program);
+        let context = Context {
+            source: source.as_bytes(),
+            index: &index,
+            version: PhpVersion::Php83,
+        };
+
+        Linter::new()
+            .with_rule(CallArguments)
+            .check(&context, &mut program)
+    };
+
+    let mut baseline = Baseline::new();
+    baseline.record("src/a.php", &lint(source));
+    assert_eq!(baseline.entries.len(), 1);
+    assert_eq!(baseline.entries[0].count, 2);
+
+    let baseline = Baseline::from_json(&baseline.to_json()).unwrap();
+
+    // known findings are not reported, even after moving.
+    let moved = source.replace("one();", "\n\none();");
+    assert!(baseline.filter("src/a.php", lint(&moved)).is_empty());
+    assert_eq!(baseline.filter("src/b.php", lint(source)).len(), 2);
+
+    // a third identical finding is new.
+    let added = format!("{}one();\n", source);
+    let findings = baseline.filter("src/a.php", lint(&added));
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].span.line, 6);
+}
@@ -0,0 +1,274 @@
+use std::fs;
+use std::path::PathBuf;
+
+use php_parser_rs::lexer::byte_string::ByteString;
+use php_parser_rs::lexer::token::Token;
+use php_parser_rs::lexer::token::TokenKind;
+use php_parser_rs::node::Node;
+use php_parser_rs::parse_lossless;
+use php_parser_rs::parse_with_tokens;
+use php_parser_rs::parser::ast::classes::ClassBody;
+use php_parser_rs::parser::ast::functions::FunctionStatement;
+use php_parser_rs::parser::ast::literals::LiteralString;
+use php_parser_rs::parser::ast::namespaces::NamespaceStatement;
+use php_parser_rs::parser::ast::variables::SimpleVariable;
+use php_parser_rs::parser::ast::Statement;
+use php_parser_rs::parser::lossless::token_range;
+use php_parser_rs::parser::lossless::LosslessProgram;
+use php_parser_rs::parser::lossless::Trivia;
+use php_parser_rs::traverser::walk;
+use php_parser_rs::traverser::walk_mut;
+use php_parser_rs::traverser::Visitor;
+use php_parser_rs::traverser::VisitorMut;
+
+struct TriviaChecker<'a> {
+    lossless: &'a LosslessProgram,
+    path: PathBuf,
+}
+
+impl Visitor for TriviaChecker<'_> {
+    fn enter_node(&mut self, node: &dyn Node) {
+        let (Some(span), Some(trivia)) = (node.span(), self.lossless.trivia(node)) else {
+            return;
+        };
+
+        let leading = span.position - trivia.leading.len()..span.position;
+        let trailing = span.end_position..span.end_position + trivia.trailing.len();
+        for token in &self.lossless.tokens {
+            if matches!(
+                token.token.kind,
+                TokenKind::SingleLineComment
+                    | TokenKind::HashMarkComment
+                    | TokenKind::MultiLineComment
+                    | TokenKind::DocumentComment
+                    | TokenKind::Eof
+            ) {
+                continue;
+            }
+
+            let position = token.token.span.position;
+            assert!(
+                !leading.contains(&position) && !trailing.contains(&position),
+                "{}: {:?} in the trivia of {}",
+                self.path.display(),
+                token.text,
+                node.kind()
+            );
+        }
+    }
+}
+
+struct TokenRangeChecker<'a> {
+    tokens: &'a [Token],
+    path: PathBuf,
+}
+
+impl Visitor for TokenRangeChecker<'_> {
+    fn enter_node(&mut self, node: &dyn Node) {
+        let (Some(span), Some(range)) = (node.span(), token_range(self.tokens, node)) else {
+            return;
+        };
+
+        let (Some(first), Some(last)) = (
+            self.tokens[range.clone()].first(),
+            self.tokens[range].last(),
+        ) else {
+            return;
+        };
+
+        assert!(
+            first.span.position == span.position && last.span.end_position <= span.end_position,
+            "{}: tokens of {}",
+            self.path.display(),
+            node.kind()
+        );
+    }
+}
+
+#[test]
+fn test_lossless_round_trips_fixtures() {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    for entry in fs::read_dir(fixtures).unwrap().flatten() {
+        let Ok(code) = fs::read(entry.path().join("code.php")) else {
+            continue;
+        };
+        let Ok(lossless) = parse_lossless(&code) else {
+            continue;
+        };
+
+        assert_eq!(lossless.print(), code, "{}", entry.path().display());
+
+        // trivia never contains the tokens of the program itself.
+        let mut checker = TriviaChecker {
+            lossless: &lossless,
+            path: entry.path(),
+        };
+        for statement in &lossless.program {
+            walk(&mut checker, statement);
+        }
+
+        // the tokens of every node start where the node does, and end within it.
+        let (program, tokens) = parse_with_tokens(&code).unwrap();
+        let mut checker = TokenRangeChecker {
+            tokens: &tokens,
+            path: entry.path(),
+        };
+        for statement in &program {
+            walk(&mut checker, statement);
+        }
+    }
+}
+
+#[test]
+fn test_trivia() {
+    let code = "<?php\n\n// first\n$a = 1; // one\n\n/** second */\n$b = 2;   \n";
+    let lossless = parse_lossless(code).unwrap();
+
+    let actual: Vec<Trivia> = lossless
+        .program
+        .iter()
+        .map(|statement| lossless.trivia(statement).unwrap())
+        .collect();
+
+    let trivia = |leading: &str, trailing: &str| Trivia {
+        leading: ByteString::from(leading),
+        trailing: ByteString::from(trailing),
+    };
+
+    assert_eq!(
+        actual,
+        vec![
+            trivia("", "\n"),
+            trivia("\n// first\n", " // one\n"),
+            trivia("\n/** second */\n", "   \n"),
+        ]
+    );
+
+    assert_eq!(
+        lossless.text(&lossless.program[1]),
+        Some(ByteString::from("$a = 1;"))
+    );
+}
+
+#[test]
+fn test_tokens_of() {
+    let code = "<?php\n/** A */\nfunction a() {}\n\n$b = [1,  2];\n";
+    let lossless = parse_lossless(code).unwrap();
+
+    let text = |index: usize| -> String {
+        lossless
+            .tokens_of(&lossless.program[index])
+            .unwrap()
+            .iter()
+            .map(|token| format!("{}{}", token.leading_trivia, token.text))
+            .collect()
+    };
+
+    assert_eq!(text(1), "\n/** A */\nfunction a() {}");
+    assert_eq!(text(2), "\n\n$b = [1,  2];");
+
+    let (program, tokens) = parse_with_tokens(code).unwrap();
+    assert_eq!(token_range(&tokens, &program[2]), Some(8..16));
+}
+
+struct Edits;
+
+impl VisitorMut for Edits {
+    fn enter_function_statement(&mut self, node: &mut FunctionStatement) {
+        node.name.value = "renamed".into();
+    }
+
+    fn enter_simple_variable(&mut self, node: &mut SimpleVariable) {
+        if node.name == b"$old" {
+            node.name = "$new".into();
+        }
+    }
+
+    fn enter_literal_string(&mut self, node: &mut LiteralString) {
+        node.value = "world".into();
+    }
+
+    fn enter_class_body(&mut self, node: &mut ClassBody) {
+        node.members.remove(0);
+    }
+}
+
+#[test]
+fn test_print_edited_program() {
+    let code = "<?php
+
+// does things
+function things($old) {
+    return $old . 'hello'; // greets
+}
+
+class Foo {
+    /** Removed. */
+    public function bar() {}
+
+    public function baz() {}
+}
+
+// removed
+echo \"$old\";
+
+echo 1;
+";
+    let mut lossless = parse_lossless(code).unwrap();
+    assert_eq!(lossless.print(), code.as_bytes());
+
+    for statement in &mut lossless.program {
+        walk_mut(&mut Edits, statement);
+    }
+    lossless.program.remove(3);
+
+    assert_eq!(
+        String::from_utf8(lossless.print()).unwrap(),
+        "<?php
+
+// does things
+function renamed($new) {
+    return $new . 'world'; // greets
+}
+
+class Foo {
+
+    public function baz() {}
+}
+
+echo 1;
+"
+    );
+}
+
+#[test]
+fn test_print_removed_from_namespace() {
+    let code = "<?php
+
+namespace Foo;
+
+const BAR = 'bar';
+
+// removed
+function baz() {}
+";
+    let mut lossless = parse_lossless(code).unwrap();
+
+    let Statement::Namespace(NamespaceStatement::Unbraced(namespace)) = &mut lossless.program[1]
+    else {
+        panic!("expected an unbraced namespace");
+    };
+    namespace.statements.pop();
+
+    // the namespace now ends earlier, but keeps its own tokens.
+    assert_eq!(
+        String::from_utf8(lossless.print()).unwrap(),
+        "<?php
+
+namespace Foo;
+
+const BAR = 'bar';
+"
+    );
+}
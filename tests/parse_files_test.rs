@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use php_parser_rs::parse_files;
+use php_parser_rs::parser::ast::classes::ClassMember;
+use php_parser_rs::parser::ast::Statement;
+use php_parser_rs::ParserConfig;
+
+#[test]
+fn test_parse_files() {
+    let root = std::env::temp_dir().join(format!("php-parser-rs-files-{}", std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+
+    let files = [
+        ("a.php", "<?php class Foo { public function handle() {} }"),
+        ("b.php", "<?php class Bar { public function handle() {} }"),
+        ("c.php", "<?php function ("),
+    ];
+    for (name, code) in files {
+        std::fs::write(root.join(name), code).unwrap();
+    }
+
+    let mut paths: Vec<_> = files.iter().map(|(name, _)| root.join(name)).collect();
+    paths.push(root.join("missing.php"));
+
+    let parsed = parse_files(&paths, ParserConfig::default());
+
+    assert!(!parsed.is_valid());
+    assert_eq!(
+        parsed
+            .files
+            .iter()
+            .map(|file| file.path.file_name().unwrap().to_str().unwrap())
+            .collect::<Vec<_>>(),
+        vec!["a.php", "b.php", "c.php"]
+    );
+    assert_eq!(parsed.unreadable.len(), 1);
+    assert_eq!(parsed.unreadable[0].0, root.join("missing.php"));
+
+    let errors: Vec<_> = parsed.errors().collect();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, root.join("c.php"));
+
+    // the same name in different files shares its storage.
+    let method = |index: usize| {
+        let Statement::Class(class) = &parsed.files[index].program[1] else {
+            panic!("expected a class");
+        };
+        let ClassMember::ConcreteMethod(method) = &class.body.members[0] else {
+            panic!("expected a method");
+        };
+
+        method.name.value.clone()
+    };
+    assert!(Arc::ptr_eq(&method(0).bytes, &method(1).bytes));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,134 @@
+use php_parser_rs::analysis::stats::ProgramStats;
+use php_parser_rs::analysis::stats::Stats;
+use php_parser_rs::lexer::LexerConfig;
+use php_parser_rs::parse_with_config;
+use php_parser_rs::parser::ast::Statement;
+use php_parser_rs::ParserConfig;
+
+#[test]
+fn test_max_depth() {
+    let config = ParserConfig {
+        max_depth: Some(8),
+        ..Default::default()
+    };
+
+    let shallow = format!("<?php\n$a = {}1{};\n", "(".repeat(4), ")".repeat(4));
+    assert!(parse_with_config(&shallow, config).is_ok());
+
+    let deep = format!("<?php\n$a = {}1{};\n", "(".repeat(500), ")".repeat(500));
+    let error = parse_with_config(&deep, config).unwrap_err();
+
+    assert_eq!(error.errors.len(), 1);
+    assert_eq!(error.errors[0].id, "E052");
+    assert_eq!(
+        error.errors[0].message,
+        "maximum nesting depth of 8 exceeded"
+    );
+
+    let nested = "<?php\nif (true) { if (true) { if (true) { if (true) { if (true) { if (true) { if (true) { if (true) { echo 1; } } } } } } } }\n";
+    let error = parse_with_config(nested, config).unwrap_err();
+    assert_eq!(error.errors[0].id, "E052");
+    assert_eq!(error.errors[0].span.line, 2);
+}
+
+#[test]
+fn test_short_tags() {
+    let source = "<? echo 1; ?>\n<?= 2 ?>\n";
+
+    let program = parse_with_config(source, ParserConfig::default()).unwrap();
+    assert!(matches!(program[0], Statement::ShortOpeningTag(_)));
+    assert!(matches!(program[1], Statement::Echo(_)));
+    // `<?=` is the `echo` of the statement it starts.
+    match &program[4] {
+        Statement::Echo(echo) => {
+            assert_eq!(&source[echo.echo.position..echo.echo.end_position], "<?=")
+        }
+        statement => panic!("expected echo, found {:?}", statement),
+    }
+
+    let config = ParserConfig {
+        lexer: LexerConfig { short_tags: false },
+        ..Default::default()
+    };
+
+    let program = parse_with_config(source, config).unwrap();
+    match &program[0] {
+        Statement::InlineHtml(html) => assert_eq!(html.html, b"<? echo 1; ?>\n"),
+        statement => panic!("expected inline html, found {:?}", statement),
+    }
+    assert!(matches!(program[1], Statement::Echo(_)));
+}
+
+#[test]
+fn test_max_attributes() {
+    let source = format!("<?php\n{}\nfunction foo() {{}}\n", "#[A]\n".repeat(100_000));
+    let program = parse_with_config(&source, ParserConfig::default()).unwrap();
+    match &program[1] {
+        Statement::Function(function) => assert_eq!(function.attributes.len(), 100_000),
+        statement => panic!("expected function, found {:?}", statement),
+    }
+
+    let config = ParserConfig {
+        max_attributes: Some(4),
+        ..Default::default()
+    };
+
+    let source = "<?php\n#[A, B]\n#[C]\n#[D, E]\nfunction foo() {}\n";
+    let error = parse_with_config(source, config).unwrap_err();
+    assert_eq!(error.errors.len(), 1);
+    assert_eq!(error.errors[0].id, "E053");
+    assert_eq!(
+        error.errors[0].message,
+        "maximum number of 4 attributes on a declaration exceeded"
+    );
+    assert_eq!(error.errors[0].span.line, 4);
+    assert_eq!(error.errors[0].span.column, 6);
+
+    // the limit applies to each declaration separately.
+    let source = "<?php\n#[A, B, C]\nclass Foo {\n    #[D, E, F]\n    public function bar(#[G] $baz) {}\n}\n";
+    assert!(parse_with_config(source, config).is_ok());
+
+    let modifiers = format!(
+        "<?php\nclass Foo {{ {} function bar() {{}} }}\n",
+        "public ".repeat(100_000)
+    );
+    let error = parse_with_config(&modifiers, config).unwrap_err();
+    assert_eq!(error.errors[0].span.column, 20);
+}
+
+#[test]
+fn test_intern_literals() {
+    let code = format!(
+        "<?php\nreturn [{}];\n",
+        "['driver' => 'mysql', 'host' => 'localhost', 'comment' => 'a rather long comment'], "
+            .repeat(100)
+    );
+
+    let program = parse_with_config(&code, ParserConfig::default()).unwrap();
+    assert_eq!(
+        program.stats(),
+        ProgramStats {
+            string_literals: 600,
+            string_literal_bytes: 100 * 52,
+            string_literal_allocations: 600,
+            shared_bytes: 0,
+        }
+    );
+
+    let config = ParserConfig {
+        intern_literals: Some(16),
+        ..Default::default()
+    };
+    let interned = parse_with_config(&code, config).unwrap();
+    assert_eq!(interned, program);
+    assert_eq!(
+        interned.stats(),
+        ProgramStats {
+            string_literals: 600,
+            string_literal_bytes: 100 * 52,
+            // the comment is longer than the threshold, so each has its own storage.
+            string_literal_allocations: 5 + 100,
+            shared_bytes: 99 * 31,
+        }
+    );
+}
@@ -0,0 +1,153 @@
+use std::fs;
+use std::path::PathBuf;
+
+use php_parser_rs::lexer::byte_string::ByteString;
+use php_parser_rs::lexer::token::OpenTagKind;
+use php_parser_rs::lexer::token::TokenKind;
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::printer::minify;
+use php_parser_rs::printer::tokens_to_source;
+
+#[test]
+fn test_tokens_to_source_round_trips_fixtures() {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    for entry in fs::read_dir(fixtures).unwrap().flatten() {
+        let Ok(code) = fs::read(entry.path().join("code.php")) else {
+            continue;
+        };
+        let Ok(tokens) = Lexer::new().tokenize_with_trivia(&code) else {
+            continue;
+        };
+
+        assert_eq!(
+            tokens_to_source(&tokens),
+            code,
+            "{}",
+            entry.path().display()
+        );
+    }
+}
+
+#[test]
+fn test_tokenize_with_trivia() {
+    let tokens = Lexer::new()
+        .tokenize_with_trivia("<?php  echo \"a\"; \n")
+        .unwrap();
+
+    let tokens: Vec<(TokenKind, String, String)> = tokens
+        .into_iter()
+        .map(|token| {
+            (
+                token.token.kind,
+                token.leading_trivia.to_string(),
+                token.text.to_string(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        tokens,
+        vec![
+            (
+                TokenKind::OpenTag(OpenTagKind::Full),
+                "".to_owned(),
+                "<?php".to_owned()
+            ),
+            (TokenKind::Echo, "  ".to_owned(), "echo".to_owned()),
+            (
+                TokenKind::LiteralDoubleQuotedString,
+                " ".to_owned(),
+                "\"a\"".to_owned()
+            ),
+            (TokenKind::SemiColon, "".to_owned(), ";".to_owned()),
+            (TokenKind::Eof, " \n".to_owned(), "".to_owned()),
+        ]
+    );
+}
+
+// The kinds and values of the tokens, ignoring comments other than docblocks.
+fn significant_tokens(code: &[u8]) -> Vec<(TokenKind, ByteString)> {
+    Lexer::new()
+        .tokenize(code)
+        .unwrap()
+        .into_iter()
+        .filter(|token| {
+            !matches!(
+                token.kind,
+                TokenKind::SingleLineComment
+                    | TokenKind::HashMarkComment
+                    | TokenKind::MultiLineComment
+            )
+        })
+        .map(|token| (token.kind, token.value))
+        .collect()
+}
+
+#[test]
+fn test_minify_preserves_tokens_of_fixtures() {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    for entry in fs::read_dir(fixtures).unwrap().flatten() {
+        let Ok(code) = fs::read(entry.path().join("code.php")) else {
+            continue;
+        };
+        if Lexer::new().tokenize(&code).is_err() {
+            continue;
+        }
+
+        let minified = minify(&code).unwrap();
+
+        assert!(minified.len() <= code.len(), "{}", entry.path().display());
+        assert_eq!(
+            significant_tokens(&minified),
+            significant_tokens(&code),
+            "{}",
+            entry.path().display()
+        );
+    }
+}
+
+#[test]
+fn test_minify() {
+    let cases: &[(&str, &str)] = &[
+        (
+            "<?php\n\n/* a */ $a  =  $b  +  +$c; # b\n",
+            "<?php $a=$b+ +$c;",
+        ),
+        (
+            "<?php\nif ($a) {\n} else if ($b) {\n}\n",
+            "<?php if($a){}else if($b){}",
+        ),
+        ("<?php $a = 1 . 2 . $b . .5;", "<?php $a=1 . 2 .$b. .5;"),
+        (
+            "<?php\n/** @var int */\n$a = 1;",
+            "<?php /** @var int */$a=1;",
+        ),
+        (
+            "<?php $a = \"a  $b  c\" . 'd  e';",
+            "<?php $a=\"a  $b  c\".'d  e';",
+        ),
+        (
+            "<?php\n$a = <<<EOF\n    a  {$b}\n      c\n    EOF;\n",
+            "<?php $a= <<<EOF\n    a  {$b}\n      c\n    EOF;",
+        ),
+        (
+            "<p>\n  <?= $a ?>\n</p>\n<?php // end\n",
+            "<p>\n  <?=$a?>\n</p>\n<?php",
+        ),
+        (
+            "<?php\n$a = 1;\n__halt_compiler();  raw  data\n",
+            "<?php $a=1;__halt_compiler();  raw  data\n",
+        ),
+    ];
+
+    for (code, expected) in cases {
+        assert_eq!(
+            String::from_utf8(minify(code).unwrap()).unwrap(),
+            *expected,
+            "{}",
+            code
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use php_parser_rs::parser;
+use php_parser_rs::parser::ast::variables::SimpleVariable;
+use php_parser_rs::parser::ast::MethodCallExpression;
+use php_parser_rs::query::find_all;
+use php_parser_rs::query::query;
+use php_parser_rs::query::Selector;
+
+const CODE: &str = "<?php
+function save($a) {
+    $a->save(strlen('a'));
+}
+
+class Foo {
+    public function bar() {
+        $this->save();
+        return fn &() => strlen($this->name);
+    }
+}
+";
+
+// The source covered by each match of the selector.
+fn texts(selector: &str) -> Vec<&'static str> {
+    let program = parser::parse(CODE).unwrap();
+
+    query(&program, selector)
+        .unwrap()
+        .iter()
+        .map(|found| {
+            let span = found.span.unwrap();
+
+            &CODE[span.position..span.end_position]
+        })
+        .collect()
+}
+
+#[test]
+fn test_find_all() {
+    let program = parser::parse(CODE).unwrap();
+
+    let calls = find_all::<MethodCallExpression>(&program);
+    assert_eq!(calls.len(), 2);
+
+    let variables: Vec<String> = find_all::<SimpleVariable>(&program)
+        .iter()
+        .map(|variable| variable.name.to_string())
+        .collect();
+    assert_eq!(variables, vec!["$a", "$a", "$this", "$this"]);
+}
+
+#[test]
+fn test_kinds() {
+    assert_eq!(
+        texts("MethodCall"),
+        vec!["$a->save(strlen('a'))", "$this->save()"]
+    );
+    assert_eq!(
+        texts("MethodCallExpression"),
+        vec!["$a->save(strlen('a'))", "$this->save()"]
+    );
+    assert_eq!(
+        texts("ArrowFunction"),
+        vec!["fn &() => strlen($this->name)"]
+    );
+}
+
+#[test]
+fn test_conditions() {
+    assert_eq!(
+        texts("FunctionCall[target=strlen]"),
+        vec!["strlen('a')", "strlen($this->name)"]
+    );
+    assert_eq!(
+        texts("MethodCall[target=$this][method=save]"),
+        vec!["$this->save()"]
+    );
+    assert_eq!(texts("PropertyFetch[property='name']"), vec!["$this->name"]);
+    assert_eq!(
+        texts("ArrowFunction[ampersand]"),
+        vec!["fn &() => strlen($this->name)"]
+    );
+    assert!(texts("Closure[ampersand]").is_empty());
+}
+
+#[test]
+fn test_descendants() {
+    assert_eq!(
+        texts("ClassStatement FunctionCall"),
+        vec!["strlen($this->name)"]
+    );
+    assert_eq!(texts("Function * Literal"), vec!["'a'"]);
+}
+
+#[test]
+fn test_invalid_selectors() {
+    for (selector, position) in [
+        ("", 0),
+        ("Foo[", 3),
+        ("Foo[]", 4),
+        ("Foo.bar", 3),
+        ("[a]", 0),
+    ] {
+        let error = selector.parse::<Selector>().unwrap_err();
+
+        assert_eq!(error.position, position, "{}", selector);
+    }
+}
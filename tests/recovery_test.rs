@@ -0,0 +1,77 @@
+use php_parser_rs::parser;
+use php_parser_rs::parser::ast::functions::FunctionStatement;
+use php_parser_rs::parser::ast::Expression;
+use php_parser_rs::parser::ast::Statement;
+
+#[test]
+fn test_valid_input_has_no_errors() {
+    let source = "<?php\nfunction foo($a) { return $a + 1; }\necho foo(1);\n";
+    let (program, errors) = parser::parse_with_recovery(source);
+
+    assert!(errors.is_empty());
+    assert_eq!(program, parser::parse(source).unwrap());
+}
+
+#[test]
+fn test_missing_expression() {
+    let (program, errors) = parser::parse_with_recovery("<?php\n$a = ;\necho 1;\n");
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "unexpected token `;`");
+    assert_eq!(program.len(), 3);
+
+    let Statement::Expression(statement) = &program[1] else {
+        panic!("expected an expression statement");
+    };
+    let Expression::AssignmentOperation(assignment) = &statement.expression else {
+        panic!("expected an assignment");
+    };
+    assert!(matches!(assignment.right(), Expression::Missing(span) if span.position == 11));
+    assert!(matches!(program[2], Statement::Echo(_)));
+}
+
+#[test]
+fn test_missing_statement() {
+    let source = "<?php\nif ($a { echo 1; }\necho 2;\n";
+    let (program, errors) = parser::parse_with_recovery(source);
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span.position, 13);
+
+    let Statement::Missing(span) = &program[1] else {
+        panic!("expected a missing statement");
+    };
+    assert_eq!(
+        &source[span.position..span.end_position],
+        "if ($a { echo 1; }"
+    );
+    assert!(matches!(program[2], Statement::Echo(_)));
+}
+
+#[test]
+fn test_unclosed_blocks_at_end_of_file() {
+    let source = "<?php\nfunction foo() {\n    echo 1;\n    $a = \n";
+    let (program, errors) = parser::parse_with_recovery(source);
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[1].message, "unexpected end of file, expecting `}`");
+
+    let Statement::Function(FunctionStatement { body, .. }) = &program[1] else {
+        panic!("expected a function");
+    };
+    assert!(matches!(body.statements[0], Statement::Echo(_)));
+    assert!(matches!(body.statements[1], Statement::Missing(_)));
+    assert_eq!(body.right_brace.length(), 0);
+}
+
+#[test]
+fn test_statements_before_lexer_error() {
+    let source = "<?php\n$a = 1;\necho $a;\n$b = \"abc";
+    let (program, errors) = parser::parse_with_recovery(source);
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].id, "E001");
+
+    assert!(matches!(program[1], Statement::Expression(_)));
+    assert!(matches!(program[2], Statement::Echo(_)));
+}
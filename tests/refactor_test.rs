@@ -0,0 +1,102 @@
+use php_parser_rs::parser;
+use php_parser_rs::refactor;
+use php_parser_rs::refactor::Refactoring;
+
+fn refactor(source: &str, refactoring: Refactoring) -> String {
+    let mut program = parser::parse(source).unwrap();
+    let fixes = refactoring.fixes(source.as_bytes(), &mut program);
+
+    String::from_utf8(refactor::apply(source.as_bytes(), &fixes)).unwrap()
+}
+
+#[test]
+fn test_closure_to_arrow_function() {
+    let source = "<?php
+$a = function ($x) use ($y) { return $x + $y; };
+$b = static function () use ($y): int {
+    return $y;
+};
+$c = function () use ($y) { return function () use ($y) { return $y; }; };
+$d = function () use (&$y) { return $y; };
+$e = function () { return $y; };
+$f = function () use ($y) { $y++; return $y; };
+$g = function () use ($y) { return yield $y; };
+$h = function () use ($y) { return $y; /* comment */ };
+";
+
+    assert_eq!(
+        refactor(source, Refactoring::ClosureToArrowFunction),
+        "<?php
+$a = fn ($x) => $x + $y;
+$b = static fn (): int => $y;
+$c = fn () => fn () => $y;
+$d = function () use (&$y) { return $y; };
+$e = function () { return $y; };
+$f = function () use ($y) { $y++; return $y; };
+$g = function () use ($y) { return yield $y; };
+$h = function () use ($y) { return $y; /* comment */ };
+"
+    );
+}
+
+#[test]
+fn test_arrow_function_to_closure() {
+    let source = "<?php
+$a = fn ($x) => $x + $y;
+$b = static fn &(): array => $this->items;
+$c = fn () => fn ($x) => $x * $y;
+$d = fn (): never => throw new Exception($y);
+$e = fn () => $$name;
+";
+
+    let closures = refactor(source, Refactoring::ArrowFunctionToClosure);
+    assert_eq!(
+        closures,
+        "<?php
+$a = function ($x) use ($y) { return $x + $y; };
+$b = static function &(): array { return $this->items; };
+$c = function () use ($y) { return function ($x) use ($y) { return $x * $y; }; };
+$d = fn (): never => throw new Exception($y);
+$e = fn () => $$name;
+"
+    );
+
+    assert_eq!(
+        refactor(&closures, Refactoring::ClosureToArrowFunction),
+        source
+    );
+}
+
+#[test]
+fn test_refactor_functions_returning_strings() {
+    let source = "<?php
+$a = fn () => $y . \"a {$x} b\";
+$b = fn () => <<<EOT
+    a $y
+    EOT;
+$c = fn () => `ls $y`;
+$d = fn () => <<<'EOT'
+    a
+    EOT;
+";
+
+    let closures = refactor(source, Refactoring::ArrowFunctionToClosure);
+    assert_eq!(
+        closures,
+        "<?php
+$a = function () use ($y, $x) { return $y . \"a {$x} b\"; };
+$b = function () use ($y) { return <<<EOT
+    a $y
+    EOT; };
+$c = function () use ($y) { return `ls $y`; };
+$d = function () { return <<<'EOT'
+    a
+    EOT; };
+"
+    );
+
+    assert_eq!(
+        refactor(&closures, Refactoring::ClosureToArrowFunction),
+        source
+    );
+}
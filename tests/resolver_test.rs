@@ -0,0 +1,151 @@
+use php_parser_rs::parser;
+use php_parser_rs::resolver::resolve;
+use php_parser_rs::resolver::NameKind;
+
+// The resolved names of the code, as `(written, kind, resolved, fallback)` in source order.
+fn names(code: &str) -> Vec<(String, NameKind, String, Option<String>)> {
+    let program = parser::parse(code).unwrap();
+    let mut names: Vec<_> = resolve(&program)
+        .iter()
+        .map(|(span, name)| (*span, name.clone()))
+        .collect();
+    names.sort_by_key(|(span, _)| span.position);
+
+    names
+        .into_iter()
+        .map(|(span, name)| {
+            (
+                code[span.position..span.end_position].to_string(),
+                name.kind,
+                name.name.to_string(),
+                name.fallback.map(|fallback| fallback.to_string()),
+            )
+        })
+        .collect()
+}
+
+fn name(
+    written: &str,
+    kind: NameKind,
+    resolved: &str,
+) -> (String, NameKind, String, Option<String>) {
+    (written.to_string(), kind, resolved.to_string(), None)
+}
+
+#[test]
+fn test_resolve_imports_and_aliases() {
+    let code = r#"<?php
+        namespace App\Http;
+
+        use Psr\Log\LoggerInterface as Logger;
+        use Psr\Http\{Message, Client\ClientInterface};
+        use function Lib\helper;
+        use const Lib\VERSION;
+
+        #[Route('/')]
+        final class Controller extends Base implements Logger, Message\RequestInterface {
+            use Concerns\HandlesErrors;
+
+            public function __invoke(ClientInterface $client): ?\Response {
+                helper(VERSION, PHP_EOL, strlen(...));
+
+                try {
+                    return new Message\Response(Logger::class);
+                } catch (Exception | \Error) {
+                    return $client instanceof namespace\Fallback ? Status::Ok : self::fail();
+                }
+            }
+        }
+    "#;
+
+    assert_eq!(
+        names(code),
+        vec![
+            name("Route", NameKind::Class, "App\\Http\\Route"),
+            name("Controller", NameKind::Class, "App\\Http\\Controller"),
+            name("Base", NameKind::Class, "App\\Http\\Base"),
+            name("Logger", NameKind::Class, "Psr\\Log\\LoggerInterface"),
+            name(
+                "Message\\RequestInterface",
+                NameKind::Class,
+                "Psr\\Http\\Message\\RequestInterface"
+            ),
+            name(
+                "Concerns\\HandlesErrors",
+                NameKind::Class,
+                "App\\Http\\Concerns\\HandlesErrors"
+            ),
+            name(
+                "ClientInterface",
+                NameKind::Class,
+                "Psr\\Http\\Client\\ClientInterface"
+            ),
+            name("\\Response", NameKind::Class, "Response"),
+            name("helper", NameKind::Function, "Lib\\helper"),
+            name("VERSION", NameKind::Constant, "Lib\\VERSION"),
+            (
+                "PHP_EOL".to_string(),
+                NameKind::Constant,
+                "App\\Http\\PHP_EOL".to_string(),
+                Some("PHP_EOL".to_string())
+            ),
+            (
+                "strlen".to_string(),
+                NameKind::Function,
+                "App\\Http\\strlen".to_string(),
+                Some("strlen".to_string())
+            ),
+            name(
+                "Message\\Response",
+                NameKind::Class,
+                "Psr\\Http\\Message\\Response"
+            ),
+            name("Logger", NameKind::Class, "Psr\\Log\\LoggerInterface"),
+            name("Exception", NameKind::Class, "App\\Http\\Exception"),
+            name("\\Error", NameKind::Class, "Error"),
+            name(
+                "namespace\\Fallback",
+                NameKind::Class,
+                "App\\Http\\Fallback"
+            ),
+            name("Status", NameKind::Class, "App\\Http\\Status"),
+        ]
+    );
+}
+
+#[test]
+fn test_resolve_declarations_per_namespace() {
+    let code = r#"<?php
+        namespace A {
+            use Foo\Bar;
+
+            function f(): Bar {}
+            const X = 1;
+        }
+
+        namespace {
+            new Bar(X);
+        }
+    "#;
+
+    let program = parser::parse(code).unwrap();
+    let resolved = resolve(&program);
+    let declarations: Vec<String> = {
+        let mut declarations: Vec<_> = resolved
+            .iter()
+            .filter(|(_, name)| name.declaration)
+            .map(|(span, name)| (span.position, name.name.to_string()))
+            .collect();
+        declarations.sort();
+        declarations.into_iter().map(|(_, name)| name).collect()
+    };
+
+    assert_eq!(declarations, vec!["A\\f", "A\\X"]);
+    assert_eq!(
+        names(code)[3..],
+        [
+            name("Bar", NameKind::Class, "Bar"),
+            name("X", NameKind::Constant, "X"),
+        ]
+    );
+}
@@ -0,0 +1,84 @@
+use php_parser_rs::lexer::token::Span;
+use php_parser_rs::snippet::render_snippet;
+use php_parser_rs::snippet::ColumnUnit;
+use php_parser_rs::snippet::SnippetOptions;
+
+#[test]
+fn test_render_snippet() {
+    let source = "<?php\n\nfunction foo() {\n    return $bar;\n}\n";
+    let position = source.find("$bar").unwrap();
+
+    let snippet = render_snippet(
+        source.as_bytes(),
+        Span::new(4, 12, position),
+        4,
+        &SnippetOptions {
+            label: Some("undefined variable".to_owned()),
+            ..SnippetOptions::default()
+        },
+    );
+
+    assert_eq!(
+        snippet,
+        "3 | function foo() {\n4 |     return $bar;\n  |            ^^^^ undefined variable\n5 | }\n"
+    );
+}
+
+#[test]
+fn test_render_snippet_spanning_multiple_lines() {
+    let source = "<?php\n$a = [\n  1,\n];\n";
+    let position = source.find('[').unwrap();
+
+    let snippet = render_snippet(
+        source.as_bytes(),
+        Span::new(2, 6, position),
+        8,
+        &SnippetOptions {
+            context_lines: 0,
+            line_numbers: false,
+            ..SnippetOptions::default()
+        },
+    );
+
+    assert_eq!(snippet, "$a = [\n     ^\n  1,\n^^^^\n];\n^\n");
+}
+
+#[test]
+fn test_render_snippet_after_multibyte_characters() {
+    let source = "<?php\n$é = $x; // ünïcödé\n";
+    let options = SnippetOptions {
+        context_lines: 0,
+        line_numbers: false,
+        ..SnippetOptions::default()
+    };
+
+    let position = source.find("$x").unwrap();
+    let snippet = render_snippet(source.as_bytes(), Span::new(2, 7, position), 2, &options);
+    assert_eq!(snippet, "$é = $x; // ünïcödé\n     ^^\n");
+
+    let position = source.find("//").unwrap();
+    let snippet = render_snippet(
+        source.as_bytes(),
+        Span::new(2, 10, position),
+        "// ünïcödé".len(),
+        &options,
+    );
+    assert_eq!(snippet, "$é = $x; // ünïcödé\n         ^^^^^^^^^^\n");
+}
+
+#[test]
+fn test_column_units() {
+    let source = "<?php\n$s = 'é😀'; $x;\n";
+    let position = source.find("$x").unwrap();
+
+    assert_eq!(ColumnUnit::Bytes.column(source.as_bytes(), position), 16);
+    assert_eq!(
+        ColumnUnit::CodePoints.column(source.as_bytes(), position),
+        12
+    );
+    assert_eq!(
+        ColumnUnit::Utf16CodeUnits.column(source.as_bytes(), position),
+        13
+    );
+    assert_eq!(ColumnUnit::CodePoints.column(source.as_bytes(), 0), 1);
+}
@@ -0,0 +1,92 @@
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::snippet::ColumnUnit;
+use php_parser_rs::source_map::LineColumn;
+use php_parser_rs::source_map::LineIndex;
+use php_parser_rs::source_map::SourceMap;
+
+#[test]
+fn test_line_index() {
+    let index = LineIndex::new(b"a\r\nbc\n\nd");
+
+    assert_eq!(index.line_count(), 4);
+    assert_eq!(index.line(0), 1);
+    assert_eq!(index.line(2), 1);
+    assert_eq!(index.line(3), 2);
+    assert_eq!(index.line(6), 3);
+    assert_eq!(index.line(100), 4);
+    assert_eq!(index.line_range(1), Some(0..3));
+    assert_eq!(index.line_range(4), Some(7..8));
+    assert_eq!(index.line_range(0), None);
+    assert_eq!(index.line_range(5), None);
+    assert_eq!(index.line_column(4), LineColumn::new(2, 2));
+    assert_eq!(index.line_column(100), LineColumn::new(4, 2));
+}
+
+#[test]
+fn test_source_map_columns() {
+    // `é` is 2 bytes and 1 UTF-16 code unit, `😀` is 4 bytes and 2 UTF-16 code units.
+    let source = "<?php\n$a = 'é😀' . $b;\n";
+    let map = SourceMap::new(source.as_bytes());
+    let b = source.find("$b").unwrap();
+
+    assert_eq!(
+        map.line_column(b, ColumnUnit::Bytes),
+        LineColumn::new(2, 17)
+    );
+    assert_eq!(
+        map.line_column(b, ColumnUnit::CodePoints),
+        LineColumn::new(2, 13)
+    );
+    assert_eq!(
+        map.line_column(b, ColumnUnit::Utf16CodeUnits),
+        LineColumn::new(2, 14)
+    );
+
+    for unit in [
+        ColumnUnit::Bytes,
+        ColumnUnit::CodePoints,
+        ColumnUnit::Utf16CodeUnits,
+    ] {
+        for (offset, _) in source.char_indices() {
+            assert_eq!(
+                map.offset(map.line_column(offset, unit), unit),
+                Some(offset),
+                "{:?} {}",
+                unit,
+                offset
+            );
+        }
+    }
+
+    // inside of `😀`, and past the end of the line.
+    assert_eq!(
+        map.offset(LineColumn::new(2, 9), ColumnUnit::Utf16CodeUnits),
+        Some(14)
+    );
+    assert_eq!(
+        map.offset(LineColumn::new(2, 100), ColumnUnit::Bytes),
+        Some(source.len() - 1)
+    );
+    assert_eq!(map.offset(LineColumn::new(4, 1), ColumnUnit::Bytes), None);
+}
+
+#[test]
+fn test_source_map_matches_spans() {
+    let source = "<?php\n\n  function foo(): void {\r\n    echo \"ü\";\n}\n";
+    let map = SourceMap::new(source.as_bytes());
+
+    for token in Lexer::new().tokenize(source).unwrap() {
+        assert_eq!(
+            map.line_column(token.span.position, ColumnUnit::Bytes),
+            LineColumn::new(token.span.line, token.span.column)
+        );
+        assert_eq!(
+            map.slice(&token.span),
+            &source.as_bytes()[token.span.position..token.span.end_position]
+        );
+    }
+
+    assert_eq!(map.line_text(3), Some(&b"  function foo(): void {"[..]));
+    assert_eq!(map.line_text(6), Some(&b""[..]));
+    assert_eq!(map.line_text(7), None);
+}
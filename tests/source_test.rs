@@ -0,0 +1,59 @@
+use php_parser_rs::lexer::source::Source;
+
+#[test]
+fn test_source_reads_are_bounded() {
+    let mut source = Source::new(b"ab");
+
+    assert_eq!(source.read(10), b"ab");
+    assert_eq!(source.peek(1, 10), b"b");
+    assert_eq!(source.peek(5, 1), b"");
+    assert_eq!(source.peek_byte(2), None);
+
+    assert_eq!(source.read_and_skip(10), b"ab");
+    assert!(source.eof());
+    assert_eq!(source.position(), 2);
+
+    source.skip(3);
+    assert_eq!(source.position(), 2);
+    assert_eq!(source.current(), None);
+    assert_eq!(source.read_remaining(), b"");
+}
+
+#[test]
+fn test_source_tracks_lines_and_columns() {
+    let mut source = Source::new(b"key = value\nother");
+
+    let start = source.span();
+    assert_eq!(source.read_while(|b| b.is_ascii_alphabetic()), b"key");
+    let key = source.span_from(start);
+    assert_eq!(source.slice(key), b"key");
+
+    source.skip_while(|b| b == b' ');
+    assert!(!source.consume(b"=="));
+    assert!(source.consume(b"="));
+
+    source.skip_while(|b| b != b'\n');
+    source.next();
+    let span = source.span();
+    assert_eq!((span.line, span.column, span.position), (2, 1, 12));
+
+    source.reset(key);
+    assert!(source.starts_with(b"key"));
+    assert_eq!(source.span().column, 1);
+}
+
+#[test]
+fn test_source_bytes() {
+    let mut source = Source::new(b"a\nb");
+
+    let bytes: Vec<_> = source
+        .bytes()
+        .map(|(span, byte)| (span.line, span.column, span.length(), byte))
+        .collect();
+
+    assert_eq!(
+        bytes,
+        vec![(1, 1, 1, b'a'), (1, 2, 1, b'\n'), (2, 1, 1, b'b')]
+    );
+    assert!(source.eof());
+}
@@ -0,0 +1,240 @@
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::node::Node;
+use php_parser_rs::parser;
+use php_parser_rs::parser::ast::Expression;
+use php_parser_rs::parser::ast::ExpressionStatement;
+use php_parser_rs::parser::ast::Statement;
+
+#[test]
+fn test_token_spans() {
+    let source = "<?php\n$a = \"x\\ny\"; // comment\necho <<<EOF\n  {$a}\n  EOF;\n";
+    let tokens = Lexer::new().tokenize(source).unwrap();
+
+    let texts: Vec<&str> = tokens
+        .iter()
+        .map(|token| &source[token.span.position..token.span.end_position])
+        .collect();
+
+    assert_eq!(
+        texts,
+        vec![
+            "<?php",
+            "$a",
+            "=",
+            "\"x\\ny\"",
+            ";",
+            "// comment",
+            "echo",
+            "<<<EOF",
+            "  ",
+            "{",
+            "$a",
+            "}",
+            "\n  EOF",
+            ";",
+            ""
+        ]
+    );
+}
+
+#[test]
+fn test_node_span() {
+    let source = "<?php\n\nclass Foo {\n    public function bar(): int { return 1; }\n}\n";
+    let program = parser::parse(source).unwrap();
+
+    let Statement::Class(class) = &program[1] else {
+        panic!("expected a class");
+    };
+
+    let span = class.span().unwrap();
+    assert_eq!((span.line, span.column), (3, 1));
+    assert_eq!(
+        &source[span.position..span.end_position],
+        &source[7..source.len() - 1]
+    );
+
+    let method = &class.body.members[0];
+    let span = method.span().unwrap();
+    assert_eq!(
+        &source[span.position..span.end_position],
+        "public function bar(): int { return 1; }"
+    );
+}
+
+#[test]
+fn test_node_span_covers_its_own_tokens() {
+    let source = "<?php\nfoo(...);\n$a = match ($b) { default => 1, };\n";
+    let program = parser::parse(source).unwrap();
+
+    let text = |node: &dyn Node| {
+        let span = node.span().unwrap();
+
+        &source[span.position..span.end_position]
+    };
+
+    assert_eq!(text(&program[1]), "foo(...);");
+    assert_eq!(text(&program[2]), "$a = match ($b) { default => 1, };");
+
+    let Statement::Expression(statement) = &program[2] else {
+        panic!("expected an expression statement");
+    };
+
+    assert_eq!(
+        text(&statement.expression),
+        "$a = match ($b) { default => 1, }"
+    );
+}
+
+#[test]
+fn test_string_spans() {
+    for code in [
+        "\"hi {$a} there\"",
+        "\"$a\"",
+        "b\"${a}\"",
+        "B\"x{$a[0]}\"",
+        "`ls $x`",
+        "<<<EOT\n  a $b\n  EOT",
+        "<<<\"EOT\"\n$b\nEOT",
+        "<<<'EOT'\n  a\n  EOT",
+        "\"x\"",
+        "'x'",
+    ] {
+        let source = format!("<?php\n$s = {};\n", code);
+        let program = parser::parse(&source).unwrap();
+
+        let Statement::Expression(statement) = &program[1] else {
+            panic!("expected an expression statement");
+        };
+
+        let span = statement.expression.span().unwrap();
+        assert_eq!(
+            &source[span.position..span.end_position],
+            format!("$s = {}", code)
+        );
+
+        let Expression::AssignmentOperation(assignment) = &statement.expression else {
+            panic!("expected an assignment");
+        };
+
+        let span = assignment.right().span().unwrap();
+        assert_eq!(&source[span.position..span.end_position], code);
+    }
+}
+
+#[test]
+fn test_string_part_spans() {
+    let source = "<?php\n\"hi {$a} there\";\n";
+    let program = parser::parse(source).unwrap();
+
+    let Statement::Expression(ExpressionStatement {
+        expression: Expression::InterpolatedString(string),
+        ..
+    }) = &program[1]
+    else {
+        panic!("expected an interpolated string");
+    };
+
+    let texts: Vec<&str> = string
+        .parts
+        .iter()
+        .map(|part| {
+            let span = part.span().unwrap();
+
+            &source[span.position..span.end_position]
+        })
+        .collect();
+
+    assert_eq!(texts, vec!["hi ", "$a", " there"]);
+}
+
+#[test]
+fn test_statement_spans_cover_their_keyword_and_semicolon() {
+    let source = "<?php\nuse A\\B, C;\nuse function A\\{b, c};\nstatic $a = 1, $b;\nglobal $c, $d;\n?>\n<p>html</p>\n<?php\n";
+    let program = parser::parse(source).unwrap();
+
+    let texts: Vec<&str> = program
+        .iter()
+        .map(|statement| {
+            let span = statement.span().unwrap();
+
+            &source[span.position..span.end_position]
+        })
+        .collect();
+
+    assert_eq!(
+        texts,
+        vec![
+            "<?php",
+            "use A\\B, C;",
+            "use function A\\{b, c};",
+            "static $a = 1, $b;",
+            "global $c, $d;",
+            "?>",
+            "\n<p>html</p>\n",
+            "<?php",
+        ]
+    );
+}
+
+#[test]
+fn test_keyword_expression_spans() {
+    for code in [
+        "null",
+        "true",
+        "false",
+        "clone $a",
+        "throw $e",
+        "yield",
+        "yield $a => $b",
+        "yield from $a",
+        "null !== $a",
+        "$a instanceof self",
+        "new static()",
+        "parent::foo()",
+    ] {
+        let source = format!("<?php\n{};\n", code);
+        let program = parser::parse(&source).unwrap();
+
+        let Statement::Expression(statement) = &program[1] else {
+            panic!("expected an expression statement");
+        };
+
+        let span = statement.expression.span().unwrap();
+        assert_eq!(&source[span.position..span.end_position], code);
+    }
+}
+
+#[test]
+fn test_switch_case_spans() {
+    let source = "<?php\nswitch ($a) {\n    case 1: foo();\n    default:\n}\n";
+    let program = parser::parse(source).unwrap();
+
+    let Statement::Switch(statement) = &program[1] else {
+        panic!("expected a switch statement");
+    };
+
+    let texts: Vec<&str> = statement
+        .body
+        .children_ref()
+        .iter()
+        .map(|case| {
+            let span = case.span().unwrap();
+
+            &source[span.position..span.end_position]
+        })
+        .collect();
+
+    assert_eq!(texts, vec!["case 1: foo();", "default:"]);
+}
+
+#[test]
+fn test_halt_compiler_span() {
+    let source = "<?php\n__halt_compiler ( ) ;payload";
+    let program = parser::parse(source).unwrap();
+
+    let span = program[1].span().unwrap();
+    assert_eq!(
+        &source[span.position..span.end_position],
+        "__halt_compiler ( ) ;"
+    );
+}
@@ -0,0 +1,58 @@
+use php_parser_rs::analysis::summary::summarize;
+use php_parser_rs::parser;
+
+const SOURCE: &str = "<?php
+namespace App;
+
+final class Foo extends Bar implements Baz {
+    public const VERSION = 1;
+
+    private ?string $name = null;
+
+    /**
+     * Run the thing.
+     *
+     * @param int $times
+     */
+    public function run(int $times, string ...$args): void {
+        echo 1;
+        echo 2;
+    }
+}
+
+function helper(&$value = null) {}
+";
+
+#[test]
+fn test_summarize() {
+    let program = parser::parse(SOURCE).unwrap();
+
+    assert_eq!(
+        summarize(&program, 1024),
+        "namespace App
+  final class Foo extends Bar implements Baz
+    public const VERSION
+    private ?string $name
+    // Run the thing.
+    public function run(int $times, string ...$args): void
+      // 2 statements
+  function helper(&$value = ...)
+    // 0 statements
+"
+    );
+}
+
+#[test]
+fn test_summarize_within_budget() {
+    let program = parser::parse(SOURCE).unwrap();
+
+    assert_eq!(
+        summarize(&program, 120),
+        "namespace App
+  final class Foo extends Bar implements Baz
+  function helper(&$value = ...)
+"
+    );
+
+    assert_eq!(summarize(&program, 40), "namespace App\n... (2 more)\n");
+}
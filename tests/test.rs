@@ -3,6 +3,7 @@ use std::fs::read_dir;
 use std::io;
 use std::path::PathBuf;
 
+use php_parser_rs::node::Node;
 use pretty_assertions::assert_str_eq;
 
 struct TestFixture {
@@ -116,3 +117,89 @@ fn run_test(test_fixture: &TestFixture) -> io::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_deeply_nested_code() {
+    let depth = 200;
+
+    let parentheses = format!("<?php $a = {}1{};", "(".repeat(depth), ")".repeat(depth));
+    assert!(php_parser_rs::parse(&parentheses).is_ok());
+
+    let arrays = format!(
+        "<?php $a = {}1{};",
+        "['a' => ".repeat(depth),
+        "]".repeat(depth)
+    );
+    assert!(php_parser_rs::parse(&arrays).is_ok());
+
+    let coalesce = format!("<?php $a = {}null;", "$b ?? ".repeat(depth));
+    assert!(php_parser_rs::parse(&coalesce).is_ok());
+
+    let blocks = format!("<?php {}{}", "if ($a) { ".repeat(depth), "}".repeat(depth));
+    assert!(php_parser_rs::parse(&blocks).is_ok());
+}
+
+#[test]
+fn test_nesting_up_to_the_default_limit() {
+    let depth = 1000;
+
+    for code in [
+        format!(
+            "<?php $a = {}1{};",
+            "['a' => ".repeat(2 * depth),
+            "]".repeat(2 * depth)
+        ),
+        format!("<?php {}{}", "if ($a) { ".repeat(depth), "}".repeat(depth)),
+    ] {
+        let program = php_parser_rs::parse(&code).unwrap();
+
+        assert_eq!(program.clone(), program);
+        assert!(!format!("{:?}", program).is_empty());
+        assert!(!serde_json::to_string(&program).unwrap().is_empty());
+        assert_eq!(
+            php_parser_rs::parse_lossless(&code).unwrap().print(),
+            code.as_bytes()
+        );
+    }
+}
+
+#[test]
+fn test_nesting_beyond_the_default_limit() {
+    let depth = 100_000;
+
+    let parentheses = format!("<?php $a = {}1{};", "(".repeat(depth), ")".repeat(depth));
+    let error = php_parser_rs::parse(&parentheses).unwrap_err();
+    assert_eq!(error.errors[0].id, "E052");
+}
+
+#[test]
+fn test_long_chains() {
+    let length = 100_000;
+
+    for code in [
+        format!("<?php $a = {}1;", "1 . ".repeat(length)),
+        format!("<?php $a = $b{};", "->c()".repeat(length)),
+        format!("<?php $a = $b{};", "[0]".repeat(length)),
+    ] {
+        let program = php_parser_rs::parse(&code).unwrap();
+
+        assert!(!format!("{:?}", program).is_empty());
+        assert!(!serde_json::to_string(&program).unwrap().is_empty());
+        assert_eq!(program[1].span().unwrap().end_position, code.len());
+        assert_eq!(program.clone(), program);
+    }
+}
+
+#[test]
+fn test_long_chains_round_trip() {
+    let length = 10_000;
+
+    for code in [
+        format!("<?php $a = $a{};", " . $a".repeat(length)),
+        format!("<?php $a = $b{};", "->c()".repeat(length)),
+    ] {
+        let lossless = php_parser_rs::parse_lossless(&code).unwrap();
+
+        assert_eq!(lossless.print(), code.as_bytes());
+    }
+}
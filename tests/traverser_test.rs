@@ -0,0 +1,252 @@
+use std::fs;
+use std::path::PathBuf;
+
+use php_parser_rs::node::Node;
+use php_parser_rs::parser;
+use php_parser_rs::parser::ast::control_flow::IfStatementBody;
+use php_parser_rs::parser::ast::functions::ClosureExpression;
+use php_parser_rs::parser::ast::loops::WhileStatementBody;
+use php_parser_rs::parser::ast::variables::SimpleVariable;
+use php_parser_rs::parser::ast::Expression;
+use php_parser_rs::parser::ast::FunctionCallExpression;
+use php_parser_rs::parser::ast::ParenthesizedExpression;
+use php_parser_rs::parser::ast::Statement;
+use php_parser_rs::traverser::transform;
+use php_parser_rs::traverser::walk;
+use php_parser_rs::traverser::walk_mut;
+use php_parser_rs::traverser::Transformer;
+use php_parser_rs::traverser::Visitor;
+use php_parser_rs::traverser::VisitorMut;
+
+#[derive(Default)]
+struct Kinds(Vec<&'static str>);
+
+impl Visitor for Kinds {
+    fn enter_node(&mut self, node: &dyn Node) {
+        self.0.push(node.kind());
+    }
+}
+
+impl VisitorMut for Kinds {
+    fn enter_node(&mut self, node: &mut dyn Node) {
+        self.0.push(node.kind());
+    }
+}
+
+#[test]
+fn test_walks_visit_the_same_nodes() {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    for entry in fs::read_dir(fixtures).unwrap().flatten() {
+        let Ok(code) = fs::read(entry.path().join("code.php")) else {
+            continue;
+        };
+        let Ok(mut program) = parser::parse(&code) else {
+            continue;
+        };
+
+        let mut immutable = Kinds::default();
+        let mut mutable = Kinds::default();
+        for statement in program.iter_mut() {
+            walk(&mut immutable, statement);
+            walk_mut(&mut mutable, statement);
+        }
+
+        assert_eq!(immutable.0, mutable.0, "{}", entry.path().display());
+    }
+}
+
+#[test]
+fn test_kind_of_generic_node() {
+    let program = parser::parse("<?php [1, 2];").unwrap();
+
+    let mut kinds = Kinds::default();
+    walk(&mut kinds, program.last().unwrap());
+
+    assert_eq!(
+        kinds.0,
+        vec![
+            "Statement",
+            "ExpressionStatement",
+            "Expression",
+            "ShortArrayExpression",
+            "CommaSeparated",
+            "ArrayItem",
+            "Expression",
+            "Literal",
+            "LiteralInteger",
+            "ArrayItem",
+            "Expression",
+            "Literal",
+            "LiteralInteger",
+        ]
+    );
+}
+
+#[test]
+fn test_typed_hooks() {
+    #[derive(Default)]
+    struct Events(Vec<String>);
+
+    impl Visitor for Events {
+        fn enter_closure_expression(&mut self, _: &ClosureExpression) {
+            self.0.push("enter closure".to_owned());
+        }
+
+        fn leave_closure_expression(&mut self, _: &ClosureExpression) {
+            self.0.push("leave closure".to_owned());
+        }
+
+        fn enter_function_call_expression(&mut self, _: &FunctionCallExpression) {
+            self.0.push("call".to_owned());
+        }
+
+        fn enter_simple_variable(&mut self, variable: &SimpleVariable) {
+            self.0.push(variable.name.to_string());
+        }
+    }
+
+    let program = parser::parse("<?php foo(function ($a) { return bar($a); }); $b;").unwrap();
+
+    let mut events = Events::default();
+    for statement in &program {
+        walk(&mut events, statement);
+    }
+
+    assert_eq!(
+        events.0,
+        vec![
+            "call",
+            "enter closure",
+            "$a",
+            "call",
+            "$a",
+            "leave closure",
+            "$b"
+        ]
+    );
+}
+
+#[test]
+fn test_visitor_mut() {
+    struct Rename;
+
+    impl VisitorMut for Rename {
+        fn enter_simple_variable(&mut self, variable: &mut SimpleVariable) {
+            if variable.name == b"$a" {
+                variable.name = b"$renamed".into();
+            }
+        }
+    }
+
+    let mut program = parser::parse("<?php $a = fn () => $a + $b;").unwrap();
+    for statement in program.iter_mut() {
+        walk_mut(&mut Rename, statement);
+    }
+
+    let mut variables = vec![];
+    struct Collect<'a>(&'a mut Vec<String>);
+    impl Visitor for Collect<'_> {
+        fn enter_simple_variable(&mut self, variable: &SimpleVariable) {
+            self.0.push(variable.name.to_string());
+        }
+    }
+
+    for statement in &program {
+        walk(&mut Collect(&mut variables), statement);
+    }
+
+    assert_eq!(variables, vec!["$renamed", "$renamed", "$b"]);
+}
+
+#[test]
+fn test_transform_without_changes() {
+    struct Identity;
+
+    impl Transformer for Identity {}
+
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    for entry in fs::read_dir(fixtures).unwrap().flatten() {
+        let Ok(code) = fs::read(entry.path().join("code.php")) else {
+            continue;
+        };
+        let Ok(program) = parser::parse(&code) else {
+            continue;
+        };
+
+        assert_eq!(
+            transform(&mut Identity, program.clone()),
+            program,
+            "{}",
+            entry.path().display()
+        );
+    }
+}
+
+#[test]
+fn test_transform_expressions() {
+    struct Unparenthesize;
+
+    impl Transformer for Unparenthesize {
+        fn transform_expression(&mut self, expression: Expression) -> Expression {
+            match expression {
+                Expression::Parenthesized(ParenthesizedExpression { expr, .. }) => *expr,
+                expression => expression,
+            }
+        }
+    }
+
+    let program = parser::parse("<?php $a = ((1)) + foo(($b));").unwrap();
+    let program = transform(&mut Unparenthesize, program);
+
+    let mut kinds = Kinds::default();
+    for statement in &program {
+        walk(&mut kinds, statement);
+    }
+
+    assert!(!kinds.0.contains(&"ParenthesizedExpression"));
+    assert!(kinds.0.contains(&"LiteralInteger"));
+}
+
+#[test]
+fn test_transform_statements() {
+    // removes `echo` statements, and repeats function calls.
+    struct Rewrite;
+
+    impl Transformer for Rewrite {
+        fn transform_statement(&mut self, statement: Statement) -> Vec<Statement> {
+            match statement {
+                Statement::Echo(_) => vec![],
+                Statement::Expression(ref expression)
+                    if matches!(expression.expression, Expression::FunctionCall(_)) =>
+                {
+                    vec![statement.clone(), statement]
+                }
+                statement => vec![statement],
+            }
+        }
+    }
+
+    let program = parser::parse(
+        "<?php echo 1; function foo() { echo 2; bar(); } if ($a) echo 3; while ($b) baz();",
+    )
+    .unwrap();
+    let program = transform(&mut Rewrite, program);
+
+    let [Statement::FullOpeningTag(_), Statement::Function(function), Statement::If(r#if), Statement::While(r#while)] =
+        &program[..]
+    else {
+        panic!("unexpected statements: {:?}", program);
+    };
+
+    assert_eq!(function.body.statements.len(), 2);
+    assert!(matches!(
+        &r#if.body,
+        IfStatementBody::Statement { statement, .. } if matches!(**statement, Statement::Noop(_))
+    ));
+    assert!(matches!(
+        &r#while.body,
+        WhileStatementBody::Statement { statement } if matches!(&**statement, Statement::Block(block) if block.statements.len() == 2)
+    ));
+}